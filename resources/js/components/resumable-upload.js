@@ -0,0 +1,143 @@
+'use strict';
+;(function () {
+    'use strict';
+
+    // 8 MB per request, a dropped connection loses at most one chunk.
+    let CHUNK_SIZE = 8 * 1024 * 1024;
+    let MAX_RETRIES = 10;
+
+    let forms = document.querySelectorAll('.js-resumable-upload');
+
+    if (!window.fetch || !window.Blob || !Blob.prototype.slice) return;
+
+    for (let i = 0; i < forms.length; i++) {
+        forms[i].addEventListener('submit', handleSubmit);
+    }
+
+    function handleSubmit(e) {
+        let form = e.target;
+        let input = form.querySelector('input[type="file"]');
+        if (!input || !input.files || input.files.length === 0) return;
+
+        e.preventDefault();
+
+        let action = e.submitter && e.submitter.value ? e.submitter.value : 'save';
+        let buttons = form.querySelectorAll('button');
+        for (let i = 0; i < buttons.length; i++) {
+            buttons[i].disabled = true;
+        }
+
+        upload(form, input.files[0])
+            .then(function () {
+                window.location.href = action === 'save_and_close' ? form.dataset.closeUrl : form.dataset.uploadUrl;
+            })
+            .catch(function (error) {
+                setProgress(form, error.message || String(error));
+                for (let i = 0; i < buttons.length; i++) {
+                    buttons[i].disabled = false;
+                }
+            });
+    }
+
+    function upload(form, file) {
+        let storageKey = 'resumable-upload:' + [file.name, file.size, file.lastModified].join(':');
+
+        return findOrCreate(form, file, storageKey)
+            .then(function (url) {
+                return sendChunks(form, file, url, 0)
+                    .then(function () {
+                        return request(form, 'POST', url + '/finalize', {});
+                    })
+                    .then(function (response) {
+                        if (!response.ok) throw new Error(response.statusText);
+                        localStorage.removeItem(storageKey);
+                    });
+            });
+    }
+
+    // The upload started before the page reload is continued.
+    function findOrCreate(form, file, storageKey) {
+        let url = localStorage.getItem(storageKey);
+        let create = function () {
+            let isPublic = form.querySelector('input[name="is_public"]');
+            let metadata = [
+                'filename ' + toBase64(file.name),
+                'filetype ' + toBase64(file.type || 'application/octet-stream'),
+                'is_public ' + toBase64(isPublic && isPublic.checked ? '1' : '0'),
+            ];
+            return request(form, 'POST', form.dataset.resumableUrl, {
+                'Upload-Length': String(file.size),
+                'Upload-Metadata': metadata.join(','),
+            }).then(function (response) {
+                if (!response.ok) throw new Error(response.statusText);
+                let location = response.headers.get('Location');
+                localStorage.setItem(storageKey, location);
+                return location;
+            });
+        };
+
+        if (!url) return create();
+
+        return request(form, 'HEAD', url, {}).then(function (response) {
+            return response.ok ? url : create();
+        });
+    }
+
+    function sendChunks(form, file, url, retries) {
+        return sendChunk(form, file, url).then(function (isDone) {
+            if (!isDone) return sendChunks(form, file, url, 0);
+        }, function (error) {
+            if (retries >= MAX_RETRIES) throw error;
+            return wait(Math.min(1000 * Math.pow(2, retries), 30000)).then(function () {
+                return sendChunks(form, file, url, retries + 1);
+            });
+        });
+    }
+
+    // The offset is asked before every chunk, so the chunk received in part is continued from the right place.
+    function sendChunk(form, file, url) {
+        return request(form, 'HEAD', url, {})
+            .then(function (response) {
+                if (!response.ok) throw new Error(response.statusText);
+                return parseInt(response.headers.get('Upload-Offset'), 10) || 0;
+            })
+            .then(function (offset) {
+                setProgress(form, Math.floor(offset / Math.max(file.size, 1) * 100) + '%');
+                if (offset >= file.size) return true;
+
+                return request(form, 'PATCH', url, {
+                    'Content-Type': 'application/offset+octet-stream',
+                    'Upload-Offset': String(offset),
+                }, file.slice(offset, offset + CHUNK_SIZE)).then(function (response) {
+                    if (!response.ok) throw new Error(response.statusText);
+                    return false;
+                });
+            });
+    }
+
+    function request(form, method, url, headers, body) {
+        headers['X-CSRF-Token'] = form.dataset.csrf;
+        headers['Tus-Resumable'] = '1.0.0';
+        return fetch(url, {
+            method: method,
+            headers: headers,
+            body: body,
+            credentials: 'same-origin',
+        });
+    }
+
+    function setProgress(form, text) {
+        let progress = form.querySelector('.js-resumable-upload-progress');
+        if (progress) progress.textContent = text;
+    }
+
+    function toBase64(value) {
+        return btoa(unescape(encodeURIComponent(value)));
+    }
+
+    function wait(ms) {
+        return new Promise(function (resolve) {
+            setTimeout(resolve, ms);
+        });
+    }
+})();
@@ -0,0 +1,211 @@
+use crate::app::controllers::api::v1::{rate_limit_throw_http, ValidationErrors};
+use crate::app::middlewares::api_auth::get_restriction_key;
+use crate::app::validator::rules::email::Email;
+use crate::app::validator::rules::required::Required;
+use crate::app::validator::rules::str_min_max_chars_count::StrMinMaxCharsCount;
+use crate::{
+    prepare_value, AuthService, Config, EmailVerificationService, LocaleService, LoginSecurityService, RateLimitService, RoleService, Session,
+    TranslatableError, TranslatorService, UserService, UserTwoFactorService, UserTwoFactorServiceError, WebAuthService,
+};
+use actix_web::web::{Data, Json, ReqData};
+use actix_web::{error, Error, HttpRequest, HttpResponse, Result};
+use serde_derive::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+
+const RL_MAX_ATTEMPTS: u64 = 5;
+const RL_TTL: u64 = 60;
+const RL_KEY: &str = "api_token";
+// The same key as on the login page, so the attempts to guess the code are counted together.
+const RL_TWO_FACTOR_KEY: &str = "login_two_factor";
+
+#[derive(Deserialize, Default, Debug)]
+pub struct TokenData {
+    pub email: Option<String>,
+    pub password: Option<String>,
+    // The current code or a recovery code, required when the second factor is enabled.
+    pub code: Option<String>,
+}
+
+pub async fn token(
+    req: HttpRequest,
+    data: Json<TokenData>,
+    config: Data<Config>,
+    auth_service: Data<AuthService>,
+    web_auth_service: Data<WebAuthService>,
+    rate_limit_service: Data<RateLimitService>,
+    translator_service: Data<TranslatorService>,
+    locale_service: Data<LocaleService>,
+    login_security_service: Data<LoginSecurityService>,
+    user_service: Data<UserService>,
+    role_service: Data<RoleService>,
+    user_two_factor_service: Data<UserTwoFactorService>,
+    email_verification_service: Data<EmailVerificationService>,
+) -> Result<HttpResponse, Error> {
+    let config = config.get_ref();
+    let auth_service = auth_service.get_ref();
+    let web_auth_service = web_auth_service.get_ref();
+    let rate_limit_service = rate_limit_service.get_ref();
+    let translator_service = translator_service.get_ref();
+    let locale_service = locale_service.get_ref();
+    let login_security_service = login_security_service.get_ref();
+    let user_service = user_service.get_ref();
+    let role_service = role_service.get_ref();
+    let user_two_factor_service = user_two_factor_service.get_ref();
+    let email_verification_service = email_verification_service.get_ref();
+
+    let mut data = data.into_inner();
+    data.prepare();
+
+    let lang: String = locale_service.get_locale_code(Some(&req), None);
+    let lang = &lang;
+
+    rate_limit_throw_http(
+        &req,
+        rate_limit_service,
+        translator_service,
+        lang,
+        RL_KEY,
+        RL_MAX_ATTEMPTS,
+        RL_TTL,
+    )?;
+
+    let email_str = translator_service.translate(lang, "page.login.fields.email");
+    let password_str = translator_service.translate(lang, "page.login.fields.password");
+
+    let mut errors = ValidationErrors::default();
+    errors.push(
+        "email",
+        Required::validated(
+            translator_service,
+            lang,
+            &data.email,
+            |value| Email::validate(translator_service, lang, value, &email_str),
+            &email_str,
+        ),
+    );
+    errors.push(
+        "password",
+        Required::validated(
+            translator_service,
+            lang,
+            &data.password,
+            |value| {
+                StrMinMaxCharsCount::validate(translator_service, lang, value, 4, 255, &password_str)
+            },
+            &password_str,
+        ),
+    );
+
+    if !errors.is_empty() {
+        return Ok(errors.to_response());
+    }
+
+    let email = data.email.as_ref().unwrap();
+    let password = data.password.as_ref().unwrap();
+    let check_message =
+        login_security_service.check_message_throw_http(translator_service, lang, email)?;
+    if let Some(check_message) = check_message {
+        return Err(error::ErrorTooManyRequests(check_message));
+    }
+
+    let user_id = match auth_service.login_by_password(email, password) {
+        Ok(user_id) => user_id,
+        Err(_) => {
+            login_security_service.register_failure_throw_http(email)?;
+            return Err(error::ErrorUnauthorized(""));
+        }
+    };
+
+    // There is no intermediate step as on the login page, so the code is sent with the password.
+    if user_two_factor_service.is_enabled_throw_http(user_id)? {
+        let rate_limit_key = format!("{}.{}", RL_TWO_FACTOR_KEY, user_id);
+        let executed = rate_limit_service.attempt_throw_http(&rate_limit_key, RL_MAX_ATTEMPTS, RL_TTL)?;
+        if !executed {
+            let message =
+                rate_limit_service.ttl_message_throw_http(translator_service, lang, &rate_limit_key)?;
+            return Err(error::ErrorTooManyRequests(message));
+        }
+
+        let code_str = translator_service.translate(lang, "page.login_two_factor.fields.code");
+        let mut errors = ValidationErrors::default();
+        errors.push(
+            "code",
+            Required::validated(
+                translator_service,
+                lang,
+                &data.code,
+                |value| StrMinMaxCharsCount::validate(translator_service, lang, value, 6, 32, &code_str),
+                &code_str,
+            ),
+        );
+        if errors.is_empty() {
+            match user_two_factor_service.verify(user_id, data.code.as_ref().unwrap()) {
+                Ok(_) => {}
+                Err(UserTwoFactorServiceError::InvalidCode) => {
+                    login_security_service.register_failure_throw_http(email)?;
+                    errors.push(
+                        "code",
+                        vec![UserTwoFactorServiceError::InvalidCode.translate(lang, translator_service)],
+                    );
+                }
+                Err(_) => return Err(error::ErrorInternalServerError("")),
+            }
+        }
+        if !errors.is_empty() {
+            return Ok(errors.to_response());
+        }
+        rate_limit_service.clear_throw_http(&rate_limit_key)?;
+    }
+    // The failures start over only when all the factors are passed.
+    login_security_service.register_success_throw_http(email)?;
+
+    let user = user_service.first_by_id_throw_http(user_id)?;
+    if let Some(key) = get_restriction_key(
+        &user,
+        role_service,
+        user_two_factor_service,
+        email_verification_service,
+        user_service,
+    )? {
+        return Err(error::ErrorForbidden(translator_service.translate(lang, key)));
+    }
+
+    let session = web_auth_service.generate_session(user_id);
+    web_auth_service
+        .save_session(&session)
+        .map_err(|_| error::ErrorInternalServerError(""))?;
+    web_auth_service.save_session_info_throw_http(&req, &session)?;
+    login_security_service.register_login_silently(&req, user_id);
+    let access_token = web_auth_service
+        .encrypt_session(&session)
+        .map_err(|_| error::ErrorInternalServerError(""))?;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "token_type": "Bearer",
+        "access_token": access_token,
+        "expires_in": config.auth.cookie.token_expires,
+    })))
+}
+
+/// Personal access tokens have no session, they are revoked on the profile page.
+pub async fn logout(
+    session: Option<ReqData<Arc<Session>>>,
+    web_auth_service: Data<WebAuthService>,
+) -> Result<HttpResponse, Error> {
+    if let Some(session) = session {
+        web_auth_service
+            .get_ref()
+            .delete_session(session.as_ref())
+            .map_err(|_| error::ErrorInternalServerError(""))?;
+    }
+    Ok(HttpResponse::NoContent().finish())
+}
+
+impl TokenData {
+    pub fn prepare(&mut self) {
+        prepare_value!(self.email);
+        prepare_value!(self.password);
+        prepare_value!(self.code);
+    }
+}
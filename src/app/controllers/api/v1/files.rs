@@ -0,0 +1,231 @@
+use crate::app::controllers::api::v1::{policy_subject_throw_http, rate_limit_throw_http, ValidationErrors};
+use crate::app::controllers::web::files::index::IndexQuery;
+use crate::app::controllers::web::files::upload::{UploadData, UploadFile};
+use crate::{
+    AuditLogAction, AuditLogEntity, AuditLogService, FileFilter, FilePaginateParams, FilePolicy, FileService, FileServiceError, FileSort,
+    LocaleService, RateLimitService, RoleService, TranslatableError, TranslatorService, User,
+};
+use actix_multipart::Multipart;
+use actix_web::web::{Data, Path, Query, ReqData};
+use actix_web::{error, Error, HttpRequest, HttpResponse, Result};
+use std::sync::Arc;
+
+const RL_MAX_ATTEMPTS: u64 = 60;
+const RL_TTL: u64 = 60;
+const RL_KEY: &str = "api_files_upload_delete";
+
+pub async fn index(
+    req: HttpRequest,
+    user: ReqData<Arc<User>>,
+    mut query: Query<IndexQuery>,
+    translator_service: Data<TranslatorService>,
+    locale_service: Data<LocaleService>,
+    role_service: Data<RoleService>,
+    file_service: Data<FileService>,
+) -> Result<HttpResponse, Error> {
+    let translator_service = translator_service.get_ref();
+    let locale_service = locale_service.get_ref();
+    let role_service = role_service.get_ref();
+    let file_service = file_service.get_ref();
+    let user = user.as_ref();
+
+    let (policy_user, user_roles) = policy_subject_throw_http(&req, &user, role_service)?;
+    if !FilePolicy::can_show_any_or_own(&policy_user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+
+    query.prepare();
+
+    let lang: String = locale_service.get_locale_code(Some(&req), Some(user));
+    let lang = &lang;
+
+    let search_str = translator_service.translate(lang, "Search");
+    let sort_str = translator_service.translate(lang, "Sort");
+
+    let mut errors = ValidationErrors::default();
+    errors.push(
+        "query",
+        query.validate(translator_service, lang, &search_str, &sort_str),
+    );
+    if !errors.is_empty() {
+        return Ok(errors.to_response());
+    }
+
+    let mut filters: Vec<FileFilter> = query.get_filters();
+    filters.extend(FilePolicy::file_filters(&policy_user, &user_roles));
+    let sorts: Vec<FileSort> = query.get_sorts();
+    let params = FilePaginateParams::new(
+        query.page.unwrap(),
+        query.per_page.unwrap(),
+        filters,
+        sorts,
+    );
+    let mut files = file_service.paginate_throw_http(&params)?;
+    let user_files_filters = FilePolicy::user_file_filters(&policy_user, &user_roles);
+    file_service
+        .load_and_attach_user_files(&mut files.records, Some(&user_files_filters), None)
+        .map_err(|_| error::ErrorInternalServerError(""))?;
+
+    Ok(HttpResponse::Ok().json(files))
+}
+
+pub async fn show(
+    req: HttpRequest,
+    path: Path<u64>,
+    user: ReqData<Arc<User>>,
+    role_service: Data<RoleService>,
+    file_service: Data<FileService>,
+) -> Result<HttpResponse, Error> {
+    let file_service = file_service.get_ref();
+    let (policy_user, user_roles) = policy_subject_throw_http(&req, &user, role_service.get_ref())?;
+    if !FilePolicy::can_show_any_or_own(&policy_user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+    let entity = file_service.first_by_id_throw_http(path.into_inner())?;
+    let mut files = vec![entity];
+    let user_files_filters = FilePolicy::user_file_filters(&policy_user, &user_roles);
+    file_service
+        .load_and_attach_user_files(&mut files, Some(&user_files_filters), None)
+        .map_err(|_| error::ErrorInternalServerError(""))?;
+    if !FilePolicy::can_show_file(&policy_user, &user_roles, &files[0]) {
+        return Err(error::ErrorForbidden(""));
+    }
+    Ok(HttpResponse::Ok().json(&files[0]))
+}
+
+pub async fn store(
+    req: HttpRequest,
+    payload: Multipart,
+    user: ReqData<Arc<User>>,
+    translator_service: Data<TranslatorService>,
+    locale_service: Data<LocaleService>,
+    rate_limit_service: Data<RateLimitService>,
+    role_service: Data<RoleService>,
+    file_service: Data<FileService>,
+    audit_log_service: Data<AuditLogService>,
+) -> Result<HttpResponse, Error> {
+    let translator_service = translator_service.get_ref();
+    let user = user.as_ref();
+
+    let (policy_user, user_roles) = policy_subject_throw_http(&req, &user, role_service.get_ref())?;
+    if !FilePolicy::can_create(&policy_user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+
+    let lang: String = locale_service.get_ref().get_locale_code(Some(&req), Some(user));
+    let lang = &lang;
+
+    rate_limit_throw_http(
+        &req,
+        rate_limit_service.get_ref(),
+        translator_service,
+        lang,
+        RL_KEY,
+        RL_MAX_ATTEMPTS,
+        RL_TTL,
+    )?;
+
+    let file_str = translator_service.translate(lang, "page.files.create.fields.file");
+    let max_size = file_service.get_upload_max_size(user, &user_roles);
+
+    let mut data = UploadData::default();
+    data.prepare_from_multipart(
+        payload,
+        file_service.get_ref(),
+        max_size,
+        lang,
+        translator_service,
+        &file_str,
+    )
+    .await?;
+
+    let is_public = if let Some(value) = &data.is_public {
+        value.eq("1") || value.eq("true")
+    } else {
+        false
+    };
+
+    let UploadFile {
+        upload,
+        filename,
+        mime,
+    } = match data.file {
+        Some(file) => file,
+        None => {
+            let mut errors = ValidationErrors::default();
+            errors.push("file", data.file_errors);
+            return Ok(errors.to_response());
+        }
+    };
+
+    let result = file_service
+        .get_ref()
+        .upload_stream_to_disk(user.id, upload, is_public, filename, mime);
+
+    match result {
+        Ok(user_file) => {
+            audit_log_service.record_throw_http(
+                &req,
+                user,
+                AuditLogAction::Create,
+                AuditLogEntity::UserFile,
+                user_file.id,
+                None,
+                AuditLogService::to_value(&user_file),
+            )?;
+            Ok(HttpResponse::Created().json(user_file))
+        }
+        Err(FileServiceError::DuplicateFile) => {
+            let mut errors = ValidationErrors::default();
+            errors.push(
+                "file",
+                vec![FileServiceError::DuplicateFile.translate(lang, translator_service)],
+            );
+            Ok(errors.to_response())
+        }
+        Err(_) => Err(error::ErrorInternalServerError("")),
+    }
+}
+
+pub async fn delete(
+    req: HttpRequest,
+    path: Path<u64>,
+    user: ReqData<Arc<User>>,
+    translator_service: Data<TranslatorService>,
+    locale_service: Data<LocaleService>,
+    rate_limit_service: Data<RateLimitService>,
+    role_service: Data<RoleService>,
+    file_service: Data<FileService>,
+    audit_log_service: Data<AuditLogService>,
+) -> Result<HttpResponse, Error> {
+    let file_service = file_service.get_ref();
+    let (policy_user, user_roles) = policy_subject_throw_http(&req, &user, role_service.get_ref())?;
+    if !FilePolicy::can_delete(&policy_user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+    let lang: String = locale_service
+        .get_ref()
+        .get_locale_code(Some(&req), Some(&user));
+    rate_limit_throw_http(
+        &req,
+        rate_limit_service.get_ref(),
+        translator_service.get_ref(),
+        &lang,
+        RL_KEY,
+        RL_MAX_ATTEMPTS,
+        RL_TTL,
+    )?;
+
+    let entity = file_service.first_by_id_throw_http(path.into_inner())?;
+    file_service.soft_delete_by_id_throw_http(entity.id)?;
+    audit_log_service.record_throw_http(
+        &req,
+        &user,
+        AuditLogAction::Delete,
+        AuditLogEntity::File,
+        entity.id,
+        AuditLogService::to_value(&entity),
+        None,
+    )?;
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -1,5 +1,10 @@
-use actix_web::{ Error, HttpResponse, Result };
+use crate::User;
+use actix_web::web::ReqData;
+use actix_web::{Error, HttpResponse, Result};
+use serde_json::json;
+use std::sync::Arc;
 
-pub async fn index() -> Result<HttpResponse, Error> {
-    Ok(HttpResponse::Ok().content_type(mime::APPLICATION_JSON.as_ref()).body("{\"test\": 1}"))
-}
\ No newline at end of file
+pub async fn index(user: ReqData<Arc<User>>) -> Result<HttpResponse, Error> {
+    let user: &User = user.as_ref();
+    Ok(HttpResponse::Ok().json(json!({ "user": user })))
+}
@@ -1 +1,147 @@
-pub mod index;
\ No newline at end of file
+pub mod auth;
+pub mod files;
+pub mod index;
+pub mod roles;
+pub mod user_files;
+pub mod users;
+
+use crate::{ApiToken, ApiTokenService, RateLimitService, Role, RoleService, TranslatorService, User};
+use actix_web::http::StatusCode;
+use actix_web::{error, Error, HttpMessage, HttpRequest, HttpResponse};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Validation errors grouped by field name, rendered as a 422 JSON response.
+#[derive(Default, Debug)]
+pub struct ValidationErrors {
+    fields: BTreeMap<String, Vec<String>>,
+}
+
+impl ValidationErrors {
+    pub fn push(&mut self, field: &str, mut errors: Vec<String>) {
+        if errors.is_empty() {
+            return;
+        }
+        self.fields
+            .entry(field.to_string())
+            .or_default()
+            .append(&mut errors);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    pub fn to_response(&self) -> HttpResponse {
+        let status = StatusCode::UNPROCESSABLE_ENTITY;
+        HttpResponse::build(status).json(json!({
+            "status_code": status.as_u16(),
+            "error_message": status.canonical_reason().unwrap_or(""),
+            "errors": &self.fields,
+        }))
+    }
+}
+
+/// Counts an attempt and returns "429 Too Many Requests" with a translated message when exhausted.
+pub fn rate_limit_throw_http(
+    req: &HttpRequest,
+    rate_limit_service: &RateLimitService,
+    translator_service: &TranslatorService,
+    lang: &str,
+    key: &str,
+    max_attempts: u64,
+    ttl: u64,
+) -> Result<(), Error> {
+    let rate_limit_key = rate_limit_service.make_key_from_request_throw_http(req, key)?;
+    let executed = rate_limit_service.attempt_throw_http(&rate_limit_key, max_attempts, ttl)?;
+    if executed {
+        return Ok(());
+    }
+    let message =
+        rate_limit_service.ttl_message_throw_http(translator_service, lang, &rate_limit_key)?;
+    Err(error::ErrorTooManyRequests(message))
+}
+
+/// Returns the user and roles to pass to policies.
+/// Requests authorized by a personal access token are limited to the token scopes.
+pub fn policy_subject_throw_http(
+    req: &HttpRequest,
+    user: &User,
+    role_service: &RoleService,
+) -> Result<(User, Vec<Role>), Error> {
+    let roles = role_service.all_throw_http()?;
+    let api_token: Option<Arc<ApiToken>> = req.extensions().get::<Arc<ApiToken>>().cloned();
+    if let Some(api_token) = api_token {
+        return Ok(ApiTokenService::apply_scopes(&api_token, user, &roles));
+    }
+    Ok((user.to_owned(), roles))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RolePolicy, UserPolicy};
+    use actix_web::body::to_bytes;
+    use serde_json::Value;
+
+    #[actix_rt::test]
+    async fn test_validation_errors_to_response() {
+        let mut errors = ValidationErrors::default();
+        assert!(errors.is_empty());
+
+        errors.push("email", Vec::new());
+        assert!(errors.is_empty());
+
+        errors.push("email", vec!["required".to_string()]);
+        errors.push("password", vec!["min".to_string()]);
+        errors.push("email", vec!["email".to_string()]);
+        assert!(!errors.is_empty());
+
+        let response = errors.to_response();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "status_code": 422,
+                "error_message": "Unprocessable Entity",
+                "errors": {
+                    "email": ["required", "email"],
+                    "password": ["min"],
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_apply_scopes_super_admin() {
+        let roles = vec![Role {
+            id: 1,
+            permissions: Some(vec!["roles_show".to_string()]),
+            ..Default::default()
+        }];
+        let user = User {
+            is_super_admin: true,
+            roles_ids: Some(vec![1]),
+            ..Default::default()
+        };
+        assert!(UserPolicy::can_show(&user, &roles));
+        assert!(RolePolicy::can_show(&user, &roles));
+
+        let api_token = ApiToken {
+            scopes: Some(vec!["users_show".to_string()]),
+            ..Default::default()
+        };
+        let (scoped_user, scoped_roles) = ApiTokenService::apply_scopes(&api_token, &user, &roles);
+        assert!(!scoped_user.is_super_admin);
+        assert_eq!(scoped_roles.len(), 1);
+        assert_eq!(scoped_user.roles_ids, Some(vec![scoped_roles[0].id]));
+        assert_eq!(scoped_roles[0].permissions, Some(vec!["users_show".to_string()]));
+        assert!(UserPolicy::can_show(&scoped_user, &scoped_roles));
+        assert!(!RolePolicy::can_show(&scoped_user, &scoped_roles));
+        assert!(!UserPolicy::can_delete(&scoped_user, &scoped_roles));
+    }
+}
@@ -0,0 +1,364 @@
+use crate::app::controllers::api::v1::{policy_subject_throw_http, rate_limit_throw_http, ValidationErrors};
+use crate::app::controllers::web::roles::index::IndexQuery;
+use crate::app::validator::rules::contains_str::ContainsStr;
+use crate::app::validator::rules::required::Required;
+use crate::app::validator::rules::str_max_chars_count::StrMaxCharsCount;
+use crate::app::validator::rules::str_min_max_chars_count::StrMinMaxCharsCount as MMLS;
+use crate::{
+    prepare_value, AuditLogAction, AuditLogEntity, AuditLogService, LocaleService, Permission, RateLimitService, Role, RoleColumn, RoleFilter,
+    RolePaginateParams, RolePolicy, RoleService, RoleServiceError, RoleSort, TranslatableError,
+    TranslatorService, User,
+};
+use actix_web::web::{Data, Json, Path, Query, ReqData};
+use actix_web::{error, Error, HttpRequest, HttpResponse, Result};
+use serde_derive::Deserialize;
+use std::sync::Arc;
+use strum::VariantNames;
+
+const RL_MAX_ATTEMPTS: u64 = 60;
+const RL_TTL: u64 = 60;
+const RL_KEY: &str = "api_roles_create_update_delete";
+
+#[derive(Deserialize, Default, Debug)]
+pub struct RoleData {
+    pub code: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub permissions: Option<Vec<String>>,
+    pub parent_id: Option<u64>,
+}
+
+pub async fn index(
+    req: HttpRequest,
+    user: ReqData<Arc<User>>,
+    mut query: Query<IndexQuery>,
+    translator_service: Data<TranslatorService>,
+    locale_service: Data<LocaleService>,
+    role_service: Data<RoleService>,
+) -> Result<HttpResponse, Error> {
+    let translator_service = translator_service.get_ref();
+    let locale_service = locale_service.get_ref();
+    let role_service = role_service.get_ref();
+    let user = user.as_ref();
+
+    let (policy_user, user_roles) = policy_subject_throw_http(&req, &user, role_service)?;
+    if !RolePolicy::can_show(&policy_user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+
+    query.prepare();
+
+    let lang: String = locale_service.get_locale_code(Some(&req), Some(user));
+    let lang = &lang;
+
+    let search_str = translator_service.translate(lang, "Search");
+    let sort_str = translator_service.translate(lang, "Sort");
+
+    let mut errors = ValidationErrors::default();
+    errors.push(
+        "query",
+        query.validate(translator_service, lang, &search_str, &sort_str),
+    );
+    if !errors.is_empty() {
+        return Ok(errors.to_response());
+    }
+
+    let filters: Vec<RoleFilter> = query.get_filters();
+    let sorts: Vec<RoleSort> = query.get_sorts();
+    let params = RolePaginateParams::new(
+        query.page.unwrap(),
+        query.per_page.unwrap(),
+        filters,
+        sorts,
+    );
+    let roles = role_service.paginate_throw_http(&params)?;
+
+    Ok(HttpResponse::Ok().json(roles))
+}
+
+pub async fn show(
+    req: HttpRequest,
+    path: Path<u64>,
+    user: ReqData<Arc<User>>,
+    role_service: Data<RoleService>,
+) -> Result<HttpResponse, Error> {
+    let role_service = role_service.get_ref();
+    let (policy_user, user_roles) = policy_subject_throw_http(&req, &user, role_service)?;
+    if !RolePolicy::can_show(&policy_user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+    let entity = role_service.first_by_id_throw_http(path.into_inner())?;
+    Ok(HttpResponse::Ok().json(entity))
+}
+
+pub async fn store(
+    req: HttpRequest,
+    data: Json<RoleData>,
+    user: ReqData<Arc<User>>,
+    translator_service: Data<TranslatorService>,
+    locale_service: Data<LocaleService>,
+    rate_limit_service: Data<RateLimitService>,
+    role_service: Data<RoleService>,
+    audit_log_service: Data<AuditLogService>,
+) -> Result<HttpResponse, Error> {
+    let (policy_user, user_roles) = policy_subject_throw_http(&req, &user, role_service.get_ref())?;
+    if !RolePolicy::can_create(&policy_user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+    save(
+        None,
+        req,
+        data.into_inner(),
+        user.as_ref(),
+        translator_service.get_ref(),
+        locale_service.get_ref(),
+        rate_limit_service.get_ref(),
+        role_service.get_ref(),
+        audit_log_service.get_ref(),
+    )
+}
+
+pub async fn update(
+    req: HttpRequest,
+    path: Path<u64>,
+    data: Json<RoleData>,
+    user: ReqData<Arc<User>>,
+    translator_service: Data<TranslatorService>,
+    locale_service: Data<LocaleService>,
+    rate_limit_service: Data<RateLimitService>,
+    role_service: Data<RoleService>,
+    audit_log_service: Data<AuditLogService>,
+) -> Result<HttpResponse, Error> {
+    let (policy_user, user_roles) = policy_subject_throw_http(&req, &user, role_service.get_ref())?;
+    if !RolePolicy::can_update(&policy_user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+    let entity = role_service
+        .get_ref()
+        .first_by_id_throw_http(path.into_inner())?;
+    save(
+        Some(entity),
+        req,
+        data.into_inner(),
+        user.as_ref(),
+        translator_service.get_ref(),
+        locale_service.get_ref(),
+        rate_limit_service.get_ref(),
+        role_service.get_ref(),
+        audit_log_service.get_ref(),
+    )
+}
+
+pub async fn delete(
+    req: HttpRequest,
+    path: Path<u64>,
+    user: ReqData<Arc<User>>,
+    translator_service: Data<TranslatorService>,
+    locale_service: Data<LocaleService>,
+    rate_limit_service: Data<RateLimitService>,
+    role_service: Data<RoleService>,
+    audit_log_service: Data<AuditLogService>,
+) -> Result<HttpResponse, Error> {
+    let role_service = role_service.get_ref();
+    let (policy_user, user_roles) = policy_subject_throw_http(&req, &user, role_service)?;
+    if !RolePolicy::can_delete(&policy_user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+    let lang: String = locale_service
+        .get_ref()
+        .get_locale_code(Some(&req), Some(&user));
+    rate_limit_throw_http(
+        &req,
+        rate_limit_service.get_ref(),
+        translator_service.get_ref(),
+        &lang,
+        RL_KEY,
+        RL_MAX_ATTEMPTS,
+        RL_TTL,
+    )?;
+
+    let entity = role_service.first_by_id_throw_http(path.into_inner())?;
+    role_service.delete_by_id_throw_http(entity.id)?;
+    audit_log_service.record_throw_http(
+        &req,
+        &user,
+        AuditLogAction::Delete,
+        AuditLogEntity::Role,
+        entity.id,
+        AuditLogService::to_value(&entity),
+        None,
+    )?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+fn save(
+    entity: Option<Role>,
+    req: HttpRequest,
+    mut data: RoleData,
+    auth_user: &User,
+    translator_service: &TranslatorService,
+    locale_service: &LocaleService,
+    rate_limit_service: &RateLimitService,
+    role_service: &RoleService,
+    audit_log_service: &AuditLogService,
+) -> Result<HttpResponse, Error> {
+    data.prepare();
+
+    let lang: String = locale_service.get_locale_code(Some(&req), Some(auth_user));
+    let lang = &lang;
+
+    rate_limit_throw_http(
+        &req,
+        rate_limit_service,
+        translator_service,
+        lang,
+        RL_KEY,
+        RL_MAX_ATTEMPTS,
+        RL_TTL,
+    )?;
+
+    let mut errors = data.validate(lang, translator_service);
+    if !errors.is_empty() {
+        return Ok(errors.to_response());
+    }
+
+    let id = entity.as_ref().map(|e| e.id).unwrap_or(0);
+    if let Some(parent_id) = data.parent_id {
+        match role_service.check_parent(id, parent_id) {
+            Ok(_) => {}
+            Err(RoleServiceError::Fail) | Err(RoleServiceError::DbConnectionFail) => {
+                return Err(error::ErrorInternalServerError(""));
+            }
+            Err(error) => {
+                errors.push("parent_id", vec![error.translate(lang, translator_service)]);
+                return Ok(errors.to_response());
+            }
+        }
+    }
+
+    let mut role_data = Role::default();
+    role_data.id = id;
+    role_data.code = data.code.to_owned().unwrap();
+    role_data.name = data.name.to_owned().unwrap();
+    role_data.description = data.description.to_owned();
+    role_data.permissions = data.permissions.to_owned();
+    role_data.parent_id = data.parent_id;
+
+    let columns: Option<Vec<RoleColumn>> = Some(vec![
+        RoleColumn::Code,
+        RoleColumn::Name,
+        RoleColumn::Description,
+        RoleColumn::Permissions,
+        RoleColumn::ParentId,
+    ]);
+
+    let result = role_service.upsert(role_data, &columns);
+
+    if let Err(error) = result {
+        if error.eq(&RoleServiceError::DuplicateCode) {
+            errors.push("code", vec![error.translate(lang, translator_service)]);
+            return Ok(errors.to_response());
+        }
+        return Err(error::ErrorInternalServerError(""));
+    }
+
+    let saved = role_service.first_by_code_throw_http(data.code.as_ref().unwrap())?;
+
+    if let Some(entity) = &entity {
+        audit_log_service.record_throw_http(
+            &req,
+            auth_user,
+            AuditLogAction::Update,
+            AuditLogEntity::Role,
+            saved.id,
+            AuditLogService::to_value(entity),
+            AuditLogService::to_value(&saved),
+        )?;
+    } else {
+        audit_log_service.record_throw_http(
+            &req,
+            auth_user,
+            AuditLogAction::Create,
+            AuditLogEntity::Role,
+            saved.id,
+            None,
+            AuditLogService::to_value(&saved),
+        )?;
+    }
+
+    if entity.is_some() {
+        Ok(HttpResponse::Ok().json(saved))
+    } else {
+        Ok(HttpResponse::Created().json(saved))
+    }
+}
+
+impl RoleData {
+    pub fn prepare(&mut self) {
+        prepare_value!(self.code);
+        prepare_value!(self.name);
+        prepare_value!(self.description);
+    }
+
+    pub fn validate(&self, lang: &str, translator_service: &TranslatorService) -> ValidationErrors {
+        let code_str = translator_service.translate(lang, "page.roles.create.fields.code");
+        let name_str = translator_service.translate(lang, "page.roles.create.fields.name");
+        let description_str =
+            translator_service.translate(lang, "page.roles.create.fields.description");
+        let permissions_str =
+            translator_service.translate(lang, "page.roles.create.fields.permissions");
+
+        let mut errors = ValidationErrors::default();
+
+        errors.push(
+            "code",
+            Required::validated(
+                translator_service,
+                lang,
+                &self.code,
+                |value| MMLS::validate(translator_service, lang, value, 4, 255, &code_str),
+                &code_str,
+            ),
+        );
+        errors.push(
+            "name",
+            Required::validated(
+                translator_service,
+                lang,
+                &self.name,
+                |value| MMLS::validate(translator_service, lang, value, 4, 255, &name_str),
+                &name_str,
+            ),
+        );
+
+        if let Some(description) = &self.description {
+            errors.push(
+                "description",
+                StrMaxCharsCount::validate(
+                    translator_service,
+                    lang,
+                    description,
+                    255,
+                    &description_str,
+                ),
+            );
+        }
+
+        if let Some(permissions) = &self.permissions {
+            for permission in permissions {
+                errors.push(
+                    "permissions",
+                    ContainsStr::validate(
+                        translator_service,
+                        lang,
+                        permission,
+                        Permission::VARIANTS,
+                        &permissions_str,
+                    ),
+                );
+            }
+        }
+
+        errors
+    }
+}
@@ -0,0 +1,277 @@
+use crate::app::controllers::api::v1::{policy_subject_throw_http, rate_limit_throw_http, ValidationErrors};
+use crate::{
+    prepare_paginate, prepare_value, validation_query_max_length_string, AuditLogAction,
+    AuditLogEntity, AuditLogService, FilePolicy, FileService,
+    LocaleService, RateLimitService, RoleService, TranslatorService, User, UserFileColumn,
+    UserFileFilter, UserFilePaginateParams, UserFileService, UserFileSort,
+};
+use actix_web::web::{Data, Json, Path, Query, ReqData};
+use actix_web::{error, Error, HttpRequest, HttpResponse, Result};
+use serde_derive::Deserialize;
+use std::str::FromStr;
+use std::sync::Arc;
+
+const RL_MAX_ATTEMPTS: u64 = 60;
+const RL_TTL: u64 = 60;
+const RL_KEY: &str = "api_user_files_update_delete";
+
+pub const DEFAULT_PER_PAGE: i64 = 15;
+pub const MAX_PER_PAGE: i64 = 100;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct IndexQuery {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+    pub search: Option<String>,
+    pub user_id: Option<u64>,
+    pub file_id: Option<u64>,
+    pub sort: Option<String>,
+}
+
+#[derive(Deserialize, Default, Debug)]
+pub struct UserFileData {
+    pub is_public: Option<bool>,
+}
+
+pub async fn index(
+    req: HttpRequest,
+    user: ReqData<Arc<User>>,
+    mut query: Query<IndexQuery>,
+    translator_service: Data<TranslatorService>,
+    locale_service: Data<LocaleService>,
+    role_service: Data<RoleService>,
+    user_file_service: Data<UserFileService>,
+) -> Result<HttpResponse, Error> {
+    let translator_service = translator_service.get_ref();
+    let locale_service = locale_service.get_ref();
+    let role_service = role_service.get_ref();
+    let user_file_service = user_file_service.get_ref();
+    let user = user.as_ref();
+
+    let (policy_user, user_roles) = policy_subject_throw_http(&req, &user, role_service)?;
+    if !FilePolicy::can_show_any_or_own(&policy_user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+
+    query.prepare();
+
+    let lang: String = locale_service.get_locale_code(Some(&req), Some(user));
+    let lang = &lang;
+
+    let search_str = translator_service.translate(lang, "Search");
+    let sort_str = translator_service.translate(lang, "Sort");
+
+    let mut errors = ValidationErrors::default();
+    errors.push(
+        "query",
+        query.validate(translator_service, lang, &search_str, &sort_str),
+    );
+    if !errors.is_empty() {
+        return Ok(errors.to_response());
+    }
+
+    let mut filters: Vec<UserFileFilter> = query.get_filters();
+    filters.extend(FilePolicy::user_file_filters(&policy_user, &user_roles));
+    let sorts: Vec<UserFileSort> = query.get_sorts();
+    let params = UserFilePaginateParams::new(
+        query.page.unwrap(),
+        query.per_page.unwrap(),
+        filters,
+        sorts,
+    );
+    let user_files = user_file_service.paginate_throw_http(&params)?;
+
+    Ok(HttpResponse::Ok().json(user_files))
+}
+
+pub async fn show(
+    req: HttpRequest,
+    path: Path<u64>,
+    user: ReqData<Arc<User>>,
+    role_service: Data<RoleService>,
+    user_file_service: Data<UserFileService>,
+) -> Result<HttpResponse, Error> {
+    let (policy_user, user_roles) = policy_subject_throw_http(&req, &user, role_service.get_ref())?;
+    if !FilePolicy::can_show_any_or_own(&policy_user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+    let entity = user_file_service
+        .get_ref()
+        .first_by_id_throw_http(path.into_inner())?;
+    if !FilePolicy::can_show_user_file(&policy_user, &user_roles, &entity) {
+        return Err(error::ErrorForbidden(""));
+    }
+    Ok(HttpResponse::Ok().json(entity))
+}
+
+pub async fn update(
+    req: HttpRequest,
+    path: Path<u64>,
+    data: Json<UserFileData>,
+    user: ReqData<Arc<User>>,
+    translator_service: Data<TranslatorService>,
+    locale_service: Data<LocaleService>,
+    rate_limit_service: Data<RateLimitService>,
+    role_service: Data<RoleService>,
+    user_file_service: Data<UserFileService>,
+    file_service: Data<FileService>,
+    audit_log_service: Data<AuditLogService>,
+) -> Result<HttpResponse, Error> {
+    let user_file_service = user_file_service.get_ref();
+    let (policy_user, user_roles) = policy_subject_throw_http(&req, &user, role_service.get_ref())?;
+    if !FilePolicy::can_update_any_or_own(&policy_user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+    let lang: String = locale_service
+        .get_ref()
+        .get_locale_code(Some(&req), Some(&user));
+    rate_limit_throw_http(
+        &req,
+        rate_limit_service.get_ref(),
+        translator_service.get_ref(),
+        &lang,
+        RL_KEY,
+        RL_MAX_ATTEMPTS,
+        RL_TTL,
+    )?;
+
+    let mut user_file = user_file_service.first_by_id_throw_http(path.into_inner())?;
+    if !FilePolicy::can_update_user_file(&policy_user, &user_roles, &user_file) {
+        return Err(error::ErrorForbidden(""));
+    }
+    let file = file_service
+        .get_ref()
+        .first_by_id_throw_http(user_file.file_id)?;
+
+    if let Some(is_public) = data.is_public {
+        let old_user_file = AuditLogService::to_value(&user_file);
+        user_file.is_public = is_public;
+        let columns = Some(vec![UserFileColumn::IsPublic]);
+        user_file_service
+            .update(user_file.clone(), &columns, &file)
+            .map_err(|_| error::ErrorInternalServerError(""))?;
+        audit_log_service.record_throw_http(
+            &req,
+            &user,
+            AuditLogAction::Update,
+            AuditLogEntity::UserFile,
+            user_file.id,
+            old_user_file,
+            AuditLogService::to_value(&user_file),
+        )?;
+    }
+
+    let saved = user_file_service.first_by_id_throw_http(user_file.id)?;
+    Ok(HttpResponse::Ok().json(saved))
+}
+
+pub async fn delete(
+    req: HttpRequest,
+    path: Path<u64>,
+    user: ReqData<Arc<User>>,
+    translator_service: Data<TranslatorService>,
+    locale_service: Data<LocaleService>,
+    rate_limit_service: Data<RateLimitService>,
+    role_service: Data<RoleService>,
+    user_file_service: Data<UserFileService>,
+    audit_log_service: Data<AuditLogService>,
+) -> Result<HttpResponse, Error> {
+    let user_file_service = user_file_service.get_ref();
+    let (policy_user, user_roles) = policy_subject_throw_http(&req, &user, role_service.get_ref())?;
+    if !FilePolicy::can_delete_any_or_own(&policy_user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+    let lang: String = locale_service
+        .get_ref()
+        .get_locale_code(Some(&req), Some(&user));
+    rate_limit_throw_http(
+        &req,
+        rate_limit_service.get_ref(),
+        translator_service.get_ref(),
+        &lang,
+        RL_KEY,
+        RL_MAX_ATTEMPTS,
+        RL_TTL,
+    )?;
+
+    let entity = user_file_service.first_by_id_throw_http(path.into_inner())?;
+    if !FilePolicy::can_delete_user_file(&policy_user, &user_roles, &entity) {
+        return Err(error::ErrorForbidden(""));
+    }
+    user_file_service.soft_delete_by_id_throw_http(entity.id)?;
+    audit_log_service.record_throw_http(
+        &req,
+        &user,
+        AuditLogAction::Delete,
+        AuditLogEntity::UserFile,
+        entity.id,
+        AuditLogService::to_value(&entity),
+        None,
+    )?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+impl IndexQuery {
+    pub fn prepare(&mut self) {
+        prepare_paginate!(self.page, self.per_page, DEFAULT_PER_PAGE, MAX_PER_PAGE);
+        prepare_value!(self.search);
+        prepare_value!(self.sort);
+        if self.sort.is_none() {
+            self.sort = Some(UserFileSort::IdDesc.to_string());
+        }
+    }
+
+    pub fn validate(
+        &mut self,
+        translator_service: &TranslatorService,
+        lang: &str,
+        search_str: &str,
+        sort_str: &str,
+    ) -> Vec<String> {
+        let mut errors: Vec<String> = Vec::new();
+
+        validation_query_max_length_string!(
+            errors,
+            self.search,
+            search_str,
+            255,
+            translator_service,
+            lang
+        );
+        validation_query_max_length_string!(
+            errors,
+            self.sort,
+            sort_str,
+            255,
+            translator_service,
+            lang
+        );
+
+        errors
+    }
+
+    pub fn get_filters(&self) -> Vec<UserFileFilter> {
+        let mut filters: Vec<UserFileFilter> = Vec::new();
+
+        if let Some(value) = &self.search {
+            filters.push(UserFileFilter::Search(value.to_string()));
+        }
+        if let Some(value) = self.user_id {
+            filters.push(UserFileFilter::UserId(value));
+        }
+        if let Some(value) = self.file_id {
+            filters.push(UserFileFilter::FileId(value));
+        }
+        filters
+    }
+
+    pub fn get_sorts(&self) -> Vec<UserFileSort> {
+        let mut sorts: Vec<UserFileSort> = Vec::new();
+        if let Some(sort_) = &self.sort {
+            if let Ok(sort__) = UserFileSort::from_str(sort_) {
+                sorts.push(sort__);
+            }
+        }
+        sorts
+    }
+}
@@ -0,0 +1,452 @@
+use crate::app::controllers::api::v1::{policy_subject_throw_http, rate_limit_throw_http, ValidationErrors};
+use crate::app::controllers::web::users::create_update::audit_user_value;
+use crate::app::controllers::web::users::index::IndexQuery;
+use crate::app::validator::password_policy::PasswordPolicy;
+use crate::app::validator::rules::contains_vec_str::ContainsVecStr;
+use crate::app::validator::rules::email::Email;
+use crate::app::validator::rules::required::Required;
+use crate::app::validator::rules::str_max_chars_count::StrMaxCharsCount;
+use crate::{
+    prepare_value, AuditLogAction, AuditLogEntity, AuditLogService, Config, LocaleService, RateLimitService, RoleService, TranslatableError,
+    TranslatorService, User, UserColumn, UserFilter, UserPaginateParams, UserPolicy, UserService,
+    UserServiceError, UserSort,
+};
+use actix_web::web::{Data, Json, Path, Query, ReqData};
+use actix_web::{error, Error, HttpRequest, HttpResponse, Result};
+use serde_derive::Deserialize;
+use std::sync::Arc;
+
+const RL_MAX_ATTEMPTS: u64 = 60;
+const RL_TTL: u64 = 60;
+const RL_KEY: &str = "api_users_create_update_delete";
+
+#[derive(Deserialize, Default, Debug)]
+pub struct UserData {
+    pub email: Option<String>,
+    pub password: Option<String>,
+    pub locale: Option<String>,
+    pub surname: Option<String>,
+    pub name: Option<String>,
+    pub patronymic: Option<String>,
+    pub roles_ids: Option<Vec<u64>>,
+}
+
+pub async fn index(
+    req: HttpRequest,
+    user: ReqData<Arc<User>>,
+    mut query: Query<IndexQuery>,
+    translator_service: Data<TranslatorService>,
+    locale_service: Data<LocaleService>,
+    user_service: Data<UserService>,
+    role_service: Data<RoleService>,
+) -> Result<HttpResponse, Error> {
+    let translator_service = translator_service.get_ref();
+    let locale_service = locale_service.get_ref();
+    let user_service = user_service.get_ref();
+    let role_service = role_service.get_ref();
+    let user = user.as_ref();
+
+    let (policy_user, user_roles) = policy_subject_throw_http(&req, &user, role_service)?;
+    if !UserPolicy::can_show(&policy_user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+
+    query.prepare();
+
+    let lang: String = locale_service.get_locale_code(Some(&req), Some(user));
+    let lang = &lang;
+
+    let search_str = translator_service.translate(lang, "Search");
+    let locale_str = translator_service.translate(lang, "page.users.index.columns.locale");
+    let sort_str = translator_service.translate(lang, "Sort");
+
+    let mut errors = ValidationErrors::default();
+    errors.push(
+        "query",
+        query.validate(translator_service, lang, &search_str, &locale_str, &sort_str),
+    );
+    if !errors.is_empty() {
+        return Ok(errors.to_response());
+    }
+
+    let filters: Vec<UserFilter> = query.get_filters();
+    let sorts: Vec<UserSort> = query.get_sorts();
+    let params = UserPaginateParams::new(
+        query.page.unwrap(),
+        query.per_page.unwrap(),
+        filters,
+        sorts,
+    );
+    let users = user_service.paginate_throw_http(&params)?;
+
+    Ok(HttpResponse::Ok().json(users))
+}
+
+pub async fn show(
+    req: HttpRequest,
+    path: Path<u64>,
+    user: ReqData<Arc<User>>,
+    user_service: Data<UserService>,
+    role_service: Data<RoleService>,
+) -> Result<HttpResponse, Error> {
+    let (policy_user, user_roles) = policy_subject_throw_http(&req, &user, role_service.get_ref())?;
+    if !UserPolicy::can_show(&policy_user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+    let entity = user_service
+        .get_ref()
+        .first_by_id_throw_http(path.into_inner())?;
+    Ok(HttpResponse::Ok().json(entity))
+}
+
+pub async fn store(
+    req: HttpRequest,
+    data: Json<UserData>,
+    user: ReqData<Arc<User>>,
+    translator_service: Data<TranslatorService>,
+    locale_service: Data<LocaleService>,
+    rate_limit_service: Data<RateLimitService>,
+    user_service: Data<UserService>,
+    role_service: Data<RoleService>,
+    audit_log_service: Data<AuditLogService>,
+    config: Data<Config>,
+) -> Result<HttpResponse, Error> {
+    let (policy_user, user_roles) = policy_subject_throw_http(&req, &user, role_service.get_ref())?;
+    if !UserPolicy::can_create(&policy_user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+    save(
+        None,
+        req,
+        data.into_inner(),
+        user.as_ref(),
+        translator_service.get_ref(),
+        locale_service.get_ref(),
+        rate_limit_service.get_ref(),
+        user_service.get_ref(),
+        role_service.get_ref(),
+        audit_log_service.get_ref(),
+        config.get_ref(),
+    )
+}
+
+pub async fn update(
+    req: HttpRequest,
+    path: Path<u64>,
+    data: Json<UserData>,
+    user: ReqData<Arc<User>>,
+    translator_service: Data<TranslatorService>,
+    locale_service: Data<LocaleService>,
+    rate_limit_service: Data<RateLimitService>,
+    user_service: Data<UserService>,
+    role_service: Data<RoleService>,
+    audit_log_service: Data<AuditLogService>,
+    config: Data<Config>,
+) -> Result<HttpResponse, Error> {
+    let (policy_user, user_roles) = policy_subject_throw_http(&req, &user, role_service.get_ref())?;
+    if !UserPolicy::can_update(&policy_user, &user_roles)
+        && !UserPolicy::can_update_same_roles(&policy_user, &user_roles)
+    {
+        return Err(error::ErrorForbidden(""));
+    }
+    let entity = user_service
+        .get_ref()
+        .first_by_id_throw_http(path.into_inner())?;
+    if !UserPolicy::can_update_user(&policy_user, &user_roles, &entity) {
+        return Err(error::ErrorForbidden(""));
+    }
+    save(
+        Some(entity),
+        req,
+        data.into_inner(),
+        user.as_ref(),
+        translator_service.get_ref(),
+        locale_service.get_ref(),
+        rate_limit_service.get_ref(),
+        user_service.get_ref(),
+        role_service.get_ref(),
+        audit_log_service.get_ref(),
+        config.get_ref(),
+    )
+}
+
+pub async fn delete(
+    req: HttpRequest,
+    path: Path<u64>,
+    user: ReqData<Arc<User>>,
+    translator_service: Data<TranslatorService>,
+    locale_service: Data<LocaleService>,
+    rate_limit_service: Data<RateLimitService>,
+    user_service: Data<UserService>,
+    role_service: Data<RoleService>,
+    audit_log_service: Data<AuditLogService>,
+) -> Result<HttpResponse, Error> {
+    let user_service = user_service.get_ref();
+    let (policy_user, user_roles) = policy_subject_throw_http(&req, &user, role_service.get_ref())?;
+    if !UserPolicy::can_delete(&policy_user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+    let lang: String = locale_service
+        .get_ref()
+        .get_locale_code(Some(&req), Some(&user));
+    rate_limit_throw_http(
+        &req,
+        rate_limit_service.get_ref(),
+        translator_service.get_ref(),
+        &lang,
+        RL_KEY,
+        RL_MAX_ATTEMPTS,
+        RL_TTL,
+    )?;
+
+    let entity = user_service.first_by_id_throw_http(path.into_inner())?;
+    user_service.delete_by_id_throw_http(entity.id)?;
+    audit_log_service.record_throw_http(
+        &req,
+        &user,
+        AuditLogAction::Delete,
+        AuditLogEntity::User,
+        entity.id,
+        AuditLogService::to_value(&entity),
+        None,
+    )?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+fn save(
+    entity: Option<User>,
+    req: HttpRequest,
+    mut data: UserData,
+    auth_user: &User,
+    translator_service: &TranslatorService,
+    locale_service: &LocaleService,
+    rate_limit_service: &RateLimitService,
+    user_service: &UserService,
+    role_service: &RoleService,
+    audit_log_service: &AuditLogService,
+    config: &Config,
+) -> Result<HttpResponse, Error> {
+    data.prepare();
+
+    let lang: String = locale_service.get_locale_code(Some(&req), Some(auth_user));
+    let lang = &lang;
+
+    rate_limit_throw_http(
+        &req,
+        rate_limit_service,
+        translator_service,
+        lang,
+        RL_KEY,
+        RL_MAX_ATTEMPTS,
+        RL_TTL,
+    )?;
+
+    let (policy_user, user_roles) = policy_subject_throw_http(&req, auth_user, role_service)?;
+    let str_locales: Vec<&str> = locale_service.get_locales_codes_ref();
+
+    let mut errors = data.validate(
+        &entity,
+        lang,
+        translator_service,
+        config,
+        &str_locales,
+    );
+    if !errors.is_empty() {
+        return Ok(errors.to_response());
+    }
+
+    let mut user_data = User::default();
+    user_data.id = entity.as_ref().map(|e| e.id).unwrap_or(0);
+    user_data.email = data.email.to_owned().unwrap();
+    user_data.locale = data.locale.to_owned();
+    user_data.surname = data.surname.to_owned();
+    user_data.name = data.name.to_owned();
+    user_data.patronymic = data.patronymic.to_owned();
+
+    let mut columns: Vec<UserColumn> = vec![
+        UserColumn::Email,
+        UserColumn::Locale,
+        UserColumn::Surname,
+        UserColumn::Name,
+        UserColumn::Patronymic,
+    ];
+
+    if UserPolicy::can_set_roles(&policy_user, &user_roles) {
+        user_data.roles_ids = data.roles_ids.to_owned();
+        columns.push(UserColumn::RolesIds);
+    }
+
+    let result = user_service.upsert(user_data, &Some(columns));
+
+    if let Err(error) = result {
+        if error.eq(&UserServiceError::DuplicateEmail) {
+            errors.push("email", vec![error.translate(lang, translator_service)]);
+            return Ok(errors.to_response());
+        }
+        return Err(error::ErrorInternalServerError(""));
+    }
+
+    let email = data.email.to_owned().unwrap();
+
+    if let Some(password) = &data.password {
+        let result = if let Some(entity) = &entity {
+            user_service.update_password_by_id(entity.id, password)
+        } else {
+            user_service.update_password_by_email(&email, password)
+        };
+        if let Err(error) = result {
+            if error.eq(&UserServiceError::PasswordReused) {
+                errors.push("password", vec![error.translate(lang, translator_service)]);
+                return Ok(errors.to_response());
+            }
+            return Err(error::ErrorInternalServerError(""));
+        }
+    }
+
+    let saved = user_service.first_by_email_throw_http(&email)?;
+
+    if let Some(entity) = &entity {
+        audit_log_service.record_throw_http(
+            &req,
+            auth_user,
+            AuditLogAction::Update,
+            AuditLogEntity::User,
+            saved.id,
+            AuditLogService::to_value(entity),
+            audit_user_value(&saved, &data.password),
+        )?;
+    } else {
+        audit_log_service.record_throw_http(
+            &req,
+            auth_user,
+            AuditLogAction::Create,
+            AuditLogEntity::User,
+            saved.id,
+            None,
+            audit_user_value(&saved, &data.password),
+        )?;
+    }
+
+    if entity.is_some() {
+        Ok(HttpResponse::Ok().json(saved))
+    } else {
+        Ok(HttpResponse::Created().json(saved))
+    }
+}
+
+impl UserData {
+    pub fn prepare(&mut self) {
+        prepare_value!(self.email);
+        prepare_value!(self.password);
+        prepare_value!(self.locale);
+        prepare_value!(self.surname);
+        prepare_value!(self.name);
+        prepare_value!(self.patronymic);
+    }
+
+    pub fn validate(
+        &self,
+        entity: &Option<User>,
+        lang: &str,
+        translator_service: &TranslatorService,
+        config: &Config,
+        str_locales: &Vec<&str>,
+    ) -> ValidationErrors {
+        let email_str = translator_service.translate(lang, "page.users.create.fields.email");
+        let password_str = translator_service.translate(lang, "page.users.create.fields.password");
+        let surname_str = translator_service.translate(lang, "page.users.create.fields.surname");
+        let name_str = translator_service.translate(lang, "page.users.create.fields.name");
+        let patronymic_str =
+            translator_service.translate(lang, "page.users.create.fields.patronymic");
+        let locale_str = translator_service.translate(lang, "page.users.create.fields.locale");
+
+        let mut errors = ValidationErrors::default();
+        let password_inputs = [
+            self.email.as_deref().unwrap_or(""),
+            self.surname.as_deref().unwrap_or(""),
+            self.name.as_deref().unwrap_or(""),
+            self.patronymic.as_deref().unwrap_or(""),
+        ];
+
+        errors.push(
+            "email",
+            Required::validated(
+                translator_service,
+                lang,
+                &self.email,
+                |value| Email::validate(translator_service, lang, value, &email_str),
+                &email_str,
+            ),
+        );
+
+        if entity.is_none() {
+            errors.push(
+                "password",
+                Required::validated(
+                    translator_service,
+                    lang,
+                    &self.password,
+                    |value| {
+                        PasswordPolicy::validate(
+                            translator_service,
+                            lang,
+                            &config.auth.password,
+                            value,
+                            &password_inputs,
+                            &password_str,
+                        )
+                    },
+                    &password_str,
+                ),
+            );
+        } else if let Some(password) = &self.password {
+            errors.push(
+                "password",
+                PasswordPolicy::validate(
+                    translator_service,
+                    lang,
+                    &config.auth.password,
+                    password,
+                    &password_inputs,
+                    &password_str,
+                ),
+            );
+        }
+
+        if let Some(locale) = &self.locale {
+            errors.push(
+                "locale",
+                ContainsVecStr::validate(translator_service, lang, locale, str_locales, &locale_str),
+            );
+        }
+
+        if let Some(surname) = &self.surname {
+            errors.push(
+                "surname",
+                StrMaxCharsCount::validate(translator_service, lang, surname, 255, &surname_str),
+            );
+        }
+
+        if let Some(name) = &self.name {
+            errors.push(
+                "name",
+                StrMaxCharsCount::validate(translator_service, lang, name, 255, &name_str),
+            );
+        }
+
+        if let Some(patronymic) = &self.patronymic {
+            errors.push(
+                "patronymic",
+                StrMaxCharsCount::validate(
+                    translator_service,
+                    lang,
+                    patronymic,
+                    255,
+                    &patronymic_str,
+                ),
+            );
+        }
+
+        errors
+    }
+}
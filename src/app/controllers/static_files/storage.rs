@@ -1,39 +1,119 @@
 use std::path::MAIN_SEPARATOR;
 use std::sync::Arc;
-use actix_web::web::{Data, Path, ReqData};
-use actix_web::{error, Error, Result};
+use actix_web::web::{Data, Path, Query, ReqData};
+use actix_web::http::header::LOCATION;
+use actix_web::{error, Error, HttpRequest, HttpResponse, Result};
 use actix_files::NamedFile;
-use crate::{Config, Disk, FilePolicy, FileService, RoleService, User, UserFileService};
+use serde_derive::Deserialize;
+use crate::{Config, Disk, FilePolicy, FileService, FileVariantService, RoleService, User};
+
+#[derive(Deserialize, Debug)]
+pub struct StorageQuery {
+    // The name of the image variant, for example ?variant=thumb.
+    pub variant: Option<String>,
+}
 
 pub async fn public(
     config: Data<Config>,
     filename: Path<String>,
+    query: Query<StorageQuery>,
+    file_service: Data<FileService>,
+    file_variant_service: Data<FileVariantService>,
 ) -> Result<NamedFile, Error> {
     let mut path = config.filesystem.disks.local.public_root.to_owned();
     let filename = filename.into_inner();
     path.push(MAIN_SEPARATOR);
     path.push_str(&filename);
 
-    Ok(NamedFile::open(path)?)
+    let file = NamedFile::open(&path)?;
+
+    // The public link is named [user_id]-[filename], the variant is given only for the published file.
+    if query.variant.is_some() {
+        if let Some((_, filename)) = filename.split_once('-') {
+            let original = file_service
+                .get_ref()
+                .first_by_disk_and_filename(&Disk::Local, filename)
+                .map_err(|_| error::ErrorInternalServerError(""))?;
+            if let Some(original) = original {
+                let path = file_variant_service
+                    .get_ref()
+                    .get_path(&original, &query.variant)
+                    .map_err(|_| error::ErrorInternalServerError(""))?;
+                return Ok(NamedFile::open(path)?);
+            }
+        }
+    }
+
+    Ok(file)
 }
 
 pub async fn private(
+    req: HttpRequest,
+    config: Data<Config>,
     user: ReqData<Arc<User>>,
     filename: Path<String>,
+    query: Query<StorageQuery>,
     role_service: Data<RoleService>,
     file_service: Data<FileService>,
-) -> Result<NamedFile, Error> {
+    file_variant_service: Data<FileVariantService>,
+) -> Result<HttpResponse, Error> {
     let role_service = role_service.get_ref();
     let user = user.as_ref();
 
     let user_roles = role_service.all_throw_http()?;
-    if !FilePolicy::can_show(&user, &user_roles) {
+    if !FilePolicy::can_show_any_or_own(user, &user_roles) {
         return Err(error::ErrorForbidden(""));
     }
 
     let filename = filename.into_inner();
     let file_service = file_service.get_ref();
-    let file = file_service.first_by_disk_and_filename_throw_http(&Disk::Local, &filename)?;
 
-    Ok(NamedFile::open(file.path)?)
+    // Files uploaded before the default disk was changed stay on the local disk.
+    let mut disks = vec![file_service.get_default_disk()];
+    if !disks.contains(&Disk::Local) {
+        disks.push(Disk::Local);
+    }
+
+    for disk in disks {
+        let file = file_service
+            .first_by_disk_and_filename(&disk, &filename)
+            .map_err(|_| error::ErrorInternalServerError(""))?;
+
+        if let Some(mut file) = file {
+            if !FilePolicy::can_show(user, &user_roles) {
+                let mut files = vec![file];
+                file_service
+                    .load_and_attach_user_files(
+                        &mut files,
+                        Some(&FilePolicy::user_file_filters(user, &user_roles)),
+                        None,
+                    )
+                    .map_err(|_| error::ErrorInternalServerError(""))?;
+                file = files.remove(0);
+                if !FilePolicy::can_show_file(user, &user_roles, &file) {
+                    return Err(error::ErrorForbidden(""));
+                }
+            }
+
+            let path = file_variant_service
+                .get_ref()
+                .get_path(&file, &query.variant)
+                .map_err(|_| error::ErrorInternalServerError(""))?;
+
+            return match disk {
+                Disk::Local => Ok(NamedFile::open(path)?.into_response(&req)),
+                Disk::S3 => {
+                    let url = file_service
+                        .get_s3_repository()
+                        .presigned_url(&path, config.filesystem.disks.s3.presigned_expires)
+                        .map_err(|_| error::ErrorInternalServerError(""))?;
+                    Ok(HttpResponse::TemporaryRedirect()
+                        .insert_header((LOCATION, url))
+                        .finish())
+                }
+            };
+        }
+    }
+
+    Err(error::ErrorNotFound(""))
 }
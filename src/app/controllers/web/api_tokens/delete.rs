@@ -0,0 +1,74 @@
+use crate::app::controllers::web::api_tokens::index::get_url;
+use crate::{
+    AlertVariant, ApiTokenService, LocaleService, RateLimitService, Session, TranslatorService,
+    User, WebAuthService, WebHttpResponse,
+};
+use actix_web::web::{Data, Form, Path, ReqData};
+use actix_web::{error, Error, HttpRequest, HttpResponse, Result, http::header::{HeaderValue, LOCATION}};
+use serde_derive::Deserialize;
+use std::sync::Arc;
+
+const RL_MAX_ATTEMPTS: u64 = 60;
+const RL_TTL: u64 = 60;
+const RL_KEY: &'static str = "api_tokens_delete";
+
+#[derive(Deserialize, Default, Debug)]
+pub struct PostData {
+    pub _token: Option<String>,
+}
+
+pub async fn invoke(
+    req: HttpRequest,
+    path: Path<u64>,
+    data: Form<PostData>,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    api_token_service: Data<ApiTokenService>,
+    locale_service: Data<LocaleService>,
+    web_auth_service: Data<WebAuthService>,
+    rate_limit_service: Data<RateLimitService>,
+    translator_service: Data<TranslatorService>,
+) -> Result<HttpResponse, Error> {
+    let web_auth_service = web_auth_service.get_ref();
+    let rate_limit_service = rate_limit_service.get_ref();
+    let locale_service = locale_service.get_ref();
+    let api_token_service = api_token_service.get_ref();
+    let translator_service = translator_service.get_ref();
+
+    web_auth_service.check_csrf_throw_http(&session, &data._token)?;
+
+    let api_token_id = path.into_inner();
+    let user = user.as_ref();
+    let lang: String = locale_service.get_locale_code(Some(&req), Some(&user));
+    let delete_api_token =
+        api_token_service.first_by_id_and_user_id_throw_http(api_token_id, user.id)?;
+
+    let rate_limit_key = rate_limit_service.make_key_from_request_throw_http(&req, RL_KEY)?;
+
+    let mut alert_variants = Vec::new();
+    let executed =
+        rate_limit_service.attempt_throw_http(&rate_limit_key, RL_MAX_ATTEMPTS, RL_TTL)?;
+
+    if executed {
+        api_token_service.delete_by_id_throw_http(delete_api_token.id)?;
+        let name = delete_api_token.name;
+        alert_variants.push(AlertVariant::ApiTokensDeleteSuccess(name));
+    } else {
+        let alert_variant = rate_limit_service.alert_variant_throw_http(
+            translator_service,
+            &lang,
+            &rate_limit_key,
+        )?;
+        alert_variants.push(alert_variant);
+    }
+
+    let location = get_url();
+
+    Ok(HttpResponse::SeeOther()
+        .set_alerts(alert_variants)
+        .insert_header((
+            LOCATION,
+            HeaderValue::from_str(&location).map_err(|_| error::ErrorInternalServerError(""))?,
+        ))
+        .finish())
+}
@@ -0,0 +1,358 @@
+use crate::app::controllers::web::{get_context_data, get_template_context};
+use crate::app::validator::rules::contains_vec_str::ContainsVecStr;
+use crate::app::validator::rules::required::Required;
+use crate::app::validator::rules::str_min_max_chars_count::StrMinMaxCharsCount as MMLS;
+use crate::libs::actix_web::types::form::Form;
+use crate::{
+    prepare_value, Alert, AlertVariant, ApiToken, ApiTokenService, AppService, RateLimitService,
+    RoleService, Session, TemplateService, TranslatableError, TranslatorService, User,
+    WebAuthService, WebHttpResponse,
+};
+use actix_web::web::{Data, ReqData};
+use actix_web::{http::Method, Error, HttpRequest, HttpResponse, Result};
+use serde_derive::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+const RL_MAX_ATTEMPTS: u64 = 10;
+const RL_TTL: u64 = 60;
+const RL_KEY: &'static str = "api_tokens_create";
+const EXPIRES_IN_DAYS: [&'static str; 4] = ["7", "30", "90", "365"];
+
+#[derive(Deserialize, Default, Debug)]
+pub struct PostData {
+    pub _token: Option<String>,
+    pub name: Option<String>,
+    pub expires_in_days: Option<String>,
+    pub scopes: Option<Vec<String>>,
+}
+
+#[derive(Deserialize, Default, Debug)]
+struct ErrorMessages {
+    pub form: Vec<String>,
+    pub name: Vec<String>,
+    pub expires_in_days: Vec<String>,
+    pub scopes: Vec<String>,
+}
+
+pub async fn index(
+    req: HttpRequest,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    translator_service: Data<TranslatorService>,
+    template_service: Data<TemplateService>,
+    app_service: Data<AppService>,
+    web_auth_service: Data<WebAuthService>,
+    rate_limit_service: Data<RateLimitService>,
+    role_service: Data<RoleService>,
+    api_token_service: Data<ApiTokenService>,
+) -> Result<HttpResponse, Error> {
+    let mut post_data = PostData::default();
+    post_data.expires_in_days = Some("30".to_string());
+    invoke(
+        req,
+        Form(post_data),
+        user,
+        session,
+        translator_service,
+        template_service,
+        app_service,
+        web_auth_service,
+        rate_limit_service,
+        role_service,
+        api_token_service,
+    )
+}
+
+pub async fn store(
+    req: HttpRequest,
+    data: Form<PostData>,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    translator_service: Data<TranslatorService>,
+    template_service: Data<TemplateService>,
+    app_service: Data<AppService>,
+    web_auth_service: Data<WebAuthService>,
+    rate_limit_service: Data<RateLimitService>,
+    role_service: Data<RoleService>,
+    api_token_service: Data<ApiTokenService>,
+) -> Result<HttpResponse, Error> {
+    invoke(
+        req,
+        data,
+        user,
+        session,
+        translator_service,
+        template_service,
+        app_service,
+        web_auth_service,
+        rate_limit_service,
+        role_service,
+        api_token_service,
+    )
+}
+
+pub fn invoke(
+    req: HttpRequest,
+    mut data: Form<PostData>,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    translator_service: Data<TranslatorService>,
+    template_service: Data<TemplateService>,
+    app_service: Data<AppService>,
+    web_auth_service: Data<WebAuthService>,
+    rate_limit_service: Data<RateLimitService>,
+    role_service: Data<RoleService>,
+    api_token_service: Data<ApiTokenService>,
+) -> Result<HttpResponse, Error> {
+    data.prepare();
+    //
+    let translator_service = translator_service.get_ref();
+    let template_service = template_service.get_ref();
+    let app_service = app_service.get_ref();
+    let web_auth_service = web_auth_service.get_ref();
+    let rate_limit_service = rate_limit_service.get_ref();
+    let role_service = role_service.get_ref();
+    let api_token_service = api_token_service.get_ref();
+
+    //
+    let user = user.as_ref();
+
+    let mut context_data = get_context_data(
+        &req,
+        user,
+        &session,
+        translator_service,
+        app_service,
+        web_auth_service,
+        role_service,
+    );
+
+    let lang = &context_data.lang;
+
+    let name_str = translator_service.translate(lang, "page.api_tokens.fields.name");
+    let expires_in_days_str =
+        translator_service.translate(lang, "page.api_tokens.fields.expires_in_days");
+    let scopes_str = translator_service.translate(lang, "page.api_tokens.fields.scopes");
+    let heading = translator_service.translate(lang, "page.api_tokens.header");
+
+    context_data.title = translator_service.translate(lang, "page.api_tokens.title");
+
+    let roles = role_service.all_throw_http()?;
+    let available_scopes: Vec<String> = ApiTokenService::available_scopes(user, &roles);
+    let available_scopes_: Vec<&str> = available_scopes.iter().map(|s| s.as_str()).collect();
+
+    //
+    let is_post = req.method().eq(&Method::POST);
+    let mut errors = ErrorMessages::default();
+    let mut created_token: Option<String> = None;
+
+    if is_post {
+        web_auth_service.check_csrf_throw_http(&session, &data._token)?;
+
+        let rate_limit_key = rate_limit_service.make_key_from_request_throw_http(&req, RL_KEY)?;
+
+        let executed =
+            rate_limit_service.attempt_throw_http(&rate_limit_key, RL_MAX_ATTEMPTS, RL_TTL)?;
+
+        if executed {
+            errors.name = Required::validated(
+                translator_service,
+                lang,
+                &data.name,
+                |value| MMLS::validate(translator_service, lang, value, 4, 255, &name_str),
+                &name_str,
+            );
+
+            if let Some(expires_in_days) = &data.expires_in_days {
+                errors.expires_in_days = ContainsVecStr::validate(
+                    translator_service,
+                    lang,
+                    expires_in_days,
+                    &EXPIRES_IN_DAYS.to_vec(),
+                    &expires_in_days_str,
+                );
+            }
+
+            if let Some(scopes) = &data.scopes {
+                for scope in scopes {
+                    errors.scopes.append(&mut ContainsVecStr::validate(
+                        translator_service,
+                        lang,
+                        scope,
+                        &available_scopes_,
+                        &scopes_str,
+                    ));
+                }
+            }
+
+            if errors.is_empty() {
+                let days: Option<i64> = data
+                    .expires_in_days
+                    .as_ref()
+                    .and_then(|days| days.parse::<i64>().ok());
+                let expires_at = api_token_service.make_expires_at(days);
+                let scopes = data.scopes.to_owned().unwrap_or_default();
+                let name = data.name.to_owned().unwrap();
+
+                let result = api_token_service.create(user.id, &name, scopes, expires_at);
+
+                match result {
+                    Ok((api_token, token)) => {
+                        let variant = AlertVariant::ApiTokensCreateSuccess(api_token.name);
+                        context_data
+                            .alerts
+                            .push(Alert::from_variant(translator_service, lang, &variant));
+                        created_token = Some(token);
+                        data.name = None;
+                        data.scopes = None;
+                    }
+                    Err(error) => errors.form.push(error.translate(lang, translator_service)),
+                }
+            }
+        } else {
+            let ttl_message =
+                rate_limit_service.ttl_message_throw_http(translator_service, lang, &rate_limit_key)?;
+            errors.form.push(ttl_message)
+        }
+
+        if created_token.is_some() {
+            rate_limit_service.clear_throw_http(&rate_limit_key)?;
+        }
+    }
+
+    //
+    for form_error in errors.form {
+        context_data.alerts.push(Alert::error(form_error));
+    }
+
+    let layout_ctx = get_template_context(&context_data);
+
+    let mut scopes: Vec<Value> = Vec::new();
+    for scope in &available_scopes {
+        let mut key = "permission.".to_string();
+        key.push_str(scope);
+        let mut checked = false;
+        if let Some(val) = &data.scopes {
+            checked = val.contains(scope);
+        }
+        scopes.push(json!({
+            "label": translator_service.translate(lang, &key),
+            "value": scope,
+            "checked": checked
+        }));
+    }
+
+    let never_str = translator_service.translate(lang, "page.api_tokens.expires_in_days.never");
+    let mut expires_in_days_options: Vec<Value> = Vec::new();
+    for days in EXPIRES_IN_DAYS {
+        let mut key = "page.api_tokens.expires_in_days.".to_string();
+        key.push_str(days);
+        expires_in_days_options.push(json!({
+            "label": translator_service.translate(lang, &key),
+            "value": days,
+        }));
+    }
+
+    let api_tokens: Vec<ApiToken> = api_token_service.all_by_user_id_throw_http(user.id)?;
+    let api_tokens: Vec<Value> = api_tokens
+        .iter()
+        .map(|api_token| {
+            let scopes: Vec<String> = api_token
+                .scopes
+                .to_owned()
+                .unwrap_or_default()
+                .iter()
+                .map(|scope| {
+                    let mut key = "permission.".to_string();
+                    key.push_str(scope);
+                    translator_service.translate(lang, &key)
+                })
+                .collect();
+            json!({
+                "id": api_token.id,
+                "name": &api_token.name,
+                "scopes": scopes.join(", "),
+                "expires_at": api_token.expires_at.to_owned().unwrap_or(never_str.to_owned()),
+                "last_used_at": &api_token.last_used_at,
+                "created_at": &api_token.created_at,
+                "is_expired": api_token_service.is_expired(api_token),
+                "delete_action": get_delete_url(api_token.id.to_string().as_str()),
+            })
+        })
+        .collect();
+
+    let fields = json!({
+        "name": { "label": name_str, "value": &data.name, "errors": errors.name },
+        "expires_in_days": { "label": expires_in_days_str, "value": &data.expires_in_days, "errors": errors.expires_in_days, "options": expires_in_days_options, "placeholder": &never_str },
+        "scopes": { "label": scopes_str, "errors": errors.scopes, "options": scopes },
+    });
+
+    let ctx = json!({
+        "ctx": layout_ctx,
+        "heading": &heading,
+        "breadcrumbs": [
+            {"href": "/", "label": translator_service.translate(lang, "page.profile.breadcrumbs.home")},
+            {"href": "/profile", "label": translator_service.translate(lang, "page.profile.breadcrumbs.profile")},
+            {"label": &heading},
+        ],
+        "created_token": created_token.map(|token| json!({
+            "label": translator_service.translate(lang, "page.api_tokens.created_token"),
+            "value": token,
+        })),
+        "columns": {
+            "name": translator_service.translate(lang, "page.api_tokens.columns.name"),
+            "scopes": translator_service.translate(lang, "page.api_tokens.columns.scopes"),
+            "expires_at": translator_service.translate(lang, "page.api_tokens.columns.expires_at"),
+            "last_used_at": translator_service.translate(lang, "page.api_tokens.columns.last_used_at"),
+            "created_at": translator_service.translate(lang, "page.api_tokens.columns.created_at"),
+            "actions": translator_service.translate(lang, "page.api_tokens.columns.actions"),
+        },
+        "api_tokens": api_tokens,
+        "empty": translator_service.translate(lang, "page.api_tokens.empty"),
+        "expired": translator_service.translate(lang, "page.api_tokens.expired"),
+        "delete": translator_service.translate(lang, "Delete"),
+        "delete_confirm": translator_service.translate(lang, "page.api_tokens.delete_confirm"),
+        "form": {
+            "heading": translator_service.translate(lang, "page.api_tokens.create"),
+            "action": get_url(),
+            "method": "post",
+            "fields": fields,
+            "save": translator_service.translate(lang, "page.api_tokens.create"),
+        },
+    });
+    let s = template_service.render_throw_http("pages/profile/api-tokens.hbs", &ctx)?;
+    Ok(HttpResponse::Ok()
+        .clear_alerts()
+        .content_type(mime::TEXT_HTML_UTF_8.as_ref())
+        .body(s))
+}
+
+pub fn get_url() -> String {
+    "/profile/api-tokens".to_string()
+}
+
+pub fn get_delete_url(id: &str) -> String {
+    let mut str_ = get_url();
+    str_.push('/');
+    str_.push_str(id);
+    str_.push_str("/delete");
+    str_
+}
+
+impl PostData {
+    pub fn prepare(&mut self) {
+        prepare_value!(self._token);
+        prepare_value!(self.name);
+        prepare_value!(self.expires_in_days);
+    }
+}
+
+impl ErrorMessages {
+    pub fn is_empty(&self) -> bool {
+        self.form.len() == 0
+            && self.name.len() == 0
+            && self.expires_in_days.len() == 0
+            && self.scopes.len() == 0
+    }
+}
@@ -0,0 +1,470 @@
+use crate::app::controllers::web::{
+    generate_2_offset_pagination_array, get_context_data, get_template_context,
+};
+use crate::app::validator::rules::contains_str::ContainsStr;
+use crate::app::validator::rules::date::Date;
+use crate::{
+    prepare_paginate, prepare_value, validation_query_max_length_string, Alert, AppService,
+    AuditLogAction, AuditLogEntity, AuditLogFilter, AuditLogPaginateParams, AuditLogPolicy,
+    AuditLogService, AuditLogSort, LocaleService, RoleService, Session, TemplateService,
+    TranslatorService, User, UserService, WebAuthService, WebHttpResponse,
+};
+use actix_web::web::{Data, Query, ReqData};
+use actix_web::{error, Error, HttpRequest, HttpResponse, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::cmp::max;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use strum::{IntoEnumIterator, VariantNames};
+
+const PAGE_URL: &'static str = "/audit-log?";
+
+pub const DEFAULT_PER_PAGE: i64 = 15;
+pub const MAX_PER_PAGE: i64 = 100;
+pub const PER_PAGES: [i64; 7] = [10, 15, 20, 30, 40, 50, 100];
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct IndexQuery {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+    pub actor_id: Option<String>,
+    pub action: Option<String>,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    pub sort: Option<String>,
+}
+
+pub struct IndexQueryLabels {
+    pub actor_id: String,
+    pub action: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub date_from: String,
+    pub date_to: String,
+    pub sort: String,
+}
+
+pub async fn invoke(
+    req: HttpRequest,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    mut query: Query<IndexQuery>,
+    translator_service: Data<TranslatorService>,
+    tmpl_service: Data<TemplateService>,
+    app_service: Data<AppService>,
+    web_auth_service: Data<WebAuthService>,
+    role_service: Data<RoleService>,
+    user_service: Data<UserService>,
+    locale_service: Data<LocaleService>,
+    audit_log_service: Data<AuditLogService>,
+) -> Result<HttpResponse, Error> {
+    let translator_service = translator_service.get_ref();
+    let tmpl_service = tmpl_service.get_ref();
+    let app_service = app_service.get_ref();
+    let web_auth_service = web_auth_service.get_ref();
+    let locale_service = locale_service.get_ref();
+    let role_service = role_service.get_ref();
+    let user_service = user_service.get_ref();
+    let audit_log_service = audit_log_service.get_ref();
+    let user = user.as_ref();
+
+    let user_roles = role_service.all_throw_http()?;
+    if !AuditLogPolicy::can_show(&user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+
+    query.prepare();
+
+    let lang: String = locale_service.get_locale_code(Some(&req), Some(&user));
+    let lang = &lang;
+
+    let reset_str = translator_service.translate(lang, "Reset");
+    let labels = IndexQueryLabels {
+        actor_id: translator_service.translate(lang, "page.audit_log.index.filters.actor_id"),
+        action: translator_service.translate(lang, "page.audit_log.index.filters.action"),
+        entity_type: translator_service.translate(lang, "page.audit_log.index.filters.entity_type"),
+        entity_id: translator_service.translate(lang, "page.audit_log.index.filters.entity_id"),
+        date_from: translator_service.translate(lang, "page.audit_log.index.filters.date_from"),
+        date_to: translator_service.translate(lang, "page.audit_log.index.filters.date_to"),
+        sort: translator_service.translate(lang, "Sort"),
+    };
+
+    let form_errors: Vec<String> = query.validate(translator_service, lang, &labels);
+
+    let page = query.page.unwrap();
+    let per_page = query.per_page.unwrap();
+    let page_str = page.to_string();
+    let filters: Vec<AuditLogFilter> = query.get_filters();
+    let sorts: Vec<AuditLogSort> = query.get_sorts();
+    let pagination_params = AuditLogPaginateParams::new(page, per_page, filters, sorts);
+    let audit_logs = audit_log_service.paginate_throw_http(&pagination_params)?;
+    let total_pages = max(audit_logs.total_pages, 1);
+    let total_pages_str = total_pages.to_string();
+
+    let mut actors_ids: Vec<u64> = audit_logs
+        .records
+        .iter()
+        .filter_map(|audit_log| audit_log.actor_id)
+        .collect();
+    actors_ids.sort();
+    actors_ids.dedup();
+    let actors: HashMap<u64, String> = if actors_ids.len() > 0 {
+        user_service
+            .all_by_ids_throw_http(&actors_ids)?
+            .iter()
+            .map(|actor| (actor.id, actor.get_full_name_with_id_and_email()))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+
+    let mut context_data = get_context_data(
+        &req,
+        user,
+        &session,
+        translator_service,
+        app_service,
+        web_auth_service,
+        role_service,
+    );
+    let mut page_vars: HashMap<&str, &str> = HashMap::new();
+    page_vars.insert("page", &page_str);
+    page_vars.insert("total_pages", &total_pages_str);
+    context_data.title =
+        translator_service.variables(lang, "page.audit_log.index.title", &page_vars);
+
+    for form_error in form_errors {
+        context_data.alerts.push(Alert::error(form_error));
+    }
+
+    let layout_ctx = get_template_context(&context_data);
+
+    let mut pagination_link = query.clone().remove_page().to_url()?;
+    pagination_link.push_str("&page=:page");
+    let pagination_nums = generate_2_offset_pagination_array(audit_logs.page, total_pages);
+
+    let translate_action = |action: &str| {
+        let mut key = "page.audit_log.index.actions.".to_string();
+        key.push_str(action);
+        translator_service.translate(lang, &key)
+    };
+    let translate_entity = |entity_type: &str| {
+        let mut key = "page.audit_log.index.entities.".to_string();
+        key.push_str(entity_type);
+        translator_service.translate(lang, &key)
+    };
+
+    let mut tags: Vec<Value> = Vec::new();
+    let filter_values = [
+        (&labels.actor_id, query.actor_id.to_owned(), query.clone().remove_page().remove_actor_id().to_url()?),
+        (&labels.action, query.action.as_ref().map(|v| translate_action(v)), query.clone().remove_page().remove_action().to_url()?),
+        (&labels.entity_type, query.entity_type.as_ref().map(|v| translate_entity(v)), query.clone().remove_page().remove_entity_type().to_url()?),
+        (&labels.entity_id, query.entity_id.to_owned(), query.clone().remove_page().remove_entity_id().to_url()?),
+        (&labels.date_from, query.date_from.to_owned(), query.clone().remove_page().remove_date_from().to_url()?),
+        (&labels.date_to, query.date_to.to_owned(), query.clone().remove_page().remove_date_to().to_url()?),
+    ];
+    for (label, value, link) in filter_values {
+        if let Some(value) = value {
+            tags.push(json!({
+                "label": label,
+                "values": [{
+                    "value": &value,
+                    "label": &value,
+                    "reset": {
+                        "href": &link,
+                        "label": &reset_str
+                    }
+                }]
+            }));
+        }
+    }
+
+    let mut sort_options: Vec<Value> = Vec::new();
+    for sort_enum in AuditLogSort::iter() {
+        let value = sort_enum.to_string();
+        let mut key = "page.audit_log.index.sort.".to_string();
+        key.push_str(&value);
+        let label = translator_service.translate(lang, &key);
+        sort_options.push(json!({ "label": label, "value": value }));
+    }
+
+    let action_options: Vec<Value> = AuditLogAction::VARIANTS
+        .iter()
+        .map(|value| json!({ "label": translate_action(value), "value": value }))
+        .collect();
+
+    let entity_type_options: Vec<Value> = AuditLogEntity::VARIANTS
+        .iter()
+        .map(|value| json!({ "label": translate_entity(value), "value": value }))
+        .collect();
+
+    let records: Vec<Value> = audit_logs
+        .records
+        .iter()
+        .map(|audit_log| {
+            let actor = audit_log
+                .actor_id
+                .map(|actor_id| actors.get(&actor_id).cloned().unwrap_or(actor_id.to_string()));
+            let to_pretty = |value: &Option<Value>| {
+                value
+                    .as_ref()
+                    .and_then(|value| serde_json::to_string_pretty(value).ok())
+            };
+            json!({
+                "id": audit_log.id,
+                "actor": actor,
+                "action": translate_action(&audit_log.action),
+                "entity_type": translate_entity(&audit_log.entity_type),
+                "entity_id": audit_log.entity_id,
+                "old_values": to_pretty(&audit_log.old_values),
+                "new_values": to_pretty(&audit_log.new_values),
+                "ip": &audit_log.ip,
+                "user_agent": &audit_log.user_agent,
+                "created_at": &audit_log.created_at,
+            })
+        })
+        .collect();
+
+    let not_selected_str = translator_service.translate(lang, "Not selected...");
+
+    let ctx = json!({
+        "ctx": &layout_ctx,
+        "heading": translator_service.translate(lang, "page.audit_log.index.header"),
+        "breadcrumbs": [
+            {"href": "/", "label": translator_service.translate(lang, "page.home.header")},
+            {"href": "/audit-log", "label": translator_service.translate(lang, "page.audit_log.index.header")},
+            {"label": translator_service.variables(lang, "Page :page of :total_pages", &page_vars)},
+        ],
+        "page_per_page": translator_service.variables(lang, "Page :page of :total_pages", &page_vars),
+        "per_page_label": translator_service.translate(lang, "Number of entries per page"),
+        "select_page": translator_service.translate(lang, "Select page"),
+        "sort": {
+            "label": &labels.sort,
+            "value": &query.sort,
+            "options": &sort_options
+        },
+        "columns": {
+            "id": translator_service.translate(lang, "page.audit_log.index.columns.id"),
+            "created_at": translator_service.translate(lang, "page.audit_log.index.columns.created_at"),
+            "actor": translator_service.translate(lang, "page.audit_log.index.columns.actor"),
+            "action": translator_service.translate(lang, "page.audit_log.index.columns.action"),
+            "entity": translator_service.translate(lang, "page.audit_log.index.columns.entity"),
+            "old_values": translator_service.translate(lang, "page.audit_log.index.columns.old_values"),
+            "new_values": translator_service.translate(lang, "page.audit_log.index.columns.new_values"),
+            "client": translator_service.translate(lang, "page.audit_log.index.columns.client"),
+        },
+        "audit_logs": {
+            "page": audit_logs.page,
+            "per_page": audit_logs.per_page,
+            "total_pages": total_pages,
+            "total_records": audit_logs.total_records,
+            "records": records,
+            "pagination_nums": pagination_nums,
+            "pagination_link": pagination_link
+        },
+        "per_pages": &PER_PAGES,
+        "filter_label": translator_service.translate(lang, "Filters"),
+        "close_label": translator_service.translate(lang, "Close"),
+        "apply_label": translator_service.translate(lang, "Apply"),
+        "tags": tags,
+        "filter": {
+            "action": "/audit-log",
+            "method": "get",
+            "reset": {
+                "href": "/audit-log",
+                "label": &reset_str
+            },
+            "actor_id": {
+                "label": &labels.actor_id,
+                "value": &query.actor_id,
+            },
+            "entity_type": {
+                "label": &labels.entity_type,
+                "value": &query.entity_type,
+                "placeholder": &not_selected_str,
+                "options": entity_type_options,
+            },
+            "entity_id": {
+                "label": &labels.entity_id,
+                "value": &query.entity_id,
+            },
+            "audit_action": {
+                "label": &labels.action,
+                "value": &query.action,
+                "placeholder": &not_selected_str,
+                "options": action_options,
+            },
+            "date_from": {
+                "label": &labels.date_from,
+                "value": &query.date_from,
+            },
+            "date_to": {
+                "label": &labels.date_to,
+                "value": &query.date_to,
+            }
+        }
+    });
+
+    let s = tmpl_service.render_throw_http("pages/audit-log/index.hbs", &ctx)?;
+    Ok(HttpResponse::Ok()
+        .clear_alerts()
+        .content_type(mime::TEXT_HTML_UTF_8.as_ref())
+        .body(s))
+}
+
+impl IndexQuery {
+    pub fn prepare(&mut self) {
+        prepare_paginate!(self.page, self.per_page, DEFAULT_PER_PAGE, MAX_PER_PAGE);
+        prepare_value!(self.actor_id);
+        prepare_value!(self.action);
+        prepare_value!(self.entity_type);
+        prepare_value!(self.entity_id);
+        prepare_value!(self.date_from);
+        prepare_value!(self.date_to);
+        prepare_value!(self.sort);
+        if self.sort.is_none() {
+            self.sort = Some(AuditLogSort::IdDesc.to_string());
+        }
+    }
+    pub fn validate(
+        &mut self,
+        translator_service: &TranslatorService,
+        lang: &str,
+        labels: &IndexQueryLabels,
+    ) -> Vec<String> {
+        let mut errors: Vec<String> = Vec::new();
+
+        for (field, label) in [
+            (&mut self.actor_id, &labels.actor_id),
+            (&mut self.entity_id, &labels.entity_id),
+        ] {
+            if let Some(value) = field {
+                if u64::from_str(value).is_err() {
+                    let mut vars = HashMap::new();
+                    vars.insert("attribute", label.as_str());
+                    errors.push(translator_service.variables(lang, "validation.integer", &vars));
+                    *field = None;
+                }
+            }
+        }
+
+        for (field, variants, label) in [
+            (&mut self.action, AuditLogAction::VARIANTS, &labels.action),
+            (&mut self.entity_type, AuditLogEntity::VARIANTS, &labels.entity_type),
+        ] {
+            if let Some(value) = field {
+                let mut errors_ =
+                    ContainsStr::validate(translator_service, lang, value, variants, label);
+                if errors_.len() != 0 {
+                    errors.append(&mut errors_);
+                    *field = None;
+                }
+            }
+        }
+
+        for (field, label) in [
+            (&mut self.date_from, &labels.date_from),
+            (&mut self.date_to, &labels.date_to),
+        ] {
+            if let Some(value) = field {
+                let mut errors_ = Date::validate(translator_service, lang, value, label);
+                if errors_.len() != 0 {
+                    errors.append(&mut errors_);
+                    *field = None;
+                }
+            }
+        }
+
+        validation_query_max_length_string!(
+            errors,
+            self.sort,
+            &labels.sort,
+            255,
+            translator_service,
+            lang
+        );
+
+        errors
+    }
+    pub fn remove_page(&mut self) -> &mut Self {
+        self.page = None;
+        self
+    }
+    pub fn remove_per_page(&mut self) -> &mut Self {
+        self.per_page = None;
+        self
+    }
+    pub fn remove_actor_id(&mut self) -> &mut Self {
+        self.actor_id = None;
+        self
+    }
+    pub fn remove_action(&mut self) -> &mut Self {
+        self.action = None;
+        self
+    }
+    pub fn remove_entity_type(&mut self) -> &mut Self {
+        self.entity_type = None;
+        self
+    }
+    pub fn remove_entity_id(&mut self) -> &mut Self {
+        self.entity_id = None;
+        self
+    }
+    pub fn remove_date_from(&mut self) -> &mut Self {
+        self.date_from = None;
+        self
+    }
+    pub fn remove_date_to(&mut self) -> &mut Self {
+        self.date_to = None;
+        self
+    }
+    pub fn remove_sort(&mut self) -> &mut Self {
+        self.sort = None;
+        self
+    }
+    pub fn to_url(&self) -> Result<String, Error> {
+        let url = serde_urlencoded::to_string(self).map_err(|e| {
+            log::error!("app::controllers::web::audit_log::index::IndexQuery::to_url - {e}");
+            error::ErrorInternalServerError("")
+        })?;
+        let mut result = PAGE_URL.to_string();
+        result.push_str(&url);
+        Ok(result)
+    }
+    pub fn get_filters(&self) -> Vec<AuditLogFilter> {
+        let mut filters: Vec<AuditLogFilter> = Vec::new();
+
+        if let Some(value) = self.actor_id.as_ref().and_then(|v| u64::from_str(v).ok()) {
+            filters.push(AuditLogFilter::ActorId(value));
+        }
+        if let Some(value) = &self.action {
+            filters.push(AuditLogFilter::Action(value.to_string()));
+        }
+        if let Some(value) = &self.entity_type {
+            filters.push(AuditLogFilter::EntityType(value.to_string()));
+        }
+        if let Some(value) = self.entity_id.as_ref().and_then(|v| u64::from_str(v).ok()) {
+            filters.push(AuditLogFilter::EntityId(value));
+        }
+        if let Some(value) = &self.date_from {
+            filters.push(AuditLogFilter::CreatedAtFrom(format!("{} 00:00:00", value)));
+        }
+        if let Some(value) = &self.date_to {
+            filters.push(AuditLogFilter::CreatedAtTo(format!("{} 23:59:59", value)));
+        }
+        filters
+    }
+    pub fn get_sorts(&self) -> Vec<AuditLogSort> {
+        let mut sorts: Vec<AuditLogSort> = Vec::new();
+        if let Some(sort_) = &self.sort {
+            if let Ok(sort__) = AuditLogSort::from_str(sort_) {
+                sorts.push(sort__);
+            }
+        }
+        sorts
+    }
+}
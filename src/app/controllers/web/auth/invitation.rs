@@ -0,0 +1,366 @@
+use crate::app::controllers::web::{get_public_context_data, get_public_template_context};
+use crate::app::validator::password_policy::PasswordPolicy;
+use crate::app::validator::rules::confirmed::Confirmed;
+use crate::app::validator::rules::required::Required;
+use crate::app::validator::rules::str_max_chars_count::StrMaxCharsCount;
+use crate::app::validator::rules::str_min_max_chars_count::StrMinMaxCharsCount;
+use crate::helpers::none_if_empty;
+use crate::{
+    AlertVariant, AppService, Config, InvitationService, InvitationServiceError, RateLimitService,
+    TemplateService, TranslatableError, TranslatorService, WebAuthService, WebHttpResponse,
+    INVITATION_URL,
+};
+use actix_web::http::header::HeaderValue;
+use actix_web::web::{Data, Form, Query};
+use actix_web::{
+    error,
+    http::{header::LOCATION, Method},
+    Error, HttpRequest, HttpResponse, Result,
+};
+use serde_derive::Deserialize;
+use serde_json::json;
+
+const RL_MAX_ATTEMPTS: u64 = 10;
+const RL_TTL: u64 = 60;
+const RL_KEY: &'static str = "invitation_accept";
+
+#[derive(Deserialize, Debug)]
+pub struct InvitationQuery {
+    pub id: Option<u64>,
+    pub signature: Option<String>,
+}
+
+#[derive(Deserialize, Default, Debug)]
+pub struct InvitationData {
+    pub password: Option<String>,
+    pub confirm_password: Option<String>,
+    pub surname: Option<String>,
+    pub name: Option<String>,
+    pub patronymic: Option<String>,
+}
+
+#[derive(Default, Debug)]
+struct ErrorMessages {
+    form: Vec<String>,
+    password: Vec<String>,
+    confirm_password: Vec<String>,
+    surname: Vec<String>,
+    name: Vec<String>,
+    patronymic: Vec<String>,
+}
+
+impl ErrorMessages {
+    fn is_empty(&self) -> bool {
+        self.form.len() == 0
+            && self.password.len() == 0
+            && self.confirm_password.len() == 0
+            && self.surname.len() == 0
+            && self.name.len() == 0
+            && self.patronymic.len() == 0
+    }
+}
+
+pub async fn show(
+    req: HttpRequest,
+    query: Query<InvitationQuery>,
+    tmpl_service: Data<TemplateService>,
+    app_service: Data<AppService>,
+    translator_service: Data<TranslatorService>,
+    rate_limit_service: Data<RateLimitService>,
+    web_auth_service: Data<WebAuthService>,
+    invitation_service: Data<InvitationService>,
+    config: Data<Config>,
+) -> Result<HttpResponse, Error> {
+    invoke(
+        req,
+        query,
+        Form(InvitationData::default()),
+        tmpl_service,
+        app_service,
+        translator_service,
+        rate_limit_service,
+        web_auth_service,
+        invitation_service,
+        config,
+    )
+    .await
+}
+
+/// The link from the invitation letter, the invitee sets the password and logs in.
+pub async fn invoke(
+    req: HttpRequest,
+    query: Query<InvitationQuery>,
+    mut data: Form<InvitationData>,
+    tmpl_service: Data<TemplateService>,
+    app_service: Data<AppService>,
+    translator_service: Data<TranslatorService>,
+    rate_limit_service: Data<RateLimitService>,
+    web_auth_service: Data<WebAuthService>,
+    invitation_service: Data<InvitationService>,
+    config: Data<Config>,
+) -> Result<HttpResponse, Error> {
+    let tmpl_service = tmpl_service.get_ref();
+    let app_service = app_service.get_ref();
+    let translator_service = translator_service.get_ref();
+    let rate_limit_service = rate_limit_service.get_ref();
+    let web_auth_service = web_auth_service.get_ref();
+    let invitation_service = invitation_service.get_ref();
+
+    let mut context_data = get_public_context_data(&req, translator_service, app_service);
+    let lang = context_data.lang.to_owned();
+    let lang = &lang;
+    context_data.title = translator_service.translate(lang, "page.invitation.title");
+
+    let (id, signature) = match (&query.id, &query.signature) {
+        (Some(id), Some(signature)) if signature.len() <= 400 => (*id, signature.to_owned()),
+        _ => (0, "".to_string()),
+    };
+
+    let user = match invitation_service.verify(id, &signature) {
+        Ok(user) => user,
+        Err(e) => {
+            let layout_ctx = get_public_template_context(&context_data);
+            let ctx = json!({
+                "ctx": layout_ctx,
+                "heading": translator_service.translate(lang, "page.invitation.header"),
+                "back": {
+                    "label": translator_service.translate(lang, "page.invitation.back"),
+                    "href": "/login",
+                },
+                "form": {
+                    "errors": [e.translate(lang, translator_service)],
+                },
+            });
+            let s = tmpl_service.render_throw_http("pages/auth.hbs", &ctx)?;
+            return Ok(HttpResponse::Ok()
+                .clear_alerts()
+                .content_type(mime::TEXT_HTML_UTF_8.as_ref())
+                .body(s));
+        }
+    };
+
+    let password_str = translator_service.translate(lang, "page.invitation.fields.password");
+    let confirm_password_str =
+        translator_service.translate(lang, "page.invitation.fields.confirm_password");
+    let surname_str = translator_service.translate(lang, "page.invitation.fields.surname");
+    let name_str = translator_service.translate(lang, "page.invitation.fields.name");
+    let patronymic_str = translator_service.translate(lang, "page.invitation.fields.patronymic");
+
+    let mut errors = ErrorMessages::default();
+    let is_post = req.method().eq(&Method::POST);
+
+    if is_post {
+        data.prepare();
+        let rate_limit_key = rate_limit_service.make_key_from_request_throw_http(&req, RL_KEY)?;
+        let executed =
+            rate_limit_service.attempt_throw_http(&rate_limit_key, RL_MAX_ATTEMPTS, RL_TTL)?;
+
+        if !executed {
+            let ttl_message = rate_limit_service.ttl_message_throw_http(
+                translator_service,
+                lang,
+                &rate_limit_key,
+            )?;
+            errors.form.push(ttl_message);
+        } else {
+            data.validate(
+                &mut errors,
+                translator_service,
+                lang,
+                config.get_ref(),
+                &user.email,
+                &password_str,
+                &confirm_password_str,
+                &surname_str,
+                &name_str,
+                &patronymic_str,
+            );
+        }
+
+        if errors.is_empty() {
+            let result = invitation_service.accept(
+                user.id,
+                &signature,
+                data.password.as_ref().unwrap(),
+                &data.surname,
+                &data.name,
+                &data.patronymic,
+            );
+
+            match result {
+                Ok(user) => {
+                    rate_limit_service.clear_throw_http(&rate_limit_key)?;
+
+                    let session = web_auth_service.generate_session(user.id);
+                    web_auth_service
+                        .save_session(&session)
+                        .map_err(|_| error::ErrorInternalServerError(""))?;
+                    web_auth_service.save_session_info_throw_http(&req, &session)?;
+
+                    return Ok(HttpResponse::SeeOther()
+                        .cookie(web_auth_service.make_cookie_throw_http(&session)?)
+                        .set_alerts(vec![AlertVariant::InvitationAcceptSuccess])
+                        .insert_header((LOCATION, HeaderValue::from_static("/")))
+                        .finish());
+                }
+                Err(InvitationServiceError::PasswordHashFail) => errors.password.push(
+                    InvitationServiceError::PasswordHashFail.translate(lang, translator_service),
+                ),
+                Err(e) => errors.form.push(e.translate(lang, translator_service)),
+            }
+        }
+    } else {
+        data.surname = user.surname.to_owned();
+        data.name = user.name.to_owned();
+        data.patronymic = user.patronymic.to_owned();
+    }
+
+    let action = format!("{}?id={}&signature={}", INVITATION_URL, user.id, signature);
+    let layout_ctx = get_public_template_context(&context_data);
+    let ctx = json!({
+        "ctx": layout_ctx,
+        "heading": translator_service.translate(lang, "page.invitation.header"),
+        "form": {
+            "action": action,
+            "method": "post",
+            "header": translator_service.translate(lang, "page.invitation.header"),
+            "fields": [
+                {
+                    "label": translator_service.translate(lang, "page.invitation.fields.email"),
+                    "type": "email",
+                    "name": "email",
+                    "readonly": "readonly",
+                    "value": &user.email,
+                },
+                {
+                    "label": password_str,
+                    "type": "password",
+                    "name": "password",
+                    "value": &data.password,
+                    "errors": errors.password,
+                },
+                {
+                    "label": confirm_password_str,
+                    "type": "password",
+                    "name": "confirm_password",
+                    "value": &data.confirm_password,
+                    "errors": errors.confirm_password,
+                },
+                {
+                    "label": surname_str,
+                    "type": "text",
+                    "name": "surname",
+                    "value": &data.surname,
+                    "errors": errors.surname,
+                },
+                {
+                    "label": name_str,
+                    "type": "text",
+                    "name": "name",
+                    "value": &data.name,
+                    "errors": errors.name,
+                },
+                {
+                    "label": patronymic_str,
+                    "type": "text",
+                    "name": "patronymic",
+                    "value": &data.patronymic,
+                    "errors": errors.patronymic,
+                }
+            ],
+            "submit": {
+                "label": translator_service.translate(lang, "page.invitation.submit"),
+            },
+            "errors": errors.form,
+        },
+    });
+
+    let s = tmpl_service.render_throw_http("pages/auth.hbs", &ctx)?;
+    Ok(HttpResponse::Ok()
+        .clear_alerts()
+        .content_type(mime::TEXT_HTML_UTF_8.as_ref())
+        .body(s))
+}
+
+impl InvitationData {
+    pub fn prepare(&mut self) {
+        self.surname = none_if_empty(&self.surname);
+        self.name = none_if_empty(&self.name);
+        self.patronymic = none_if_empty(&self.patronymic);
+    }
+
+    fn validate(
+        &self,
+        errors: &mut ErrorMessages,
+        translator_service: &TranslatorService,
+        lang: &str,
+        config: &Config,
+        email: &str,
+        password_str: &str,
+        confirm_password_str: &str,
+        surname_str: &str,
+        name_str: &str,
+        patronymic_str: &str,
+    ) {
+        errors.password = Required::validated(
+            translator_service,
+            lang,
+            &self.password,
+            |value| {
+                let surname = self.surname.as_deref().unwrap_or("");
+                let name = self.name.as_deref().unwrap_or("");
+                let patronymic = self.patronymic.as_deref().unwrap_or("");
+                PasswordPolicy::validate(
+                    translator_service,
+                    lang,
+                    &config.auth.password,
+                    value,
+                    &[email, surname, name, patronymic],
+                    password_str,
+                )
+            },
+            password_str,
+        );
+        errors.confirm_password = Required::validated(
+            translator_service,
+            lang,
+            &self.confirm_password,
+            |value| {
+                StrMinMaxCharsCount::validate(
+                    translator_service,
+                    lang,
+                    value,
+                    4,
+                    255,
+                    confirm_password_str,
+                )
+            },
+            confirm_password_str,
+        );
+        if errors.password.len() == 0 && errors.confirm_password.len() == 0 {
+            let mut errors_: Vec<String> = Confirmed::validate(
+                translator_service,
+                lang,
+                self.password.as_ref().unwrap(),
+                self.confirm_password.as_ref().unwrap(),
+                password_str,
+            );
+            errors.confirm_password.append(&mut errors_);
+        }
+        if let Some(surname) = &self.surname {
+            errors.surname =
+                StrMaxCharsCount::validate(translator_service, lang, surname, 255, surname_str);
+        }
+        if let Some(name) = &self.name {
+            errors.name = StrMaxCharsCount::validate(translator_service, lang, name, 255, name_str);
+        }
+        if let Some(patronymic) = &self.patronymic {
+            errors.patronymic =
+                StrMaxCharsCount::validate(translator_service, lang, patronymic, 255, patronymic_str);
+        }
+    }
+}
+
+pub fn get_url() -> String {
+    INVITATION_URL.to_string()
+}
@@ -1,7 +1,7 @@
 use crate::app::controllers::web::{get_public_context_data, get_public_template_context};
 use crate::app::validator::rules::email::Email;
 use crate::app::validator::rules::required::Required;
-use crate::{prepare_value, AlertVariant, RateLimitService, Session, WebAuthService, WebHttpResponse};
+use crate::{prepare_value, AlertVariant, LoginSecurityService, OidcService, RateLimitService, Session, UserTwoFactorService, WebAuthService, WebHttpResponse};
 use crate::{AppService, AuthService, TemplateService, TranslatorService};
 use actix_web::web::Data;
 use actix_web::web::Form;
@@ -9,6 +9,7 @@ use actix_web::{error, Error, HttpRequest, HttpResponse, Result, http::{Method,
 use actix_web::http::header::HeaderValue;
 use serde_derive::Deserialize;
 use serde_json::json;
+use std::collections::HashMap;
 use crate::app::validator::rules::str_min_max_chars_count::StrMinMaxCharsCount;
 
 const RL_MAX_ATTEMPTS: u64 = 5;
@@ -29,6 +30,9 @@ pub async fn show(
     translator_service: Data<TranslatorService>,
     rate_limit_service: Data<RateLimitService>,
     web_auth_service: Data<WebAuthService>,
+    user_two_factor_service: Data<UserTwoFactorService>,
+    oidc_service: Data<OidcService>,
+    login_security_service: Data<LoginSecurityService>,
 ) -> Result<HttpResponse, Error> {
     invoke(
         req,
@@ -42,6 +46,9 @@ pub async fn show(
         translator_service,
         rate_limit_service,
         web_auth_service,
+        user_two_factor_service,
+        oidc_service,
+        login_security_service,
     )
     .await
 }
@@ -55,6 +62,9 @@ pub async fn invoke(
     translator_service: Data<TranslatorService>,
     rate_limit_service: Data<RateLimitService>,
     web_auth_service: Data<WebAuthService>,
+    user_two_factor_service: Data<UserTwoFactorService>,
+    oidc_service: Data<OidcService>,
+    login_security_service: Data<LoginSecurityService>,
 ) -> Result<HttpResponse, Error> {
     let auth_service = auth_service.get_ref();
     let tmpl_service = tmpl_service.get_ref();
@@ -62,6 +72,9 @@ pub async fn invoke(
     let translator_service = translator_service.get_ref();
     let rate_limit_service = rate_limit_service.get_ref();
     let web_auth_service = web_auth_service.get_ref();
+    let user_two_factor_service = user_two_factor_service.get_ref();
+    let oidc_service = oidc_service.get_ref();
+    let login_security_service = login_security_service.get_ref();
 
     let auth_result = web_auth_service.login_by_req(&req);
 
@@ -81,7 +94,7 @@ pub async fn invoke(
     let password_str = translator_service.translate(lang, "page.login.fields.password");
 
     let is_post = req.method().eq(&Method::POST);
-    let (is_done, email_errors, password_errors, form_errors, session) = post(
+    let (is_done, email_errors, password_errors, form_errors, session, two_factor_token) = post(
         is_post,
         &req,
         &mut data,
@@ -92,9 +105,19 @@ pub async fn invoke(
         auth_service,
         web_auth_service,
         rate_limit_service,
+        user_two_factor_service,
+        login_security_service,
     )
     .await?;
 
+    if let Some(two_factor_token) = two_factor_token {
+        return Ok(HttpResponse::SeeOther()
+            .cookie(user_two_factor_service.make_login_cookie(&two_factor_token))
+            .clear_alerts()
+            .insert_header((LOCATION, HeaderValue::from_static("/login/two-factor")))
+            .finish());
+    }
+
     if is_done {
         let session = session.unwrap();
         return Ok(HttpResponse::SeeOther()
@@ -104,6 +127,19 @@ pub async fn invoke(
             .finish());
     }
 
+    let providers: Vec<serde_json::Value> = oidc_service
+        .providers()
+        .iter()
+        .map(|provider| {
+            let mut vars: HashMap<&str, &str> = HashMap::new();
+            vars.insert("name", &provider.name);
+            json!({
+                "label": translator_service.variables(lang, "page.login.oidc", &vars),
+                "href": OidcService::get_login_url(&provider.code),
+            })
+        })
+        .collect();
+
     let layout_ctx = get_public_template_context(&context_data);
     let ctx = json!({
         "ctx": layout_ctx,
@@ -139,6 +175,7 @@ pub async fn invoke(
                 "href": "/register"
             },
             "errors": form_errors,
+            "providers": providers,
         },
     });
 
@@ -161,12 +198,15 @@ async fn post(
     auth_service: &AuthService,
     web_auth_service: &WebAuthService,
     rate_limit_service: &RateLimitService,
-) -> Result<(bool, Vec<String>, Vec<String>, Vec<String>, Option<Session>), Error> {
+    user_two_factor_service: &UserTwoFactorService,
+    login_security_service: &LoginSecurityService,
+) -> Result<(bool, Vec<String>, Vec<String>, Vec<String>, Option<Session>, Option<String>), Error> {
     let mut is_done = false;
     let mut form_errors: Vec<String> = Vec::new();
     let mut email_errors: Vec<String> = Vec::new();
     let mut password_errors: Vec<String> = Vec::new();
     let mut session: Option<Session> = None;
+    let mut two_factor_token: Option<String> = None;
 
     if is_post {
         let rate_limit_key = rate_limit_service
@@ -196,16 +236,41 @@ async fn post(
             if email_errors.len() == 0 && password_errors.len() == 0 {
                 let email_value = data.email.as_ref().unwrap();
                 let password_value = data.password.as_ref().unwrap();
-                let auth_result = auth_service.login_by_password(email_value, password_value);
+                // The attempts are also counted per account, so the password can not be guessed from many addresses.
+                let check_message = login_security_service.check_message_throw_http(
+                    translator_service,
+                    lang,
+                    email_value,
+                )?;
 
-                if let Ok(user_id) = auth_result {
-                    let session_ = web_auth_service.generate_session(user_id);
-                    web_auth_service
-                        .save_session(&session_)
+                if let Some(check_message) = check_message {
+                    form_errors.push(check_message);
+                } else if let Ok(user_id) =
+                    auth_service.login_by_password(email_value, password_value)
+                {
+                    let is_two_factor_enabled = user_two_factor_service
+                        .is_enabled(user_id)
                         .map_err(|_| error::ErrorInternalServerError(""))?;
-                    session = Some(session_);
+
+                    if is_two_factor_enabled {
+                        // The session is issued and the failures start over after the second factor check.
+                        let token = user_two_factor_service
+                            .make_login_token(user_id)
+                            .map_err(|_| error::ErrorInternalServerError(""))?;
+                        two_factor_token = Some(token);
+                    } else {
+                        login_security_service.register_success_throw_http(email_value)?;
+                        let session_ = web_auth_service.generate_session(user_id);
+                        web_auth_service
+                            .save_session(&session_)
+                            .map_err(|_| error::ErrorInternalServerError(""))?;
+                        web_auth_service.save_session_info_throw_http(req, &session_)?;
+                        login_security_service.register_login_silently(req, user_id);
+                        session = Some(session_);
+                    }
                     is_done = true;
                 } else {
+                    login_security_service.register_failure_throw_http(email_value)?;
                     form_errors.push(translator_service.translate(lang, "alert.login.fail"));
                 }
             };
@@ -233,7 +298,7 @@ async fn post(
         }
     }
 
-    Ok((is_done, email_errors, password_errors, form_errors, session))
+    Ok((is_done, email_errors, password_errors, form_errors, session, two_factor_token))
 }
 
 impl LoginData {
@@ -0,0 +1,243 @@
+use crate::app::controllers::web::{get_public_context_data, get_public_template_context};
+use crate::app::validator::rules::required::Required;
+use crate::app::validator::rules::str_min_max_chars_count::StrMinMaxCharsCount;
+use crate::{prepare_value, AlertVariant, LoginSecurityService, RateLimitService, Session, TranslatableError, UserService, UserTwoFactorService, UserTwoFactorServiceError, WebAuthService, WebHttpResponse};
+use crate::{AppService, TemplateService, TranslatorService};
+use actix_web::web::Data;
+use actix_web::web::Form;
+use actix_web::{error, Error, HttpRequest, HttpResponse, Result, http::{Method, header::{LOCATION}}};
+use actix_web::http::header::HeaderValue;
+use serde_derive::Deserialize;
+use serde_json::json;
+
+const RL_MAX_ATTEMPTS: u64 = 5;
+const RL_TTL: u64 = 60;
+const RL_KEY: &'static str = "login_two_factor";
+
+#[derive(Deserialize, Debug)]
+pub struct LoginTwoFactorData {
+    pub code: Option<String>,
+}
+
+pub async fn show(
+    req: HttpRequest,
+    tmpl_service: Data<TemplateService>,
+    app_service: Data<AppService>,
+    translator_service: Data<TranslatorService>,
+    rate_limit_service: Data<RateLimitService>,
+    web_auth_service: Data<WebAuthService>,
+    user_two_factor_service: Data<UserTwoFactorService>,
+    login_security_service: Data<LoginSecurityService>,
+    user_service: Data<UserService>,
+) -> Result<HttpResponse, Error> {
+    invoke(
+        req,
+        Form(LoginTwoFactorData { code: None }),
+        tmpl_service,
+        app_service,
+        translator_service,
+        rate_limit_service,
+        web_auth_service,
+        user_two_factor_service,
+        login_security_service,
+        user_service,
+    )
+    .await
+}
+
+pub async fn invoke(
+    req: HttpRequest,
+    mut data: Form<LoginTwoFactorData>,
+    tmpl_service: Data<TemplateService>,
+    app_service: Data<AppService>,
+    translator_service: Data<TranslatorService>,
+    rate_limit_service: Data<RateLimitService>,
+    web_auth_service: Data<WebAuthService>,
+    user_two_factor_service: Data<UserTwoFactorService>,
+    login_security_service: Data<LoginSecurityService>,
+    user_service: Data<UserService>,
+) -> Result<HttpResponse, Error> {
+    data.prepare();
+    let tmpl_service = tmpl_service.get_ref();
+    let app_service = app_service.get_ref();
+    let translator_service = translator_service.get_ref();
+    let rate_limit_service = rate_limit_service.get_ref();
+    let web_auth_service = web_auth_service.get_ref();
+    let user_two_factor_service = user_two_factor_service.get_ref();
+    let login_security_service = login_security_service.get_ref();
+    let user_service = user_service.get_ref();
+
+    // The user has to pass the password check first.
+    let token = user_two_factor_service.get_login_token_from_request(&req);
+    let user_id: Option<u64> = if let Some(token) = &token {
+        user_two_factor_service
+            .get_login_user_id(token)
+            .map_err(|_| error::ErrorInternalServerError(""))?
+    } else {
+        None
+    };
+
+    if token.is_none() || user_id.is_none() {
+        return Ok(HttpResponse::SeeOther()
+            .cookie(user_two_factor_service.make_clear_login_cookie())
+            .insert_header((LOCATION, HeaderValue::from_static("/login")))
+            .finish());
+    }
+    let token = token.unwrap();
+    let user_id = user_id.unwrap();
+    // The failures of the code are counted with the password failures of the account.
+    let email = user_service.first_by_id_throw_http(user_id)?.email;
+
+    let mut context_data = get_public_context_data(&req, translator_service, app_service);
+    let lang = &context_data.lang;
+    context_data.title = translator_service.translate(lang, "page.login_two_factor.title");
+
+    let code_str = translator_service.translate(lang, "page.login_two_factor.fields.code");
+
+    let is_post = req.method().eq(&Method::POST);
+    let (code_errors, form_errors, session) = post(
+        is_post,
+        &mut data,
+        user_id,
+        &email,
+        &code_str,
+        lang,
+        translator_service,
+        web_auth_service,
+        rate_limit_service,
+        user_two_factor_service,
+        login_security_service,
+    )
+    .await?;
+
+    if let Some(session) = session {
+        web_auth_service.save_session_info_throw_http(&req, &session)?;
+        user_two_factor_service
+            .delete_login_token(&token)
+            .map_err(|_| error::ErrorInternalServerError(""))?;
+        login_security_service.register_login_silently(&req, user_id);
+        return Ok(HttpResponse::SeeOther()
+            .cookie(web_auth_service.make_cookie_throw_http(&session)?)
+            .cookie(user_two_factor_service.make_clear_login_cookie())
+            .set_alerts(vec![AlertVariant::LoginSuccess])
+            .insert_header((LOCATION, HeaderValue::from_static("/")))
+            .finish());
+    }
+
+    let layout_ctx = get_public_template_context(&context_data);
+    let ctx = json!({
+        "ctx": layout_ctx,
+        "heading": translator_service.translate(lang, "page.login_two_factor.header"),
+        "back": {
+            "label": translator_service.translate(lang, "page.login_two_factor.back"),
+            "href": "/login"
+        },
+        "form": {
+            "action": "/login/two-factor",
+            "method": "post",
+            "fields": [
+                {
+                    "label": code_str,
+                    "type": "text",
+                    "name": "code",
+                    "value": &data.code,
+                    "errors": code_errors,
+                    "autocomplete": "one-time-code",
+                }
+            ],
+            "submit": {
+                "label": translator_service.translate(lang, "page.login_two_factor.submit"),
+                "text": translator_service.translate(lang, "page.login_two_factor.text"),
+            },
+            "errors": form_errors,
+        },
+    });
+
+    let s = tmpl_service.render_throw_http("pages/auth.hbs", &ctx)?;
+
+    Ok(HttpResponse::Ok()
+        .clear_alerts()
+        .content_type(mime::TEXT_HTML_UTF_8.as_ref())
+        .body(s))
+}
+
+async fn post(
+    is_post: bool,
+    data: &mut Form<LoginTwoFactorData>,
+    user_id: u64,
+    email: &str,
+    code_str: &String,
+    lang: &str,
+    translator_service: &TranslatorService,
+    web_auth_service: &WebAuthService,
+    rate_limit_service: &RateLimitService,
+    user_two_factor_service: &UserTwoFactorService,
+    login_security_service: &LoginSecurityService,
+) -> Result<(Vec<String>, Vec<String>, Option<Session>), Error> {
+    let mut form_errors: Vec<String> = Vec::new();
+    let mut code_errors: Vec<String> = Vec::new();
+    let mut session: Option<Session> = None;
+
+    if is_post {
+        // The attempts are counted per user, so the code can not be guessed from many addresses.
+        let rate_limit_key = format!("{}.{}", RL_KEY, user_id);
+
+        let executed = rate_limit_service
+            .attempt(&rate_limit_key, RL_MAX_ATTEMPTS, RL_TTL)
+            .map_err(|_| error::ErrorInternalServerError(""))?;
+
+        if executed {
+            code_errors = Required::validated(translator_service, lang, &data.code, |value| {
+                StrMinMaxCharsCount::validate(translator_service, lang, value, 6, 32, code_str)
+            }, code_str);
+
+            // The account locked after the password check can not be finished with the code.
+            let check_message =
+                login_security_service.check_message_throw_http(translator_service, lang, email)?;
+
+            if let Some(check_message) = check_message {
+                form_errors.push(check_message);
+            } else if code_errors.is_empty() {
+                let code = data.code.as_ref().unwrap();
+                match user_two_factor_service.verify(user_id, code) {
+                    Ok(_) => {
+                        login_security_service.register_success_throw_http(email)?;
+                        let session_ = web_auth_service.generate_session(user_id);
+                        web_auth_service
+                            .save_session(&session_)
+                            .map_err(|_| error::ErrorInternalServerError(""))?;
+                        session = Some(session_);
+                    }
+                    Err(UserTwoFactorServiceError::InvalidCode) => {
+                        login_security_service.register_failure_throw_http(email)?;
+                        code_errors.push(
+                            UserTwoFactorServiceError::InvalidCode.translate(lang, translator_service),
+                        );
+                    }
+                    Err(e) => form_errors.push(e.translate(lang, translator_service)),
+                }
+            }
+
+            data.code = None;
+        } else {
+            let ttl_message = rate_limit_service
+                .ttl_message(translator_service, lang, &rate_limit_key)
+                .map_err(|_| error::ErrorInternalServerError(""))?;
+            form_errors.push(ttl_message)
+        }
+
+        if session.is_some() {
+            rate_limit_service
+                .clear(&rate_limit_key)
+                .map_err(|_| error::ErrorInternalServerError(""))?;
+        }
+    }
+
+    Ok((code_errors, form_errors, session))
+}
+
+impl LoginTwoFactorData {
+    pub fn prepare(&mut self) {
+        prepare_value!(self.code);
+    }
+}
@@ -1,5 +1,9 @@
+pub mod invitation;
 pub mod login;
+pub mod login_two_factor;
 pub mod logout;
+pub mod oidc;
 pub mod register;
 pub mod reset_password;
-pub mod reset_password_confirm;
\ No newline at end of file
+pub mod reset_password_confirm;
+pub mod verify_email;
\ No newline at end of file
@@ -0,0 +1,134 @@
+use crate::app::controllers::web::{get_public_context_data, get_public_template_context};
+use crate::{
+    AlertVariant, AppService, LoginSecurityService, OidcService, OidcServiceError, TemplateService, TranslatableError,
+    TranslatorService, UserTwoFactorService, WebAuthService, WebHttpResponse,
+};
+use actix_web::http::header::HeaderValue;
+use actix_web::web::{Data, Path, Query};
+use actix_web::{error, http::header::LOCATION, Error, HttpRequest, HttpResponse, Result};
+use serde_derive::Deserialize;
+use serde_json::json;
+use subtle::ConstantTimeEq;
+
+#[derive(Deserialize, Debug)]
+pub struct OidcCallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Sends the user to the identity provider.
+pub async fn redirect(
+    path: Path<String>,
+    oidc_service: Data<OidcService>,
+) -> Result<HttpResponse, Error> {
+    let oidc_service = oidc_service.get_ref();
+    let (url, state) = oidc_service.make_authorization_url_throw_http(&path.into_inner())?;
+
+    Ok(HttpResponse::SeeOther()
+        .cookie(oidc_service.make_state_cookie(&state))
+        .insert_header((
+            LOCATION,
+            HeaderValue::from_str(&url).map_err(|_| error::ErrorInternalServerError(""))?,
+        ))
+        .finish())
+}
+
+/// The identity provider returns the user here, the login ends as the login by the password.
+pub async fn callback(
+    req: HttpRequest,
+    path: Path<String>,
+    query: Query<OidcCallbackQuery>,
+    tmpl_service: Data<TemplateService>,
+    app_service: Data<AppService>,
+    translator_service: Data<TranslatorService>,
+    oidc_service: Data<OidcService>,
+    web_auth_service: Data<WebAuthService>,
+    user_two_factor_service: Data<UserTwoFactorService>,
+    login_security_service: Data<LoginSecurityService>,
+) -> Result<HttpResponse, Error> {
+    let tmpl_service = tmpl_service.get_ref();
+    let app_service = app_service.get_ref();
+    let translator_service = translator_service.get_ref();
+    let oidc_service = oidc_service.get_ref();
+    let web_auth_service = web_auth_service.get_ref();
+    let user_two_factor_service = user_two_factor_service.get_ref();
+    let login_security_service = login_security_service.get_ref();
+
+    let code = path.into_inner();
+    let cookie_state = oidc_service.get_state_from_request(&req).unwrap_or_default();
+
+    let result = match (&query.error, &query.code, &query.state) {
+        (None, Some(authorization_code), Some(state))
+            if !cookie_state.is_empty()
+                && bool::from(cookie_state.as_bytes().ct_eq(state.as_bytes())) =>
+        {
+            oidc_service.login_by_callback(&code, state, authorization_code)
+        }
+        (Some(e), _, _) => {
+            log::error!("oidc::callback - {code} - {e}");
+            Err(OidcServiceError::Fail)
+        }
+        _ => Err(OidcServiceError::StateInvalid),
+    };
+
+    let user_id = match result {
+        Ok(user_id) => user_id,
+        Err(OidcServiceError::ProviderNotFound) => return Err(error::ErrorNotFound("")),
+        Err(e) => {
+            let mut context_data = get_public_context_data(&req, translator_service, app_service);
+            let lang = &context_data.lang;
+            context_data.title = translator_service.translate(lang, "page.login.title");
+            let layout_ctx = get_public_template_context(&context_data);
+            let ctx = json!({
+                "ctx": layout_ctx,
+                "heading": translator_service.translate(lang, "page.login.header"),
+                "back": {
+                    "label": translator_service.translate(lang, "page.login.oidc_back"),
+                    "href": "/login",
+                },
+                "form": {
+                    "errors": [e.translate(lang, translator_service)],
+                },
+            });
+
+            let s = tmpl_service.render_throw_http("pages/auth.hbs", &ctx)?;
+            return Ok(HttpResponse::Ok()
+                .cookie(oidc_service.make_clear_state_cookie())
+                .clear_alerts()
+                .content_type(mime::TEXT_HTML_UTF_8.as_ref())
+                .body(s));
+        }
+    };
+
+    let is_two_factor_enabled = user_two_factor_service
+        .is_enabled(user_id)
+        .map_err(|_| error::ErrorInternalServerError(""))?;
+
+    if is_two_factor_enabled {
+        // The session is issued after the second factor check.
+        let token = user_two_factor_service
+            .make_login_token(user_id)
+            .map_err(|_| error::ErrorInternalServerError(""))?;
+        return Ok(HttpResponse::SeeOther()
+            .cookie(oidc_service.make_clear_state_cookie())
+            .cookie(user_two_factor_service.make_login_cookie(&token))
+            .clear_alerts()
+            .insert_header((LOCATION, HeaderValue::from_static("/login/two-factor")))
+            .finish());
+    }
+
+    let session = web_auth_service.generate_session(user_id);
+    web_auth_service
+        .save_session(&session)
+        .map_err(|_| error::ErrorInternalServerError(""))?;
+    web_auth_service.save_session_info_throw_http(&req, &session)?;
+    login_security_service.register_login_silently(&req, user_id);
+
+    Ok(HttpResponse::SeeOther()
+        .cookie(oidc_service.make_clear_state_cookie())
+        .cookie(web_auth_service.make_cookie_throw_http(&session)?)
+        .set_alerts(vec![AlertVariant::LoginSuccess])
+        .insert_header((LOCATION, HeaderValue::from_static("/")))
+        .finish())
+}
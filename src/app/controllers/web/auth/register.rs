@@ -1,11 +1,13 @@
 use crate::app::controllers::web::{get_public_context_data, get_public_template_context};
 use crate::app::middlewares::web_auth::REDIRECT_TO;
+use crate::app::validator::password_policy::PasswordPolicy;
 use crate::app::validator::rules::confirmed::Confirmed;
 use crate::app::validator::rules::email::Email;
 use crate::app::validator::rules::required::Required;
 use crate::{prepare_value, AlertVariant, RateLimitService, TranslatableError, WebHttpResponse};
 use crate::{
-    AppService, AuthService, AuthServiceError, Credentials, TemplateService, TranslatorService,
+    AppService, AuthService, AuthServiceError, Config, Credentials, EmailVerificationService,
+    TemplateService, TranslatorService, UserService,
 };
 use actix_web::web::{Data, Form};
 use actix_web::{error, Error, HttpRequest, HttpResponse, Result, http::{Method, header::{LOCATION}}};
@@ -32,6 +34,9 @@ pub async fn show(
     app_service: Data<AppService>,
     translator_service: Data<TranslatorService>,
     rate_limit_service: Data<RateLimitService>,
+    user_service: Data<UserService>,
+    email_verification_service: Data<EmailVerificationService>,
+    config: Data<Config>,
 ) -> Result<HttpResponse, Error> {
     invoke(
         req,
@@ -45,6 +50,9 @@ pub async fn show(
         translator_service,
         auth_service,
         rate_limit_service,
+        user_service,
+        email_verification_service,
+        config,
     )
     .await
 }
@@ -57,12 +65,17 @@ pub async fn invoke(
     translator_service: Data<TranslatorService>,
     auth_service: Data<AuthService>,
     rate_limit_service: Data<RateLimitService>,
+    user_service: Data<UserService>,
+    email_verification_service: Data<EmailVerificationService>,
+    config: Data<Config>,
 ) -> Result<HttpResponse, Error> {
     let tmpl_service = tmpl_service.get_ref();
     let app_service = app_service.get_ref();
     let translator_service = translator_service.get_ref();
     let auth_service = auth_service.get_ref();
     let rate_limit_service = rate_limit_service.get_ref();
+    let user_service = user_service.get_ref();
+    let email_verification_service = email_verification_service.get_ref();
 
     let mut context_data = get_public_context_data(&req, translator_service, app_service);
     let lang = &context_data.lang;
@@ -85,12 +98,21 @@ pub async fn invoke(
         lang,
         auth_service,
         rate_limit_service,
+        config.get_ref(),
     )
     .await?;
 
     if is_done {
+        let mut alert_variants = vec![AlertVariant::RegisterSuccess];
+        // The account is already created, so the failed letter can be sent again from the notice page.
+        let email = data.email.to_owned().unwrap_or("".to_string());
+        if let Ok(Some(user)) = user_service.first_by_email(&email) {
+            if email_verification_service.send(&user, lang).is_ok() {
+                alert_variants.push(AlertVariant::VerifyEmailSent);
+            }
+        }
         return Ok(HttpResponse::SeeOther()
-            .set_alerts(vec![AlertVariant::RegisterSuccess])
+            .set_alerts(alert_variants)
             .insert_header((
                 LOCATION,
                 HeaderValue::from_static(REDIRECT_TO),
@@ -161,6 +183,7 @@ async fn post(
     lang: &str,
     auth_service: &AuthService,
     rate_limit_service: &RateLimitService,
+    config: &Config,
 ) -> Result<(bool, Vec<String>, Vec<String>, Vec<String>, Vec<String>), Error> {
     let mut is_done = false;
     let mut form_errors: Vec<String> = Vec::new();
@@ -183,12 +206,13 @@ async fn post(
             }, email_str);
             password_errors =
                 Required::validated(translator_service, lang, &data.password, |value| {
-                    StrMinMaxCharsCount::validate(
+                    let email = data.email.as_deref().unwrap_or("");
+                    PasswordPolicy::validate(
                         translator_service,
                         lang,
+                        &config.auth.password,
                         value,
-                        4,
-                        255,
+                        &[email],
                         password_str,
                     )
                 }, password_str);
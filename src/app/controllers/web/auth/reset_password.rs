@@ -3,8 +3,8 @@ use crate::app::validator::rules::email::Email;
 use crate::app::validator::rules::required::Required;
 use crate::{RateLimitService, UserService};
 use crate::{
-    prepare_value, Alert, AppService, AuthService, EmailAddress, EmailMessage, MailService,
-    RandomService, TemplateService, TranslatorService, WebHttpResponse, RESET_PASSWORD_TTL,
+    prepare_value, Alert, AppService, AuthService, EmailAddress, EmailMessage, JobPayload,
+    JobService, RandomService, TemplateService, TranslatorService, WebHttpResponse, RESET_PASSWORD_TTL,
 };
 use actix_web::{web::{Data, Form}, error, Error, HttpRequest, HttpResponse, Result, http::Method};
 use serde_derive::Deserialize;
@@ -26,7 +26,7 @@ pub async fn show(
     tmpl_service: Data<TemplateService>,
     app_service: Data<AppService>,
     translator_service: Data<TranslatorService>,
-    mail_service: Data<MailService>,
+    job_service: Data<JobService>,
     auth_service: Data<AuthService>,
     user_service: Data<UserService>,
     random_service: Data<RandomService>,
@@ -38,7 +38,7 @@ pub async fn show(
         tmpl_service,
         app_service,
         translator_service,
-        mail_service,
+        job_service,
         auth_service,
         user_service,
         random_service,
@@ -53,7 +53,7 @@ pub async fn invoke(
     tmpl_service: Data<TemplateService>,
     app_service: Data<AppService>,
     translator_service: Data<TranslatorService>,
-    mail_service: Data<MailService>,
+    job_service: Data<JobService>,
     auth_service: Data<AuthService>,
     user_service: Data<UserService>,
     random_service: Data<RandomService>,
@@ -62,7 +62,7 @@ pub async fn invoke(
     let tmpl_service = tmpl_service.get_ref();
     let app_service = app_service.get_ref();
     let translator_service = translator_service.get_ref();
-    let mail_service = mail_service.get_ref();
+    let job_service = job_service.get_ref();
     let auth_service = auth_service.get_ref();
     let user_service = user_service.get_ref();
     let random_service = random_service.get_ref();
@@ -83,7 +83,7 @@ pub async fn invoke(
         &email_str,
         translator_service,
         auth_service,
-        mail_service,
+        job_service,
         tmpl_service,
         app_service,
         user_service,
@@ -142,7 +142,7 @@ async fn post(
     email_str: &String,
     translator_service: &TranslatorService,
     auth_service: &AuthService,
-    mail_service: &MailService,
+    job_service: &JobService,
     tmpl_service: &TemplateService,
     app_service: &AppService,
     user_service: &UserService,
@@ -231,9 +231,9 @@ async fn post(
                     ),
                     text_body: button_href,
                 };
-                let send_email_result = mail_service.send_email(&message);
+                let dispatch_result = job_service.dispatch(JobPayload::SendEmail(message));
 
-                if send_email_result.is_err() {
+                if dispatch_result.is_err() {
                     let email_str = translator_service.translate(lang, "alert.reset_password.fail");
                     form_errors.push(email_str);
                 } else {
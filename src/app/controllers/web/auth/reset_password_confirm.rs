@@ -1,12 +1,13 @@
 use crate::app::controllers::web::auth::reset_password::CODE_LEN;
 use crate::app::controllers::web::{get_public_context_data, get_public_template_context};
 use crate::app::middlewares::web_auth::REDIRECT_TO;
+use crate::app::validator::password_policy::PasswordPolicy;
 use crate::app::validator::rules::confirmed::Confirmed;
 use crate::app::validator::rules::email::Email;
 use crate::app::validator::rules::str_min_max_chars_count::StrMinMaxCharsCount;
 use crate::app::validator::rules::required::Required;
-use crate::{prepare_value, AlertVariant, RateLimitService, UserService, WebHttpResponse, RESET_PASSWORD_TTL};
-use crate::{AppService, AuthService, TemplateService, TranslatorService};
+use crate::{prepare_value, AlertVariant, RateLimitService, TranslatableError, UserService, UserServiceError, WebAuthService, WebHttpResponse, RESET_PASSWORD_TTL};
+use crate::{AppService, AuthService, Config, TemplateService, TranslatorService};
 use actix_web::web::{Data, Form, Query};
 use actix_web::{
     error,
@@ -44,6 +45,8 @@ pub async fn show(
     auth_service: Data<AuthService>,
     user_service: Data<UserService>,
     rate_limit_service: Data<RateLimitService>,
+    web_auth_service: Data<WebAuthService>,
+    config: Data<Config>,
 ) -> Result<HttpResponse, Error> {
     invoke(
         req,
@@ -60,6 +63,8 @@ pub async fn show(
         auth_service,
         user_service,
         rate_limit_service,
+        web_auth_service,
+        config,
     )
     .await
 }
@@ -74,6 +79,8 @@ pub async fn invoke(
     auth_service: Data<AuthService>,
     user_service: Data<UserService>,
     rate_limit_service: Data<RateLimitService>,
+    web_auth_service: Data<WebAuthService>,
+    config: Data<Config>,
 ) -> Result<HttpResponse, Error> {
     let tmpl_service = tmpl_service.get_ref();
     let app_service = app_service.get_ref();
@@ -81,6 +88,7 @@ pub async fn invoke(
     let auth_service = auth_service.get_ref();
     let user_service = user_service.get_ref();
     let rate_limit_service = rate_limit_service.get_ref();
+    let web_auth_service = web_auth_service.get_ref();
 
     let query = query.into_inner();
 
@@ -127,6 +135,8 @@ pub async fn invoke(
             auth_service,
             user_service,
             rate_limit_service,
+            web_auth_service,
+            config.get_ref(),
         )
         .await?;
 
@@ -210,6 +220,8 @@ async fn post(
     auth_service: &AuthService,
     user_service: &UserService,
     rate_limit_service: &RateLimitService,
+    web_auth_service: &WebAuthService,
+    config: &Config,
 ) -> Result<
     (
         bool,
@@ -250,12 +262,13 @@ async fn post(
                 lang,
                 &data.password,
                 |value| {
-                    StrMinMaxCharsCount::validate(
+                    let email = data.email.as_deref().unwrap_or("");
+                    PasswordPolicy::validate(
                         translator_service,
                         lang,
+                        &config.auth.password,
                         value,
-                        4,
-                        255,
+                        &[email],
                         &password_str,
                     )
                 },
@@ -321,14 +334,21 @@ async fn post(
                     .map_err(|_| error::ErrorInternalServerError(""))?;
 
                 if is_exists_code {
-                    user_service
-                        .update_password_by_email(email, password)
-                        .map_err(|_| error::ErrorInternalServerError(""))?;
-                    auth_service
-                        .delete_reset_password_code(email, code)
-                        .map_err(|_| error::ErrorInternalServerError(""))?;
-
-                    is_done1 = true;
+                    match user_service.update_password_by_email(email, password) {
+                        Ok(()) => {
+                            // Whoever knew the old password must not stay logged in.
+                            let user = user_service.first_by_email_throw_http(email)?;
+                            web_auth_service.revoke_all_sessions_throw_http(user.id, None)?;
+                            auth_service
+                                .delete_reset_password_code(email, code)
+                                .map_err(|_| error::ErrorInternalServerError(""))?;
+
+                            is_done1 = true;
+                        }
+                        Err(UserServiceError::PasswordReused) => password_errors
+                            .push(UserServiceError::PasswordReused.translate(lang, translator_service)),
+                        Err(_) => return Err(error::ErrorInternalServerError("")),
+                    }
                 } else {
                     code_errors.push("Reset password code not exists.".to_string());
                 }
@@ -0,0 +1,199 @@
+use crate::app::controllers::web::{get_public_context_data, get_public_template_context};
+use crate::{
+    AlertVariant, AppService, EmailVerificationService, EmailVerificationServiceError,
+    LocaleService, RateLimitService, Session, TemplateService, TranslatableError,
+    TranslatorService, User, WebAuthService, WebHttpResponse, EMAIL_VERIFICATION_URL,
+};
+use actix_web::web::{Data, Form, Query, ReqData};
+use actix_web::{error, Error, HttpRequest, HttpResponse, Result, http::header::{HeaderValue, LOCATION}};
+use serde_derive::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+
+const RL_MAX_ATTEMPTS: u64 = 3;
+const RL_TTL: u64 = 300;
+const RL_KEY: &'static str = "verify_email_resend";
+
+#[derive(Deserialize, Debug)]
+pub struct VerifyEmailQuery {
+    pub id: Option<u64>,
+    pub expires: Option<i64>,
+    pub signature: Option<String>,
+}
+
+#[derive(Deserialize, Default, Debug)]
+pub struct ResendData {
+    pub _token: Option<String>,
+}
+
+/// The link from the letter, it works without the session.
+pub async fn invoke(
+    req: HttpRequest,
+    query: Query<VerifyEmailQuery>,
+    tmpl_service: Data<TemplateService>,
+    app_service: Data<AppService>,
+    translator_service: Data<TranslatorService>,
+    email_verification_service: Data<EmailVerificationService>,
+) -> Result<HttpResponse, Error> {
+    let tmpl_service = tmpl_service.get_ref();
+    let app_service = app_service.get_ref();
+    let translator_service = translator_service.get_ref();
+    let email_verification_service = email_verification_service.get_ref();
+
+    let mut context_data = get_public_context_data(&req, translator_service, app_service);
+    let lang = &context_data.lang;
+
+    let result = match (&query.id, &query.expires, &query.signature) {
+        (Some(id), Some(expires), Some(signature)) => {
+            email_verification_service.verify(*id, *expires, signature)
+        }
+        _ => Err(EmailVerificationServiceError::InvalidSignature),
+    };
+
+    let error = match result {
+        Ok(_) => {
+            return Ok(HttpResponse::SeeOther()
+                .set_alerts(vec![AlertVariant::VerifyEmailSuccess])
+                .insert_header((LOCATION, HeaderValue::from_static("/")))
+                .finish());
+        }
+        Err(e) => e.translate(lang, translator_service),
+    };
+
+    context_data.title = translator_service.translate(lang, "page.verify_email.title");
+    let layout_ctx = get_public_template_context(&context_data);
+    let ctx = json!({
+        "ctx": layout_ctx,
+        "heading": translator_service.translate(lang, "page.verify_email.header"),
+        "back": {
+            "label": translator_service.translate(lang, "page.verify_email.back"),
+            "href": "/",
+        },
+        "form": {
+            "errors": [error],
+        },
+    });
+
+    let s = tmpl_service.render_throw_http("pages/auth.hbs", &ctx)?;
+    Ok(HttpResponse::Ok()
+        .clear_alerts()
+        .content_type(mime::TEXT_HTML_UTF_8.as_ref())
+        .body(s))
+}
+
+/// The "verify your email" page, the only page of the unverified users.
+pub async fn notice(
+    req: HttpRequest,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    tmpl_service: Data<TemplateService>,
+    app_service: Data<AppService>,
+    translator_service: Data<TranslatorService>,
+    web_auth_service: Data<WebAuthService>,
+) -> Result<HttpResponse, Error> {
+    let tmpl_service = tmpl_service.get_ref();
+    let app_service = app_service.get_ref();
+    let translator_service = translator_service.get_ref();
+    let web_auth_service = web_auth_service.get_ref();
+
+    if user.is_email_verified() {
+        return Ok(HttpResponse::SeeOther()
+            .insert_header((LOCATION, HeaderValue::from_static("/")))
+            .finish());
+    }
+
+    let mut context_data = get_public_context_data(&req, translator_service, app_service);
+    let lang = &context_data.lang;
+    context_data.title = translator_service.translate(lang, "page.verify_email.title");
+
+    let layout_ctx = get_public_template_context(&context_data);
+    let ctx = json!({
+        "ctx": layout_ctx,
+        "heading": translator_service.translate(lang, "page.verify_email.header"),
+        "form": {
+            "action": get_resend_url(),
+            "method": "post",
+            "csrf": web_auth_service.new_csrf(&session),
+            "logout": {
+                "label": translator_service.translate(lang, "page.verify_email.logout"),
+                "action": "/logout",
+            },
+            "submit": {
+                "label": translator_service.translate(lang, "page.verify_email.resend"),
+                "text": translator_service.translate(lang, "page.verify_email.text"),
+            },
+        },
+    });
+
+    let s = tmpl_service.render_throw_http("pages/auth.hbs", &ctx)?;
+    Ok(HttpResponse::Ok()
+        .clear_alerts()
+        .content_type(mime::TEXT_HTML_UTF_8.as_ref())
+        .body(s))
+}
+
+pub async fn resend(
+    req: HttpRequest,
+    data: Form<ResendData>,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    locale_service: Data<LocaleService>,
+    translator_service: Data<TranslatorService>,
+    web_auth_service: Data<WebAuthService>,
+    rate_limit_service: Data<RateLimitService>,
+    email_verification_service: Data<EmailVerificationService>,
+) -> Result<HttpResponse, Error> {
+    let locale_service = locale_service.get_ref();
+    let translator_service = translator_service.get_ref();
+    let web_auth_service = web_auth_service.get_ref();
+    let rate_limit_service = rate_limit_service.get_ref();
+    let email_verification_service = email_verification_service.get_ref();
+
+    web_auth_service.check_csrf_throw_http(&session, &data._token)?;
+
+    let user = user.as_ref();
+    let lang: String = locale_service.get_locale_code(Some(&req), Some(user));
+    let rate_limit_key = rate_limit_service.make_key_from_request_throw_http(&req, RL_KEY)?;
+
+    let mut alert_variants = Vec::new();
+    let executed =
+        rate_limit_service.attempt_throw_http(&rate_limit_key, RL_MAX_ATTEMPTS, RL_TTL)?;
+
+    if executed {
+        if !user.is_email_verified() {
+            email_verification_service.send_throw_http(user, &lang)?;
+        }
+        alert_variants.push(AlertVariant::VerifyEmailSent);
+    } else {
+        let alert_variant = rate_limit_service.alert_variant_throw_http(
+            translator_service,
+            &lang,
+            &rate_limit_key,
+        )?;
+        alert_variants.push(alert_variant);
+    }
+
+    Ok(HttpResponse::SeeOther()
+        .set_alerts(alert_variants)
+        .insert_header((
+            LOCATION,
+            HeaderValue::from_str(&get_notice_url()).map_err(|_| error::ErrorInternalServerError(""))?,
+        ))
+        .finish())
+}
+
+pub fn get_url() -> String {
+    EMAIL_VERIFICATION_URL.to_string()
+}
+
+pub fn get_notice_url() -> String {
+    let mut str_ = get_url();
+    str_.push_str("/notice");
+    str_
+}
+
+pub fn get_resend_url() -> String {
+    let mut str_ = get_url();
+    str_.push_str("/resend");
+    str_
+}
@@ -25,6 +25,14 @@ pub fn default_error_handler<B>(
     }
 
     let path = req.uri().path();
+
+    // API controllers may already respond with a structured JSON error (e.g. validation errors),
+    // such a body is passed through as is.
+    if path.starts_with("/api") && res.error().is_none() && is_json_response(&res) {
+        let res = ServiceResponse::new(req, res).map_into_left_body();
+        return Ok(ErrorHandlerResponse::Response(res));
+    }
+
     let res = if path.starts_with("/storage") || path.starts_with("/css") || path.starts_with("/js") || path.starts_with("/svg") {
         res.set_body("".to_string())
     } else {
@@ -64,14 +72,21 @@ fn get_error_json_response<B>(mut response: HttpResponse<B>) -> HttpResponse<Str
     );
 
     let error_message: String = get_error_message(&response);
-    let status_code: String = response.status().as_str().to_owned();
 
-    let mut json = "{\"status_code\":".to_string();
-    json.push_str(&status_code);
-    json.push_str(", \"error_message\":\"");
-    json.push_str(&error_message);
-    json.push_str("\"}");
-    response.set_body(json)
+    let json = json!({
+        "status_code": response.status().as_u16(),
+        "error_message": error_message,
+    });
+    response.set_body(json.to_string())
+}
+
+fn is_json_response<B>(response: &HttpResponse<B>) -> bool {
+    response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.starts_with(mime::APPLICATION_JSON.as_ref()))
+        .unwrap_or(false)
 }
 
 fn get_error_html_response<B>(
@@ -0,0 +1,161 @@
+use crate::{
+    AlertVariant, Config, Disk, ExportFormat, ExportParams, ExportService, FileService,
+    TranslatorService, User, WebHttpResponse,
+};
+use actix_files::NamedFile;
+use actix_web::http::header::{
+    ContentDisposition, DispositionParam, DispositionType, HeaderValue, LOCATION, ORIGIN, REFERER,
+};
+use actix_web::web::{Data, Path, ReqData};
+use actix_web::{error, Error, HttpRequest, HttpResponse, Result};
+use serde_derive::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use strum::IntoEnumIterator;
+
+#[derive(Deserialize, Debug)]
+pub struct ExportQuery {
+    pub format: Option<String>,
+}
+
+impl ExportQuery {
+    pub fn get_format(&self) -> Result<ExportFormat, Error> {
+        let format = self.format.as_deref().unwrap_or("csv");
+        ExportFormat::from_str(format).map_err(|_| error::ErrorBadRequest(""))
+    }
+}
+
+#[derive(Deserialize, Default, Debug)]
+pub struct PostData {
+    pub _token: Option<String>,
+    pub selected: Option<Vec<u64>>,
+}
+
+impl PostData {
+    pub fn get_selected(&self) -> Result<Vec<u64>, Error> {
+        match &self.selected {
+            Some(ids) if !ids.is_empty() => Ok(ids.to_owned()),
+            _ => Err(error::ErrorBadRequest("")),
+        }
+    }
+}
+
+pub fn get_download_url(id: &str) -> String {
+    format!("{}/{}", crate::EXPORT_DOWNLOAD_URL, id)
+}
+
+/// The small export is returned as the attachment, the large one is queued and the user is sent back to the list.
+pub fn respond(
+    req: &HttpRequest,
+    user: &User,
+    params: ExportParams,
+    export_service: &ExportService,
+    default_location: &'static str,
+) -> Result<HttpResponse, Error> {
+    let count = export_service.count_throw_http(&params)?;
+
+    if export_service.is_background(count) {
+        export_service.dispatch_throw_http(user, params)?;
+
+        let headers = req.headers();
+        let default = HeaderValue::from_static(default_location);
+        let location = headers
+            .get(REFERER)
+            .unwrap_or(headers.get(ORIGIN).unwrap_or(&default));
+        let location = location.to_str().unwrap_or(default_location);
+
+        return Ok(HttpResponse::SeeOther()
+            .set_alerts(vec![AlertVariant::ExportQueued(count.to_string())])
+            .insert_header((
+                LOCATION,
+                HeaderValue::from_str(location).unwrap_or(default),
+            ))
+            .finish());
+    }
+
+    let path = export_service.write_to_temp_file_throw_http(&params)?;
+    let file = std::fs::File::open(&path);
+    // The opened file is read to the end after its path is removed.
+    export_service.delete_temp_file(&path);
+    let file = file?;
+
+    let mime = mime::Mime::from_str(params.format.mime())
+        .map_err(|_| error::ErrorInternalServerError(""))?;
+    let filename = export_service.make_filename(&params);
+    Ok(NamedFile::from_file(file, &filename)?
+        .set_content_type(mime)
+        .set_content_disposition(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(filename)],
+        })
+        .into_response(req))
+}
+
+/// The export made in the background, it is available to its owner only.
+pub async fn download(
+    req: HttpRequest,
+    config: Data<Config>,
+    path: Path<u64>,
+    user: ReqData<Arc<User>>,
+    export_service: Data<ExportService>,
+    file_service: Data<FileService>,
+) -> Result<HttpResponse, Error> {
+    let user = user.as_ref();
+    let (user_file, file) = export_service
+        .get_ref()
+        .first_download_throw_http(user, path.into_inner())?;
+
+    let disk = Disk::from_str(&file.disk).map_err(|_| error::ErrorInternalServerError(""))?;
+    match disk {
+        Disk::Local => {
+            let filename = user_file.upload_filename.unwrap_or(file.filename);
+            Ok(NamedFile::open(&file.path)?
+                .set_content_disposition(ContentDisposition {
+                    disposition: DispositionType::Attachment,
+                    parameters: vec![DispositionParam::Filename(filename)],
+                })
+                .into_response(&req))
+        }
+        Disk::S3 => {
+            let url = file_service
+                .get_ref()
+                .get_s3_repository()
+                .presigned_url(&file.path, config.filesystem.disks.s3.presigned_expires)
+                .map_err(|_| error::ErrorInternalServerError(""))?;
+            Ok(HttpResponse::TemporaryRedirect()
+                .insert_header((LOCATION, url))
+                .finish())
+        }
+    }
+}
+
+/// The export links of the list page: the whole filtered list and the selected records.
+pub fn get_template_context(
+    translator_service: &TranslatorService,
+    lang: &str,
+    export_url: &str,
+    selected_url: &str,
+) -> Value {
+    let mut options: Vec<Value> = Vec::new();
+    let mut selected: Vec<Value> = Vec::new();
+    for format in ExportFormat::iter() {
+        let label = translator_service.translate(lang, &format!("page.export.{}", format));
+        options.push(json!({
+            "label": &label,
+            "href": format!("{}&format={}", export_url, format),
+        }));
+        let mut vars: HashMap<&str, &str> = HashMap::new();
+        vars.insert("format", &label);
+        selected.push(json!({
+            "label": translator_service.variables(lang, "page.export.selected", &vars),
+            "action": format!("{}?format={}", selected_url, format),
+        }));
+    }
+    json!({
+        "label": translator_service.translate(lang, "page.export.label"),
+        "options": options,
+        "selected": selected,
+    })
+}
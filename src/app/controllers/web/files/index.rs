@@ -1,6 +1,6 @@
 use crate::app::controllers::web::files::upload::get_upload_url;
 use crate::app::controllers::web::{
-    generate_2_offset_pagination_array, get_context_data, get_template_context,
+    exports, generate_2_offset_pagination_array, get_context_data, get_template_context,
 };
 use crate::{prepare_paginate, prepare_value, validation_query_max_length_string, Alert, AppService, Config, FileFilter, FilePaginateParams, FilePolicy, FileService, FileSort, LocaleService, RoleService, Session, TemplateService, TranslatorService, User, WebAuthService, WebHttpResponse};
 use actix_web::web::{Data, Query, ReqData};
@@ -14,6 +14,7 @@ use std::sync::Arc;
 use strum::IntoEnumIterator;
 
 const PAGE_URL: &'static str = "/files?";
+const EXPORT_URL: &'static str = "/files/export?";
 
 pub const DEFAULT_PER_PAGE: i64 = 15;
 pub const MAX_PER_PAGE: i64 = 100;
@@ -51,7 +52,7 @@ pub async fn invoke(
     let user = user.as_ref();
 
     let user_roles = role_service.all_throw_http()?;
-    if !FilePolicy::can_show(&user, &user_roles) {
+    if !FilePolicy::can_show_any_or_own(user, &user_roles) {
         return Err(error::ErrorForbidden(""));
     }
 
@@ -69,15 +70,21 @@ pub async fn invoke(
     let page = query.page.unwrap();
     let per_page = query.per_page.unwrap();
     let page_str = page.to_string();
-    let filters: Vec<FileFilter> = query.get_filters();
+    let mut filters: Vec<FileFilter> = query.get_filters();
+    filters.extend(FilePolicy::file_filters(user, &user_roles));
     let sorts: Vec<FileSort> = query.get_sorts();
     let pagination_params = FilePaginateParams::new(page, per_page, filters, sorts);
     let mut files = file_service.paginate_throw_http(&pagination_params)?;
     let total_pages = max(files.total_pages, 1);
     let total_pages_str = total_pages.to_string();
 
+    // The owners of the shared files are hidden from the users seeing only their own files.
+    let user_files_filters = FilePolicy::user_file_filters(user, &user_roles);
     file_service
-        .load_and_attach_user_files(&mut files.records, None, None)
+        .load_and_attach_user_files(&mut files.records, Some(&user_files_filters), None)
+        .map_err(|_| error::ErrorInternalServerError(""))?;
+    file_service
+        .load_and_attach_variants(&mut files.records)
         .map_err(|_| error::ErrorInternalServerError(""))?;
 
     let mut context_data = get_context_data(
@@ -127,7 +134,9 @@ pub async fn invoke(
         sort_options.push(json!({ "label": label, "value": value }));
     }
 
-    let mut selected: Option<Value> = None;
+    let mut selected: Value = json!({
+        "label": translator_service.translate(lang, "Selected"),
+    });
     let mut create: Option<Value> = None;
     let mut edit: Option<Value> = None;
     let mut delete: Option<Value> = None;
@@ -147,13 +156,10 @@ pub async fn invoke(
     }
 
     if FilePolicy::can_delete(&user, &user_roles) {
-        selected = Some(json!({
-            "label": translator_service.translate(lang, "Selected"),
-            "delete": translator_service.translate(lang, "Delete selected"),
-            "delete_confirm": translator_service.translate(lang, "Delete selected?"),
-            "restore": translator_service.translate(lang, "Restore selected"),
-            "restore_confirm": translator_service.translate(lang, "Restore selected?"),
-        }));
+        selected["delete"] = json!(translator_service.translate(lang, "Delete selected"));
+        selected["delete_confirm"] = json!(translator_service.translate(lang, "Delete selected?"));
+        selected["restore"] = json!(translator_service.translate(lang, "Restore selected"));
+        selected["restore_confirm"] = json!(translator_service.translate(lang, "Restore selected?"));
         delete = Some(json!({
             "action": "/files/:id/delete",
             "method": "post",
@@ -182,6 +188,17 @@ pub async fn invoke(
             "options": &sort_options
         },
         "selected": selected,
+        // The export is not scoped by the owner, so it is given only with "files_show".
+        "export": if FilePolicy::can_show(user, &user_roles) {
+            Some(exports::get_template_context(
+                translator_service,
+                lang,
+                &query.clone().remove_page().remove_per_page().to_export_url()?,
+                "/files/export",
+            ))
+        } else {
+            None
+        },
         "columns": {
             "id": translator_service.translate(lang, "page.files.index.columns.id"),
             "filename": translator_service.translate(lang, "page.files.index.columns.filename"),
@@ -290,6 +307,15 @@ impl IndexQuery {
         result.push_str(&url);
         Ok(result)
     }
+    pub fn to_export_url(&self) -> Result<String, Error> {
+        let url = serde_urlencoded::to_string(self).map_err(|e| {
+            log::error!("app::controllers::web::files::index::IndexQuery::to_export_url - {e}");
+            error::ErrorInternalServerError("")
+        })?;
+        let mut result = EXPORT_URL.to_string();
+        result.push_str(&url);
+        Ok(result)
+    }
     pub fn get_filters(&self) -> Vec<FileFilter> {
         let mut filters: Vec<FileFilter> = Vec::new();
 
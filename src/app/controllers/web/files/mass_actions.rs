@@ -1,7 +1,7 @@
 use crate::helpers::join_vec;
 use crate::libs::actix_web::types::form::Form;
 use crate::{
-    AlertVariant, FilePolicy, FileService, LocaleService, RateLimitService, RoleService, Session,
+    AlertVariant, AuditLogAction, AuditLogEntity, AuditLogService, FilePolicy, FileService, LocaleService, RateLimitService, RoleService, Session,
     TranslatorService, User, WebAuthService, WebHttpResponse,
 };
 use actix_web::{
@@ -35,12 +35,14 @@ pub async fn invoke(
     web_auth_service: Data<WebAuthService>,
     rate_limit_service: Data<RateLimitService>,
     translator_service: Data<TranslatorService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     let web_auth_service = web_auth_service.get_ref();
     let rate_limit_service = rate_limit_service.get_ref();
     let locale_service = locale_service.get_ref();
     let role_service = role_service.get_ref();
     let file_service = file_service.get_ref();
+    let audit_log_service = audit_log_service.get_ref();
     let translator_service = translator_service.get_ref();
 
     web_auth_service.check_csrf_throw_http(&session, &data._token)?;
@@ -65,13 +67,37 @@ pub async fn invoke(
                     if !FilePolicy::can_delete(&user, &user_roles) {
                         return Err(error::ErrorForbidden(""));
                     }
+                    let files = file_service.all_by_ids_throw_http(ids)?;
                     file_service.soft_delete_by_ids_throw_http(ids)?;
+                    for file in &files {
+                        audit_log_service.record_throw_http(
+                            &req,
+                            user,
+                            AuditLogAction::Delete,
+                            AuditLogEntity::File,
+                            file.id,
+                            AuditLogService::to_value(file),
+                            None,
+                        )?;
+                    }
                     alert_variants.push(AlertVariant::FilesMassDeleteSuccess(join_vec(ids, ", ")));
                 } else if action.eq("restore") {
                     if !FilePolicy::can_delete(&user, &user_roles) {
                         return Err(error::ErrorForbidden(""));
                     }
+                    let files = file_service.all_by_ids_throw_http(ids)?;
                     file_service.restore_by_ids_throw_http(ids)?;
+                    for file in &files {
+                        audit_log_service.record_throw_http(
+                            &req,
+                            user,
+                            AuditLogAction::Restore,
+                            AuditLogEntity::File,
+                            file.id,
+                            None,
+                            AuditLogService::to_value(file),
+                        )?;
+                    }
                     alert_variants.push(AlertVariant::FilesMassRestoreSuccess(join_vec(ids, ", ")));
                 }
             }
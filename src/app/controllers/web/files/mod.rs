@@ -2,4 +2,6 @@ pub mod delete;
 pub mod index;
 pub mod mass_actions;
 pub mod restore;
+pub mod resumable;
 pub mod upload;
+pub mod export;
@@ -1,5 +1,5 @@
 use crate::{
-    AlertVariant, FilePolicy, FileService, LocaleService, RateLimitService, RoleService, Session,
+    AlertVariant, AuditLogAction, AuditLogEntity, AuditLogService, FilePolicy, FileService, LocaleService, RateLimitService, RoleService, Session,
     TranslatorService, User, WebAuthService, WebHttpResponse,
 };
 use actix_web::{web::{Data, Form, Path, ReqData}, error, Error, HttpRequest, HttpResponse, Result, http::header::{ORIGIN, REFERER, LOCATION}, http::header::HeaderValue};
@@ -27,12 +27,14 @@ pub async fn invoke(
     web_auth_service: Data<WebAuthService>,
     rate_limit_service: Data<RateLimitService>,
     translator_service: Data<TranslatorService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     let web_auth_service = web_auth_service.get_ref();
     let rate_limit_service = rate_limit_service.get_ref();
     let locale_service = locale_service.get_ref();
     let role_service = role_service.get_ref();
     let file_service = file_service.get_ref();
+    let audit_log_service = audit_log_service.get_ref();
     let translator_service = translator_service.get_ref();
 
     web_auth_service.check_csrf_throw_http(&session, &data._token)?;
@@ -54,12 +56,21 @@ pub async fn invoke(
         rate_limit_service.attempt_throw_http(&rate_limit_key, RL_MAX_ATTEMPTS, RL_TTL)?;
 
     if executed {
-        let name = delete_file.filename;
+        let name = delete_file.filename.to_owned();
         if delete_file.is_delete {
             if delete_file.is_deleted {
                 alert_variants.push(AlertVariant::FilesNonRecoverableWarning(name));
             } else {
                 file_service.restore_by_id_throw_http(delete_file.id)?;
+                audit_log_service.record_throw_http(
+                    &req,
+                    user,
+                    AuditLogAction::Restore,
+                    AuditLogEntity::File,
+                    delete_file.id,
+                    None,
+                    AuditLogService::to_value(&delete_file),
+                )?;
                 alert_variants.push(AlertVariant::FilesRestoreSuccess(name));
             }
         }
@@ -0,0 +1,351 @@
+use crate::app::controllers::api::v1::rate_limit_throw_http;
+use crate::app::controllers::web::files::upload::get_upload_url;
+use crate::{
+    AlertVariant, AuditLogAction, AuditLogEntity, AuditLogService, FilePolicy, FileService,
+    LocaleService, RateLimitService, ResumableUpload, ResumableUploadService,
+    ResumableUploadServiceError, RoleService, Session, TranslatorService, User, WebAuthService,
+    WebHttpResponse,
+};
+use actix_web::http::header::{HeaderValue, CACHE_CONTROL, CONTENT_TYPE, LOCATION};
+use actix_web::web::{Data, Path, Payload, ReqData};
+use actix_web::{error, Error, HttpRequest, HttpResponse, Result};
+use base64_stream::FromBase64Reader;
+use futures_util::StreamExt;
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Write};
+use std::sync::Arc;
+
+const RL_MAX_ATTEMPTS: u64 = 60;
+const RL_TTL: u64 = 60;
+const RL_KEY: &'static str = "files_upload_resumable";
+
+// The requests follow the tus protocol (https://tus.io/protocols/resumable-upload), the finalize step is added on top.
+const TUS_RESUMABLE: &'static str = "1.0.0";
+const TUS_CONTENT_TYPE: &'static str = "application/offset+octet-stream";
+const CSRF_HEADER: &'static str = "X-CSRF-Token";
+
+pub async fn create(
+    req: HttpRequest,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    translator_service: Data<TranslatorService>,
+    locale_service: Data<LocaleService>,
+    web_auth_service: Data<WebAuthService>,
+    rate_limit_service: Data<RateLimitService>,
+    role_service: Data<RoleService>,
+    file_service: Data<FileService>,
+    resumable_upload_service: Data<ResumableUploadService>,
+) -> Result<HttpResponse, Error> {
+    let user = user.as_ref();
+    let user_roles = role_service.all_throw_http()?;
+    if !FilePolicy::can_create(user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+    check_csrf(&req, &session, web_auth_service.get_ref())?;
+
+    let lang: String = locale_service.get_locale_code(Some(&req), Some(user));
+    rate_limit_throw_http(
+        &req,
+        rate_limit_service.get_ref(),
+        translator_service.get_ref(),
+        &lang,
+        RL_KEY,
+        RL_MAX_ATTEMPTS,
+        RL_TTL,
+    )?;
+
+    let length = get_header_u64(&req, "Upload-Length").ok_or(error::ErrorBadRequest(""))?;
+    let metadata = get_metadata(&req);
+    let filename = metadata.get("filename").cloned();
+    let mime = metadata.get("filetype").cloned();
+    let is_public = metadata
+        .get("is_public")
+        .map(|value| value.eq("1") || value.eq("true"))
+        .unwrap_or(false);
+
+    let max_size = file_service.get_upload_max_size(user, &user_roles);
+    let upload = resumable_upload_service.create_throw_http(
+        user.id, length, max_size, filename, mime, is_public,
+    )?;
+
+    let location = get_resumable_url(&upload.id);
+    Ok(HttpResponse::Created()
+        .insert_header(("Tus-Resumable", TUS_RESUMABLE))
+        .insert_header(("Upload-Offset", upload.offset.to_string()))
+        .insert_header((
+            LOCATION,
+            HeaderValue::from_str(&location).map_err(|_| error::ErrorInternalServerError(""))?,
+        ))
+        .finish())
+}
+
+pub async fn head(
+    path: Path<String>,
+    user: ReqData<Arc<User>>,
+    role_service: Data<RoleService>,
+    resumable_upload_service: Data<ResumableUploadService>,
+) -> Result<HttpResponse, Error> {
+    let user = user.as_ref();
+    let user_roles = role_service.all_throw_http()?;
+    if !FilePolicy::can_create(user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+
+    let upload = resumable_upload_service.first_throw_http(user.id, &path.into_inner())?;
+
+    Ok(HttpResponse::Ok()
+        .insert_header(("Tus-Resumable", TUS_RESUMABLE))
+        .insert_header(("Upload-Offset", upload.offset.to_string()))
+        .insert_header(("Upload-Length", upload.length.to_string()))
+        .insert_header((CACHE_CONTROL, "no-store"))
+        .finish())
+}
+
+pub async fn patch(
+    req: HttpRequest,
+    path: Path<String>,
+    mut payload: Payload,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    web_auth_service: Data<WebAuthService>,
+    role_service: Data<RoleService>,
+    resumable_upload_service: Data<ResumableUploadService>,
+) -> Result<HttpResponse, Error> {
+    let resumable_upload_service = resumable_upload_service.get_ref();
+    let user = user.as_ref();
+    let user_roles = role_service.all_throw_http()?;
+    if !FilePolicy::can_create(user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+    check_csrf(&req, &session, web_auth_service.get_ref())?;
+
+    let content_type = req
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("");
+    if !content_type.eq(TUS_CONTENT_TYPE) {
+        return Err(error::ErrorUnsupportedMediaType(""));
+    }
+    let offset = get_header_u64(&req, "Upload-Offset").ok_or(error::ErrorBadRequest(""))?;
+
+    let mut upload = resumable_upload_service.first_throw_http(user.id, &path.into_inner())?;
+    resumable_upload_service
+        .lock(&upload)
+        .map_err(|e| e.to_http())?;
+
+    let result = write_chunk(resumable_upload_service, &mut upload, offset, &mut payload).await;
+
+    resumable_upload_service
+        .unlock(&upload)
+        .map_err(|e| e.to_http())?;
+    result.map_err(|e| e.to_http())?;
+
+    Ok(HttpResponse::NoContent()
+        .insert_header(("Tus-Resumable", TUS_RESUMABLE))
+        .insert_header(("Upload-Offset", upload.offset.to_string()))
+        .finish())
+}
+
+// The received part of the chunk is saved even if the connection is dropped, so the client can resume from it.
+async fn write_chunk(
+    resumable_upload_service: &ResumableUploadService,
+    upload: &mut ResumableUpload,
+    offset: u64,
+    payload: &mut Payload,
+) -> Result<(), ResumableUploadServiceError> {
+    let mut write_stream = resumable_upload_service.append_stream(upload, offset)?;
+    let mut new_offset = offset;
+    let mut result: Result<(), ResumableUploadServiceError> = Ok(());
+
+    while let Some(chunk) = payload.next().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(_) => break,
+        };
+        if new_offset + chunk.len() as u64 > upload.length {
+            result = Err(ResumableUploadServiceError::TooLarge);
+            break;
+        }
+        if let Err(e) = write_stream.write_all(&chunk) {
+            log::error!("files::resumable::write_chunk - {e}");
+            result = Err(ResumableUploadServiceError::Fail);
+            break;
+        }
+        new_offset += chunk.len() as u64;
+    }
+
+    if let Err(e) = write_stream.flush() {
+        log::error!("files::resumable::write_chunk - {e}");
+        return Err(ResumableUploadServiceError::Fail);
+    }
+    drop(write_stream);
+
+    resumable_upload_service.set_offset(upload, new_offset)?;
+    result
+}
+
+pub async fn finalize(
+    req: HttpRequest,
+    path: Path<String>,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    web_auth_service: Data<WebAuthService>,
+    role_service: Data<RoleService>,
+    resumable_upload_service: Data<ResumableUploadService>,
+    audit_log_service: Data<AuditLogService>,
+) -> Result<HttpResponse, Error> {
+    let resumable_upload_service = resumable_upload_service.get_ref();
+    let user = user.as_ref();
+    let user_roles = role_service.all_throw_http()?;
+    if !FilePolicy::can_create(user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+    check_csrf(&req, &session, web_auth_service.get_ref())?;
+
+    let upload = resumable_upload_service.first_throw_http(user.id, &path.into_inner())?;
+    resumable_upload_service
+        .lock(&upload)
+        .map_err(|e| e.to_http())?;
+
+    let result = resumable_upload_service.finalize_throw_http(&upload);
+
+    resumable_upload_service
+        .unlock(&upload)
+        .map_err(|e| e.to_http())?;
+    let user_file = result?;
+
+    audit_log_service.record_throw_http(
+        &req,
+        user,
+        AuditLogAction::Create,
+        AuditLogEntity::UserFile,
+        user_file.id,
+        None,
+        AuditLogService::to_value(&user_file),
+    )?;
+
+    let mut alert_variants: Vec<AlertVariant> = Vec::new();
+    if let Some(upload_filename) = &user_file.upload_filename {
+        alert_variants.push(AlertVariant::FilesCreateSuccess(upload_filename.to_owned()));
+    }
+
+    Ok(HttpResponse::Created()
+        .set_alerts(alert_variants)
+        .json(user_file))
+}
+
+pub async fn delete(
+    req: HttpRequest,
+    path: Path<String>,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    web_auth_service: Data<WebAuthService>,
+    role_service: Data<RoleService>,
+    resumable_upload_service: Data<ResumableUploadService>,
+) -> Result<HttpResponse, Error> {
+    let resumable_upload_service = resumable_upload_service.get_ref();
+    let user = user.as_ref();
+    let user_roles = role_service.all_throw_http()?;
+    if !FilePolicy::can_create(user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+    check_csrf(&req, &session, web_auth_service.get_ref())?;
+
+    let upload = resumable_upload_service.first_throw_http(user.id, &path.into_inner())?;
+    resumable_upload_service.delete_throw_http(&upload)?;
+
+    Ok(HttpResponse::NoContent()
+        .insert_header(("Tus-Resumable", TUS_RESUMABLE))
+        .finish())
+}
+
+// The requests are sent by the script of the upload page, the csrf token is passed in the header.
+fn check_csrf(
+    req: &HttpRequest,
+    session: &Session,
+    web_auth_service: &WebAuthService,
+) -> Result<bool, Error> {
+    let token: Option<String> = req
+        .headers()
+        .get(CSRF_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string());
+    web_auth_service.check_csrf_throw_http(session, &token)
+}
+
+fn get_header_u64(req: &HttpRequest, name: &str) -> Option<u64> {
+    req.headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+}
+
+// Upload-Metadata: [key] [base64 value],[key] [base64 value]
+fn get_metadata(req: &HttpRequest) -> HashMap<String, String> {
+    let value = req
+        .headers()
+        .get("Upload-Metadata")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("");
+    parse_metadata(value)
+}
+
+fn parse_metadata(value: &str) -> HashMap<String, String> {
+    let mut result: HashMap<String, String> = HashMap::new();
+    for pair in value.split(',') {
+        let mut parts = pair.trim().splitn(2, ' ');
+        let key = parts.next().unwrap_or("").trim();
+        if key.is_empty() {
+            continue;
+        }
+        let value = parts.next().unwrap_or("").trim();
+        let mut decoded = String::new();
+        if FromBase64Reader::new(Cursor::new(value))
+            .read_to_string(&mut decoded)
+            .is_ok()
+        {
+            let value = decoded;
+            let value = value.trim().to_string();
+            if !value.is_empty() {
+                result.insert(key.to_string(), value);
+            }
+        }
+    }
+    result
+}
+
+pub fn get_resumable_create_url() -> String {
+    let mut str_ = get_upload_url();
+    str_.push_str("/resumable");
+    str_
+}
+
+pub fn get_resumable_url(id: &str) -> String {
+    let mut str_ = get_resumable_create_url();
+    str_.push('/');
+    str_.push_str(id);
+    str_
+}
+
+pub fn get_resumable_finalize_url(id: &str) -> String {
+    let mut str_ = get_resumable_url(id);
+    str_.push_str("/finalize");
+    str_
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_metadata() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::controllers::web::files::resumable::tests::test_parse_metadata
+        let metadata = parse_metadata("filename YXJjaGl2ZS50YXIuZ3o=,filetype YXBwbGljYXRpb24vZ3ppcA==, is_public MQ==,empty");
+        assert_eq!(metadata.get("filename").unwrap(), "archive.tar.gz");
+        assert_eq!(metadata.get("filetype").unwrap(), "application/gzip");
+        assert_eq!(metadata.get("is_public").unwrap(), "1");
+        assert!(!metadata.contains_key("empty"));
+        assert!(parse_metadata("").is_empty());
+    }
+}
@@ -1,12 +1,12 @@
+use crate::app::controllers::web::files::resumable::get_resumable_create_url;
 use crate::app::controllers::web::{get_context_data, get_template_context};
+use crate::app::validator::rules::bytes_mut_max_length::BytesMutMaxLength;
 use crate::{
-    prepare_upload_text_value, Alert, AlertVariant, AppService, FilePolicy, FileService,
-    FileServiceError, RateLimitService, Role, RoleService, Session, TemplateService,
+    assign_value_bytes_to_string, Alert, AlertVariant, AppService, AuditLogAction,
+    AuditLogEntity, AuditLogService, FilePolicy, FileService, FileServiceError,
+    FileUploadStream, LocaleService, RateLimitService, Role, RoleService, Session, TemplateService,
     TranslatableError, TranslatorService, User, UserFile, WebAuthService, WebHttpResponse,
 };
-use actix_multipart::form::tempfile::TempFile;
-use actix_multipart::form::text::Text;
-use actix_multipart::form::MultipartForm;
 use actix_web::http::header::HeaderValue;
 use actix_web::{
     error,
@@ -16,17 +16,26 @@ use actix_web::{
 };
 use serde::Deserialize;
 use serde_json::json;
+use std::collections::HashMap;
 use std::sync::Arc;
 use actix_multipart::Multipart;
+use bytes::BytesMut;
 use futures_util::{StreamExt, TryStreamExt};
+use mime::Mime;
 
-#[derive(Debug, MultipartForm)]
+#[derive(Default)]
 pub struct UploadData {
-    #[multipart(limit = "100MB")]
-    file: TempFile,
-    _token: Option<Text<String>>,
-    action: Option<Text<String>>,
-    is_public: Option<Text<String>>,
+    pub file: Option<UploadFile>,
+    pub file_errors: Vec<String>,
+    pub _token: Option<String>,
+    pub action: Option<String>,
+    pub is_public: Option<String>,
+}
+
+pub struct UploadFile {
+    pub upload: FileUploadStream,
+    pub filename: Option<String>,
+    pub mime: Option<Mime>,
 }
 
 const RL_MAX_ATTEMPTS: u64 = 10;
@@ -51,6 +60,7 @@ pub async fn show(
     rate_limit_service: Data<RateLimitService>,
     role_service: Data<RoleService>,
     file_service: Data<FileService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     let user_roles: Vec<Role> = role_service.all_throw_http()?;
     if !FilePolicy::can_create(&user, &user_roles) {
@@ -68,12 +78,13 @@ pub async fn show(
         rate_limit_service,
         role_service,
         file_service,
+        audit_log_service,
     )
 }
 
 pub async fn upload(
     req: HttpRequest,
-    MultipartForm(data): MultipartForm<UploadData>,
+    payload: Multipart,
     user: ReqData<Arc<User>>,
     session: ReqData<Arc<Session>>,
     translator_service: Data<TranslatorService>,
@@ -83,11 +94,29 @@ pub async fn upload(
     rate_limit_service: Data<RateLimitService>,
     role_service: Data<RoleService>,
     file_service: Data<FileService>,
+    audit_log_service: Data<AuditLogService>,
+    locale_service: Data<LocaleService>,
 ) -> Result<HttpResponse, Error> {
     let user_roles = role_service.all_throw_http()?;
     if !FilePolicy::can_create(&user, &user_roles) {
         return Err(error::ErrorForbidden(""));
     }
+
+    let lang: String = locale_service.get_locale_code(Some(&req), Some(&user));
+    let file_str = translator_service.translate(&lang, "page.files.create.fields.file");
+    let max_size = file_service.get_upload_max_size(&user, &user_roles);
+
+    let mut data = UploadData::default();
+    data.prepare_from_multipart(
+        payload,
+        file_service.get_ref(),
+        max_size,
+        &lang,
+        translator_service.get_ref(),
+        &file_str,
+    )
+    .await?;
+
     invoke(
         Some(data),
         req,
@@ -100,11 +129,12 @@ pub async fn upload(
         rate_limit_service,
         role_service,
         file_service,
+        audit_log_service,
     )
 }
 
 pub fn invoke(
-    mut upload_form: Option<UploadData>,
+    upload_form: Option<UploadData>,
     req: HttpRequest,
     user: ReqData<Arc<User>>,
     session: ReqData<Arc<Session>>,
@@ -115,12 +145,8 @@ pub fn invoke(
     rate_limit_service: Data<RateLimitService>,
     role_service: Data<RoleService>,
     file_service: Data<FileService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
-    if let Some(mut upload_form_) = upload_form {
-        upload_form_.prepare();
-        upload_form = Some(upload_form_);
-    }
-
     //
     let translator_service = translator_service.get_ref();
     let template_service = template_service.get_ref();
@@ -129,6 +155,7 @@ pub fn invoke(
     let rate_limit_service = rate_limit_service.get_ref();
     let role_service = role_service.get_ref();
     let file_service = file_service.get_ref();
+    let audit_log_service = audit_log_service.get_ref();
 
     let mut action_value: Option<String> = Some("save".to_string());
     let mut is_public_value: bool = false;
@@ -167,9 +194,7 @@ pub fn invoke(
         let mut csrf_token = None;
 
         if let Some(upload_form) = &upload_form {
-            if let Some(Text(value)) = &upload_form._token {
-                csrf_token = Some(value.to_owned());
-            }
+            csrf_token = upload_form._token.to_owned();
         }
 
         web_auth_service.check_csrf_throw_http(&session, &csrf_token)?;
@@ -181,32 +206,40 @@ pub fn invoke(
 
         if executed {
             if let Some(form) = &upload_form {
-                if let Some(Text(value)) = &form.action {
+                if let Some(value) = &form.action {
                     action_value = Some(value.to_owned());
                 }
-                if let Some(Text(value)) = &form.is_public {
+                if let Some(value) = &form.is_public {
                     is_public_value = value.eq("1");
                 }
+                errors.file = form.file_errors.to_owned();
             }
 
             if errors.is_empty() {
-                if let Some(form) = &upload_form {
-                    let path = form
-                        .file
-                        .file
-                        .path()
-                        .to_str()
-                        .ok_or(error::ErrorInternalServerError(""))?;
-
-                    let result = file_service.upload_local_file_to_local_disk(
+                if let Some(UploadFile {
+                    upload,
+                    filename,
+                    mime,
+                }) = upload_form.and_then(|form| form.file)
+                {
+                    let result = file_service.upload_stream_to_disk(
                         user.id,
-                        path,
+                        upload,
                         is_public_value,
-                        form.file.file_name.to_owned(),
-                        form.file.content_type.to_owned(),
+                        filename,
+                        mime,
                     );
 
                     if let Ok(user_file_) = result {
+                        audit_log_service.record_throw_http(
+                            &req,
+                            user,
+                            AuditLogAction::Create,
+                            AuditLogEntity::UserFile,
+                            user_file_.id,
+                            None,
+                            AuditLogService::to_value(&user_file_),
+                        )?;
                         user_file = Some(user_file_);
                     } else if let Err(error) = result {
                         if error.ne(&FileServiceError::DuplicateFile) {
@@ -286,6 +319,7 @@ pub fn invoke(
         ],
         "form": {
             "action": get_upload_url(),
+            "resumable_action": get_resumable_create_url(),
             "method": "post",
             "fields": fields,
             "save": translator_service.translate(lang, "Save"),
@@ -303,52 +337,6 @@ pub fn invoke(
         .body(s))
 }
 
-// pub async fn upload(
-//     mut payload: Multipart,
-//     req: HttpRequest,
-//     user: ReqData<Arc<User>>,
-//     session: ReqData<Arc<Session>>,
-// ) -> Result<HttpResponse, Error> {
-//     // iterate over multipart stream
-//     while let Ok(Some(mut field)) = payload.try_next().await {
-//         // dbg!(&field);
-//         // let content_type = field.content_type();
-//         // dbg!(content_type);
-//         let content_disposition = field.content_disposition().unwrap();
-//         // dbg!(content_disposition);
-//         let field_name = content_disposition.get_name().unwrap().to_string();
-//         // dbg!(&field_name);
-//         // let filename = content_disposition.get_filename().unwrap();
-//         // dbg!(filename);
-//         // let filepath = format!(".{}", file_path);
-//         //
-//         // // File::create is blocking operation, use threadpool
-//         // let mut f = web::block(|| std::fs::File::create(filepath))
-//         //     .await
-//         //     .unwrap();
-//         //
-//         // let mut bytes = BytesMut::new();
-//         // // Field in turn is stream of *Bytes* object
-//         while let Some(chunk) = field.next().await {
-//             // let data = chunk.unwrap();
-//             // bytes.extend_from_slice(&data);
-//             dbg!(&field_name);
-//             // dbg!(&data);
-//             // filesystem operations are blocking, we have to use threadpool
-//             // f = web::block(move || f.write_all(&data).map(|_| f))
-//             //     .await
-//             //     .unwrap();
-//         }
-//         // if field_name.eq("_token") {
-//         //     dbg!(&bytes);
-//         // }
-//     }
-//
-//     Ok(HttpResponse::Ok()
-//         .content_type(mime::APPLICATION_JSON.as_ref())
-//         .body("{\"test\": 1}"))
-// }
-
 pub async fn avatar(
     mut payload: Multipart,
     req: HttpRequest,
@@ -403,10 +391,101 @@ pub fn get_upload_avatar_url() -> String {
 }
 
 impl UploadData {
-    pub fn prepare(&mut self) {
-        prepare_upload_text_value!(self._token);
-        prepare_upload_text_value!(self.action);
-        prepare_upload_text_value!(self.is_public);
+    // The file is written to the disk chunk by chunk, so the upload is never held in memory.
+    pub async fn prepare_from_multipart(
+        &mut self,
+        mut payload: Multipart,
+        file_service: &FileService,
+        max_size: u64,
+        lang: &str,
+        translator_service: &TranslatorService,
+        file_str: &str,
+    ) -> Result<(), Error> {
+        while let Ok(Some(mut field)) = payload.try_next().await {
+            let content_disposition = field.content_disposition();
+            if content_disposition.is_none() {
+                return Err(error::ErrorBadRequest(""));
+            }
+            let content_disposition = content_disposition.unwrap();
+
+            let field_name = content_disposition.get_name();
+            if field_name.is_none() {
+                return Err(error::ErrorBadRequest(""));
+            }
+            let field_name = field_name.unwrap().to_string();
+
+            if field_name.eq("file") {
+                let filename = content_disposition
+                    .get_filename()
+                    .map(|filename| filename.to_string());
+                let mime = field.content_type().cloned();
+
+                let mut upload = file_service
+                    .upload_stream(max_size)
+                    .map_err(|_| error::ErrorInternalServerError(""))?;
+                let mut is_too_large = false;
+
+                while let Some(chunk) = field.next().await {
+                    let data = chunk.map_err(|_| error::ErrorBadRequest(""))?;
+                    if let Err(e) = upload.write(&data) {
+                        if e.ne(&FileServiceError::TooLarge) {
+                            return Err(error::ErrorInternalServerError(""));
+                        }
+                        is_too_large = true;
+                        break;
+                    }
+                }
+
+                if is_too_large {
+                    let max = upload.max_size().to_string();
+                    let mut vars = HashMap::new();
+                    vars.insert("attribute", file_str);
+                    vars.insert("max", max.as_str());
+                    self.file_errors.push(translator_service.variables(
+                        lang,
+                        "validation.max.file",
+                        &vars,
+                    ));
+                } else if upload.size() > 0 {
+                    self.file = Some(UploadFile {
+                        upload,
+                        filename,
+                        mime,
+                    });
+                }
+                continue;
+            }
+
+            let mut bytes = BytesMut::new();
+            while let Some(chunk) = field.next().await {
+                let data = chunk.map_err(|_| error::ErrorBadRequest(""))?;
+                bytes.extend_from_slice(&data);
+                if !BytesMutMaxLength::apply(&bytes, 400) {
+                    bytes.clear();
+                    break;
+                }
+            }
+            let bytes = bytes.freeze();
+
+            match field_name.as_str() {
+                "_token" => assign_value_bytes_to_string!(bytes, self._token),
+                "action" => assign_value_bytes_to_string!(bytes, self.action),
+                "is_public" => assign_value_bytes_to_string!(bytes, self.is_public),
+                _ => {}
+            }
+        }
+
+        if self.file.is_none() && self.file_errors.is_empty() {
+            let mut vars = HashMap::new();
+            vars.insert("attribute", file_str);
+            self.file_errors.push(translator_service.variables(
+                lang,
+                "validation.required",
+                &vars,
+            ));
+        }
+
+        Ok(())
     }
 }
 
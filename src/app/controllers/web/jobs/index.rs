@@ -0,0 +1,174 @@
+use crate::app::controllers::web::jobs::retry::get_retry_url;
+use crate::app::controllers::web::{
+    generate_2_offset_pagination_array, get_context_data, get_template_context,
+};
+use crate::{
+    prepare_paginate, AppService, JobPolicy, JobService, LocaleService, RoleService, Session,
+    TemplateService, TranslatorService, User, WebAuthService, WebHttpResponse,
+};
+use actix_web::web::{Data, Query, ReqData};
+use actix_web::{error, Error, HttpRequest, HttpResponse, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::cmp::max;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const PAGE_URL: &'static str = "/jobs/failed?";
+
+pub const DEFAULT_PER_PAGE: i64 = 15;
+pub const MAX_PER_PAGE: i64 = 100;
+pub const PER_PAGES: [i64; 7] = [10, 15, 20, 30, 40, 50, 100];
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct IndexQuery {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+pub async fn invoke(
+    req: HttpRequest,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    mut query: Query<IndexQuery>,
+    translator_service: Data<TranslatorService>,
+    tmpl_service: Data<TemplateService>,
+    app_service: Data<AppService>,
+    web_auth_service: Data<WebAuthService>,
+    role_service: Data<RoleService>,
+    locale_service: Data<LocaleService>,
+    job_service: Data<JobService>,
+) -> Result<HttpResponse, Error> {
+    let translator_service = translator_service.get_ref();
+    let tmpl_service = tmpl_service.get_ref();
+    let app_service = app_service.get_ref();
+    let web_auth_service = web_auth_service.get_ref();
+    let locale_service = locale_service.get_ref();
+    let role_service = role_service.get_ref();
+    let job_service = job_service.get_ref();
+    let user = user.as_ref();
+
+    let user_roles = role_service.all_throw_http()?;
+    if !JobPolicy::can_show(&user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+    let is_can_retry = JobPolicy::can_retry(&user, &user_roles);
+
+    query.prepare();
+
+    let lang: String = locale_service.get_locale_code(Some(&req), Some(&user));
+    let lang = &lang;
+
+    let page = query.page.unwrap();
+    let per_page = query.per_page.unwrap();
+    let total_records = job_service.failed_count_throw_http()? as i64;
+    let total_pages = max((total_records + per_page - 1) / per_page, 1);
+    let page = if page > total_pages { total_pages } else { page };
+    let jobs = job_service.failed_throw_http((page - 1) * per_page, per_page)?;
+    let page_str = page.to_string();
+    let total_pages_str = total_pages.to_string();
+
+    let mut context_data = get_context_data(
+        &req,
+        user,
+        &session,
+        translator_service,
+        app_service,
+        web_auth_service,
+        role_service,
+    );
+    let mut page_vars: HashMap<&str, &str> = HashMap::new();
+    page_vars.insert("page", &page_str);
+    page_vars.insert("total_pages", &total_pages_str);
+    context_data.title = translator_service.variables(lang, "page.jobs.failed.title", &page_vars);
+
+    let layout_ctx = get_template_context(&context_data);
+
+    let mut pagination_link = query.clone().remove_page().to_url()?;
+    pagination_link.push_str("&page=:page");
+    let pagination_nums = generate_2_offset_pagination_array(page, total_pages);
+
+    let records: Vec<Value> = jobs
+        .iter()
+        .map(|job| {
+            let mut key = "page.jobs.failed.names.".to_string();
+            key.push_str(&job.payload.to_string());
+            json!({
+                "id": &job.id,
+                "name": translator_service.translate(lang, &key),
+                "attempts": format!("{} / {}", job.attempts, job.max_attempts),
+                "created_at": &job.created_at,
+                "failed_at": &job.failed_at,
+                "error": &job.error,
+                "retry_action": if is_can_retry { Some(get_retry_url(&job.id)) } else { None },
+            })
+        })
+        .collect();
+
+    let ctx = json!({
+        "ctx": &layout_ctx,
+        "heading": translator_service.translate(lang, "page.jobs.failed.header"),
+        "breadcrumbs": [
+            {"href": "/", "label": translator_service.translate(lang, "page.home.header")},
+            {"href": get_url(), "label": translator_service.translate(lang, "page.jobs.failed.header")},
+            {"label": translator_service.variables(lang, "Page :page of :total_pages", &page_vars)},
+        ],
+        "page_per_page": translator_service.variables(lang, "Page :page of :total_pages", &page_vars),
+        "per_page_label": translator_service.translate(lang, "Number of entries per page"),
+        "columns": {
+            "id": translator_service.translate(lang, "page.jobs.failed.columns.id"),
+            "name": translator_service.translate(lang, "page.jobs.failed.columns.name"),
+            "attempts": translator_service.translate(lang, "page.jobs.failed.columns.attempts"),
+            "created_at": translator_service.translate(lang, "page.jobs.failed.columns.created_at"),
+            "failed_at": translator_service.translate(lang, "page.jobs.failed.columns.failed_at"),
+            "error": translator_service.translate(lang, "page.jobs.failed.columns.error"),
+            "actions": translator_service.translate(lang, "page.jobs.failed.columns.actions"),
+        },
+        "jobs": {
+            "page": page,
+            "per_page": per_page,
+            "total_pages": total_pages,
+            "total_records": total_records,
+            "records": records,
+            "pagination_nums": pagination_nums,
+            "pagination_link": pagination_link
+        },
+        "per_pages": &PER_PAGES,
+        "empty": translator_service.translate(lang, "page.jobs.failed.empty"),
+        "retry": translator_service.translate(lang, "page.jobs.failed.retry"),
+        "retry_confirm": translator_service.translate(lang, "page.jobs.failed.retry_confirm"),
+        "filter": {
+            "action": get_url(),
+            "method": "get",
+        }
+    });
+
+    let s = tmpl_service.render_throw_http("pages/jobs/failed.hbs", &ctx)?;
+    Ok(HttpResponse::Ok()
+        .clear_alerts()
+        .content_type(mime::TEXT_HTML_UTF_8.as_ref())
+        .body(s))
+}
+
+impl IndexQuery {
+    pub fn prepare(&mut self) {
+        prepare_paginate!(self.page, self.per_page, DEFAULT_PER_PAGE, MAX_PER_PAGE);
+    }
+    pub fn remove_page(&mut self) -> &mut Self {
+        self.page = None;
+        self
+    }
+    pub fn to_url(&self) -> Result<String, Error> {
+        let url = serde_urlencoded::to_string(self).map_err(|e| {
+            log::error!("app::controllers::web::jobs::index::IndexQuery::to_url - {e}");
+            error::ErrorInternalServerError("")
+        })?;
+        let mut result = PAGE_URL.to_string();
+        result.push_str(&url);
+        Ok(result)
+    }
+}
+
+pub fn get_url() -> String {
+    "/jobs/failed".to_string()
+}
@@ -0,0 +1,86 @@
+use crate::app::controllers::web::jobs::index::get_url;
+use crate::{
+    AlertVariant, JobPolicy, JobService, LocaleService, RateLimitService, RoleService, Session,
+    TranslatorService, User, WebAuthService, WebHttpResponse,
+};
+use actix_web::web::{Data, Form, Path, ReqData};
+use actix_web::{error, Error, HttpRequest, HttpResponse, Result, http::header::{HeaderValue, LOCATION}};
+use serde_derive::Deserialize;
+use std::sync::Arc;
+
+const RL_MAX_ATTEMPTS: u64 = 60;
+const RL_TTL: u64 = 60;
+const RL_KEY: &'static str = "jobs_retry";
+
+#[derive(Deserialize, Default, Debug)]
+pub struct PostData {
+    pub _token: Option<String>,
+}
+
+pub async fn invoke(
+    req: HttpRequest,
+    path: Path<String>,
+    data: Form<PostData>,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    job_service: Data<JobService>,
+    locale_service: Data<LocaleService>,
+    web_auth_service: Data<WebAuthService>,
+    rate_limit_service: Data<RateLimitService>,
+    translator_service: Data<TranslatorService>,
+    role_service: Data<RoleService>,
+) -> Result<HttpResponse, Error> {
+    let web_auth_service = web_auth_service.get_ref();
+    let rate_limit_service = rate_limit_service.get_ref();
+    let locale_service = locale_service.get_ref();
+    let job_service = job_service.get_ref();
+    let translator_service = translator_service.get_ref();
+    let role_service = role_service.get_ref();
+
+    web_auth_service.check_csrf_throw_http(&session, &data._token)?;
+
+    let user = user.as_ref();
+    let user_roles = role_service.all_throw_http()?;
+    if !JobPolicy::can_retry(user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+
+    let job_id = path.into_inner();
+    let lang: String = locale_service.get_locale_code(Some(&req), Some(&user));
+    let rate_limit_key = rate_limit_service.make_key_from_request_throw_http(&req, RL_KEY)?;
+
+    let mut alert_variants = Vec::new();
+    let executed =
+        rate_limit_service.attempt_throw_http(&rate_limit_key, RL_MAX_ATTEMPTS, RL_TTL)?;
+
+    if executed {
+        let job = job_service.retry_throw_http(&job_id)?;
+        let mut key = "page.jobs.failed.names.".to_string();
+        key.push_str(&job.payload.to_string());
+        let name = translator_service.translate(&lang, &key);
+        alert_variants.push(AlertVariant::JobsRetrySuccess(name));
+    } else {
+        let alert_variant = rate_limit_service.alert_variant_throw_http(
+            translator_service,
+            &lang,
+            &rate_limit_key,
+        )?;
+        alert_variants.push(alert_variant);
+    }
+
+    Ok(HttpResponse::SeeOther()
+        .set_alerts(alert_variants)
+        .insert_header((
+            LOCATION,
+            HeaderValue::from_str(&get_url()).map_err(|_| error::ErrorInternalServerError(""))?,
+        ))
+        .finish())
+}
+
+pub fn get_retry_url(id: &str) -> String {
+    let mut str_ = get_url();
+    str_.push('/');
+    str_.push_str(id);
+    str_.push_str("/retry");
+    str_
+}
@@ -1,16 +1,21 @@
+pub mod api_tokens;
+pub mod audit_log;
 pub mod auth;
 pub mod errors;
+pub mod exports;
 pub mod files;
 pub mod home;
+pub mod jobs;
 pub mod locale;
 pub mod profile;
 pub mod roles;
+pub mod two_factor;
 pub mod user_files;
 pub mod users;
 
 use crate::{
-    Alert, AlertVariant, AppService, FilePolicy, Locale, RolePolicy, RoleService, Session,
-    TranslatorService, User, UserPolicy, WebAuthService, ALERTS_KEY,
+    Alert, AlertVariant, AppService, AuditLogPolicy, FilePolicy, JobPolicy, Locale, RolePolicy, RoleService,
+    Session, TranslatorService, User, UserPolicy, WebAuthService, ALERTS_KEY,
 };
 use actix_web::cookie::time::Duration;
 use actix_web::cookie::Cookie;
@@ -134,6 +139,8 @@ pub fn get_template_context<'a>(data: &'a ContextData) -> Value {
     let mut sidebar_users_index: Option<String> = None;
     let mut sidebar_roles_index: Option<String> = None;
     let mut sidebar_files: Option<String> = None;
+    let mut sidebar_audit_log: Option<String> = None;
+    let mut sidebar_jobs: Option<String> = None;
     let mut is_sidebar_users_dropdown = false;
 
     if let Ok(roles) = role_service.all() {
@@ -149,9 +156,18 @@ pub fn get_template_context<'a>(data: &'a ContextData) -> Value {
         }
         is_sidebar_users_dropdown = is_users_show && is_roles_show;
 
-        if FilePolicy::can_show(user, &roles) {
+        if FilePolicy::can_show_any_or_own(user, &roles) {
             sidebar_files = Some(translator_service.translate(lang, "layout.sidebar.files"));
         }
+
+        if AuditLogPolicy::can_show(user, &roles) {
+            sidebar_audit_log =
+                Some(translator_service.translate(lang, "layout.sidebar.audit_log"));
+        }
+
+        if JobPolicy::can_show(user, &roles) {
+            sidebar_jobs = Some(translator_service.translate(lang, "layout.sidebar.jobs"));
+        }
     }
 
     json!({
@@ -166,6 +182,8 @@ pub fn get_template_context<'a>(data: &'a ContextData) -> Value {
               "roles": sidebar_roles_index,
             },
             "files": sidebar_files,
+            "audit_log": sidebar_audit_log,
+            "jobs": sidebar_jobs,
             "profile": translator_service.translate(lang, "layout.sidebar.profile"),
             "logout": translator_service.translate(lang, "layout.sidebar.logout"),
         },
@@ -1,5 +1,5 @@
 use crate::app::controllers::web::users::create_update::{invoke as users_create_update_invoke, InvokeData, InvokeRoute};
-use crate::{AppService, FileService, LocaleService, RateLimitService, RoleService, TemplateService, TranslatorService, UserFileService, UserService};
+use crate::{AppService, AuditLogService, FileService, LocaleService, RateLimitService, RoleService, TemplateService, TranslatorService, UserFileService, UserService};
 use crate::{Session, User, WebAuthService};
 use actix_web::web::{Data, ReqData};
 use actix_web::{Error, HttpRequest, HttpResponse, Result};
@@ -21,6 +21,7 @@ pub async fn index(
     role_service: Data<RoleService>,
     user_file_service: Data<UserFileService>,
     file_service: Data<FileService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     users_create_update_invoke(InvokeData {
         route: InvokeRoute::ProfileEdit,
@@ -40,6 +41,7 @@ pub async fn index(
         role_service,
         user_file_service,
         file_service,
+        audit_log_service,
     }).await
 }
 
@@ -59,6 +61,7 @@ pub async fn update(
     role_service: Data<RoleService>,
     user_file_service: Data<UserFileService>,
     file_service: Data<FileService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     users_create_update_invoke(InvokeData {
         route: InvokeRoute::ProfileUpdate,
@@ -78,6 +81,7 @@ pub async fn update(
         role_service,
         user_file_service,
         file_service,
+        audit_log_service,
     }).await
 }
 
@@ -3,7 +3,7 @@ use crate::app::validator::rules::str_max_chars_count::StrMaxCharsCount;
 use crate::app::validator::rules::str_min_max_chars_count::{StrMinMaxCharsCount as MMLS};
 use crate::app::validator::rules::required::Required;
 use crate::libs::actix_web::types::form::Form;
-use crate::{
+use crate::{AuditLogAction, AuditLogEntity, AuditLogService, 
     prepare_value, Alert, AlertVariant, AppService, Permission, RateLimitService, Role, RoleColumn,
     RolePolicy, RoleService, RoleServiceError, Session, TemplateService, TranslatableError,
     TranslatorService, User, WebAuthService, WebHttpResponse,
@@ -19,6 +19,7 @@ use strum::VariantNames;
 const RL_MAX_ATTEMPTS: u64 = 10;
 const RL_TTL: u64 = 60;
 const RL_KEY: &'static str = "roles_create_update";
+const ROLE_UPLOAD_MAX_SIZE_UNIT: u64 = 1048576;
 
 #[derive(Deserialize, Default, Debug)]
 pub struct PostData {
@@ -28,6 +29,10 @@ pub struct PostData {
     pub name: Option<String>,
     pub description: Option<String>,
     pub permissions: Option<Vec<String>>,
+    pub requires_two_factor: Option<String>,
+    // The upload limit in megabytes.
+    pub upload_max_size: Option<String>,
+    pub parent_id: Option<String>,
 }
 
 #[derive(Deserialize, Default, Debug)]
@@ -37,6 +42,8 @@ struct ErrorMessages {
     pub name: Vec<String>,
     pub description: Vec<String>,
     pub permissions: Vec<String>,
+    pub upload_max_size: Vec<String>,
+    pub parent_id: Vec<String>,
 }
 
 pub async fn create(
@@ -49,6 +56,7 @@ pub async fn create(
     web_auth_service: Data<WebAuthService>,
     rate_limit_service: Data<RateLimitService>,
     role_service: Data<RoleService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     let roles = role_service.all_throw_http()?;
     if !RolePolicy::can_create(&user, &roles) {
@@ -67,6 +75,7 @@ pub async fn create(
         web_auth_service,
         rate_limit_service,
         role_service,
+        audit_log_service,
     )
 }
 
@@ -81,6 +90,7 @@ pub async fn store(
     web_auth_service: Data<WebAuthService>,
     rate_limit_service: Data<RateLimitService>,
     role_service: Data<RoleService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     let roles = role_service.all_throw_http()?;
     if !RolePolicy::can_create(&user, &roles) {
@@ -98,6 +108,7 @@ pub async fn store(
         web_auth_service,
         rate_limit_service,
         role_service,
+        audit_log_service,
     )
 }
 
@@ -112,6 +123,7 @@ pub async fn edit(
     web_auth_service: Data<WebAuthService>,
     rate_limit_service: Data<RateLimitService>,
     role_service: Data<RoleService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     let roles = role_service.all_throw_http()?;
     if !RolePolicy::can_update(&user, &roles) {
@@ -126,6 +138,11 @@ pub async fn edit(
         name: Some(edit_role.name.to_owned()),
         description: edit_role.description.to_owned(),
         permissions: edit_role.permissions.to_owned(),
+        requires_two_factor: edit_role.requires_two_factor.then(|| "1".to_string()),
+        upload_max_size: edit_role
+            .upload_max_size
+            .map(|value| (value / ROLE_UPLOAD_MAX_SIZE_UNIT).to_string()),
+        parent_id: edit_role.parent_id.map(|value| value.to_string()),
     };
     let edit_role = Some(edit_role);
     let data = Form(post_data);
@@ -141,6 +158,7 @@ pub async fn edit(
         web_auth_service,
         rate_limit_service,
         role_service,
+        audit_log_service,
     )
 }
 
@@ -156,6 +174,7 @@ pub async fn update(
     web_auth_service: Data<WebAuthService>,
     rate_limit_service: Data<RateLimitService>,
     role_service: Data<RoleService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     let roles = role_service.all_throw_http()?;
     if !RolePolicy::can_update(&user, &roles) {
@@ -175,6 +194,7 @@ pub async fn update(
         web_auth_service,
         rate_limit_service,
         role_service,
+        audit_log_service,
     )
 }
 
@@ -190,6 +210,7 @@ pub fn invoke(
     web_auth_service: Data<WebAuthService>,
     rate_limit_service: Data<RateLimitService>,
     role_service: Data<RoleService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     data.prepare();
     //
@@ -199,9 +220,11 @@ pub fn invoke(
     let web_auth_service = web_auth_service.get_ref();
     let rate_limit_service = rate_limit_service.get_ref();
     let role_service = role_service.get_ref();
+    let audit_log_service = audit_log_service.get_ref();
 
     //
     let user = user.as_ref();
+    let auth_user: &User = user;
 
     let mut alert_variants: Vec<AlertVariant> = Vec::new();
     let mut context_data = get_context_data(
@@ -220,6 +243,11 @@ pub fn invoke(
     let name_str = translator_service.translate(lang, "page.roles.create.fields.name");
     let description_str = translator_service.translate(lang, "page.roles.create.fields.description");
     let permissions_str = translator_service.translate(lang, "page.roles.create.fields.permissions");
+    let requires_two_factor_str =
+        translator_service.translate(lang, "page.roles.create.fields.requires_two_factor");
+    let upload_max_size_str =
+        translator_service.translate(lang, "page.roles.create.fields.upload_max_size");
+    let parent_id_str = translator_service.translate(lang, "page.roles.create.fields.parent_id");
 
     let (title, heading, action) = if let Some(edit_role) = &edit_role {
         let mut vars: HashMap<&str, &str> = HashMap::new();
@@ -276,25 +304,64 @@ pub fn invoke(
                     StrMaxCharsCount::validate(translator_service, lang, description, 255, &description_str);
             }
 
-            if errors.is_empty() {
-                let id = if let Some(edit_role) = &edit_role {
-                    edit_role.id
+            let mut upload_max_size: Option<u64> = None;
+            if let Some(value) = &data.upload_max_size {
+                if let Ok(value) = value.parse::<u64>() {
+                    upload_max_size = Some(value.saturating_mul(ROLE_UPLOAD_MAX_SIZE_UNIT));
                 } else {
-                    0
+                    let mut vars = HashMap::new();
+                    vars.insert("attribute", upload_max_size_str.as_str());
+                    errors.upload_max_size.push(translator_service.variables(
+                        lang,
+                        "validation.integer",
+                        &vars,
+                    ));
+                }
+            }
+
+            let id = if let Some(edit_role) = &edit_role {
+                edit_role.id
+            } else {
+                0
+            };
+
+            let mut parent_id: Option<u64> = None;
+            if let Some(value) = &data.parent_id {
+                let result = match value.parse::<u64>() {
+                    Ok(value) => role_service.check_parent(id, value).map(|_| value),
+                    Err(_) => Err(RoleServiceError::ParentNotFound),
                 };
+                match result {
+                    Ok(value) => parent_id = Some(value),
+                    Err(error) => errors.parent_id.push(error.translate(lang, translator_service)),
+                }
+            }
+
+            if errors.is_empty() {
                 let mut role_data = Role::default();
                 role_data.id = id;
                 role_data.code = data.code.clone().unwrap();
                 role_data.name = data.name.clone().unwrap();
                 role_data.description = data.description.to_owned();
                 role_data.permissions = data.permissions.to_owned();
+                role_data.parent_id = parent_id;
 
-                let columns: Option<Vec<RoleColumn>> = Some(vec![
+                let mut columns: Vec<RoleColumn> = vec![
                     RoleColumn::Code,
                     RoleColumn::Name,
                     RoleColumn::Description,
                     RoleColumn::Permissions,
-                ]);
+                    RoleColumn::ParentId,
+                ];
+
+                if user.is_super_admin {
+                    role_data.requires_two_factor = data.requires_two_factor.is_some();
+                    columns.push(RoleColumn::RequiresTwoFactor);
+                    role_data.upload_max_size = upload_max_size;
+                    columns.push(RoleColumn::UploadMaxSize);
+                }
+
+                let columns: Option<Vec<RoleColumn>> = Some(columns);
 
                 let result = role_service.upsert(role_data, &columns);
 
@@ -330,11 +397,29 @@ pub fn invoke(
         if let Some(edit_role) = &edit_role {
             let user = role_service.first_by_id_throw_http(edit_role.id)?;
             id = user.id.to_string();
+            audit_log_service.record_throw_http(
+                &req,
+                auth_user,
+                AuditLogAction::Update,
+                AuditLogEntity::Role,
+                user.id,
+                AuditLogService::to_value(edit_role),
+                AuditLogService::to_value(&user),
+            )?;
             let name_ = user.name;
             alert_variants.push(AlertVariant::RolesUpdateSuccess(name_))
         } else if let Some(code_) = &data.code {
             let user = role_service.first_by_code_throw_http(code_)?;
             id = user.id.to_string();
+            audit_log_service.record_throw_http(
+                &req,
+                auth_user,
+                AuditLogAction::Create,
+                AuditLogEntity::Role,
+                user.id,
+                None,
+                AuditLogService::to_value(&user),
+            )?;
             let name_ = user.name;
             alert_variants.push(AlertVariant::RolesCreateSuccess(name_))
         }
@@ -388,11 +473,71 @@ pub fn invoke(
         }));
     }
 
+    // The roles inheriting from the edited one are not offered, they would make a cycle.
+    let roles = role_service.all_throw_http()?;
+    let role_id = edit_role.as_ref().map(|role| role.id).unwrap_or(0);
+    let parent_options: Vec<Value> = roles
+        .iter()
+        .filter(|role| !RoleService::is_parent_cycle(&roles, role_id, role.id))
+        .map(|role| json!({ "value": role.id.to_string(), "label": &role.name }))
+        .collect();
+
+    let effective_permissions: Option<Value> = edit_role.as_ref().map(|edit_role| {
+        let own_str =
+            translator_service.translate(lang, "page.roles.create.effective_permissions.own");
+        let items: Vec<Value> = RoleService::effective_permissions(&roles, edit_role.id)
+            .iter()
+            .map(|(permission, source_id)| {
+                let mut key = "permission.".to_string();
+                key.push_str(permission);
+                let is_inherited = source_id.ne(&edit_role.id);
+                let source = if is_inherited {
+                    let name = roles
+                        .iter()
+                        .find(|role| role.id.eq(source_id))
+                        .map(|role| role.name.to_owned())
+                        .unwrap_or_default();
+                    let mut vars: HashMap<&str, &str> = HashMap::new();
+                    vars.insert("name", &name);
+                    translator_service.variables(
+                        lang,
+                        "page.roles.create.effective_permissions.inherited",
+                        &vars,
+                    )
+                } else {
+                    own_str.to_owned()
+                };
+                json!({
+                    "label": translator_service.translate(lang, &key),
+                    "source": source,
+                    "is_inherited": is_inherited,
+                })
+            })
+            .collect();
+        json!({
+            "label": translator_service.translate(lang, "page.roles.create.tabs.effective_permissions"),
+            "description": translator_service.translate(lang, "page.roles.create.effective_permissions.description"),
+            "empty": translator_service.translate(lang, "page.roles.create.effective_permissions.empty"),
+            "items": items,
+        })
+    });
+
     let fields = json!({
         "code": { "label": code_str, "value": &data.code, "errors": errors.code },
         "name": { "label": name_str, "value": &data.name, "errors": errors.name },
         "description": { "label": description_str, "value": &data.description, "errors": errors.description },
         "permissions": { "label": permissions_str, "value": &data.permissions, "errors": errors.permissions, "options": permissions },
+        "parent_id": { "label": parent_id_str, "value": &data.parent_id, "errors": errors.parent_id, "options": parent_options, "placeholder": translator_service.translate(lang, "Not selected...") },
+        "requires_two_factor": if user.is_super_admin {
+            Some(json!({ "label": requires_two_factor_str, "checked": data.requires_two_factor.is_some() }))
+        } else {
+            None
+        },
+        "upload_max_size": if user.is_super_admin {
+            Some(json!({ "label": upload_max_size_str, "value": &data.upload_max_size, "errors": errors.upload_max_size }))
+        } else {
+            None
+        },
     });
 
     let ctx = json!({
@@ -407,6 +552,7 @@ pub fn invoke(
             {"href": "/roles", "label": translator_service.translate(lang, "page.roles.index.header")},
             {"label": &heading},
         ],
+        "effective_permissions": effective_permissions,
         "form": {
             "action": &action,
             "method": "post",
@@ -443,6 +589,9 @@ impl PostData {
         prepare_value!(self.code);
         prepare_value!(self.name);
         prepare_value!(self.description);
+        prepare_value!(self.requires_two_factor);
+        prepare_value!(self.upload_max_size);
+        prepare_value!(self.parent_id);
     }
 }
 
@@ -453,5 +602,7 @@ impl ErrorMessages {
             && self.name.len() == 0
             && self.description.len() == 0
             && self.permissions.len() == 0
+            && self.upload_max_size.len() == 0
+            && self.parent_id.len() == 0
     }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    AlertVariant, LocaleService, RateLimitService, RolePolicy, RoleService, Session,
+    AlertVariant, AuditLogAction, AuditLogEntity, AuditLogService, LocaleService, RateLimitService, RolePolicy, RoleService, Session,
     TranslatorService, User, WebAuthService, WebHttpResponse,
 };
 use actix_web::web::{Data, Form, Path, ReqData};
@@ -27,12 +27,14 @@ pub async fn invoke(
     web_auth_service: Data<WebAuthService>,
     rate_limit_service: Data<RateLimitService>,
     translator_service: Data<TranslatorService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     let web_auth_service = web_auth_service.get_ref();
     let rate_limit_service = rate_limit_service.get_ref();
     let locale_service = locale_service.get_ref();
     let role_service = role_service.get_ref();
     let translator_service = translator_service.get_ref();
+    let audit_log_service = audit_log_service.get_ref();
 
     web_auth_service.check_csrf_throw_http(&session, &data._token)?;
 
@@ -54,6 +56,15 @@ pub async fn invoke(
 
     if executed {
         role_service.delete_by_id_throw_http(delete_role.id)?;
+        audit_log_service.record_throw_http(
+            &req,
+            user,
+            AuditLogAction::Delete,
+            AuditLogEntity::Role,
+            delete_role.id,
+            AuditLogService::to_value(&delete_role),
+            None,
+        )?;
         let name = delete_role.name;
         alert_variants.push(AlertVariant::RolesDeleteSuccess(name));
     } else {
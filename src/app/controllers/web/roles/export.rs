@@ -0,0 +1,88 @@
+use crate::app::controllers::web::exports::{respond, ExportQuery, PostData};
+use crate::app::controllers::web::roles::index::IndexQuery;
+use crate::libs::actix_web::types::form::Form;
+use crate::{
+    ExportEntity, ExportParams, ExportService, LocaleService, RoleService, Session,
+    TranslatorService, User, RolePolicy, WebAuthService,
+};
+use actix_web::web::{Data, Query, ReqData};
+use actix_web::{error, Error, HttpRequest, HttpResponse, Result};
+use std::sync::Arc;
+
+const PAGE_URL: &'static str = "/roles";
+
+/// Exports all the roles matching the filters and the sort of the list page.
+pub async fn invoke(
+    req: HttpRequest,
+    user: ReqData<Arc<User>>,
+    mut query: Query<IndexQuery>,
+    export_query: Query<ExportQuery>,
+    translator_service: Data<TranslatorService>,
+    locale_service: Data<LocaleService>,
+    role_service: Data<RoleService>,
+    export_service: Data<ExportService>,
+) -> Result<HttpResponse, Error> {
+    let translator_service = translator_service.get_ref();
+    let user = user.as_ref();
+
+    let user_roles = role_service.get_ref().all_throw_http()?;
+    if !RolePolicy::can_show(user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+
+    let format = export_query.get_format()?;
+    let lang: String = locale_service.get_ref().get_locale_code(Some(&req), Some(user));
+
+    query.prepare();
+    query.validate(
+        translator_service,
+        &lang,
+        &translator_service.translate(&lang, "Search"),
+        &translator_service.translate(&lang, "Sort"),
+    );
+
+    let params = ExportParams {
+        entity: ExportEntity::Roles,
+        format,
+        search: query.search.to_owned(),
+        locale: None,
+        sort: query.sort.to_owned(),
+        ids: None,
+        lang,
+    };
+    respond(&req, user, params, export_service.get_ref(), PAGE_URL)
+}
+
+/// Exports the roles selected on the list page.
+pub async fn selected(
+    req: HttpRequest,
+    data: Form<PostData>,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    export_query: Query<ExportQuery>,
+    locale_service: Data<LocaleService>,
+    web_auth_service: Data<WebAuthService>,
+    role_service: Data<RoleService>,
+    export_service: Data<ExportService>,
+) -> Result<HttpResponse, Error> {
+    web_auth_service
+        .get_ref()
+        .check_csrf_throw_http(&session, &data._token)?;
+
+    let user = user.as_ref();
+    let user_roles = role_service.get_ref().all_throw_http()?;
+    if !RolePolicy::can_show(user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+
+    let params = ExportParams {
+        entity: ExportEntity::Roles,
+        format: export_query.get_format()?,
+        search: None,
+        locale: None,
+        sort: None,
+        ids: Some(data.get_selected()?),
+        lang: locale_service.get_ref().get_locale_code(Some(&req), Some(user)),
+    };
+    respond(&req, user, params, export_service.get_ref(), PAGE_URL)
+}
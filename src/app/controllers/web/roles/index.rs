@@ -1,5 +1,5 @@
 use crate::app::controllers::web::{
-    generate_2_offset_pagination_array, get_context_data, get_template_context,
+    exports, generate_2_offset_pagination_array, get_context_data, get_template_context,
 };
 use crate::{
     prepare_paginate, prepare_value, validation_query_max_length_string, Alert, AppService,
@@ -17,6 +17,7 @@ use std::sync::Arc;
 use strum::IntoEnumIterator;
 
 const PAGE_URL: &'static str = "/roles?";
+const EXPORT_URL: &'static str = "/roles/export?";
 
 pub const DEFAULT_PER_PAGE: i64 = 15;
 pub const MAX_PER_PAGE: i64 = 100;
@@ -123,7 +124,9 @@ pub async fn invoke(
         sort_options.push(json!({ "label": label, "value": value }));
     }
 
-    let mut selected: Option<Value> = None;
+    let mut selected: Value = json!({
+        "label": translator_service.translate(lang, "Selected"),
+    });
     let mut create: Option<Value> = None;
     let mut edit: Option<Value> = None;
     let mut delete: Option<Value> = None;
@@ -143,11 +146,8 @@ pub async fn invoke(
     }
 
     if RolePolicy::can_delete(&user, &user_roles) {
-        selected = Some(json!({
-            "label": translator_service.translate(lang, "Selected"),
-            "delete": translator_service.translate(lang, "Delete selected"),
-            "delete_confirm": translator_service.translate(lang, "Delete selected?"),
-        }));
+        selected["delete"] = json!(translator_service.translate(lang, "Delete selected"));
+        selected["delete_confirm"] = json!(translator_service.translate(lang, "Delete selected?"));
         delete = Some(json!({
             "action": "/roles/:id/delete",
             "method": "post",
@@ -176,6 +176,12 @@ pub async fn invoke(
             "options": &sort_options
         },
         "selected": selected,
+        "export": exports::get_template_context(
+            translator_service,
+            lang,
+            &query.clone().remove_page().remove_per_page().to_export_url()?,
+            "/roles/export",
+        ),
         "columns": {
             "id": translator_service.translate(lang, "page.roles.index.columns.id"),
             "code": translator_service.translate(lang, "page.roles.index.columns.code"),
@@ -284,6 +290,15 @@ impl IndexQuery {
         result.push_str(&url);
         Ok(result)
     }
+    pub fn to_export_url(&self) -> Result<String, Error> {
+        let url = serde_urlencoded::to_string(self).map_err(|e| {
+            log::error!("app::controllers::web::roles::index::IndexQuery::to_export_url - {e}");
+            error::ErrorInternalServerError("")
+        })?;
+        let mut result = EXPORT_URL.to_string();
+        result.push_str(&url);
+        Ok(result)
+    }
     pub fn get_filters(&self) -> Vec<RoleFilter> {
         let mut filters: Vec<RoleFilter> = Vec::new();
 
@@ -1,6 +1,6 @@
 use crate::libs::actix_web::types::form::Form;
 use crate::{
-    AlertVariant, LocaleService, RateLimitService, RolePolicy, RoleService, Session,
+    AlertVariant, AuditLogAction, AuditLogEntity, AuditLogService, LocaleService, RateLimitService, RolePolicy, RoleService, Session,
     TranslatorService, User, WebAuthService, WebHttpResponse,
 };
 use actix_web::{web::{Data, ReqData}, error, Error, HttpRequest, HttpResponse, Result, http::header::{HeaderValue, ORIGIN, REFERER, LOCATION}};
@@ -29,12 +29,14 @@ pub async fn invoke(
     web_auth_service: Data<WebAuthService>,
     rate_limit_service: Data<RateLimitService>,
     translator_service: Data<TranslatorService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     let web_auth_service = web_auth_service.get_ref();
     let rate_limit_service = rate_limit_service.get_ref();
     let locale_service = locale_service.get_ref();
     let role_service = role_service.get_ref();
     let translator_service = translator_service.get_ref();
+    let audit_log_service = audit_log_service.get_ref();
 
     web_auth_service.check_csrf_throw_http(&session, &data._token)?;
 
@@ -58,7 +60,19 @@ pub async fn invoke(
                     if !RolePolicy::can_delete(&user, &roles) {
                         return Err(error::ErrorForbidden(""));
                     }
+                    let delete_roles = role_service.all_by_ids_throw_http(ids)?;
                     role_service.delete_by_ids_throw_http(ids)?;
+                    for delete_role in &delete_roles {
+                        audit_log_service.record_throw_http(
+                            &req,
+                            user,
+                            AuditLogAction::Delete,
+                            AuditLogEntity::Role,
+                            delete_role.id,
+                            AuditLogService::to_value(delete_role),
+                            None,
+                        )?;
+                    }
                     alert_variants.push(AlertVariant::RolesMassDeleteSuccess(
                         join_vec(ids, ", "),
                     ));
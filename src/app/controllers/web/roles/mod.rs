@@ -1,4 +1,5 @@
 pub mod index;
 pub mod create_update;
 pub mod delete;
-pub mod mass_actions;
\ No newline at end of file
+pub mod mass_actions;
+pub mod export;
\ No newline at end of file
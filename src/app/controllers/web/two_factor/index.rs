@@ -0,0 +1,339 @@
+use crate::app::controllers::web::{get_context_data, get_template_context};
+use crate::app::validator::rules::contains_str::ContainsStr;
+use crate::app::validator::rules::required::Required;
+use crate::app::validator::rules::str_min_max_chars_count::StrMinMaxCharsCount as MMCC;
+use crate::libs::actix_web::types::form::Form;
+use crate::{
+    prepare_value, Alert, AlertVariant, AppService, RateLimitService, RoleService, Session,
+    TemplateService, TranslatableError, TranslatorService, User, UserTwoFactorService,
+    UserTwoFactorServiceError, WebAuthService, WebHttpResponse,
+};
+use actix_web::web::{Data, ReqData};
+use actix_web::{error, http::Method, Error, HttpRequest, HttpResponse, Result};
+use serde_derive::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const RL_MAX_ATTEMPTS: u64 = 5;
+const RL_TTL: u64 = 60;
+const RL_KEY: &'static str = "two_factor";
+const ACTIONS: [&'static str; 4] = ["enable", "confirm", "recovery_codes", "disable"];
+
+#[derive(Deserialize, Default, Debug)]
+pub struct PostData {
+    pub _token: Option<String>,
+    pub action: Option<String>,
+    pub code: Option<String>,
+}
+
+#[derive(Deserialize, Default, Debug)]
+struct ErrorMessages {
+    pub form: Vec<String>,
+    pub code: Vec<String>,
+}
+
+pub async fn index(
+    req: HttpRequest,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    translator_service: Data<TranslatorService>,
+    template_service: Data<TemplateService>,
+    app_service: Data<AppService>,
+    web_auth_service: Data<WebAuthService>,
+    rate_limit_service: Data<RateLimitService>,
+    role_service: Data<RoleService>,
+    user_two_factor_service: Data<UserTwoFactorService>,
+) -> Result<HttpResponse, Error> {
+    invoke(
+        req,
+        Form(PostData::default()),
+        user,
+        session,
+        translator_service,
+        template_service,
+        app_service,
+        web_auth_service,
+        rate_limit_service,
+        role_service,
+        user_two_factor_service,
+    )
+}
+
+pub async fn store(
+    req: HttpRequest,
+    data: Form<PostData>,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    translator_service: Data<TranslatorService>,
+    template_service: Data<TemplateService>,
+    app_service: Data<AppService>,
+    web_auth_service: Data<WebAuthService>,
+    rate_limit_service: Data<RateLimitService>,
+    role_service: Data<RoleService>,
+    user_two_factor_service: Data<UserTwoFactorService>,
+) -> Result<HttpResponse, Error> {
+    invoke(
+        req,
+        data,
+        user,
+        session,
+        translator_service,
+        template_service,
+        app_service,
+        web_auth_service,
+        rate_limit_service,
+        role_service,
+        user_two_factor_service,
+    )
+}
+
+pub fn invoke(
+    req: HttpRequest,
+    mut data: Form<PostData>,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    translator_service: Data<TranslatorService>,
+    template_service: Data<TemplateService>,
+    app_service: Data<AppService>,
+    web_auth_service: Data<WebAuthService>,
+    rate_limit_service: Data<RateLimitService>,
+    role_service: Data<RoleService>,
+    user_two_factor_service: Data<UserTwoFactorService>,
+) -> Result<HttpResponse, Error> {
+    data.prepare();
+    //
+    let translator_service = translator_service.get_ref();
+    let template_service = template_service.get_ref();
+    let app_service = app_service.get_ref();
+    let web_auth_service = web_auth_service.get_ref();
+    let rate_limit_service = rate_limit_service.get_ref();
+    let role_service = role_service.get_ref();
+    let user_two_factor_service = user_two_factor_service.get_ref();
+
+    //
+    let user = user.as_ref();
+
+    let mut context_data = get_context_data(
+        &req,
+        user,
+        &session,
+        translator_service,
+        app_service,
+        web_auth_service,
+        role_service,
+    );
+
+    let lang = &context_data.lang;
+
+    let code_str = translator_service.translate(lang, "page.two_factor.fields.code");
+    let action_str = translator_service.translate(lang, "page.two_factor.fields.action");
+    let heading = translator_service.translate(lang, "page.two_factor.header");
+
+    context_data.title = translator_service.translate(lang, "page.two_factor.title");
+
+    //
+    let is_post = req.method().eq(&Method::POST);
+    let mut errors = ErrorMessages::default();
+    let mut recovery_codes: Option<Vec<String>> = None;
+
+    if is_post {
+        web_auth_service.check_csrf_throw_http(&session, &data._token)?;
+
+        let rate_limit_key = rate_limit_service.make_key_from_request_throw_http(&req, RL_KEY)?;
+
+        let executed =
+            rate_limit_service.attempt_throw_http(&rate_limit_key, RL_MAX_ATTEMPTS, RL_TTL)?;
+
+        let mut is_done = false;
+
+        if executed {
+            let action_errors = Required::validated(
+                translator_service,
+                lang,
+                &data.action,
+                |value| ContainsStr::validate(translator_service, lang, value, &ACTIONS, &action_str),
+                &action_str,
+            );
+            errors.form = action_errors;
+
+            let action = data.action.to_owned().unwrap_or_default();
+
+            // Every action except the start of the enrollment is confirmed by the current code.
+            if errors.is_empty() && action.ne("enable") {
+                errors.code = Required::validated(
+                    translator_service,
+                    lang,
+                    &data.code,
+                    |value| MMCC::validate(translator_service, lang, value, 6, 32, &code_str),
+                    &code_str,
+                );
+            }
+
+            if errors.is_empty() {
+                let code = data.code.to_owned().unwrap_or_default();
+                let result: Result<Option<AlertVariant>, UserTwoFactorServiceError> =
+                    match action.as_str() {
+                        "enable" => user_two_factor_service
+                            .start_enrollment(user.id)
+                            .map(|_| None),
+                        "confirm" => user_two_factor_service
+                            .confirm_enrollment(user.id, &code)
+                            .map(|codes| {
+                                recovery_codes = Some(codes);
+                                Some(AlertVariant::TwoFactorEnableSuccess)
+                            }),
+                        "recovery_codes" => user_two_factor_service
+                            .verify(user.id, &code)
+                            .and_then(|_| user_two_factor_service.regenerate_recovery_codes(user.id))
+                            .map(|codes| {
+                                recovery_codes = Some(codes);
+                                Some(AlertVariant::TwoFactorRecoveryCodesSuccess)
+                            }),
+                        _ => user_two_factor_service
+                            .verify(user.id, &code)
+                            .and_then(|_| user_two_factor_service.disable(user.id))
+                            .map(|_| Some(AlertVariant::TwoFactorDisableSuccess)),
+                    };
+
+                match result {
+                    Ok(variant) => {
+                        if let Some(variant) = variant {
+                            context_data
+                                .alerts
+                                .push(Alert::from_variant(translator_service, lang, &variant));
+                        }
+                        is_done = true;
+                    }
+                    Err(UserTwoFactorServiceError::InvalidCode) => errors
+                        .code
+                        .push(UserTwoFactorServiceError::InvalidCode.translate(lang, translator_service)),
+                    Err(error) => errors.form.push(error.translate(lang, translator_service)),
+                }
+            }
+
+            data.code = None;
+        } else {
+            let ttl_message =
+                rate_limit_service.ttl_message_throw_http(translator_service, lang, &rate_limit_key)?;
+            errors.form.push(ttl_message)
+        }
+
+        if is_done {
+            rate_limit_service.clear_throw_http(&rate_limit_key)?;
+        }
+    }
+
+    //
+    for form_error in errors.form {
+        context_data.alerts.push(Alert::error(form_error));
+    }
+
+    let roles = role_service.all_throw_http()?;
+    let is_required = UserTwoFactorService::is_required(user, &roles);
+    if is_required && !user_two_factor_service.is_enabled_throw_http(user.id)? {
+        context_data.alerts.push(Alert::from_variant(
+            translator_service,
+            lang,
+            &AlertVariant::TwoFactorRequiredWarning,
+        ));
+    }
+
+    let layout_ctx = get_template_context(&context_data);
+
+    let user_two_factor = user_two_factor_service.first_by_user_id_throw_http(user.id)?;
+
+    let mut enrollment = None;
+    let mut enabled = None;
+
+    if let Some(user_two_factor) = &user_two_factor {
+        if user_two_factor.is_confirmed() {
+            let recovery_codes_count = user_two_factor
+                .recovery_codes
+                .as_ref()
+                .map(|codes| codes.len())
+                .unwrap_or(0)
+                .to_string();
+            let mut vars = HashMap::new();
+            vars.insert("count", recovery_codes_count.as_str());
+            enabled = Some(json!({
+                "text": translator_service.translate(lang, "page.two_factor.enabled"),
+                "recovery_codes_count": translator_service.variables(lang, "page.two_factor.recovery_codes_count", &vars),
+                "recovery_codes": translator_service.translate(lang, "page.two_factor.recovery_codes"),
+                "disable": translator_service.translate(lang, "page.two_factor.disable"),
+                "disable_confirm": translator_service.translate(lang, "page.two_factor.disable_confirm"),
+            }));
+        } else {
+            let secret = user_two_factor_service
+                .get_secret(user_two_factor)
+                .map_err(|_| error::ErrorInternalServerError(""))?;
+            let issuer = translator_service.translate(lang, "app.name");
+            let uri = UserTwoFactorService::provisioning_uri(&issuer, &user.email, &secret);
+            let qr_code = UserTwoFactorService::qr_code_svg(&uri)
+                .map_err(|_| error::ErrorInternalServerError(""))?;
+            enrollment = Some(json!({
+                "text": translator_service.translate(lang, "page.two_factor.scan"),
+                "qr_code": qr_code,
+                "secret": {
+                    "label": translator_service.translate(lang, "page.two_factor.secret"),
+                    "value": secret,
+                },
+                "confirm": translator_service.translate(lang, "page.two_factor.confirm"),
+            }));
+        }
+    }
+
+    let ctx = json!({
+        "ctx": layout_ctx,
+        "heading": &heading,
+        "breadcrumbs": [
+            {"href": "/", "label": translator_service.translate(lang, "page.profile.breadcrumbs.home")},
+            {"href": "/profile", "label": translator_service.translate(lang, "page.profile.breadcrumbs.profile")},
+            {"label": &heading},
+        ],
+        "recovery_codes": recovery_codes.map(|codes| json!({
+            "text": translator_service.translate(lang, "page.two_factor.recovery_codes_text"),
+            "codes": codes,
+        })),
+        "disabled": if user_two_factor.is_none() {
+            Some(json!({
+                "text": translator_service.translate(lang, "page.two_factor.disabled"),
+                "enable": translator_service.translate(lang, "page.two_factor.enable"),
+            }))
+        } else {
+            None
+        },
+        "enrollment": enrollment,
+        "enabled": enabled,
+        "form": {
+            "action": get_url(),
+            "method": "post",
+            "fields": {
+                "code": { "label": code_str, "errors": errors.code },
+            },
+        },
+    });
+    let s = template_service.render_throw_http("pages/profile/two-factor.hbs", &ctx)?;
+    Ok(HttpResponse::Ok()
+        .clear_alerts()
+        .content_type(mime::TEXT_HTML_UTF_8.as_ref())
+        .body(s))
+}
+
+pub fn get_url() -> String {
+    "/profile/two-factor".to_string()
+}
+
+impl PostData {
+    pub fn prepare(&mut self) {
+        prepare_value!(self._token);
+        prepare_value!(self.action);
+        prepare_value!(self.code);
+    }
+}
+
+impl ErrorMessages {
+    pub fn is_empty(&self) -> bool {
+        self.form.len() == 0 && self.code.len() == 0
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{AlertVariant, FilePolicy, FileService, LocaleService, RateLimitService, RoleService, Session, TranslatorService, User, UserFileService, WebAuthService, WebHttpResponse};
+use crate::{AlertVariant, AuditLogAction, AuditLogEntity, AuditLogService, FilePolicy, FileService, LocaleService, RateLimitService, RoleService, Session, TranslatorService, User, UserFileService, WebAuthService, WebHttpResponse};
 use actix_web::{
     error,
     http::header::HeaderValue,
@@ -31,19 +31,21 @@ pub async fn invoke(
     web_auth_service: Data<WebAuthService>,
     rate_limit_service: Data<RateLimitService>,
     translator_service: Data<TranslatorService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     let web_auth_service = web_auth_service.get_ref();
     let rate_limit_service = rate_limit_service.get_ref();
     let locale_service = locale_service.get_ref();
     let role_service = role_service.get_ref();
     let user_file_service = user_file_service.get_ref();
+    let audit_log_service = audit_log_service.get_ref();
     let file_service = file_service.get_ref();
     let translator_service = translator_service.get_ref();
 
     web_auth_service.check_csrf_throw_http(&session, &data._token)?;
 
     let user_roles = role_service.all_throw_http()?;
-    if !FilePolicy::can_delete(&user, &user_roles) {
+    if !FilePolicy::can_delete_any_or_own(&user, &user_roles) {
         return Err(error::ErrorForbidden(""));
     }
 
@@ -51,6 +53,9 @@ pub async fn invoke(
     let user = user.as_ref();
     let lang: String = locale_service.get_locale_code(Some(&req), Some(&user));
     let delete_user_file = user_file_service.first_by_id_throw_http(user_file_id)?;
+    if !FilePolicy::can_delete_user_file(user, &user_roles, &delete_user_file) {
+        return Err(error::ErrorForbidden(""));
+    }
     let file = file_service.first_by_id_throw_http(delete_user_file.file_id)?;
 
     let rate_limit_key = rate_limit_service.make_key_from_request_throw_http(&req, RL_KEY)?;
@@ -66,6 +71,15 @@ pub async fn invoke(
         } else {
             if delete_user_file.is_deleted {
                 user_file_service.restore_by_id_throw_http(delete_user_file.id)?;
+                audit_log_service.record_throw_http(
+                    &req,
+                    user,
+                    AuditLogAction::Restore,
+                    AuditLogEntity::UserFile,
+                    delete_user_file.id,
+                    None,
+                    AuditLogService::to_value(&delete_user_file),
+                )?;
                 let name = format!("UserFileID:{}", delete_user_file.id);
                 alert_variants.push(AlertVariant::FilesRestoreSuccess(name));
             }
@@ -1,7 +1,8 @@
 use crate::app::controllers::web::{get_context_data, get_template_context};
 use crate::libs::actix_web::types::form::Form;
 use crate::{
-    prepare_value, Alert, AlertVariant, AppService, File, FilePolicy, FileService,
+    prepare_value, Alert, AlertVariant, AppService, AuditLogAction, AuditLogEntity,
+    AuditLogService, File, FilePolicy, FileService,
     RateLimitService, Role, RoleService, Session, TemplateService, TranslatableError,
     TranslatorService, User, UserFile, UserFileColumn, UserFileService, WebAuthService,
     WebHttpResponse,
@@ -49,13 +50,17 @@ pub async fn edit(
     role_service: Data<RoleService>,
     user_file_service: Data<UserFileService>,
     file_service: Data<FileService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     let user_roles: Vec<Role> = role_service.all_throw_http()?;
-    if !FilePolicy::can_update(&user, &user_roles) {
+    if !FilePolicy::can_update_any_or_own(&user, &user_roles) {
         return Err(error::ErrorForbidden(""));
     }
     let id = path.into_inner();
     let edit_user_file = user_file_service.get_ref().first_by_id_throw_http(id)?;
+    if !FilePolicy::can_update_user_file(&user, &user_roles, &edit_user_file) {
+        return Err(error::ErrorForbidden(""));
+    }
     let edit_file = file_service
         .get_ref()
         .first_by_id_throw_http(edit_user_file.file_id)?;
@@ -83,6 +88,7 @@ pub async fn edit(
         rate_limit_service,
         role_service,
         user_file_service,
+        audit_log_service,
     )
 }
 
@@ -100,13 +106,17 @@ pub async fn update(
     role_service: Data<RoleService>,
     user_file_service: Data<UserFileService>,
     file_service: Data<FileService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     let user_roles = role_service.all_throw_http()?;
-    if !FilePolicy::can_update(&user, &user_roles) {
+    if !FilePolicy::can_update_any_or_own(&user, &user_roles) {
         return Err(error::ErrorForbidden(""));
     }
     let id = path.into_inner();
     let edit_user_file = user_file_service.get_ref().first_by_id_throw_http(id)?;
+    if !FilePolicy::can_update_user_file(&user, &user_roles, &edit_user_file) {
+        return Err(error::ErrorForbidden(""));
+    }
     let edit_file = file_service
         .get_ref()
         .first_by_id_throw_http(edit_user_file.file_id)?;
@@ -124,6 +134,7 @@ pub async fn update(
         rate_limit_service,
         role_service,
         user_file_service,
+        audit_log_service,
     )
 }
 
@@ -141,6 +152,7 @@ pub fn invoke(
     rate_limit_service: Data<RateLimitService>,
     role_service: Data<RoleService>,
     user_file_service: Data<UserFileService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     data.prepare();
     //
@@ -150,6 +162,7 @@ pub fn invoke(
     let web_auth_service = web_auth_service.get_ref();
     let rate_limit_service = rate_limit_service.get_ref();
     let role_service = role_service.get_ref();
+    let audit_log_service = audit_log_service.get_ref();
 
     //
     let user = user.as_ref();
@@ -192,6 +205,7 @@ pub fn invoke(
             rate_limit_service.attempt_throw_http(&rate_limit_key, RL_MAX_ATTEMPTS, RL_TTL)?;
 
         if executed {
+            let old_user_file = AuditLogService::to_value(&user_file);
             if let Some(value) = &data.is_public {
                 user_file.is_public = value.eq("1");
             } else {
@@ -205,6 +219,15 @@ pub fn invoke(
                 if let Err(error) = result {
                     errors.form.push(error.translate(lang, translator_service));
                 } else {
+                    audit_log_service.record_throw_http(
+                        &req,
+                        user,
+                        AuditLogAction::Update,
+                        AuditLogEntity::UserFile,
+                        user_file.id,
+                        old_user_file,
+                        AuditLogService::to_value(&user_file),
+                    )?;
                     is_done = true;
                 }
             }
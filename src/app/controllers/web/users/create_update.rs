@@ -1,5 +1,6 @@
 use crate::app::controllers::web::profile::get_url as get_profile_url;
 use crate::app::controllers::web::{get_context_data, get_template_context};
+use crate::app::validator::password_policy::PasswordPolicy;
 use crate::app::validator::rules::bytes_mut_max_length::BytesMutMaxLength;
 use crate::app::validator::rules::confirmed::Confirmed;
 use crate::app::validator::rules::contains_str::ContainsStr;
@@ -9,7 +10,7 @@ use crate::app::validator::rules::mimes::Mimes;
 use crate::app::validator::rules::required::Required;
 use crate::app::validator::rules::str_max_chars_count::StrMaxCharsCount;
 use crate::app::validator::rules::str_min_max_chars_count::StrMinMaxCharsCount as MMCC;
-use crate::{assign_value_bytes_to_string, Alert, AlertVariant, AppService, FileService, Locale, LocaleService, RateLimitService, RoleService, Session, TemplateService, TranslatableError, TranslatorService, User, UserColumn, UserFileService, UserPolicy, UserService, UserServiceError, WebAuthService, WebHttpResponse, USER_AVATAR_MAX_SIZE, USER_AVATAR_MIMES};
+use crate::{assign_value_bytes_to_string, Alert, AlertVariant, AppService, AuditLogAction, Config, AuditLogEntity, AuditLogService, FileService, FileVariantService, FILE_VARIANT_THUMB, Locale, LocaleService, RateLimitService, RoleService, Session, TemplateService, TranslatableError, TranslatorService, User, UserColumn, UserFileService, UserPolicy, UserService, UserServiceError, UserTwoFactorService, WebAuthService, WebHttpResponse, InvitationService, LoginSecurityService, USER_AVATAR_MAX_SIZE, USER_AVATAR_MIMES, AUDIT_LOG_HIDDEN_VALUE};
 use actix_multipart::Multipart;
 use actix_web::http::header::HeaderValue;
 use actix_web::{
@@ -66,6 +67,8 @@ pub struct PostData {
     pub email: Option<String>,
     pub password: Option<String>,
     pub confirm_password: Option<String>,
+    // The new user sets the password by the link from the invitation letter.
+    pub invite: Option<String>,
     pub locale: Option<String>,
     pub surname: Option<String>,
     pub name: Option<String>,
@@ -131,6 +134,7 @@ pub struct InvokeData<'a> {
     pub role_service: Data<RoleService>,
     pub user_file_service: Data<UserFileService>,
     pub file_service: Data<FileService>,
+    pub audit_log_service: Data<AuditLogService>,
 }
 
 pub async fn create(
@@ -148,6 +152,7 @@ pub async fn create(
     role_service: Data<RoleService>,
     user_file_service: Data<UserFileService>,
     file_service: Data<FileService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     invoke(InvokeData {
         route: InvokeRoute::Create,
@@ -167,6 +172,7 @@ pub async fn create(
         role_service,
         user_file_service,
         file_service,
+        audit_log_service,
     })
     .await
 }
@@ -187,6 +193,7 @@ pub async fn store(
     role_service: Data<RoleService>,
     user_file_service: Data<UserFileService>,
     file_service: Data<FileService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     invoke(InvokeData {
         route: InvokeRoute::Store,
@@ -206,6 +213,7 @@ pub async fn store(
         role_service,
         user_file_service,
         file_service,
+        audit_log_service,
     })
     .await
 }
@@ -226,6 +234,7 @@ pub async fn edit(
     role_service: Data<RoleService>,
     user_file_service: Data<UserFileService>,
     file_service: Data<FileService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     let user_id = path.into_inner();
     let entity = user_service.get_ref().first_by_id_throw_http(user_id)?;
@@ -247,6 +256,7 @@ pub async fn edit(
         role_service,
         user_file_service,
         file_service,
+        audit_log_service,
     })
     .await
 }
@@ -269,6 +279,7 @@ pub async fn update(
     role_service: Data<RoleService>,
     user_file_service: Data<UserFileService>,
     file_service: Data<FileService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     let user_id = path.into_inner();
     let entity = user_service.get_ref().first_by_id_throw_http(user_id)?;
@@ -290,6 +301,7 @@ pub async fn update(
         role_service,
         user_file_service,
         file_service,
+        audit_log_service,
     })
     .await
 }
@@ -335,13 +347,39 @@ pub async fn invoke(
     let role_service = invoke_data.role_service.get_ref();
     let user_file_service = invoke_data.user_file_service.get_ref();
     let file_service = invoke_data.file_service.get_ref();
+    let audit_log_service = invoke_data.audit_log_service.get_ref();
+    // Handlers are already at the extractors limit, so the service is taken from the app data.
+    let user_two_factor_service: &Data<UserTwoFactorService> = req
+        .app_data::<Data<UserTwoFactorService>>()
+        .ok_or(error::ErrorInternalServerError(""))?;
+    let user_two_factor_service = Arc::clone(user_two_factor_service);
+    let file_variant_service: &Data<FileVariantService> = req
+        .app_data::<Data<FileVariantService>>()
+        .ok_or(error::ErrorInternalServerError(""))?;
+    let file_variant_service = Arc::clone(file_variant_service);
+    let invitation_service: &Data<InvitationService> = req
+        .app_data::<Data<InvitationService>>()
+        .ok_or(error::ErrorInternalServerError(""))?;
+    let invitation_service = Arc::clone(invitation_service);
+    let config: &Data<Config> = req
+        .app_data::<Data<Config>>()
+        .ok_or(error::ErrorInternalServerError(""))?;
+    let config = Arc::clone(config);
+    let login_security_service: &Data<LoginSecurityService> = req
+        .app_data::<Data<LoginSecurityService>>()
+        .ok_or(error::ErrorInternalServerError(""))?;
+    let login_security_service = Arc::clone(login_security_service);
 
     let user_roles = role_service.all_throw_http()?;
+    let can_update_entity = match &entity {
+        Some(entity) => UserPolicy::can_update_user(auth_user, &user_roles, entity),
+        None => false,
+    };
 
     if (route.eq(&InvokeRoute::Create) && !UserPolicy::can_create(auth_user, &user_roles))
         || (route.eq(&InvokeRoute::Store) && !UserPolicy::can_create(auth_user, &user_roles))
-        || (route.eq(&InvokeRoute::Edit) && !UserPolicy::can_update(auth_user, &user_roles))
-        || (route.eq(&InvokeRoute::Update) && !UserPolicy::can_update(auth_user, &user_roles))
+        || (route.eq(&InvokeRoute::Edit) && !can_update_entity)
+        || (route.eq(&InvokeRoute::Update) && !can_update_entity)
     {
         return Err(error::ErrorForbidden(""));
     }
@@ -382,6 +420,7 @@ pub async fn invoke(
     let locale_str = translator_service.translate(lang, "page.users.create.fields.locale");
     let roles_ids_str = translator_service.translate(lang, "page.users.create.fields.roles_ids");
     let avatar_str = translator_service.translate(lang, "page.users.create.fields.avatar");
+    let invite_str = translator_service.translate(lang, "page.users.create.fields.invite");
 
     let mut data: PostData = PostData::default();
 
@@ -401,6 +440,7 @@ pub async fn invoke(
                 &entity,
                 lang,
                 translator_service,
+                &config,
                 &email_str,
                 &password_str,
                 &confirm_password_str,
@@ -514,7 +554,7 @@ pub async fn invoke(
 
                 let upload_filename = avatar.filename;
                 let mime = avatar.mime;
-                let user_file = file_service.upload_bytes_file_to_local_disk(auth_user.id, bytes, true, upload_filename, mime)
+                let user_file = file_service.upload_bytes_file_to_disk(auth_user.id, bytes, true, upload_filename, mime)
                     .map_err(|_| error::ErrorInternalServerError(""))?;
                 columns.push(UserColumn::AvatarId);
                 user_data.avatar_id = Some(user_file.id);
@@ -540,8 +580,22 @@ pub async fn invoke(
                     user_service.update_password_by_email(&email, password)
                 };
 
+                if result.is_ok() {
+                    // The current session survives when users change their own password.
+                    if let Some(entity) = &entity {
+                        let except_token_id = if entity.id == auth_user.id {
+                            Some(auth_session.get_token_id())
+                        } else {
+                            None
+                        };
+                        web_auth_service.revoke_all_sessions_throw_http(entity.id, except_token_id)?;
+                    }
+                }
+
                 if let Err(error) = result {
-                    if error.eq(&UserServiceError::PasswordHashFail) {
+                    if error.eq(&UserServiceError::PasswordHashFail)
+                        || error.eq(&UserServiceError::PasswordReused)
+                    {
                         errors
                             .password
                             .push(error.translate(lang, translator_service));
@@ -551,6 +605,14 @@ pub async fn invoke(
                 }
             }
 
+            if errors.is_empty() && entity.is_none() && data.invite.is_some() {
+                let email = data.email.to_owned().unwrap();
+                let user = user_service.first_by_email_throw_http(&email)?;
+                if let Err(error) = invitation_service.invite(user.id, lang) {
+                    errors.form.push(error.translate(lang, translator_service));
+                }
+            }
+
             is_done = errors.is_empty();
         }
 
@@ -570,13 +632,35 @@ pub async fn invoke(
         if let Some(entity) = &entity {
             let user = user_service.first_by_id_throw_http(entity.id)?;
             id = user.id.to_string();
+            audit_log_service.record_throw_http(
+                &req,
+                auth_user,
+                AuditLogAction::Update,
+                AuditLogEntity::User,
+                user.id,
+                AuditLogService::to_value(entity),
+                audit_user_value(&user, &data.password),
+            )?;
             let name_ = user.get_full_name_with_id_and_email();
             alert_variants.push(AlertVariant::UsersUpdateSuccess(name_))
         } else if let Some(email_) = &data.email {
             let user = user_service.first_by_email_throw_http(email_)?;
             id = user.id.to_string();
+            audit_log_service.record_throw_http(
+                &req,
+                auth_user,
+                AuditLogAction::Create,
+                AuditLogEntity::User,
+                user.id,
+                None,
+                audit_user_value(&user, &data.password),
+            )?;
             let name_ = user.get_full_name_with_id_and_email();
-            alert_variants.push(AlertVariant::UsersCreateSuccess(name_))
+            if data.invite.is_some() {
+                alert_variants.push(AlertVariant::UsersInvitationSent(name_))
+            } else {
+                alert_variants.push(AlertVariant::UsersCreateSuccess(name_))
+            }
         }
 
         if let Some(action) = &data.action {
@@ -646,16 +730,23 @@ pub async fn invoke(
         if let Some(avatar_id) = &entity.avatar_id {
             if let Ok(user_file) = user_file_service.first_by_id(*avatar_id) {
                 if let Some(user_file) = user_file {
-                    avatar_src = user_file_service.get_public_path(&user_file);
+                    avatar_src = file_variant_service.get_public_path(&user_file, FILE_VARIANT_THUMB);
                 }
             }
         }
     }
 
+    let field_invite: Option<Value> = if entity.is_none() && !is_profile {
+        Some(json!({ "label": invite_str, "checked": data.invite.is_some() }))
+    } else {
+        None
+    };
+
     let fields = json!({
         "email": { "label": email_str, "value": &data.email, "errors": errors.email },
         "password": { "label": password_str, "value": &data.password, "errors": errors.password },
         "confirm_password": { "label": confirm_password_str, "value": &data.confirm_password, "errors": errors.confirm_password },
+        "invite": field_invite,
         "surname": { "label": surname_str, "value": &data.surname, "errors": errors.surname },
         "name": { "label": name_str, "value": &data.name, "errors": errors.name },
         "patronymic": { "label": patronymic_str, "value": &data.patronymic, "errors": errors.patronymic },
@@ -664,6 +755,101 @@ pub async fn invoke(
         "avatar": { "label": avatar_str, "errors": errors.avatar, "src": avatar_src },
     });
 
+    let api_tokens = if is_profile {
+        Some(json!({
+            "label": translator_service.translate(lang, "page.profile.api_tokens"),
+            "href": crate::app::controllers::web::api_tokens::index::get_url(),
+        }))
+    } else {
+        None
+    };
+
+    let two_factor = if is_profile {
+        Some(json!({
+            "label": translator_service.translate(lang, "page.profile.two_factor"),
+            "href": crate::app::controllers::web::two_factor::index::get_url(),
+        }))
+    } else {
+        None
+    };
+
+    let mut two_factor_reset: Option<Value> = None;
+    if !is_profile && auth_user.is_super_admin {
+        if let Some(entity) = &entity {
+            if user_two_factor_service.is_enabled_throw_http(entity.id)? {
+                two_factor_reset = Some(json!({
+                    "label": translator_service.translate(lang, "page.users.edit.two_factor_reset"),
+                    "confirm": translator_service.translate(lang, "page.users.edit.two_factor_reset_confirm"),
+                    "action": crate::app::controllers::web::users::two_factor_reset::get_url(entity.id.to_string().as_str()),
+                }));
+            }
+        }
+    }
+
+    let mut unlock: Option<Value> = None;
+    if !is_profile {
+        if let Some(entity) = &entity {
+            let locked_ttl = login_security_service
+                .locked_ttl(&entity.email)
+                .map_err(|_| error::ErrorInternalServerError(""))?;
+            if locked_ttl > 0 {
+                let minutes = locked_ttl.div_ceil(60).to_string();
+                let mut vars = HashMap::new();
+                vars.insert("minutes", minutes.as_str());
+                unlock = Some(json!({
+                    "label": translator_service.translate(lang, "page.users.edit.unlock"),
+                    "confirm": translator_service.variables(lang, "page.users.edit.unlock_confirm", &vars),
+                    "action": crate::app::controllers::web::users::unlock::get_url(entity.id.to_string().as_str()),
+                }));
+            }
+        }
+    }
+
+    let mut sessions: Option<Value> = None;
+    if let Some(entity) = &entity {
+        let revoke_action = if is_profile {
+            crate::app::controllers::web::users::sessions::get_profile_revoke_url()
+        } else {
+            crate::app::controllers::web::users::sessions::get_revoke_url(entity.id.to_string().as_str())
+        };
+        let is_own = entity.id == auth_user.id;
+        let current_label = translator_service.translate(lang, "page.sessions.current");
+        let items: Vec<Value> = web_auth_service
+            .all_sessions_throw_http(entity.id)?
+            .iter()
+            .map(|session| {
+                let is_current = is_own && session.token_id == auth_session.get_token_id();
+                json!({
+                    "token_id": session.token_id,
+                    "created_at": session.created_at,
+                    "last_activity_at": session.last_activity_at,
+                    "ip": session.ip,
+                    "user_agent": session.user_agent,
+                    "expires_at": session.expires_at,
+                    "current": if is_current { Some(&current_label) } else { None },
+                })
+            })
+            .collect();
+        sessions = Some(json!({
+            "heading": translator_service.translate(lang, "page.sessions.header"),
+            "action": revoke_action,
+            "columns": {
+                "created_at": translator_service.translate(lang, "page.sessions.columns.created_at"),
+                "last_activity_at": translator_service.translate(lang, "page.sessions.columns.last_activity_at"),
+                "ip": translator_service.translate(lang, "page.sessions.columns.ip"),
+                "user_agent": translator_service.translate(lang, "page.sessions.columns.user_agent"),
+                "expires_at": translator_service.translate(lang, "page.sessions.columns.expires_at"),
+                "actions": translator_service.translate(lang, "page.sessions.columns.actions"),
+            },
+            "empty": translator_service.translate(lang, "page.sessions.empty"),
+            "revoke": translator_service.translate(lang, "page.sessions.revoke"),
+            "revoke_confirm": translator_service.translate(lang, "page.sessions.revoke_confirm"),
+            "revoke_all": translator_service.translate(lang, if is_own { "page.sessions.revoke_others" } else { "page.sessions.revoke_all" }),
+            "revoke_all_confirm": translator_service.translate(lang, "page.sessions.revoke_all_confirm"),
+            "items": items,
+        }));
+    }
+
     let (breadcrumbs, save_and_close, close) = if is_profile {
         let breadcrumbs = json!([
             {"href": "/", "label": translator_service.translate(lang, "page.profile.breadcrumbs.home")},
@@ -692,6 +878,11 @@ pub async fn invoke(
             "extended": translator_service.translate(lang, "page.users.create.tabs.extended"),
         },
         "breadcrumbs": breadcrumbs,
+        "api_tokens": api_tokens,
+        "two_factor": two_factor,
+        "two_factor_reset": two_factor_reset,
+        "unlock": unlock,
+        "sessions": sessions,
         "form": {
             "action": &action,
             "method": "post",
@@ -708,6 +899,17 @@ pub async fn invoke(
         .body(s))
 }
 
+/// The user state for the audit log, a changed password is marked without its value.
+pub fn audit_user_value(user: &User, password: &Option<String>) -> Option<Value> {
+    let mut value = AuditLogService::to_value(user);
+    if password.is_some() {
+        if let Some(Value::Object(map)) = &mut value {
+            map.insert("password".to_string(), json!(AUDIT_LOG_HIDDEN_VALUE));
+        }
+    }
+    value
+}
+
 pub fn get_create_url() -> String {
     "/users/create".to_string()
 }
@@ -734,6 +936,7 @@ impl PostData {
         entity: &Option<User>,
         lang: &str,
         translator_service: &TranslatorService,
+        config: &Config,
         email_str: &str,
         password_str: &str,
         confirm_password_str: &str,
@@ -821,6 +1024,7 @@ impl PostData {
                 "confirm_password" => {
                     assign_value_bytes_to_string!(bytes, self.confirm_password)
                 }
+                "invite" => assign_value_bytes_to_string!(bytes, self.invite),
                 "locale" => assign_value_bytes_to_string!(bytes, self.locale),
                 "surname" => assign_value_bytes_to_string!(bytes, self.surname),
                 "name" => assign_value_bytes_to_string!(bytes, self.name),
@@ -886,26 +1090,55 @@ impl PostData {
         );
         errors.email.append(&mut errors_);
 
+        // The invited user chooses the password, so the typed one is ignored.
+        if entity.is_some() {
+            self.invite = None;
+        }
+        if self.invite.is_some() {
+            self.password = None;
+            self.confirm_password = None;
+        }
+
         // Validation password
-        if entity.is_none() {
+        let email = self.email.as_deref().unwrap_or("");
+        let surname = self.surname.as_deref().unwrap_or("");
+        let name = self.name.as_deref().unwrap_or("");
+        let patronymic = self.patronymic.as_deref().unwrap_or("");
+        let password_inputs = [email, surname, name, patronymic];
+        if entity.is_none() && self.invite.is_none() {
             let mut errors_: Vec<String> = Required::validated(
                 translator_service,
                 lang,
                 &self.password,
-                |value| MMCC::validate(translator_service, lang, value, 4, 255, password_str),
+                |value| {
+                    PasswordPolicy::validate(
+                        translator_service,
+                        lang,
+                        &config.auth.password,
+                        value,
+                        &password_inputs,
+                        password_str,
+                    )
+                },
                 password_str,
             );
             errors.password.append(&mut errors_);
         } else {
             if let Some(password) = &self.password {
-                let mut errors_: Vec<String> =
-                    MMCC::validate(translator_service, lang, password, 4, 255, password_str);
+                let mut errors_: Vec<String> = PasswordPolicy::validate(
+                    translator_service,
+                    lang,
+                    &config.auth.password,
+                    password,
+                    &password_inputs,
+                    password_str,
+                );
                 errors.password.append(&mut errors_);
             }
         }
 
         // Validation confirm_password
-        if entity.is_none() || self.password.is_some() {
+        if (entity.is_none() && self.invite.is_none()) || self.password.is_some() {
             let mut errors_: Vec<String> = Required::validated(
                 translator_service,
                 lang,
@@ -1,5 +1,5 @@
 use crate::{
-    AlertVariant, LocaleService, RateLimitService, RoleService, Session, TranslatorService, User,
+    AlertVariant, AuditLogAction, AuditLogEntity, AuditLogService, LocaleService, RateLimitService, RoleService, Session, TranslatorService, User,
     UserPolicy, UserService, WebAuthService, WebHttpResponse,
 };
 use actix_web::{web::{Data, Form, Path, ReqData}, error, Error, HttpRequest, HttpResponse, Result, http::header::{HeaderValue, ORIGIN, REFERER, LOCATION}};
@@ -27,6 +27,7 @@ pub async fn invoke(
     rate_limit_service: Data<RateLimitService>,
     translator_service: Data<TranslatorService>,
     role_service: Data<RoleService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     let web_auth_service = web_auth_service.get_ref();
     let rate_limit_service = rate_limit_service.get_ref();
@@ -34,6 +35,7 @@ pub async fn invoke(
     let user_service = user_service.get_ref();
     let translator_service = translator_service.get_ref();
     let role_service = role_service.get_ref();
+    let audit_log_service = audit_log_service.get_ref();
 
     web_auth_service.check_csrf_throw_http(&session, &data._token)?;
 
@@ -55,6 +57,15 @@ pub async fn invoke(
 
     if executed {
         user_service.delete_by_id_throw_http(delete_user.id)?;
+        audit_log_service.record_throw_http(
+            &req,
+            user,
+            AuditLogAction::Delete,
+            AuditLogEntity::User,
+            delete_user.id,
+            AuditLogService::to_value(&delete_user),
+            None,
+        )?;
         let name = delete_user.get_full_name_with_id_and_email();
         alert_variants.push(AlertVariant::UsersDeleteSuccess(name));
     } else {
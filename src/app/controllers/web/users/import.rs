@@ -0,0 +1,454 @@
+use crate::app::controllers::web::{get_context_data, get_template_context, ContextData};
+use crate::app::validator::rules::bytes_mut_max_length::BytesMutMaxLength;
+use crate::libs::actix_web::types::form::Form;
+use crate::{
+    assign_value_bytes_to_string, Alert, AlertVariant, AppService, AuditLogAction, AuditLogEntity,
+    AuditLogService, LocaleService, RateLimitService, Role, RoleService, Session, TemplateService,
+    TranslatableError, TranslatorService, User, UserImportPreview, UserImportService,
+    UserImportStatus, UserPolicy, WebAuthService, WebHttpResponse, USER_IMPORT_MAX_ROWS,
+    USER_IMPORT_MAX_SIZE,
+};
+use actix_multipart::Multipart;
+use actix_web::http::header::{HeaderValue, LOCATION};
+use actix_web::web::{Data, ReqData};
+use actix_web::{error, Error, HttpRequest, HttpResponse, Result};
+use bytes::{Bytes, BytesMut};
+use futures_util::{StreamExt, TryStreamExt};
+use serde_derive::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const RL_MAX_ATTEMPTS: u64 = 10;
+const RL_TTL: u64 = 60;
+const RL_KEY: &'static str = "users_import";
+
+#[derive(Default, Debug)]
+pub struct UploadData {
+    pub _token: Option<String>,
+    pub invite: Option<String>,
+    pub file: Option<Bytes>,
+}
+
+#[derive(Deserialize, Default, Debug)]
+pub struct PostData {
+    pub _token: Option<String>,
+    // The staged file of the preview.
+    pub key: Option<String>,
+    pub invite: Option<String>,
+}
+
+#[derive(Default, Debug)]
+struct ErrorMessages {
+    pub form: Vec<String>,
+    pub file: Vec<String>,
+}
+
+/// The users can be created and updated by the import only with the both permissions.
+fn can_import(user: &User, user_roles: &Vec<Role>) -> bool {
+    UserPolicy::can_create(user, user_roles) && UserPolicy::can_update(user, user_roles)
+}
+
+/// The upload form.
+pub async fn show(
+    req: HttpRequest,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    translator_service: Data<TranslatorService>,
+    template_service: Data<TemplateService>,
+    app_service: Data<AppService>,
+    web_auth_service: Data<WebAuthService>,
+    role_service: Data<RoleService>,
+) -> Result<HttpResponse, Error> {
+    let user = user.as_ref();
+    let user_roles = role_service.get_ref().all_throw_http()?;
+    if !can_import(user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+
+    let context_data = get_context_data(
+        &req,
+        user,
+        &session,
+        translator_service.get_ref(),
+        app_service.get_ref(),
+        web_auth_service.get_ref(),
+        role_service.get_ref(),
+    );
+    render(
+        context_data,
+        translator_service.get_ref(),
+        template_service.get_ref(),
+        ErrorMessages::default(),
+        false,
+        None,
+    )
+}
+
+/// Parses the file and shows the dry run: what is created, updated and skipped with the errors of every row.
+pub async fn preview(
+    req: HttpRequest,
+    payload: Multipart,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    translator_service: Data<TranslatorService>,
+    template_service: Data<TemplateService>,
+    app_service: Data<AppService>,
+    web_auth_service: Data<WebAuthService>,
+    rate_limit_service: Data<RateLimitService>,
+    role_service: Data<RoleService>,
+    user_import_service: Data<UserImportService>,
+) -> Result<HttpResponse, Error> {
+    let translator_service = translator_service.get_ref();
+    let rate_limit_service = rate_limit_service.get_ref();
+    let user_import_service = user_import_service.get_ref();
+    let user = user.as_ref();
+
+    let user_roles = role_service.get_ref().all_throw_http()?;
+    if !can_import(user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+
+    let mut context_data = get_context_data(
+        &req,
+        user,
+        &session,
+        translator_service,
+        app_service.get_ref(),
+        web_auth_service.get_ref(),
+        role_service.get_ref(),
+    );
+    let lang = context_data.lang.to_owned();
+    let file_str = translator_service.translate(&lang, "page.users.import.fields.file");
+
+    let mut errors = ErrorMessages::default();
+    let data = UploadData::prepare_from_multipart(
+        payload,
+        &lang,
+        translator_service,
+        &file_str,
+        &mut errors,
+    )
+    .await?;
+
+    web_auth_service
+        .get_ref()
+        .check_csrf_throw_http(&session, &data._token)?;
+
+    let rate_limit_key = rate_limit_service.make_key_from_request_throw_http(&req, RL_KEY)?;
+    let executed =
+        rate_limit_service.attempt_throw_http(&rate_limit_key, RL_MAX_ATTEMPTS, RL_TTL)?;
+    if !executed {
+        let ttl_message =
+            rate_limit_service.ttl_message_throw_http(translator_service, &lang, &rate_limit_key)?;
+        errors.form.push(ttl_message);
+    }
+
+    let is_invite = data.invite.is_some();
+    let mut preview: Option<(String, UserImportPreview)> = None;
+    if let (true, Some(bytes)) = (errors.form.is_empty() && errors.file.is_empty(), &data.file) {
+        match user_import_service.parse(bytes) {
+            Ok(file) => {
+                let can_set_roles = UserPolicy::can_set_roles(user, &user_roles);
+                let result = user_import_service.preview_throw_http(&file, &lang, can_set_roles)?;
+                let key = user_import_service.stage_throw_http(user, &file)?;
+                preview = Some((key, result));
+            }
+            Err(e) => errors.file.push(e.translate(&lang, translator_service)),
+        }
+    }
+
+    for form_error in errors.form.drain(..) {
+        context_data.alerts.push(Alert::error(form_error));
+    }
+    render(
+        context_data,
+        translator_service,
+        template_service.get_ref(),
+        errors,
+        is_invite,
+        preview,
+    )
+}
+
+/// Imports the staged file of the preview.
+pub async fn store(
+    req: HttpRequest,
+    data: Form<PostData>,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    locale_service: Data<LocaleService>,
+    web_auth_service: Data<WebAuthService>,
+    rate_limit_service: Data<RateLimitService>,
+    translator_service: Data<TranslatorService>,
+    role_service: Data<RoleService>,
+    audit_log_service: Data<AuditLogService>,
+    user_import_service: Data<UserImportService>,
+) -> Result<HttpResponse, Error> {
+    let rate_limit_service = rate_limit_service.get_ref();
+    let audit_log_service = audit_log_service.get_ref();
+    let user_import_service = user_import_service.get_ref();
+
+    web_auth_service
+        .get_ref()
+        .check_csrf_throw_http(&session, &data._token)?;
+
+    let user = user.as_ref();
+    let user_roles = role_service.get_ref().all_throw_http()?;
+    if !can_import(user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+
+    let lang: String = locale_service.get_ref().get_locale_code(Some(&req), Some(user));
+    let key = data.key.as_deref().ok_or(error::ErrorBadRequest(""))?;
+    let rate_limit_key = rate_limit_service.make_key_from_request_throw_http(&req, RL_KEY)?;
+
+    let mut alert_variants = Vec::new();
+    let executed =
+        rate_limit_service.attempt_throw_http(&rate_limit_key, RL_MAX_ATTEMPTS, RL_TTL)?;
+
+    if executed {
+        let file = user_import_service.first_staged_throw_http(user, key)?;
+        let can_set_roles = UserPolicy::can_set_roles(user, &user_roles);
+        let result = user_import_service.import_throw_http(
+            &file,
+            &lang,
+            can_set_roles,
+            data.invite.is_some(),
+        )?;
+        user_import_service.forget_staged(user, key);
+
+        for created in &result.created {
+            audit_log_service.record_throw_http(
+                &req,
+                user,
+                AuditLogAction::Create,
+                AuditLogEntity::User,
+                created.id,
+                None,
+                AuditLogService::to_value(created),
+            )?;
+        }
+        for (old_user, new_user) in &result.updated {
+            audit_log_service.record_throw_http(
+                &req,
+                user,
+                AuditLogAction::Update,
+                AuditLogEntity::User,
+                new_user.id,
+                AuditLogService::to_value(old_user),
+                AuditLogService::to_value(new_user),
+            )?;
+        }
+
+        alert_variants.push(AlertVariant::UsersImportSuccess(
+            result.created.len().to_string(),
+            result.updated.len().to_string(),
+            result.skipped.to_string(),
+        ));
+        if !result.failed_lines.is_empty() {
+            let lines: Vec<String> = result.failed_lines.iter().map(|l| l.to_string()).collect();
+            alert_variants.push(AlertVariant::UsersImportFail(lines.join(", ")));
+        }
+        rate_limit_service.clear_throw_http(&rate_limit_key)?;
+    } else {
+        let alert_variant = rate_limit_service.alert_variant_throw_http(
+            translator_service.get_ref(),
+            &lang,
+            &rate_limit_key,
+        )?;
+        alert_variants.push(alert_variant);
+
+        return Ok(HttpResponse::SeeOther()
+            .set_alerts(alert_variants)
+            .insert_header((
+                LOCATION,
+                HeaderValue::from_str(&get_url()).map_err(|_| error::ErrorInternalServerError(""))?,
+            ))
+            .finish());
+    }
+
+    Ok(HttpResponse::SeeOther()
+        .set_alerts(alert_variants)
+        .insert_header((LOCATION, HeaderValue::from_static("/users")))
+        .finish())
+}
+
+fn render(
+    context_data: ContextData,
+    translator_service: &TranslatorService,
+    template_service: &TemplateService,
+    errors: ErrorMessages,
+    is_invite: bool,
+    preview: Option<(String, UserImportPreview)>,
+) -> Result<HttpResponse, Error> {
+    let lang = context_data.lang.to_owned();
+    let lang = &lang;
+    let mut context_data = context_data;
+    context_data.title = translator_service.translate(lang, "page.users.import.title");
+    let layout_ctx = get_template_context(&context_data);
+
+    let max_rows = USER_IMPORT_MAX_ROWS.to_string();
+    let mut vars = HashMap::new();
+    vars.insert("max_rows", max_rows.as_str());
+
+    let preview: Option<Value> = preview.map(|(key, preview)| {
+        let status_str = |status: &UserImportStatus| {
+            translator_service.translate(lang, &format!("page.users.import.statuses.{}", status))
+        };
+        let records: Vec<Value> = preview
+            .rows
+            .iter()
+            .map(|preview_row| {
+                let row = &preview_row.row;
+                json!({
+                    "line": row.line,
+                    "email": &row.email,
+                    "surname": &row.surname,
+                    "name": &row.name,
+                    "patronymic": &row.patronymic,
+                    "locale": &row.locale,
+                    "roles": row.roles.join(", "),
+                    "status": status_str(&preview_row.status),
+                    "is_skipped": preview_row.status.eq(&UserImportStatus::Skip),
+                    "errors": &preview_row.errors,
+                })
+            })
+            .collect();
+
+        let created = preview.created.to_string();
+        let updated = preview.updated.to_string();
+        let skipped = preview.skipped.to_string();
+        let mut vars = HashMap::new();
+        vars.insert("created", created.as_str());
+        vars.insert("updated", updated.as_str());
+        vars.insert("skipped", skipped.as_str());
+
+        json!({
+            "action": get_confirm_url(),
+            "key": key,
+            "invite": is_invite,
+            "summary": translator_service.variables(lang, "page.users.import.summary", &vars),
+            "is_empty": preview.created == 0 && preview.updated == 0,
+            "columns": {
+                "line": translator_service.translate(lang, "page.users.import.columns.line"),
+                "email": translator_service.translate(lang, "page.users.index.columns.email"),
+                "surname": translator_service.translate(lang, "page.users.index.columns.surname"),
+                "name": translator_service.translate(lang, "page.users.index.columns.name"),
+                "patronymic": translator_service.translate(lang, "page.users.index.columns.patronymic"),
+                "locale": translator_service.translate(lang, "page.users.index.columns.locale"),
+                "roles": translator_service.translate(lang, "page.users.index.columns.roles"),
+                "status": translator_service.translate(lang, "page.users.import.columns.status"),
+            },
+            "records": records,
+            "submit": translator_service.translate(lang, "page.users.import.submit"),
+        })
+    });
+
+    let ctx = json!({
+        "ctx": &layout_ctx,
+        "heading": translator_service.translate(lang, "page.users.import.header"),
+        "breadcrumbs": [
+            {"href": "/", "label": translator_service.translate(lang, "page.home.header")},
+            {"href": "/users", "label": translator_service.translate(lang, "page.users.index.header")},
+            {"label": translator_service.translate(lang, "page.users.import.header")},
+        ],
+        "form": {
+            "action": get_url(),
+            "description": translator_service.variables(lang, "page.users.import.description", &vars),
+            "fields": {
+                "file": {
+                    "label": translator_service.translate(lang, "page.users.import.fields.file"),
+                    "errors": errors.file,
+                },
+                "invite": {
+                    "label": translator_service.translate(lang, "page.users.import.fields.invite"),
+                    "checked": is_invite,
+                },
+            },
+            "submit": translator_service.translate(lang, "page.users.import.preview"),
+            "close": {
+                "label": translator_service.translate(lang, "Close"),
+                "href": "/users",
+            },
+        },
+        "preview": preview,
+    });
+
+    let s = template_service.render_throw_http("pages/users/import.hbs", &ctx)?;
+    Ok(HttpResponse::Ok()
+        .clear_alerts()
+        .content_type(mime::TEXT_HTML_UTF_8.as_ref())
+        .body(s))
+}
+
+pub fn get_url() -> String {
+    "/users/import".to_string()
+}
+
+pub fn get_confirm_url() -> String {
+    let mut str_ = get_url();
+    str_.push_str("/confirm");
+    str_
+}
+
+impl UploadData {
+    async fn prepare_from_multipart(
+        mut payload: Multipart,
+        lang: &str,
+        translator_service: &TranslatorService,
+        file_str: &str,
+        errors: &mut ErrorMessages,
+    ) -> Result<Self, Error> {
+        let mut data = Self::default();
+
+        while let Ok(Some(mut field)) = payload.try_next().await {
+            let field_name = field
+                .content_disposition()
+                .and_then(|content_disposition| content_disposition.get_name())
+                .map(|field_name| field_name.to_string())
+                .ok_or(error::ErrorBadRequest(""))?;
+
+            let mut bytes = BytesMut::new();
+            while let Some(chunk) = field.next().await {
+                let chunk = chunk.map_err(|_| error::ErrorBadRequest(""))?;
+                bytes.extend_from_slice(&chunk);
+                if field_name.eq("file") {
+                    let mut errors_ = BytesMutMaxLength::validate(
+                        translator_service,
+                        lang,
+                        &bytes,
+                        USER_IMPORT_MAX_SIZE,
+                        file_str,
+                    );
+                    if !errors_.is_empty() {
+                        errors.file.append(&mut errors_);
+                        bytes.clear();
+                        break;
+                    }
+                } else if !BytesMutMaxLength::apply(&bytes, 400) {
+                    bytes.clear();
+                    break;
+                }
+            }
+            let bytes = bytes.freeze();
+
+            match field_name.as_str() {
+                "_token" => assign_value_bytes_to_string!(bytes, data._token),
+                "invite" => assign_value_bytes_to_string!(bytes, data.invite),
+                "file" if !bytes.is_empty() => data.file = Some(bytes),
+                _ => {}
+            }
+        }
+
+        if data.file.is_none() && errors.file.is_empty() {
+            let mut vars = HashMap::new();
+            vars.insert("attribute", file_str);
+            errors
+                .file
+                .push(translator_service.variables(lang, "validation.required", &vars));
+        }
+
+        Ok(data)
+    }
+}
@@ -1,5 +1,5 @@
 use crate::app::controllers::web::{
-    generate_2_offset_pagination_array, get_context_data, get_template_context,
+    exports, generate_2_offset_pagination_array, get_context_data, get_template_context,
 };
 use crate::{
     prepare_paginate, prepare_value, validation_query_max_length_string, Alert, AppService,
@@ -17,6 +17,7 @@ use std::sync::Arc;
 use strum::IntoEnumIterator;
 
 const PAGE_URL: &'static str = "/users?";
+const EXPORT_URL: &'static str = "/users/export?";
 
 pub const DEFAULT_PER_PAGE: i64 = 15;
 pub const MAX_PER_PAGE: i64 = 100;
@@ -146,8 +147,12 @@ pub async fn invoke(
         sort_options.push(json!({ "label": label, "value": value }));
     }
 
-    let mut selected: Option<Value> = None;
+    let mut selected: Value = json!({
+        "label": translator_service.translate(lang, "Selected"),
+    });
     let mut create: Option<Value> = None;
+    let mut invitations: Option<Value> = None;
+    let mut import: Option<Value> = None;
     let mut edit: Option<Value> = None;
     let mut delete: Option<Value> = None;
 
@@ -156,21 +161,42 @@ pub async fn invoke(
             "label": translator_service.translate(lang, "Create user"),
             "href": "/users/create"
         }));
+        invitations = Some(json!({
+            "label": translator_service.translate(lang, "page.users.invitations.header"),
+            "href": crate::app::controllers::web::users::invitations::get_url(),
+        }));
     }
 
-    if UserPolicy::can_update(&user, &user_roles) {
+    if UserPolicy::can_create(user, &user_roles) && UserPolicy::can_update(user, &user_roles) {
+        import = Some(json!({
+            "label": translator_service.translate(lang, "page.users.import.header"),
+            "href": crate::app::controllers::web::users::import::get_url(),
+        }));
+    }
+
+    if UserPolicy::can_update(user, &user_roles)
+        || UserPolicy::can_update_same_roles(user, &user_roles)
+    {
         edit = Some(json!({
             "label": translator_service.translate(lang, "Edit user"),
             "href": "/users/:id"
         }));
     }
 
+    // The users sharing no role with the manager are listed without the edit button.
+    let records: Vec<Value> = users
+        .records
+        .iter()
+        .map(|record| {
+            let mut value = json!(record);
+            value["can_edit"] = json!(UserPolicy::can_update_user(user, &user_roles, record));
+            value
+        })
+        .collect();
+
     if UserPolicy::can_delete(&user, &user_roles) {
-        selected = Some(json!({
-            "label": translator_service.translate(lang, "Selected"),
-            "delete": translator_service.translate(lang, "Delete selected"),
-            "delete_confirm": translator_service.translate(lang, "Delete selected?"),
-        }));
+        selected["delete"] = json!(translator_service.translate(lang, "Delete selected"));
+        selected["delete_confirm"] = json!(translator_service.translate(lang, "Delete selected?"));
         delete = Some(json!({
             "action": "/users/:id/delete",
             "method": "post",
@@ -188,6 +214,8 @@ pub async fn invoke(
             {"label": translator_service.variables(lang, "Page :page of :total_pages", &page_vars)},
         ],
         "create": create,
+        "invitations": invitations,
+        "import": import,
         "edit": edit,
         "delete": delete,
         "page_per_page": translator_service.variables(lang, "Page :page of :total_pages", &page_vars),
@@ -199,6 +227,12 @@ pub async fn invoke(
             "options": &sort_options
         },
         "selected": selected,
+        "export": exports::get_template_context(
+            translator_service,
+            lang,
+            &query.clone().remove_page().remove_per_page().to_export_url()?,
+            "/users/export",
+        ),
         "columns": {
             "id": translator_service.translate(lang, "page.users.index.columns.id"),
             "email": translator_service.translate(lang, "page.users.index.columns.email"),
@@ -213,7 +247,7 @@ pub async fn invoke(
             "per_page": users.per_page,
             "total_pages": total_pages,
             "total_records": users.total_records,
-            "records": users.records,
+            "records": records,
             "pagination_nums": pagination_nums,
             "pagination_link": pagination_link
         },
@@ -334,6 +368,15 @@ impl IndexQuery {
         result.push_str(&url);
         Ok(result)
     }
+    pub fn to_export_url(&self) -> Result<String, Error> {
+        let url = serde_urlencoded::to_string(self).map_err(|e| {
+            log::error!("app::controllers::web::users::index::IndexQuery::to_export_url - {e}");
+            error::ErrorInternalServerError("")
+        })?;
+        let mut result = EXPORT_URL.to_string();
+        result.push_str(&url);
+        Ok(result)
+    }
     pub fn get_filters(&self) -> Vec<UserFilter> {
         let mut filters: Vec<UserFilter> = Vec::new();
 
@@ -0,0 +1,274 @@
+use crate::app::controllers::web::{get_context_data, get_template_context};
+use crate::helpers::DATE_TIME_FORMAT;
+use crate::{
+    AlertVariant, AppService, AuditLogAction, AuditLogEntity, AuditLogService, InvitationService,
+    LocaleService, RateLimitService, RoleService, Session, TemplateService, TranslatorService, User,
+    UserPolicy, UserService, WebAuthService, WebHttpResponse,
+};
+use actix_web::web::{Data, Form, Path, ReqData};
+use actix_web::{error, Error, HttpRequest, HttpResponse, Result, http::header::{HeaderValue, LOCATION}};
+use chrono::DateTime;
+use serde_derive::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+const RL_MAX_ATTEMPTS: u64 = 30;
+const RL_TTL: u64 = 60;
+const RL_KEY: &'static str = "users_invitations";
+
+#[derive(Deserialize, Default, Debug)]
+pub struct PostData {
+    pub _token: Option<String>,
+}
+
+/// The users who have not accepted the invitation yet.
+pub async fn index(
+    req: HttpRequest,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    translator_service: Data<TranslatorService>,
+    tmpl_service: Data<TemplateService>,
+    app_service: Data<AppService>,
+    web_auth_service: Data<WebAuthService>,
+    role_service: Data<RoleService>,
+    invitation_service: Data<InvitationService>,
+) -> Result<HttpResponse, Error> {
+    let translator_service = translator_service.get_ref();
+    let tmpl_service = tmpl_service.get_ref();
+    let app_service = app_service.get_ref();
+    let web_auth_service = web_auth_service.get_ref();
+    let role_service = role_service.get_ref();
+    let invitation_service = invitation_service.get_ref();
+    let user = user.as_ref();
+
+    let user_roles = role_service.all_throw_http()?;
+    if !UserPolicy::can_create(user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+
+    let mut context_data = get_context_data(
+        &req,
+        user,
+        &session,
+        translator_service,
+        app_service,
+        web_auth_service,
+        role_service,
+    );
+    let lang = context_data.lang.to_owned();
+    let lang = &lang;
+    context_data.title = translator_service.translate(lang, "page.users.invitations.title");
+    let layout_ctx = get_template_context(&context_data);
+
+    let pending_str = translator_service.translate(lang, "page.users.invitations.statuses.pending");
+    let expired_str = translator_service.translate(lang, "page.users.invitations.statuses.expired");
+
+    let records: Vec<Value> = invitation_service
+        .all_pending_throw_http()?
+        .iter()
+        .map(|invited_user| {
+            let id = invited_user.id.to_string();
+            let expires_at = invitation_service
+                .get_expires_at(invited_user)
+                .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+                .map(|date_time| date_time.format(DATE_TIME_FORMAT).to_string());
+            let is_expired = invitation_service.is_expired(invited_user);
+            json!({
+                "id": invited_user.id,
+                "name": invited_user.get_full_name_with_id_and_email(),
+                "invited_at": &invited_user.invited_at,
+                "expires_at": expires_at,
+                "status": if is_expired { &expired_str } else { &pending_str },
+                "is_expired": is_expired,
+                "resend_action": get_resend_url(&id),
+                "revoke_action": get_revoke_url(&id),
+            })
+        })
+        .collect();
+
+    let ctx = json!({
+        "ctx": &layout_ctx,
+        "heading": translator_service.translate(lang, "page.users.invitations.header"),
+        "breadcrumbs": [
+            {"href": "/", "label": translator_service.translate(lang, "page.home.header")},
+            {"href": "/users", "label": translator_service.translate(lang, "page.users.index.header")},
+            {"label": translator_service.translate(lang, "page.users.invitations.header")},
+        ],
+        "create": {
+            "label": translator_service.translate(lang, "page.users.invitations.create"),
+            "href": crate::app::controllers::web::users::create_update::get_create_url(),
+        },
+        "columns": {
+            "user": translator_service.translate(lang, "page.users.invitations.columns.user"),
+            "invited_at": translator_service.translate(lang, "page.users.invitations.columns.invited_at"),
+            "expires_at": translator_service.translate(lang, "page.users.invitations.columns.expires_at"),
+            "status": translator_service.translate(lang, "page.users.invitations.columns.status"),
+            "actions": translator_service.translate(lang, "page.users.invitations.columns.actions"),
+        },
+        "records": records,
+        "empty": translator_service.translate(lang, "page.users.invitations.empty"),
+        "resend": translator_service.translate(lang, "page.users.invitations.resend"),
+        "resend_confirm": translator_service.translate(lang, "page.users.invitations.resend_confirm"),
+        "revoke": translator_service.translate(lang, "page.users.invitations.revoke"),
+        "revoke_confirm": translator_service.translate(lang, "page.users.invitations.revoke_confirm"),
+    });
+
+    let s = tmpl_service.render_throw_http("pages/users/invitations.hbs", &ctx)?;
+    Ok(HttpResponse::Ok()
+        .clear_alerts()
+        .content_type(mime::TEXT_HTML_UTF_8.as_ref())
+        .body(s))
+}
+
+/// Sends a new link, the previous one stops working.
+pub async fn resend(
+    req: HttpRequest,
+    path: Path<u64>,
+    data: Form<PostData>,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    user_service: Data<UserService>,
+    locale_service: Data<LocaleService>,
+    web_auth_service: Data<WebAuthService>,
+    rate_limit_service: Data<RateLimitService>,
+    translator_service: Data<TranslatorService>,
+    role_service: Data<RoleService>,
+    invitation_service: Data<InvitationService>,
+) -> Result<HttpResponse, Error> {
+    let web_auth_service = web_auth_service.get_ref();
+    let rate_limit_service = rate_limit_service.get_ref();
+    let locale_service = locale_service.get_ref();
+    let user_service = user_service.get_ref();
+    let translator_service = translator_service.get_ref();
+    let role_service = role_service.get_ref();
+    let invitation_service = invitation_service.get_ref();
+
+    web_auth_service.check_csrf_throw_http(&session, &data._token)?;
+
+    let user = user.as_ref();
+    let user_roles = role_service.all_throw_http()?;
+    if !UserPolicy::can_create(user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+
+    let lang: String = locale_service.get_locale_code(Some(&req), Some(&user));
+    let invited_user = user_service.first_by_id_throw_http(path.into_inner())?;
+    let rate_limit_key = rate_limit_service.make_key_from_request_throw_http(&req, RL_KEY)?;
+
+    let mut alert_variants = Vec::new();
+    let executed =
+        rate_limit_service.attempt_throw_http(&rate_limit_key, RL_MAX_ATTEMPTS, RL_TTL)?;
+
+    if executed {
+        invitation_service.resend_throw_http(&invited_user, &lang)?;
+        let name = invited_user.get_full_name_with_id_and_email();
+        alert_variants.push(AlertVariant::UsersInvitationSent(name));
+    } else {
+        let alert_variant = rate_limit_service.alert_variant_throw_http(
+            translator_service,
+            &lang,
+            &rate_limit_key,
+        )?;
+        alert_variants.push(alert_variant);
+    }
+
+    Ok(HttpResponse::SeeOther()
+        .set_alerts(alert_variants)
+        .insert_header((
+            LOCATION,
+            HeaderValue::from_str(&get_url()).map_err(|_| error::ErrorInternalServerError(""))?,
+        ))
+        .finish())
+}
+
+/// The link stops working, the user stays in the list of the users without the password.
+pub async fn revoke(
+    req: HttpRequest,
+    path: Path<u64>,
+    data: Form<PostData>,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    user_service: Data<UserService>,
+    locale_service: Data<LocaleService>,
+    web_auth_service: Data<WebAuthService>,
+    rate_limit_service: Data<RateLimitService>,
+    translator_service: Data<TranslatorService>,
+    role_service: Data<RoleService>,
+    audit_log_service: Data<AuditLogService>,
+    invitation_service: Data<InvitationService>,
+) -> Result<HttpResponse, Error> {
+    let web_auth_service = web_auth_service.get_ref();
+    let rate_limit_service = rate_limit_service.get_ref();
+    let locale_service = locale_service.get_ref();
+    let user_service = user_service.get_ref();
+    let translator_service = translator_service.get_ref();
+    let role_service = role_service.get_ref();
+    let audit_log_service = audit_log_service.get_ref();
+    let invitation_service = invitation_service.get_ref();
+
+    web_auth_service.check_csrf_throw_http(&session, &data._token)?;
+
+    let user = user.as_ref();
+    let user_roles = role_service.all_throw_http()?;
+    if !UserPolicy::can_create(user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+
+    let lang: String = locale_service.get_locale_code(Some(&req), Some(&user));
+    let invited_user = user_service.first_by_id_throw_http(path.into_inner())?;
+    let rate_limit_key = rate_limit_service.make_key_from_request_throw_http(&req, RL_KEY)?;
+
+    let mut alert_variants = Vec::new();
+    let executed =
+        rate_limit_service.attempt_throw_http(&rate_limit_key, RL_MAX_ATTEMPTS, RL_TTL)?;
+
+    if executed {
+        invitation_service.revoke_throw_http(&invited_user)?;
+        audit_log_service.record_throw_http(
+            &req,
+            user,
+            AuditLogAction::Update,
+            AuditLogEntity::User,
+            invited_user.id,
+            Some(json!({"invited_at": &invited_user.invited_at})),
+            Some(json!({"invited_at": null})),
+        )?;
+        let name = invited_user.get_full_name_with_id_and_email();
+        alert_variants.push(AlertVariant::UsersInvitationRevokeSuccess(name));
+    } else {
+        let alert_variant = rate_limit_service.alert_variant_throw_http(
+            translator_service,
+            &lang,
+            &rate_limit_key,
+        )?;
+        alert_variants.push(alert_variant);
+    }
+
+    Ok(HttpResponse::SeeOther()
+        .set_alerts(alert_variants)
+        .insert_header((
+            LOCATION,
+            HeaderValue::from_str(&get_url()).map_err(|_| error::ErrorInternalServerError(""))?,
+        ))
+        .finish())
+}
+
+pub fn get_url() -> String {
+    "/users/invitations".to_string()
+}
+
+pub fn get_resend_url(id: &str) -> String {
+    let mut str_ = get_url();
+    str_.push('/');
+    str_.push_str(id);
+    str_.push_str("/resend");
+    str_
+}
+
+pub fn get_revoke_url(id: &str) -> String {
+    let mut str_ = get_url();
+    str_.push('/');
+    str_.push_str(id);
+    str_.push_str("/revoke");
+    str_
+}
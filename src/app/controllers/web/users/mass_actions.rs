@@ -1,6 +1,6 @@
 use crate::libs::actix_web::types::form::Form;
 use crate::{
-    AlertVariant, LocaleService, RateLimitService, RoleService, Session, TranslatorService, User,
+    AlertVariant, AuditLogAction, AuditLogEntity, AuditLogService, LocaleService, RateLimitService, RoleService, Session, TranslatorService, User,
     UserPolicy, UserService, WebAuthService, WebHttpResponse,
 };
 use actix_web::{web::{Data, ReqData}, error, Error, HttpRequest, HttpResponse, Result, http::{header::{HeaderValue, ORIGIN, REFERER, LOCATION}}};
@@ -30,6 +30,7 @@ pub async fn invoke(
     rate_limit_service: Data<RateLimitService>,
     translator_service: Data<TranslatorService>,
     role_service: Data<RoleService>,
+    audit_log_service: Data<AuditLogService>,
 ) -> Result<HttpResponse, Error> {
     let web_auth_service = web_auth_service.get_ref();
     let rate_limit_service = rate_limit_service.get_ref();
@@ -37,6 +38,7 @@ pub async fn invoke(
     let user_service = user_service.get_ref();
     let translator_service = translator_service.get_ref();
     let role_service = role_service.get_ref();
+    let audit_log_service = audit_log_service.get_ref();
 
     web_auth_service.check_csrf_throw_http(&session, &data._token)?;
 
@@ -60,7 +62,19 @@ pub async fn invoke(
                     if !UserPolicy::can_delete(&user, &roles) {
                         return Err(error::ErrorForbidden(""));
                     }
+                    let delete_users = user_service.all_by_ids_throw_http(ids)?;
                     user_service.delete_by_ids_throw_http(ids)?;
+                    for delete_user in &delete_users {
+                        audit_log_service.record_throw_http(
+                            &req,
+                            user,
+                            AuditLogAction::Delete,
+                            AuditLogEntity::User,
+                            delete_user.id,
+                            AuditLogService::to_value(delete_user),
+                            None,
+                        )?;
+                    }
                     alert_variants.push(AlertVariant::UsersMassDeleteSuccess(
                         join_vec(ids, ", "),
                     ));
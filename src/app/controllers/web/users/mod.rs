@@ -1,4 +1,10 @@
 pub mod index;
 pub mod create_update;
 pub mod delete;
-pub mod mass_actions;
\ No newline at end of file
+pub mod mass_actions;
+pub mod two_factor_reset;
+pub mod sessions;
+pub mod invitations;
+pub mod unlock;
+pub mod export;
+pub mod import;
\ No newline at end of file
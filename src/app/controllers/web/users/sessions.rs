@@ -0,0 +1,173 @@
+use crate::app::controllers::web::profile::get_url as get_profile_url;
+use crate::app::controllers::web::users::create_update::get_edit_url;
+use crate::{
+    AlertVariant, AuditLogAction, AuditLogEntity, AuditLogService, LocaleService, RateLimitService,
+    RoleService, Session, TranslatorService, User, UserPolicy, UserService, WebAuthService,
+    WebHttpResponse,
+};
+use actix_web::{web::{Data, Form, Path, ReqData}, error, Error, HttpRequest, HttpResponse, Result, http::header::{HeaderValue, LOCATION}};
+use serde_derive::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+
+const RL_MAX_ATTEMPTS: u64 = 60;
+const RL_TTL: u64 = 60;
+const RL_KEY: &'static str = "users_sessions_revoke";
+
+#[derive(Deserialize, Default, Debug)]
+pub struct PostData {
+    pub _token: Option<String>,
+    // Without the token id all sessions except the current one are revoked.
+    pub token_id: Option<u64>,
+}
+
+pub async fn profile_revoke(
+    req: HttpRequest,
+    data: Form<PostData>,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    locale_service: Data<LocaleService>,
+    web_auth_service: Data<WebAuthService>,
+    rate_limit_service: Data<RateLimitService>,
+    translator_service: Data<TranslatorService>,
+) -> Result<HttpResponse, Error> {
+    let web_auth_service = web_auth_service.get_ref();
+    let rate_limit_service = rate_limit_service.get_ref();
+    let locale_service = locale_service.get_ref();
+    let translator_service = translator_service.get_ref();
+
+    web_auth_service.check_csrf_throw_http(&session, &data._token)?;
+
+    let user = user.as_ref();
+    let lang: String = locale_service.get_locale_code(Some(&req), Some(&user));
+    let rate_limit_key = rate_limit_service.make_key_from_request_throw_http(&req, RL_KEY)?;
+
+    let mut alert_variants = Vec::new();
+    let executed =
+        rate_limit_service.attempt_throw_http(&rate_limit_key, RL_MAX_ATTEMPTS, RL_TTL)?;
+
+    if executed {
+        alert_variants.push(revoke(web_auth_service, user.id, &session, data.token_id)?);
+    } else {
+        let alert_variant = rate_limit_service.alert_variant_throw_http(
+            translator_service,
+            &lang,
+            &rate_limit_key,
+        )?;
+        alert_variants.push(alert_variant);
+    }
+
+    Ok(HttpResponse::SeeOther()
+        .set_alerts(alert_variants)
+        .insert_header((
+            LOCATION,
+            HeaderValue::from_str(&get_profile_url()).map_err(|_| error::ErrorInternalServerError(""))?,
+        ))
+        .finish())
+}
+
+pub async fn revoke_by_user(
+    req: HttpRequest,
+    path: Path<u64>,
+    data: Form<PostData>,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    user_service: Data<UserService>,
+    locale_service: Data<LocaleService>,
+    web_auth_service: Data<WebAuthService>,
+    rate_limit_service: Data<RateLimitService>,
+    translator_service: Data<TranslatorService>,
+    role_service: Data<RoleService>,
+    audit_log_service: Data<AuditLogService>,
+) -> Result<HttpResponse, Error> {
+    let web_auth_service = web_auth_service.get_ref();
+    let rate_limit_service = rate_limit_service.get_ref();
+    let locale_service = locale_service.get_ref();
+    let user_service = user_service.get_ref();
+    let translator_service = translator_service.get_ref();
+    let role_service = role_service.get_ref();
+    let audit_log_service = audit_log_service.get_ref();
+
+    web_auth_service.check_csrf_throw_http(&session, &data._token)?;
+
+    let user = user.as_ref();
+    let user_roles = role_service.all_throw_http()?;
+    if !UserPolicy::can_update(user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+
+    let user_id = path.into_inner();
+    let lang: String = locale_service.get_locale_code(Some(&req), Some(&user));
+    let edit_user = user_service.first_by_id_throw_http(user_id)?;
+
+    let rate_limit_key = rate_limit_service.make_key_from_request_throw_http(&req, RL_KEY)?;
+
+    let mut alert_variants = Vec::new();
+    let executed =
+        rate_limit_service.attempt_throw_http(&rate_limit_key, RL_MAX_ATTEMPTS, RL_TTL)?;
+
+    if executed {
+        let sessions_count = web_auth_service.all_sessions_throw_http(edit_user.id)?.len();
+        alert_variants.push(revoke(web_auth_service, edit_user.id, &session, data.token_id)?);
+        let new_sessions_count = web_auth_service.all_sessions_throw_http(edit_user.id)?.len();
+        audit_log_service.record_throw_http(
+            &req,
+            user,
+            AuditLogAction::Update,
+            AuditLogEntity::User,
+            edit_user.id,
+            Some(json!({"sessions": sessions_count})),
+            Some(json!({"sessions": new_sessions_count})),
+        )?;
+    } else {
+        let alert_variant = rate_limit_service.alert_variant_throw_http(
+            translator_service,
+            &lang,
+            &rate_limit_key,
+        )?;
+        alert_variants.push(alert_variant);
+    }
+
+    let location = get_edit_url(edit_user.id.to_string().as_str());
+
+    Ok(HttpResponse::SeeOther()
+        .set_alerts(alert_variants)
+        .insert_header((
+            LOCATION,
+            HeaderValue::from_str(&location).map_err(|_| error::ErrorInternalServerError(""))?,
+        ))
+        .finish())
+}
+
+/// The session of the current request is never revoked in bulk.
+fn revoke(
+    web_auth_service: &WebAuthService,
+    user_id: u64,
+    session: &Session,
+    token_id: Option<u64>,
+) -> Result<AlertVariant, Error> {
+    if let Some(token_id) = token_id {
+        web_auth_service.revoke_session_throw_http(user_id, token_id)?;
+        return Ok(AlertVariant::SessionsRevokeSuccess);
+    }
+
+    let except_token_id = if session.get_user_id() == user_id {
+        Some(session.get_token_id())
+    } else {
+        None
+    };
+    web_auth_service.revoke_all_sessions_throw_http(user_id, except_token_id)?;
+    Ok(AlertVariant::SessionsRevokeAllSuccess)
+}
+
+pub fn get_profile_revoke_url() -> String {
+    let mut str_ = get_profile_url();
+    str_.push_str("/sessions/revoke");
+    str_
+}
+
+pub fn get_revoke_url(id: &str) -> String {
+    let mut str_ = get_edit_url(id);
+    str_.push_str("/sessions/revoke");
+    str_
+}
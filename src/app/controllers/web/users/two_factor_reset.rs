@@ -0,0 +1,98 @@
+use crate::app::controllers::web::users::create_update::get_edit_url;
+use crate::{
+    AlertVariant, AuditLogAction, AuditLogEntity, AuditLogService, LocaleService, RateLimitService,
+    Session, TranslatorService, User, UserService, UserTwoFactorService, WebAuthService,
+    WebHttpResponse,
+};
+use actix_web::{web::{Data, Form, Path, ReqData}, error, Error, HttpRequest, HttpResponse, Result, http::header::{HeaderValue, LOCATION}};
+use serde_derive::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+
+const RL_MAX_ATTEMPTS: u64 = 60;
+const RL_TTL: u64 = 60;
+const RL_KEY: &'static str = "users_two_factor_reset";
+
+#[derive(Deserialize, Default, Debug)]
+pub struct PostData {
+    pub _token: Option<String>,
+}
+
+pub async fn invoke(
+    req: HttpRequest,
+    path: Path<u64>,
+    data: Form<PostData>,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    user_service: Data<UserService>,
+    locale_service: Data<LocaleService>,
+    web_auth_service: Data<WebAuthService>,
+    rate_limit_service: Data<RateLimitService>,
+    translator_service: Data<TranslatorService>,
+    audit_log_service: Data<AuditLogService>,
+    user_two_factor_service: Data<UserTwoFactorService>,
+) -> Result<HttpResponse, Error> {
+    let web_auth_service = web_auth_service.get_ref();
+    let rate_limit_service = rate_limit_service.get_ref();
+    let locale_service = locale_service.get_ref();
+    let user_service = user_service.get_ref();
+    let translator_service = translator_service.get_ref();
+    let audit_log_service = audit_log_service.get_ref();
+    let user_two_factor_service = user_two_factor_service.get_ref();
+
+    web_auth_service.check_csrf_throw_http(&session, &data._token)?;
+
+    // Only super admins can take the second factor away from another user.
+    if !user.is_super_admin {
+        return Err(error::ErrorForbidden(""));
+    }
+
+    let user_id = path.into_inner();
+    let user = user.as_ref();
+    let lang: String = locale_service.get_locale_code(Some(&req), Some(&user));
+    let reset_user = user_service.first_by_id_throw_http(user_id)?;
+
+    let rate_limit_key = rate_limit_service.make_key_from_request_throw_http(&req, RL_KEY)?;
+
+    let mut alert_variants = Vec::new();
+    let executed =
+        rate_limit_service.attempt_throw_http(&rate_limit_key, RL_MAX_ATTEMPTS, RL_TTL)?;
+
+    if executed {
+        user_two_factor_service.disable_throw_http(reset_user.id)?;
+        audit_log_service.record_throw_http(
+            &req,
+            user,
+            AuditLogAction::Update,
+            AuditLogEntity::User,
+            reset_user.id,
+            Some(json!({"two_factor": true})),
+            Some(json!({"two_factor": false})),
+        )?;
+        let name = reset_user.get_full_name_with_id_and_email();
+        alert_variants.push(AlertVariant::UsersTwoFactorResetSuccess(name));
+    } else {
+        let alert_variant = rate_limit_service.alert_variant_throw_http(
+            translator_service,
+            &lang,
+            &rate_limit_key,
+        )?;
+        alert_variants.push(alert_variant);
+    }
+
+    let location = get_edit_url(reset_user.id.to_string().as_str());
+
+    Ok(HttpResponse::SeeOther()
+        .set_alerts(alert_variants)
+        .insert_header((
+            LOCATION,
+            HeaderValue::from_str(&location).map_err(|_| error::ErrorInternalServerError(""))?,
+        ))
+        .finish())
+}
+
+pub fn get_url(id: &str) -> String {
+    let mut str_ = get_edit_url(id);
+    str_.push_str("/two-factor/reset");
+    str_
+}
@@ -0,0 +1,99 @@
+use crate::app::controllers::web::users::create_update::get_edit_url;
+use crate::{
+    AlertVariant, AuditLogAction, AuditLogEntity, AuditLogService, LocaleService,
+    LoginSecurityService, RateLimitService, RoleService, Session, TranslatorService, User,
+    UserPolicy, UserService, WebAuthService, WebHttpResponse,
+};
+use actix_web::{web::{Data, Form, Path, ReqData}, error, Error, HttpRequest, HttpResponse, Result, http::header::{HeaderValue, LOCATION}};
+use serde_derive::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+
+const RL_MAX_ATTEMPTS: u64 = 60;
+const RL_TTL: u64 = 60;
+const RL_KEY: &'static str = "users_unlock";
+
+#[derive(Deserialize, Default, Debug)]
+pub struct PostData {
+    pub _token: Option<String>,
+}
+
+/// Ends the lockout after the failed logins before its time, the failures are forgotten too.
+pub async fn invoke(
+    req: HttpRequest,
+    path: Path<u64>,
+    data: Form<PostData>,
+    user: ReqData<Arc<User>>,
+    session: ReqData<Arc<Session>>,
+    user_service: Data<UserService>,
+    locale_service: Data<LocaleService>,
+    web_auth_service: Data<WebAuthService>,
+    rate_limit_service: Data<RateLimitService>,
+    translator_service: Data<TranslatorService>,
+    role_service: Data<RoleService>,
+    audit_log_service: Data<AuditLogService>,
+    login_security_service: Data<LoginSecurityService>,
+) -> Result<HttpResponse, Error> {
+    let web_auth_service = web_auth_service.get_ref();
+    let rate_limit_service = rate_limit_service.get_ref();
+    let locale_service = locale_service.get_ref();
+    let user_service = user_service.get_ref();
+    let translator_service = translator_service.get_ref();
+    let role_service = role_service.get_ref();
+    let audit_log_service = audit_log_service.get_ref();
+    let login_security_service = login_security_service.get_ref();
+
+    web_auth_service.check_csrf_throw_http(&session, &data._token)?;
+
+    let user = user.as_ref();
+    let user_roles = role_service.all_throw_http()?;
+    if !UserPolicy::can_update(user, &user_roles) {
+        return Err(error::ErrorForbidden(""));
+    }
+
+    let lang: String = locale_service.get_locale_code(Some(&req), Some(&user));
+    let locked_user = user_service.first_by_id_throw_http(path.into_inner())?;
+    let rate_limit_key = rate_limit_service.make_key_from_request_throw_http(&req, RL_KEY)?;
+
+    let mut alert_variants = Vec::new();
+    let executed =
+        rate_limit_service.attempt_throw_http(&rate_limit_key, RL_MAX_ATTEMPTS, RL_TTL)?;
+
+    if executed {
+        login_security_service.unlock_throw_http(&locked_user.email)?;
+        audit_log_service.record_throw_http(
+            &req,
+            user,
+            AuditLogAction::Update,
+            AuditLogEntity::User,
+            locked_user.id,
+            Some(json!({"locked": true})),
+            Some(json!({"locked": false})),
+        )?;
+        let name = locked_user.get_full_name_with_id_and_email();
+        alert_variants.push(AlertVariant::UsersUnlockSuccess(name));
+    } else {
+        let alert_variant = rate_limit_service.alert_variant_throw_http(
+            translator_service,
+            &lang,
+            &rate_limit_key,
+        )?;
+        alert_variants.push(alert_variant);
+    }
+
+    let location = get_edit_url(locked_user.id.to_string().as_str());
+
+    Ok(HttpResponse::SeeOther()
+        .set_alerts(alert_variants)
+        .insert_header((
+            LOCATION,
+            HeaderValue::from_str(&location).map_err(|_| error::ErrorInternalServerError(""))?,
+        ))
+        .finish())
+}
+
+pub fn get_url(id: &str) -> String {
+    let mut str_ = get_edit_url(id);
+    str_.push_str("/unlock");
+    str_
+}
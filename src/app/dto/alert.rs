@@ -125,6 +125,80 @@ impl Alert {
                 let vars = one_variables!("ids", ids);
                 Self::success(translator_service.variables(&lang, "alert.files.mass_restore.success", &vars))
             }
+            AlertVariant::ApiTokensCreateSuccess(name) => {
+                let vars = one_variables!("name", name);
+                Self::success(translator_service.variables(&lang, "alert.api_tokens.create.success", &vars))
+            }
+            AlertVariant::ApiTokensDeleteSuccess(name) => {
+                let vars = one_variables!("name", name);
+                Self::success(translator_service.variables(&lang, "alert.api_tokens.delete.success", &vars))
+            }
+            AlertVariant::TwoFactorEnableSuccess => {
+                Self::success(translator_service.translate(&lang, "alert.two_factor.enable.success"))
+            }
+            AlertVariant::TwoFactorDisableSuccess => {
+                Self::success(translator_service.translate(&lang, "alert.two_factor.disable.success"))
+            }
+            AlertVariant::TwoFactorRecoveryCodesSuccess => {
+                Self::success(translator_service.translate(&lang, "alert.two_factor.recovery_codes.success"))
+            }
+            AlertVariant::TwoFactorRequiredWarning => {
+                Self::warning(translator_service.translate(&lang, "alert.two_factor.required.warning"))
+            }
+            AlertVariant::UsersTwoFactorResetSuccess(name) => {
+                let vars = one_variables!("name", name);
+                Self::success(translator_service.variables(&lang, "alert.users.two_factor_reset.success", &vars))
+            }
+            AlertVariant::SessionsRevokeSuccess => {
+                Self::success(translator_service.translate(&lang, "alert.sessions.revoke.success"))
+            }
+            AlertVariant::SessionsRevokeAllSuccess => {
+                Self::success(translator_service.translate(&lang, "alert.sessions.revoke_all.success"))
+            }
+            AlertVariant::JobsRetrySuccess(name) => {
+                let vars = one_variables!("name", name);
+                Self::success(translator_service.variables(&lang, "alert.jobs.retry.success", &vars))
+            }
+            AlertVariant::VerifyEmailSuccess => {
+                Self::success(translator_service.translate(&lang, "alert.verify_email.success"))
+            }
+            AlertVariant::VerifyEmailSent => {
+                Self::success(translator_service.translate(&lang, "alert.verify_email.sent"))
+            }
+            AlertVariant::VerifyEmailRequiredWarning => {
+                Self::warning(translator_service.translate(&lang, "alert.verify_email.required.warning"))
+            }
+            AlertVariant::UsersInvitationSent(name) => {
+                let vars = one_variables!("name", name);
+                Self::success(translator_service.variables(&lang, "alert.users.invitation.sent", &vars))
+            }
+            AlertVariant::UsersInvitationRevokeSuccess(name) => {
+                let vars = one_variables!("name", name);
+                Self::success(translator_service.variables(&lang, "alert.users.invitation.revoke.success", &vars))
+            }
+            AlertVariant::InvitationAcceptSuccess => {
+                Self::success(translator_service.translate(&lang, "alert.invitation.accept.success"))
+            }
+            AlertVariant::PasswordExpiredWarning => {
+                Self::warning(translator_service.translate(&lang, "alert.password.expired.warning"))
+            }
+            AlertVariant::UsersUnlockSuccess(name) => {
+                let vars = one_variables!("name", name);
+                Self::success(translator_service.variables(&lang, "alert.users.unlock.success", &vars))
+            }
+            AlertVariant::UsersImportSuccess(created, updated, skipped) => {
+                let mut vars = two_variables!("created", created, "updated", updated);
+                vars.insert("skipped", skipped);
+                Self::success(translator_service.variables(&lang, "alert.users.import.success", &vars))
+            }
+            AlertVariant::UsersImportFail(lines) => {
+                let vars = one_variables!("lines", lines);
+                Self::warning(translator_service.variables(&lang, "alert.users.import.fail", &vars))
+            }
+            AlertVariant::ExportQueued(count) => {
+                let vars = one_variables!("count", count);
+                Self::info(translator_service.variables(&lang, "alert.export.queued", &vars))
+            }
         }
     }
 }
@@ -152,6 +226,27 @@ pub enum AlertVariant {
     FilesNonRecoverableWarning(String),
     FilesMassDeleteSuccess(String),
     FilesMassRestoreSuccess(String),
+    ApiTokensCreateSuccess(String),
+    ApiTokensDeleteSuccess(String),
+    TwoFactorEnableSuccess,
+    TwoFactorDisableSuccess,
+    TwoFactorRecoveryCodesSuccess,
+    TwoFactorRequiredWarning,
+    UsersTwoFactorResetSuccess(String),
+    SessionsRevokeSuccess,
+    SessionsRevokeAllSuccess,
+    JobsRetrySuccess(String),
+    VerifyEmailSuccess,
+    VerifyEmailSent,
+    VerifyEmailRequiredWarning,
+    UsersInvitationSent(String),
+    UsersInvitationRevokeSuccess(String),
+    InvitationAcceptSuccess,
+    PasswordExpiredWarning,
+    UsersUnlockSuccess(String),
+    UsersImportSuccess(String, String, String),
+    UsersImportFail(String),
+    ExportQueued(String),
 }
 
 impl AlertVariant {
@@ -213,6 +308,37 @@ impl AlertVariant {
             Self::FilesMassRestoreSuccess(ids) => {
                 format!("files_mass_delete_success::{ids}")
             }
+            Self::ApiTokensCreateSuccess(name) => {
+                format!("api_tokens_create_success::{name}")
+            }
+            Self::ApiTokensDeleteSuccess(name) => {
+                format!("api_tokens_delete_success::{name}")
+            }
+            Self::TwoFactorEnableSuccess => "two_factor_enable_success".to_string(),
+            Self::TwoFactorDisableSuccess => "two_factor_disable_success".to_string(),
+            Self::TwoFactorRecoveryCodesSuccess => "two_factor_recovery_codes_success".to_string(),
+            Self::TwoFactorRequiredWarning => "two_factor_required_warning".to_string(),
+            Self::UsersTwoFactorResetSuccess(name) => {
+                format!("users_two_factor_reset_success::{name}")
+            }
+            Self::SessionsRevokeSuccess => "sessions_revoke_success".to_string(),
+            Self::SessionsRevokeAllSuccess => "sessions_revoke_all_success".to_string(),
+            Self::JobsRetrySuccess(name) => format!("jobs_retry_success::{name}"),
+            Self::VerifyEmailSuccess => "verify_email_success".to_string(),
+            Self::VerifyEmailSent => "verify_email_sent".to_string(),
+            Self::VerifyEmailRequiredWarning => "verify_email_required_warning".to_string(),
+            Self::UsersInvitationSent(name) => format!("users_invitation_sent::{name}"),
+            Self::UsersInvitationRevokeSuccess(name) => {
+                format!("users_invitation_revoke_success::{name}")
+            }
+            Self::InvitationAcceptSuccess => "invitation_accept_success".to_string(),
+            Self::PasswordExpiredWarning => "password_expired_warning".to_string(),
+            Self::UsersUnlockSuccess(name) => format!("users_unlock_success::{name}"),
+            Self::UsersImportSuccess(created, updated, skipped) => {
+                format!("users_import_success::{created}::{updated}::{skipped}")
+            }
+            Self::UsersImportFail(lines) => format!("users_import_fail::{lines}"),
+            Self::ExportQueued(count) => format!("export_queued::{count}"),
         }
     }
 
@@ -292,6 +418,59 @@ impl AlertVariant {
                 let p = string.get(1).ok_or(ParseAlertVariantError)?;
                 Ok(Self::FilesMassRestoreSuccess(p.to_string()))
             }
+            "api_tokens_create_success" => {
+                let p = string.get(1).ok_or(ParseAlertVariantError)?;
+                Ok(Self::ApiTokensCreateSuccess(p.to_string()))
+            }
+            "api_tokens_delete_success" => {
+                let p = string.get(1).ok_or(ParseAlertVariantError)?;
+                Ok(Self::ApiTokensDeleteSuccess(p.to_string()))
+            }
+            "two_factor_enable_success" => Ok(Self::TwoFactorEnableSuccess),
+            "two_factor_disable_success" => Ok(Self::TwoFactorDisableSuccess),
+            "two_factor_recovery_codes_success" => Ok(Self::TwoFactorRecoveryCodesSuccess),
+            "two_factor_required_warning" => Ok(Self::TwoFactorRequiredWarning),
+            "sessions_revoke_success" => Ok(Self::SessionsRevokeSuccess),
+            "sessions_revoke_all_success" => Ok(Self::SessionsRevokeAllSuccess),
+            "users_two_factor_reset_success" => {
+                let p = string.get(1).ok_or(ParseAlertVariantError)?;
+                Ok(Self::UsersTwoFactorResetSuccess(p.to_string()))
+            }
+            "jobs_retry_success" => {
+                let p = string.get(1).ok_or(ParseAlertVariantError)?;
+                Ok(Self::JobsRetrySuccess(p.to_string()))
+            }
+            "verify_email_success" => Ok(Self::VerifyEmailSuccess),
+            "verify_email_sent" => Ok(Self::VerifyEmailSent),
+            "verify_email_required_warning" => Ok(Self::VerifyEmailRequiredWarning),
+            "users_invitation_sent" => {
+                let p = string.get(1).ok_or(ParseAlertVariantError)?;
+                Ok(Self::UsersInvitationSent(p.to_string()))
+            }
+            "users_invitation_revoke_success" => {
+                let p = string.get(1).ok_or(ParseAlertVariantError)?;
+                Ok(Self::UsersInvitationRevokeSuccess(p.to_string()))
+            }
+            "invitation_accept_success" => Ok(Self::InvitationAcceptSuccess),
+            "password_expired_warning" => Ok(Self::PasswordExpiredWarning),
+            "users_unlock_success" => {
+                let p = string.get(1).ok_or(ParseAlertVariantError)?;
+                Ok(Self::UsersUnlockSuccess(p.to_string()))
+            }
+            "users_import_success" => {
+                let p1 = string.get(1).ok_or(ParseAlertVariantError)?;
+                let p2 = string.get(2).ok_or(ParseAlertVariantError)?;
+                let p3 = string.get(3).ok_or(ParseAlertVariantError)?;
+                Ok(Self::UsersImportSuccess(p1.to_string(), p2.to_string(), p3.to_string()))
+            }
+            "users_import_fail" => {
+                let p = string.get(1).ok_or(ParseAlertVariantError)?;
+                Ok(Self::UsersImportFail(p.to_string()))
+            }
+            "export_queued" => {
+                let p = string.get(1).ok_or(ParseAlertVariantError)?;
+                Ok(Self::ExportQueued(p.to_string()))
+            }
             _ => Err(ParseAlertVariantError),
         }
     }
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString, VariantNames};
+
+// Personal access tokens of users.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct ApiToken {
+    pub id: u64,
+    // The token owner.
+    pub user_id: u64,
+    // The token name.
+    pub name: String,
+    // Hash of the sha256 token.
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    // Permissions available to the token.
+    pub scopes: Option<Vec<String>>,
+    // After this time, the token is no longer valid.
+    pub expires_at: Option<String>,
+    // The datetime of the last token usage.
+    pub last_used_at: Option<String>,
+    // The datetime of the token creation.
+    pub created_at: Option<String>,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Display,
+    EnumString,
+    Serialize,
+    Deserialize,
+    VariantNames,
+    EnumIter,
+    Eq,
+    PartialEq,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum ApiTokenColumn {
+    Id,
+    UserId,
+    Name,
+    TokenHash,
+    Scopes,
+    ExpiresAt,
+    LastUsedAt,
+    CreatedAt,
+}
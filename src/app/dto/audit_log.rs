@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use strum_macros::{Display, EnumIter, EnumString, VariantNames};
+
+// Trail of admin mutations.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct AuditLog {
+    pub id: u64,
+    // The user who made the change.
+    pub actor_id: Option<u64>,
+    // AuditLogAction.
+    pub action: String,
+    // AuditLogEntity.
+    pub entity_type: String,
+    // The ID of the changed entity.
+    pub entity_id: u64,
+    // Changed fields before the change.
+    pub old_values: Option<Value>,
+    // Changed fields after the change.
+    pub new_values: Option<Value>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    // The datetime of the change.
+    pub created_at: Option<String>,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Display,
+    EnumString,
+    Serialize,
+    Deserialize,
+    VariantNames,
+    EnumIter,
+    Eq,
+    PartialEq,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum AuditLogColumn {
+    Id,
+    ActorId,
+    Action,
+    EntityType,
+    EntityId,
+    OldValues,
+    NewValues,
+    Ip,
+    UserAgent,
+    CreatedAt,
+}
+
+#[derive(Debug, Clone, Copy, Display, EnumString, VariantNames, EnumIter, Eq, PartialEq)]
+#[strum(serialize_all = "snake_case")]
+pub enum AuditLogAction {
+    Create,
+    Update,
+    Delete,
+    Restore,
+}
+
+#[derive(Debug, Clone, Copy, Display, EnumString, VariantNames, EnumIter, Eq, PartialEq)]
+#[strum(serialize_all = "snake_case")]
+pub enum AuditLogEntity {
+    User,
+    Role,
+    File,
+    UserFile,
+    RateLimit,
+}
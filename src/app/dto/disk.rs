@@ -7,6 +7,7 @@ use strum_macros::{Display, EnumString};
 #[strum(serialize_all = "snake_case")]
 pub enum Disk {
     Local,
+    S3,
 }
 
 impl Default for Disk {
@@ -0,0 +1,78 @@
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString};
+
+#[derive(Debug, Clone, Copy, Display, EnumString, EnumIter, Serialize, Deserialize, Eq, PartialEq)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Csv,
+    Xlsx,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Xlsx => "xlsx",
+            Self::Json => "json",
+        }
+    }
+
+    pub fn mime(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            Self::Json => "application/json",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Display, EnumString, Serialize, Deserialize, Eq, PartialEq)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ExportEntity {
+    Users,
+    Roles,
+    Files,
+}
+
+// The list query of the index page, the export returns the same records without the pagination.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportParams {
+    pub entity: ExportEntity,
+    pub format: ExportFormat,
+    pub search: Option<String>,
+    pub locale: Option<String>,
+    pub sort: Option<String>,
+    // The ids selected on the page, all the filtered records are exported if it is not set.
+    pub ids: Option<Vec<u64>>,
+    // The language of the column headers.
+    pub lang: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportValue {
+    Null,
+    Bool(bool),
+    Number(u64),
+    Text(String),
+}
+
+impl From<Option<String>> for ExportValue {
+    fn from(value: Option<String>) -> Self {
+        match value {
+            Some(value) => Self::Text(value),
+            None => Self::Null,
+        }
+    }
+}
+
+impl From<Option<u64>> for ExportValue {
+    fn from(value: Option<u64>) -> Self {
+        match value {
+            Some(value) => Self::Number(value),
+            None => Self::Null,
+        }
+    }
+}
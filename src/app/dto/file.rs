@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use strum_macros::{Display, EnumIter, EnumString, VariantNames};
-use crate::UserFile;
+use crate::{FileVariant, UserFile};
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct File {
@@ -33,6 +33,8 @@ pub struct File {
     pub disk: String,
     // Ref UserFile
     pub user_files: Option<Vec<UserFile>>,
+    // Ref FileVariant
+    pub variants: Option<Vec<FileVariant>>,
 }
 
 #[derive(
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString, VariantNames};
+
+// Resized and converted versions of the image files.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct FileVariant {
+    pub id: u64,
+    // Relation to the files table.
+    pub file_id: u64,
+    // The variant name from the config.
+    pub name: String,
+    // The file name by mask: [hash]-[size]-[name].[extensions].
+    pub filename: String,
+    // The path where the variant is saved on disk.
+    pub path: String,
+    // The variant type.
+    pub mime: Option<String>,
+    // The variant size in bytes.
+    pub size: Option<u64>,
+    // The image width in pixels.
+    pub width: Option<u32>,
+    // The image height in pixels.
+    pub height: Option<u32>,
+    // The disk where the variant is stored.
+    pub disk: String,
+    // The datetime of the variant creation.
+    pub created_at: Option<String>,
+    // The datetime of the last variant update.
+    pub updated_at: Option<String>,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Display,
+    EnumString,
+    Serialize,
+    Deserialize,
+    VariantNames,
+    EnumIter,
+    Eq,
+    PartialEq,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum FileVariantColumn {
+    Id,
+    FileId,
+    Name,
+    Filename,
+    Path,
+    Mime,
+    Size,
+    Width,
+    Height,
+    Disk,
+    CreatedAt,
+    UpdatedAt,
+}
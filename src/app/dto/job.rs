@@ -0,0 +1,30 @@
+use crate::{model_redis_impl, EmailMessage, ExportParams};
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::Display;
+
+// The background job, it is stored in the key-value store and the queues keep only its id.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Job {
+    pub id: String,
+    pub payload: JobPayload,
+    // The count of the started attempts, it is incremented when the job is reserved.
+    pub attempts: u32,
+    pub max_attempts: u32,
+    // Unix timestamp, the job is not reserved before this time.
+    pub available_at: i64,
+    pub created_at: Option<String>,
+    pub failed_at: Option<String>,
+    pub error: Option<String>,
+}
+
+model_redis_impl!(Job);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum JobPayload {
+    SendEmail(EmailMessage),
+    GenerateFileVariants { file_id: u64, is_replaced: bool },
+    CollectFileGarbage,
+    SyncLdapUsers,
+    Export { user_id: u64, params: ExportParams },
+}
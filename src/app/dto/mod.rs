@@ -1,17 +1,35 @@
 mod alert;
+mod api_token;
+mod audit_log;
 mod disk;
+mod export;
 mod file;
+mod file_variant;
+mod job;
 mod locale;
 mod permission;
+mod resumable_upload;
 mod role;
 mod user;
 mod user_file;
+mod user_import;
+mod user_session;
+mod user_two_factor;
 
 pub use self::alert::*;
+pub use self::api_token::*;
+pub use self::audit_log::*;
 pub use self::disk::*;
+pub use self::export::*;
 pub use self::file::*;
+pub use self::file_variant::*;
+pub use self::job::*;
 pub use self::locale::*;
 pub use self::permission::*;
+pub use self::resumable_upload::*;
 pub use self::role::*;
 pub use self::user::*;
 pub use self::user_file::*;
+pub use self::user_import::*;
+pub use self::user_session::*;
+pub use self::user_two_factor::*;
@@ -1,4 +1,5 @@
 use serde_derive::{Deserialize, Serialize};
+use std::str::FromStr;
 use strum_macros::{Display, EnumString};
 
 #[derive(
@@ -11,6 +12,7 @@ pub enum Permission {
     UsersUpdate,
     UsersDelete,
     UsersSetRoles,
+    UsersUpdateSameRoles,
     RolesShow,
     RolesCreate,
     RolesUpdate,
@@ -19,4 +21,25 @@ pub enum Permission {
     FilesCreate,
     FilesUpdate,
     FilesDelete,
+    FilesShowOwn,
+    FilesUpdateOwn,
+    FilesDeleteOwn,
+    AuditLogShow,
+    JobsShow,
+    JobsRetry,
+}
+
+impl Permission {
+    /// The bit of the permission in the masks of the roles, the order of the declaration is kept.
+    pub fn bit(&self) -> u64 {
+        1 << (*self as u64)
+    }
+
+    /// The mask of the known permissions, the unknown codes are skipped.
+    pub fn mask_of(permissions: &Vec<String>) -> u64 {
+        permissions
+            .iter()
+            .filter_map(|permission| Self::from_str(permission).ok())
+            .fold(0, |mask, permission| mask | permission.bit())
+    }
 }
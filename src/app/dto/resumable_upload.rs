@@ -0,0 +1,19 @@
+use crate::model_redis_impl;
+use serde_derive::{Deserialize, Serialize};
+
+// The state of an unfinished resumable upload, the content is staged on the local disk.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct ResumableUpload {
+    pub id: String,
+    pub user_id: u64,
+    // The total size of the file in bytes.
+    pub length: u64,
+    // The count of the received bytes.
+    pub offset: u64,
+    pub filename: Option<String>,
+    pub mime: Option<String>,
+    pub is_public: bool,
+    pub created_at: Option<String>,
+}
+
+model_redis_impl!(ResumableUpload);
@@ -1,3 +1,4 @@
+use crate::Permission;
 use serde_derive::{Deserialize, Serialize};
 use strum_macros::{Display, EnumString, VariantNames, EnumIter};
 
@@ -8,6 +9,27 @@ pub struct Role {
     pub name: String,
     pub description: Option<String>,
     pub permissions: Option<Vec<String>>,
+    pub requires_two_factor: bool,
+    // The upload limit in bytes, the default one is used if it is not set.
+    pub upload_max_size: Option<u64>,
+    // The role the permissions are inherited from.
+    pub parent_id: Option<u64>,
+    // The mask of the own and the inherited permissions, it is set by `RoleService::resolve_permissions`.
+    #[serde(skip)]
+    pub effective_permissions: Option<u64>,
+}
+
+impl Role {
+    /// The roles loaded by `RoleService::all` are checked by the mask, the other ones by the own permissions.
+    pub fn has_permission(&self, permission: &Permission) -> bool {
+        if let Some(mask) = self.effective_permissions {
+            return mask & permission.bit() != 0;
+        }
+        let permission = permission.to_string();
+        self.permissions
+            .as_ref()
+            .is_some_and(|permissions| permissions.contains(&permission))
+    }
 }
 
 #[derive(Debug, Clone, Copy, Display, EnumString, Serialize, Deserialize, VariantNames, EnumIter, Eq, PartialEq)]
@@ -18,4 +40,7 @@ pub enum RoleColumn {
     Name,
     Description,
     Permissions,
+    RequiresTwoFactor,
+    UploadMaxSize,
+    ParentId,
 }
\ No newline at end of file
@@ -17,6 +17,15 @@ pub struct User {
     pub is_super_admin: bool,
     pub roles_ids: Option<Vec<u64>>,
     pub avatar_id: Option<u64>,
+    // The self-registered users confirm the email by the link from the letter.
+    pub email_verified_at: Option<String>,
+    // The users provisioned from the directory, they log in by the directory password.
+    pub ldap_dn: Option<String>,
+    pub disabled_at: Option<String>,
+    // The users created by the invitation until they set the password by the link from the letter.
+    pub invited_at: Option<String>,
+    // The users are sent to the profile to change the password when it is older than the max age.
+    pub password_changed_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, Display, EnumString, Serialize, Deserialize, VariantNames, EnumIter, Eq, PartialEq)]
@@ -31,6 +40,11 @@ pub enum UserColumn {
     IsSuperAdmin,
     RolesIds,
     AvatarId,
+    EmailVerifiedAt,
+    LdapDn,
+    DisabledAt,
+    InvitedAt,
+    PasswordChangedAt,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
@@ -38,6 +52,9 @@ pub struct UserCredentials {
     pub id: u64,
     pub email: String,
     pub password: Option<String>,
+    pub disabled_at: Option<String>,
+    // The hashes of the previous passwords, the newest first.
+    pub password_history: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Copy, Display, EnumString, Serialize, Deserialize, VariantNames, EnumIter)]
@@ -46,6 +63,8 @@ pub enum UserCredentialsColumn {
     Id,
     Email,
     Password,
+    DisabledAt,
+    PasswordHistory,
 }
 
 impl User {
@@ -82,6 +101,21 @@ impl User {
 
         full_name
     }
+    pub fn is_email_verified(&self) -> bool {
+        self.email_verified_at.is_some()
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.disabled_at.is_some()
+    }
+
+    pub fn is_ldap(&self) -> bool {
+        self.ldap_dn.is_some()
+    }
+
+    pub fn is_invited(&self) -> bool {
+        self.invited_at.is_some()
+    }
     pub fn empty(email: String) -> Self {
         let mut entity = Self::default();
         entity.email = email;
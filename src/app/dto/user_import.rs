@@ -0,0 +1,73 @@
+use crate::User;
+use serde_derive::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
+
+pub const USER_IMPORT_MAX_SIZE: usize = 5242880;
+pub const USER_IMPORT_MAX_ROWS: usize = 1000;
+
+// The header is matched by the code or by the column label of the users list in any language.
+pub const USER_IMPORT_COLUMNS: [&'static str; 6] =
+    ["email", "surname", "name", "patronymic", "locale", "roles"];
+
+/// The parsed file, the existing users keep the values of the columns missing in it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UserImportFile {
+    pub columns: Vec<String>,
+    pub rows: Vec<UserImportRow>,
+}
+
+impl UserImportFile {
+    pub fn has_column(&self, code: &str) -> bool {
+        self.columns.iter().any(|column| column.eq(code))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UserImportRow {
+    // The line number in the file, the header is the first one.
+    pub line: usize,
+    pub email: Option<String>,
+    pub surname: Option<String>,
+    pub name: Option<String>,
+    pub patronymic: Option<String>,
+    pub locale: Option<String>,
+    // The codes of the roles.
+    pub roles: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Display, EnumString, Serialize, Deserialize, Eq, PartialEq)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum UserImportStatus {
+    Create,
+    Update,
+    Skip,
+}
+
+#[derive(Debug, Clone)]
+pub struct UserImportPreviewRow {
+    pub row: UserImportRow,
+    pub status: UserImportStatus,
+    pub errors: Vec<String>,
+    // The user with the same email, it is updated.
+    pub user: Option<User>,
+    pub roles_ids: Option<Vec<u64>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UserImportPreview {
+    pub rows: Vec<UserImportPreviewRow>,
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+}
+
+/// The users created and updated by the import with their previous state, they go to the audit log.
+#[derive(Debug, Clone, Default)]
+pub struct UserImportResult {
+    pub created: Vec<User>,
+    pub updated: Vec<(User, User)>,
+    pub skipped: usize,
+    // The lines failed to be saved or invited, the invited users are created anyway.
+    pub failed_lines: Vec<usize>,
+}
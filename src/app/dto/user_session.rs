@@ -0,0 +1,19 @@
+use crate::model_redis_impl;
+use serde_derive::{Deserialize, Serialize};
+
+// Details of an active web session, stored next to the session token.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct UserSession {
+    // The id of the current session token, it changes when the token is rotated.
+    pub token_id: u64,
+    // The id of the rotated token, it stays valid for a short time after the rotation.
+    pub previous_token_id: Option<u64>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: Option<String>,
+    pub last_activity_at: Option<String>,
+    // Filled when the sessions are listed, the expiry follows the token lifetime.
+    pub expires_at: Option<String>,
+}
+
+model_redis_impl!(UserSession);
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString, VariantNames};
+
+// TOTP second factor of users.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct UserTwoFactor {
+    pub id: u64,
+    // The second factor owner.
+    pub user_id: u64,
+    // The encrypted TOTP secret.
+    #[serde(skip_serializing)]
+    pub secret: String,
+    // Hashes of the unused recovery codes.
+    #[serde(skip_serializing)]
+    pub recovery_codes: Option<Vec<String>>,
+    // The last accepted TOTP time step, a code can not be used twice.
+    pub last_used_step: Option<u64>,
+    // The datetime of the enrollment confirmation, the second factor is enabled after it.
+    pub confirmed_at: Option<String>,
+    // The datetime of the enrollment start.
+    pub created_at: Option<String>,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Display,
+    EnumString,
+    Serialize,
+    Deserialize,
+    VariantNames,
+    EnumIter,
+    Eq,
+    PartialEq,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum UserTwoFactorColumn {
+    Id,
+    UserId,
+    Secret,
+    RecoveryCodes,
+    LastUsedStep,
+    ConfirmedAt,
+    CreatedAt,
+}
+
+impl UserTwoFactor {
+    pub fn is_confirmed(&self) -> bool {
+        self.confirmed_at.is_some()
+    }
+}
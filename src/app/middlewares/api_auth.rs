@@ -0,0 +1,181 @@
+use crate::{
+    ApiToken, ApiTokenService, EmailVerificationService, LocaleService, RoleService, Session, TranslatorService, User,
+    UserService, UserTwoFactorService, WebAuthService,
+};
+use actix_utils::future::{ready, Ready};
+use actix_web::body::BoxBody;
+use actix_web::web::Data;
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    error, Error, HttpMessage,
+};
+use std::sync::Arc;
+use std::{future::Future, pin::Pin, rc::Rc};
+
+/// Authenticates API requests by the "Authorization: Bearer <token>" header.
+/// The token is either a session token issued by "/api/v1/auth/token"
+/// or a personal access token created on the profile page.
+/// Unlike WebAuthMiddleware it never redirects: unauthorized requests get a 401 error
+/// and restricted users a 403 error, which the default error handler renders as JSON.
+#[derive(Clone)]
+pub struct ApiAuthMiddleware;
+
+impl<S> Transform<S, ServiceRequest> for ApiAuthMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<BoxBody>, Error = Error> + 'static,
+    S::Future: 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = InnerApiAuthMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(InnerApiAuthMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct InnerApiAuthMiddleware<S> {
+    service: Rc<S>,
+}
+
+/// Returns the translation key of the reason the user has to finish the account setup on the site
+/// before using the API: a required second factor that is not enabled yet, an unverified email
+/// or an expired password.
+/// The checks are the same as in WebAuthMiddleware, which redirects to the pages to fix them.
+/// A failed second factor check is a "500 Internal Server Error", it never lets the user skip the enrollment.
+pub fn get_restriction_key(
+    user: &User,
+    role_service: &RoleService,
+    user_two_factor_service: &UserTwoFactorService,
+    email_verification_service: &EmailVerificationService,
+    user_service: &UserService,
+) -> Result<Option<&'static str>, Error> {
+    if let Some(roles_ids) = user.roles_ids.as_ref().filter(|ids| !ids.is_empty()) {
+        let roles = role_service.all_by_ids_throw_http(roles_ids)?;
+        if UserTwoFactorService::is_required(user, &roles)
+            && !user_two_factor_service.is_enabled_throw_http(user.id)?
+        {
+            return Ok(Some("alert.two_factor.required.warning"));
+        }
+    }
+    if email_verification_service.is_blocked(user) {
+        return Ok(Some("alert.verify_email.required.warning"));
+    }
+    if user_service.is_password_expired(user) {
+        return Ok(Some("alert.password.expired.warning"));
+    }
+    Ok(None)
+}
+
+/// Fails with the "403 Forbidden" error and the translated message when the user is restricted.
+fn check_restriction(req: &ServiceRequest, user: &User) -> Result<(), Error> {
+    let key = get_restriction_key(
+        user,
+        req.app_data::<Data<RoleService>>().unwrap(),
+        req.app_data::<Data<UserTwoFactorService>>().unwrap(),
+        req.app_data::<Data<EmailVerificationService>>().unwrap(),
+        req.app_data::<Data<UserService>>().unwrap(),
+    )?;
+    if let Some(key) = key {
+        let locale_service = req.app_data::<Data<LocaleService>>().unwrap();
+        let translator_service = req.app_data::<Data<TranslatorService>>().unwrap();
+        let lang = locale_service.get_locale_code(Some(req.request()), Some(user));
+        return Err(error::ErrorForbidden(translator_service.translate(&lang, key)));
+    }
+    Ok(())
+}
+
+impl<S> Service<ServiceRequest> for InnerApiAuthMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<BoxBody>, Error = Error> + 'static,
+    S::Future: 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let web_auth_service: &Data<WebAuthService> =
+            req.app_data::<Data<WebAuthService>>().unwrap();
+        let web_auth_service = Arc::clone(web_auth_service);
+
+        let token = web_auth_service.get_bearer_token_from_request(req.request());
+
+        if token.is_none() {
+            return Box::pin(async move { Ok(req.error_response(error::ErrorUnauthorized(""))) });
+        }
+        let token: String = token.unwrap();
+
+        let api_token_service: &Data<ApiTokenService> =
+            req.app_data::<Data<ApiTokenService>>().unwrap();
+
+        if api_token_service.is_api_token(&token) {
+            let api_token = api_token_service.login_by_token(&token);
+
+            if api_token.is_err() {
+                return Box::pin(async move { Ok(req.error_response(error::ErrorUnauthorized(""))) });
+            }
+            let api_token: ApiToken = api_token.unwrap();
+
+            let user_service: &Data<UserService> = req.app_data::<Data<UserService>>().unwrap();
+            let user = user_service.first_by_id(api_token.user_id);
+
+            if !matches!(&user, Ok(Some(user)) if !user.is_disabled()) {
+                return Box::pin(async move { Ok(req.error_response(error::ErrorUnauthorized(""))) });
+            }
+
+            let user: User = user.unwrap().unwrap();
+
+            if let Err(e) = check_restriction(&req, &user) {
+                return Box::pin(async move { Ok(req.error_response(e)) });
+            }
+
+            let api_token: Arc<ApiToken> = Arc::new(api_token);
+            req.extensions_mut().insert(api_token);
+
+            let user: Arc<User> = Arc::new(user);
+            req.extensions_mut().insert(user);
+
+            let fut = self.service.call(req);
+
+            return Box::pin(fut);
+        }
+
+        let session = web_auth_service.get_session_from_bearer(req.request());
+
+        if session.is_none() {
+            return Box::pin(async move { Ok(req.error_response(error::ErrorUnauthorized(""))) });
+        }
+        let session: Session = session.unwrap();
+
+        let user = web_auth_service.login_by_bearer_session(&session);
+
+        if user.is_err() {
+            return Box::pin(async move { Ok(req.error_response(error::ErrorUnauthorized(""))) });
+        }
+
+        let user: User = user.unwrap();
+
+        if let Err(e) = check_restriction(&req, &user) {
+            return Box::pin(async move { Ok(req.error_response(e)) });
+        }
+
+        let _ = web_auth_service.save_session_info(req.request(), &session);
+
+        let session: Arc<Session> = Arc::new(session);
+        req.extensions_mut().insert(session);
+
+        let user: Arc<User> = Arc::new(user);
+        req.extensions_mut().insert(user);
+
+        let fut = self.service.call(req);
+
+        Box::pin(fut)
+    }
+}
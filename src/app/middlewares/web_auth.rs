@@ -1,4 +1,7 @@
-use crate::{Session, User, WebAuthService};
+use crate::app::controllers::web::auth::verify_email::{get_notice_url as get_verify_email_notice_url, get_url as get_verify_email_url};
+use crate::app::controllers::web::profile::get_url as get_profile_url;
+use crate::app::controllers::web::two_factor::index::get_url as get_two_factor_url;
+use crate::{AlertVariant, EmailVerificationService, RoleService, Session, User, UserService, UserTwoFactorService, WebAuthService, WebHttpResponse};
 use actix_utils::future::{ready, Ready};
 use actix_web::body::BoxBody;
 use actix_web::web::Data;
@@ -36,6 +39,46 @@ pub struct InnerWebAuthMiddleware<S> {
     service: Rc<S>,
 }
 
+/// Users with a role that requires the second factor can only open its page until they enable it.
+/// A failed check is a "500 Internal Server Error", it never lets the user skip the enrollment.
+fn is_two_factor_enrollment_needed(req: &ServiceRequest, user: &User) -> Result<bool, Error> {
+    if user.roles_ids.as_ref().map(|ids| ids.is_empty()).unwrap_or(true) {
+        return Ok(false);
+    }
+    if req.path().starts_with(&get_two_factor_url()) {
+        return Ok(false);
+    }
+    let role_service = req.app_data::<Data<RoleService>>();
+    let user_two_factor_service = req.app_data::<Data<UserTwoFactorService>>();
+    if let (Some(role_service), Some(user_two_factor_service)) = (role_service, user_two_factor_service) {
+        let roles = role_service.all_by_ids_throw_http(user.roles_ids.as_ref().unwrap())?;
+        if UserTwoFactorService::is_required(user, &roles) {
+            return Ok(!user_two_factor_service.is_enabled_throw_http(user.id)?);
+        }
+    }
+    Ok(false)
+}
+
+/// Unverified users can only open the "verify your email" page when the verification is required.
+fn is_email_verification_needed(req: &ServiceRequest, user: &User) -> bool {
+    if req.path().starts_with(&get_verify_email_url()) {
+        return false;
+    }
+    req.app_data::<Data<EmailVerificationService>>()
+        .map(|email_verification_service| email_verification_service.is_blocked(user))
+        .unwrap_or(false)
+}
+
+/// Users with an expired password can only open the profile page until they change it.
+fn is_password_change_needed(req: &ServiceRequest, user: &User) -> bool {
+    if req.path().starts_with(&get_profile_url()) {
+        return false;
+    }
+    req.app_data::<Data<UserService>>()
+        .map(|user_service| user_service.is_password_expired(user))
+        .unwrap_or(false)
+}
+
 fn unauthorized_redirect(auth_service: &WebAuthService) -> HttpResponse {
     HttpResponse::SeeOther()
         .cookie(auth_service.make_clear_cookie())
@@ -82,6 +125,60 @@ where
         }
 
         let (user, new_session) = auth_data.unwrap();
+
+        // The session list keeps working even if the details were not saved.
+        let _ = web_auth_service.save_session_info(req.request(), &new_session);
+
+        let is_two_factor_enrollment_needed = match is_two_factor_enrollment_needed(&req, &user) {
+            Ok(is_two_factor_enrollment_needed) => is_two_factor_enrollment_needed,
+            Err(e) => return Box::pin(async move { Ok(req.error_response(e)) }),
+        };
+
+        if is_two_factor_enrollment_needed {
+            return Box::pin(async move {
+                let location = get_two_factor_url();
+                let res = HttpResponse::SeeOther()
+                    .cookie(web_auth_service.make_cookie_throw_http(&new_session)?)
+                    .set_alerts(vec![AlertVariant::TwoFactorRequiredWarning])
+                    .insert_header((
+                        LOCATION,
+                        HeaderValue::from_str(&location).unwrap_or(HeaderValue::from_static("/")),
+                    ))
+                    .finish();
+                Ok(req.into_response(res))
+            });
+        }
+
+        if is_email_verification_needed(&req, &user) {
+            return Box::pin(async move {
+                let location = get_verify_email_notice_url();
+                let res = HttpResponse::SeeOther()
+                    .cookie(web_auth_service.make_cookie_throw_http(&new_session)?)
+                    .set_alerts(vec![AlertVariant::VerifyEmailRequiredWarning])
+                    .insert_header((
+                        LOCATION,
+                        HeaderValue::from_str(&location).unwrap_or(HeaderValue::from_static("/")),
+                    ))
+                    .finish();
+                Ok(req.into_response(res))
+            });
+        }
+
+        if is_password_change_needed(&req, &user) {
+            return Box::pin(async move {
+                let location = get_profile_url();
+                let res = HttpResponse::SeeOther()
+                    .cookie(web_auth_service.make_cookie_throw_http(&new_session)?)
+                    .set_alerts(vec![AlertVariant::PasswordExpiredWarning])
+                    .insert_header((
+                        LOCATION,
+                        HeaderValue::from_str(&location).unwrap_or(HeaderValue::from_static("/")),
+                    ))
+                    .finish();
+                Ok(req.into_response(res))
+            });
+        }
+
         let new_session: Arc<Session> = Arc::new(new_session);
         let new_session_rc: Arc<Session> = Arc::clone(&new_session);
         req.extensions_mut().insert(Arc::clone(&new_session));
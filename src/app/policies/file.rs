@@ -1,4 +1,7 @@
-use crate::{can_permission, Permission, Role, User};
+use crate::{
+    can_permission, has_permission, File, FileFilter, Permission, Role, User, UserFile,
+    UserFileFilter,
+};
 
 pub struct FilePolicy;
 
@@ -15,4 +18,136 @@ impl FilePolicy {
     pub fn can_delete(user: &User, user_roles: &Vec<Role>) -> bool {
         can_permission!(user, user_roles, Permission::FilesDelete);
     }
-}
\ No newline at end of file
+    pub fn can_show_own(user: &User, user_roles: &Vec<Role>) -> bool {
+        can_permission!(user, user_roles, Permission::FilesShowOwn);
+    }
+    pub fn can_update_own(user: &User, user_roles: &Vec<Role>) -> bool {
+        can_permission!(user, user_roles, Permission::FilesUpdateOwn);
+    }
+    pub fn can_delete_own(user: &User, user_roles: &Vec<Role>) -> bool {
+        can_permission!(user, user_roles, Permission::FilesDeleteOwn);
+    }
+
+    /// The list of the files is opened with any of the permissions, it is scoped by `file_filters`.
+    pub fn can_show_any_or_own(user: &User, user_roles: &Vec<Role>) -> bool {
+        Self::can_show(user, user_roles) || Self::can_show_own(user, user_roles)
+    }
+    pub fn can_update_any_or_own(user: &User, user_roles: &Vec<Role>) -> bool {
+        Self::can_update(user, user_roles) || Self::can_update_own(user, user_roles)
+    }
+    pub fn can_delete_any_or_own(user: &User, user_roles: &Vec<Role>) -> bool {
+        Self::can_delete(user, user_roles) || Self::can_delete_own(user, user_roles)
+    }
+
+    pub fn can_show_user_file(user: &User, user_roles: &Vec<Role>, user_file: &UserFile) -> bool {
+        Self::can_show(user, user_roles)
+            || (user_file.user_id == user.id
+                && has_permission(user, user_roles, Permission::FilesShowOwn))
+    }
+    pub fn can_update_user_file(user: &User, user_roles: &Vec<Role>, user_file: &UserFile) -> bool {
+        Self::can_update(user, user_roles)
+            || (user_file.user_id == user.id
+                && has_permission(user, user_roles, Permission::FilesUpdateOwn))
+    }
+    pub fn can_delete_user_file(user: &User, user_roles: &Vec<Role>, user_file: &UserFile) -> bool {
+        Self::can_delete(user, user_roles)
+            || (user_file.user_id == user.id
+                && has_permission(user, user_roles, Permission::FilesDeleteOwn))
+    }
+
+    /// The file is owned by the user having a user file of it, the user files must be attached.
+    /// The file itself is shared by all of its owners, so it is deleted only with "files_delete".
+    pub fn can_show_file(user: &User, user_roles: &Vec<Role>, file: &File) -> bool {
+        if Self::can_show(user, user_roles) {
+            return true;
+        }
+        if !Self::can_show_own(user, user_roles) {
+            return false;
+        }
+        match &file.user_files {
+            Some(user_files) => user_files.iter().any(|user_file| user_file.user_id == user.id),
+            None => false,
+        }
+    }
+
+    /// The filters limiting the list of the files to the ones the user may see.
+    pub fn file_filters(user: &User, user_roles: &Vec<Role>) -> Vec<FileFilter> {
+        if Self::can_show(user, user_roles) {
+            Vec::new()
+        } else {
+            vec![FileFilter::UserId(user.id)]
+        }
+    }
+
+    /// The filters limiting the list of the user files to the ones the user may see.
+    pub fn user_file_filters(user: &User, user_roles: &Vec<Role>) -> Vec<UserFileFilter> {
+        if Self::can_show(user, user_roles) {
+            Vec::new()
+        } else {
+            vec![UserFileFilter::UserId(user.id)]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy() {
+        let mut user = User::empty("".to_string());
+        user.id = 1;
+        let mut user_roles: Vec<Role> = vec![Role::default()];
+        let own_user_file = UserFile {
+            user_id: 1,
+            ..Default::default()
+        };
+        let other_user_file = UserFile {
+            user_id: 2,
+            ..Default::default()
+        };
+        assert_eq!(FilePolicy::can_show_any_or_own(&user, &user_roles), false);
+        assert_eq!(FilePolicy::can_update_user_file(&user, &user_roles, &own_user_file), false);
+
+        user.is_super_admin = true;
+        assert_eq!(FilePolicy::can_update_user_file(&user, &user_roles, &other_user_file), true);
+        assert_eq!(FilePolicy::file_filters(&user, &user_roles).len(), 0);
+
+        user.is_super_admin = false;
+        user.roles_ids = Some(vec![1]);
+        user_roles = vec![Role {
+            id: 1,
+            permissions: Some(vec![
+                Permission::FilesShowOwn.to_string(),
+                Permission::FilesUpdateOwn.to_string(),
+            ]),
+            ..Default::default()
+        }];
+        assert_eq!(FilePolicy::can_show(&user, &user_roles), false);
+        assert_eq!(FilePolicy::can_show_any_or_own(&user, &user_roles), true);
+        assert_eq!(FilePolicy::can_update_any_or_own(&user, &user_roles), true);
+        assert_eq!(FilePolicy::can_delete_any_or_own(&user, &user_roles), false);
+        assert_eq!(FilePolicy::can_show_user_file(&user, &user_roles, &own_user_file), true);
+        assert_eq!(FilePolicy::can_show_user_file(&user, &user_roles, &other_user_file), false);
+        assert_eq!(FilePolicy::can_update_user_file(&user, &user_roles, &own_user_file), true);
+        assert_eq!(FilePolicy::can_update_user_file(&user, &user_roles, &other_user_file), false);
+        assert_eq!(FilePolicy::can_delete_user_file(&user, &user_roles, &own_user_file), false);
+
+        let mut file = File::default();
+        assert_eq!(FilePolicy::can_show_file(&user, &user_roles, &file), false);
+        file.user_files = Some(vec![other_user_file.clone()]);
+        assert_eq!(FilePolicy::can_show_file(&user, &user_roles, &file), false);
+        file.user_files = Some(vec![other_user_file.clone(), own_user_file.clone()]);
+        assert_eq!(FilePolicy::can_show_file(&user, &user_roles, &file), true);
+
+        let filters = FilePolicy::user_file_filters(&user, &user_roles);
+        assert_eq!(filters.len(), 1);
+        assert!(matches!(filters[0], UserFileFilter::UserId(1)));
+        let filters = FilePolicy::file_filters(&user, &user_roles);
+        assert!(matches!(filters[0], FileFilter::UserId(1)));
+
+        user_roles[0].permissions = Some(vec![Permission::FilesUpdate.to_string()]);
+        assert_eq!(FilePolicy::can_update_user_file(&user, &user_roles, &other_user_file), true);
+        assert_eq!(FilePolicy::can_show_user_file(&user, &user_roles, &own_user_file), false);
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{can_permission, Permission, Role, User};
+
+pub struct JobPolicy;
+
+impl JobPolicy {
+    pub fn can_show(user: &User, user_roles: &Vec<Role>) -> bool {
+        can_permission!(user, user_roles, Permission::JobsShow);
+    }
+    pub fn can_retry(user: &User, user_roles: &Vec<Role>) -> bool {
+        can_permission!(user, user_roles, Permission::JobsRetry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy() {
+        let mut user = User::empty("".to_string());
+        let mut user_roles: Vec<Role> = vec![Role::default()];
+        assert_eq!(JobPolicy::can_show(&user, &user_roles), false);
+        assert_eq!(JobPolicy::can_retry(&user, &user_roles), false);
+        user.is_super_admin = true;
+        assert_eq!(JobPolicy::can_show(&user, &user_roles), true);
+        assert_eq!(JobPolicy::can_retry(&user, &user_roles), true);
+        user.is_super_admin = false;
+        user.roles_ids = Some(vec![1]);
+        let mut role = Role::default();
+        role.id = 1;
+        role.permissions = Some(vec![Permission::JobsShow.to_string()]);
+        user_roles = vec![role];
+        assert_eq!(JobPolicy::can_show(&user, &user_roles), true);
+        assert_eq!(JobPolicy::can_retry(&user, &user_roles), false);
+    }
+}
@@ -1,8 +1,14 @@
+use crate::{Permission, Role, User};
+
+mod audit_log;
 mod file;
+mod job;
 mod role;
 mod user;
 
+pub use self::audit_log::{*};
 pub use self::file::{*};
+pub use self::job::{*};
 pub use self::role::{*};
 pub use self::user::{*};
 
@@ -64,14 +70,15 @@ macro_rules! can_permission {
         }
         let roles_ids = $user.roles_ids.as_ref().unwrap();
         for role in $roles {
-            if let Some(permissions) = &role.permissions {
-                if permissions.len() != 0 && roles_ids.contains(&role.id) {
-                    if permissions.contains(&$permission.to_string()) {
-                        return true;
-                    }
-                }
+            if roles_ids.contains(&role.id) && role.has_permission(&$permission) {
+                return true;
             }
         }
         return false;
     };
-}
\ No newline at end of file
+}
+
+/// The same check as `can_permission!` as an expression, for the policies combining the permissions.
+pub fn has_permission(user: &User, user_roles: &Vec<Role>, permission: Permission) -> bool {
+    can_permission!(user, user_roles, permission);
+}
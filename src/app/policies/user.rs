@@ -1,4 +1,4 @@
-use crate::{can_permission, Permission, Role, User};
+use crate::{can_permission, has_permission, Permission, Role, User};
 
 pub struct UserPolicy;
 
@@ -18,6 +18,28 @@ impl UserPolicy {
     pub fn can_set_roles(user: &User, user_roles: &Vec<Role>) -> bool {
         can_permission!(user, user_roles, Permission::UsersSetRoles);
     }
+    pub fn can_update_same_roles(user: &User, user_roles: &Vec<Role>) -> bool {
+        can_permission!(user, user_roles, Permission::UsersUpdateSameRoles);
+    }
+
+    /// With "users_update_same_roles" only the users sharing a role with the user are updated,
+    /// the super admins are never among them.
+    pub fn can_update_user(user: &User, user_roles: &Vec<Role>, edit_user: &User) -> bool {
+        if Self::can_update(user, user_roles) {
+            return true;
+        }
+        if edit_user.is_super_admin
+            || !has_permission(user, user_roles, Permission::UsersUpdateSameRoles)
+        {
+            return false;
+        }
+        match (&user.roles_ids, &edit_user.roles_ids) {
+            (Some(roles_ids), Some(edit_roles_ids)) => {
+                roles_ids.iter().any(|role_id| edit_roles_ids.contains(role_id))
+            }
+            _ => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -47,4 +69,28 @@ mod tests {
         assert_eq!(UserPolicy::can_create(&user, &user_roles), false);
         assert_eq!(UserPolicy::can_update(&user, &user_roles), false);
     }
+
+    #[test]
+    fn test_can_update_user() {
+        let mut user = User::empty("".to_string());
+        user.roles_ids = Some(vec![1]);
+        let user_roles: Vec<Role> = vec![Role {
+            id: 1,
+            permissions: Some(vec![Permission::UsersUpdateSameRoles.to_string()]),
+            ..Default::default()
+        }];
+
+        let mut edit_user = User::empty("".to_string());
+        assert_eq!(UserPolicy::can_update_user(&user, &user_roles, &edit_user), false);
+        edit_user.roles_ids = Some(vec![2]);
+        assert_eq!(UserPolicy::can_update_user(&user, &user_roles, &edit_user), false);
+        edit_user.roles_ids = Some(vec![2, 1]);
+        assert_eq!(UserPolicy::can_update_user(&user, &user_roles, &edit_user), true);
+        assert_eq!(UserPolicy::can_update(&user, &user_roles), false);
+        edit_user.is_super_admin = true;
+        assert_eq!(UserPolicy::can_update_user(&user, &user_roles, &edit_user), false);
+
+        user.is_super_admin = true;
+        assert_eq!(UserPolicy::can_update_user(&user, &user_roles, &edit_user), true);
+    }
 }
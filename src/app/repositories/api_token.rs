@@ -0,0 +1,164 @@
+use crate::{
+    option_take_json_from_mysql_row, option_to_json_string_for_mysql, take_from_mysql_row,
+    take_some_datetime_from_mysql_row, ApiToken, ApiTokenColumn, AppError, FromMysqlDto,
+    MysqlColumnEnum, MysqlIdColumn, MysqlPool, MysqlQueryBuilder, MysqlRepository,
+    PaginateParams, ToMysqlDto,
+};
+use actix_web::web::Data;
+use mysql::Row;
+use mysql::Value;
+use strum_macros::{Display, EnumIter, EnumString};
+
+pub struct ApiTokenMysqlRepository {
+    db_pool: Data<MysqlPool>,
+}
+
+impl MysqlRepository<ApiToken, ApiTokenPaginateParams, ApiTokenColumn, ApiTokenFilter, ApiTokenSort>
+    for ApiTokenMysqlRepository
+{
+    fn get_repository_name(&self) -> &str {
+        "ApiTokenMysqlRepository"
+    }
+    fn get_table(&self) -> &str {
+        "api_tokens"
+    }
+    fn get_db_pool(&self) -> &MysqlPool {
+        self.db_pool.get_ref()
+    }
+}
+
+impl ApiTokenMysqlRepository {
+    pub fn new(db_pool: Data<MysqlPool>) -> Self {
+        Self { db_pool }
+    }
+
+    pub fn first_by_token_hash(&self, token_hash: &str) -> Result<Option<ApiToken>, AppError> {
+        let filters = vec![ApiTokenFilter::TokenHash(token_hash.to_string())];
+        self.first(&filters)
+    }
+
+    pub fn all_by_user_id(&self, user_id: u64) -> Result<Vec<ApiToken>, AppError> {
+        let filters = vec![ApiTokenFilter::UserId(user_id)];
+        let sorts = vec![ApiTokenSort::IdDesc];
+        self.all(Some(&filters), Some(&sorts), &None)
+    }
+
+    pub fn first_by_id_and_user_id(
+        &self,
+        id: u64,
+        user_id: u64,
+    ) -> Result<Option<ApiToken>, AppError> {
+        let filters = vec![ApiTokenFilter::Id(id), ApiTokenFilter::UserId(user_id)];
+        self.first(&filters)
+    }
+
+    pub fn delete_by_id(&self, id: u64) -> Result<(), AppError> {
+        let filters = vec![ApiTokenFilter::Id(id)];
+        self.delete(&filters)
+    }
+
+    pub fn update_last_used_at_by_id(&self, id: u64, last_used_at: &str) -> Result<(), AppError> {
+        let filters = vec![ApiTokenFilter::Id(id)];
+
+        let mut data = ApiToken::default();
+        data.last_used_at = Some(last_used_at.to_string());
+
+        let columns: Option<Vec<ApiTokenColumn>> = Some(vec![ApiTokenColumn::LastUsedAt]);
+
+        self.update(&filters, &data, &columns)
+    }
+}
+
+pub type ApiTokenPaginateParams = PaginateParams<ApiTokenFilter, ApiTokenSort>;
+
+#[derive(Debug, Clone)]
+pub enum ApiTokenFilter {
+    Id(u64),
+    UserId(u64),
+    TokenHash(String),
+}
+
+impl MysqlQueryBuilder for ApiTokenFilter {
+    fn push_params_to_mysql_query(&self, query: &mut String) {
+        match self {
+            Self::Id(_) => query.push_str("id=:f_id"),
+            Self::UserId(_) => query.push_str("user_id=:f_user_id"),
+            Self::TokenHash(_) => query.push_str("token_hash=:f_token_hash"),
+        }
+    }
+
+    fn push_params_to_vec(&self, params: &mut Vec<(String, Value)>) {
+        match self {
+            Self::Id(value) => {
+                params.push(("f_id".to_string(), Value::from(value)));
+            }
+            Self::UserId(value) => {
+                params.push(("f_user_id".to_string(), Value::from(value)));
+            }
+            Self::TokenHash(value) => {
+                params.push(("f_token_hash".to_string(), Value::from(value.to_string())));
+            }
+        }
+    }
+}
+
+#[derive(Debug, Display, EnumString, EnumIter)]
+#[strum(serialize_all = "snake_case")]
+pub enum ApiTokenSort {
+    IdAsc,
+    IdDesc,
+}
+
+impl MysqlQueryBuilder for ApiTokenSort {
+    fn push_params_to_mysql_query(&self, query: &mut String) {
+        match self {
+            Self::IdAsc => query.push_str("id ASC"),
+            Self::IdDesc => query.push_str("id DESC"),
+        };
+    }
+
+    fn push_params_to_vec(&self, _: &mut Vec<(String, Value)>) {}
+}
+
+impl ToMysqlDto<ApiTokenColumn> for ApiToken {
+    fn push_mysql_param_to_vec(&self, column: &ApiTokenColumn, params: &mut Vec<(String, Value)>) {
+        match column {
+            ApiTokenColumn::Id => params.push((column.to_string(), Value::from(self.id.to_owned()))),
+            ApiTokenColumn::UserId => params.push((column.to_string(), Value::from(self.user_id.to_owned()))),
+            ApiTokenColumn::Name => params.push((column.to_string(), Value::from(self.name.to_owned()))),
+            ApiTokenColumn::TokenHash => params.push((column.to_string(), Value::from(self.token_hash.to_owned()))),
+            ApiTokenColumn::Scopes => {
+                let scopes: Option<String> = option_to_json_string_for_mysql(&self.scopes);
+                params.push((column.to_string(), Value::from(scopes)))
+            }
+            ApiTokenColumn::ExpiresAt => params.push((column.to_string(), Value::from(self.expires_at.to_owned()))),
+            ApiTokenColumn::LastUsedAt => params.push((column.to_string(), Value::from(self.last_used_at.to_owned()))),
+            ApiTokenColumn::CreatedAt => params.push((column.to_string(), Value::from(self.created_at.to_owned()))),
+        }
+    }
+    fn get_id(&self) -> u64 {
+        self.id
+    }
+}
+
+impl FromMysqlDto for ApiToken {
+    fn take_from_mysql_row(row: &mut Row) -> Result<Self, AppError> {
+        Ok(Self {
+            id: take_from_mysql_row(row, ApiTokenColumn::Id.to_string().as_str())?,
+            user_id: take_from_mysql_row(row, ApiTokenColumn::UserId.to_string().as_str())?,
+            name: take_from_mysql_row(row, ApiTokenColumn::Name.to_string().as_str())?,
+            token_hash: take_from_mysql_row(row, ApiTokenColumn::TokenHash.to_string().as_str())?,
+            scopes: option_take_json_from_mysql_row(row, ApiTokenColumn::Scopes.to_string().as_str()),
+            expires_at: take_some_datetime_from_mysql_row(row, ApiTokenColumn::ExpiresAt.to_string().as_str())?,
+            last_used_at: take_some_datetime_from_mysql_row(row, ApiTokenColumn::LastUsedAt.to_string().as_str())?,
+            created_at: take_some_datetime_from_mysql_row(row, ApiTokenColumn::CreatedAt.to_string().as_str())?,
+        })
+    }
+}
+
+impl MysqlColumnEnum for ApiTokenColumn {}
+impl MysqlIdColumn for ApiTokenColumn {
+    fn get_mysql_id_column() -> Self {
+        Self::Id
+    }
+}
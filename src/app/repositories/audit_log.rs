@@ -0,0 +1,155 @@
+use crate::{
+    option_take_json_from_mysql_row, option_to_json_string_for_mysql, take_from_mysql_row,
+    take_some_datetime_from_mysql_row, AppError, AuditLog, AuditLogColumn, FromMysqlDto,
+    MysqlColumnEnum, MysqlIdColumn, MysqlPool, MysqlQueryBuilder, MysqlRepository,
+    PaginateParams, ToMysqlDto,
+};
+use actix_web::web::Data;
+use mysql::Row;
+use mysql::Value;
+use strum_macros::{Display, EnumIter, EnumString};
+
+pub struct AuditLogMysqlRepository {
+    db_pool: Data<MysqlPool>,
+}
+
+impl MysqlRepository<AuditLog, AuditLogPaginateParams, AuditLogColumn, AuditLogFilter, AuditLogSort>
+    for AuditLogMysqlRepository
+{
+    fn get_repository_name(&self) -> &str {
+        "AuditLogMysqlRepository"
+    }
+    fn get_table(&self) -> &str {
+        "audit_logs"
+    }
+    fn get_db_pool(&self) -> &MysqlPool {
+        self.db_pool.get_ref()
+    }
+}
+
+impl AuditLogMysqlRepository {
+    pub fn new(db_pool: Data<MysqlPool>) -> Self {
+        Self { db_pool }
+    }
+}
+
+pub type AuditLogPaginateParams = PaginateParams<AuditLogFilter, AuditLogSort>;
+
+#[derive(Debug, Clone)]
+pub enum AuditLogFilter {
+    Id(u64),
+    ActorId(u64),
+    Action(String),
+    EntityType(String),
+    EntityId(u64),
+    CreatedAtFrom(String),
+    CreatedAtTo(String),
+}
+
+impl MysqlQueryBuilder for AuditLogFilter {
+    fn push_params_to_mysql_query(&self, query: &mut String) {
+        match self {
+            Self::Id(_) => query.push_str("id=:f_id"),
+            Self::ActorId(_) => query.push_str("actor_id=:f_actor_id"),
+            Self::Action(_) => query.push_str("action=:f_action"),
+            Self::EntityType(_) => query.push_str("entity_type=:f_entity_type"),
+            Self::EntityId(_) => query.push_str("entity_id=:f_entity_id"),
+            Self::CreatedAtFrom(_) => query.push_str("created_at>=:f_created_at_from"),
+            Self::CreatedAtTo(_) => query.push_str("created_at<=:f_created_at_to"),
+        }
+    }
+
+    fn push_params_to_vec(&self, params: &mut Vec<(String, Value)>) {
+        match self {
+            Self::Id(value) => {
+                params.push(("f_id".to_string(), Value::from(value)));
+            }
+            Self::ActorId(value) => {
+                params.push(("f_actor_id".to_string(), Value::from(value)));
+            }
+            Self::Action(value) => {
+                params.push(("f_action".to_string(), Value::from(value.to_string())));
+            }
+            Self::EntityType(value) => {
+                params.push(("f_entity_type".to_string(), Value::from(value.to_string())));
+            }
+            Self::EntityId(value) => {
+                params.push(("f_entity_id".to_string(), Value::from(value)));
+            }
+            Self::CreatedAtFrom(value) => {
+                params.push(("f_created_at_from".to_string(), Value::from(value.to_string())));
+            }
+            Self::CreatedAtTo(value) => {
+                params.push(("f_created_at_to".to_string(), Value::from(value.to_string())));
+            }
+        }
+    }
+}
+
+#[derive(Debug, Display, EnumString, EnumIter)]
+#[strum(serialize_all = "snake_case")]
+pub enum AuditLogSort {
+    IdAsc,
+    IdDesc,
+}
+
+impl MysqlQueryBuilder for AuditLogSort {
+    fn push_params_to_mysql_query(&self, query: &mut String) {
+        match self {
+            Self::IdAsc => query.push_str("id ASC"),
+            Self::IdDesc => query.push_str("id DESC"),
+        };
+    }
+
+    fn push_params_to_vec(&self, _: &mut Vec<(String, Value)>) {}
+}
+
+impl ToMysqlDto<AuditLogColumn> for AuditLog {
+    fn push_mysql_param_to_vec(&self, column: &AuditLogColumn, params: &mut Vec<(String, Value)>) {
+        match column {
+            AuditLogColumn::Id => params.push((column.to_string(), Value::from(self.id.to_owned()))),
+            AuditLogColumn::ActorId => params.push((column.to_string(), Value::from(self.actor_id.to_owned()))),
+            AuditLogColumn::Action => params.push((column.to_string(), Value::from(self.action.to_owned()))),
+            AuditLogColumn::EntityType => params.push((column.to_string(), Value::from(self.entity_type.to_owned()))),
+            AuditLogColumn::EntityId => params.push((column.to_string(), Value::from(self.entity_id.to_owned()))),
+            AuditLogColumn::OldValues => {
+                let old_values: Option<String> = option_to_json_string_for_mysql(&self.old_values);
+                params.push((column.to_string(), Value::from(old_values)))
+            }
+            AuditLogColumn::NewValues => {
+                let new_values: Option<String> = option_to_json_string_for_mysql(&self.new_values);
+                params.push((column.to_string(), Value::from(new_values)))
+            }
+            AuditLogColumn::Ip => params.push((column.to_string(), Value::from(self.ip.to_owned()))),
+            AuditLogColumn::UserAgent => params.push((column.to_string(), Value::from(self.user_agent.to_owned()))),
+            AuditLogColumn::CreatedAt => params.push((column.to_string(), Value::from(self.created_at.to_owned()))),
+        }
+    }
+    fn get_id(&self) -> u64 {
+        self.id
+    }
+}
+
+impl FromMysqlDto for AuditLog {
+    fn take_from_mysql_row(row: &mut Row) -> Result<Self, AppError> {
+        Ok(Self {
+            id: take_from_mysql_row(row, AuditLogColumn::Id.to_string().as_str())?,
+            actor_id: take_from_mysql_row(row, AuditLogColumn::ActorId.to_string().as_str())?,
+            action: take_from_mysql_row(row, AuditLogColumn::Action.to_string().as_str())?,
+            entity_type: take_from_mysql_row(row, AuditLogColumn::EntityType.to_string().as_str())?,
+            entity_id: take_from_mysql_row(row, AuditLogColumn::EntityId.to_string().as_str())?,
+            old_values: option_take_json_from_mysql_row(row, AuditLogColumn::OldValues.to_string().as_str()),
+            new_values: option_take_json_from_mysql_row(row, AuditLogColumn::NewValues.to_string().as_str()),
+            ip: take_from_mysql_row(row, AuditLogColumn::Ip.to_string().as_str())?,
+            user_agent: take_from_mysql_row(row, AuditLogColumn::UserAgent.to_string().as_str())?,
+            created_at: take_some_datetime_from_mysql_row(row, AuditLogColumn::CreatedAt.to_string().as_str())?,
+        })
+    }
+}
+
+impl MysqlColumnEnum for AuditLogColumn {}
+impl MysqlIdColumn for AuditLogColumn {
+    fn get_mysql_id_column() -> Self {
+        Self::Id
+    }
+}
@@ -2,12 +2,12 @@ use crate::helpers::{
     collect_directories_from_dir_into_str_vec, collect_files_from_dir_into_str_vec,
     create_dir_all_for_file,
 };
+use sha2::{Digest, Sha256};
 use std::fs;
-use std::fs::File;
+use std::fs::{File, OpenOptions};
 use std::io;
-use std::io::{BufReader, BufWriter, ErrorKind};
+use std::io::{BufRead, BufReader, BufWriter, ErrorKind, Seek, SeekFrom, Write};
 use std::os::unix::fs::MetadataExt;
-use std::process::Command;
 use std::time::SystemTime;
 use ureq::http::StatusCode;
 
@@ -37,7 +37,7 @@ pub trait DiskRepository {
     fn get(&self, path: &str) -> io::Result<Vec<u8>>;
     // Get a resource to read the file.
     #[allow(unused_variables)]
-    fn read_stream(&self, path: &str) -> io::Result<BufReader<File>> {
+    fn read_stream(&self, path: &str) -> io::Result<Box<dyn BufRead>> {
         Err(io::Error::other(FUN_NOT_DEFINED_ERROR_MESSAGE))
     }
     // Store the uploaded file on the disk.
@@ -47,7 +47,12 @@ pub trait DiskRepository {
     }
     // Write a new file using a stream.
     #[allow(unused_variables)]
-    fn write_stream(&self, path: &str) -> io::Result<BufWriter<File>> {
+    fn write_stream(&self, path: &str) -> io::Result<Box<dyn Write>> {
+        Err(io::Error::other(FUN_NOT_DEFINED_ERROR_MESSAGE))
+    }
+    // Write a new file using a stream, the final path is given when the content is complete.
+    #[allow(unused_variables)]
+    fn upload_stream(&self, temp_path: &str) -> io::Result<Box<dyn DiskUploadStream>> {
         Err(io::Error::other(FUN_NOT_DEFINED_ERROR_MESSAGE))
     }
     // // Get the visibility for the given path.
@@ -109,6 +114,12 @@ pub trait DiskRepository {
     }
 }
 
+// The file being written whose path depends on the content, e.g. on the hash of it.
+pub trait DiskUploadStream: Write {
+    // Store the written content at the path, the hash is the SHA-256 of the content.
+    fn finish(self: Box<Self>, path: &str, hash: &str) -> io::Result<()>;
+}
+
 pub struct DiskLocalRepository {
     pub root: String,
     pub separator: String,
@@ -151,6 +162,14 @@ impl DiskLocalRepository {
 
         Ok(())
     }
+    // Open the file to write from the offset, the content after the offset is dropped.
+    pub fn append_stream(&self, path: &str, offset: u64) -> io::Result<Box<dyn Write>> {
+        create_dir_all_for_file(&path, &self.separator)?;
+        let mut file = OpenOptions::new().create(true).write(true).truncate(false).open(path)?;
+        file.set_len(offset)?;
+        file.seek(SeekFrom::Start(offset))?;
+        Ok(Box::new(BufWriter::new(file)))
+    }
     pub fn set_public(
         &self,
         path: &str,
@@ -201,11 +220,10 @@ impl DiskRepository for DiskLocalRepository {
         make_local_path(path, &self.root, &self.separator)
     }
     fn hash(&self, file_path: &str) -> io::Result<String> {
-        let hash = Command::new("sha256sum").args([file_path]).output()?.stdout;
-        let hash =
-            String::from_utf8(hash).map_err(|e| io::Error::new(ErrorKind::Other, e.to_string()))?;
-        let hash = hash.split_whitespace().next().unwrap_or("").to_string();
-        Ok(hash)
+        let mut hasher = Sha256::new();
+        let mut reader = BufReader::new(File::open(file_path)?);
+        io::copy(&mut reader, &mut hasher)?;
+        Ok(hex::encode(hasher.finalize()))
     }
     fn exists(&self, path: &str) -> io::Result<bool> {
         fs::exists(path)
@@ -213,13 +231,23 @@ impl DiskRepository for DiskLocalRepository {
     fn get(&self, path: &str) -> io::Result<Vec<u8>> {
         fs::read(path)
     }
-    fn read_stream(&self, path: &str) -> io::Result<BufReader<File>> {
+    fn read_stream(&self, path: &str) -> io::Result<Box<dyn BufRead>> {
         let file = File::open(path)?;
-        Ok(BufReader::new(file))
+        Ok(Box::new(BufReader::new(file)))
     }
-    fn write_stream(&self, path: &str) -> io::Result<BufWriter<File>> {
+    fn write_stream(&self, path: &str) -> io::Result<Box<dyn Write>> {
+        create_dir_all_for_file(&path, &self.separator)?;
         let file = File::create(path)?;
-        Ok(BufWriter::new(file))
+        Ok(Box::new(BufWriter::new(file)))
+    }
+    fn upload_stream(&self, temp_path: &str) -> io::Result<Box<dyn DiskUploadStream>> {
+        create_dir_all_for_file(temp_path, &self.separator)?;
+        let file = File::create(temp_path)?;
+        Ok(Box::new(DiskLocalUploadStream {
+            temp_path: temp_path.to_string(),
+            separator: self.separator.to_owned(),
+            writer: BufWriter::new(file),
+        }))
     }
     fn size(&self, path: &str) -> io::Result<u64> {
         Ok(fs::metadata(path)?.size())
@@ -272,6 +300,30 @@ impl DiskRepository for DiskLocalRepository {
     }
 }
 
+// The content is written to the temporary file, it is renamed to the final path on finish.
+pub struct DiskLocalUploadStream {
+    temp_path: String,
+    separator: String,
+    writer: BufWriter<File>,
+}
+
+impl Write for DiskLocalUploadStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl DiskUploadStream for DiskLocalUploadStream {
+    fn finish(mut self: Box<Self>, path: &str, _hash: &str) -> io::Result<()> {
+        self.writer.flush()?;
+        create_dir_all_for_file(path, &self.separator)?;
+        fs::rename(&self.temp_path, path)
+    }
+}
+
 pub struct DiskExternalRepository {}
 
 impl DiskExternalRepository {
@@ -776,4 +828,31 @@ mod tests {
         assert_eq!(String::from_utf8(final_content).unwrap(), content);
         fs::remove_file(&path).unwrap();
     }
+
+    #[test]
+    fn test_local_disk_call_upload_stream() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::repositories::disk::tests::test_local_disk_call_upload_stream
+        let root = env::current_dir().unwrap();
+        let root = root.to_str().unwrap();
+        let repository = DiskLocalRepository::new(root, root, MAIN_SEPARATOR_STR);
+        let dir_path = repository
+            .path("/test_local_disk_call_upload_stream")
+            .unwrap();
+        let temp_path = repository
+            .path("/test_local_disk_call_upload_stream/.uploads/test.part")
+            .unwrap();
+        let path = repository
+            .path("/test_local_disk_call_upload_stream/sub/test.txt")
+            .unwrap();
+
+        let mut upload_stream = repository.upload_stream(&temp_path).unwrap();
+        upload_stream.write_all(b"Test data1\n").unwrap();
+        upload_stream.write_all(b"Test data2").unwrap();
+        assert!(fs::exists(&temp_path).unwrap());
+        upload_stream.finish(&path, "").unwrap();
+
+        assert!(!fs::exists(&temp_path).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "Test data1\nTest data2");
+        fs::remove_dir_all(&dir_path).unwrap();
+    }
 }
@@ -155,12 +155,15 @@ pub enum FileFilter {
     Id(u64),
     Ids(Vec<u64>),
     CreatorUserId(u64),
+    // The files having a user file of the user, including the deleted ones.
+    UserId(u64),
     Disk(String),
     Path(String),
     Filename(String),
     Search(String),
     IsDelete(bool),
     IsDeleted(bool),
+    DeleteAtBefore(String),
 }
 
 impl MysqlQueryBuilder for FileFilter {
@@ -172,12 +175,16 @@ impl MysqlQueryBuilder for FileFilter {
                 query.push_str(&v)
             },
             Self::CreatorUserId(_) => query.push_str("creator_user_id=:f_creator_user_id"),
+            Self::UserId(_) => {
+                query.push_str("id IN (SELECT file_id FROM users_files WHERE user_id=:f_user_id)")
+            }
             Self::Disk(_) => query.push_str("disk=:f_disk"),
             Self::Path(_) => query.push_str("path=:f_path"),
             Self::Filename(_) => query.push_str("filename=:f_filename"),
             Self::Search(_) => query.push_str("(filename LIKE :f_search OR path LIKE :f_search)"),
             Self::IsDelete(_) => query.push_str("is_delete=:f_is_delete"),
             Self::IsDeleted(_) => query.push_str("is_deleted=:f_is_deleted"),
+            Self::DeleteAtBefore(_) => query.push_str("delete_at<=:f_delete_at_before"),
         }
     }
 
@@ -190,6 +197,9 @@ impl MysqlQueryBuilder for FileFilter {
             Self::CreatorUserId(value) => {
                 params.push(("f_creator_user_id".to_string(), Value::from(value)));
             }
+            Self::UserId(value) => {
+                params.push(("f_user_id".to_string(), Value::from(value)));
+            }
             Self::Disk(value) => {
                 params.push(("f_disk".to_string(), Value::from(value)));
             }
@@ -211,6 +221,9 @@ impl MysqlQueryBuilder for FileFilter {
             Self::IsDeleted(value) => {
                 params.push(("f_is_deleted".to_string(), Value::from(value)));
             }
+            Self::DeleteAtBefore(value) => {
+                params.push(("f_delete_at_before".to_string(), Value::from(value)));
+            }
         }
     }
 }
@@ -317,6 +330,7 @@ impl FromMysqlDto for File {
             is_deleted: take_from_mysql_row(row, FileColumn::IsDeleted.to_string().as_str())?,
             disk: take_from_mysql_row(row, FileColumn::Disk.to_string().as_str())?,
             user_files: None,
+            variants: None,
         })
     }
 }
@@ -0,0 +1,182 @@
+use crate::helpers::join_vec;
+use crate::{
+    take_from_mysql_row, take_some_datetime_from_mysql_row, AppError, FileVariant,
+    FileVariantColumn, FromMysqlDto, MysqlColumnEnum, MysqlIdColumn, MysqlPool, MysqlQueryBuilder,
+    MysqlRepository, PaginateParams, ToMysqlDto,
+};
+use actix_web::web::Data;
+use mysql::Row;
+use mysql::Value;
+use strum_macros::{Display, EnumIter, EnumString};
+
+pub struct FileVariantMysqlRepository {
+    db_pool: Data<MysqlPool>,
+}
+
+impl
+    MysqlRepository<
+        FileVariant,
+        FileVariantPaginateParams,
+        FileVariantColumn,
+        FileVariantFilter,
+        FileVariantSort,
+    > for FileVariantMysqlRepository
+{
+    fn get_repository_name(&self) -> &str {
+        "FileVariantMysqlRepository"
+    }
+    fn get_table(&self) -> &str {
+        "file_variants"
+    }
+    fn get_db_pool(&self) -> &MysqlPool {
+        self.db_pool.get_ref()
+    }
+}
+
+impl FileVariantMysqlRepository {
+    pub fn new(db_pool: Data<MysqlPool>) -> Self {
+        Self { db_pool }
+    }
+
+    pub fn first_by_file_id_and_name(
+        &self,
+        file_id: u64,
+        name: &str,
+    ) -> Result<Option<FileVariant>, AppError> {
+        let filters = vec![
+            FileVariantFilter::FileId(file_id),
+            FileVariantFilter::Name(name.to_string()),
+        ];
+        self.first(&filters)
+    }
+
+    pub fn all_by_file_ids(&self, file_ids: &Vec<u64>) -> Result<Vec<FileVariant>, AppError> {
+        let filters = vec![FileVariantFilter::FileIds(file_ids.to_owned())];
+        let sorts = vec![FileVariantSort::IdAsc];
+        self.all(Some(&filters), Some(&sorts), &None)
+    }
+
+    pub fn update_by_id(
+        &self,
+        id: u64,
+        data: &FileVariant,
+        columns: &Option<Vec<FileVariantColumn>>,
+    ) -> Result<(), AppError> {
+        let filters = vec![FileVariantFilter::Id(id)];
+        self.update(&filters, data, columns)
+    }
+
+    pub fn delete_by_file_id(&self, file_id: u64) -> Result<(), AppError> {
+        let filters = vec![FileVariantFilter::FileId(file_id)];
+        self.delete(&filters)
+    }
+}
+
+pub type FileVariantPaginateParams = PaginateParams<FileVariantFilter, FileVariantSort>;
+
+#[derive(Debug, Clone)]
+pub enum FileVariantFilter {
+    Id(u64),
+    FileId(u64),
+    FileIds(Vec<u64>),
+    Name(String),
+}
+
+impl MysqlQueryBuilder for FileVariantFilter {
+    fn push_params_to_mysql_query(&self, query: &mut String) {
+        match self {
+            Self::Id(_) => query.push_str("id=:f_id"),
+            Self::FileId(_) => query.push_str("file_id=:f_file_id"),
+            Self::FileIds(value) => {
+                let v = format!("file_id in ({})", join_vec(value, ","));
+                query.push_str(&v)
+            }
+            Self::Name(_) => query.push_str("name=:f_name"),
+        }
+    }
+
+    fn push_params_to_vec(&self, params: &mut Vec<(String, Value)>) {
+        match self {
+            Self::Id(value) => {
+                params.push(("f_id".to_string(), Value::from(value)));
+            }
+            Self::FileId(value) => {
+                params.push(("f_file_id".to_string(), Value::from(value)));
+            }
+            Self::FileIds(_) => {}
+            Self::Name(value) => {
+                params.push(("f_name".to_string(), Value::from(value)));
+            }
+        }
+    }
+}
+
+#[derive(Debug, Display, EnumString, EnumIter)]
+#[strum(serialize_all = "snake_case")]
+pub enum FileVariantSort {
+    IdAsc,
+    IdDesc,
+}
+
+impl MysqlQueryBuilder for FileVariantSort {
+    fn push_params_to_mysql_query(&self, query: &mut String) {
+        match self {
+            Self::IdAsc => query.push_str("id ASC"),
+            Self::IdDesc => query.push_str("id DESC"),
+        };
+    }
+
+    fn push_params_to_vec(&self, _: &mut Vec<(String, Value)>) {}
+}
+
+impl ToMysqlDto<FileVariantColumn> for FileVariant {
+    fn push_mysql_param_to_vec(
+        &self,
+        column: &FileVariantColumn,
+        params: &mut Vec<(String, Value)>,
+    ) {
+        match column {
+            FileVariantColumn::Id => params.push((column.to_string(), Value::from(self.id.to_owned()))),
+            FileVariantColumn::FileId => params.push((column.to_string(), Value::from(self.file_id.to_owned()))),
+            FileVariantColumn::Name => params.push((column.to_string(), Value::from(self.name.to_owned()))),
+            FileVariantColumn::Filename => params.push((column.to_string(), Value::from(self.filename.to_owned()))),
+            FileVariantColumn::Path => params.push((column.to_string(), Value::from(self.path.to_owned()))),
+            FileVariantColumn::Mime => params.push((column.to_string(), Value::from(self.mime.to_owned()))),
+            FileVariantColumn::Size => params.push((column.to_string(), Value::from(self.size.to_owned()))),
+            FileVariantColumn::Width => params.push((column.to_string(), Value::from(self.width.to_owned()))),
+            FileVariantColumn::Height => params.push((column.to_string(), Value::from(self.height.to_owned()))),
+            FileVariantColumn::Disk => params.push((column.to_string(), Value::from(self.disk.to_owned()))),
+            FileVariantColumn::CreatedAt => params.push((column.to_string(), Value::from(self.created_at.to_owned()))),
+            FileVariantColumn::UpdatedAt => params.push((column.to_string(), Value::from(self.updated_at.to_owned()))),
+        }
+    }
+    fn get_id(&self) -> u64 {
+        self.id
+    }
+}
+
+impl FromMysqlDto for FileVariant {
+    fn take_from_mysql_row(row: &mut Row) -> Result<Self, AppError> {
+        Ok(Self {
+            id: take_from_mysql_row(row, FileVariantColumn::Id.to_string().as_str())?,
+            file_id: take_from_mysql_row(row, FileVariantColumn::FileId.to_string().as_str())?,
+            name: take_from_mysql_row(row, FileVariantColumn::Name.to_string().as_str())?,
+            filename: take_from_mysql_row(row, FileVariantColumn::Filename.to_string().as_str())?,
+            path: take_from_mysql_row(row, FileVariantColumn::Path.to_string().as_str())?,
+            mime: take_from_mysql_row(row, FileVariantColumn::Mime.to_string().as_str())?,
+            size: take_from_mysql_row(row, FileVariantColumn::Size.to_string().as_str())?,
+            width: take_from_mysql_row(row, FileVariantColumn::Width.to_string().as_str())?,
+            height: take_from_mysql_row(row, FileVariantColumn::Height.to_string().as_str())?,
+            disk: take_from_mysql_row(row, FileVariantColumn::Disk.to_string().as_str())?,
+            created_at: take_some_datetime_from_mysql_row(row, FileVariantColumn::CreatedAt.to_string().as_str())?,
+            updated_at: take_some_datetime_from_mysql_row(row, FileVariantColumn::UpdatedAt.to_string().as_str())?,
+        })
+    }
+}
+
+impl MysqlColumnEnum for FileVariantColumn {}
+impl MysqlIdColumn for FileVariantColumn {
+    fn get_mysql_id_column() -> Self {
+        Self::Id
+    }
+}
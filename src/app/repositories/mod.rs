@@ -1,20 +1,30 @@
+mod api_token;
+mod audit_log;
 mod disk;
+mod disk_s3;
 mod file;
+mod file_variant;
 mod kv;
 mod mysql;
 mod redis;
 mod role;
 mod user;
 mod user_file;
+mod user_two_factor;
 
+pub use self::api_token::*;
+pub use self::audit_log::*;
 pub use self::disk::*;
+pub use self::disk_s3::*;
 pub use self::file::*;
+pub use self::file_variant::*;
 pub use self::kv::*;
 pub use self::mysql::*;
 pub use self::redis::*;
 pub use self::role::*;
 pub use self::user::*;
 pub use self::user_file::*;
+pub use self::user_two_factor::*;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -44,6 +44,13 @@ impl RoleMysqlRepository {
         self.delete(&filters)
     }
 
+    /// The children of the deleted roles become the root ones.
+    pub fn clear_parent_ids(&self, parent_ids: &Vec<u64>) -> Result<(), AppError> {
+        let filters = vec![RoleFilter::ParentIds(parent_ids.to_owned())];
+        let columns = Some(vec![RoleColumn::ParentId]);
+        self.update(&filters, &Role::default(), &columns)
+    }
+
     pub fn first_by_code(&self, code: &str) -> Result<Option<Role>, AppError> {
         let filters: Vec<RoleFilter> = vec![RoleFilter::Code(code.to_string())];
         self.first(&filters)
@@ -68,6 +75,7 @@ pub enum RoleFilter {
     Ids(Vec<u64>),
     Code(String),
     Search(String),
+    ParentIds(Vec<u64>),
 }
 
 impl MysqlQueryBuilder for RoleFilter {
@@ -80,6 +88,10 @@ impl MysqlQueryBuilder for RoleFilter {
             },
             Self::Code(_) => query.push_str("code=:f_code"),
             Self::Search(_) => query.push_str("(name LIKE :f_search OR code LIKE :f_search)"),
+            Self::ParentIds(value) => {
+                let v = format!("parent_id in ({})", join_vec(value, ","));
+                query.push_str(&v)
+            },
         }
     }
 
@@ -98,6 +110,7 @@ impl MysqlQueryBuilder for RoleFilter {
                 s.push_str("%");
                 params.push(("f_search".to_string(), Value::from(s)));
             }
+            Self::ParentIds(_) => {}
         }
     }
 }
@@ -146,6 +159,18 @@ impl ToMysqlDto<RoleColumn> for Role {
                     option_to_json_string_for_mysql(&self.permissions);
                 params.push((column.to_string(), Value::from(permissions)))
             }
+            RoleColumn::RequiresTwoFactor => params.push((
+                column.to_string(),
+                Value::from(self.requires_two_factor.to_owned()),
+            )),
+            RoleColumn::UploadMaxSize => params.push((
+                column.to_string(),
+                Value::from(self.upload_max_size.to_owned()),
+            )),
+            RoleColumn::ParentId => params.push((
+                column.to_string(),
+                Value::from(self.parent_id.to_owned()),
+            )),
         }
     }
     fn get_id(&self) -> u64 {
@@ -164,6 +189,16 @@ impl FromMysqlDto for Role {
                 row,
                 RoleColumn::Permissions.to_string().as_str(),
             ),
+            requires_two_factor: take_from_mysql_row(
+                row,
+                RoleColumn::RequiresTwoFactor.to_string().as_str(),
+            )?,
+            upload_max_size: take_from_mysql_row(
+                row,
+                RoleColumn::UploadMaxSize.to_string().as_str(),
+            )?,
+            parent_id: take_from_mysql_row(row, RoleColumn::ParentId.to_string().as_str())?,
+            effective_permissions: None,
         })
     }
 }
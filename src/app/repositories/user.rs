@@ -1,4 +1,4 @@
-use crate::{make_select_mysql_query, make_update_mysql_query, option_take_json_from_mysql_row, option_to_json_string_for_mysql, take_from_mysql_row, AppError, FromMysqlDto, MysqlAllColumnEnum, MysqlColumnEnum, MysqlIdColumn, MysqlPool, MysqlQueryBuilder, MysqlRepository, PaginateParams, Role, RoleFilter, ToMysqlDto, User, UserColumn, UserCredentials, UserCredentialsColumn, UserServiceError};
+use crate::{make_select_mysql_query, make_update_mysql_query, option_take_json_from_mysql_row, option_to_json_string_for_mysql, take_from_mysql_row, take_some_datetime_from_mysql_row, AppError, FromMysqlDto, MysqlAllColumnEnum, MysqlColumnEnum, MysqlIdColumn, MysqlPool, MysqlQueryBuilder, MysqlRepository, PaginateParams, Role, RoleFilter, ToMysqlDto, User, UserColumn, UserCredentials, UserCredentialsColumn, UserServiceError};
 use actix_web::web::Data;
 use mysql::prelude::Queryable;
 use mysql::Value;
@@ -77,6 +77,42 @@ impl UserMysqlRepository {
         self.try_row_to_credentials(&mut row)
     }
 
+    pub fn first_credentials_by_id(&self, id: u64) -> Result<Option<UserCredentials>, AppError> {
+        let table = self.get_table();
+        let columns = UserCredentialsColumn::mysql_all_select_columns();
+        let query = make_select_mysql_query(table, &columns, "id=:id", "");
+        let mut conn = self.connection()?;
+        let mut row: Option<Row> = conn
+            .exec_first(query, params! {"id" => id})
+            .map_err(|e| self.log_error("first_credentials_by_id", e.to_string()))?;
+
+        self.try_row_to_credentials(&mut row)
+    }
+
+    /// Sets the new password with the history of the previous ones and marks the time of the change.
+    pub fn change_password_by_id(
+        &self,
+        id: u64,
+        password: &str,
+        password_history: &Option<Vec<String>>,
+    ) -> Result<(), AppError> {
+        let table = self.get_table();
+        let mut conn = self.connection()?;
+        let password_history: Option<String> = option_to_json_string_for_mysql(password_history);
+        let query = make_update_mysql_query(
+            table,
+            "password=:password, password_history=:password_history, password_changed_at=UTC_TIMESTAMP()",
+            "id=:id",
+        );
+        conn.exec_drop(
+            query,
+            params! { "id" => id, "password" => password, "password_history" => password_history },
+        )
+        .map_err(|e| self.log_error("change_password_by_id", e.to_string()))?;
+
+        Ok(())
+    }
+
     pub fn update_password_by_id(&self, id: u64, password: &str) -> Result<(), AppError> {
         let table = self.get_table();
         let mut conn = self.connection()?;
@@ -117,6 +153,8 @@ pub enum UserFilter {
     Email(String),
     Search(String),
     Locale(String),
+    IsLdap,
+    IsInvited,
 }
 
 impl MysqlQueryBuilder for UserFilter {
@@ -130,6 +168,8 @@ impl MysqlQueryBuilder for UserFilter {
             Self::Email(_) => query.push_str("email=:f_email"),
             Self::Search(_) => query.push_str("(email LIKE :f_search OR surname LIKE :f_search OR name LIKE :f_search OR patronymic LIKE :f_search)"),
             Self::Locale(_) => query.push_str("locale=:f_locale"),
+            Self::IsLdap => query.push_str("ldap_dn IS NOT NULL"),
+            Self::IsInvited => query.push_str("invited_at IS NOT NULL"),
         }
     }
 
@@ -157,6 +197,8 @@ impl MysqlQueryBuilder for UserFilter {
                     Value::from(value.to_string().into_bytes()),
                 ));
             }
+            Self::IsLdap => {}
+            Self::IsInvited => {}
         }
     }
 }
@@ -229,6 +271,22 @@ impl ToMysqlDto<UserColumn> for User {
             UserColumn::AvatarId => {
                 params.push((column.to_string(), Value::from(self.avatar_id.to_owned())))
             }
+            UserColumn::EmailVerifiedAt => {
+                params.push((column.to_string(), Value::from(self.email_verified_at.to_owned())))
+            }
+            UserColumn::LdapDn => {
+                params.push((column.to_string(), Value::from(self.ldap_dn.to_owned())))
+            }
+            UserColumn::DisabledAt => {
+                params.push((column.to_string(), Value::from(self.disabled_at.to_owned())))
+            }
+            UserColumn::InvitedAt => {
+                params.push((column.to_string(), Value::from(self.invited_at.to_owned())))
+            }
+            UserColumn::PasswordChangedAt => params.push((
+                column.to_string(),
+                Value::from(self.password_changed_at.to_owned()),
+            )),
         }
     }
     fn get_id(&self) -> u64 {
@@ -251,6 +309,23 @@ impl FromMysqlDto for User {
                 UserColumn::RolesIds.to_string().as_str(),
             ),
             avatar_id: take_from_mysql_row(row, UserColumn::AvatarId.to_string().as_str())?,
+            email_verified_at: take_some_datetime_from_mysql_row(
+                row,
+                UserColumn::EmailVerifiedAt.to_string().as_str(),
+            )?,
+            ldap_dn: take_from_mysql_row(row, UserColumn::LdapDn.to_string().as_str())?,
+            disabled_at: take_some_datetime_from_mysql_row(
+                row,
+                UserColumn::DisabledAt.to_string().as_str(),
+            )?,
+            invited_at: take_some_datetime_from_mysql_row(
+                row,
+                UserColumn::InvitedAt.to_string().as_str(),
+            )?,
+            password_changed_at: take_some_datetime_from_mysql_row(
+                row,
+                UserColumn::PasswordChangedAt.to_string().as_str(),
+            )?,
         })
     }
 }
@@ -271,6 +346,14 @@ impl ToMysqlDto<UserCredentialsColumn> for UserCredentials {
             UserCredentialsColumn::Password => {
                 params.push((column.to_string(), Value::from(self.password.to_owned())));
             }
+            UserCredentialsColumn::DisabledAt => {
+                params.push((column.to_string(), Value::from(self.disabled_at.to_owned())));
+            }
+            UserCredentialsColumn::PasswordHistory => {
+                let password_history: Option<String> =
+                    option_to_json_string_for_mysql(&self.password_history);
+                params.push((column.to_string(), Value::from(password_history)));
+            }
         }
     }
     fn get_id(&self) -> u64 {
@@ -288,6 +371,14 @@ impl FromMysqlDto for UserCredentials {
                 UserCredentialsColumn::Password.to_string().as_str(),
             )
             .unwrap_or(None),
+            disabled_at: take_some_datetime_from_mysql_row(
+                row,
+                UserCredentialsColumn::DisabledAt.to_string().as_str(),
+            )?,
+            password_history: option_take_json_from_mysql_row(
+                row,
+                UserCredentialsColumn::PasswordHistory.to_string().as_str(),
+            ),
         })
     }
 }
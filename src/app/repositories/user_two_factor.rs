@@ -0,0 +1,152 @@
+use crate::{
+    option_take_json_from_mysql_row, option_to_json_string_for_mysql, take_from_mysql_row,
+    take_some_datetime_from_mysql_row, AppError, FromMysqlDto, MysqlColumnEnum, MysqlIdColumn,
+    MysqlPool, MysqlQueryBuilder, MysqlRepository, PaginateParams, ToMysqlDto, UserTwoFactor,
+    UserTwoFactorColumn,
+};
+use actix_web::web::Data;
+use mysql::Row;
+use mysql::Value;
+use strum_macros::{Display, EnumIter, EnumString};
+
+pub struct UserTwoFactorMysqlRepository {
+    db_pool: Data<MysqlPool>,
+}
+
+impl
+    MysqlRepository<
+        UserTwoFactor,
+        UserTwoFactorPaginateParams,
+        UserTwoFactorColumn,
+        UserTwoFactorFilter,
+        UserTwoFactorSort,
+    > for UserTwoFactorMysqlRepository
+{
+    fn get_repository_name(&self) -> &str {
+        "UserTwoFactorMysqlRepository"
+    }
+    fn get_table(&self) -> &str {
+        "user_two_factors"
+    }
+    fn get_db_pool(&self) -> &MysqlPool {
+        self.db_pool.get_ref()
+    }
+}
+
+impl UserTwoFactorMysqlRepository {
+    pub fn new(db_pool: Data<MysqlPool>) -> Self {
+        Self { db_pool }
+    }
+
+    pub fn first_by_user_id(&self, user_id: u64) -> Result<Option<UserTwoFactor>, AppError> {
+        let filters = vec![UserTwoFactorFilter::UserId(user_id)];
+        self.first(&filters)
+    }
+
+    pub fn delete_by_user_id(&self, user_id: u64) -> Result<(), AppError> {
+        let filters = vec![UserTwoFactorFilter::UserId(user_id)];
+        self.delete(&filters)
+    }
+
+    pub fn update_by_id(
+        &self,
+        id: u64,
+        data: &UserTwoFactor,
+        columns: &Option<Vec<UserTwoFactorColumn>>,
+    ) -> Result<(), AppError> {
+        let filters = vec![UserTwoFactorFilter::Id(id)];
+        self.update(&filters, data, columns)
+    }
+}
+
+pub type UserTwoFactorPaginateParams = PaginateParams<UserTwoFactorFilter, UserTwoFactorSort>;
+
+#[derive(Debug, Clone)]
+pub enum UserTwoFactorFilter {
+    Id(u64),
+    UserId(u64),
+}
+
+impl MysqlQueryBuilder for UserTwoFactorFilter {
+    fn push_params_to_mysql_query(&self, query: &mut String) {
+        match self {
+            Self::Id(_) => query.push_str("id=:f_id"),
+            Self::UserId(_) => query.push_str("user_id=:f_user_id"),
+        }
+    }
+
+    fn push_params_to_vec(&self, params: &mut Vec<(String, Value)>) {
+        match self {
+            Self::Id(value) => {
+                params.push(("f_id".to_string(), Value::from(value)));
+            }
+            Self::UserId(value) => {
+                params.push(("f_user_id".to_string(), Value::from(value)));
+            }
+        }
+    }
+}
+
+#[derive(Debug, Display, EnumString, EnumIter)]
+#[strum(serialize_all = "snake_case")]
+pub enum UserTwoFactorSort {
+    IdAsc,
+    IdDesc,
+}
+
+impl MysqlQueryBuilder for UserTwoFactorSort {
+    fn push_params_to_mysql_query(&self, query: &mut String) {
+        match self {
+            Self::IdAsc => query.push_str("id ASC"),
+            Self::IdDesc => query.push_str("id DESC"),
+        };
+    }
+
+    fn push_params_to_vec(&self, _: &mut Vec<(String, Value)>) {}
+}
+
+impl ToMysqlDto<UserTwoFactorColumn> for UserTwoFactor {
+    fn push_mysql_param_to_vec(
+        &self,
+        column: &UserTwoFactorColumn,
+        params: &mut Vec<(String, Value)>,
+    ) {
+        match column {
+            UserTwoFactorColumn::Id => params.push((column.to_string(), Value::from(self.id.to_owned()))),
+            UserTwoFactorColumn::UserId => params.push((column.to_string(), Value::from(self.user_id.to_owned()))),
+            UserTwoFactorColumn::Secret => params.push((column.to_string(), Value::from(self.secret.to_owned()))),
+            UserTwoFactorColumn::RecoveryCodes => {
+                let recovery_codes: Option<String> =
+                    option_to_json_string_for_mysql(&self.recovery_codes);
+                params.push((column.to_string(), Value::from(recovery_codes)))
+            }
+            UserTwoFactorColumn::LastUsedStep => params.push((column.to_string(), Value::from(self.last_used_step.to_owned()))),
+            UserTwoFactorColumn::ConfirmedAt => params.push((column.to_string(), Value::from(self.confirmed_at.to_owned()))),
+            UserTwoFactorColumn::CreatedAt => params.push((column.to_string(), Value::from(self.created_at.to_owned()))),
+        }
+    }
+    fn get_id(&self) -> u64 {
+        self.id
+    }
+}
+
+impl FromMysqlDto for UserTwoFactor {
+    fn take_from_mysql_row(row: &mut Row) -> Result<Self, AppError> {
+        Ok(Self {
+            id: take_from_mysql_row(row, UserTwoFactorColumn::Id.to_string().as_str())?,
+            user_id: take_from_mysql_row(row, UserTwoFactorColumn::UserId.to_string().as_str())?,
+            secret: take_from_mysql_row(row, UserTwoFactorColumn::Secret.to_string().as_str())?,
+            recovery_codes: option_take_json_from_mysql_row(row, UserTwoFactorColumn::RecoveryCodes.to_string().as_str()),
+            last_used_step: take_from_mysql_row(row, UserTwoFactorColumn::LastUsedStep.to_string().as_str())?,
+            confirmed_at: take_some_datetime_from_mysql_row(row, UserTwoFactorColumn::ConfirmedAt.to_string().as_str())?,
+            created_at: take_some_datetime_from_mysql_row(row, UserTwoFactorColumn::CreatedAt.to_string().as_str())?,
+        })
+    }
+}
+
+impl MysqlColumnEnum for UserTwoFactorColumn {}
+impl MysqlIdColumn for UserTwoFactorColumn {
+    fn get_mysql_id_column() -> Self {
+        Self::Id
+    }
+}
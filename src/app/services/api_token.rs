@@ -0,0 +1,280 @@
+use crate::helpers::{now_date_time_str, DATE_TIME_FORMAT};
+use crate::{
+    ApiToken, ApiTokenMysqlRepository, AppError, HashService, MysqlRepository, Permission,
+    RandomService, Role, TranslatableError, TranslatorService, User,
+};
+use actix_web::web::Data;
+use actix_web::{error, Error};
+use chrono::{Duration, Utc};
+use std::str::FromStr;
+use strum::VariantNames;
+use strum_macros::{Display, EnumString};
+
+pub const API_TOKEN_PREFIX: &str = "pat_";
+pub const API_TOKEN_LENGTH: usize = 48;
+
+pub struct ApiTokenService {
+    hash_service: Data<HashService>,
+    random_service: Data<RandomService>,
+    api_token_repository: Data<ApiTokenMysqlRepository>,
+}
+
+impl ApiTokenService {
+    pub fn new(
+        hash_service: Data<HashService>,
+        random_service: Data<RandomService>,
+        api_token_repository: Data<ApiTokenMysqlRepository>,
+    ) -> Self {
+        Self {
+            hash_service,
+            random_service,
+            api_token_repository,
+        }
+    }
+
+    pub fn get_service_name(&self) -> &str {
+        "ApiTokenService"
+    }
+
+    pub fn log_error(&self, method: &str, error: String, e: ApiTokenServiceError) -> ApiTokenServiceError {
+        let service_name = self.get_service_name().to_string();
+        log::error!("{}::{} - {}", service_name, method, error);
+        e
+    }
+
+    fn match_error(&self, method: &str, e: AppError) -> ApiTokenServiceError {
+        self.log_error(method, e.to_string(), ApiTokenServiceError::Fail)
+    }
+
+    pub fn is_api_token(&self, token: &str) -> bool {
+        token.starts_with(API_TOKEN_PREFIX)
+    }
+
+    pub fn hash_token(&self, token: &str) -> String {
+        self.hash_service.get_ref().hash(token)
+    }
+
+    pub fn generate_token(&self) -> String {
+        let mut token = API_TOKEN_PREFIX.to_string();
+        token.push_str(&self.random_service.get_ref().str(API_TOKEN_LENGTH));
+        token
+    }
+
+    pub fn make_expires_at(&self, days: Option<i64>) -> Option<String> {
+        days.map(|days| {
+            (Utc::now() + Duration::days(days))
+                .format(DATE_TIME_FORMAT)
+                .to_string()
+        })
+    }
+
+    pub fn is_expired(&self, api_token: &ApiToken) -> bool {
+        if let Some(expires_at) = &api_token.expires_at {
+            return expires_at.as_str() <= now_date_time_str().as_str();
+        }
+        false
+    }
+
+    pub fn all_by_user_id(&self, user_id: u64) -> Result<Vec<ApiToken>, ApiTokenServiceError> {
+        self.api_token_repository
+            .get_ref()
+            .all_by_user_id(user_id)
+            .map_err(|e| self.match_error("all_by_user_id", e))
+    }
+
+    pub fn all_by_user_id_throw_http(&self, user_id: u64) -> Result<Vec<ApiToken>, Error> {
+        self.all_by_user_id(user_id)
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+
+    pub fn first_by_id_and_user_id(
+        &self,
+        id: u64,
+        user_id: u64,
+    ) -> Result<Option<ApiToken>, ApiTokenServiceError> {
+        self.api_token_repository
+            .get_ref()
+            .first_by_id_and_user_id(id, user_id)
+            .map_err(|e| self.match_error("first_by_id_and_user_id", e))
+    }
+
+    pub fn first_by_id_and_user_id_throw_http(&self, id: u64, user_id: u64) -> Result<ApiToken, Error> {
+        let entity = self
+            .first_by_id_and_user_id(id, user_id)
+            .map_err(|_| error::ErrorInternalServerError(""))?;
+        if let Some(entity) = entity {
+            return Ok(entity);
+        }
+        Err(error::ErrorNotFound(""))
+    }
+
+    /// Creates a token and returns it together with the plain text value.
+    /// The plain text value is not stored and can only be shown once.
+    pub fn create(
+        &self,
+        user_id: u64,
+        name: &str,
+        scopes: Vec<String>,
+        expires_at: Option<String>,
+    ) -> Result<(ApiToken, String), ApiTokenServiceError> {
+        let token = self.generate_token();
+        let token_hash = self.hash_token(&token);
+
+        let mut data = ApiToken::default();
+        data.user_id = user_id;
+        data.name = name.to_string();
+        data.token_hash = token_hash.to_owned();
+        data.scopes = Some(scopes);
+        data.expires_at = expires_at;
+        data.created_at = Some(now_date_time_str());
+
+        let repository = self.api_token_repository.get_ref();
+        repository
+            .insert(&vec![data], None)
+            .map_err(|e| self.match_error("create", e))?;
+
+        let api_token = repository
+            .first_by_token_hash(&token_hash)
+            .map_err(|e| self.match_error("create", e))?
+            .ok_or(ApiTokenServiceError::NotFound)?;
+
+        Ok((api_token, token))
+    }
+
+    pub fn delete_by_id(&self, id: u64) -> Result<(), ApiTokenServiceError> {
+        self.api_token_repository
+            .get_ref()
+            .delete_by_id(id)
+            .map_err(|e| self.match_error("delete_by_id", e))
+    }
+
+    pub fn delete_by_id_throw_http(&self, id: u64) -> Result<(), Error> {
+        self.delete_by_id(id)
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+
+    /// Finds a valid token by its plain text value and marks it as used.
+    pub fn login_by_token(&self, token: &str) -> Result<ApiToken, ApiTokenServiceError> {
+        if !self.is_api_token(token) {
+            return Err(ApiTokenServiceError::NotFound);
+        }
+        let repository = self.api_token_repository.get_ref();
+        let mut api_token = repository
+            .first_by_token_hash(&self.hash_token(token))
+            .map_err(|e| self.match_error("login_by_token", e))?
+            .ok_or(ApiTokenServiceError::NotFound)?;
+
+        if self.is_expired(&api_token) {
+            return Err(ApiTokenServiceError::Expired);
+        }
+
+        let last_used_at = now_date_time_str();
+        repository
+            .update_last_used_at_by_id(api_token.id, &last_used_at)
+            .map_err(|e| self.match_error("login_by_token", e))?;
+        api_token.last_used_at = Some(last_used_at);
+
+        Ok(api_token)
+    }
+
+    /// Returns the scopes the user is allowed to grant to a token.
+    pub fn available_scopes(user: &User, roles: &Vec<Role>) -> Vec<String> {
+        let user_roles_ids: Vec<u64> = user.roles_ids.to_owned().unwrap_or_default();
+
+        Permission::VARIANTS
+            .iter()
+            .map(|variant| variant.to_string())
+            .filter(|scope| {
+                user.is_super_admin
+                    || Permission::from_str(scope).is_ok_and(|permission| {
+                        roles.iter().any(|role| {
+                            user_roles_ids.contains(&role.id) && role.has_permission(&permission)
+                        })
+                    })
+            })
+            .collect()
+    }
+
+    /// Narrows the user permissions down to the token scopes.
+    /// The result is passed to policies instead of the user and all roles.
+    pub fn apply_scopes(api_token: &ApiToken, user: &User, roles: &Vec<Role>) -> (User, Vec<Role>) {
+        let available_scopes = Self::available_scopes(user, roles);
+
+        let permissions: Vec<String> = api_token
+            .scopes
+            .to_owned()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|scope| available_scopes.contains(scope))
+            .collect();
+
+        let mut scoped_role = Role::default();
+        scoped_role.code = "api_token".to_string();
+        scoped_role.name = api_token.name.to_owned();
+        scoped_role.permissions = Some(permissions);
+
+        let mut scoped_user = user.to_owned();
+        scoped_user.is_super_admin = false;
+        scoped_user.roles_ids = Some(vec![scoped_role.id]);
+
+        (scoped_user, vec![scoped_role])
+    }
+}
+
+#[derive(Debug, Clone, Copy, Display, EnumString, PartialEq, Eq)]
+pub enum ApiTokenServiceError {
+    DbConnectionFail,
+    NotFound,
+    Expired,
+    Fail,
+}
+
+impl TranslatableError for ApiTokenServiceError {
+    fn translate(&self, lang: &str, translator_service: &TranslatorService) -> String {
+        match self {
+            Self::DbConnectionFail => {
+                translator_service.translate(lang, "error.ApiTokenServiceError.DbConnectionFail")
+            }
+            Self::NotFound => translator_service.translate(lang, "error.ApiTokenServiceError.NotFound"),
+            Self::Expired => translator_service.translate(lang, "error.ApiTokenServiceError.Expired"),
+            _ => translator_service.translate(lang, "error.ApiTokenServiceError.Fail"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_scopes() {
+        let mut role = Role::default();
+        role.id = 1;
+        role.permissions = Some(vec!["users_show".to_string(), "roles_show".to_string()]);
+        let roles = vec![role];
+
+        let mut user = User::default();
+        user.roles_ids = Some(vec![1]);
+
+        let mut api_token = ApiToken::default();
+        api_token.scopes = Some(vec!["users_show".to_string(), "files_show".to_string()]);
+
+        let (scoped_user, scoped_roles) = ApiTokenService::apply_scopes(&api_token, &user, &roles);
+        assert!(!scoped_user.is_super_admin);
+        assert_eq!(scoped_roles.len(), 1);
+        assert_eq!(scoped_roles[0].permissions, Some(vec!["users_show".to_string()]));
+
+        assert_eq!(
+            ApiTokenService::available_scopes(&user, &roles),
+            vec!["users_show".to_string(), "roles_show".to_string()]
+        );
+
+        user.is_super_admin = true;
+        let (scoped_user, scoped_roles) = ApiTokenService::apply_scopes(&api_token, &user, &roles);
+        assert!(!scoped_user.is_super_admin);
+        assert_eq!(
+            scoped_roles[0].permissions,
+            Some(vec!["users_show".to_string(), "files_show".to_string()])
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use crate::helpers::now_date_time_str;
+use crate::{
+    AppError, AuditLog, AuditLogAction, AuditLogEntity, AuditLogMysqlRepository,
+    AuditLogPaginateParams, MysqlRepository, PaginationResult, TranslatableError,
+    TranslatorService, User,
+};
+use actix_web::http::header::USER_AGENT;
+use actix_web::web::Data;
+use actix_web::{error, Error, HttpRequest};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use strum_macros::{Display, EnumString};
+
+pub const AUDIT_LOG_USER_AGENT_MAX_LENGTH: usize = 512;
+pub const AUDIT_LOG_HIDDEN_VALUE: &str = "********";
+
+pub struct AuditLogService {
+    audit_log_repository: Data<AuditLogMysqlRepository>,
+}
+
+impl AuditLogService {
+    pub fn new(audit_log_repository: Data<AuditLogMysqlRepository>) -> Self {
+        Self {
+            audit_log_repository,
+        }
+    }
+
+    pub fn get_service_name(&self) -> &str {
+        "AuditLogService"
+    }
+
+    pub fn log_error(&self, method: &str, error: String, e: AuditLogServiceError) -> AuditLogServiceError {
+        let service_name = self.get_service_name().to_string();
+        log::error!("{}::{} - {}", service_name, method, error);
+        e
+    }
+
+    fn match_error(&self, method: &str, e: AppError) -> AuditLogServiceError {
+        self.log_error(method, e.to_string(), AuditLogServiceError::Fail)
+    }
+
+    /// Serializes an entity for the "old_values" and "new_values" columns.
+    pub fn to_value<T: Serialize>(entity: &T) -> Option<Value> {
+        serde_json::to_value(entity).ok()
+    }
+
+    /// Leaves only the fields that differ between the two states.
+    /// Creations and deletions keep the whole state of the entity.
+    pub fn diff(old_values: Option<Value>, new_values: Option<Value>) -> (Option<Value>, Option<Value>) {
+        match (old_values, new_values) {
+            (Some(Value::Object(old_values)), Some(Value::Object(new_values))) => {
+                let mut old_diff: Map<String, Value> = Map::new();
+                let mut new_diff: Map<String, Value> = Map::new();
+
+                for (key, new_value) in &new_values {
+                    let old_value = old_values.get(key).unwrap_or(&Value::Null);
+                    if old_value.ne(new_value) {
+                        old_diff.insert(key.to_owned(), old_value.to_owned());
+                        new_diff.insert(key.to_owned(), new_value.to_owned());
+                    }
+                }
+                for (key, old_value) in &old_values {
+                    if !new_values.contains_key(key) {
+                        old_diff.insert(key.to_owned(), old_value.to_owned());
+                        new_diff.insert(key.to_owned(), Value::Null);
+                    }
+                }
+
+                (Some(Value::Object(old_diff)), Some(Value::Object(new_diff)))
+            }
+            (old_values, new_values) => (old_values, new_values),
+        }
+    }
+
+    fn is_empty_diff(old_values: &Option<Value>, new_values: &Option<Value>) -> bool {
+        let is_empty = |v: &Option<Value>| match v {
+            Some(Value::Object(map)) => map.is_empty(),
+            Some(_) => false,
+            None => true,
+        };
+        is_empty(old_values) && is_empty(new_values)
+    }
+
+    /// Returns None for an update without changed fields, it is not recorded.
+    fn make_data(
+        action: AuditLogAction,
+        entity_type: AuditLogEntity,
+        entity_id: u64,
+        old_values: Option<Value>,
+        new_values: Option<Value>,
+    ) -> Option<AuditLog> {
+        let (old_values, new_values) = Self::diff(old_values, new_values);
+
+        if action.eq(&AuditLogAction::Update) && Self::is_empty_diff(&old_values, &new_values) {
+            return None;
+        }
+
+        Some(AuditLog {
+            action: action.to_string(),
+            entity_type: entity_type.to_string(),
+            entity_id,
+            old_values,
+            new_values,
+            created_at: Some(now_date_time_str()),
+            ..Default::default()
+        })
+    }
+
+    pub fn record(
+        &self,
+        req: &HttpRequest,
+        actor: &User,
+        action: AuditLogAction,
+        entity_type: AuditLogEntity,
+        entity_id: u64,
+        old_values: Option<Value>,
+        new_values: Option<Value>,
+    ) -> Result<(), AuditLogServiceError> {
+        let data = Self::make_data(action, entity_type, entity_id, old_values, new_values);
+        if data.is_none() {
+            return Ok(());
+        }
+        let mut data = data.unwrap();
+
+        let user_agent: Option<String> = req
+            .headers()
+            .get(USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.chars().take(AUDIT_LOG_USER_AGENT_MAX_LENGTH).collect());
+
+        data.actor_id = Some(actor.id);
+        data.ip = req.peer_addr().map(|addr| addr.ip().to_string());
+        data.user_agent = user_agent;
+
+        self.audit_log_repository
+            .get_ref()
+            .insert(&vec![data], None)
+            .map_err(|e| self.match_error("record", e))
+    }
+
+    /// The changes made from the console have neither a user nor a request,
+    /// the name of the actor is written in place of the user agent.
+    pub fn record_console(
+        &self,
+        actor: &str,
+        action: AuditLogAction,
+        entity_type: AuditLogEntity,
+        entity_id: u64,
+        old_values: Option<Value>,
+        new_values: Option<Value>,
+    ) -> Result<(), AuditLogServiceError> {
+        let data = Self::make_data(action, entity_type, entity_id, old_values, new_values);
+        if data.is_none() {
+            return Ok(());
+        }
+        let mut data = data.unwrap();
+        data.user_agent = Some(actor.to_string());
+
+        self.audit_log_repository
+            .get_ref()
+            .insert(&vec![data], None)
+            .map_err(|e| self.match_error("record_console", e))
+    }
+
+    pub fn record_throw_http(
+        &self,
+        req: &HttpRequest,
+        actor: &User,
+        action: AuditLogAction,
+        entity_type: AuditLogEntity,
+        entity_id: u64,
+        old_values: Option<Value>,
+        new_values: Option<Value>,
+    ) -> Result<(), Error> {
+        self.record(req, actor, action, entity_type, entity_id, old_values, new_values)
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+
+    pub fn paginate(
+        &self,
+        params: &AuditLogPaginateParams,
+    ) -> Result<PaginationResult<AuditLog>, AuditLogServiceError> {
+        self.audit_log_repository
+            .get_ref()
+            .paginate(params)
+            .map_err(|e| self.match_error("paginate", e))
+    }
+
+    pub fn paginate_throw_http(
+        &self,
+        params: &AuditLogPaginateParams,
+    ) -> Result<PaginationResult<AuditLog>, Error> {
+        self.paginate(params)
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Display, EnumString, PartialEq, Eq)]
+pub enum AuditLogServiceError {
+    DbConnectionFail,
+    Fail,
+}
+
+impl TranslatableError for AuditLogServiceError {
+    fn translate(&self, lang: &str, translator_service: &TranslatorService) -> String {
+        match self {
+            Self::DbConnectionFail => {
+                translator_service.translate(lang, "error.AuditLogServiceError.DbConnectionFail")
+            }
+            _ => translator_service.translate(lang, "error.AuditLogServiceError.Fail"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff() {
+        let old_values = Some(json!({"id": 1, "email": "a@a.a", "name": "A"}));
+        let new_values = Some(json!({"id": 1, "email": "b@b.b", "name": "A"}));
+        let (old_diff, new_diff) = AuditLogService::diff(old_values, new_values);
+        assert_eq!(old_diff, Some(json!({"email": "a@a.a"})));
+        assert_eq!(new_diff, Some(json!({"email": "b@b.b"})));
+
+        let old_values = Some(json!({"id": 1}));
+        let (old_diff, new_diff) = AuditLogService::diff(old_values.to_owned(), old_values);
+        assert!(AuditLogService::is_empty_diff(&old_diff, &new_diff));
+
+        let new_values = Some(json!({"id": 1}));
+        let (old_diff, new_diff) = AuditLogService::diff(None, new_values.to_owned());
+        assert_eq!(old_diff, None);
+        assert_eq!(new_diff, new_values);
+    }
+
+    #[test]
+    fn test_make_data() {
+        let data = AuditLogService::make_data(
+            AuditLogAction::Update,
+            AuditLogEntity::User,
+            1,
+            Some(json!({"sessions": 2})),
+            Some(json!({"sessions": 0})),
+        )
+        .unwrap();
+        assert_eq!(data.action, "update");
+        assert_eq!(data.entity_type, "user");
+        assert_eq!(data.entity_id, 1);
+        assert_eq!(data.old_values, Some(json!({"sessions": 2})));
+        assert_eq!(data.new_values, Some(json!({"sessions": 0})));
+        assert_eq!(data.actor_id, None);
+
+        let data = AuditLogService::make_data(
+            AuditLogAction::Update,
+            AuditLogEntity::User,
+            1,
+            Some(json!({"sessions": 0})),
+            Some(json!({"sessions": 0})),
+        );
+        assert!(data.is_none());
+
+        let data = AuditLogService::make_data(
+            AuditLogAction::Delete,
+            AuditLogEntity::RateLimit,
+            0,
+            Some(json!({"pattern": "*.*", "cleared": 0})),
+            None,
+        );
+        assert!(data.is_some());
+    }
+}
@@ -1,8 +1,8 @@
 use crate::app::validator::rules::email::Email;
 use crate::app::validator::rules::str_min_max_length::StrMinMaxLength;
 use crate::{
-    HashService, KeyValueService, AppError, TranslatableError, TranslatorService, User,
-    UserService, UserServiceError,
+    HashService, KeyValueService, AppError, LdapService, LdapServiceError, TranslatableError,
+    TranslatorService, User, UserService, UserServiceError,
 };
 use actix_web::web::Data;
 use serde_derive::{Deserialize, Serialize};
@@ -15,6 +15,7 @@ pub struct AuthService {
     key_value_service: Data<KeyValueService>,
     hash_service: Data<HashService>,
     user_service: Data<UserService>,
+    ldap_service: Data<LdapService>,
 }
 
 impl AuthService {
@@ -22,16 +23,32 @@ impl AuthService {
         key_value_service: Data<KeyValueService>,
         hash_service: Data<HashService>,
         user_service: Data<UserService>,
+        ldap_service: Data<LdapService>,
     ) -> Self {
         Self {
             key_value_service,
             hash_service,
             user_service,
+            ldap_service,
         }
     }
 
     /// Search for a user by the provided credentials and return his id.
+    /// The directory is asked first, the local password is checked for the logins unknown to it.
     pub fn login_by_password(&self, email: &str, password: &str) -> Result<u64, AuthServiceError> {
+        let ldap_service = self.ldap_service.get_ref();
+        if ldap_service.is_enabled() {
+            match ldap_service.login_by_password(email, password) {
+                Ok(Some(user_id)) => return Ok(user_id),
+                Ok(None) => {}
+                Err(LdapServiceError::InvalidCredentials) | Err(LdapServiceError::UserNotFound) => {
+                    return Err(AuthServiceError::Fail);
+                }
+                // The local administrators can log in while the directory is not available.
+                Err(_) => {}
+            }
+        }
+
         let hash_service = self.hash_service.get_ref();
         let user_service = self.user_service.get_ref();
         let user = user_service
@@ -46,7 +63,7 @@ impl AuthService {
         }
 
         let user = user.unwrap();
-        if user.password.is_none() {
+        if user.password.is_none() || user.disabled_at.is_some() {
             return Err(AuthServiceError::Fail);
         }
 
@@ -59,6 +76,12 @@ impl AuthService {
             })?;
 
         if is_verified {
+            // The hash is upgraded while the plain password is known, the failure does not prevent the login.
+            if hash_service.needs_rehash(&user_password_hash) {
+                if let Err(e) = user_service.rehash_password_by_id(user.id, password) {
+                    log::error!("AuthService::login_by_password - {e}");
+                }
+            }
             Ok(user.id)
         } else {
             Err(AuthServiceError::Fail)
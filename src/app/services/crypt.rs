@@ -3,10 +3,25 @@ use crate::{Config, HashService, RandomService};
 use actix_web::web::Data;
 use base64_stream::FromBase64Reader;
 use base64_stream::ToBase64Reader;
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::sign::Signer;
 use serde_derive::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::io::{Cursor, Read};
 use strum_macros::{Display, EnumString};
-
+use subtle::ConstantTimeEq;
+
+// The current format of the encrypted values:
+// [version: 1 byte][key id: 4 bytes][nonce: 12 bytes][ciphertext][tag: 16 bytes]
+// The version and the key id are authenticated as the additional data.
+pub const CRYPT_VERSION: u8 = 1;
+const CRYPT_KEY_ID_LEN: usize = 4;
+const CRYPT_NONCE_LEN: usize = 12;
+const CRYPT_TAG_LEN: usize = 16;
+const CRYPT_HEADER_LEN: usize = 1 + CRYPT_KEY_ID_LEN;
+
+// The legacy AES-256-CBC format, it is only decrypted.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct EncryptedData {
     pub iv: String,
@@ -14,12 +29,34 @@ pub struct EncryptedData {
     pub mac: String,
 }
 
+#[derive(Clone)]
+pub struct CryptKey {
+    pub id: [u8; CRYPT_KEY_ID_LEN],
+    key_string: String,
+    key: [u8; 32],
+}
+
+impl CryptKey {
+    pub fn new(key_string: &str) -> Self {
+        let key: [u8; 32] = CryptService::parse_key(key_string);
+        let mut hasher = Sha256::new();
+        hasher.update(key);
+        let hash = hasher.finalize();
+        let mut id = [0; CRYPT_KEY_ID_LEN];
+        id.copy_from_slice(&hash[..CRYPT_KEY_ID_LEN]);
+        Self {
+            id,
+            key_string: key_string.to_owned(),
+            key,
+        }
+    }
+}
+
 pub struct CryptService {
     random_service: Data<RandomService>,
     hash_service: Data<HashService>,
-    cipher: openssl::symm::Cipher,
-    cipher_key_string: String,
-    cipher_key: [u8; 32],
+    // The first key is the current APP_KEY, the others are APP_PREVIOUS_KEYS.
+    keys: Vec<CryptKey>,
 }
 
 impl CryptService {
@@ -28,17 +65,18 @@ impl CryptService {
         random_service: Data<RandomService>,
         hash_service: Data<HashService>,
     ) -> Self {
-        if config.get_ref().app.key.len() == 0 {
+        let config = config.get_ref();
+        if config.app.key.len() == 0 {
             panic!("APP_KEY is missing!");
         }
-        let cipher_key_string: String = config.get_ref().app.key.to_owned();
-        let cipher_key: [u8; 32] = Self::parse_key(&cipher_key_string);
+        let mut keys: Vec<CryptKey> = vec![CryptKey::new(&config.app.key)];
+        for key in &config.app.previous_keys {
+            keys.push(CryptKey::new(key));
+        }
         Self {
             random_service,
             hash_service,
-            cipher: openssl::symm::Cipher::aes_256_cbc(),
-            cipher_key_string,
-            cipher_key,
+            keys,
         }
     }
 
@@ -61,111 +99,222 @@ impl CryptService {
         vec_into_array(key)
     }
 
-    fn hash(&self, iv: &str, value: &str, key: &str) -> Result<String, CryptServiceError> {
+    fn current_key(&self) -> &CryptKey {
+        &self.keys[0]
+    }
+
+    fn legacy_hash(&self, iv: &str, value: &str, key: &str) -> Result<String, CryptServiceError> {
         let mut s = iv.to_owned();
         s.push_str(value);
         s.push_str(key);
         let hash_service = self.hash_service.get_ref();
         let hash = hash_service.hash_vec(s);
         hash_service.to_base64(hash).map_err(|e| {
-            log::error!("CryptService::hash - {e}");
+            log::error!("CryptService::legacy_hash - {e}");
             CryptServiceError::Fail
         })
     }
 
     pub fn encrypt_string(&self, string: &str) -> Result<String, CryptServiceError> {
-        let hash_service = self.hash_service.get_ref();
-        let iv: [u8; 128] = self.random_service.get_ref().bytes_128();
-        let value: Vec<u8> =
-            openssl::symm::encrypt(self.cipher, &self.cipher_key, Some(&iv), string.as_bytes())
-                .map_err(|e| {
-                    log::error!("CryptService::encrypt_string - {e}");
-                    CryptServiceError::Fail
-                })?;
+        let nonce: [u8; CRYPT_NONCE_LEN] = self.random_service.get_ref().bytes_12();
+        let data = Self::encrypt_aead(self.current_key(), &nonce, string.as_bytes())?;
 
-        let iv_base64: String = hash_service.to_base64(iv).map_err(|e| {
-            log::error!("CryptService::encrypt_string - {e}");
-            CryptServiceError::Fail
-        })?;
-        let value_base64: String = hash_service.to_base64(value).map_err(|e| {
+        self.hash_service.get_ref().to_base64(data).map_err(|e| {
             log::error!("CryptService::encrypt_string - {e}");
             CryptServiceError::Fail
-        })?;
-        let mac: String = self
-            .hash(&iv_base64, &value_base64, &self.cipher_key_string)
+        })
+    }
+
+    pub fn decrypt_string(&self, data_base64: &str) -> Result<String, CryptServiceError> {
+        let data: Vec<u8> = self
+            .hash_service
+            .get_ref()
+            .base64_to_end(data_base64)
             .map_err(|e| {
-                log::error!("CryptService::encrypt_string - {e}");
+                log::error!("CryptService::decrypt_string - {e}");
                 CryptServiceError::Fail
             })?;
 
-        let data = EncryptedData {
-            iv: iv_base64,
-            value: value_base64,
-            mac,
+        let decrypted = if data.first() == Some(&CRYPT_VERSION) {
+            Self::decrypt_aead(&self.keys, &data)?
+        } else {
+            self.decrypt_legacy(&data)?
         };
 
-        let data_string: String = serde_json::to_string(&data).map_err(|e| {
-            log::error!("CryptService::encrypt_string - {e}");
-            CryptServiceError::Fail
-        })?;
-        let data_base64: String = hash_service.to_base64(data_string).map_err(|e| {
-            log::error!("CryptService::encrypt_string - {e}");
+        String::from_utf8(decrypted).map_err(|e| {
+            log::error!("CryptService::decrypt_string - {e}");
             CryptServiceError::Fail
-        })?;
+        })
+    }
 
-        Ok(data_base64)
+    /// Whether the value is encrypted in the legacy format or by one of the previous keys.
+    pub fn needs_reencrypt(&self, data_base64: &str) -> bool {
+        let data = match self.hash_service.get_ref().base64_to_end(data_base64) {
+            Ok(data) => data,
+            Err(_) => return true,
+        };
+        if data.len() < CRYPT_HEADER_LEN || data[0] != CRYPT_VERSION {
+            return true;
+        }
+        data[1..CRYPT_HEADER_LEN] != self.current_key().id
     }
 
-    pub fn decrypt_string(&self, data_base64: &str) -> Result<String, CryptServiceError> {
-        let hash_service = self.hash_service.get_ref();
-        let data_string: String = hash_service.base64_to_string(data_base64).map_err(|e| {
-            log::error!("CryptService::decrypt_string - {e}");
+    /// Decrypts the value by any known key and encrypts it by the current key.
+    pub fn reencrypt_string(&self, data_base64: &str) -> Result<String, CryptServiceError> {
+        let string = self.decrypt_string(data_base64)?;
+        self.encrypt_string(&string)
+    }
+
+    /// HMAC-SHA256 of the data by the current key in hex, it is used to sign the links.
+    pub fn sign(&self, data: &str) -> Result<String, CryptServiceError> {
+        Self::hmac(&self.current_key().key_string, data)
+    }
+
+    /// Whether the signature was made by any known key, so the links signed before
+    /// the APP_KEY rotation keep working.
+    pub fn verify_signature(&self, data: &str, signature: &str) -> Result<bool, CryptServiceError> {
+        Self::verify_hmac(&self.keys, data, signature)
+    }
+
+    fn hmac(key: &str, data: &str) -> Result<String, CryptServiceError> {
+        let key = PKey::hmac(key.as_bytes()).map_err(|e| {
+            log::error!("CryptService::hmac - {e}");
             CryptServiceError::Fail
         })?;
-        let data: EncryptedData = serde_json::from_str(&data_string).map_err(|e| {
-            log::error!("CryptService::decrypt_string - {e}");
+        let mut signer = Signer::new(MessageDigest::sha256(), &key).map_err(|e| {
+            log::error!("CryptService::hmac - {e}");
+            CryptServiceError::Fail
+        })?;
+        signer.update(data.as_bytes()).map_err(|e| {
+            log::error!("CryptService::hmac - {e}");
+            CryptServiceError::Fail
+        })?;
+        let hmac = signer.sign_to_vec().map_err(|e| {
+            log::error!("CryptService::hmac - {e}");
             CryptServiceError::Fail
         })?;
-        let mac: String = self
-            .hash(&data.iv, &data.value, &self.cipher_key_string)
+        Ok(hex::encode(hmac))
+    }
+
+    fn verify_hmac(
+        keys: &[CryptKey],
+        data: &str,
+        signature: &str,
+    ) -> Result<bool, CryptServiceError> {
+        for key in keys {
+            let expected = Self::hmac(&key.key_string, data)?;
+            if bool::from(expected.as_bytes().ct_eq(signature.as_bytes())) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn encrypt_aead(
+        key: &CryptKey,
+        nonce: &[u8; CRYPT_NONCE_LEN],
+        value: &[u8],
+    ) -> Result<Vec<u8>, CryptServiceError> {
+        let mut data: Vec<u8> = Vec::with_capacity(
+            CRYPT_HEADER_LEN + CRYPT_NONCE_LEN + value.len() + CRYPT_TAG_LEN,
+        );
+        data.push(CRYPT_VERSION);
+        data.extend_from_slice(&key.id);
+
+        let mut tag = [0; CRYPT_TAG_LEN];
+        let encrypted = openssl::symm::encrypt_aead(
+            openssl::symm::Cipher::aes_256_gcm(),
+            &key.key,
+            Some(nonce),
+            &data,
+            value,
+            &mut tag,
+        )
             .map_err(|e| {
-                log::error!("CryptService::decrypt_string - {e}");
+                log::error!("CryptService::encrypt_aead - {e}");
                 CryptServiceError::Fail
             })?;
 
-        if mac.ne(&data.mac) {
+        data.extend_from_slice(nonce);
+        data.extend_from_slice(&encrypted);
+        data.extend_from_slice(&tag);
+        Ok(data)
+    }
+
+    fn decrypt_aead(keys: &[CryptKey], data: &[u8]) -> Result<Vec<u8>, CryptServiceError> {
+        if data.len() < CRYPT_HEADER_LEN + CRYPT_NONCE_LEN + CRYPT_TAG_LEN {
             return Err(CryptServiceError::Fail);
         }
+        let header = &data[..CRYPT_HEADER_LEN];
+        let key = keys
+            .iter()
+            .find(|key| key.id == header[1..])
+            .ok_or(CryptServiceError::KeyNotFound)?;
+        let nonce = &data[CRYPT_HEADER_LEN..CRYPT_HEADER_LEN + CRYPT_NONCE_LEN];
+        let tag = &data[data.len() - CRYPT_TAG_LEN..];
+        let value = &data[CRYPT_HEADER_LEN + CRYPT_NONCE_LEN..data.len() - CRYPT_TAG_LEN];
+
+        openssl::symm::decrypt_aead(
+            openssl::symm::Cipher::aes_256_gcm(),
+            &key.key,
+            Some(nonce),
+            header,
+            value,
+            tag,
+        )
+            .map_err(|e| {
+                log::error!("CryptService::decrypt_aead - {e}");
+                CryptServiceError::Fail
+            })
+    }
 
+    // The legacy values carry no key id, so every known key is tried.
+    fn decrypt_legacy(&self, data: &[u8]) -> Result<Vec<u8>, CryptServiceError> {
+        let hash_service = self.hash_service.get_ref();
+        let data: EncryptedData = serde_json::from_slice(data).map_err(|e| {
+            log::error!("CryptService::decrypt_legacy - {e}");
+            CryptServiceError::Fail
+        })?;
         let iv: Vec<u8> = hash_service.base64_to_end(&data.iv).map_err(|e| {
-            log::error!("CryptService::decrypt_string - {e}");
+            log::error!("CryptService::decrypt_legacy - {e}");
             CryptServiceError::Fail
         })?;
         let value: Vec<u8> = hash_service.base64_to_end(&data.value).map_err(|e| {
-            log::error!("CryptService::decrypt_string - {e}");
+            log::error!("CryptService::decrypt_legacy - {e}");
             CryptServiceError::Fail
         })?;
 
-        let decrypted = openssl::symm::decrypt(self.cipher, &self.cipher_key, Some(&iv), &value)
-            .map_err(|e| {
-                log::error!("CryptService::decrypt_string - {e}");
-                CryptServiceError::Fail
-            })?;
+        for key in &self.keys {
+            let mac: String = self.legacy_hash(&data.iv, &data.value, &key.key_string)?;
+            if !bool::from(mac.as_bytes().ct_eq(data.mac.as_bytes())) {
+                continue;
+            }
+
+            return openssl::symm::decrypt(
+                openssl::symm::Cipher::aes_256_cbc(),
+                &key.key,
+                Some(&iv),
+                &value,
+            )
+                .map_err(|e| {
+                    log::error!("CryptService::decrypt_legacy - {e}");
+                    CryptServiceError::Fail
+                });
+        }
 
-        String::from_utf8(decrypted).map_err(|e| {
-            log::error!("CryptService::decrypt_string - {e}");
-            CryptServiceError::Fail
-        })
+        Err(CryptServiceError::KeyNotFound)
     }
 }
 
 #[derive(Debug, Clone, Copy, Display, EnumString)]
 pub enum CryptServiceError {
+    KeyNotFound,
     Fail,
 }
 
 #[cfg(test)]
 mod tests {
+    use super::{CryptKey, CryptServiceError, CRYPT_VERSION};
     use crate::{preparation, CryptService};
     use base64_stream::FromBase64Reader;
     use base64_stream::ToBase64Reader;
@@ -193,6 +342,58 @@ mod tests {
         assert!(CryptService::random_key().len() > 0);
     }
 
+    #[test]
+    fn encrypt_aead_with_key_rotation() {
+        let old_key = CryptKey::new(&CryptService::random_key());
+        let new_key = CryptKey::new(&CryptService::random_key());
+        let nonce = [7; 12];
+
+        let encrypted = CryptService::encrypt_aead(&old_key, &nonce, DATA.as_bytes()).unwrap();
+        assert_eq!(encrypted[0], CRYPT_VERSION);
+        assert_eq!(&encrypted[1..5], &old_key.id);
+
+        let keys = vec![new_key.clone(), old_key.clone()];
+        let decrypted = CryptService::decrypt_aead(&keys, &encrypted).unwrap();
+        assert_eq!(DATA.as_bytes(), decrypted.as_slice());
+
+        let keys = vec![new_key.clone()];
+        assert!(matches!(
+            CryptService::decrypt_aead(&keys, &encrypted),
+            Err(CryptServiceError::KeyNotFound)
+        ));
+
+        let mut tampered = encrypted.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        let keys = vec![old_key.clone()];
+        assert!(CryptService::decrypt_aead(&keys, &tampered).is_err());
+
+        // The key id is authenticated too.
+        let mut tampered = encrypted.clone();
+        tampered[1..5].copy_from_slice(&new_key.id);
+        let keys = vec![new_key, old_key];
+        assert!(CryptService::decrypt_aead(&keys, &tampered).is_err());
+    }
+
+    #[test]
+    fn verify_hmac_with_key_rotation() {
+        let old_key = CryptKey::new(&CryptService::random_key());
+        let new_key = CryptKey::new(&CryptService::random_key());
+
+        let signature = CryptService::hmac(&old_key.key_string, DATA).unwrap();
+        assert_eq!(signature.len(), 64);
+        assert_eq!(signature, CryptService::hmac(&old_key.key_string, DATA).unwrap());
+        assert_ne!(signature, CryptService::hmac(&new_key.key_string, DATA).unwrap());
+
+        let keys = vec![new_key.clone(), old_key.clone()];
+        assert!(CryptService::verify_hmac(&keys, DATA, &signature).unwrap());
+        assert!(!CryptService::verify_hmac(&keys, "data", &signature).unwrap());
+        assert!(!CryptService::verify_hmac(&keys, DATA, "").unwrap());
+
+        let keys = vec![new_key];
+        assert!(!CryptService::verify_hmac(&keys, DATA, &signature).unwrap());
+    }
+
     #[test]
     fn encrypt_string() {
         let (_, all_services) = preparation();
@@ -0,0 +1,241 @@
+use crate::helpers::now_date_time_str;
+use crate::{
+    AppService, Config, CryptService, EmailAddress, EmailMessage, JobPayload, JobService, TemplateService,
+    TranslatableError, TranslatorService, User, UserService,
+};
+use actix_web::web::Data;
+use actix_web::{error, Error};
+use chrono::Utc;
+use serde_json::json;
+use strum_macros::{Display, EnumString};
+
+pub const EMAIL_VERIFICATION_URL: &'static str = "/verify-email";
+
+pub struct EmailVerificationService {
+    config: Data<Config>,
+    user_service: Data<UserService>,
+    job_service: Data<JobService>,
+    app_service: Data<AppService>,
+    template_service: Data<TemplateService>,
+    translator_service: Data<TranslatorService>,
+    crypt_service: Data<CryptService>,
+}
+
+impl EmailVerificationService {
+    pub fn new(
+        config: Data<Config>,
+        user_service: Data<UserService>,
+        job_service: Data<JobService>,
+        app_service: Data<AppService>,
+        template_service: Data<TemplateService>,
+        translator_service: Data<TranslatorService>,
+        crypt_service: Data<CryptService>,
+    ) -> Self {
+        Self {
+            config,
+            user_service,
+            job_service,
+            app_service,
+            template_service,
+            translator_service,
+            crypt_service,
+        }
+    }
+
+    pub fn get_service_name(&self) -> &str {
+        "EmailVerificationService"
+    }
+
+    pub fn log_error(
+        &self,
+        method: &str,
+        error: String,
+        e: EmailVerificationServiceError,
+    ) -> EmailVerificationServiceError {
+        let service_name = self.get_service_name().to_string();
+        log::error!("{}::{} - {}", service_name, method, error);
+        e
+    }
+
+    /// Whether the unverified users are blocked by the WebAuthMiddleware.
+    pub fn is_required(&self) -> bool {
+        self.config.get_ref().auth.email_verification
+    }
+
+    pub fn is_blocked(&self, user: &User) -> bool {
+        self.is_required() && !user.is_email_verified()
+    }
+
+    /// The link is valid until the expiration and only for the current email of the user.
+    pub fn make_url(&self, user: &User) -> Result<String, EmailVerificationServiceError> {
+        let config = self.config.get_ref();
+        let expires = Utc::now().timestamp() + config.auth.email_verification_expires;
+        let signature = self
+            .crypt_service
+            .get_ref()
+            .sign(&email_verification_data(user.id, &user.email, expires))
+            .map_err(|e| self.log_error("make_url", e.to_string(), EmailVerificationServiceError::Fail))?;
+
+        let params = format!(
+            "{}?id={}&expires={}&signature={}",
+            EMAIL_VERIFICATION_URL, user.id, expires, signature
+        );
+        self.app_service
+            .get_ref()
+            .url()
+            .join(&params)
+            .map(|url| url.to_string())
+            .map_err(|e| self.log_error("make_url", e.to_string(), EmailVerificationServiceError::Fail))
+    }
+
+    /// Sends the letter with the verification link through the job queue.
+    pub fn send(&self, user: &User, lang: &str) -> Result<(), EmailVerificationServiceError> {
+        if user.is_email_verified() {
+            return Err(EmailVerificationServiceError::AlreadyVerified);
+        }
+        let app_service = self.app_service.get_ref();
+        let translator_service = self.translator_service.get_ref();
+
+        let site_domain = app_service
+            .url()
+            .domain()
+            .unwrap_or("localhost")
+            .to_string();
+        let logo_src = app_service
+            .url()
+            .join("/svg/logo.svg")
+            .map_err(|e| self.log_error("send", e.to_string(), EmailVerificationServiceError::Fail))?
+            .to_string();
+        let button_href = self.make_url(user)?;
+
+        let ctx = json!({
+            "title": translator_service.translate(lang, "mail.verify_email.title"),
+            "description": translator_service.translate(lang, "mail.verify_email.description"),
+            "lang": lang.to_owned(),
+            "site_name": translator_service.translate(lang, "mail.verify_email.site_name"),
+            "site_url": app_service.url().to_string(),
+            "site_domain": site_domain,
+            "logo_src": logo_src,
+            "header": translator_service.translate(lang, "mail.verify_email.header"),
+            "button_label": translator_service.translate(lang, "mail.verify_email.button"),
+            "button_href": button_href.to_owned(),
+        });
+        let html_body = self
+            .template_service
+            .get_ref()
+            .render("emails/auth/verify_email.hbs", &ctx)
+            .map_err(|e| self.log_error("send", e.to_string(), EmailVerificationServiceError::Fail))?;
+
+        let message = EmailMessage {
+            from: None,
+            reply_to: None,
+            to: EmailAddress {
+                name: None,
+                email: user.email.to_owned(),
+            },
+            subject: translator_service.translate(lang, "mail.verify_email.subject"),
+            html_body: Some(html_body),
+            text_body: button_href,
+        };
+        self.job_service
+            .get_ref()
+            .dispatch(JobPayload::SendEmail(message))
+            .map_err(|e| self.log_error("send", e.to_string(), EmailVerificationServiceError::Fail))?;
+        Ok(())
+    }
+
+    pub fn send_throw_http(&self, user: &User, lang: &str) -> Result<(), Error> {
+        self.send(user, lang).map_err(|e| e.to_http())
+    }
+
+    /// Checks the link and marks the email as verified.
+    pub fn verify(
+        &self,
+        user_id: u64,
+        expires: i64,
+        signature: &str,
+    ) -> Result<User, EmailVerificationServiceError> {
+        if expires < Utc::now().timestamp() {
+            return Err(EmailVerificationServiceError::Expired);
+        }
+
+        let user_service = self.user_service.get_ref();
+        let mut user = user_service
+            .first_by_id(user_id)
+            .map_err(|e| self.log_error("verify", e.to_string(), EmailVerificationServiceError::Fail))?
+            .ok_or(EmailVerificationServiceError::InvalidSignature)?;
+
+        let is_valid = self
+            .crypt_service
+            .get_ref()
+            .verify_signature(&email_verification_data(user.id, &user.email, expires), signature)
+            .map_err(|e| self.log_error("verify", e.to_string(), EmailVerificationServiceError::Fail))?;
+        if !is_valid {
+            return Err(EmailVerificationServiceError::InvalidSignature);
+        }
+
+        if user.is_email_verified() {
+            return Ok(user);
+        }
+
+        user_service
+            .mark_email_verified_by_id(user.id)
+            .map_err(|e| self.log_error("verify", e.to_string(), EmailVerificationServiceError::Fail))?;
+        user.email_verified_at = Some(now_date_time_str());
+        Ok(user)
+    }
+}
+
+/// The signed data of the link: the user id, the email and the expiration.
+pub fn email_verification_data(user_id: u64, email: &str, expires: i64) -> String {
+    format!("{}|{}|{}", user_id, email, expires)
+}
+
+#[derive(Debug, Clone, Copy, Display, EnumString, PartialEq, Eq)]
+pub enum EmailVerificationServiceError {
+    AlreadyVerified,
+    Expired,
+    InvalidSignature,
+    Fail,
+}
+
+impl EmailVerificationServiceError {
+    pub fn to_http(&self) -> Error {
+        match self {
+            Self::AlreadyVerified => error::ErrorConflict(""),
+            Self::Expired | Self::InvalidSignature => error::ErrorForbidden(""),
+            _ => error::ErrorInternalServerError(""),
+        }
+    }
+}
+
+impl TranslatableError for EmailVerificationServiceError {
+    fn translate(&self, lang: &str, translator_service: &TranslatorService) -> String {
+        match self {
+            Self::AlreadyVerified => translator_service
+                .translate(lang, "error.EmailVerificationServiceError.AlreadyVerified"),
+            Self::Expired => {
+                translator_service.translate(lang, "error.EmailVerificationServiceError.Expired")
+            }
+            Self::InvalidSignature => translator_service
+                .translate(lang, "error.EmailVerificationServiceError.InvalidSignature"),
+            _ => translator_service.translate(lang, "error.EmailVerificationServiceError.Fail"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email_verification_data() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::email_verification::tests::test_email_verification_data
+        // The format is kept, so the links sent before stay valid.
+        assert_eq!(email_verification_data(1, "user@example.com", 100), "1|user@example.com|100");
+        assert_ne!(
+            email_verification_data(1, "user@example.com", 100),
+            email_verification_data(1, "user@example.com", 101)
+        );
+    }
+}
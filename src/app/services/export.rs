@@ -0,0 +1,936 @@
+use crate::{
+    AppService, DiskLocalRepository, DiskRepository, EmailAddress, EmailMessage, ExportEntity,
+    ExportFormat, ExportParams, ExportValue, File, FileFilter, FilePaginateParams, FileService,
+    FileSort, JobPayload, JobService, RandomService, RoleFilter, RolePaginateParams, RoleService,
+    RoleSort, TemplateService, TranslatableError, TranslatorService, User, UserFile, UserFilter,
+    UserFileService, UserPaginateParams, UserService, UserSort,
+};
+use actix_web::web::Data;
+use actix_web::{error, Error};
+use chrono::Utc;
+use serde_json::json;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufWriter, Seek, Write};
+use std::path::{Path, MAIN_SEPARATOR_STR};
+use std::str::FromStr;
+use strum_macros::{Display, EnumString};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+// The larger exports are written by the worker and sent to the user as a link to the file.
+pub const EXPORT_SYNC_MAX_ROWS: i64 = 5000;
+pub const EXPORT_CHUNK_SIZE: i64 = 500;
+pub const EXPORT_DIRECTORY: &'static str = ".exports";
+pub const EXPORT_DOWNLOAD_URL: &'static str = "/exports";
+
+const USERS_COLUMNS: [&'static str; 9] = [
+    "id",
+    "email",
+    "surname",
+    "name",
+    "patronymic",
+    "locale",
+    "roles",
+    "email_verified_at",
+    "disabled_at",
+];
+const ROLES_COLUMNS: [&'static str; 7] = [
+    "id",
+    "code",
+    "name",
+    "description",
+    "permissions",
+    "requires_two_factor",
+    "upload_max_size",
+];
+const FILES_COLUMNS: [&'static str; 9] = [
+    "id",
+    "filename",
+    "mime",
+    "size",
+    "hash",
+    "disk",
+    "is_deleted",
+    "created_at",
+    "updated_at",
+];
+
+pub struct ExportService {
+    translator_service: Data<TranslatorService>,
+    template_service: Data<TemplateService>,
+    app_service: Data<AppService>,
+    job_service: Data<JobService>,
+    user_service: Data<UserService>,
+    role_service: Data<RoleService>,
+    file_service: Data<FileService>,
+    user_file_service: Data<UserFileService>,
+    disk_local_repository: Data<DiskLocalRepository>,
+    random_service: Data<RandomService>,
+}
+
+impl ExportService {
+    pub fn new(
+        translator_service: Data<TranslatorService>,
+        template_service: Data<TemplateService>,
+        app_service: Data<AppService>,
+        job_service: Data<JobService>,
+        user_service: Data<UserService>,
+        role_service: Data<RoleService>,
+        file_service: Data<FileService>,
+        user_file_service: Data<UserFileService>,
+        disk_local_repository: Data<DiskLocalRepository>,
+        random_service: Data<RandomService>,
+    ) -> Self {
+        Self {
+            translator_service,
+            template_service,
+            app_service,
+            job_service,
+            user_service,
+            role_service,
+            file_service,
+            user_file_service,
+            disk_local_repository,
+            random_service,
+        }
+    }
+
+    pub fn get_service_name(&self) -> &str {
+        "ExportService"
+    }
+
+    pub fn log_error(&self, method: &str, error: String, e: ExportServiceError) -> ExportServiceError {
+        let service_name = self.get_service_name().to_string();
+        log::error!("{}::{} - {}", service_name, method, error);
+        e
+    }
+
+    /// The count of the records matching the filters, it decides whether the export runs in the background.
+    pub fn count(&self, params: &ExportParams) -> Result<i64, ExportServiceError> {
+        let total_records = match params.entity {
+            ExportEntity::Users => self
+                .user_service
+                .get_ref()
+                .paginate(&UserPaginateParams::new(1, 1, user_filters(params), Vec::new()))
+                .map_err(|e| self.log_error("count", e.to_string(), ExportServiceError::Fail))?
+                .total_records,
+            ExportEntity::Roles => self
+                .role_service
+                .get_ref()
+                .paginate(&RolePaginateParams::new(1, 1, role_filters(params), Vec::new()))
+                .map_err(|e| self.log_error("count", e.to_string(), ExportServiceError::Fail))?
+                .total_records,
+            ExportEntity::Files => self
+                .file_service
+                .get_ref()
+                .paginate(&FilePaginateParams::new(1, 1, file_filters(params), Vec::new()))
+                .map_err(|e| self.log_error("count", e.to_string(), ExportServiceError::Fail))?
+                .total_records,
+        };
+        Ok(total_records)
+    }
+
+    pub fn count_throw_http(&self, params: &ExportParams) -> Result<i64, Error> {
+        self.count(params).map_err(|e| e.to_http())
+    }
+
+    pub fn is_background(&self, count: i64) -> bool {
+        count > EXPORT_SYNC_MAX_ROWS
+    }
+
+    /// The name of the downloaded file, for example: users-2025-01-01-100000.csv
+    pub fn make_filename(&self, params: &ExportParams) -> String {
+        format!(
+            "{}-{}.{}",
+            params.entity,
+            Utc::now().format("%Y-%m-%d-%H%M%S"),
+            params.format.extension()
+        )
+    }
+
+    /// Writes the records to a temporary file of the local disk page by page, the caller deletes the file.
+    pub fn write_to_temp_file(&self, params: &ExportParams) -> Result<String, ExportServiceError> {
+        let mut path = EXPORT_DIRECTORY.to_string();
+        path.push_str(MAIN_SEPARATOR_STR);
+        path.push_str(self.random_service.get_ref().str(32).as_str());
+        path.push('.');
+        path.push_str(params.format.extension());
+        let path = self
+            .disk_local_repository
+            .get_ref()
+            .path(&path)
+            .map_err(|e| self.log_error("write_to_temp_file", e.to_string(), ExportServiceError::Fail))?;
+
+        if let Err(e) = self.write_to_path(params, &path) {
+            self.delete_temp_file(&path);
+            return Err(self.log_error("write_to_temp_file", e.to_string(), ExportServiceError::Fail));
+        }
+        Ok(path)
+    }
+
+    pub fn write_to_temp_file_throw_http(&self, params: &ExportParams) -> Result<String, Error> {
+        self.write_to_temp_file(params).map_err(|e| e.to_http())
+    }
+
+    pub fn delete_temp_file(&self, path: &str) {
+        if let Err(e) = self.disk_local_repository.get_ref().delete(path) {
+            self.log_error("delete_temp_file", e.to_string(), ExportServiceError::Fail);
+        }
+    }
+
+    fn write_to_path(&self, params: &ExportParams, path: &str) -> Result<(), String> {
+        if let Some(directory) = Path::new(path).parent() {
+            fs::create_dir_all(directory).map_err(|e| e.to_string())?;
+        }
+        let file = fs::File::create(path).map_err(|e| e.to_string())?;
+        let columns = self.columns(params);
+        let mut writer =
+            ExportWriter::new(params.format, BufWriter::new(file), columns).map_err(|e| e.to_string())?;
+
+        match params.entity {
+            ExportEntity::Users => self.write_users(params, &mut writer)?,
+            ExportEntity::Roles => self.write_roles(params, &mut writer)?,
+            ExportEntity::Files => self.write_files(params, &mut writer)?,
+        }
+
+        writer
+            .finish()
+            .and_then(|mut inner| inner.flush())
+            .map_err(|e| e.to_string())
+    }
+
+    fn columns(&self, params: &ExportParams) -> Vec<(String, String)> {
+        let translator_service = self.translator_service.get_ref();
+        let (codes, key): (&[&str], &str) = match params.entity {
+            ExportEntity::Users => (&USERS_COLUMNS, "page.users.index.columns."),
+            ExportEntity::Roles => (&ROLES_COLUMNS, "page.roles.index.columns."),
+            ExportEntity::Files => (&FILES_COLUMNS, "page.files.index.columns."),
+        };
+        codes
+            .iter()
+            .map(|code| {
+                let label = translator_service.translate(&params.lang, &format!("{}{}", key, code));
+                (code.to_string(), label)
+            })
+            .collect()
+    }
+
+    fn write_users<W: Write + Seek>(
+        &self,
+        params: &ExportParams,
+        writer: &mut ExportWriter<W>,
+    ) -> Result<(), String> {
+        let roles_codes: HashMap<u64, String> = self
+            .role_service
+            .get_ref()
+            .all()
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .map(|role| (role.id, role.code))
+            .collect();
+
+        let mut page = 1;
+        loop {
+            let pagination_params =
+                UserPaginateParams::new(page, EXPORT_CHUNK_SIZE, user_filters(params), user_sorts(params));
+            let users = self
+                .user_service
+                .get_ref()
+                .paginate(&pagination_params)
+                .map_err(|e| e.to_string())?;
+            for user in users.records {
+                let roles: Vec<String> = user
+                    .roles_ids
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|id| roles_codes.get(id).cloned())
+                    .collect();
+                writer
+                    .write_row(&[
+                        ExportValue::Number(user.id),
+                        ExportValue::Text(user.email),
+                        user.surname.into(),
+                        user.name.into(),
+                        user.patronymic.into(),
+                        user.locale.into(),
+                        ExportValue::Text(roles.join(", ")),
+                        user.email_verified_at.into(),
+                        user.disabled_at.into(),
+                    ])
+                    .map_err(|e| e.to_string())?;
+            }
+            if page >= users.total_pages {
+                return Ok(());
+            }
+            page += 1;
+        }
+    }
+
+    fn write_roles<W: Write + Seek>(
+        &self,
+        params: &ExportParams,
+        writer: &mut ExportWriter<W>,
+    ) -> Result<(), String> {
+        let mut page = 1;
+        loop {
+            let pagination_params =
+                RolePaginateParams::new(page, EXPORT_CHUNK_SIZE, role_filters(params), role_sorts(params));
+            let roles = self
+                .role_service
+                .get_ref()
+                .paginate(&pagination_params)
+                .map_err(|e| e.to_string())?;
+            for role in roles.records {
+                writer
+                    .write_row(&[
+                        ExportValue::Number(role.id),
+                        ExportValue::Text(role.code),
+                        ExportValue::Text(role.name),
+                        role.description.into(),
+                        ExportValue::Text(role.permissions.unwrap_or_default().join(", ")),
+                        ExportValue::Bool(role.requires_two_factor),
+                        role.upload_max_size.into(),
+                    ])
+                    .map_err(|e| e.to_string())?;
+            }
+            if page >= roles.total_pages {
+                return Ok(());
+            }
+            page += 1;
+        }
+    }
+
+    fn write_files<W: Write + Seek>(
+        &self,
+        params: &ExportParams,
+        writer: &mut ExportWriter<W>,
+    ) -> Result<(), String> {
+        let mut page = 1;
+        loop {
+            let pagination_params =
+                FilePaginateParams::new(page, EXPORT_CHUNK_SIZE, file_filters(params), file_sorts(params));
+            let files = self
+                .file_service
+                .get_ref()
+                .paginate(&pagination_params)
+                .map_err(|e| e.to_string())?;
+            for file in files.records {
+                writer
+                    .write_row(&[
+                        ExportValue::Number(file.id),
+                        ExportValue::Text(file.filename),
+                        file.mime.into(),
+                        file.size.into(),
+                        file.hash.into(),
+                        ExportValue::Text(file.disk),
+                        ExportValue::Bool(file.is_deleted),
+                        file.created_at.into(),
+                        file.updated_at.into(),
+                    ])
+                    .map_err(|e| e.to_string())?;
+            }
+            if page >= files.total_pages {
+                return Ok(());
+            }
+            page += 1;
+        }
+    }
+
+    pub fn dispatch(&self, user: &User, params: ExportParams) -> Result<(), ExportServiceError> {
+        self.job_service
+            .get_ref()
+            .dispatch(JobPayload::Export {
+                user_id: user.id,
+                params,
+            })
+            .map(|_| ())
+            .map_err(|e| self.log_error("dispatch", e.to_string(), ExportServiceError::Fail))
+    }
+
+    pub fn dispatch_throw_http(&self, user: &User, params: ExportParams) -> Result<(), Error> {
+        self.dispatch(user, params).map_err(|e| e.to_http())
+    }
+
+    /// Runs in the worker: the export is saved as a private file of the user and the link is sent by email.
+    pub fn export_to_user_file(
+        &self,
+        user_id: u64,
+        params: &ExportParams,
+    ) -> Result<UserFile, ExportServiceError> {
+        let user = self
+            .user_service
+            .get_ref()
+            .first_by_id(user_id)
+            .map_err(|e| self.log_error("export_to_user_file", e.to_string(), ExportServiceError::Fail))?
+            .ok_or(ExportServiceError::NotFound)?;
+
+        let path = self.write_to_temp_file(params)?;
+        let mime = mime::Mime::from_str(params.format.mime()).ok();
+        let result = self.file_service.get_ref().upload_local_file_to_disk(
+            user.id,
+            &path,
+            false,
+            Some(self.make_filename(params)),
+            mime,
+        );
+        self.delete_temp_file(&path);
+        let user_file = result.map_err(|e| {
+            self.log_error("export_to_user_file", e.to_string(), ExportServiceError::Fail)
+        })?;
+
+        self.send(&user, &user_file, &params.lang)?;
+        Ok(user_file)
+    }
+
+    /// The export file of the user, the other users get NotFound.
+    pub fn first_download(
+        &self,
+        user: &User,
+        user_file_id: u64,
+    ) -> Result<(UserFile, File), ExportServiceError> {
+        let user_file = self
+            .user_file_service
+            .get_ref()
+            .first_by_id(user_file_id)
+            .map_err(|e| self.log_error("first_download", e.to_string(), ExportServiceError::Fail))?
+            .ok_or(ExportServiceError::NotFound)?;
+        if user_file.user_id != user.id || user_file.is_deleted {
+            return Err(ExportServiceError::NotFound);
+        }
+        let file = self
+            .file_service
+            .get_ref()
+            .first_by_id(user_file.file_id)
+            .map_err(|e| self.log_error("first_download", e.to_string(), ExportServiceError::Fail))?
+            .ok_or(ExportServiceError::NotFound)?;
+        if file.is_deleted {
+            return Err(ExportServiceError::NotFound);
+        }
+        Ok((user_file, file))
+    }
+
+    pub fn first_download_throw_http(
+        &self,
+        user: &User,
+        user_file_id: u64,
+    ) -> Result<(UserFile, File), Error> {
+        self.first_download(user, user_file_id).map_err(|e| e.to_http())
+    }
+
+    fn send(&self, user: &User, user_file: &UserFile, lang: &str) -> Result<(), ExportServiceError> {
+        let app_service = self.app_service.get_ref();
+        let translator_service = self.translator_service.get_ref();
+
+        let site_domain = app_service
+            .url()
+            .domain()
+            .unwrap_or("localhost")
+            .to_string();
+        let logo_src = app_service
+            .url()
+            .join("/svg/logo.svg")
+            .map_err(|e| self.log_error("send", e.to_string(), ExportServiceError::Fail))?
+            .to_string();
+        let button_href = app_service
+            .url()
+            .join(&format!("{}/{}", EXPORT_DOWNLOAD_URL, user_file.id))
+            .map_err(|e| self.log_error("send", e.to_string(), ExportServiceError::Fail))?
+            .to_string();
+
+        let ctx = json!({
+            "title": translator_service.translate(lang, "mail.export_ready.title"),
+            "description": translator_service.translate(lang, "mail.export_ready.description"),
+            "lang": lang.to_owned(),
+            "site_name": translator_service.translate(lang, "mail.export_ready.site_name"),
+            "site_url": app_service.url().to_string(),
+            "site_domain": site_domain,
+            "logo_src": logo_src,
+            "header": translator_service.translate(lang, "mail.export_ready.header"),
+            "button_label": translator_service.translate(lang, "mail.export_ready.button"),
+            "button_href": button_href.to_owned(),
+        });
+        let html_body = self
+            .template_service
+            .get_ref()
+            .render("emails/auth/security_notice.hbs", &ctx)
+            .map_err(|e| self.log_error("send", e.to_string(), ExportServiceError::Fail))?;
+
+        let message = EmailMessage {
+            from: None,
+            reply_to: None,
+            to: EmailAddress {
+                name: None,
+                email: user.email.to_owned(),
+            },
+            subject: translator_service.translate(lang, "mail.export_ready.subject"),
+            html_body: Some(html_body),
+            text_body: button_href,
+        };
+        self.job_service
+            .get_ref()
+            .dispatch(JobPayload::SendEmail(message))
+            .map_err(|e| self.log_error("send", e.to_string(), ExportServiceError::Fail))?;
+        Ok(())
+    }
+}
+
+fn user_filters(params: &ExportParams) -> Vec<UserFilter> {
+    let mut filters: Vec<UserFilter> = Vec::new();
+    if let Some(ids) = &params.ids {
+        filters.push(UserFilter::Ids(ids.to_owned()));
+    }
+    if let Some(value) = &params.search {
+        filters.push(UserFilter::Search(value.to_string()));
+    }
+    if let Some(value) = &params.locale {
+        filters.push(UserFilter::Locale(value.to_string()));
+    }
+    filters
+}
+
+fn user_sorts(params: &ExportParams) -> Vec<UserSort> {
+    params
+        .sort
+        .as_ref()
+        .and_then(|sort| UserSort::from_str(sort).ok())
+        .into_iter()
+        .collect()
+}
+
+fn role_filters(params: &ExportParams) -> Vec<RoleFilter> {
+    let mut filters: Vec<RoleFilter> = Vec::new();
+    if let Some(ids) = &params.ids {
+        filters.push(RoleFilter::Ids(ids.to_owned()));
+    }
+    if let Some(value) = &params.search {
+        filters.push(RoleFilter::Search(value.to_string()));
+    }
+    filters
+}
+
+fn role_sorts(params: &ExportParams) -> Vec<RoleSort> {
+    params
+        .sort
+        .as_ref()
+        .and_then(|sort| RoleSort::from_str(sort).ok())
+        .into_iter()
+        .collect()
+}
+
+fn file_filters(params: &ExportParams) -> Vec<FileFilter> {
+    let mut filters: Vec<FileFilter> = Vec::new();
+    if let Some(ids) = &params.ids {
+        filters.push(FileFilter::Ids(ids.to_owned()));
+    }
+    if let Some(value) = &params.search {
+        filters.push(FileFilter::Search(value.to_string()));
+    }
+    filters
+}
+
+fn file_sorts(params: &ExportParams) -> Vec<FileSort> {
+    params
+        .sort
+        .as_ref()
+        .and_then(|sort| FileSort::from_str(sort).ok())
+        .into_iter()
+        .collect()
+}
+
+enum ExportWriterInner<W: Write + Seek> {
+    Plain(W),
+    Zip(Box<ZipWriter<W>>),
+}
+
+/// Writes the rows one by one, so only the current row is kept in memory.
+/// The XLSX is a zip of the minimal SpreadsheetML parts with the inline strings.
+pub struct ExportWriter<W: Write + Seek> {
+    format: ExportFormat,
+    columns: Vec<(String, String)>,
+    inner: ExportWriterInner<W>,
+    rows: u64,
+}
+
+impl<W: Write + Seek> ExportWriter<W> {
+    /// The columns are pairs of the code and the label, the JSON keys are the codes.
+    pub fn new(format: ExportFormat, inner: W, columns: Vec<(String, String)>) -> io::Result<Self> {
+        let inner = match format {
+            ExportFormat::Xlsx => ExportWriterInner::Zip(Box::new(ZipWriter::new(inner))),
+            _ => ExportWriterInner::Plain(inner),
+        };
+        let mut writer = Self {
+            format,
+            columns,
+            inner,
+            rows: 0,
+        };
+        writer.write_header()?;
+        Ok(writer)
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        match &mut self.inner {
+            ExportWriterInner::Plain(inner) => match self.format {
+                ExportFormat::Json => inner.write_all(b"["),
+                _ => {
+                    // The BOM lets Excel detect UTF-8.
+                    inner.write_all(b"\xEF\xBB\xBF")?;
+                    let labels: Vec<String> =
+                        self.columns.iter().map(|(_, label)| csv_escape(label)).collect();
+                    inner.write_all(labels.join(",").as_bytes())?;
+                    inner.write_all(b"\r\n")
+                }
+            },
+            ExportWriterInner::Zip(zip) => {
+                let options =
+                    SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+                for (name, content) in XLSX_PARTS {
+                    zip.start_file(*name, options).map_err(io::Error::other)?;
+                    zip.write_all(XML_DECLARATION.as_bytes())?;
+                    zip.write_all(content.as_bytes())?;
+                }
+                zip.start_file("xl/worksheets/sheet1.xml", options)
+                    .map_err(io::Error::other)?;
+                zip.write_all(XML_DECLARATION.as_bytes())?;
+                zip.write_all(
+                    b"<worksheet xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\"><sheetData>",
+                )?;
+                let mut row = String::from("<row r=\"1\">");
+                for (i, (_, label)) in self.columns.iter().enumerate() {
+                    row.push_str(&format!(
+                        "<c r=\"{}1\" s=\"1\" t=\"inlineStr\"><is><t xml:space=\"preserve\">{}</t></is></c>",
+                        xlsx_column_name(i),
+                        xml_escape(label)
+                    ));
+                }
+                row.push_str("</row>");
+                zip.write_all(row.as_bytes())
+            }
+        }
+    }
+
+    pub fn write_row(&mut self, values: &[ExportValue]) -> io::Result<()> {
+        self.rows += 1;
+        match &mut self.inner {
+            ExportWriterInner::Plain(inner) => match self.format {
+                ExportFormat::Json => {
+                    let mut row = String::from(if self.rows == 1 { "\n{" } else { ",\n{" });
+                    for (i, ((code, _), value)) in self.columns.iter().zip(values).enumerate() {
+                        if i != 0 {
+                            row.push(',');
+                        }
+                        row.push_str(&serde_json::to_string(code)?);
+                        row.push(':');
+                        row.push_str(&json_value(value)?);
+                    }
+                    row.push('}');
+                    inner.write_all(row.as_bytes())
+                }
+                _ => {
+                    let cells: Vec<String> = values.iter().map(csv_value).collect();
+                    inner.write_all(cells.join(",").as_bytes())?;
+                    inner.write_all(b"\r\n")
+                }
+            },
+            ExportWriterInner::Zip(zip) => {
+                // The first row is the header.
+                let r = self.rows + 1;
+                let mut row = format!("<row r=\"{}\">", r);
+                for (i, value) in values.iter().enumerate() {
+                    let cell = xlsx_column_name(i);
+                    match value {
+                        ExportValue::Null => {}
+                        ExportValue::Bool(value) => row.push_str(&format!(
+                            "<c r=\"{}{}\" t=\"b\"><v>{}</v></c>",
+                            cell, r, *value as u8
+                        )),
+                        ExportValue::Number(value) => {
+                            row.push_str(&format!("<c r=\"{}{}\"><v>{}</v></c>", cell, r, value))
+                        }
+                        ExportValue::Text(value) => row.push_str(&format!(
+                            "<c r=\"{}{}\" t=\"inlineStr\"><is><t xml:space=\"preserve\">{}</t></is></c>",
+                            cell,
+                            r,
+                            xml_escape(value)
+                        )),
+                    }
+                }
+                row.push_str("</row>");
+                zip.write_all(row.as_bytes())
+            }
+        }
+    }
+
+    pub fn rows(&self) -> u64 {
+        self.rows
+    }
+
+    pub fn finish(self) -> io::Result<W> {
+        match self.inner {
+            ExportWriterInner::Plain(mut inner) => {
+                if self.format == ExportFormat::Json {
+                    inner.write_all(if self.rows == 0 { b"]" } else { b"\n]" })?;
+                }
+                Ok(inner)
+            }
+            ExportWriterInner::Zip(mut zip) => {
+                zip.write_all(b"</sheetData></worksheet>")?;
+                zip.finish().map_err(io::Error::other)
+            }
+        }
+    }
+}
+
+const XML_DECLARATION: &'static str =
+    "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n";
+
+const XLSX_PARTS: &'static [(&'static str, &'static str)] = &[
+    (
+        "[Content_Types].xml",
+        "<Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\
+<Default Extension=\"rels\" ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/>\
+<Default Extension=\"xml\" ContentType=\"application/xml\"/>\
+<Override PartName=\"/xl/workbook.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml\"/>\
+<Override PartName=\"/xl/worksheets/sheet1.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml\"/>\
+<Override PartName=\"/xl/styles.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml\"/>\
+</Types>",
+    ),
+    (
+        "_rels/.rels",
+        "<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+<Relationship Id=\"rId1\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument\" Target=\"xl/workbook.xml\"/>\
+</Relationships>",
+    ),
+    (
+        "xl/workbook.xml",
+        "<workbook xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\" xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\">\
+<sheets><sheet name=\"Sheet1\" sheetId=\"1\" r:id=\"rId1\"/></sheets>\
+</workbook>",
+    ),
+    (
+        "xl/_rels/workbook.xml.rels",
+        "<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+<Relationship Id=\"rId1\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet\" Target=\"worksheets/sheet1.xml\"/>\
+<Relationship Id=\"rId2\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles\" Target=\"styles.xml\"/>\
+</Relationships>",
+    ),
+    (
+        "xl/styles.xml",
+        "<styleSheet xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\">\
+<fonts count=\"2\"><font><sz val=\"11\"/><name val=\"Calibri\"/></font><font><b/><sz val=\"11\"/><name val=\"Calibri\"/></font></fonts>\
+<fills count=\"2\"><fill><patternFill patternType=\"none\"/></fill><fill><patternFill patternType=\"gray125\"/></fill></fills>\
+<borders count=\"1\"><border><left/><right/><top/><bottom/><diagonal/></border></borders>\
+<cellStyleXfs count=\"1\"><xf numFmtId=\"0\" fontId=\"0\" fillId=\"0\" borderId=\"0\"/></cellStyleXfs>\
+<cellXfs count=\"2\"><xf numFmtId=\"0\" fontId=\"0\" fillId=\"0\" borderId=\"0\" xfId=\"0\"/><xf numFmtId=\"0\" fontId=\"1\" fillId=\"0\" borderId=\"0\" xfId=\"0\" applyFont=\"1\"/></cellXfs>\
+</styleSheet>",
+    ),
+];
+
+/// The spreadsheet column by the zero based index: A, B, ..., Z, AA, AB, ...
+pub fn xlsx_column_name(index: usize) -> String {
+    let mut index = index + 1;
+    let mut name: Vec<u8> = Vec::new();
+    while index > 0 {
+        let rem = (index - 1) % 26;
+        name.push(b'A' + rem as u8);
+        index = (index - 1) / 26;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+/// The control characters are not allowed in XML 1.0, they are dropped.
+pub fn xml_escape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\t' | '\n' | '\r' => result.push(c),
+            c if (c as u32) < 0x20 => {}
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// The text starting with a formula character is prefixed with a quote, so the spreadsheet does not run it.
+pub fn csv_escape(value: &str) -> String {
+    let mut value = value.to_string();
+    if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        value.insert(0, '\'');
+    }
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn csv_value(value: &ExportValue) -> String {
+    match value {
+        ExportValue::Null => String::new(),
+        ExportValue::Bool(value) => value.to_string(),
+        ExportValue::Number(value) => value.to_string(),
+        ExportValue::Text(value) => csv_escape(value),
+    }
+}
+
+fn json_value(value: &ExportValue) -> serde_json::Result<String> {
+    match value {
+        ExportValue::Null => Ok("null".to_string()),
+        ExportValue::Bool(value) => Ok(value.to_string()),
+        ExportValue::Number(value) => Ok(value.to_string()),
+        ExportValue::Text(value) => serde_json::to_string(value),
+    }
+}
+
+#[derive(Debug, Clone, Copy, Display, EnumString, PartialEq, Eq)]
+pub enum ExportServiceError {
+    NotFound,
+    Fail,
+}
+
+impl ExportServiceError {
+    pub fn to_http(&self) -> Error {
+        match self {
+            Self::NotFound => error::ErrorNotFound(""),
+            _ => error::ErrorInternalServerError(""),
+        }
+    }
+}
+
+impl TranslatableError for ExportServiceError {
+    fn translate(&self, lang: &str, translator_service: &TranslatorService) -> String {
+        match self {
+            Self::NotFound => translator_service.translate(lang, "error.ExportServiceError.NotFound"),
+            _ => translator_service.translate(lang, "error.ExportServiceError.Fail"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Read};
+    use zip::ZipArchive;
+
+    fn columns() -> Vec<(String, String)> {
+        vec![
+            ("id".to_string(), "ID".to_string()),
+            ("email".to_string(), "E-mail".to_string()),
+            ("is_deleted".to_string(), "Is deleted?".to_string()),
+        ]
+    }
+
+    fn write(format: ExportFormat, rows: &[Vec<ExportValue>]) -> Vec<u8> {
+        let mut writer = ExportWriter::new(format, Cursor::new(Vec::new()), columns()).unwrap();
+        for row in rows {
+            writer.write_row(row).unwrap();
+        }
+        assert_eq!(writer.rows(), rows.len() as u64);
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_csv_escape() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::export::tests::test_csv_escape
+        assert_eq!(csv_escape("text"), "text");
+        assert_eq!(csv_escape("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_escape("line\nbreak"), "\"line\nbreak\"");
+        assert_eq!(csv_escape("=SUM(A1:A2)"), "'=SUM(A1:A2)");
+        assert_eq!(csv_escape("+1"), "'+1");
+        assert_eq!(csv_escape("@cmd"), "'@cmd");
+        assert_eq!(csv_escape("=1,2"), "\"'=1,2\"");
+    }
+
+    #[test]
+    fn test_xml_escape() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::export::tests::test_xml_escape
+        assert_eq!(xml_escape("a < b & c > \"d\""), "a &lt; b &amp; c &gt; &quot;d&quot;");
+        assert_eq!(xml_escape("a\u{0}b\u{1b}c\td\ne"), "abc\td\ne");
+        assert_eq!(xml_escape("Иванов"), "Иванов");
+    }
+
+    #[test]
+    fn test_xlsx_column_name() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::export::tests::test_xlsx_column_name
+        assert_eq!(xlsx_column_name(0), "A");
+        assert_eq!(xlsx_column_name(25), "Z");
+        assert_eq!(xlsx_column_name(26), "AA");
+        assert_eq!(xlsx_column_name(27), "AB");
+        assert_eq!(xlsx_column_name(701), "ZZ");
+        assert_eq!(xlsx_column_name(702), "AAA");
+    }
+
+    #[test]
+    fn test_write_csv() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::export::tests::test_write_csv
+        let rows = vec![
+            vec![ExportValue::Number(1), ExportValue::Text("a@b.c".to_string()), ExportValue::Bool(false)],
+            vec![ExportValue::Number(2), ExportValue::Text("=1+1".to_string()), ExportValue::Null],
+        ];
+        let result = String::from_utf8(write(ExportFormat::Csv, &rows)).unwrap();
+        assert_eq!(
+            result,
+            "\u{feff}ID,E-mail,Is deleted?\r\n1,a@b.c,false\r\n2,'=1+1,\r\n"
+        );
+    }
+
+    #[test]
+    fn test_write_json() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::export::tests::test_write_json
+        let rows = vec![
+            vec![ExportValue::Number(1), ExportValue::Text("\"quoted\"".to_string()), ExportValue::Bool(true)],
+            vec![ExportValue::Number(2), ExportValue::Null, ExportValue::Bool(false)],
+        ];
+        let result = write(ExportFormat::Json, &rows);
+        let value: serde_json::Value = serde_json::from_slice(&result).unwrap();
+        assert_eq!(
+            value,
+            json!([
+                {"id": 1, "email": "\"quoted\"", "is_deleted": true},
+                {"id": 2, "email": null, "is_deleted": false},
+            ])
+        );
+
+        let result = write(ExportFormat::Json, &[]);
+        assert_eq!(result, b"[]");
+    }
+
+    #[test]
+    fn test_write_xlsx() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::export::tests::test_write_xlsx
+        let rows = vec![vec![
+            ExportValue::Number(1),
+            ExportValue::Text("<a&b>".to_string()),
+            ExportValue::Bool(true),
+        ]];
+        let result = write(ExportFormat::Xlsx, &rows);
+        let mut archive = ZipArchive::new(Cursor::new(result)).unwrap();
+        for name in [
+            "[Content_Types].xml",
+            "_rels/.rels",
+            "xl/workbook.xml",
+            "xl/_rels/workbook.xml.rels",
+            "xl/styles.xml",
+        ] {
+            assert!(archive.by_name(name).is_ok(), "{}", name);
+        }
+        let mut sheet = String::new();
+        archive
+            .by_name("xl/worksheets/sheet1.xml")
+            .unwrap()
+            .read_to_string(&mut sheet)
+            .unwrap();
+        assert!(sheet.contains("<c r=\"A1\" s=\"1\" t=\"inlineStr\"><is><t xml:space=\"preserve\">ID</t></is></c>"));
+        assert!(sheet.contains("<c r=\"A2\"><v>1</v></c>"));
+        assert!(sheet.contains("<c r=\"B2\" t=\"inlineStr\"><is><t xml:space=\"preserve\">&lt;a&amp;b&gt;</t></is></c>"));
+        assert!(sheet.contains("<c r=\"C2\" t=\"b\"><v>1</v></c>"));
+        assert!(sheet.ends_with("</sheetData></worksheet>"));
+    }
+}
@@ -1,6 +1,8 @@
 #![allow(dead_code)]
-use crate::helpers::now_date_time_str;
-use crate::{AppError, Config, Disk, DiskExternalRepository, DiskLocalRepository, DiskRepository, File, FileColumn, FileFilter, FileMysqlRepository, FilePaginateParams, HashService, MysqlRepository, PaginationResult, RandomService, TranslatableError, TranslatorService, UserFile, UserFileColumn, UserFileFilter, UserFileMysqlRepository, UserFileService, UserFileServiceError, UserFileSort, UserServiceError};
+use crate::helpers::{now_date_time_str, DATE_TIME_FORMAT};
+use crate::{AppError, JobPayload, JobService, Config, Disk, DiskExternalRepository, DiskLocalRepository, DiskRepository, DiskS3Repository, DiskUploadStream, File, FileColumn, FileFilter, FileVariant, FileVariantService, FileMysqlRepository, FilePaginateParams, HashService, MysqlRepository, PaginationResult, RandomService, TranslatableError, TranslatorService, UserFile, UserFileColumn, UserFileFilter, UserFileMysqlRepository, UserFileService, UserFileServiceError, UserFileSort, UserServiceError, Role, User};
+use sha2::{Digest, Sha256};
+use chrono::{Duration, Utc};
 use actix_web::web::Data;
 use actix_web::{error, Error};
 use mime::Mime;
@@ -8,17 +10,26 @@ use mime2ext::mime2ext;
 use std::collections::HashMap;
 use std::io::{Read, Write};
 use std::path::MAIN_SEPARATOR_STR;
+use std::str::FromStr;
+use std::sync::Arc;
 use strum_macros::{Display, EnumString};
 
 pub const FILE_DEFAULT_IS_PUBLIC: bool = false;
 pub const FILE_DIRECTORY: &'static str = "files";
+pub const FILE_UPLOAD_DIRECTORY: &'static str = ".uploads";
+pub const FILE_UPLOAD_BUFFER_SIZE: usize = 65536;
+// 7 days to restore the deleted file, then the content is deleted from the disk.
+pub const FILE_DELETE_GRACE_PERIOD: i64 = 604800;
 
 pub struct FileService {
     config: Data<Config>,
     file_repository: Data<FileMysqlRepository>,
     user_file_service: Data<UserFileService>,
+    file_variant_service: Data<FileVariantService>,
+    job_service: Data<JobService>,
     disk_local_repository: Data<DiskLocalRepository>,
     disk_external_repository: Data<DiskExternalRepository>,
+    disk_s3_repository: Data<DiskS3Repository>,
     random_repository: Data<RandomService>,
     hash_service: Data<HashService>,
 }
@@ -28,8 +39,11 @@ impl FileService {
         config: Data<Config>,
         file_repository: Data<FileMysqlRepository>,
         user_file_service: Data<UserFileService>,
+        file_variant_service: Data<FileVariantService>,
+        job_service: Data<JobService>,
         disk_local_repository: Data<DiskLocalRepository>,
         disk_external_repository: Data<DiskExternalRepository>,
+        disk_s3_repository: Data<DiskS3Repository>,
         random_repository: Data<RandomService>,
         hash_service: Data<HashService>,
     ) -> Self {
@@ -37,8 +51,11 @@ impl FileService {
             config,
             file_repository,
             user_file_service,
+            file_variant_service,
+            job_service,
             disk_local_repository,
             disk_external_repository,
+            disk_s3_repository,
             random_repository,
             hash_service,
         }
@@ -54,6 +71,22 @@ impl FileService {
         e
     }
 
+    // The disk for new uploads from the FILESYSTEM_DISK env.
+    pub fn get_default_disk(&self) -> Disk {
+        Disk::from_str(&self.config.get_ref().filesystem.default).unwrap_or_default()
+    }
+
+    pub fn get_disk_repository(&self, disk: &Disk) -> &dyn DiskRepository {
+        match disk {
+            Disk::Local => self.disk_local_repository.get_ref(),
+            Disk::S3 => self.disk_s3_repository.get_ref(),
+        }
+    }
+
+    pub fn get_s3_repository(&self) -> &DiskS3Repository {
+        self.disk_s3_repository.get_ref()
+    }
+
     pub fn first_by_id(&self, id: u64) -> Result<Option<File>, FileServiceError> {
         self.file_repository
             .get_ref()
@@ -71,6 +104,19 @@ impl FileService {
         Err(error::ErrorNotFound(""))
     }
 
+    pub fn all_by_ids(&self, ids: &Vec<u64>) -> Result<Vec<File>, FileServiceError> {
+        let filters = vec![FileFilter::Ids(ids.to_owned())];
+        self.file_repository
+            .get_ref()
+            .all(Some(&filters), None, &None)
+            .map_err(|e| self.match_error(e))
+    }
+
+    pub fn all_by_ids_throw_http(&self, ids: &Vec<u64>) -> Result<Vec<File>, Error> {
+        self.all_by_ids(ids)
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+
     pub fn first_by_disk_and_filename(
         &self,
         disk: &Disk,
@@ -233,7 +279,7 @@ impl FileService {
             .map_err(|_| error::ErrorInternalServerError(""))
     }
 
-    pub fn upload_bytes_file_to_local_disk(
+    pub fn upload_bytes_file_to_disk(
         &self,
         user_id: u64,
         bytes: Vec<u8>,
@@ -243,14 +289,14 @@ impl FileService {
     ) -> Result<UserFile, FileServiceError> {
         let file_repository = self.file_repository.get_ref();
         let user_file_service = self.user_file_service.get_ref();
-        let disk_local_repository = self.disk_local_repository.get_ref();
         let hash_service = self.hash_service.get_ref();
 
         if let Some(upload_filename_) = upload_filename {
             upload_filename = Some(upload_filename_.trim().to_string());
         }
 
-        let disk = Disk::Local;
+        let disk = self.get_default_disk();
+        let disk_repository = self.get_disk_repository(&disk);
 
         // 1) Make filename [hash]-[size].[extensions]
         // 1.1) Make hash
@@ -285,9 +331,9 @@ impl FileService {
         }
 
         // 2) Make path = [root]/[service_folder]/[filename]
-        let path: String = disk_local_repository.path(&filename).map_err(|e| {
+        let path: String = disk_repository.path(&filename).map_err(|e| {
             self.log_error(
-                "upload_bytes_file_to_local_disk",
+                "upload_bytes_file_to_disk",
                 e.to_string(),
                 FileServiceError::Fail,
             )
@@ -298,7 +344,7 @@ impl FileService {
             .first_by_disk_and_path(&disk, &path)
             .map_err(|e| {
                 self.log_error(
-                    "upload_bytes_file_to_local_disk",
+                    "upload_bytes_file_to_disk",
                     e.to_string(),
                     FileServiceError::Fail,
                 )
@@ -317,7 +363,7 @@ impl FileService {
             file.disk = disk.to_string();
         } else if file.disk.ne(disk.to_string().as_str()) {
             return Err(self.log_error(
-                "upload_bytes_file_to_local_disk",
+                "upload_bytes_file_to_disk",
                 format!("Disk not equal. File ID: {}.", file.id),
                 FileServiceError::Fail,
             ));
@@ -376,9 +422,9 @@ impl FileService {
         }
 
         // 5) Copy content if necessary.
-        let is_exists_in_fs = disk_local_repository.exists(&file.path).map_err(|e| {
+        let is_exists_in_fs = disk_repository.exists(&file.path).map_err(|e| {
             self.log_error(
-                "upload_bytes_file_to_local_disk",
+                "upload_bytes_file_to_disk",
                 e.to_string(),
                 FileServiceError::Fail,
             )
@@ -390,9 +436,9 @@ impl FileService {
         if is_exists_in_fs {
             if let Some(new_file_hash) = &file.hash {
                 let old_file_hash: String =
-                    disk_local_repository.hash(&file.path).map_err(|e| {
+                    disk_repository.hash(&file.path).map_err(|e| {
                         self.log_error(
-                            "upload_bytes_file_to_local_disk",
+                            "upload_bytes_file_to_disk",
                             e.to_string(),
                             FileServiceError::Fail,
                         )
@@ -409,9 +455,9 @@ impl FileService {
         }
 
         if is_delete_old_file {
-            disk_local_repository.delete(&file.path).map_err(|e| {
+            disk_repository.delete(&file.path).map_err(|e| {
                 self.log_error(
-                    "upload_bytes_file_to_local_disk",
+                    "upload_bytes_file_to_disk",
                     e.to_string(),
                     FileServiceError::Fail,
                 )
@@ -419,11 +465,11 @@ impl FileService {
         }
 
         if is_copy {
-            disk_local_repository
+            disk_repository
                 .put(&file.path, bytes)
                 .map_err(|e| {
                     self.log_error(
-                        "upload_bytes_file_to_local_disk",
+                        "upload_bytes_file_to_disk",
                         e.to_string(),
                         FileServiceError::Fail,
                     )
@@ -438,7 +484,7 @@ impl FileService {
                 .first_by_disk_and_path(&disk, &file.path)
                 .map_err(|e| {
                     self.log_error(
-                        "upload_bytes_file_to_local_disk",
+                        "upload_bytes_file_to_disk",
                         e.to_string(),
                         FileServiceError::Fail,
                     )
@@ -447,19 +493,21 @@ impl FileService {
                 file = file_;
             } else {
                 return Err(self.log_error(
-                    "upload_bytes_file_to_local_disk",
+                    "upload_bytes_file_to_disk",
                     format!("File created, but not found {}", &file.path),
                     FileServiceError::NotFound,
                 ));
             }
         }
 
+        self.refresh_variants(&file, is_delete_old_file);
+
         // 7) Upsert user file in db
         let user_file: Option<UserFile> = user_file_service
             .first_by_user_id_and_file_id(user_id, file.id)
             .map_err(|e| {
                 self.log_error(
-                    "upload_bytes_file_to_local_disk",
+                    "upload_bytes_file_to_disk",
                     e.to_string(),
                     FileServiceError::Fail,
                 )
@@ -478,7 +526,7 @@ impl FileService {
             user_file.user_id = user_id;
         } else if user_file.user_id.ne(&user_id) {
             return Err(self.log_error(
-                "upload_bytes_file_to_local_disk",
+                "upload_bytes_file_to_disk",
                 format!("User ID not equal. User File ID: {}.", user_file.id),
                 FileServiceError::Fail,
             ));
@@ -488,7 +536,7 @@ impl FileService {
             user_file.file_id = file.id;
         } else if user_file.file_id.ne(&file.id) {
             return Err(self.log_error(
-                "upload_bytes_file_to_local_disk",
+                "upload_bytes_file_to_disk",
                 format!("File ID not equal. User File ID: {}.", user_file.id),
                 FileServiceError::Fail,
             ));
@@ -529,7 +577,7 @@ impl FileService {
             let file_id = user_file.file_id;
             user_file_service.upsert(user_file, &None, &file).map_err(|e| {
                 self.log_error(
-                    "upload_bytes_file_to_local_disk",
+                    "upload_bytes_file_to_disk",
                     e.to_string(),
                     FileServiceError::Fail,
                 )
@@ -539,7 +587,7 @@ impl FileService {
                 .first_by_user_id_and_file_id(user_id, file_id)
                 .map_err(|e| {
                     self.log_error(
-                        "upload_bytes_file_to_local_disk",
+                        "upload_bytes_file_to_disk",
                         e.to_string(),
                         FileServiceError::Fail,
                     )
@@ -548,7 +596,7 @@ impl FileService {
                 user_file = user_file_;
             } else {
                 return Err(self.log_error(
-                    "upload_bytes_file_to_local_disk",
+                    "upload_bytes_file_to_disk",
                     format!("User File created, but not found {}", &file.path),
                     FileServiceError::NotFound,
                 ));
@@ -558,51 +606,134 @@ impl FileService {
         Ok(user_file)
     }
 
-    pub fn upload_local_file_to_local_disk(
+    pub fn upload_local_file_to_disk(
         &self,
         user_id: u64,
         upload_path: &str,
         is_public: bool,
-        mut upload_filename: Option<String>,
+        upload_filename: Option<String>,
         mut mime: Option<Mime>,
     ) -> Result<UserFile, FileServiceError> {
-        let file_repository = self.file_repository.get_ref();
-        let user_file_service = self.user_file_service.get_ref();
         let disk_local_repository = self.disk_local_repository.get_ref();
 
         let is_exists = disk_local_repository.exists(upload_path).map_err(|e| {
             self.log_error(
-                "upload_local_file_to_local_disk",
+                "upload_local_file_to_disk",
                 e.to_string(),
                 FileServiceError::Fail,
             )
         })?;
         if !is_exists {
             return Err(self.log_error(
-                "upload_local_file_to_local_disk",
+                "upload_local_file_to_disk",
                 format!("File not found {}", upload_path),
                 FileServiceError::NotFound,
             ));
         }
 
+        if mime.is_none() {
+            mime = mime_guess::from_path(upload_path).first()
+        }
+
+        let mut read_stream = disk_local_repository
+            .read_stream(upload_path)
+            .map_err(|e| {
+                self.log_error(
+                    "upload_local_file_to_disk",
+                    e.to_string(),
+                    FileServiceError::Fail,
+                )
+            })?;
+
+        let mut upload = self.upload_stream(u64::MAX)?;
+        let mut buf = [0u8; FILE_UPLOAD_BUFFER_SIZE];
+        loop {
+            let length = read_stream.read(&mut buf).map_err(|e| {
+                self.log_error(
+                    "upload_local_file_to_disk",
+                    e.to_string(),
+                    FileServiceError::Fail,
+                )
+            })?;
+            if length == 0 {
+                break;
+            }
+            upload.write(&buf[..length])?;
+        }
+
+        self.upload_stream_to_disk(user_id, upload, is_public, upload_filename, mime)
+    }
+
+    // The upload limit is the largest limit of the user roles, the default one is used if no role sets it.
+    pub fn get_upload_max_size(&self, user: &User, roles: &Vec<Role>) -> u64 {
+        let roles_ids = user.roles_ids.to_owned().unwrap_or_default();
+        roles
+            .iter()
+            .filter(|role| roles_ids.contains(&role.id))
+            .filter_map(|role| role.upload_max_size)
+            .max()
+            .unwrap_or(self.config.get_ref().filesystem.upload_max_size)
+    }
+
+    // Start writing a new upload to a temporary path of the default disk.
+    pub fn upload_stream(&self, max_size: u64) -> Result<FileUploadStream, FileServiceError> {
+        let disk = self.get_default_disk();
+        let disk_repository: Arc<dyn DiskRepository> = match disk {
+            Disk::Local => self.disk_local_repository.clone().into_inner(),
+            Disk::S3 => self.disk_s3_repository.clone().into_inner(),
+        };
+
+        let mut temp_path = FILE_UPLOAD_DIRECTORY.to_string();
+        temp_path.push_str(MAIN_SEPARATOR_STR);
+        temp_path.push_str(self.random_repository.get_ref().str(32).as_str());
+        temp_path.push_str(".part");
+
+        let path = disk_repository.path(&temp_path).map_err(|e| {
+            self.log_error("upload_stream", e.to_string(), FileServiceError::Fail)
+        })?;
+        let write_stream = disk_repository.upload_stream(&path).map_err(|e| {
+            self.log_error("upload_stream", e.to_string(), FileServiceError::Fail)
+        })?;
+
+        Ok(FileUploadStream {
+            disk,
+            path,
+            disk_repository,
+            write_stream: Some(write_stream),
+            hasher: Sha256::new(),
+            size: 0,
+            max_size,
+            is_moved: false,
+        })
+    }
+
+    // Finish the upload: the content is stored at [hash]-[size].[extension] or dropped if the same file exists.
+    pub fn upload_stream_to_disk(
+        &self,
+        user_id: u64,
+        mut upload: FileUploadStream,
+        is_public: bool,
+        mut upload_filename: Option<String>,
+        mut mime: Option<Mime>,
+    ) -> Result<UserFile, FileServiceError> {
+        let file_repository = self.file_repository.get_ref();
+        let user_file_service = self.user_file_service.get_ref();
+
         if let Some(upload_filename_) = upload_filename {
             upload_filename = Some(upload_filename_.trim().to_string());
         }
 
-        let disk = Disk::Local;
+        let disk = upload.disk;
+        let disk_repository = self.get_disk_repository(&disk);
 
         // 1) Make filename [hash]-[size].[extensions]
         // 1.1) Make hash
-        let hash: String = disk_local_repository.hash(upload_path).map_err(|e| {
-            self.log_error(
-                "upload_local_file_to_local_disk",
-                e.to_string(),
-                FileServiceError::Fail,
-            )
-        })?;
+        let hash: String = hex::encode(upload.hasher.finalize_reset());
 
         if mime.is_none() {
-            mime = mime_guess::from_path(upload_path).first()
+            if let Some(upload_filename) = &upload_filename {
+                mime = mime_guess::from_path(upload_filename).first()
+            }
         }
 
         let mime_str: Option<String> = if let Some(mime) = &mime {
@@ -612,79 +743,59 @@ impl FileService {
         };
 
         // 1.2) Make size
-        let size = disk_local_repository.size(upload_path).map_err(|e| {
-            self.log_error(
-                "upload_local_file_to_local_disk",
-                e.to_string(),
-                FileServiceError::Fail,
-            )
-        })?;
+        let size: u64 = upload.size;
 
         // 1.3) Make extensions
-        let mut mimes_from_path: Vec<Mime> = Vec::new();
-
-        let upload_path_ = upload_path.to_lowercase();
-        let last_path = upload_path_.split(MAIN_SEPARATOR_STR).last();
-        let mut i = 0;
-        if let Some(last_path) = last_path {
-            for path_ in last_path.split('.') {
-                i += 1;
-                if i > 20 {
-                    // A stub in case of an attack through multiple file extensions.
-                    mimes_from_path = Vec::new();
-                    break;
-                }
-                let g_mime: Option<Mime> = mime_guess::from_ext(path_.trim()).first();
-                if let Some(mime_) = g_mime {
-                    mimes_from_path.push(mime_);
-                }
-            }
-        }
-
-        if let Some(mime) = mime {
-            if let Some(mime_) = mimes_from_path.last() {
-                if mime.ne(mime_) {
-                    mimes_from_path.push(mime.to_owned());
-                }
-            } else {
-                mimes_from_path.push(mime.to_owned());
-            }
-        }
-        let mut extensions: String = "".to_string();
-        for mime_ in mimes_from_path {
-            if let Some(ext) = mime2ext(mime_) {
-                if !extensions.is_empty() {
-                    extensions.push('.');
-                }
-                extensions.push_str(ext.trim());
-            }
-        }
-
         // [hash]-[size].[extensions]
         let mut filename = hash.to_owned();
         filename.push('-');
         filename.push_str(size.to_string().as_str());
 
-        if !extensions.is_empty() {
-            filename.push('.');
-            filename.push_str(extensions.as_str());
+        if let Some(mime_) = &mime {
+            if let Some(ext) = mime2ext(mime_) {
+                filename.push('.');
+                filename.push_str(ext.trim());
+            }
         }
 
         // 2) Make path = [root]/[service_folder]/[filename]
-        let path: String = disk_local_repository.path(&filename).map_err(|e| {
+        let path: String = disk_repository.path(&filename).map_err(|e| {
             self.log_error(
-                "upload_local_file_to_local_disk",
+                "upload_stream_to_disk",
                 e.to_string(),
                 FileServiceError::Fail,
             )
         })?;
 
+        // 2.1) Store content if the same file is not stored yet, the duplicate is deleted when the upload is dropped.
+        // The [hash]-[size] name identifies the content, so the stored file is not read again.
+        let is_exists_in_fs = disk_repository.exists(&path).map_err(|e| {
+            self.log_error(
+                "upload_stream_to_disk",
+                e.to_string(),
+                FileServiceError::Fail,
+            )
+        })?;
+
+        if !is_exists_in_fs {
+            if let Some(write_stream) = upload.write_stream.take() {
+                write_stream.finish(&path, &hash).map_err(|e| {
+                    self.log_error(
+                        "upload_stream_to_disk",
+                        e.to_string(),
+                        FileServiceError::Fail,
+                    )
+                })?;
+                upload.is_moved = true;
+            }
+        }
+
         // 3) Find old file in db or make new file
         let file: Option<File> = file_repository
             .first_by_disk_and_path(&disk, &path)
             .map_err(|e| {
                 self.log_error(
-                    "upload_local_file_to_local_disk",
+                    "upload_stream_to_disk",
                     e.to_string(),
                     FileServiceError::Fail,
                 )
@@ -703,7 +814,7 @@ impl FileService {
             file.disk = disk.to_string();
         } else if file.disk.ne(disk.to_string().as_str()) {
             return Err(self.log_error(
-                "upload_local_file_to_local_disk",
+                "upload_stream_to_disk",
                 format!("Disk not equal. File ID: {}.", file.id),
                 FileServiceError::Fail,
             ));
@@ -761,94 +872,6 @@ impl FileService {
             is_upsert = true;
         }
 
-        // 5) Copy content if necessary.
-        let is_exists_in_fs = disk_local_repository.exists(&file.path).map_err(|e| {
-            self.log_error(
-                "upload_local_file_to_local_disk",
-                e.to_string(),
-                FileServiceError::Fail,
-            )
-        })?;
-
-        let mut is_delete_old_file = false;
-        let mut is_copy = !is_exists_in_fs;
-
-        if is_exists_in_fs {
-            if let Some(new_file_hash) = &file.hash {
-                let old_file_hash: String =
-                    disk_local_repository.hash(&file.path).map_err(|e| {
-                        self.log_error(
-                            "upload_local_file_to_local_disk",
-                            e.to_string(),
-                            FileServiceError::Fail,
-                        )
-                    })?;
-
-                if old_file_hash.ne(new_file_hash) {
-                    is_copy = true;
-                    is_delete_old_file = true;
-                }
-            } else {
-                is_copy = true;
-                is_delete_old_file = true;
-            }
-        }
-
-        if is_delete_old_file {
-            disk_local_repository.delete(&file.path).map_err(|e| {
-                self.log_error(
-                    "upload_local_file_to_local_disk",
-                    e.to_string(),
-                    FileServiceError::Fail,
-                )
-            })?;
-        }
-
-        if is_copy {
-            let mut read_stream = disk_local_repository
-                .read_stream(upload_path)
-                .map_err(|e| {
-                    self.log_error(
-                        "upload_local_file_to_local_disk",
-                        e.to_string(),
-                        FileServiceError::Fail,
-                    )
-                })?;
-            let mut write_stream = disk_local_repository
-                .write_stream(&file.path)
-                .map_err(|e| {
-                    self.log_error(
-                        "upload_local_file_to_local_disk",
-                        e.to_string(),
-                        FileServiceError::Fail,
-                    )
-                })?;
-            let mut buf: Vec<u8> = Vec::new();
-            while read_stream.read_to_end(&mut buf).map_err(|e| {
-                self.log_error(
-                    "upload_local_file_to_local_disk",
-                    e.to_string(),
-                    FileServiceError::Fail,
-                )
-            })? > 0 {
-                write_stream.write_all(&buf).map_err(|e| {
-                    self.log_error(
-                        "upload_local_file_to_local_disk",
-                        e.to_string(),
-                        FileServiceError::Fail,
-                    )
-                })?;
-                buf.clear();
-            }
-            write_stream.flush().map_err(|e| {
-                self.log_error(
-                    "upload_local_file_to_local_disk",
-                    e.to_string(),
-                    FileServiceError::Fail,
-                )
-            })?;
-        }
-
         // 6) Upsert file meta in db
         if is_upsert {
             self.upsert(file.to_owned(), &None)?;
@@ -857,7 +880,7 @@ impl FileService {
                 .first_by_disk_and_path(&disk, &file.path)
                 .map_err(|e| {
                     self.log_error(
-                        "upload_local_file_to_local_disk",
+                        "upload_stream_to_disk",
                         e.to_string(),
                         FileServiceError::Fail,
                     )
@@ -866,19 +889,21 @@ impl FileService {
                 file = file_;
             } else {
                 return Err(self.log_error(
-                    "upload_local_file_to_local_disk",
+                    "upload_stream_to_disk",
                     format!("File created, but not found {}", &file.path),
                     FileServiceError::NotFound,
                 ));
             }
         }
 
+        self.refresh_variants(&file, false);
+
         // 7) Upsert user file in db
         let user_file: Option<UserFile> = user_file_service
             .first_by_user_id_and_file_id(user_id, file.id)
             .map_err(|e| {
                 self.log_error(
-                    "upload_local_file_to_local_disk",
+                    "upload_stream_to_disk",
                     e.to_string(),
                     FileServiceError::Fail,
                 )
@@ -897,7 +922,7 @@ impl FileService {
             user_file.user_id = user_id;
         } else if user_file.user_id.ne(&user_id) {
             return Err(self.log_error(
-                "upload_local_file_to_local_disk",
+                "upload_stream_to_disk",
                 format!("User ID not equal. User File ID: {}.", user_file.id),
                 FileServiceError::Fail,
             ));
@@ -907,7 +932,7 @@ impl FileService {
             user_file.file_id = file.id;
         } else if user_file.file_id.ne(&file.id) {
             return Err(self.log_error(
-                "upload_local_file_to_local_disk",
+                "upload_stream_to_disk",
                 format!("File ID not equal. User File ID: {}.", user_file.id),
                 FileServiceError::Fail,
             ));
@@ -948,7 +973,7 @@ impl FileService {
             let file_id = user_file.file_id;
             user_file_service.upsert(user_file, &None, &file).map_err(|e| {
                 self.log_error(
-                    "upload_local_file_to_local_disk",
+                    "upload_stream_to_disk",
                     e.to_string(),
                     FileServiceError::Fail,
                 )
@@ -958,7 +983,7 @@ impl FileService {
                 .first_by_user_id_and_file_id(user_id, file_id)
                 .map_err(|e| {
                     self.log_error(
-                        "upload_local_file_to_local_disk",
+                        "upload_stream_to_disk",
                         e.to_string(),
                         FileServiceError::Fail,
                     )
@@ -967,7 +992,7 @@ impl FileService {
                 user_file = user_file_;
             } else {
                 return Err(self.log_error(
-                    "upload_local_file_to_local_disk",
+                    "upload_stream_to_disk",
                     format!("User File created, but not found {}", &file.path),
                     FileServiceError::NotFound,
                 ));
@@ -977,6 +1002,116 @@ impl FileService {
         Ok(user_file)
     }
 
+    // The variants are generated by the worker, without the queue they are generated in place.
+    // The variants failure does not fail the upload, the original is served instead of the missing variants.
+    fn refresh_variants(&self, file: &File, is_replaced: bool) {
+        let payload = JobPayload::GenerateFileVariants {
+            file_id: file.id,
+            is_replaced,
+        };
+        if self.job_service.get_ref().dispatch(payload).is_ok() {
+            return;
+        }
+        if let Err(e) = self.file_variant_service.get_ref().refresh(file, is_replaced) {
+            self.log_error("refresh_variants", e.to_string(), FileServiceError::Fail);
+        }
+    }
+
+    // Delete the content of the files deleted longer than the grace period ago.
+    pub fn collect_garbage(&self) -> Result<u64, FileServiceError> {
+        let delete_at = (Utc::now() - Duration::seconds(FILE_DELETE_GRACE_PERIOD))
+            .format(DATE_TIME_FORMAT)
+            .to_string();
+        let filters = vec![
+            FileFilter::IsDelete(true),
+            FileFilter::IsDeleted(false),
+            FileFilter::DeleteAtBefore(delete_at),
+        ];
+        let files = self
+            .file_repository
+            .get_ref()
+            .all(Some(&filters), None, &None)
+            .map_err(|e| self.log_error("collect_garbage", e.to_string(), FileServiceError::Fail))?;
+
+        let mut count: u64 = 0;
+        for file in files {
+            self.delete_from_disk(file)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    // The public links, the variants and the original are deleted, the row is kept with the is_deleted label.
+    fn delete_from_disk(&self, mut file: File) -> Result<(), FileServiceError> {
+        let user_file_service = self.user_file_service.get_ref();
+        let filters = vec![
+            UserFileFilter::FileId(file.id),
+            UserFileFilter::IsPublic(true),
+        ];
+        let user_files = user_file_service
+            .all(Some(&filters), None)
+            .map_err(|e| self.match_user_service_error(e))?;
+        let columns = Some(vec![
+            UserFileColumn::IsPublic,
+            UserFileColumn::Filename,
+            UserFileColumn::Path,
+        ]);
+        for mut user_file in user_files {
+            user_file.is_public = false;
+            user_file_service
+                .update(user_file, &columns, &file)
+                .map_err(|e| self.match_user_service_error(e))?;
+        }
+
+        self.file_variant_service
+            .get_ref()
+            .delete_by_file(&file)
+            .map_err(|e| self.log_error("delete_from_disk", e.to_string(), FileServiceError::Fail))?;
+
+        let disk = Disk::from_str(&file.disk)
+            .map_err(|e| self.log_error("delete_from_disk", e.to_string(), FileServiceError::Fail))?;
+        self.get_disk_repository(&disk)
+            .delete(&file.path)
+            .map_err(|e| self.log_error("delete_from_disk", e.to_string(), FileServiceError::Fail))?;
+
+        file.is_deleted = true;
+        file.deleted_at = Some(now_date_time_str());
+        self.update(
+            file,
+            &Some(vec![FileColumn::IsDeleted, FileColumn::DeletedAt]),
+        )
+    }
+
+    pub fn load_and_attach_variants(&self, files: &mut Vec<File>) -> Result<(), FileServiceError> {
+        if files.is_empty() {
+            return Ok(());
+        }
+
+        let file_ids: Vec<u64> = files.iter().map(|f| f.id).collect();
+        let variants = self
+            .file_variant_service
+            .get_ref()
+            .all_by_file_ids(&file_ids)
+            .map_err(|e| {
+                self.log_error(
+                    "load_and_attach_variants",
+                    e.to_string(),
+                    FileServiceError::Fail,
+                )
+            })?;
+        let mut variants_idx: HashMap<u64, Vec<FileVariant>> = HashMap::new();
+
+        for variant in variants {
+            variants_idx.entry(variant.file_id).or_default().push(variant);
+        }
+
+        for file in files {
+            file.variants = variants_idx.remove(&file.id);
+        }
+
+        Ok(())
+    }
+
     pub fn load_and_attach_user_files(
         &self,
         files: &mut Vec<File>,
@@ -1024,11 +1159,63 @@ impl FileService {
     }
 }
 
+// The upload being written to the disk, the temporary file is deleted if the upload is not finished.
+pub struct FileUploadStream {
+    disk: Disk,
+    path: String,
+    disk_repository: Arc<dyn DiskRepository>,
+    write_stream: Option<Box<dyn DiskUploadStream>>,
+    hasher: Sha256,
+    size: u64,
+    max_size: u64,
+    is_moved: bool,
+}
+
+impl FileUploadStream {
+    pub fn write(&mut self, chunk: &[u8]) -> Result<(), FileServiceError> {
+        self.size += chunk.len() as u64;
+        if self.size > self.max_size {
+            return Err(FileServiceError::TooLarge);
+        }
+        self.hasher.update(chunk);
+        if let Some(write_stream) = &mut self.write_stream {
+            write_stream.write_all(chunk).map_err(|e| {
+                log::error!("FileUploadStream::write - {e}");
+                FileServiceError::Fail
+            })?;
+        }
+        Ok(())
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn max_size(&self) -> u64 {
+        self.max_size
+    }
+}
+
+impl Drop for FileUploadStream {
+    fn drop(&mut self) {
+        if self.is_moved {
+            return;
+        }
+        self.write_stream = None;
+        if let Err(e) = self.disk_repository.delete(&self.path) {
+            if e.kind().ne(&std::io::ErrorKind::NotFound) {
+                log::error!("FileUploadStream::drop - {e}");
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Display, EnumString, PartialEq, Eq)]
 pub enum FileServiceError {
     DbConnectionFail,
     DuplicateFile,
     NotFound,
+    TooLarge,
     Fail,
     UserFileServiceFail,
 }
@@ -1043,6 +1230,7 @@ impl TranslatableError for FileServiceError {
                 translator_service.translate(lang, "error.FileServiceError.DuplicateFile")
             }
             Self::NotFound => translator_service.translate(lang, "error.FileServiceError.NotFound"),
+            Self::TooLarge => translator_service.translate(lang, "error.FileServiceError.TooLarge"),
             Self::UserFileServiceFail => {
                 translator_service.translate(lang, "error.UserFileServiceError.Fail")
             }
@@ -1059,6 +1247,47 @@ mod tests {
     use std::{env, fs};
     use test::Bencher;
 
+    #[test]
+    fn test_upload_stream() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::file::tests::test_upload_stream
+        use super::{FileServiceError, FileUploadStream};
+        use crate::{DiskLocalRepository, DiskRepository};
+        use sha2::{Digest, Sha256};
+        use std::sync::Arc;
+
+        let root = env::current_dir().unwrap();
+        let root = root.to_str().unwrap();
+        let disk_repository = Arc::new(DiskLocalRepository::new(root, root, MAIN_SEPARATOR_STR));
+        let path = disk_repository
+            .path("/test_upload_stream/.uploads/test_upload_stream.part")
+            .unwrap();
+
+        let mut upload = FileUploadStream {
+            disk: Disk::Local,
+            path: path.to_owned(),
+            disk_repository: disk_repository.clone(),
+            write_stream: Some(disk_repository.upload_stream(&path).unwrap()),
+            hasher: Sha256::new(),
+            size: 0,
+            max_size: 8,
+            is_moved: false,
+        };
+        upload.write(b"test").unwrap();
+        upload.write(b"test").unwrap();
+        assert_eq!(upload.size(), 8);
+        assert_eq!(upload.write(b"t"), Err(FileServiceError::TooLarge));
+        assert_eq!(
+            hex::encode(upload.hasher.finalize_reset()),
+            "37268335dd6931045bdcdf92623ff819a64244b53d0e746d438797349d4da578"
+        );
+        assert!(fs::exists(&path).unwrap());
+
+        // The unfinished upload is deleted.
+        drop(upload);
+        assert!(!fs::exists(&path).unwrap());
+        fs::remove_dir_all(disk_repository.path("/test_upload_stream").unwrap()).unwrap();
+    }
+
     // #[test]
     // fn test_upload_local_file_to_local_disk() {
     //     // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::file::tests::test_upload_local_file_to_local_disk
@@ -0,0 +1,380 @@
+use crate::config::FilesystemImageVariantConfig;
+use crate::helpers::now_date_time_str;
+use crate::{
+    Config, Disk, DiskLocalRepository, DiskRepository, DiskS3Repository, File, FileVariant,
+    FileVariantMysqlRepository, MysqlRepository, TranslatableError,
+    TranslatorService, UserFile, UserFileService,
+};
+use actix_web::web::Data;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat, ImageReader};
+use std::io::Cursor;
+use std::str::FromStr;
+use strum_macros::{Display, EnumString};
+
+// The variant rendered in the tables and the avatar, it is expected in FILESYSTEM_IMAGE_VARIANTS.
+pub const FILE_VARIANT_THUMB: &'static str = "thumb";
+
+pub struct FileVariantService {
+    config: Data<Config>,
+    file_variant_repository: Data<FileVariantMysqlRepository>,
+    user_file_service: Data<UserFileService>,
+    disk_local_repository: Data<DiskLocalRepository>,
+    disk_s3_repository: Data<DiskS3Repository>,
+}
+
+impl FileVariantService {
+    pub fn new(
+        config: Data<Config>,
+        file_variant_repository: Data<FileVariantMysqlRepository>,
+        user_file_service: Data<UserFileService>,
+        disk_local_repository: Data<DiskLocalRepository>,
+        disk_s3_repository: Data<DiskS3Repository>,
+    ) -> Self {
+        Self {
+            config,
+            file_variant_repository,
+            user_file_service,
+            disk_local_repository,
+            disk_s3_repository,
+        }
+    }
+
+    pub fn get_service_name(&self) -> &str {
+        "FileVariantService"
+    }
+
+    pub fn log_error(
+        &self,
+        method: &str,
+        error: String,
+        e: FileVariantServiceError,
+    ) -> FileVariantServiceError {
+        let service_name = self.get_service_name().to_string();
+        log::error!("{}::{} - {}", service_name, method, error);
+        e
+    }
+
+    pub fn get_disk_repository(&self, disk: &Disk) -> &dyn DiskRepository {
+        match disk {
+            Disk::Local => self.disk_local_repository.get_ref(),
+            Disk::S3 => self.disk_s3_repository.get_ref(),
+        }
+    }
+
+    pub fn get_variants_config(&self) -> &Vec<FilesystemImageVariantConfig> {
+        &self.config.get_ref().filesystem.image_variants
+    }
+
+    pub fn first_by_file_id_and_name(
+        &self,
+        file_id: u64,
+        name: &str,
+    ) -> Result<Option<FileVariant>, FileVariantServiceError> {
+        self.file_variant_repository
+            .get_ref()
+            .first_by_file_id_and_name(file_id, name)
+            .map_err(|e| {
+                self.log_error(
+                    "first_by_file_id_and_name",
+                    e.to_string(),
+                    FileVariantServiceError::Fail,
+                )
+            })
+    }
+
+    pub fn all_by_file_ids(
+        &self,
+        file_ids: &Vec<u64>,
+    ) -> Result<Vec<FileVariant>, FileVariantServiceError> {
+        if file_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.file_variant_repository
+            .get_ref()
+            .all_by_file_ids(file_ids)
+            .map_err(|e| {
+                self.log_error("all_by_file_ids", e.to_string(), FileVariantServiceError::Fail)
+            })
+    }
+
+    // The path of the requested variant, the original is served while the variant is not generated.
+    pub fn get_path(
+        &self,
+        file: &File,
+        name: &Option<String>,
+    ) -> Result<String, FileVariantServiceError> {
+        if let Some(name) = name {
+            if let Some(variant) = self.first_by_file_id_and_name(file.id, name)? {
+                return Ok(variant.path);
+            }
+        }
+        Ok(file.path.to_owned())
+    }
+
+    pub fn get_public_path(&self, user_file: &UserFile, name: &str) -> Option<String> {
+        let user_file_service = self.user_file_service.get_ref();
+        let public_path = user_file_service.get_public_path(user_file)?;
+
+        if Disk::Local.to_string().eq(&user_file.disk) {
+            return Some(format!("{}?variant={}", public_path, name));
+        }
+
+        // The objects of the S3 disk are not served by the app, the variant object url is given instead.
+        if Disk::S3.to_string().eq(&user_file.disk) {
+            if let Ok(Some(variant)) = self.first_by_file_id_and_name(user_file.file_id, name) {
+                if let Ok(url) = self.disk_s3_repository.get_ref().url(&variant.path) {
+                    return Some(url);
+                }
+            }
+        }
+
+        Some(public_path)
+    }
+
+    pub fn delete_by_file(&self, file: &File) -> Result<(), FileVariantServiceError> {
+        let variants = self.all_by_file_ids(&vec![file.id])?;
+        if variants.is_empty() {
+            return Ok(());
+        }
+        for variant in &variants {
+            let disk = Disk::from_str(&variant.disk).map_err(|e| {
+                self.log_error("delete_by_file", e.to_string(), FileVariantServiceError::Fail)
+            })?;
+            self.get_disk_repository(&disk)
+                .delete(&variant.path)
+                .map_err(|e| {
+                    self.log_error("delete_by_file", e.to_string(), FileVariantServiceError::Fail)
+                })?;
+        }
+        self.file_variant_repository
+            .get_ref()
+            .delete_by_file_id(file.id)
+            .map_err(|e| self.log_error("delete_by_file", e.to_string(), FileVariantServiceError::Fail))
+    }
+
+    // Drop the variants of the replaced content and generate the missing ones.
+    pub fn refresh(&self, file: &File, is_replaced: bool) -> Result<(), FileVariantServiceError> {
+        if is_replaced {
+            self.delete_by_file(file)?;
+        }
+        self.generate(file)?;
+        Ok(())
+    }
+
+    // Generate the missing variants of the image file and store them next to the original.
+    pub fn generate(&self, file: &File) -> Result<Vec<FileVariant>, FileVariantServiceError> {
+        let source_format = match get_image_format(&file.mime) {
+            Some(format) => format,
+            None => return Ok(Vec::new()),
+        };
+        let disk = Disk::from_str(&file.disk)
+            .map_err(|e| self.log_error("generate", e.to_string(), FileVariantServiceError::Fail))?;
+        let disk_repository = self.get_disk_repository(&disk);
+        let file_variant_repository = self.file_variant_repository.get_ref();
+
+        let variants = self.all_by_file_ids(&vec![file.id])?;
+
+        let mut image: Option<DynamicImage> = None;
+
+        for config in self.get_variants_config() {
+            let variant = variants.iter().find(|v| v.name.eq(&config.name));
+            if let Some(variant) = variant {
+                let is_exists = disk_repository.exists(&variant.path).map_err(|e| {
+                    self.log_error("generate", e.to_string(), FileVariantServiceError::Fail)
+                })?;
+                if is_exists {
+                    continue;
+                }
+            }
+
+            if image.is_none() {
+                let bytes = disk_repository.get(&file.path).map_err(|e| {
+                    self.log_error("generate", e.to_string(), FileVariantServiceError::Fail)
+                })?;
+                image = Some(
+                    ImageReader::with_format(Cursor::new(bytes), source_format)
+                        .decode()
+                        .map_err(|e| {
+                            self.log_error("generate", e.to_string(), FileVariantServiceError::Fail)
+                        })?,
+                );
+            }
+            let image = image.as_ref().unwrap();
+
+            let format = get_variant_format(config, source_format);
+            let variant_image = make_variant_image(image, config);
+            let bytes = encode_variant_image(&variant_image, format).map_err(|e| {
+                self.log_error("generate", e.to_string(), FileVariantServiceError::Fail)
+            })?;
+
+            let filename = make_variant_filename(&file.filename, &config.name, format);
+            let path = disk_repository.path(&filename).map_err(|e| {
+                self.log_error("generate", e.to_string(), FileVariantServiceError::Fail)
+            })?;
+
+            let mut data = FileVariant::default();
+            if let Some(variant) = variant {
+                data = variant.to_owned();
+            }
+            data.file_id = file.id;
+            data.name = config.name.to_owned();
+            data.filename = filename;
+            data.path = path;
+            data.mime = Some(format.to_mime_type().to_string());
+            data.size = Some(bytes.len() as u64);
+            data.width = Some(variant_image.width());
+            data.height = Some(variant_image.height());
+            data.disk = disk.to_string();
+            if data.created_at.is_none() {
+                data.created_at = Some(now_date_time_str());
+            }
+            data.updated_at = Some(now_date_time_str());
+
+            disk_repository.put(&data.path, bytes).map_err(|e| {
+                self.log_error("generate", e.to_string(), FileVariantServiceError::Fail)
+            })?;
+
+            let result = if data.id == 0 {
+                file_variant_repository.insert(&vec![data], None)
+            } else {
+                file_variant_repository.update_by_id(data.id, &data, &None)
+            };
+            result.map_err(|e| {
+                self.log_error("generate", e.to_string(), FileVariantServiceError::Fail)
+            })?;
+        }
+
+        self.all_by_file_ids(&vec![file.id])
+    }
+}
+
+// Only the images which the image crate can decode get the variants, svg and others are skipped.
+fn get_image_format(mime: &Option<String>) -> Option<ImageFormat> {
+    let format = ImageFormat::from_mime_type(mime.as_ref()?)?;
+    if format.reading_enabled() {
+        Some(format)
+    } else {
+        None
+    }
+}
+
+fn get_variant_format(config: &FilesystemImageVariantConfig, source_format: ImageFormat) -> ImageFormat {
+    if config.format.eq("webp") || !source_format.writing_enabled() {
+        ImageFormat::WebP
+    } else {
+        source_format
+    }
+}
+
+// [hash]-[size].[extensions] -> [hash]-[size]-[name].[extension]
+fn make_variant_filename(filename: &str, name: &str, format: ImageFormat) -> String {
+    let stem = filename.split('.').next().unwrap_or(filename);
+    let mut result = stem.to_string();
+    result.push('-');
+    result.push_str(name);
+    if let Some(ext) = format.extensions_str().first() {
+        result.push('.');
+        result.push_str(ext);
+    }
+    result
+}
+
+// The images are not upscaled, a smaller image keeps its size.
+fn make_variant_image(image: &DynamicImage, config: &FilesystemImageVariantConfig) -> DynamicImage {
+    let is_larger = image.width() > config.width || image.height() > config.height;
+    if config.width == 0 || config.height == 0 || !is_larger {
+        return image.to_owned();
+    }
+    match config.fit.as_str() {
+        "crop" => image.resize_to_fill(config.width, config.height, FilterType::Lanczos3),
+        "resize" => image.resize(config.width, config.height, FilterType::Lanczos3),
+        _ => image.to_owned(),
+    }
+}
+
+// The encoders accept only some color types: jpeg is saved without the alpha channel, webp in 8 bits.
+fn encode_variant_image(image: &DynamicImage, format: ImageFormat) -> image::ImageResult<Vec<u8>> {
+    let image = match format {
+        ImageFormat::Jpeg => DynamicImage::ImageRgb8(image.to_rgb8()),
+        ImageFormat::WebP if image.color().has_alpha() => DynamicImage::ImageRgba8(image.to_rgba8()),
+        ImageFormat::WebP => DynamicImage::ImageRgb8(image.to_rgb8()),
+        _ => image.to_owned(),
+    };
+    let mut bytes: Vec<u8> = Vec::new();
+    image.write_to(&mut Cursor::new(&mut bytes), format)?;
+    Ok(bytes)
+}
+
+#[derive(Debug, Clone, Copy, Display, EnumString, PartialEq, Eq)]
+pub enum FileVariantServiceError {
+    NotFound,
+    Fail,
+}
+
+impl TranslatableError for FileVariantServiceError {
+    fn translate(&self, lang: &str, translator_service: &TranslatorService) -> String {
+        match self {
+            Self::NotFound => {
+                translator_service.translate(lang, "error.FileVariantServiceError.NotFound")
+            }
+            _ => translator_service.translate(lang, "error.FileVariantServiceError.Fail"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::RgbaImage;
+
+    fn make_config(fit: &str, width: u32, height: u32, format: &str) -> FilesystemImageVariantConfig {
+        FilesystemImageVariantConfig {
+            name: "thumb".to_string(),
+            fit: fit.to_string(),
+            width,
+            height,
+            format: format.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_make_variant_image() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::file_variant::tests::test_make_variant_image
+        let image = DynamicImage::ImageRgba8(RgbaImage::new(400, 200));
+
+        let variant = make_variant_image(&image, &make_config("crop", 100, 100, "webp"));
+        assert_eq!((variant.width(), variant.height()), (100, 100));
+
+        let variant = make_variant_image(&image, &make_config("resize", 100, 100, "webp"));
+        assert_eq!((variant.width(), variant.height()), (100, 50));
+
+        let variant = make_variant_image(&image, &make_config("resize", 800, 800, "webp"));
+        assert_eq!((variant.width(), variant.height()), (400, 200));
+
+        let variant = make_variant_image(&image, &make_config("none", 0, 0, "webp"));
+        assert_eq!((variant.width(), variant.height()), (400, 200));
+    }
+
+    #[test]
+    fn test_encode_variant_image() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::file_variant::tests::test_encode_variant_image
+        let image = DynamicImage::ImageRgba8(RgbaImage::new(20, 10));
+
+        for format in [ImageFormat::WebP, ImageFormat::Jpeg, ImageFormat::Png] {
+            let bytes = encode_variant_image(&image, format).unwrap();
+            let decoded = image::load_from_memory_with_format(&bytes, format).unwrap();
+            assert_eq!((decoded.width(), decoded.height()), (20, 10));
+        }
+
+        let config = make_config("resize", 10, 10, "original");
+        assert_eq!(get_variant_format(&config, ImageFormat::Png), ImageFormat::Png);
+        let config = make_config("resize", 10, 10, "webp");
+        assert_eq!(get_variant_format(&config, ImageFormat::Png), ImageFormat::WebP);
+
+        assert_eq!(
+            make_variant_filename("abc-123.jpg", "thumb", ImageFormat::WebP),
+            "abc-123-thumb.webp"
+        );
+    }
+}
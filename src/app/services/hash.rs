@@ -4,29 +4,52 @@ use sha2::{Digest, Sha256};
 use std::io::{Cursor, Read};
 use actix_web::web::Data;
 use strum_macros::{Display, EnumString};
+use subtle::ConstantTimeEq;
+
+pub const PASSWORD_HASH_ALGORITHM: &'static str = "argon2id";
+// The argon2 version 1.3, the only version of the libsodium.
+const PASSWORD_HASH_VERSION: u32 = 19;
+const PASSWORD_HASH_LEN: usize = 32;
 
 #[derive(Debug)]
 pub struct HashService {
-    argon2id_salt: [u8; 16]
+    // The salts of the hashes made before the PHC strings, they are only verified and rehashed.
+    // The salt was derived from APP_KEY, so the previous keys are kept after the rotation.
+    legacy_argon2id_salts: Vec<[u8; 16]>,
+    argon2id_opslimit: u32,
+    argon2id_memlimit: u32,
 }
 
 impl HashService {
     pub fn new(config: Data<Config>) -> Self {
+        let config = config.get_ref();
         Self {
-            argon2id_salt: Self::gen_argon2id_salt(config.get_ref())
+            legacy_argon2id_salts: Self::gen_legacy_argon2id_salts(config),
+            argon2id_opslimit: config.hash.argon2id_opslimit,
+            argon2id_memlimit: Self::round_memlimit(config.hash.argon2id_memlimit),
+        }
+    }
+
+    // The PHC string keeps the memory in KiB, so the limit in bytes is rounded down to KiB as argon2 does,
+    // otherwise the hashes made by the limit which is not a multiple of KiB would be rehashed on every login.
+    fn round_memlimit(memlimit: u32) -> u32 {
+        memlimit / 1024 * 1024
+    }
+
+    fn gen_legacy_argon2id_salts(config: &Config) -> Vec<[u8; 16]> {
+        let mut salts = vec![Self::gen_legacy_argon2id_salt(&config.app.key)];
+        for key in &config.app.previous_keys {
+            salts.push(Self::gen_legacy_argon2id_salt(key));
         }
+        salts
     }
 
-    fn gen_argon2id_salt(config: &Config) -> [u8; 16] {
-        let mut app_key = config.app.key.to_owned().into_bytes();
+    fn gen_legacy_argon2id_salt(key: &str) -> [u8; 16] {
+        let mut app_key = key.to_owned().into_bytes();
         app_key.resize(sodoken::argon2::ARGON2_ID_SALTBYTES, 0);
 
         let mut salt = [0; sodoken::argon2::ARGON2_ID_SALTBYTES];
-        sodoken::random::randombytes_buf(&mut salt).unwrap();
-        for i in 0..sodoken::argon2::ARGON2_ID_SALTBYTES {
-            let v = app_key.get(i).unwrap();
-            salt[i] = v.to_owned();
-        }
+        salt.copy_from_slice(&app_key[..sodoken::argon2::ARGON2_ID_SALTBYTES]);
         salt
     }
 
@@ -79,34 +102,170 @@ impl HashService {
         Ok(result)
     }
 
+    // Verify the password in constant time, both the PHC strings and the legacy hex hashes are supported.
     pub fn verify_password(&self, password: &str, hash: &str) -> Result<bool, HashServiceError> {
-        let new_hash = self.hash_password(password)?;
-        Ok(new_hash.eq(hash))
+        if hash.starts_with('$') {
+            let phc = self.parse_password_hash(hash)?;
+            let actual = self.argon2id(
+                password,
+                &phc.salt,
+                phc.opslimit,
+                phc.memlimit,
+                phc.hash.len(),
+            )?;
+            return Ok(actual.ct_eq(&phc.hash).into());
+        }
+
+        let expected = hex::decode(hash).map_err(|e| {
+            log::error!("HashService::verify_password - {e}");
+            HashServiceError::PasswordHashInvalid
+        })?;
+        for salt in &self.legacy_argon2id_salts {
+            let actual = self.argon2id(
+                password,
+                salt,
+                sodoken::argon2::ARGON2_ID_OPSLIMIT_INTERACTIVE,
+                sodoken::argon2::ARGON2_ID_MEMLIMIT_INTERACTIVE,
+                expected.len(),
+            )?;
+            if bool::from(actual.ct_eq(&expected)) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
     }
 
+    // The hash with the random salt in the PHC string format:
+    // $argon2id$v=19$m=[memory in KiB],t=[iterations],p=1$[salt]$[hash]
     pub fn hash_password(&self, password: &str) -> Result<String, HashServiceError> {
-        let mut hash = <sodoken::SizedLockedArray<64>>::new().unwrap();
+        let mut salt = [0; sodoken::argon2::ARGON2_ID_SALTBYTES];
+        sodoken::random::randombytes_buf(&mut salt).map_err(|e| {
+            log::error!("HashService::hash_password - {e}");
+            HashServiceError::HashPasswordFail
+        })?;
+
+        let hash = self.argon2id(
+            password,
+            &salt,
+            self.argon2id_opslimit,
+            self.argon2id_memlimit,
+            PASSWORD_HASH_LEN,
+        )?;
+
+        Ok(format!(
+            "${}$v={}$m={},t={},p=1${}${}",
+            PASSWORD_HASH_ALGORITHM,
+            PASSWORD_HASH_VERSION,
+            self.argon2id_memlimit / 1024,
+            self.argon2id_opslimit,
+            self.to_phc_base64(&salt)?,
+            self.to_phc_base64(&hash)?,
+        ))
+    }
+
+    // The legacy hashes and the hashes with the other cost are made again on the next login.
+    pub fn needs_rehash(&self, hash: &str) -> bool {
+        match self.parse_password_hash(hash) {
+            Ok(phc) => {
+                phc.opslimit != self.argon2id_opslimit
+                    || phc.memlimit != self.argon2id_memlimit
+                    || phc.hash.len() != PASSWORD_HASH_LEN
+            }
+            Err(_) => true,
+        }
+    }
 
+    fn parse_password_hash(&self, hash: &str) -> Result<PasswordHash, HashServiceError> {
+        let parts: Vec<&str> = hash.split('$').collect();
+        if parts.len() != 6 || !parts[0].is_empty() || parts[1] != PASSWORD_HASH_ALGORITHM {
+            return Err(HashServiceError::PasswordHashInvalid);
+        }
+        if parts[2] != format!("v={}", PASSWORD_HASH_VERSION) {
+            return Err(HashServiceError::PasswordHashInvalid);
+        }
+
+        let mut memlimit: Option<u32> = None;
+        let mut opslimit: Option<u32> = None;
+        for param in parts[3].split(',') {
+            let (key, value) = param
+                .split_once('=')
+                .ok_or(HashServiceError::PasswordHashInvalid)?;
+            let value = value
+                .parse::<u32>()
+                .map_err(|_| HashServiceError::PasswordHashInvalid)?;
+            match key {
+                "m" => memlimit = Some(value.checked_mul(1024).ok_or(HashServiceError::PasswordHashInvalid)?),
+                "t" => opslimit = Some(value),
+                "p" if value == 1 => {}
+                _ => return Err(HashServiceError::PasswordHashInvalid),
+            }
+        }
+
+        let salt = self.phc_base64_to_end(parts[4])?;
+        let salt: [u8; sodoken::argon2::ARGON2_ID_SALTBYTES] = salt
+            .try_into()
+            .map_err(|_| HashServiceError::PasswordHashInvalid)?;
+
+        Ok(PasswordHash {
+            opslimit: opslimit.ok_or(HashServiceError::PasswordHashInvalid)?,
+            memlimit: memlimit.ok_or(HashServiceError::PasswordHashInvalid)?,
+            salt,
+            hash: self.phc_base64_to_end(parts[5])?,
+        })
+    }
+
+    fn argon2id(
+        &self,
+        password: &str,
+        salt: &[u8; sodoken::argon2::ARGON2_ID_SALTBYTES],
+        opslimit: u32,
+        memlimit: u32,
+        len: usize,
+    ) -> Result<Vec<u8>, HashServiceError> {
+        let mut hash = vec![0; len];
         sodoken::argon2::blocking_argon2id(
-            &mut *hash.lock(),
+            &mut hash,
             password.as_bytes(),
-            &self.argon2id_salt,
-            sodoken::argon2::ARGON2_ID_OPSLIMIT_INTERACTIVE,
-            sodoken::argon2::ARGON2_ID_MEMLIMIT_INTERACTIVE,
+            salt,
+            opslimit,
+            memlimit,
         )
             .map_err(|e| {
-                log::error!("HashService::hash_password - {e}");
-                HashServiceError::Fail
+                log::error!("HashService::argon2id - {e}");
+                HashServiceError::HashPasswordFail
             })?;
+        Ok(hash)
+    }
+
+    // The PHC strings use the base64 without the padding.
+    fn to_phc_base64(&self, value: &[u8]) -> Result<String, HashServiceError> {
+        Ok(self.to_base64(value)?.trim_end_matches('=').to_string())
+    }
 
-        let h = *hash.lock();
-        Ok(hex::encode(h))
+    fn phc_base64_to_end(&self, value: &str) -> Result<Vec<u8>, HashServiceError> {
+        let mut base64 = value.to_string();
+        while !base64.len().is_multiple_of(4) {
+            base64.push('=');
+        }
+        let result = self.base64_to_end(&base64)?;
+        if result.is_empty() {
+            return Err(HashServiceError::PasswordHashInvalid);
+        }
+        Ok(result)
     }
 }
 
+struct PasswordHash {
+    opslimit: u32,
+    memlimit: u32,
+    salt: [u8; sodoken::argon2::ARGON2_ID_SALTBYTES],
+    hash: Vec<u8>,
+}
+
 #[derive(Debug, Clone, Copy, Display, EnumString)]
 pub enum HashServiceError {
     HashPasswordFail,
+    PasswordHashInvalid,
     Fail,
 }
 
@@ -128,7 +287,9 @@ mod tests {
         let password2 = "password123".to_string();
         let password_hash1 = hash.hash_password(&password1).unwrap();
         let password_hash2 = hash.hash_password(&password2).unwrap();
-        assert_eq!(password_hash1, password_hash2);
+        assert_ne!(password_hash1, password_hash2);
+        assert!(password_hash1.starts_with("$argon2id$v=19$"));
+        assert!(!hash.needs_rehash(&password_hash1));
     }
 
     #[bench]
@@ -154,6 +315,79 @@ mod tests {
         assert!(!hash.verify_password(&password2, &password_hash).unwrap());
     }
 
+    fn make_hash_service() -> HashService {
+        HashService {
+            legacy_argon2id_salts: vec![
+                [1; sodoken::argon2::ARGON2_ID_SALTBYTES],
+                [2; sodoken::argon2::ARGON2_ID_SALTBYTES],
+            ],
+            argon2id_opslimit: sodoken::argon2::ARGON2_ID_OPSLIMIT_MIN,
+            argon2id_memlimit: sodoken::argon2::ARGON2_ID_MEMLIMIT_MIN,
+        }
+    }
+
+    #[test]
+    fn verify_legacy_password() {
+        let hash = make_hash_service();
+
+        let password = "password123".to_string();
+        let legacy_hash = hash
+            .argon2id(
+                &password,
+                &hash.legacy_argon2id_salts[1],
+                sodoken::argon2::ARGON2_ID_OPSLIMIT_INTERACTIVE,
+                sodoken::argon2::ARGON2_ID_MEMLIMIT_INTERACTIVE,
+                64,
+            )
+            .unwrap();
+        let legacy_hash = hex::encode(legacy_hash);
+
+        assert!(hash.verify_password(&password, &legacy_hash).unwrap());
+        assert!(!hash.verify_password("password", &legacy_hash).unwrap());
+        assert!(hash.needs_rehash(&legacy_hash));
+    }
+
+    #[test]
+    fn needs_rehash() {
+        let hash = make_hash_service();
+
+        let password_hash = hash.hash_password("password123").unwrap();
+        assert!(!hash.needs_rehash(&password_hash));
+        assert!(hash.verify_password("password123", &password_hash).unwrap());
+
+        let stronger_hash = HashService {
+            argon2id_opslimit: hash.argon2id_opslimit + 1,
+            ..make_hash_service()
+        };
+        assert!(stronger_hash.needs_rehash(&password_hash));
+        assert!(stronger_hash.verify_password("password123", &password_hash).unwrap());
+
+        let uneven_hash = HashService {
+            argon2id_memlimit: HashService::round_memlimit(hash.argon2id_memlimit + 100),
+            ..make_hash_service()
+        };
+        assert_eq!(uneven_hash.argon2id_memlimit, hash.argon2id_memlimit);
+        let password_hash = uneven_hash.hash_password("password123").unwrap();
+        assert!(!uneven_hash.needs_rehash(&password_hash));
+    }
+
+    #[test]
+    fn parse_password_hash() {
+        let hash = make_hash_service();
+
+        let password_hash = hash.hash_password("password123").unwrap();
+        let phc = hash.parse_password_hash(&password_hash).unwrap();
+        assert_eq!(phc.opslimit, hash.argon2id_opslimit);
+        assert_eq!(phc.memlimit, hash.argon2id_memlimit);
+        assert_eq!(phc.hash.len(), PASSWORD_HASH_LEN);
+
+        assert!(hash.parse_password_hash("$argon2i$v=19$m=8,t=1,p=1$AAAA$AAAA").is_err());
+        assert!(hash.parse_password_hash("$argon2id$v=16$m=8,t=1,p=1$AAAA$AAAA").is_err());
+        assert!(hash.parse_password_hash("$argon2id$v=19$m=8,t=1,p=2$AAAA$AAAA").is_err());
+        assert!(hash.parse_password_hash("$argon2id$v=19$m=8,t=1,p=1$AAAA").is_err());
+        assert!(hash.verify_password("password123", "$argon2id$v=19$m=8$$").is_err());
+    }
+
     #[bench]
     fn bench_verify_password(b: &mut Bencher) {
         // 65,405,926.30 ns/iter (+/- 3,679,559.61)
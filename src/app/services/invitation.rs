@@ -0,0 +1,360 @@
+use crate::helpers::{now_date_time_str, DATE_TIME_FORMAT};
+use crate::{
+    AppService, Config, CryptService, EmailAddress, EmailMessage, JobPayload, JobService, TemplateService,
+    TranslatableError, TranslatorService, User, UserColumn, UserService, UserServiceError,
+};
+use actix_web::web::Data;
+use actix_web::{error, Error};
+use chrono::{NaiveDateTime, Utc};
+use serde_json::json;
+use strum_macros::{Display, EnumString};
+
+pub const INVITATION_URL: &'static str = "/invitation";
+
+pub struct InvitationService {
+    config: Data<Config>,
+    user_service: Data<UserService>,
+    job_service: Data<JobService>,
+    app_service: Data<AppService>,
+    template_service: Data<TemplateService>,
+    translator_service: Data<TranslatorService>,
+    crypt_service: Data<CryptService>,
+}
+
+impl InvitationService {
+    pub fn new(
+        config: Data<Config>,
+        user_service: Data<UserService>,
+        job_service: Data<JobService>,
+        app_service: Data<AppService>,
+        template_service: Data<TemplateService>,
+        translator_service: Data<TranslatorService>,
+        crypt_service: Data<CryptService>,
+    ) -> Self {
+        Self {
+            config,
+            user_service,
+            job_service,
+            app_service,
+            template_service,
+            translator_service,
+            crypt_service,
+        }
+    }
+
+    pub fn get_service_name(&self) -> &str {
+        "InvitationService"
+    }
+
+    pub fn log_error(
+        &self,
+        method: &str,
+        error: String,
+        e: InvitationServiceError,
+    ) -> InvitationServiceError {
+        let service_name = self.get_service_name().to_string();
+        log::error!("{}::{} - {}", service_name, method, error);
+        e
+    }
+
+    /// The timestamp after which the link from the letter stops working.
+    pub fn get_expires_at(&self, user: &User) -> Option<i64> {
+        let invited_at = user.invited_at.as_ref()?;
+        let expires = self.config.get_ref().auth.invitation_expires;
+        invitation_expires_at(invited_at, expires)
+    }
+
+    pub fn is_expired(&self, user: &User) -> bool {
+        self.get_expires_at(user)
+            .map(|expires_at| expires_at < Utc::now().timestamp())
+            .unwrap_or(true)
+    }
+
+    pub fn all_pending(&self) -> Result<Vec<User>, InvitationServiceError> {
+        self.user_service
+            .get_ref()
+            .all_invited()
+            .map_err(|e| self.log_error("all_pending", e.to_string(), InvitationServiceError::Fail))
+    }
+
+    pub fn all_pending_throw_http(&self) -> Result<Vec<User>, Error> {
+        self.all_pending().map_err(|e| e.to_http())
+    }
+
+    /// The link is signed with the time of the invitation, so the resent invitation replaces
+    /// the previous link and the accepted or revoked one stops working.
+    pub fn make_url(&self, user: &User) -> Result<String, InvitationServiceError> {
+        let invited_at = user
+            .invited_at
+            .as_ref()
+            .ok_or(InvitationServiceError::NotInvited)?;
+        let signature = self
+            .crypt_service
+            .get_ref()
+            .sign(&invitation_data(user.id, &user.email, invited_at))
+            .map_err(|e| self.log_error("make_url", e.to_string(), InvitationServiceError::Fail))?;
+
+        let params = format!("{}?id={}&signature={}", INVITATION_URL, user.id, signature);
+        self.app_service
+            .get_ref()
+            .url()
+            .join(&params)
+            .map(|url| url.to_string())
+            .map_err(|e| self.log_error("make_url", e.to_string(), InvitationServiceError::Fail))
+    }
+
+    /// Starts a new invitation of the user and sends the letter, the resend works the same way.
+    pub fn invite(&self, user_id: u64, lang: &str) -> Result<User, InvitationServiceError> {
+        let user_service = self.user_service.get_ref();
+        let mut user = user_service
+            .first_by_id(user_id)
+            .map_err(|e| self.log_error("invite", e.to_string(), InvitationServiceError::Fail))?
+            .ok_or(InvitationServiceError::NotInvited)?;
+
+        user.invited_at = Some(now_date_time_str());
+        user_service
+            .update(&user, &Some(vec![UserColumn::InvitedAt]))
+            .map_err(|e| self.log_error("invite", e.to_string(), InvitationServiceError::Fail))?;
+
+        let lang = user.locale.to_owned().unwrap_or(lang.to_string());
+        self.send(&user, &lang)?;
+        Ok(user)
+    }
+
+    pub fn invite_throw_http(&self, user_id: u64, lang: &str) -> Result<User, Error> {
+        self.invite(user_id, lang).map_err(|e| e.to_http())
+    }
+
+    /// The pending invitation can be resent only, the users with the password log in themselves.
+    pub fn resend(&self, user: &User, lang: &str) -> Result<User, InvitationServiceError> {
+        if !user.is_invited() {
+            return Err(InvitationServiceError::NotInvited);
+        }
+        self.invite(user.id, lang)
+    }
+
+    pub fn resend_throw_http(&self, user: &User, lang: &str) -> Result<User, Error> {
+        self.resend(user, lang).map_err(|e| e.to_http())
+    }
+
+    /// The link stops working, the user stays without the password.
+    pub fn revoke(&self, user: &User) -> Result<(), InvitationServiceError> {
+        if !user.is_invited() {
+            return Err(InvitationServiceError::NotInvited);
+        }
+        let mut data = User::default();
+        data.id = user.id;
+        data.invited_at = None;
+        self.user_service
+            .get_ref()
+            .update(&data, &Some(vec![UserColumn::InvitedAt]))
+            .map_err(|e| self.log_error("revoke", e.to_string(), InvitationServiceError::Fail))
+    }
+
+    pub fn revoke_throw_http(&self, user: &User) -> Result<(), Error> {
+        self.revoke(user).map_err(|e| e.to_http())
+    }
+
+    fn send(&self, user: &User, lang: &str) -> Result<(), InvitationServiceError> {
+        let app_service = self.app_service.get_ref();
+        let translator_service = self.translator_service.get_ref();
+
+        let site_domain = app_service
+            .url()
+            .domain()
+            .unwrap_or("localhost")
+            .to_string();
+        let logo_src = app_service
+            .url()
+            .join("/svg/logo.svg")
+            .map_err(|e| self.log_error("send", e.to_string(), InvitationServiceError::Fail))?
+            .to_string();
+        let button_href = self.make_url(user)?;
+
+        let ctx = json!({
+            "title": translator_service.translate(lang, "mail.invitation.title"),
+            "description": translator_service.translate(lang, "mail.invitation.description"),
+            "lang": lang.to_owned(),
+            "site_name": translator_service.translate(lang, "mail.invitation.site_name"),
+            "site_url": app_service.url().to_string(),
+            "site_domain": site_domain,
+            "logo_src": logo_src,
+            "header": translator_service.translate(lang, "mail.invitation.header"),
+            "button_label": translator_service.translate(lang, "mail.invitation.button"),
+            "button_href": button_href.to_owned(),
+        });
+        let html_body = self
+            .template_service
+            .get_ref()
+            .render("emails/auth/invitation.hbs", &ctx)
+            .map_err(|e| self.log_error("send", e.to_string(), InvitationServiceError::Fail))?;
+
+        let message = EmailMessage {
+            from: None,
+            reply_to: None,
+            to: EmailAddress {
+                name: None,
+                email: user.email.to_owned(),
+            },
+            subject: translator_service.translate(lang, "mail.invitation.subject"),
+            html_body: Some(html_body),
+            text_body: button_href,
+        };
+        self.job_service
+            .get_ref()
+            .dispatch(JobPayload::SendEmail(message))
+            .map_err(|e| self.log_error("send", e.to_string(), InvitationServiceError::Fail))?;
+        Ok(())
+    }
+
+    /// Checks the link from the letter.
+    pub fn verify(&self, user_id: u64, signature: &str) -> Result<User, InvitationServiceError> {
+        let user = self
+            .user_service
+            .get_ref()
+            .first_by_id(user_id)
+            .map_err(|e| self.log_error("verify", e.to_string(), InvitationServiceError::Fail))?
+            .ok_or(InvitationServiceError::InvalidSignature)?;
+        let invited_at = user
+            .invited_at
+            .as_ref()
+            .ok_or(InvitationServiceError::InvalidSignature)?;
+
+        let is_valid = self
+            .crypt_service
+            .get_ref()
+            .verify_signature(&invitation_data(user.id, &user.email, invited_at), signature)
+            .map_err(|e| self.log_error("verify", e.to_string(), InvitationServiceError::Fail))?;
+        if !is_valid {
+            return Err(InvitationServiceError::InvalidSignature);
+        }
+
+        if self.is_expired(&user) {
+            return Err(InvitationServiceError::Expired);
+        }
+
+        Ok(user)
+    }
+
+    /// Sets the password and the profile fields chosen by the invitee, the link can not be used again.
+    pub fn accept(
+        &self,
+        user_id: u64,
+        signature: &str,
+        password: &str,
+        surname: &Option<String>,
+        name: &Option<String>,
+        patronymic: &Option<String>,
+    ) -> Result<User, InvitationServiceError> {
+        let mut user = self.verify(user_id, signature)?;
+        let user_service = self.user_service.get_ref();
+
+        user_service
+            .update_password_by_id(user.id, password)
+            .map_err(|e| match e {
+                UserServiceError::PasswordHashFail => InvitationServiceError::PasswordHashFail,
+                _ => self.log_error("accept", e.to_string(), InvitationServiceError::Fail),
+            })?;
+
+        user.surname = surname.to_owned();
+        user.name = name.to_owned();
+        user.patronymic = patronymic.to_owned();
+        user.invited_at = None;
+        // The letter reached the invitee, so the email is confirmed.
+        if !user.is_email_verified() {
+            user.email_verified_at = Some(now_date_time_str());
+        }
+        let columns = vec![
+            UserColumn::Surname,
+            UserColumn::Name,
+            UserColumn::Patronymic,
+            UserColumn::InvitedAt,
+            UserColumn::EmailVerifiedAt,
+        ];
+        user_service
+            .update(&user, &Some(columns))
+            .map_err(|e| self.log_error("accept", e.to_string(), InvitationServiceError::Fail))?;
+
+        Ok(user)
+    }
+}
+
+/// The signed data of the link: the user id, the email and the time of the invitation.
+pub fn invitation_data(user_id: u64, email: &str, invited_at: &str) -> String {
+    format!("invitation|{}|{}|{}", user_id, email, invited_at)
+}
+
+pub fn invitation_expires_at(invited_at: &str, expires: i64) -> Option<i64> {
+    NaiveDateTime::parse_from_str(invited_at, DATE_TIME_FORMAT)
+        .ok()
+        .map(|invited_at| invited_at.and_utc().timestamp() + expires)
+}
+
+#[derive(Debug, Clone, Copy, Display, EnumString, PartialEq, Eq)]
+pub enum InvitationServiceError {
+    NotInvited,
+    Expired,
+    InvalidSignature,
+    PasswordHashFail,
+    Fail,
+}
+
+impl InvitationServiceError {
+    pub fn to_http(&self) -> Error {
+        match self {
+            Self::NotInvited => error::ErrorConflict(""),
+            Self::Expired | Self::InvalidSignature => error::ErrorForbidden(""),
+            _ => error::ErrorInternalServerError(""),
+        }
+    }
+}
+
+impl TranslatableError for InvitationServiceError {
+    fn translate(&self, lang: &str, translator_service: &TranslatorService) -> String {
+        match self {
+            Self::NotInvited => {
+                translator_service.translate(lang, "error.InvitationServiceError.NotInvited")
+            }
+            Self::Expired => {
+                translator_service.translate(lang, "error.InvitationServiceError.Expired")
+            }
+            Self::InvalidSignature => {
+                translator_service.translate(lang, "error.InvitationServiceError.InvalidSignature")
+            }
+            Self::PasswordHashFail => {
+                translator_service.translate(lang, "error.InvitationServiceError.PasswordHashFail")
+            }
+            _ => translator_service.translate(lang, "error.InvitationServiceError.Fail"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invitation_data() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::invitation::tests::test_invitation_data
+        let invited_at = "2025-01-01 10:00:00";
+        // The format is kept, so the links sent before stay valid.
+        assert_eq!(
+            invitation_data(1, "user@example.com", invited_at),
+            "invitation|1|user@example.com|2025-01-01 10:00:00"
+        );
+        // The resent invitation replaces the previous link.
+        assert_ne!(
+            invitation_data(1, "user@example.com", invited_at),
+            invitation_data(1, "user@example.com", "2025-01-01 10:00:01")
+        );
+    }
+
+    #[test]
+    fn test_invitation_expires_at() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::invitation::tests::test_invitation_expires_at
+        assert_eq!(invitation_expires_at("1970-01-01 00:00:00", 60), Some(60));
+        assert_eq!(invitation_expires_at("2025-01-01 00:00:00", 86400), Some(1735776000));
+        assert_eq!(invitation_expires_at("", 60), None);
+        assert_eq!(invitation_expires_at("2025-01-01", 60), None);
+    }
+}
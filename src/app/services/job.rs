@@ -0,0 +1,357 @@
+use crate::helpers::now_date_time_str;
+use crate::{
+    Job, JobPayload, KeyValueService, RandomService, TranslatableError, TranslatorService,
+};
+use actix_web::web::Data;
+use actix_web::{error, Error};
+use chrono::Utc;
+use redis::Script;
+use strum_macros::{Display, EnumString};
+
+pub const JOB_DEFAULT_MAX_ATTEMPTS: u32 = 5;
+// 5 minutes for the worker to complete or release the job, then the job is returned to the queue.
+pub const JOB_RESERVE_TIMEOUT: i64 = 300;
+const JOB_BACKOFF_BASE: i64 = 10;
+const JOB_BACKOFF_MAX: i64 = 3600;
+const JOB_ID_LEN: usize = 32;
+
+const JOB_QUEUE_KEY: &'static str = "jobs.queue";
+const JOB_DELAYED_KEY: &'static str = "jobs.delayed";
+const JOB_RESERVED_KEY: &'static str = "jobs.reserved";
+const JOB_FAILED_KEY: &'static str = "jobs.failed";
+
+// KEYS[1] - the queue, KEYS[2] - the reserved set, ARGV[1] - the reservation deadline.
+const JOB_RESERVE_SCRIPT: &'static str = r"
+local id = redis.call('LPOP', KEYS[1])
+if id then
+    redis.call('ZADD', KEYS[2], ARGV[1], id)
+end
+return id
+";
+
+// KEYS[1] - the delayed or the reserved set, KEYS[2] - the queue, ARGV[1] - the current time.
+const JOB_MIGRATE_SCRIPT: &'static str = r"
+local ids = redis.call('ZRANGEBYSCORE', KEYS[1], '-inf', ARGV[1])
+for _, id in ipairs(ids) do
+    redis.call('ZREM', KEYS[1], id)
+    redis.call('RPUSH', KEYS[2], id)
+end
+return #ids
+";
+
+pub struct JobService {
+    key_value_service: Data<KeyValueService>,
+    random_service: Data<RandomService>,
+}
+
+impl JobService {
+    pub fn new(key_value_service: Data<KeyValueService>, random_service: Data<RandomService>) -> Self {
+        Self {
+            key_value_service,
+            random_service,
+        }
+    }
+
+    pub fn get_service_name(&self) -> &str {
+        "JobService"
+    }
+
+    pub fn log_error(&self, method: &str, error: String, e: JobServiceError) -> JobServiceError {
+        let service_name = self.get_service_name().to_string();
+        log::error!("{}::{} - {}", service_name, method, error);
+        e
+    }
+
+    fn make_key(&self, id: &str) -> String {
+        format!("jobs.job.{}", id)
+    }
+
+    fn save(&self, job: &Job) -> Result<(), JobServiceError> {
+        self.key_value_service
+            .get_ref()
+            .set(self.make_key(&job.id), job)
+            .map_err(|e| self.log_error("save", e.to_string(), JobServiceError::Fail))
+    }
+
+    pub fn first(&self, id: &str) -> Result<Option<Job>, JobServiceError> {
+        self.key_value_service
+            .get_ref()
+            .get(self.make_key(id))
+            .map_err(|e| self.log_error("first", e.to_string(), JobServiceError::Fail))
+    }
+
+    pub fn dispatch(&self, payload: JobPayload) -> Result<Job, JobServiceError> {
+        self.dispatch_later(payload, 0)
+    }
+
+    pub fn dispatch_throw_http(&self, payload: JobPayload) -> Result<Job, Error> {
+        self.dispatch(payload).map_err(|e| e.to_http())
+    }
+
+    // The job is reserved not earlier than the delay in seconds.
+    pub fn dispatch_later(&self, payload: JobPayload, delay: i64) -> Result<Job, JobServiceError> {
+        let job = Job {
+            id: self.random_service.get_ref().str(JOB_ID_LEN),
+            payload,
+            attempts: 0,
+            max_attempts: JOB_DEFAULT_MAX_ATTEMPTS,
+            available_at: Utc::now().timestamp() + delay,
+            created_at: Some(now_date_time_str()),
+            failed_at: None,
+            error: None,
+        };
+        self.save(&job)?;
+
+        let key_value_service = self.key_value_service.get_ref();
+        if delay > 0 {
+            key_value_service
+                .zadd(JOB_DELAYED_KEY, &job.id, job.available_at)
+                .map_err(|e| self.log_error("dispatch_later", e.to_string(), JobServiceError::Fail))?;
+        } else {
+            key_value_service
+                .rpush(JOB_QUEUE_KEY, &job.id)
+                .map_err(|e| self.log_error("dispatch_later", e.to_string(), JobServiceError::Fail))?;
+        }
+
+        Ok(job)
+    }
+
+    // The job is dispatched at most once per period, the period is marked in the key-value store.
+    pub fn dispatch_once_per(
+        &self,
+        payload: JobPayload,
+        seconds: i64,
+    ) -> Result<Option<Job>, JobServiceError> {
+        let key_value_service = self.key_value_service.get_ref();
+        let key = format!("jobs.once.{}", payload);
+        let value: u64 = key_value_service
+            .incr(&key, 1)
+            .map_err(|e| self.log_error("dispatch_once_per", e.to_string(), JobServiceError::Fail))?;
+        if value > 1 {
+            return Ok(None);
+        }
+        key_value_service
+            .expire(&key, seconds)
+            .map_err(|e| self.log_error("dispatch_once_per", e.to_string(), JobServiceError::Fail))?;
+        self.dispatch(payload).map(Some)
+    }
+
+    // Move the due delayed jobs and the jobs of the dead workers back to the queue.
+    fn migrate(&self, from: &str, now: i64) -> Result<(), JobServiceError> {
+        let script = Script::new(JOB_MIGRATE_SCRIPT);
+        let now = now.to_string();
+        let _: u64 = self
+            .key_value_service
+            .get_ref()
+            .invoke_script(&script, &[from, JOB_QUEUE_KEY], &[&now])
+            .map_err(|e| self.log_error("migrate", e.to_string(), JobServiceError::Fail))?;
+        Ok(())
+    }
+
+    // Take the next job from the queue, the job must be completed or released before the reservation timeout.
+    pub fn reserve(&self) -> Result<Option<Job>, JobServiceError> {
+        let now = Utc::now().timestamp();
+        self.migrate(JOB_DELAYED_KEY, now)?;
+        self.migrate(JOB_RESERVED_KEY, now)?;
+
+        let script = Script::new(JOB_RESERVE_SCRIPT);
+        let deadline = (now + JOB_RESERVE_TIMEOUT).to_string();
+        let id: Option<String> = self
+            .key_value_service
+            .get_ref()
+            .invoke_script(&script, &[JOB_QUEUE_KEY, JOB_RESERVED_KEY], &[&deadline])
+            .map_err(|e| self.log_error("reserve", e.to_string(), JobServiceError::Fail))?;
+
+        let id = match id {
+            Some(id) => id,
+            None => return Ok(None),
+        };
+
+        let mut job = match self.first(&id)? {
+            Some(job) => job,
+            None => {
+                self.unreserve(&id)?;
+                return Ok(None);
+            }
+        };
+
+        job.attempts += 1;
+        self.save(&job)?;
+        Ok(Some(job))
+    }
+
+    fn unreserve(&self, id: &str) -> Result<(), JobServiceError> {
+        self.key_value_service
+            .get_ref()
+            .zrem(JOB_RESERVED_KEY, id)
+            .map_err(|e| self.log_error("unreserve", e.to_string(), JobServiceError::Fail))
+    }
+
+    pub fn complete(&self, job: &Job) -> Result<(), JobServiceError> {
+        self.unreserve(&job.id)?;
+        self.key_value_service
+            .get_ref()
+            .del(self.make_key(&job.id))
+            .map_err(|e| self.log_error("complete", e.to_string(), JobServiceError::Fail))
+    }
+
+    // Retry the job later with the backoff, the job is failed when the attempts are over.
+    pub fn release(&self, job: &mut Job, error: &str) -> Result<(), JobServiceError> {
+        if job.attempts >= job.max_attempts {
+            return self.fail(job, error);
+        }
+
+        job.error = Some(error.to_string());
+        job.available_at = Utc::now().timestamp() + get_backoff(job.attempts);
+        self.save(job)?;
+        self.unreserve(&job.id)?;
+        self.key_value_service
+            .get_ref()
+            .zadd(JOB_DELAYED_KEY, &job.id, job.available_at)
+            .map_err(|e| self.log_error("release", e.to_string(), JobServiceError::Fail))
+    }
+
+    pub fn fail(&self, job: &mut Job, error: &str) -> Result<(), JobServiceError> {
+        job.error = Some(error.to_string());
+        job.failed_at = Some(now_date_time_str());
+        self.save(job)?;
+        self.unreserve(&job.id)?;
+        self.key_value_service
+            .get_ref()
+            .rpush(JOB_FAILED_KEY, &job.id)
+            .map_err(|e| self.log_error("fail", e.to_string(), JobServiceError::Fail))
+    }
+
+    // The failed jobs from the newest.
+    pub fn failed(&self, offset: i64, limit: i64) -> Result<Vec<Job>, JobServiceError> {
+        let start = -(offset as isize) - (limit as isize);
+        let stop = -(offset as isize) - 1;
+        let ids: Vec<String> = self
+            .key_value_service
+            .get_ref()
+            .lrange(JOB_FAILED_KEY, start, stop)
+            .map_err(|e| self.log_error("failed", e.to_string(), JobServiceError::Fail))?;
+
+        let mut jobs: Vec<Job> = Vec::new();
+        for id in ids.iter().rev() {
+            if let Some(job) = self.first(id)? {
+                jobs.push(job);
+            }
+        }
+        Ok(jobs)
+    }
+
+    pub fn failed_throw_http(&self, offset: i64, limit: i64) -> Result<Vec<Job>, Error> {
+        self.failed(offset, limit).map_err(|e| e.to_http())
+    }
+
+    pub fn failed_count(&self) -> Result<u64, JobServiceError> {
+        self.key_value_service
+            .get_ref()
+            .llen(JOB_FAILED_KEY)
+            .map_err(|e| self.log_error("failed_count", e.to_string(), JobServiceError::Fail))
+    }
+
+    pub fn failed_count_throw_http(&self) -> Result<u64, Error> {
+        self.failed_count().map_err(|e| e.to_http())
+    }
+
+    // Return the failed job to the queue with the fresh attempts.
+    pub fn retry(&self, id: &str) -> Result<Job, JobServiceError> {
+        let mut job = self.first(id)?.ok_or(JobServiceError::NotFound)?;
+
+        let removed = self
+            .key_value_service
+            .get_ref()
+            .lrem(JOB_FAILED_KEY, 0, id)
+            .map_err(|e| self.log_error("retry", e.to_string(), JobServiceError::Fail))?;
+        if removed == 0 {
+            return Err(JobServiceError::NotFound);
+        }
+
+        job.attempts = 0;
+        job.available_at = Utc::now().timestamp();
+        job.failed_at = None;
+        job.error = None;
+        self.save(&job)?;
+        self.key_value_service
+            .get_ref()
+            .rpush(JOB_QUEUE_KEY, &job.id)
+            .map_err(|e| self.log_error("retry", e.to_string(), JobServiceError::Fail))?;
+
+        Ok(job)
+    }
+
+    pub fn retry_throw_http(&self, id: &str) -> Result<Job, Error> {
+        self.retry(id).map_err(|e| e.to_http())
+    }
+}
+
+// The delay in seconds before the next attempt: 10, 20, 40 ... up to 1 hour.
+pub fn get_backoff(attempts: u32) -> i64 {
+    let exp = attempts.max(1).saturating_sub(1).min(31);
+    JOB_BACKOFF_BASE
+        .saturating_mul(2i64.pow(exp))
+        .min(JOB_BACKOFF_MAX)
+}
+
+#[derive(Debug, Clone, Copy, Display, EnumString, PartialEq, Eq)]
+pub enum JobServiceError {
+    NotFound,
+    Fail,
+}
+
+impl JobServiceError {
+    pub fn to_http(&self) -> Error {
+        match self {
+            Self::NotFound => error::ErrorNotFound(""),
+            _ => error::ErrorInternalServerError(""),
+        }
+    }
+}
+
+impl TranslatableError for JobServiceError {
+    fn translate(&self, lang: &str, translator_service: &TranslatorService) -> String {
+        match self {
+            Self::NotFound => translator_service.translate(lang, "error.JobServiceError.NotFound"),
+            _ => translator_service.translate(lang, "error.JobServiceError.Fail"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::preparation;
+
+    #[test]
+    fn test_get_backoff() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::job::tests::test_get_backoff
+        assert_eq!(get_backoff(0), 10);
+        assert_eq!(get_backoff(1), 10);
+        assert_eq!(get_backoff(2), 20);
+        assert_eq!(get_backoff(3), 40);
+        assert_eq!(get_backoff(9), 2560);
+        assert_eq!(get_backoff(10), 3600);
+        assert_eq!(get_backoff(u32::MAX), 3600);
+    }
+
+    #[test]
+    fn test_release_and_retry() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::job::tests::test_release_and_retry
+        let (_, all_services) = preparation();
+        let job_service = all_services.job_service.get_ref();
+
+        let mut job = job_service.dispatch(JobPayload::CollectFileGarbage).unwrap();
+        job.attempts = job.max_attempts;
+        job_service.release(&mut job, "error").unwrap();
+        assert!(job_service.first(&job.id).unwrap().unwrap().failed_at.is_some());
+
+        let job = job_service.retry(&job.id).unwrap();
+        assert_eq!(job.attempts, 0);
+        assert!(job.failed_at.is_none());
+        assert_eq!(job_service.retry(&job.id).err(), Some(JobServiceError::NotFound));
+        job_service.complete(&job).unwrap();
+        assert!(job_service.first(&job.id).unwrap().is_none());
+    }
+}
@@ -1,7 +1,7 @@
 use crate::redis_connection::RedisPool;
 use actix_web::web::Data;
 use r2d2::PooledConnection;
-use redis::{Client, Commands, Expiry, FromRedisValue, RedisError, ToRedisArgs};
+use redis::{Client, Commands, Expiry, FromRedisValue, RedisError, Script, ToRedisArgs};
 use crate::AppError;
 
 #[derive(Debug, Clone)]
@@ -63,6 +63,10 @@ impl KeyValueService {
         self.get_connection()?.del(key)
     }
 
+    pub fn scan_match(&self, pattern: &str) -> Result<Vec<String>, AppError> {
+        self.get_connection()?.scan_match(pattern)
+    }
+
     pub fn incr<K: ToRedisArgs, D: ToRedisArgs, V: FromRedisValue>(
         &self,
         key: K,
@@ -77,6 +81,70 @@ impl KeyValueService {
     ) -> Result<V, AppError> {
         self.get_connection()?.ttl(key)
     }
+
+    pub fn sadd<K: ToRedisArgs, M: ToRedisArgs>(&self, key: K, member: M) -> Result<(), AppError> {
+        self.get_connection()?.sadd(key, member)
+    }
+
+    pub fn srem<K: ToRedisArgs, M: ToRedisArgs>(&self, key: K, member: M) -> Result<(), AppError> {
+        self.get_connection()?.srem(key, member)
+    }
+
+    pub fn smembers<K: ToRedisArgs, V: FromRedisValue>(
+        &self,
+        key: K,
+    ) -> Result<Vec<V>, AppError> {
+        self.get_connection()?.smembers(key)
+    }
+
+    pub fn rpush<K: ToRedisArgs, V: ToRedisArgs>(&self, key: K, value: V) -> Result<(), AppError> {
+        self.get_connection()?.rpush(key, value)
+    }
+
+    pub fn lrange<K: ToRedisArgs, V: FromRedisValue>(
+        &self,
+        key: K,
+        start: isize,
+        stop: isize,
+    ) -> Result<Vec<V>, AppError> {
+        self.get_connection()?.lrange(key, start, stop)
+    }
+
+    pub fn lrem<K: ToRedisArgs, V: ToRedisArgs>(
+        &self,
+        key: K,
+        count: isize,
+        value: V,
+    ) -> Result<u64, AppError> {
+        self.get_connection()?.lrem(key, count, value)
+    }
+
+    pub fn llen<K: ToRedisArgs>(&self, key: K) -> Result<u64, AppError> {
+        self.get_connection()?.llen(key)
+    }
+
+    pub fn zadd<K: ToRedisArgs, S: ToRedisArgs, M: ToRedisArgs>(
+        &self,
+        key: K,
+        member: M,
+        score: S,
+    ) -> Result<(), AppError> {
+        self.get_connection()?.zadd(key, member, score)
+    }
+
+    pub fn zrem<K: ToRedisArgs, M: ToRedisArgs>(&self, key: K, member: M) -> Result<(), AppError> {
+        self.get_connection()?.zrem(key, member)
+    }
+
+    // Run the Lua script, the commands of the script are executed atomically.
+    pub fn invoke_script<V: FromRedisValue>(
+        &self,
+        script: &Script,
+        keys: &[&str],
+        args: &[&str],
+    ) -> Result<V, AppError> {
+        self.get_connection()?.invoke_script(script, keys, args)
+    }
 }
 
 pub struct KeyValueServiceConnection {
@@ -175,6 +243,15 @@ impl KeyValueServiceConnection {
         })
     }
 
+    // The keys by the glob pattern, SCAN does not block the server as KEYS does.
+    pub fn scan_match(&mut self, pattern: &str) -> Result<Vec<String>, AppError> {
+        let keys = self.conn.scan_match(pattern).map_err(|e| {
+            log::error!("KeyValueService::scan_match - {e}");
+            AppError(Some(e.to_string()))
+        })?;
+        Ok(keys.collect())
+    }
+
     pub fn incr<K: ToRedisArgs, D: ToRedisArgs, V: FromRedisValue>(
         &mut self,
         key: K,
@@ -195,5 +272,120 @@ impl KeyValueServiceConnection {
             AppError(Some(e.to_string()))
         })
     }
-}
 
+    pub fn sadd<K: ToRedisArgs, M: ToRedisArgs>(
+        &mut self,
+        key: K,
+        member: M,
+    ) -> Result<(), AppError> {
+        self.conn.sadd(key, member).map_err(|e| {
+            log::error!("KeyValueService::sadd - {e}");
+            AppError(Some(e.to_string()))
+        })
+    }
+
+    pub fn srem<K: ToRedisArgs, M: ToRedisArgs>(
+        &mut self,
+        key: K,
+        member: M,
+    ) -> Result<(), AppError> {
+        self.conn.srem(key, member).map_err(|e| {
+            log::error!("KeyValueService::srem - {e}");
+            AppError(Some(e.to_string()))
+        })
+    }
+
+    pub fn smembers<K: ToRedisArgs, V: FromRedisValue>(
+        &mut self,
+        key: K,
+    ) -> Result<Vec<V>, AppError> {
+        self.conn.smembers(key).map_err(|e| {
+            log::error!("KeyValueService::smembers - {e}");
+            AppError(Some(e.to_string()))
+        })
+    }
+
+    pub fn rpush<K: ToRedisArgs, V: ToRedisArgs>(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> Result<(), AppError> {
+        self.conn.rpush(key, value).map_err(|e| {
+            log::error!("KeyValueService::rpush - {e}");
+            AppError(Some(e.to_string()))
+        })
+    }
+
+    pub fn lrange<K: ToRedisArgs, V: FromRedisValue>(
+        &mut self,
+        key: K,
+        start: isize,
+        stop: isize,
+    ) -> Result<Vec<V>, AppError> {
+        self.conn.lrange(key, start, stop).map_err(|e| {
+            log::error!("KeyValueService::lrange - {e}");
+            AppError(Some(e.to_string()))
+        })
+    }
+
+    pub fn lrem<K: ToRedisArgs, V: ToRedisArgs>(
+        &mut self,
+        key: K,
+        count: isize,
+        value: V,
+    ) -> Result<u64, AppError> {
+        self.conn.lrem(key, count, value).map_err(|e| {
+            log::error!("KeyValueService::lrem - {e}");
+            AppError(Some(e.to_string()))
+        })
+    }
+
+    pub fn llen<K: ToRedisArgs>(&mut self, key: K) -> Result<u64, AppError> {
+        self.conn.llen(key).map_err(|e| {
+            log::error!("KeyValueService::llen - {e}");
+            AppError(Some(e.to_string()))
+        })
+    }
+
+    pub fn zadd<K: ToRedisArgs, S: ToRedisArgs, M: ToRedisArgs>(
+        &mut self,
+        key: K,
+        member: M,
+        score: S,
+    ) -> Result<(), AppError> {
+        self.conn.zadd(key, member, score).map_err(|e| {
+            log::error!("KeyValueService::zadd - {e}");
+            AppError(Some(e.to_string()))
+        })
+    }
+
+    pub fn zrem<K: ToRedisArgs, M: ToRedisArgs>(
+        &mut self,
+        key: K,
+        member: M,
+    ) -> Result<(), AppError> {
+        self.conn.zrem(key, member).map_err(|e| {
+            log::error!("KeyValueService::zrem - {e}");
+            AppError(Some(e.to_string()))
+        })
+    }
+
+    pub fn invoke_script<V: FromRedisValue>(
+        &mut self,
+        script: &Script,
+        keys: &[&str],
+        args: &[&str],
+    ) -> Result<V, AppError> {
+        let mut invocation = script.prepare_invoke();
+        for key in keys {
+            invocation.key(*key);
+        }
+        for arg in args {
+            invocation.arg(*arg);
+        }
+        invocation.invoke(&mut *self.conn).map_err(|e| {
+            log::error!("KeyValueService::invoke_script - {e}");
+            AppError(Some(e.to_string()))
+        })
+    }
+}
@@ -0,0 +1,764 @@
+use crate::config::AuthLdapConfig;
+use crate::{Config, RoleService, TranslatableError, TranslatorService, User, UserColumn, UserService};
+use actix_web::web::Data;
+use ldap3::{ldap_escape, LdapConn, LdapConnSettings, Scope, SearchEntry};
+use std::collections::HashMap;
+use std::thread;
+use std::time::Duration;
+use strum_macros::{Display, EnumString};
+
+// https://datatracker.ietf.org/doc/html/rfc4511#section-4.1.9
+const LDAP_INVALID_CREDENTIALS: u32 = 49;
+
+pub struct LdapService {
+    config: Data<Config>,
+    user_service: Data<UserService>,
+    role_service: Data<RoleService>,
+}
+
+impl LdapService {
+    pub fn new(
+        config: Data<Config>,
+        user_service: Data<UserService>,
+        role_service: Data<RoleService>,
+    ) -> Self {
+        Self {
+            config,
+            user_service,
+            role_service,
+        }
+    }
+
+    pub fn get_service_name(&self) -> &str {
+        "LdapService"
+    }
+
+    pub fn log_error(&self, method: &str, error: String, e: LdapServiceError) -> LdapServiceError {
+        let service_name = self.get_service_name().to_string();
+        log::error!("{}::{} - {}", service_name, method, error);
+        e
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.get_ref().auth.ldap.enabled
+    }
+
+    /// Returns None when the login is unknown to the directory, so the local password can be checked.
+    pub fn login_by_password(
+        &self,
+        login: &str,
+        password: &str,
+    ) -> Result<Option<u64>, LdapServiceError> {
+        let config = &self.config.get_ref().auth.ldap;
+        let identity = Self::authenticate(config, login, password)
+            .map_err(|e| self.log_error("login_by_password", login.to_string(), e))?;
+
+        match identity {
+            Some(identity) => Ok(Some(self.provision(&identity)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Creates or updates the user by the directory entry and synchronizes the roles from the groups.
+    pub fn provision(&self, identity: &LdapIdentity) -> Result<u64, LdapServiceError> {
+        let config = &self.config.get_ref().auth.ldap;
+        let user_service = self.user_service.get_ref();
+
+        let user = user_service
+            .first_by_email(&identity.email)
+            .map_err(|e| self.log_error("provision", e.to_string(), LdapServiceError::Fail))?;
+        let mut user = match user {
+            Some(user) => user,
+            None => {
+                if !config.create_users {
+                    return Err(LdapServiceError::UserNotFound);
+                }
+                user_service
+                    .upsert(User::empty(identity.email.to_owned()), &None)
+                    .map_err(|e| self.log_error("provision", e.to_string(), LdapServiceError::Fail))?;
+                user_service
+                    .first_by_email(&identity.email)
+                    .map_err(|e| self.log_error("provision", e.to_string(), LdapServiceError::Fail))?
+                    .ok_or(LdapServiceError::Fail)?
+            }
+        };
+
+        // The local accounts are linked to the directory on the first login, except the disabled ones.
+        if user.is_disabled() && !user.is_ldap() {
+            return Err(LdapServiceError::UserNotFound);
+        }
+
+        self.update_user(config, &mut user, identity)?;
+        Ok(user.id)
+    }
+
+    fn update_user(
+        &self,
+        config: &AuthLdapConfig,
+        user: &mut User,
+        identity: &LdapIdentity,
+    ) -> Result<(), LdapServiceError> {
+        let mut columns: Vec<UserColumn> = Vec::new();
+
+        if !user.ldap_dn.as_ref().eq(&Some(&identity.dn)) {
+            user.ldap_dn = Some(identity.dn.to_owned());
+            columns.push(UserColumn::LdapDn);
+        }
+        if user.is_disabled() {
+            user.disabled_at = None;
+            columns.push(UserColumn::DisabledAt);
+        }
+        for (column, value, new_value) in [
+            (UserColumn::Surname, &mut user.surname, &identity.surname),
+            (UserColumn::Name, &mut user.name, &identity.name),
+            (UserColumn::Patronymic, &mut user.patronymic, &identity.patronymic),
+        ] {
+            if new_value.is_some() && !new_value.eq(value) {
+                *value = new_value.to_owned();
+                columns.push(column);
+            }
+        }
+
+        if config.roles.len() != 0 {
+            let roles_ids = self
+                .role_service
+                .get_ref()
+                .sync_mapped_roles_ids(&user.roles_ids, &config.roles, &identity.groups)
+                .map_err(|e| self.log_error("update_user", e.to_string(), LdapServiceError::Fail))?;
+            if !roles_ids.eq(user.roles_ids.as_ref().unwrap_or(&Vec::new())) {
+                user.roles_ids = Some(roles_ids);
+                columns.push(UserColumn::RolesIds);
+            }
+        }
+
+        if columns.len() == 0 {
+            return Ok(());
+        }
+        self.user_service
+            .get_ref()
+            .update(user, &Some(columns))
+            .map_err(|e| self.log_error("update_user", e.to_string(), LdapServiceError::Fail))
+    }
+
+    /// Updates the users provisioned from the directory and disables the ones removed from it.
+    /// Nothing is disabled when the directory is not available, the users found by several entries are skipped.
+    pub fn sync(&self) -> Result<LdapSyncResult, LdapServiceError> {
+        let config = &self.config.get_ref().auth.ldap;
+        let user_service = self.user_service.get_ref();
+        let mut result = LdapSyncResult::default();
+
+        let users = user_service
+            .all_ldap()
+            .map_err(|e| self.log_error("sync", e.to_string(), LdapServiceError::Fail))?;
+        let emails: Vec<String> = users.iter().map(|user| user.email.to_owned()).collect();
+        let identities = Self::find_identities(config, &emails)
+            .map_err(|e| self.log_error("sync", e.to_string(), e))?;
+
+        for (mut user, identity) in users.into_iter().zip(identities) {
+            match identity {
+                LdapSearchResult::Found(identity) => {
+                    self.update_user(config, &mut user, &identity)?;
+                    result.updated += 1;
+                }
+                LdapSearchResult::NotFound if !user.is_disabled() => {
+                    user_service
+                        .set_disabled_by_id(user.id, true)
+                        .map_err(|e| self.log_error("sync", e.to_string(), LdapServiceError::Fail))?;
+                    result.disabled += 1;
+                }
+                LdapSearchResult::NotFound => {}
+                LdapSearchResult::Ambiguous => {
+                    log::error!("LdapService::sync - several entries found by {}, the user is skipped", user.email);
+                    result.skipped += 1;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// The blocking client of ldap3 starts its own runtime, which can not be nested in the runtime
+    /// of the web server, so the connection lives in a separate thread.
+    fn with_connection<T: Send, F: FnOnce(&mut LdapConn) -> Result<T, LdapServiceError> + Send>(
+        config: &AuthLdapConfig,
+        f: F,
+    ) -> Result<T, LdapServiceError> {
+        thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    let settings = LdapConnSettings::new()
+                        .set_conn_timeout(Duration::from_secs(config.timeout))
+                        .set_starttls(config.starttls);
+                    let mut conn = LdapConn::with_settings(settings, &config.url)
+                        .map_err(|_| LdapServiceError::ConnectionFail)?;
+                    conn.with_timeout(Duration::from_secs(config.timeout));
+                    let result = f(&mut conn);
+                    let _ = conn.unbind();
+                    result
+                })
+                .join()
+                .unwrap_or(Err(LdapServiceError::Fail))
+        })
+    }
+
+    fn bind_service_account(config: &AuthLdapConfig, conn: &mut LdapConn) -> Result<(), LdapServiceError> {
+        if config.bind_dn.is_empty() {
+            return Ok(());
+        }
+        conn.simple_bind(&config.bind_dn, &config.bind_password)
+            .and_then(|result| result.success())
+            .map_err(|_| LdapServiceError::ConnectionFail)?;
+        Ok(())
+    }
+
+    fn search_identity(
+        config: &AuthLdapConfig,
+        conn: &mut LdapConn,
+        login: &str,
+    ) -> Result<LdapSearchResult, LdapServiceError> {
+        let filter = Self::make_filter(&config.user_filter, login);
+        let attributes = vec![
+            config.email_attribute.as_str(),
+            config.surname_attribute.as_str(),
+            config.name_attribute.as_str(),
+            config.patronymic_attribute.as_str(),
+            config.groups_attribute.as_str(),
+        ];
+        let (entries, _) = conn
+            .search(&config.base_dn, Scope::Subtree, &filter, attributes)
+            .and_then(|result| result.success())
+            .map_err(|_| LdapServiceError::SearchFail)?;
+
+        // The ambiguous login is not resolved, the filter has to point to one entry.
+        if entries.len() > 1 {
+            return Ok(LdapSearchResult::Ambiguous);
+        }
+        let Some(entry) = entries.into_iter().next() else {
+            return Ok(LdapSearchResult::NotFound);
+        };
+        let entry = SearchEntry::construct(entry);
+        match Self::entry_to_identity(config, &entry.dn, &entry.attrs, login) {
+            Some(identity) => Ok(LdapSearchResult::Found(identity)),
+            None => Ok(LdapSearchResult::NotFound),
+        }
+    }
+
+    /// Finds the user by the service account and checks the password by the bind with the user DN.
+    pub fn authenticate(
+        config: &AuthLdapConfig,
+        login: &str,
+        password: &str,
+    ) -> Result<Option<LdapIdentity>, LdapServiceError> {
+        // The empty password is the unauthenticated bind which succeeds on many servers.
+        if login.is_empty() || password.is_empty() {
+            return Err(LdapServiceError::InvalidCredentials);
+        }
+
+        Self::with_connection(config, |conn| {
+            Self::bind_service_account(config, conn)?;
+            let LdapSearchResult::Found(identity) = Self::search_identity(config, conn, login)? else {
+                return Ok(None);
+            };
+
+            let result = conn
+                .simple_bind(&identity.dn, password)
+                .map_err(|_| LdapServiceError::ConnectionFail)?;
+            match result.rc {
+                0 => Ok(Some(identity)),
+                LDAP_INVALID_CREDENTIALS => Err(LdapServiceError::InvalidCredentials),
+                _ => Err(LdapServiceError::ConnectionFail),
+            }
+        })
+    }
+
+    /// Looks up the entries by the logins through one connection, in the same order.
+    pub fn find_identities(
+        config: &AuthLdapConfig,
+        logins: &Vec<String>,
+    ) -> Result<Vec<LdapSearchResult>, LdapServiceError> {
+        if logins.len() == 0 {
+            return Ok(Vec::new());
+        }
+        Self::with_connection(config, |conn| {
+            Self::bind_service_account(config, conn)?;
+            let mut identities: Vec<LdapSearchResult> = Vec::new();
+            for login in logins {
+                identities.push(Self::search_identity(config, conn, login)?);
+            }
+            Ok(identities)
+        })
+    }
+
+    pub fn make_filter(template: &str, login: &str) -> String {
+        template.replace("{login}", &ldap_escape(login))
+    }
+
+    pub fn entry_to_identity(
+        config: &AuthLdapConfig,
+        dn: &str,
+        attrs: &HashMap<String, Vec<String>>,
+        login: &str,
+    ) -> Option<LdapIdentity> {
+        // The attribute names are case-insensitive in LDAP.
+        let get_values = |name: &str| -> Vec<String> {
+            attrs
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, values)| values.to_owned())
+                .unwrap_or_default()
+        };
+        let get_value = |name: &str| -> Option<String> {
+            get_values(name)
+                .into_iter()
+                .map(|value| value.trim().to_string())
+                .find(|value| !value.is_empty())
+        };
+
+        // The login is the email in the panel, so the entry without the email is found by the email.
+        let email = get_value(&config.email_attribute).unwrap_or(login.trim().to_string());
+        if email.is_empty() {
+            return None;
+        }
+
+        Some(LdapIdentity {
+            dn: dn.to_string(),
+            email,
+            surname: get_value(&config.surname_attribute),
+            name: get_value(&config.name_attribute),
+            patronymic: get_value(&config.patronymic_attribute),
+            groups: get_values(&config.groups_attribute),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LdapIdentity {
+    pub dn: String,
+    pub email: String,
+    pub surname: Option<String>,
+    pub name: Option<String>,
+    pub patronymic: Option<String>,
+    pub groups: Vec<String>,
+}
+
+// The login may match several entries when the filter is too wide, such a login is not resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LdapSearchResult {
+    Found(LdapIdentity),
+    NotFound,
+    Ambiguous,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LdapSyncResult {
+    pub updated: u64,
+    pub disabled: u64,
+    // The users found by several entries, they are left as is.
+    pub skipped: u64,
+}
+
+#[derive(Debug, Clone, Copy, Display, EnumString, PartialEq, Eq)]
+pub enum LdapServiceError {
+    ConnectionFail,
+    SearchFail,
+    InvalidCredentials,
+    UserNotFound,
+    Fail,
+}
+
+impl TranslatableError for LdapServiceError {
+    fn translate(&self, lang: &str, translator_service: &TranslatorService) -> String {
+        match self {
+            Self::ConnectionFail => {
+                translator_service.translate(lang, "error.LdapServiceError.ConnectionFail")
+            }
+            Self::InvalidCredentials => {
+                translator_service.translate(lang, "error.LdapServiceError.InvalidCredentials")
+            }
+            Self::UserNotFound => {
+                translator_service.translate(lang, "error.LdapServiceError.UserNotFound")
+            }
+            _ => translator_service.translate(lang, "error.LdapServiceError.Fail"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::{Arc, Mutex};
+
+    const BASE_DN: &'static str = "ou=people,dc=example,dc=org";
+    const ADMINS_DN: &'static str = "cn=admins,ou=groups,dc=example,dc=org";
+
+    struct MockEntry {
+        dn: String,
+        password: String,
+        attrs: Vec<(String, Vec<String>)>,
+    }
+
+    type MockDirectory = Arc<Mutex<Vec<MockEntry>>>;
+
+    // The BER element: the tag and the content.
+    struct Ber {
+        tag: u8,
+        content: Vec<u8>,
+    }
+
+    fn read_ber(data: &[u8]) -> Option<(Ber, &[u8])> {
+        let tag = *data.first()?;
+        let first = *data.get(1)? as usize;
+        let (length, offset) = if first & 0x80 == 0 {
+            (first, 2)
+        } else {
+            let count = first & 0x7f;
+            let mut length = 0;
+            for i in 0..count {
+                length = (length << 8) | *data.get(2 + i)? as usize;
+            }
+            (length, 2 + count)
+        };
+        let content = data.get(offset..offset + length)?.to_vec();
+        Some((Ber { tag, content }, &data[offset + length..]))
+    }
+
+    fn read_all_ber(mut data: &[u8]) -> Vec<Ber> {
+        let mut items = Vec::new();
+        while let Some((item, rest)) = read_ber(data) {
+            items.push(item);
+            data = rest;
+        }
+        items
+    }
+
+    fn write_ber(tag: u8, content: &[u8]) -> Vec<u8> {
+        let mut result = vec![tag];
+        let length = content.len();
+        if length < 0x80 {
+            result.push(length as u8);
+        } else {
+            let bytes: Vec<u8> = length.to_be_bytes().into_iter().skip_while(|b| *b == 0).collect();
+            result.push(0x80 | bytes.len() as u8);
+            result.extend(bytes);
+        }
+        result.extend_from_slice(content);
+        result
+    }
+
+    fn write_int(tag: u8, value: u32) -> Vec<u8> {
+        let mut bytes: Vec<u8> = value.to_be_bytes().into_iter().skip_while(|b| *b == 0).collect();
+        if bytes.first().map(|b| b & 0x80 != 0).unwrap_or(true) {
+            bytes.insert(0, 0);
+        }
+        write_ber(tag, &bytes)
+    }
+
+    fn read_int(content: &[u8]) -> u32 {
+        content.iter().fold(0, |value, b| (value << 8) | *b as u32)
+    }
+
+    fn write_result(msg_id: u32, tag: u8, rc: u32) -> Vec<u8> {
+        let mut result = write_int(0x0a, rc);
+        result.extend(write_ber(0x04, b""));
+        result.extend(write_ber(0x04, b""));
+        let mut message = write_int(0x02, msg_id);
+        message.extend(write_ber(tag, &result));
+        write_ber(0x30, &message)
+    }
+
+    fn get_attr<'a>(entry: &'a MockEntry, name: &str) -> Option<&'a Vec<String>> {
+        entry
+            .attrs
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, values)| values)
+    }
+
+    // The and, or, not, equality and presence filters.
+    fn is_match(entry: &MockEntry, filter: &Ber) -> bool {
+        match filter.tag {
+            0xa0 => read_all_ber(&filter.content).iter().all(|f| is_match(entry, f)),
+            0xa1 => read_all_ber(&filter.content).iter().any(|f| is_match(entry, f)),
+            0xa2 => !read_all_ber(&filter.content).iter().all(|f| is_match(entry, f)),
+            0xa3 => {
+                let items = read_all_ber(&filter.content);
+                let name = String::from_utf8_lossy(&items[0].content).to_string();
+                let value = String::from_utf8_lossy(&items[1].content).to_string();
+                get_attr(entry, &name)
+                    .map(|values| values.iter().any(|v| v.eq_ignore_ascii_case(&value)))
+                    .unwrap_or(false)
+            }
+            0x87 => get_attr(entry, &String::from_utf8_lossy(&filter.content)).is_some(),
+            _ => false,
+        }
+    }
+
+    fn handle_mock_request(directory: &MockDirectory, message: &Ber) -> Option<Vec<u8>> {
+        let items = read_all_ber(&message.content);
+        let msg_id = read_int(&items[0].content);
+        let op = &items[1];
+
+        match op.tag {
+            // BindRequest
+            0x60 => {
+                let fields = read_all_ber(&op.content);
+                let dn = String::from_utf8_lossy(&fields[1].content).to_string();
+                let password = String::from_utf8_lossy(&fields[2].content).to_string();
+                let directory = directory.lock().unwrap();
+                let is_valid = (dn.is_empty() && password.is_empty())
+                    || directory
+                        .iter()
+                        .any(|entry| entry.dn.eq_ignore_ascii_case(&dn) && entry.password.eq(&password));
+                Some(write_result(msg_id, 0x61, if is_valid { 0 } else { 49 }))
+            }
+            // SearchRequest
+            0x63 => {
+                let fields = read_all_ber(&op.content);
+                let base = String::from_utf8_lossy(&fields[0].content).to_lowercase();
+                let filter = &fields[6];
+                let mut response = Vec::new();
+                for entry in directory.lock().unwrap().iter() {
+                    if !entry.dn.to_lowercase().ends_with(&base) || !is_match(entry, filter) {
+                        continue;
+                    }
+                    let mut attrs = Vec::new();
+                    for (name, values) in &entry.attrs {
+                        let mut vals = Vec::new();
+                        for value in values {
+                            vals.extend(write_ber(0x04, value.as_bytes()));
+                        }
+                        let mut attr = write_ber(0x04, name.as_bytes());
+                        attr.extend(write_ber(0x31, &vals));
+                        attrs.extend(write_ber(0x30, &attr));
+                    }
+                    let mut result_entry = write_ber(0x04, entry.dn.as_bytes());
+                    result_entry.extend(write_ber(0x30, &attrs));
+                    let mut message = write_int(0x02, msg_id);
+                    message.extend(write_ber(0x64, &result_entry));
+                    response.extend(write_ber(0x30, &message));
+                }
+                response.extend(write_result(msg_id, 0x65, 0));
+                Some(response)
+            }
+            // UnbindRequest
+            _ => None,
+        }
+    }
+
+    fn handle_mock_connection(directory: MockDirectory, mut stream: TcpStream) {
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut chunk = [0; 4096];
+        loop {
+            while let Some((message, rest)) = read_ber(&buffer) {
+                buffer = rest.to_vec();
+                match handle_mock_request(&directory, &message) {
+                    Some(response) => stream.write_all(&response).unwrap(),
+                    None => return,
+                }
+            }
+            match stream.read(&mut chunk) {
+                Ok(0) | Err(_) => return,
+                Ok(size) => buffer.extend_from_slice(&chunk[..size]),
+            }
+        }
+    }
+
+    /// The stand-in of the OpenLDAP server, it supports the simple bind and the search.
+    fn start_mock_ldap() -> (String, MockDirectory) {
+        let attrs = |email: &str, name: &str, groups: Vec<&str>| -> Vec<(String, Vec<String>)> {
+            vec![
+                ("objectClass".to_string(), vec!["top".to_string(), "inetOrgPerson".to_string()]),
+                ("mail".to_string(), vec![email.to_string()]),
+                ("givenName".to_string(), vec![name.to_string()]),
+                ("sn".to_string(), vec!["Smith".to_string()]),
+                ("memberOf".to_string(), groups.iter().map(|g| g.to_string()).collect()),
+            ]
+        };
+        let directory: MockDirectory = Arc::new(Mutex::new(vec![
+            MockEntry {
+                dn: "cn=reader,dc=example,dc=org".to_string(),
+                password: "reader".to_string(),
+                attrs: vec![],
+            },
+            MockEntry {
+                dn: format!("uid=john,{}", BASE_DN),
+                password: "john_password".to_string(),
+                attrs: attrs("john@example.com", "John", vec![ADMINS_DN]),
+            },
+            MockEntry {
+                dn: format!("uid=jane,{}", BASE_DN),
+                password: "jane_password".to_string(),
+                attrs: attrs("jane@example.com", "Jane", vec![]),
+            },
+        ]));
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("ldap://{}", listener.local_addr().unwrap());
+        let directory_ = directory.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { break };
+                let directory = directory_.clone();
+                thread::spawn(move || handle_mock_connection(directory, stream));
+            }
+        });
+
+        (url, directory)
+    }
+
+    fn make_config(url: &str) -> AuthLdapConfig {
+        AuthLdapConfig {
+            enabled: true,
+            url: url.to_string(),
+            starttls: false,
+            timeout: 5,
+            bind_dn: "cn=reader,dc=example,dc=org".to_string(),
+            bind_password: "reader".to_string(),
+            base_dn: BASE_DN.to_string(),
+            user_filter: "(&(objectClass=inetOrgPerson)(mail={login}))".to_string(),
+            email_attribute: "mail".to_string(),
+            surname_attribute: "sn".to_string(),
+            name_attribute: "givenName".to_string(),
+            patronymic_attribute: "middleName".to_string(),
+            groups_attribute: "memberOf".to_string(),
+            roles: vec![(ADMINS_DN.to_string(), "admin".to_string())],
+            create_users: true,
+            sync_period: 3600,
+        }
+    }
+
+    #[test]
+    fn make_filter() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::ldap::tests::make_filter
+        assert_eq!(
+            LdapService::make_filter("(mail={login})", "user@example.com"),
+            "(mail=user@example.com)"
+        );
+        assert_eq!(
+            LdapService::make_filter("(mail={login})", "*)(uid=*"),
+            "(mail=\\2a\\29\\28uid=\\2a)"
+        );
+    }
+
+    #[test]
+    fn entry_to_identity() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::ldap::tests::entry_to_identity
+        let config = make_config("ldap://127.0.0.1");
+        let mut attrs: HashMap<String, Vec<String>> = HashMap::new();
+        attrs.insert("MAIL".to_string(), vec!["john@example.com".to_string()]);
+        attrs.insert("givenname".to_string(), vec!["John".to_string()]);
+        attrs.insert("sn".to_string(), vec!["".to_string(), "Smith".to_string()]);
+        attrs.insert("memberOf".to_string(), vec![ADMINS_DN.to_string()]);
+
+        let identity = LdapService::entry_to_identity(&config, "uid=john", &attrs, "login@example.com").unwrap();
+        assert_eq!(
+            identity,
+            LdapIdentity {
+                dn: "uid=john".to_string(),
+                email: "john@example.com".to_string(),
+                surname: Some("Smith".to_string()),
+                name: Some("John".to_string()),
+                patronymic: None,
+                groups: vec![ADMINS_DN.to_string()],
+            }
+        );
+
+        attrs.remove("MAIL");
+        let identity = LdapService::entry_to_identity(&config, "uid=john", &attrs, "login@example.com").unwrap();
+        assert_eq!(identity.email, "login@example.com");
+    }
+
+    #[test]
+    fn authenticate() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::ldap::tests::authenticate
+        let (url, _) = start_mock_ldap();
+        let config = make_config(&url);
+
+        let identity = LdapService::authenticate(&config, "john@example.com", "john_password")
+            .unwrap()
+            .unwrap();
+        assert_eq!(identity.dn, format!("uid=john,{}", BASE_DN));
+        assert_eq!(identity.name, Some("John".to_string()));
+        assert_eq!(identity.surname, Some("Smith".to_string()));
+        assert_eq!(
+            RoleService::map_groups_to_codes(&identity.groups, &config.roles),
+            vec!["admin".to_string()]
+        );
+
+        assert_eq!(
+            LdapService::authenticate(&config, "john@example.com", "jane_password"),
+            Err(LdapServiceError::InvalidCredentials)
+        );
+        assert_eq!(
+            LdapService::authenticate(&config, "john@example.com", ""),
+            Err(LdapServiceError::InvalidCredentials)
+        );
+        assert_eq!(LdapService::authenticate(&config, "local@example.com", "password"), Ok(None));
+        assert_eq!(LdapService::authenticate(&config, "*", "john_password"), Ok(None));
+
+        let mut wrong_config = make_config(&url);
+        wrong_config.bind_password = "wrong".to_string();
+        assert_eq!(
+            LdapService::authenticate(&wrong_config, "john@example.com", "john_password"),
+            Err(LdapServiceError::ConnectionFail)
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let closed_url = format!("ldap://{}", listener.local_addr().unwrap());
+        drop(listener);
+        assert_eq!(
+            LdapService::authenticate(&make_config(&closed_url), "john@example.com", "john_password"),
+            Err(LdapServiceError::ConnectionFail)
+        );
+    }
+
+    #[actix_rt::test]
+    async fn authenticate_in_runtime() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::ldap::tests::authenticate_in_runtime
+        let (url, _) = start_mock_ldap();
+        let config = make_config(&url);
+        assert!(LdapService::authenticate(&config, "jane@example.com", "jane_password")
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn find_identities() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::ldap::tests::find_identities
+        let (url, directory) = start_mock_ldap();
+        let config = make_config(&url);
+        let logins = vec!["john@example.com".to_string(), "jane@example.com".to_string()];
+
+        let identities = LdapService::find_identities(&config, &logins).unwrap();
+        assert!(identities
+            .iter()
+            .all(|identity| matches!(identity, LdapSearchResult::Found(_))));
+
+        // The login matching several entries is not resolved, so the sync does not disable the user.
+        directory.lock().unwrap().push(MockEntry {
+            dn: format!("uid=jane2,{}", BASE_DN),
+            password: "jane2_password".to_string(),
+            attrs: vec![
+                ("objectClass".to_string(), vec!["inetOrgPerson".to_string()]),
+                ("mail".to_string(), vec!["jane@example.com".to_string()]),
+            ],
+        });
+        let identities = LdapService::find_identities(&config, &logins).unwrap();
+        assert!(matches!(identities[0], LdapSearchResult::Found(_)));
+        assert_eq!(identities[1], LdapSearchResult::Ambiguous);
+        assert_eq!(LdapService::authenticate(&config, "jane@example.com", "jane_password"), Ok(None));
+
+        // The removed user is not found by the sync.
+        directory
+            .lock()
+            .unwrap()
+            .retain(|entry| !entry.dn.starts_with("uid=jane"));
+        let identities = LdapService::find_identities(&config, &logins).unwrap();
+        assert!(matches!(identities[0], LdapSearchResult::Found(_)));
+        assert_eq!(identities[1], LdapSearchResult::NotFound);
+
+        assert_eq!(LdapService::find_identities(&config, &Vec::new()).unwrap().len(), 0);
+    }
+}
@@ -0,0 +1,438 @@
+use crate::helpers::now_date_time_str;
+use crate::{
+    AppService, Config, EmailAddress, EmailMessage, HashService, JobPayload, JobService,
+    KeyValueService, TemplateService, TranslatableError, TranslatorService, User, UserService,
+};
+use actix_web::http::header::USER_AGENT;
+use actix_web::web::Data;
+use actix_web::{error, Error, HttpRequest};
+use serde_json::json;
+use std::collections::HashMap;
+use strum_macros::{Display, EnumString};
+
+const FAILURES_KEY: &'static str = "login_security:failures:";
+const DELAY_KEY: &'static str = "login_security:delay:";
+const LOCK_KEY: &'static str = "login_security:lock:";
+const DEVICES_KEY: &'static str = "login_security:devices:";
+// The devices of the set can not be ordered by the last login, so the full set starts over.
+const DEVICES_MAX_COUNT: usize = 100;
+
+/// The failed logins are counted per account, so the password of one account can not be guessed
+/// from many addresses. The owner is notified about the lockout and the logins from new devices.
+pub struct LoginSecurityService {
+    config: Data<Config>,
+    key_value_service: Data<KeyValueService>,
+    hash_service: Data<HashService>,
+    user_service: Data<UserService>,
+    job_service: Data<JobService>,
+    app_service: Data<AppService>,
+    template_service: Data<TemplateService>,
+    translator_service: Data<TranslatorService>,
+}
+
+impl LoginSecurityService {
+    pub fn new(
+        config: Data<Config>,
+        key_value_service: Data<KeyValueService>,
+        hash_service: Data<HashService>,
+        user_service: Data<UserService>,
+        job_service: Data<JobService>,
+        app_service: Data<AppService>,
+        template_service: Data<TemplateService>,
+        translator_service: Data<TranslatorService>,
+    ) -> Self {
+        Self {
+            config,
+            key_value_service,
+            hash_service,
+            user_service,
+            job_service,
+            app_service,
+            template_service,
+            translator_service,
+        }
+    }
+
+    pub fn get_service_name(&self) -> &str {
+        "LoginSecurityService"
+    }
+
+    pub fn log_error(
+        &self,
+        method: &str,
+        error: String,
+        e: LoginSecurityServiceError,
+    ) -> LoginSecurityServiceError {
+        let service_name = self.get_service_name().to_string();
+        log::error!("{}::{} - {}", service_name, method, error);
+        e
+    }
+
+    /// The email is not stored in the key value storage as is.
+    fn make_account_key(&self, prefix: &str, email: &str) -> String {
+        let mut key = prefix.to_string();
+        key.push_str(&self.hash_service.get_ref().hash(email.trim().to_lowercase()));
+        key
+    }
+
+    fn ttl(&self, key: &str) -> Result<u64, LoginSecurityServiceError> {
+        let value: i64 = self
+            .key_value_service
+            .get_ref()
+            .ttl(key)
+            .map_err(|e| self.log_error("ttl", e.to_string(), LoginSecurityServiceError::Fail))?;
+        if value <= 0 {
+            return Ok(0);
+        }
+        Ok(value as u64)
+    }
+
+    /// The seconds until the account is unlocked, 0 when it is not locked.
+    pub fn locked_ttl(&self, email: &str) -> Result<u64, LoginSecurityServiceError> {
+        self.ttl(&self.make_account_key(LOCK_KEY, email))
+    }
+
+    pub fn is_locked(&self, email: &str) -> Result<bool, LoginSecurityServiceError> {
+        Ok(self.locked_ttl(email)? > 0)
+    }
+
+    /// The next attempt is allowed after the progressive delay and not during the lockout.
+    pub fn check(&self, email: &str) -> Result<(), LoginSecurityServiceError> {
+        if self.is_locked(email)? {
+            return Err(LoginSecurityServiceError::Locked);
+        }
+        if self.ttl(&self.make_account_key(DELAY_KEY, email))? > 0 {
+            return Err(LoginSecurityServiceError::Delayed);
+        }
+        Ok(())
+    }
+
+    /// The reason why the attempt is rejected with the time to wait, None when it is allowed.
+    pub fn check_message(
+        &self,
+        translator_service: &TranslatorService,
+        lang: &str,
+        email: &str,
+    ) -> Result<Option<String>, LoginSecurityServiceError> {
+        let (key, ttl) = match self.check(email) {
+            Ok(()) => return Ok(None),
+            Err(LoginSecurityServiceError::Locked) => (
+                "error.LoginSecurityServiceError.LockedFor",
+                self.locked_ttl(email)?,
+            ),
+            Err(LoginSecurityServiceError::Delayed) => (
+                "validation.rate_limit",
+                self.ttl(&self.make_account_key(DELAY_KEY, email))?,
+            ),
+            Err(e) => return Err(e),
+        };
+
+        let unit = translator_service.choices(lang, "unit.after_seconds", ttl as i64, None);
+        let seconds = ttl.to_string();
+        let mut vars = HashMap::new();
+        vars.insert("seconds", seconds.as_str());
+        vars.insert("unit", unit.as_str());
+        Ok(Some(translator_service.variables(lang, key, &vars)))
+    }
+
+    pub fn check_message_throw_http(
+        &self,
+        translator_service: &TranslatorService,
+        lang: &str,
+        email: &str,
+    ) -> Result<Option<String>, Error> {
+        self.check_message(translator_service, lang, email)
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+
+    /// Counts the failure, sets the delay of the next attempt and locks the account after too many failures.
+    pub fn register_failure(&self, email: &str) -> Result<(), LoginSecurityServiceError> {
+        let config = &self.config.get_ref().auth.lockout;
+        let key_value_service = self.key_value_service.get_ref();
+        let failures_key = self.make_account_key(FAILURES_KEY, email);
+
+        let failures: u64 = key_value_service.incr(&failures_key, 1).map_err(|e| {
+            self.log_error("register_failure", e.to_string(), LoginSecurityServiceError::Fail)
+        })?;
+        if failures == 1 {
+            key_value_service
+                .expire(&failures_key, config.window as i64)
+                .map_err(|e| {
+                    self.log_error("register_failure", e.to_string(), LoginSecurityServiceError::Fail)
+                })?;
+        }
+
+        if config.max_failures > 0 && failures >= config.max_failures {
+            key_value_service
+                .set_ex(self.make_account_key(LOCK_KEY, email), now_date_time_str(), config.lockout_time)
+                .map_err(|e| {
+                    self.log_error("register_failure", e.to_string(), LoginSecurityServiceError::Fail)
+                })?;
+            self.clear_failures(email)?;
+            // The account stays locked even if the owner was not notified.
+            if let Err(e) = self.notify_locked(email) {
+                self.log_error("register_failure", e.to_string(), e);
+            }
+            return Ok(());
+        }
+
+        let delay = progressive_delay(failures, config.delay_after, config.delay, config.max_delay);
+        if delay > 0 {
+            key_value_service
+                .set_ex(self.make_account_key(DELAY_KEY, email), failures, delay)
+                .map_err(|e| {
+                    self.log_error("register_failure", e.to_string(), LoginSecurityServiceError::Fail)
+                })?;
+        }
+
+        Ok(())
+    }
+
+    pub fn register_failure_throw_http(&self, email: &str) -> Result<(), Error> {
+        self.register_failure(email)
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+
+    fn clear_failures(&self, email: &str) -> Result<(), LoginSecurityServiceError> {
+        let key_value_service = self.key_value_service.get_ref();
+        for prefix in [FAILURES_KEY, DELAY_KEY] {
+            key_value_service
+                .del(self.make_account_key(prefix, email))
+                .map_err(|e| {
+                    self.log_error("clear_failures", e.to_string(), LoginSecurityServiceError::Fail)
+                })?;
+        }
+        Ok(())
+    }
+
+    /// The password was correct, the counter starts over.
+    pub fn register_success(&self, email: &str) -> Result<(), LoginSecurityServiceError> {
+        self.clear_failures(email)
+    }
+
+    pub fn register_success_throw_http(&self, email: &str) -> Result<(), Error> {
+        self.register_success(email)
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+
+    /// The admin action, the owner can log in again at once.
+    pub fn unlock(&self, email: &str) -> Result<(), LoginSecurityServiceError> {
+        self.clear_failures(email)?;
+        self.key_value_service
+            .get_ref()
+            .del(self.make_account_key(LOCK_KEY, email))
+            .map_err(|e| self.log_error("unlock", e.to_string(), LoginSecurityServiceError::Fail))
+    }
+
+    pub fn unlock_throw_http(&self, email: &str) -> Result<(), Error> {
+        self.unlock(email)
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+
+    /// Remembers the IP address and the browser of the session, the owner gets a letter when the pair
+    /// is new. The very first login of the user is remembered silently.
+    pub fn register_login(&self, req: &HttpRequest, user_id: u64) -> Result<(), LoginSecurityServiceError> {
+        let config = &self.config.get_ref().auth.lockout;
+        if !config.notify_new_login {
+            return Ok(());
+        }
+        let key_value_service = self.key_value_service.get_ref();
+
+        let ip = req
+            .peer_addr()
+            .map(|addr| addr.ip().to_string())
+            .unwrap_or("".to_string());
+        let user_agent = req
+            .headers()
+            .get(USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+            .to_string();
+        let device = self
+            .hash_service
+            .get_ref()
+            .hash(format!("{}|{}", &ip, &user_agent));
+
+        let devices_key = format!("{}{}", DEVICES_KEY, user_id);
+        let devices: Vec<String> = key_value_service.smembers(&devices_key).map_err(|e| {
+            self.log_error("register_login", e.to_string(), LoginSecurityServiceError::Fail)
+        })?;
+        if !devices.contains(&device) {
+            if devices.len() >= DEVICES_MAX_COUNT {
+                key_value_service.del(&devices_key).map_err(|e| {
+                    self.log_error("register_login", e.to_string(), LoginSecurityServiceError::Fail)
+                })?;
+            }
+            key_value_service.sadd(&devices_key, &device).map_err(|e| {
+                self.log_error("register_login", e.to_string(), LoginSecurityServiceError::Fail)
+            })?;
+        }
+        // Each login keeps the known devices for one more period.
+        key_value_service
+            .expire(&devices_key, config.devices_ttl as i64)
+            .map_err(|e| {
+                self.log_error("register_login", e.to_string(), LoginSecurityServiceError::Fail)
+            })?;
+        if devices.contains(&device) || devices.is_empty() {
+            return Ok(());
+        }
+
+        let user = self
+            .user_service
+            .get_ref()
+            .first_by_id(user_id)
+            .map_err(|e| self.log_error("register_login", e.to_string(), LoginSecurityServiceError::Fail))?;
+        if let Some(user) = user {
+            let lang = self.get_user_lang(&user);
+            let translator_service = self.translator_service.get_ref();
+            let mut vars = HashMap::new();
+            vars.insert("ip", ip.as_str());
+            vars.insert("user_agent", user_agent.as_str());
+            let date_time = now_date_time_str();
+            vars.insert("date_time", date_time.as_str());
+            let description =
+                translator_service.variables(&lang, "mail.new_login.description", &vars);
+            self.send(&user, &lang, "new_login", description)?;
+        }
+        Ok(())
+    }
+
+    /// The notification must not break the login.
+    pub fn register_login_silently(&self, req: &HttpRequest, user_id: u64) {
+        let _ = self.register_login(req, user_id);
+    }
+
+    fn notify_locked(&self, email: &str) -> Result<(), LoginSecurityServiceError> {
+        let user = self
+            .user_service
+            .get_ref()
+            .first_by_email(email)
+            .map_err(|e| self.log_error("notify_locked", e.to_string(), LoginSecurityServiceError::Fail))?;
+        // Nobody is notified about the attempts to guess an unknown email.
+        let Some(user) = user else {
+            return Ok(());
+        };
+
+        let lang = self.get_user_lang(&user);
+        let translator_service = self.translator_service.get_ref();
+        let minutes = (self.config.get_ref().auth.lockout.lockout_time / 60).max(1).to_string();
+        let mut vars = HashMap::new();
+        vars.insert("minutes", minutes.as_str());
+        let description = translator_service.variables(&lang, "mail.account_locked.description", &vars);
+        self.send(&user, &lang, "account_locked", description)
+    }
+
+    fn get_user_lang(&self, user: &User) -> String {
+        user.locale
+            .to_owned()
+            .unwrap_or(self.config.get_ref().app.locale.to_owned())
+    }
+
+    fn send(
+        &self,
+        user: &User,
+        lang: &str,
+        mail_key: &str,
+        description: String,
+    ) -> Result<(), LoginSecurityServiceError> {
+        let app_service = self.app_service.get_ref();
+        let translator_service = self.translator_service.get_ref();
+
+        let site_domain = app_service
+            .url()
+            .domain()
+            .unwrap_or("localhost")
+            .to_string();
+        let logo_src = app_service
+            .url()
+            .join("/svg/logo.svg")
+            .map_err(|e| self.log_error("send", e.to_string(), LoginSecurityServiceError::Fail))?
+            .to_string();
+        let button_href = app_service
+            .url()
+            .join("/reset-password")
+            .map_err(|e| self.log_error("send", e.to_string(), LoginSecurityServiceError::Fail))?
+            .to_string();
+
+        let ctx = json!({
+            "title": translator_service.translate(lang, &format!("mail.{}.title", mail_key)),
+            "description": &description,
+            "lang": lang.to_owned(),
+            "site_name": translator_service.translate(lang, &format!("mail.{}.site_name", mail_key)),
+            "site_url": app_service.url().to_string(),
+            "site_domain": site_domain,
+            "logo_src": logo_src,
+            "header": translator_service.translate(lang, &format!("mail.{}.header", mail_key)),
+            "button_label": translator_service.translate(lang, &format!("mail.{}.button", mail_key)),
+            "button_href": button_href,
+        });
+        let html_body = self
+            .template_service
+            .get_ref()
+            .render("emails/auth/security_notice.hbs", &ctx)
+            .map_err(|e| self.log_error("send", e.to_string(), LoginSecurityServiceError::Fail))?;
+
+        let message = EmailMessage {
+            from: None,
+            reply_to: None,
+            to: EmailAddress {
+                name: None,
+                email: user.email.to_owned(),
+            },
+            subject: translator_service.translate(lang, &format!("mail.{}.subject", mail_key)),
+            html_body: Some(html_body),
+            text_body: description,
+        };
+        self.job_service
+            .get_ref()
+            .dispatch(JobPayload::SendEmail(message))
+            .map_err(|e| self.log_error("send", e.to_string(), LoginSecurityServiceError::Fail))?;
+        Ok(())
+    }
+}
+
+/// The wait before the next attempt, it doubles with each failure after the allowed ones.
+pub fn progressive_delay(failures: u64, delay_after: u64, delay: u64, max_delay: u64) -> u64 {
+    if failures < delay_after || delay == 0 {
+        return 0;
+    }
+    let exponent = (failures - delay_after).min(32) as u32;
+    delay.saturating_mul(2u64.saturating_pow(exponent)).min(max_delay)
+}
+
+#[derive(Debug, Clone, Copy, Display, EnumString, PartialEq, Eq)]
+pub enum LoginSecurityServiceError {
+    Locked,
+    Delayed,
+    Fail,
+}
+
+impl TranslatableError for LoginSecurityServiceError {
+    fn translate(&self, lang: &str, translator_service: &TranslatorService) -> String {
+        match self {
+            Self::Locked => translator_service.translate(lang, "error.LoginSecurityServiceError.Locked"),
+            Self::Delayed => translator_service.translate(lang, "error.LoginSecurityServiceError.Delayed"),
+            _ => translator_service.translate(lang, "error.LoginSecurityServiceError.Fail"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progressive_delay() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::login_security::tests::test_progressive_delay
+        assert_eq!(progressive_delay(0, 3, 1, 30), 0);
+        assert_eq!(progressive_delay(2, 3, 1, 30), 0);
+        assert_eq!(progressive_delay(3, 3, 1, 30), 1);
+        assert_eq!(progressive_delay(4, 3, 1, 30), 2);
+        assert_eq!(progressive_delay(5, 3, 1, 30), 4);
+        assert_eq!(progressive_delay(7, 3, 1, 30), 16);
+        assert_eq!(progressive_delay(8, 3, 1, 30), 30);
+        assert_eq!(progressive_delay(1000, 3, 1, 30), 30);
+        assert_eq!(progressive_delay(5, 3, 0, 30), 0);
+        assert_eq!(progressive_delay(5, 0, 2, 60), 60);
+    }
+}
@@ -5,6 +5,7 @@ use crate::app::connections::smtp::{
 use crate::config::MailSmtpConfig;
 use crate::{Config};
 use actix_web::web::Data;
+use serde_derive::{Deserialize, Serialize};
 use strum_macros::{Display, EnumString};
 
 
@@ -40,7 +41,7 @@ impl MailService {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmailMessage {
     pub from: Option<EmailAddress>,
     pub reply_to: Option<EmailAddress>,
@@ -50,7 +51,7 @@ pub struct EmailMessage {
     pub text_body: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmailAddress {
     pub name: Option<String>,
     pub email: String,
@@ -1,35 +1,61 @@
+mod api_token;
 mod app;
+mod audit_log;
 mod auth;
 mod crypt;
 mod disk;
+mod email_verification;
+mod export;
 mod file;
+mod file_variant;
 mod hash;
+mod invitation;
+mod login_security;
+mod job;
 mod key_value;
+mod ldap;
 mod locale;
 mod mail;
+mod oidc;
 mod random;
 mod rate_limit;
+mod resumable_upload;
 mod role;
 mod template;
 mod translator;
 mod user;
 mod user_file;
+mod user_import;
+mod user_two_factor;
 mod web_auth;
 
+pub use self::api_token::*;
 pub use self::app::*;
+pub use self::audit_log::*;
 pub use self::auth::*;
 pub use self::crypt::*;
 pub use self::disk::*;
+pub use self::email_verification::*;
+pub use self::export::*;
 pub use self::file::*;
+pub use self::file_variant::*;
 pub use self::hash::*;
+pub use self::invitation::*;
+pub use self::login_security::*;
+pub use self::job::*;
 pub use self::key_value::*;
+pub use self::ldap::*;
 pub use self::locale::*;
 pub use self::mail::*;
+pub use self::oidc::*;
 pub use self::random::*;
 pub use self::rate_limit::*;
+pub use self::resumable_upload::*;
 pub use self::role::*;
 pub use self::template::*;
 pub use self::translator::*;
 pub use self::user::*;
 pub use self::user_file::*;
+pub use self::user_import::*;
+pub use self::user_two_factor::*;
 pub use self::web_auth::*;
@@ -0,0 +1,965 @@
+use crate::app::validator::rules::email::Email;
+use crate::config::AuthOidcProviderConfig;
+use crate::{
+    AppService, Config, KeyValueService, RandomService, RoleService,
+    TranslatableError, TranslatorService, User, UserColumn, UserService,
+};
+use actix_web::cookie::time::Duration;
+use actix_web::cookie::{Cookie, SameSite};
+use actix_web::web::Data;
+use actix_web::{error, Error, HttpRequest};
+use chrono::Utc;
+use openssl::bn::BigNum;
+use openssl::hash::{hash, MessageDigest};
+use openssl::pkey::PKey;
+use openssl::rsa::Rsa;
+use openssl::sign::Verifier;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use strum_macros::{Display, EnumString};
+use subtle::ConstantTimeEq;
+
+pub const OIDC_URL: &'static str = "/login/oidc";
+pub const OIDC_STATE_TTL: u64 = 600;
+pub const OIDC_STATE_COOKIE: &'static str = "oidc_state";
+const OIDC_STATE_KEY: &'static str = "oidc.state";
+const OIDC_CACHE_KEY: &'static str = "oidc.cache";
+const OIDC_CACHE_TTL: u64 = 3600;
+// The allowed clock difference with the provider in seconds.
+const OIDC_LEEWAY: i64 = 60;
+
+pub struct OidcService {
+    config: Data<Config>,
+    key_value_service: Data<KeyValueService>,
+    random_service: Data<RandomService>,
+    app_service: Data<AppService>,
+    user_service: Data<UserService>,
+    role_service: Data<RoleService>,
+}
+
+impl OidcService {
+    pub fn new(
+        config: Data<Config>,
+        key_value_service: Data<KeyValueService>,
+        random_service: Data<RandomService>,
+        app_service: Data<AppService>,
+        user_service: Data<UserService>,
+        role_service: Data<RoleService>,
+    ) -> Self {
+        Self {
+            config,
+            key_value_service,
+            random_service,
+            app_service,
+            user_service,
+            role_service,
+        }
+    }
+
+    pub fn get_service_name(&self) -> &str {
+        "OidcService"
+    }
+
+    pub fn log_error(&self, method: &str, error: String, e: OidcServiceError) -> OidcServiceError {
+        let service_name = self.get_service_name().to_string();
+        log::error!("{}::{} - {}", service_name, method, error);
+        e
+    }
+
+    pub fn providers(&self) -> &Vec<AuthOidcProviderConfig> {
+        &self.config.get_ref().auth.oidc_providers
+    }
+
+    pub fn get_provider(&self, code: &str) -> Result<&AuthOidcProviderConfig, OidcServiceError> {
+        self.providers()
+            .iter()
+            .find(|provider| provider.code.eq(code))
+            .ok_or(OidcServiceError::ProviderNotFound)
+    }
+
+    pub fn get_login_url(code: &str) -> String {
+        format!("{}/{}", OIDC_URL, code)
+    }
+
+    pub fn get_callback_url(code: &str) -> String {
+        format!("{}/{}/callback", OIDC_URL, code)
+    }
+
+    pub fn make_redirect_uri(&self, code: &str) -> Result<String, OidcServiceError> {
+        self.app_service
+            .get_ref()
+            .url()
+            .join(&Self::get_callback_url(code))
+            .map(|url| url.to_string())
+            .map_err(|e| self.log_error("make_redirect_uri", e.to_string(), OidcServiceError::Fail))
+    }
+
+    /// The discovery document is cached, the provider is not requested on every login.
+    pub fn discover(
+        &self,
+        provider: &AuthOidcProviderConfig,
+    ) -> Result<OidcDiscovery, OidcServiceError> {
+        let key = format!("{}.{}.discovery", OIDC_CACHE_KEY, provider.code);
+        if let Some(discovery) = self.get_cache::<OidcDiscovery>(&key) {
+            return Ok(discovery);
+        }
+        let discovery = Self::fetch_discovery(&provider.issuer)
+            .map_err(|e| self.log_error("discover", provider.issuer.to_owned(), e))?;
+        self.set_cache(&key, &discovery);
+        Ok(discovery)
+    }
+
+    /// The keys are requested again when the token is signed by an unknown key,
+    /// so the key rotation on the provider side does not break the login.
+    pub fn jwks(
+        &self,
+        provider: &AuthOidcProviderConfig,
+        discovery: &OidcDiscovery,
+        is_force: bool,
+    ) -> Result<OidcJwks, OidcServiceError> {
+        let key = format!("{}.{}.jwks", OIDC_CACHE_KEY, provider.code);
+        if !is_force {
+            if let Some(jwks) = self.get_cache::<OidcJwks>(&key) {
+                return Ok(jwks);
+            }
+        }
+        let jwks = Self::fetch_jwks(&discovery.jwks_uri)
+            .map_err(|e| self.log_error("jwks", discovery.jwks_uri.to_owned(), e))?;
+        self.set_cache(&key, &jwks);
+        Ok(jwks)
+    }
+
+    fn get_cache<T: serde::de::DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let value: Option<String> = self.key_value_service.get_ref().get(key).ok()?;
+        serde_json::from_str(&value?).ok()
+    }
+
+    fn set_cache<T: serde::Serialize>(&self, key: &str, value: &T) {
+        if let Ok(value) = serde_json::to_string(value) {
+            if let Err(e) = self.key_value_service.get_ref().set_ex(key, value, OIDC_CACHE_TTL) {
+                log::error!("OidcService::set_cache - {key} - {e}");
+            }
+        }
+    }
+
+    /// Starts the authorization code flow with PKCE, returns the url of the provider and the state.
+    pub fn make_authorization_url(
+        &self,
+        code: &str,
+    ) -> Result<(String, String), OidcServiceError> {
+        let provider = self.get_provider(code)?;
+        let discovery = self.discover(provider)?;
+        let random_service = self.random_service.get_ref();
+
+        let login_state = OidcLoginState {
+            provider: provider.code.to_owned(),
+            nonce: random_service.str(32),
+            code_verifier: random_service.str(64),
+        };
+        let state = random_service.str(32);
+        let value = serde_json::to_string(&login_state)
+            .map_err(|e| self.log_error("make_authorization_url", e.to_string(), OidcServiceError::Fail))?;
+        self.key_value_service
+            .get_ref()
+            .set_ex(self.make_state_key(&state), value, OIDC_STATE_TTL)
+            .map_err(|e| self.log_error("make_authorization_url", e.to_string(), OidcServiceError::Fail))?;
+
+        let redirect_uri = self.make_redirect_uri(&provider.code)?;
+        let code_challenge = Self::pkce_challenge(&login_state.code_verifier)?;
+        let mut url = url::Url::parse(&discovery.authorization_endpoint).map_err(|e| {
+            self.log_error("make_authorization_url", e.to_string(), OidcServiceError::DiscoveryFail)
+        })?;
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &provider.client_id)
+            .append_pair("redirect_uri", &redirect_uri)
+            .append_pair("scope", &provider.scopes)
+            .append_pair("state", &state)
+            .append_pair("nonce", &login_state.nonce)
+            .append_pair("code_challenge", &code_challenge)
+            .append_pair("code_challenge_method", "S256");
+
+        Ok((url.to_string(), state))
+    }
+
+    pub fn make_authorization_url_throw_http(
+        &self,
+        code: &str,
+    ) -> Result<(String, String), Error> {
+        self.make_authorization_url(code).map_err(|e| e.to_http())
+    }
+
+    fn make_state_key(&self, state: &str) -> String {
+        format!("{}.{}", OIDC_STATE_KEY, state)
+    }
+
+    /// Finishes the flow: exchanges the code, validates the ID token and returns the id of the linked user.
+    pub fn login_by_callback(
+        &self,
+        code: &str,
+        state: &str,
+        authorization_code: &str,
+    ) -> Result<u64, OidcServiceError> {
+        let provider = self.get_provider(code)?;
+
+        // The state is single use.
+        let value: Option<String> = self
+            .key_value_service
+            .get_ref()
+            .get_connection()
+            .and_then(|mut conn| conn.get_del(self.make_state_key(state)))
+            .map_err(|e| self.log_error("login_by_callback", e.to_string(), OidcServiceError::Fail))?;
+        let login_state: OidcLoginState = value
+            .and_then(|value| serde_json::from_str(&value).ok())
+            .ok_or(OidcServiceError::StateInvalid)?;
+        if !login_state.provider.eq(&provider.code) {
+            return Err(OidcServiceError::StateInvalid);
+        }
+
+        let discovery = self.discover(provider)?;
+        let redirect_uri = self.make_redirect_uri(&provider.code)?;
+        let id_token = Self::exchange_code(
+            &discovery.token_endpoint,
+            provider,
+            &redirect_uri,
+            authorization_code,
+            &login_state.code_verifier,
+        )
+        .map_err(|e| self.log_error("login_by_callback", provider.code.to_owned(), e))?;
+
+        let now = Utc::now().timestamp();
+        let mut jwks = self.jwks(provider, &discovery, false)?;
+        let mut result = Self::verify_id_token(&id_token, &jwks, &discovery.issuer, &provider.client_id, &login_state.nonce, now);
+        if let Err(OidcServiceError::KeyNotFound) = result {
+            jwks = self.jwks(provider, &discovery, true)?;
+            result = Self::verify_id_token(&id_token, &jwks, &discovery.issuer, &provider.client_id, &login_state.nonce, now);
+        }
+        let claims = result.map_err(|e| self.log_error("login_by_callback", provider.code.to_owned(), e))?;
+
+        let identity = Self::map_claims(provider, &claims)?;
+        self.link_user(provider, &identity)
+    }
+
+    /// Creates or finds the user by the email and synchronizes the roles from the groups claim.
+    fn link_user(
+        &self,
+        provider: &AuthOidcProviderConfig,
+        identity: &OidcIdentity,
+    ) -> Result<u64, OidcServiceError> {
+        let user_service = self.user_service.get_ref();
+        let mut user = user_service
+            .first_by_email(&identity.email)
+            .map_err(|e| self.log_error("link_user", e.to_string(), OidcServiceError::Fail))?;
+
+        if user.is_none() {
+            if !provider.create_users {
+                return Err(OidcServiceError::UserNotFound);
+            }
+            let mut new_user = User::empty(identity.email.to_owned());
+            new_user.name = identity.name.to_owned();
+            new_user.surname = identity.surname.to_owned();
+            user_service
+                .upsert(new_user, &None)
+                .map_err(|e| self.log_error("link_user", e.to_string(), OidcServiceError::Fail))?;
+            user = user_service
+                .first_by_email(&identity.email)
+                .map_err(|e| self.log_error("link_user", e.to_string(), OidcServiceError::Fail))?;
+        }
+        let mut user = user.ok_or(OidcServiceError::Fail)?;
+        if user.is_disabled() {
+            return Err(OidcServiceError::UserNotFound);
+        }
+
+        // The provider has already confirmed the email.
+        if !user.is_email_verified() {
+            user_service
+                .mark_email_verified_by_id(user.id)
+                .map_err(|e| self.log_error("link_user", e.to_string(), OidcServiceError::Fail))?;
+        }
+
+        if provider.roles.len() != 0 {
+            let roles_ids = self
+                .role_service
+                .get_ref()
+                .sync_mapped_roles_ids(&user.roles_ids, &provider.roles, &identity.groups)
+                .map_err(|e| self.log_error("link_user", e.to_string(), OidcServiceError::Fail))?;
+            if !roles_ids.eq(user.roles_ids.as_ref().unwrap_or(&Vec::new())) {
+                user.roles_ids = Some(roles_ids);
+                user_service
+                    .update(&user, &Some(vec![UserColumn::RolesIds]))
+                    .map_err(|e| self.log_error("link_user", e.to_string(), OidcServiceError::Fail))?;
+            }
+        }
+
+        Ok(user.id)
+    }
+
+    pub fn get_state_from_request(&self, req: &HttpRequest) -> Option<String> {
+        let cookie = req.cookie(OIDC_STATE_COOKIE)?;
+        let state = cookie.value();
+        if state.is_empty() {
+            return None;
+        }
+        Some(state.to_string())
+    }
+
+    // The state is bound to the browser which started the login, SameSite=Lax lets
+    // the cookie come back with the redirect from the provider.
+    fn make_state_cookie_(&self, state: &str, max_age: u64) -> Cookie<'static> {
+        let config = self.config.get_ref();
+        let mut cookie = Cookie::build(OIDC_STATE_COOKIE, state.to_string())
+            .path(OIDC_URL)
+            .http_only(true)
+            .same_site(SameSite::Lax)
+            .secure(config.auth.cookie.cookie_secure)
+            .max_age(Duration::seconds(max_age as i64));
+
+        if config.auth.cookie.cookie_domain != "" {
+            cookie = cookie.domain(config.auth.cookie.cookie_domain.to_owned());
+        }
+
+        cookie.finish()
+    }
+
+    pub fn make_state_cookie(&self, state: &str) -> Cookie<'static> {
+        self.make_state_cookie_(state, OIDC_STATE_TTL)
+    }
+
+    pub fn make_clear_state_cookie(&self) -> Cookie<'static> {
+        self.make_state_cookie_("", 0)
+    }
+
+    pub fn fetch_discovery(issuer: &str) -> Result<OidcDiscovery, OidcServiceError> {
+        let url = format!("{}/.well-known/openid-configuration", issuer.trim_end_matches('/'));
+        let body = ureq::get(&url)
+            .call()
+            .map_err(|_| OidcServiceError::DiscoveryFail)?
+            .into_body()
+            .read_to_string()
+            .map_err(|_| OidcServiceError::DiscoveryFail)?;
+        let discovery: OidcDiscovery =
+            serde_json::from_str(&body).map_err(|_| OidcServiceError::DiscoveryFail)?;
+
+        if !discovery.issuer.trim_end_matches('/').eq(issuer.trim_end_matches('/')) {
+            return Err(OidcServiceError::DiscoveryFail);
+        }
+        Ok(discovery)
+    }
+
+    pub fn fetch_jwks(jwks_uri: &str) -> Result<OidcJwks, OidcServiceError> {
+        let body = ureq::get(jwks_uri)
+            .call()
+            .map_err(|_| OidcServiceError::DiscoveryFail)?
+            .into_body()
+            .read_to_string()
+            .map_err(|_| OidcServiceError::DiscoveryFail)?;
+        serde_json::from_str(&body).map_err(|_| OidcServiceError::DiscoveryFail)
+    }
+
+    /// Exchanges the authorization code for the ID token, the client authenticates with client_secret_post.
+    pub fn exchange_code(
+        token_endpoint: &str,
+        provider: &AuthOidcProviderConfig,
+        redirect_uri: &str,
+        authorization_code: &str,
+        code_verifier: &str,
+    ) -> Result<String, OidcServiceError> {
+        let mut form: Vec<(&str, &str)> = vec![
+            ("grant_type", "authorization_code"),
+            ("code", authorization_code),
+            ("redirect_uri", redirect_uri),
+            ("client_id", &provider.client_id),
+            ("code_verifier", code_verifier),
+        ];
+        if !provider.client_secret.is_empty() {
+            form.push(("client_secret", &provider.client_secret));
+        }
+
+        let body = ureq::post(token_endpoint)
+            .header("Accept", "application/json")
+            .send_form(form)
+            .map_err(|_| OidcServiceError::TokenExchangeFail)?
+            .into_body()
+            .read_to_string()
+            .map_err(|_| OidcServiceError::TokenExchangeFail)?;
+        let response: OidcTokenResponse =
+            serde_json::from_str(&body).map_err(|_| OidcServiceError::TokenExchangeFail)?;
+        Ok(response.id_token)
+    }
+
+    pub fn pkce_challenge(code_verifier: &str) -> Result<String, OidcServiceError> {
+        let digest = hash(MessageDigest::sha256(), code_verifier.as_bytes())
+            .map_err(|_| OidcServiceError::Fail)?;
+        Ok(base64url_encode(&digest))
+    }
+
+    /// Checks the RS256 signature by the provider keys and the standard claims, returns the claims.
+    pub fn verify_id_token(
+        id_token: &str,
+        jwks: &OidcJwks,
+        issuer: &str,
+        client_id: &str,
+        nonce: &str,
+        now: i64,
+    ) -> Result<Map<String, Value>, OidcServiceError> {
+        let parts: Vec<&str> = id_token.split('.').collect();
+        if parts.len() != 3 {
+            return Err(OidcServiceError::IdTokenInvalid);
+        }
+        let header: OidcJwtHeader = base64url_decode(parts[0])
+            .and_then(|header| serde_json::from_slice(&header).ok())
+            .ok_or(OidcServiceError::IdTokenInvalid)?;
+        let claims: Map<String, Value> = base64url_decode(parts[1])
+            .and_then(|claims| serde_json::from_slice(&claims).ok())
+            .ok_or(OidcServiceError::IdTokenInvalid)?;
+        let signature = base64url_decode(parts[2]).ok_or(OidcServiceError::IdTokenInvalid)?;
+
+        // The algorithm is fixed, "none" and the HMAC algorithms are never accepted.
+        if !header.alg.eq("RS256") {
+            return Err(OidcServiceError::IdTokenInvalid);
+        }
+
+        let rsa_keys: Vec<&OidcJwk> = jwks
+            .keys
+            .iter()
+            .filter(|key| key.kty.eq("RSA"))
+            .filter(|key| key.use_.as_ref().map(|use_| use_.eq("sig")).unwrap_or(true))
+            .collect();
+        let jwk = match &header.kid {
+            Some(kid) => rsa_keys
+                .into_iter()
+                .find(|key| key.kid.as_ref().map(|id| id.eq(kid)).unwrap_or(false)),
+            None if rsa_keys.len() == 1 => rsa_keys.into_iter().next(),
+            None => None,
+        }
+        .ok_or(OidcServiceError::KeyNotFound)?;
+
+        let n = jwk.n.as_ref().and_then(|n| base64url_decode(n));
+        let e = jwk.e.as_ref().and_then(|e| base64url_decode(e));
+        let (Some(n), Some(e)) = (n, e) else {
+            return Err(OidcServiceError::KeyNotFound);
+        };
+        let rsa = BigNum::from_slice(&n)
+            .and_then(|n| BigNum::from_slice(&e).map(|e| (n, e)))
+            .and_then(|(n, e)| Rsa::from_public_components(n, e))
+            .and_then(PKey::from_rsa)
+            .map_err(|_| OidcServiceError::KeyNotFound)?;
+        let mut verifier =
+            Verifier::new(MessageDigest::sha256(), &rsa).map_err(|_| OidcServiceError::Fail)?;
+        let signing_input = format!("{}.{}", parts[0], parts[1]);
+        let is_valid = verifier
+            .verify_oneshot(&signature, signing_input.as_bytes())
+            .unwrap_or(false);
+        if !is_valid {
+            return Err(OidcServiceError::IdTokenInvalid);
+        }
+
+        let iss = claims.get("iss").and_then(|v| v.as_str()).unwrap_or("");
+        if !iss.trim_end_matches('/').eq(issuer.trim_end_matches('/')) {
+            return Err(OidcServiceError::IdTokenInvalid);
+        }
+
+        let is_audience = match claims.get("aud") {
+            Some(Value::String(aud)) => aud.eq(client_id),
+            Some(Value::Array(aud)) => {
+                aud.iter().any(|v| v.as_str() == Some(client_id))
+                    && (aud.len() == 1
+                        || claims.get("azp").and_then(|v| v.as_str()) == Some(client_id))
+            }
+            _ => false,
+        };
+        if !is_audience {
+            return Err(OidcServiceError::IdTokenInvalid);
+        }
+
+        let exp = claims.get("exp").and_then(|v| v.as_i64()).unwrap_or(0);
+        if exp + OIDC_LEEWAY < now {
+            return Err(OidcServiceError::IdTokenInvalid);
+        }
+        if let Some(iat) = claims.get("iat").and_then(|v| v.as_i64()) {
+            if iat - OIDC_LEEWAY > now {
+                return Err(OidcServiceError::IdTokenInvalid);
+            }
+        }
+
+        let token_nonce = claims.get("nonce").and_then(|v| v.as_str()).unwrap_or("");
+        if !bool::from(token_nonce.as_bytes().ct_eq(nonce.as_bytes())) {
+            return Err(OidcServiceError::IdTokenInvalid);
+        }
+
+        Ok(claims)
+    }
+
+    pub fn map_claims(
+        provider: &AuthOidcProviderConfig,
+        claims: &Map<String, Value>,
+    ) -> Result<OidcIdentity, OidcServiceError> {
+        let email = get_claim(claims, &provider.email_claim)
+            .and_then(|v| v.as_str())
+            .map(|v| v.trim().to_string())
+            .unwrap_or_default();
+        if !Email::apply(&email) {
+            return Err(OidcServiceError::EmailMissing);
+        }
+        // The user is linked by the email, so the unconfirmed one would let anybody take the account.
+        if let Some(Value::Bool(false)) = claims.get("email_verified") {
+            return Err(OidcServiceError::EmailMissing);
+        }
+
+        let get_str = |path: &str| -> Option<String> {
+            get_claim(claims, path)
+                .and_then(|v| v.as_str())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        let groups: Vec<String> = match get_claim(claims, &provider.groups_claim) {
+            Some(Value::Array(groups)) => groups
+                .iter()
+                .filter_map(|v| v.as_str())
+                .map(|v| v.to_string())
+                .collect(),
+            Some(Value::String(group)) => vec![group.to_string()],
+            _ => Vec::new(),
+        };
+
+        Ok(OidcIdentity {
+            email,
+            name: get_str(&provider.name_claim),
+            surname: get_str(&provider.surname_claim),
+            groups,
+        })
+    }
+}
+
+fn get_claim<'a>(claims: &'a Map<String, Value>, path: &str) -> Option<&'a Value> {
+    if path.is_empty() {
+        return None;
+    }
+    let mut parts = path.split('.');
+    let mut value = claims.get(parts.next()?)?;
+    for part in parts {
+        value = value.get(part)?;
+    }
+    Some(value)
+}
+
+pub fn base64url_encode(value: &[u8]) -> String {
+    openssl::base64::encode_block(value)
+        .trim_end_matches('=')
+        .replace('+', "-")
+        .replace('/', "_")
+}
+
+pub fn base64url_decode(value: &str) -> Option<Vec<u8>> {
+    let mut base64 = value.replace('-', "+").replace('_', "/");
+    while !base64.len().is_multiple_of(4) {
+        base64.push('=');
+    }
+    openssl::base64::decode_block(&base64).ok()
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct OidcDiscovery {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct OidcJwks {
+    pub keys: Vec<OidcJwk>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct OidcJwk {
+    pub kty: String,
+    pub kid: Option<String>,
+    #[serde(rename = "use")]
+    pub use_: Option<String>,
+    pub n: Option<String>,
+    pub e: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OidcJwtHeader {
+    alg: String,
+    kid: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OidcTokenResponse {
+    id_token: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+struct OidcLoginState {
+    provider: String,
+    nonce: String,
+    code_verifier: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OidcIdentity {
+    pub email: String,
+    pub name: Option<String>,
+    pub surname: Option<String>,
+    pub groups: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Display, EnumString, PartialEq, Eq)]
+pub enum OidcServiceError {
+    ProviderNotFound,
+    DiscoveryFail,
+    StateInvalid,
+    TokenExchangeFail,
+    KeyNotFound,
+    IdTokenInvalid,
+    EmailMissing,
+    UserNotFound,
+    Fail,
+}
+
+impl OidcServiceError {
+    pub fn to_http(&self) -> Error {
+        match self {
+            Self::ProviderNotFound => error::ErrorNotFound(""),
+            _ => error::ErrorInternalServerError(""),
+        }
+    }
+}
+
+impl TranslatableError for OidcServiceError {
+    fn translate(&self, lang: &str, translator_service: &TranslatorService) -> String {
+        match self {
+            Self::ProviderNotFound => {
+                translator_service.translate(lang, "error.OidcServiceError.ProviderNotFound")
+            }
+            Self::StateInvalid => {
+                translator_service.translate(lang, "error.OidcServiceError.StateInvalid")
+            }
+            Self::EmailMissing => {
+                translator_service.translate(lang, "error.OidcServiceError.EmailMissing")
+            }
+            Self::UserNotFound => {
+                translator_service.translate(lang, "error.OidcServiceError.UserNotFound")
+            }
+            _ => translator_service.translate(lang, "error.OidcServiceError.Fail"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::pkey::Private;
+    use openssl::sign::Signer;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    const CLIENT_ID: &'static str = "panel";
+
+    fn make_provider(issuer: &str) -> AuthOidcProviderConfig {
+        AuthOidcProviderConfig {
+            code: "company".to_string(),
+            name: "Company".to_string(),
+            issuer: issuer.to_string(),
+            client_id: CLIENT_ID.to_string(),
+            client_secret: "secret".to_string(),
+            scopes: "openid email profile".to_string(),
+            email_claim: "email".to_string(),
+            name_claim: "given_name".to_string(),
+            surname_claim: "family_name".to_string(),
+            groups_claim: "realm_access.roles".to_string(),
+            roles: vec![
+                ("admins".to_string(), "admin".to_string()),
+                ("editors".to_string(), "editor".to_string()),
+                ("writers".to_string(), "editor".to_string()),
+            ],
+            create_users: true,
+        }
+    }
+
+    fn make_jwks(key: &PKey<Private>, kid: &str) -> OidcJwks {
+        let rsa = key.rsa().unwrap();
+        OidcJwks {
+            keys: vec![OidcJwk {
+                kty: "RSA".to_string(),
+                kid: Some(kid.to_string()),
+                use_: Some("sig".to_string()),
+                n: Some(base64url_encode(&rsa.n().to_vec())),
+                e: Some(base64url_encode(&rsa.e().to_vec())),
+            }],
+        }
+    }
+
+    fn make_id_token(key: &PKey<Private>, header: &Value, claims: &Value) -> String {
+        let header = base64url_encode(header.to_string().as_bytes());
+        let claims = base64url_encode(claims.to_string().as_bytes());
+        let signing_input = format!("{}.{}", header, claims);
+        let mut signer = Signer::new(MessageDigest::sha256(), key).unwrap();
+        let signature = signer.sign_oneshot_to_vec(signing_input.as_bytes()).unwrap();
+        format!("{}.{}", signing_input, base64url_encode(&signature))
+    }
+
+    fn make_claims(issuer: &str, now: i64) -> Value {
+        serde_json::json!({
+            "iss": issuer,
+            "aud": CLIENT_ID,
+            "exp": now + 300,
+            "iat": now,
+            "nonce": "nonce",
+            "email": "user@example.com",
+            "email_verified": true,
+            "given_name": "John",
+            "family_name": "Smith",
+            "realm_access": {"roles": ["admins", "writers"]},
+        })
+    }
+
+    /// Serves the fixed responses by the path, the bodies of the requests are sent to the channel.
+    fn start_mock_idp<F: FnOnce(&str) -> Vec<(String, String)>>(
+        make_routes: F,
+    ) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let routes = make_routes(&base_url);
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let path = request_line.split(' ').nth(1).unwrap_or("").to_string();
+
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap_or(0);
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                if content_length != 0 {
+                    let _ = sender.send(String::from_utf8(body).unwrap());
+                }
+
+                let response = routes.iter().find(|(route, _)| route.eq(&path));
+                let (status, body) = match response {
+                    Some((_, body)) => ("200 OK", body.to_owned()),
+                    None => ("404 Not Found", "{}".to_string()),
+                };
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+            }
+        });
+
+        (base_url, receiver)
+    }
+
+    #[test]
+    fn pkce_challenge() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::oidc::tests::pkce_challenge
+        // The example from RFC 7636.
+        assert_eq!(
+            OidcService::pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk").unwrap(),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn base64url() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::oidc::tests::base64url
+        let value: Vec<u8> = vec![251, 255, 191, 0, 1];
+        let encoded = base64url_encode(&value);
+        assert_eq!(encoded, "-_-_AAE");
+        assert_eq!(base64url_decode(&encoded).unwrap(), value);
+        assert!(base64url_decode("@@@").is_none());
+    }
+
+    #[test]
+    fn verify_id_token() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::oidc::tests::verify_id_token
+        let issuer = "http://127.0.0.1/realms/company";
+        let now = Utc::now().timestamp();
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let other_key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let jwks = make_jwks(&key, "key1");
+        let header = serde_json::json!({"alg": "RS256", "kid": "key1"});
+        let claims = make_claims(issuer, now);
+
+        let id_token = make_id_token(&key, &header, &claims);
+        let result = OidcService::verify_id_token(&id_token, &jwks, issuer, CLIENT_ID, "nonce", now).unwrap();
+        assert_eq!(result.get("email").unwrap(), "user@example.com");
+
+        let verify = |id_token: &str| {
+            OidcService::verify_id_token(id_token, &jwks, issuer, CLIENT_ID, "nonce", now)
+        };
+
+        // The signature of another key.
+        let id_token = make_id_token(&other_key, &header, &claims);
+        assert_eq!(verify(&id_token), Err(OidcServiceError::IdTokenInvalid));
+
+        // The unknown key.
+        let id_token = make_id_token(&key, &serde_json::json!({"alg": "RS256", "kid": "key2"}), &claims);
+        assert_eq!(verify(&id_token), Err(OidcServiceError::KeyNotFound));
+
+        // The unsigned token.
+        let id_token = format!(
+            "{}.{}.",
+            base64url_encode(serde_json::json!({"alg": "none"}).to_string().as_bytes()),
+            base64url_encode(claims.to_string().as_bytes())
+        );
+        assert_eq!(verify(&id_token), Err(OidcServiceError::IdTokenInvalid));
+
+        let mut changed = claims.clone();
+        changed["email"] = Value::from("admin@example.com");
+        let parts: Vec<String> = make_id_token(&key, &header, &claims).split('.').map(|v| v.to_string()).collect();
+        let id_token = format!("{}.{}.{}", parts[0], base64url_encode(changed.to_string().as_bytes()), parts[2]);
+        assert_eq!(verify(&id_token), Err(OidcServiceError::IdTokenInvalid));
+
+        let cases: Vec<(&str, Value)> = vec![
+            ("iss", Value::from("http://127.0.0.1/realms/other")),
+            ("aud", Value::from("other")),
+            ("aud", serde_json::json!(["other", CLIENT_ID])),
+            ("exp", Value::from(now - 3600)),
+            ("iat", Value::from(now + 3600)),
+            ("nonce", Value::from("other")),
+        ];
+        for (name, value) in cases {
+            let mut changed = claims.clone();
+            changed[name] = value;
+            let id_token = make_id_token(&key, &header, &changed);
+            assert_eq!(verify(&id_token), Err(OidcServiceError::IdTokenInvalid), "{}", name);
+        }
+
+        // The audience list with the authorized party.
+        let mut changed = claims.clone();
+        changed["aud"] = serde_json::json!(["other", CLIENT_ID]);
+        changed["azp"] = Value::from(CLIENT_ID);
+        let id_token = make_id_token(&key, &header, &changed);
+        assert!(verify(&id_token).is_ok());
+    }
+
+    #[test]
+    fn map_claims() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::oidc::tests::map_claims
+        let issuer = "http://127.0.0.1/realms/company";
+        let provider = make_provider(issuer);
+        let claims = make_claims(issuer, 0);
+        let claims = claims.as_object().unwrap();
+
+        let identity = OidcService::map_claims(&provider, claims).unwrap();
+        assert_eq!(
+            identity,
+            OidcIdentity {
+                email: "user@example.com".to_string(),
+                name: Some("John".to_string()),
+                surname: Some("Smith".to_string()),
+                groups: vec!["admins".to_string(), "writers".to_string()],
+            }
+        );
+        assert_eq!(
+            RoleService::map_groups_to_codes(&identity.groups, &provider.roles),
+            vec!["admin".to_string(), "editor".to_string()]
+        );
+
+        let mut changed = claims.clone();
+        changed.insert("email_verified".to_string(), Value::Bool(false));
+        assert_eq!(OidcService::map_claims(&provider, &changed), Err(OidcServiceError::EmailMissing));
+
+        let mut changed = claims.clone();
+        changed.remove("email");
+        assert_eq!(OidcService::map_claims(&provider, &changed), Err(OidcServiceError::EmailMissing));
+    }
+
+    #[test]
+    fn mock_idp() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::oidc::tests::mock_idp
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let jwks = make_jwks(&key, "key1");
+        let now = Utc::now().timestamp();
+
+        let (issuer, receiver) = start_mock_idp(|issuer| {
+            let discovery = OidcDiscovery {
+                issuer: issuer.to_string(),
+                authorization_endpoint: format!("{}/auth", issuer),
+                token_endpoint: format!("{}/token", issuer),
+                jwks_uri: format!("{}/certs", issuer),
+            };
+            let id_token = make_id_token(
+                &key,
+                &serde_json::json!({"alg": "RS256", "kid": "key1"}),
+                &make_claims(issuer, now),
+            );
+            vec![
+                (
+                    "/.well-known/openid-configuration".to_string(),
+                    serde_json::to_string(&discovery).unwrap(),
+                ),
+                ("/certs".to_string(), serde_json::to_string(&jwks).unwrap()),
+                (
+                    "/token".to_string(),
+                    serde_json::json!({"access_token": "access", "token_type": "Bearer", "id_token": id_token}).to_string(),
+                ),
+            ]
+        });
+        let provider = make_provider(&issuer);
+
+        let discovery = OidcService::fetch_discovery(&issuer).unwrap();
+        assert_eq!(discovery.token_endpoint, format!("{}/token", issuer));
+        assert_eq!(
+            OidcService::fetch_discovery(&format!("{}/", issuer)).unwrap().issuer,
+            issuer
+        );
+
+        let jwks = OidcService::fetch_jwks(&discovery.jwks_uri).unwrap();
+        assert_eq!(jwks.keys.len(), 1);
+
+        let id_token = OidcService::exchange_code(
+            &discovery.token_endpoint,
+            &provider,
+            "http://localhost/login/oidc/company/callback",
+            "code",
+            "verifier",
+        )
+        .unwrap();
+        let form: Vec<(String, String)> = serde_urlencoded::from_str(&receiver.recv().unwrap()).unwrap();
+        assert!(form.contains(&("grant_type".to_string(), "authorization_code".to_string())));
+        assert!(form.contains(&("code_verifier".to_string(), "verifier".to_string())));
+        assert!(form.contains(&("client_secret".to_string(), "secret".to_string())));
+
+        let claims = OidcService::verify_id_token(&id_token, &jwks, &discovery.issuer, CLIENT_ID, "nonce", now).unwrap();
+        let identity = OidcService::map_claims(&provider, &claims).unwrap();
+        assert_eq!(identity.email, "user@example.com");
+
+        // The discovery of another issuer is rejected.
+        let (other_issuer, _) = start_mock_idp(|_| {
+            vec![(
+                "/.well-known/openid-configuration".to_string(),
+                serde_json::to_string(&discovery).unwrap(),
+            )]
+        });
+        assert_eq!(
+            OidcService::fetch_discovery(&other_issuer).unwrap_err(),
+            OidcServiceError::DiscoveryFail
+        );
+    }
+}
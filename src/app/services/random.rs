@@ -22,6 +22,11 @@ impl RandomService {
         rng.random_range(range)
     }
 
+    pub fn bytes_12(&self) -> [u8; 12] {
+        let mut rng = rand::rng();
+        rng.random()
+    }
+
     pub fn bytes_16(&self) -> [u8; 16] {
         let mut rng = rand::rng();
         rng.random()
@@ -67,6 +67,24 @@ impl RateLimitService {
             .map_err(|_| error::ErrorInternalServerError(""))
     }
 
+    /// Deletes the limits by the glob pattern of the key, returns the count of the deleted limits.
+    pub fn clear_by_pattern(&self, pattern: &str) -> Result<u64, RateLimitServiceError> {
+        let mut connection = self.get_connection()?;
+        let keys = connection
+            .scan_match(&self.make_store_key(pattern))
+            .map_err(|e| {
+                log::error!("RateLimitService::clear_by_pattern - {e}");
+                RateLimitServiceError::Fail
+            })?;
+        for key in keys.iter() {
+            connection.del(key).map_err(|e| {
+                log::error!("RateLimitService::clear_by_pattern - {e}");
+                RateLimitServiceError::Fail
+            })?;
+        }
+        Ok(keys.len() as u64)
+    }
+
     fn ttl_(
         &self,
         connection: &mut KeyValueServiceConnection,
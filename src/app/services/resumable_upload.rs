@@ -0,0 +1,352 @@
+use crate::helpers::now_date_time_str;
+use crate::{
+    DiskLocalRepository, DiskRepository, FileService, FileServiceError, KeyValueService,
+    RandomService, ResumableUpload, TranslatableError, TranslatorService, UserFile,
+    FILE_UPLOAD_DIRECTORY,
+};
+use actix_web::web::Data;
+use actix_web::{error, Error};
+use mime::Mime;
+use std::io::Write;
+use std::path::MAIN_SEPARATOR_STR;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+use strum_macros::{Display, EnumString};
+
+// 24 hours to finish the upload after the last received chunk.
+pub const RESUMABLE_UPLOAD_TTL: u64 = 86400;
+pub const RESUMABLE_UPLOAD_DIRECTORY: &'static str = "resumable";
+const RESUMABLE_UPLOAD_LOCK_TTL: i64 = 60;
+
+pub struct ResumableUploadService {
+    key_value_service: Data<KeyValueService>,
+    file_service: Data<FileService>,
+    disk_local_repository: Data<DiskLocalRepository>,
+    random_service: Data<RandomService>,
+}
+
+impl ResumableUploadService {
+    pub fn new(
+        key_value_service: Data<KeyValueService>,
+        file_service: Data<FileService>,
+        disk_local_repository: Data<DiskLocalRepository>,
+        random_service: Data<RandomService>,
+    ) -> Self {
+        Self {
+            key_value_service,
+            file_service,
+            disk_local_repository,
+            random_service,
+        }
+    }
+
+    pub fn get_service_name(&self) -> &str {
+        "ResumableUploadService"
+    }
+
+    pub fn log_error(
+        &self,
+        method: &str,
+        error: String,
+        e: ResumableUploadServiceError,
+    ) -> ResumableUploadServiceError {
+        let service_name = self.get_service_name().to_string();
+        log::error!("{}::{} - {}", service_name, method, error);
+        e
+    }
+
+    fn make_key(&self, user_id: u64, id: &str) -> String {
+        format!("files.resumable.{}.{}", user_id, id)
+    }
+
+    fn make_lock_key(&self, user_id: u64, id: &str) -> String {
+        format!("files.resumable.{}.{}.lock", user_id, id)
+    }
+
+    fn make_directory(&self) -> Result<String, ResumableUploadServiceError> {
+        let mut directory = FILE_UPLOAD_DIRECTORY.to_string();
+        directory.push_str(MAIN_SEPARATOR_STR);
+        directory.push_str(RESUMABLE_UPLOAD_DIRECTORY);
+        self.disk_local_repository
+            .get_ref()
+            .path(&directory)
+            .map_err(|e| {
+                self.log_error(
+                    "make_directory",
+                    e.to_string(),
+                    ResumableUploadServiceError::Fail,
+                )
+            })
+    }
+
+    // The path of the staged content: [root]/.uploads/resumable/[user_id]-[id].part
+    pub fn make_path(&self, upload: &ResumableUpload) -> Result<String, ResumableUploadServiceError> {
+        let mut path = self.make_directory()?;
+        path.push_str(MAIN_SEPARATOR_STR);
+        path.push_str(format!("{}-{}.part", upload.user_id, upload.id).as_str());
+        Ok(path)
+    }
+
+    pub fn create(
+        &self,
+        user_id: u64,
+        length: u64,
+        max_size: u64,
+        filename: Option<String>,
+        mime: Option<String>,
+        is_public: bool,
+    ) -> Result<ResumableUpload, ResumableUploadServiceError> {
+        if length > max_size {
+            return Err(ResumableUploadServiceError::TooLarge);
+        }
+
+        self.delete_expired_staged_files();
+
+        let upload = ResumableUpload {
+            id: self.random_service.get_ref().str(32),
+            user_id,
+            length,
+            offset: 0,
+            filename,
+            mime,
+            is_public,
+            created_at: Some(now_date_time_str()),
+        };
+
+        let path = self.make_path(&upload)?;
+        self.disk_local_repository
+            .get_ref()
+            .put(&path, Vec::new())
+            .map_err(|e| {
+                self.log_error("create", e.to_string(), ResumableUploadServiceError::Fail)
+            })?;
+
+        self.save(&upload)?;
+        Ok(upload)
+    }
+
+    pub fn create_throw_http(
+        &self,
+        user_id: u64,
+        length: u64,
+        max_size: u64,
+        filename: Option<String>,
+        mime: Option<String>,
+        is_public: bool,
+    ) -> Result<ResumableUpload, Error> {
+        self.create(user_id, length, max_size, filename, mime, is_public)
+            .map_err(|e| e.to_http())
+    }
+
+    fn save(&self, upload: &ResumableUpload) -> Result<(), ResumableUploadServiceError> {
+        self.key_value_service
+            .get_ref()
+            .set_ex(
+                self.make_key(upload.user_id, &upload.id),
+                upload,
+                RESUMABLE_UPLOAD_TTL,
+            )
+            .map_err(|e| self.log_error("save", e.to_string(), ResumableUploadServiceError::Fail))
+    }
+
+    pub fn first(
+        &self,
+        user_id: u64,
+        id: &str,
+    ) -> Result<Option<ResumableUpload>, ResumableUploadServiceError> {
+        self.key_value_service
+            .get_ref()
+            .get(self.make_key(user_id, id))
+            .map_err(|e| self.log_error("first", e.to_string(), ResumableUploadServiceError::Fail))
+    }
+
+    pub fn first_throw_http(&self, user_id: u64, id: &str) -> Result<ResumableUpload, Error> {
+        let upload = self.first(user_id, id).map_err(|e| e.to_http())?;
+        if let Some(upload) = upload {
+            Ok(upload)
+        } else {
+            Err(error::ErrorNotFound(""))
+        }
+    }
+
+    // Only one chunk of the upload is written at the same time.
+    pub fn lock(&self, upload: &ResumableUpload) -> Result<(), ResumableUploadServiceError> {
+        let key_value_service = self.key_value_service.get_ref();
+        let key = self.make_lock_key(upload.user_id, &upload.id);
+        let value: u64 = key_value_service
+            .incr(&key, 1)
+            .map_err(|e| self.log_error("lock", e.to_string(), ResumableUploadServiceError::Fail))?;
+        if value > 1 {
+            return Err(ResumableUploadServiceError::Locked);
+        }
+        key_value_service
+            .expire(&key, RESUMABLE_UPLOAD_LOCK_TTL)
+            .map_err(|e| self.log_error("lock", e.to_string(), ResumableUploadServiceError::Fail))
+    }
+
+    pub fn unlock(&self, upload: &ResumableUpload) -> Result<(), ResumableUploadServiceError> {
+        self.key_value_service
+            .get_ref()
+            .del(self.make_lock_key(upload.user_id, &upload.id))
+            .map_err(|e| self.log_error("unlock", e.to_string(), ResumableUploadServiceError::Fail))
+    }
+
+    // Open the staged content to append the chunk, the offset must be equal to the received bytes.
+    pub fn append_stream(
+        &self,
+        upload: &ResumableUpload,
+        offset: u64,
+    ) -> Result<Box<dyn Write>, ResumableUploadServiceError> {
+        if offset != upload.offset {
+            return Err(ResumableUploadServiceError::OffsetMismatch);
+        }
+        let path = self.make_path(upload)?;
+        self.disk_local_repository
+            .get_ref()
+            .append_stream(&path, offset)
+            .map_err(|e| {
+                self.log_error(
+                    "append_stream",
+                    e.to_string(),
+                    ResumableUploadServiceError::Fail,
+                )
+            })
+    }
+
+    // Save the count of the received bytes after the chunk is written.
+    pub fn set_offset(
+        &self,
+        upload: &mut ResumableUpload,
+        offset: u64,
+    ) -> Result<(), ResumableUploadServiceError> {
+        if offset > upload.length {
+            return Err(ResumableUploadServiceError::TooLarge);
+        }
+        upload.offset = offset;
+        self.save(upload)
+    }
+
+    // Hand off the complete content to the FileService, the dedup and the user file creation are done there.
+    pub fn finalize(&self, upload: &ResumableUpload) -> Result<UserFile, ResumableUploadServiceError> {
+        if upload.offset != upload.length {
+            return Err(ResumableUploadServiceError::Incomplete);
+        }
+        let path = self.make_path(upload)?;
+        let mime: Option<Mime> = upload
+            .mime
+            .as_ref()
+            .and_then(|mime| Mime::from_str(mime).ok());
+
+        let user_file = self
+            .file_service
+            .get_ref()
+            .upload_local_file_to_disk(
+                upload.user_id,
+                &path,
+                upload.is_public,
+                upload.filename.to_owned(),
+                mime,
+            )
+            .map_err(|e| {
+                self.log_error(
+                    "finalize",
+                    e.to_string(),
+                    ResumableUploadServiceError::FileServiceFail(e),
+                )
+            })?;
+
+        self.delete(upload)?;
+        Ok(user_file)
+    }
+
+    pub fn finalize_throw_http(&self, upload: &ResumableUpload) -> Result<UserFile, Error> {
+        self.finalize(upload).map_err(|e| e.to_http())
+    }
+
+    pub fn delete(&self, upload: &ResumableUpload) -> Result<(), ResumableUploadServiceError> {
+        let path = self.make_path(upload)?;
+        self.disk_local_repository
+            .get_ref()
+            .delete(&path)
+            .map_err(|e| self.log_error("delete", e.to_string(), ResumableUploadServiceError::Fail))?;
+        self.key_value_service
+            .get_ref()
+            .del(self.make_key(upload.user_id, &upload.id))
+            .map_err(|e| self.log_error("delete", e.to_string(), ResumableUploadServiceError::Fail))
+    }
+
+    pub fn delete_throw_http(&self, upload: &ResumableUpload) -> Result<(), Error> {
+        self.delete(upload).map_err(|e| e.to_http())
+    }
+
+    // The state of the abandoned uploads expires in the store, their staged content is deleted here.
+    fn delete_expired_staged_files(&self) {
+        let disk_local_repository = self.disk_local_repository.get_ref();
+        let directory = match self.make_directory() {
+            Ok(directory) => directory,
+            Err(_) => return,
+        };
+        let paths = match disk_local_repository.files(&directory, false) {
+            Ok(paths) => paths,
+            Err(_) => return,
+        };
+        let expired_at = SystemTime::now() - Duration::from_secs(RESUMABLE_UPLOAD_TTL);
+        for path in paths {
+            if let Ok(last_modified) = disk_local_repository.last_modified(&path) {
+                if last_modified < expired_at {
+                    if let Err(e) = disk_local_repository.delete(&path) {
+                        log::error!("ResumableUploadService::delete_expired_staged_files - {e}");
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Display, EnumString, PartialEq, Eq)]
+pub enum ResumableUploadServiceError {
+    NotFound,
+    OffsetMismatch,
+    Locked,
+    TooLarge,
+    Incomplete,
+    #[strum(disabled)]
+    FileServiceFail(FileServiceError),
+    Fail,
+}
+
+impl ResumableUploadServiceError {
+    pub fn to_http(&self) -> Error {
+        match self {
+            Self::NotFound => error::ErrorNotFound(""),
+            Self::OffsetMismatch => error::ErrorConflict(""),
+            Self::Locked => error::ErrorLocked(""),
+            Self::TooLarge => error::ErrorPayloadTooLarge(""),
+            Self::Incomplete => error::ErrorConflict(""),
+            _ => error::ErrorInternalServerError(""),
+        }
+    }
+}
+
+impl TranslatableError for ResumableUploadServiceError {
+    fn translate(&self, lang: &str, translator_service: &TranslatorService) -> String {
+        match self {
+            Self::NotFound => {
+                translator_service.translate(lang, "error.ResumableUploadServiceError.NotFound")
+            }
+            Self::OffsetMismatch => translator_service
+                .translate(lang, "error.ResumableUploadServiceError.OffsetMismatch"),
+            Self::Locked => {
+                translator_service.translate(lang, "error.ResumableUploadServiceError.Locked")
+            }
+            Self::TooLarge => {
+                translator_service.translate(lang, "error.ResumableUploadServiceError.TooLarge")
+            }
+            Self::Incomplete => {
+                translator_service.translate(lang, "error.ResumableUploadServiceError.Incomplete")
+            }
+            Self::FileServiceFail(e) => e.translate(lang, translator_service),
+            _ => translator_service.translate(lang, "error.ResumableUploadServiceError.Fail"),
+        }
+    }
+}
@@ -1,6 +1,9 @@
-use crate::{AppError, MysqlRepository, PaginationResult, Role, RoleColumn, RoleFilter, RoleMysqlRepository, RolePaginateParams, TranslatableError, TranslatorService, User, UserColumn, UserFilter, UserServiceError};
+use crate::{AppError, MysqlRepository, PaginationResult, Permission, Role, RoleColumn, RoleFilter, RoleMysqlRepository, RolePaginateParams, TranslatableError, TranslatorService, User, UserColumn, UserFilter, UserServiceError};
 use actix_web::web::Data;
 use actix_web::{error, Error};
+use std::collections::HashMap;
+use std::str::FromStr;
+use strum::VariantNames;
 use strum_macros::{Display, EnumString};
 
 pub struct RoleService {
@@ -12,11 +15,15 @@ impl RoleService {
         Self { role_repository }
     }
 
+    /// All the roles with the inherited permissions resolved, the policies are given them.
     pub fn all(&self) -> Result<Vec<Role>, RoleServiceError> {
-        self.role_repository
+        let mut roles = self
+            .role_repository
             .get_ref()
             .all(None, None, &None)
-            .map_err(|e| self.match_error(e))
+            .map_err(|e| self.match_error(e))?;
+        Self::resolve_permissions(&mut roles);
+        Ok(roles)
     }
 
     pub fn all_throw_http(&self) -> Result<Vec<Role>, Error> {
@@ -41,6 +48,125 @@ impl RoleService {
         Err(error::ErrorNotFound(""))
     }
 
+    pub fn all_by_ids(&self, ids: &Vec<u64>) -> Result<Vec<Role>, RoleServiceError> {
+        let filters = vec![RoleFilter::Ids(ids.to_owned())];
+        self.role_repository
+            .get_ref()
+            .all(Some(&filters), None, &None)
+            .map_err(|e| self.match_error(e))
+    }
+
+    pub fn all_by_ids_throw_http(&self, ids: &Vec<u64>) -> Result<Vec<Role>, Error> {
+        self.all_by_ids(ids)
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+
+    /// The permissions of the user from the own roles and their parents, the super admin has all of them.
+    pub fn user_permissions(&self, user: &User) -> Result<Vec<String>, RoleServiceError> {
+        let roles = match &user.roles_ids {
+            Some(roles_ids) if !user.is_super_admin && roles_ids.len() != 0 => self.all()?,
+            _ => Vec::new(),
+        };
+        Ok(Self::permissions_of_roles(user, &roles))
+    }
+
+    /// The known permissions in the order of the declaration, as they are checked by the policies.
+    pub fn permissions_of_roles(user: &User, roles: &Vec<Role>) -> Vec<String> {
+        let roles_ids = user.roles_ids.to_owned().unwrap_or_default();
+        Permission::VARIANTS
+            .iter()
+            .filter(|permission| {
+                user.is_super_admin
+                    || Permission::from_str(permission).is_ok_and(|permission| {
+                        roles.iter().any(|role| {
+                            roles_ids.contains(&role.id) && role.has_permission(&permission)
+                        })
+                    })
+            })
+            .map(|permission| permission.to_string())
+            .collect()
+    }
+
+    /// The ids of the parents of the role from the nearest one, the chain ends on a missing role or a cycle.
+    pub fn ancestors_ids(roles: &Vec<Role>, role_id: u64) -> Vec<u64> {
+        let parents: HashMap<u64, Option<u64>> =
+            roles.iter().map(|role| (role.id, role.parent_id)).collect();
+        let mut ids: Vec<u64> = Vec::new();
+        let mut current = parents.get(&role_id).copied().flatten();
+        while let Some(id) = current {
+            if id == role_id || ids.contains(&id) || !parents.contains_key(&id) {
+                break;
+            }
+            ids.push(id);
+            current = parents.get(&id).copied().flatten();
+        }
+        ids
+    }
+
+    /// Sets the mask of the own and the inherited permissions of every role,
+    /// so the policies check a bit instead of searching the codes.
+    pub fn resolve_permissions(roles: &mut Vec<Role>) {
+        let masks: HashMap<u64, u64> = roles
+            .iter()
+            .map(|role| {
+                (role.id, Permission::mask_of(role.permissions.as_ref().unwrap_or(&Vec::new())))
+            })
+            .collect();
+        let effective: Vec<u64> = roles
+            .iter()
+            .map(|role| {
+                Self::ancestors_ids(roles, role.id)
+                    .iter()
+                    .fold(masks[&role.id], |mask, id| mask | masks[id])
+            })
+            .collect();
+        for (role, mask) in roles.iter_mut().zip(effective) {
+            role.effective_permissions = Some(mask);
+        }
+    }
+
+    /// The own and the inherited permissions of the role in the order of the declaration,
+    /// each one with the id of the nearest role in the chain granting it.
+    pub fn effective_permissions(roles: &Vec<Role>, role_id: u64) -> Vec<(String, u64)> {
+        let mut chain: Vec<u64> = vec![role_id];
+        chain.extend(Self::ancestors_ids(roles, role_id));
+        let chain: Vec<&Role> = chain
+            .iter()
+            .filter_map(|id| roles.iter().find(|role| role.id.eq(id)))
+            .collect();
+
+        let mut result: Vec<(String, u64)> = Vec::new();
+        for variant in Permission::VARIANTS {
+            let granted = chain.iter().find(|role| {
+                role.permissions
+                    .as_ref()
+                    .is_some_and(|permissions| permissions.iter().any(|p| p.eq(variant)))
+            });
+            if let Some(role) = granted {
+                result.push((variant.to_string(), role.id));
+            }
+        }
+        result
+    }
+
+    /// The parent makes a cycle if the role is already among its parents or is the parent itself.
+    pub fn is_parent_cycle(roles: &Vec<Role>, role_id: u64, parent_id: u64) -> bool {
+        role_id != 0
+            && (parent_id == role_id || Self::ancestors_ids(roles, parent_id).contains(&role_id))
+    }
+
+    /// Checks the parent chosen for the role before the save, the new role has the id 0.
+    pub fn check_parent(&self, role_id: u64, parent_id: u64) -> Result<(), RoleServiceError> {
+        let roles = self.all()?;
+        if !roles.iter().any(|role| role.id == parent_id) {
+            return Err(RoleServiceError::ParentNotFound);
+        }
+        if Self::is_parent_cycle(&roles, role_id, parent_id) {
+            return Err(RoleServiceError::ParentCycle);
+        }
+        Ok(())
+    }
+
     pub fn first_by_code(&self, code: &str) -> Result<Option<Role>, RoleServiceError> {
         self.role_repository
             .get_ref()
@@ -113,6 +239,10 @@ impl RoleService {
     }
 
     pub fn delete_by_id(&self, id: u64) -> Result<(), RoleServiceError> {
+        self.role_repository
+            .get_ref()
+            .clear_parent_ids(&vec![id])
+            .map_err(|e| self.match_error(e))?;
         self.role_repository
             .get_ref()
             .delete_by_id(id)
@@ -125,6 +255,10 @@ impl RoleService {
     }
 
     pub fn delete_by_ids(&self, ids: &Vec<u64>) -> Result<(), RoleServiceError> {
+        self.role_repository
+            .get_ref()
+            .clear_parent_ids(ids)
+            .map_err(|e| self.match_error(e))?;
         self.role_repository
             .get_ref()
             .delete_by_ids(ids)
@@ -153,6 +287,69 @@ impl RoleService {
         self.paginate(params)
             .map_err(|_| error::ErrorInternalServerError(""))
     }
+
+    /// The codes of the roles granted by the external groups, the groups are compared case-insensitively.
+    pub fn map_groups_to_codes(groups: &Vec<String>, mapping: &Vec<(String, String)>) -> Vec<String> {
+        let mut codes: Vec<String> = Vec::new();
+        for (group, role_code) in mapping {
+            let is_member = groups.iter().any(|item| item.eq_ignore_ascii_case(group));
+            if is_member && !codes.contains(role_code) {
+                codes.push(role_code.to_owned());
+            }
+        }
+        codes
+    }
+
+    /// The roles from the mapping are managed by the external provider: they are granted by the groups
+    /// and revoked without them, the other roles of the user are kept.
+    pub fn sync_mapped_roles_ids(
+        &self,
+        roles_ids: &Option<Vec<u64>>,
+        mapping: &Vec<(String, String)>,
+        groups: &Vec<String>,
+    ) -> Result<Vec<u64>, RoleServiceError> {
+        let mut mapped_roles: Vec<(String, u64)> = Vec::new();
+        for (_, role_code) in mapping {
+            let Some(role) = self.first_by_code(role_code)? else {
+                log::error!("RoleService::sync_mapped_roles_ids - role {role_code} not found");
+                continue;
+            };
+            mapped_roles.push((role_code.to_owned(), role.id));
+        }
+
+        let granted_codes = Self::map_groups_to_codes(groups, mapping);
+        Ok(Self::merge_mapped_roles_ids(roles_ids, &mapped_roles, &granted_codes))
+    }
+
+    /// The mapped roles are pairs of the code and the id, they are kept only when granted by the codes.
+    pub fn merge_mapped_roles_ids(
+        roles_ids: &Option<Vec<u64>>,
+        mapped_roles: &[(String, u64)],
+        granted_codes: &[String],
+    ) -> Vec<u64> {
+        let mut managed_ids: Vec<u64> = Vec::new();
+        let mut granted_ids: Vec<u64> = Vec::new();
+        for (role_code, role_id) in mapped_roles {
+            managed_ids.push(*role_id);
+            if granted_codes.contains(role_code) && !granted_ids.contains(role_id) {
+                granted_ids.push(*role_id);
+            }
+        }
+
+        let mut result: Vec<u64> = roles_ids
+            .as_ref()
+            .unwrap_or(&Vec::new())
+            .iter()
+            .filter(|id| !managed_ids.contains(id) || granted_ids.contains(id))
+            .copied()
+            .collect();
+        for id in granted_ids {
+            if !result.contains(&id) {
+                result.push(id);
+            }
+        }
+        result
+    }
 }
 
 #[derive(Debug, Clone, Copy, Display, EnumString, PartialEq, Eq)]
@@ -160,6 +357,8 @@ pub enum RoleServiceError {
     DbConnectionFail,
     DuplicateCode,
     NotFound,
+    ParentNotFound,
+    ParentCycle,
     Fail,
 }
 
@@ -173,7 +372,170 @@ impl TranslatableError for RoleServiceError {
                 translator_service.translate(lang, "error.RoleServiceError.DuplicateCode")
             }
             Self::NotFound => translator_service.translate(lang, "error.RoleServiceError.NotFound"),
+            Self::ParentNotFound => {
+                translator_service.translate(lang, "error.RoleServiceError.ParentNotFound")
+            }
+            Self::ParentCycle => {
+                translator_service.translate(lang, "error.RoleServiceError.ParentCycle")
+            }
             _ => translator_service.translate(lang, "error.RoleServiceError.Fail"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_groups_to_codes() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::role::tests::map_groups_to_codes
+        let mapping = vec![
+            ("admins".to_string(), "admin".to_string()),
+            ("editors".to_string(), "editor".to_string()),
+            ("CN=Writers,OU=Groups,DC=example,DC=org".to_string(), "editor".to_string()),
+        ];
+        assert_eq!(
+            RoleService::map_groups_to_codes(
+                &vec!["admins".to_string(), "cn=writers,ou=groups,dc=example,dc=org".to_string()],
+                &mapping
+            ),
+            vec!["admin".to_string(), "editor".to_string()]
+        );
+        assert_eq!(
+            RoleService::map_groups_to_codes(&vec!["guests".to_string()], &mapping),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn merge_mapped_roles_ids() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::role::tests::merge_mapped_roles_ids
+        let mapping = vec![
+            ("admins".to_string(), "admin".to_string()),
+            ("editors".to_string(), "editor".to_string()),
+        ];
+        let mapped_roles = vec![("admin".to_string(), 1), ("editor".to_string(), 2)];
+        let merge = |roles_ids: Option<Vec<u64>>, groups: Vec<&str>| {
+            let groups: Vec<String> = groups.iter().map(|g| g.to_string()).collect();
+            let granted_codes = RoleService::map_groups_to_codes(&groups, &mapping);
+            RoleService::merge_mapped_roles_ids(&roles_ids, &mapped_roles, &granted_codes)
+        };
+
+        // The group without the mapping grants nothing and revokes the mapped roles, the others are kept.
+        assert_eq!(merge(None, vec!["guests"]), Vec::<u64>::new());
+        assert_eq!(merge(Some(vec![1, 2, 7]), vec!["guests"]), vec![7]);
+
+        assert_eq!(merge(Some(vec![7]), vec!["Editors"]), vec![7, 2]);
+        assert_eq!(merge(Some(vec![1, 7]), vec!["admins", "editors"]), vec![1, 7, 2]);
+        assert_eq!(merge(Some(vec![1, 2]), vec!["admins"]), vec![1]);
+    }
+
+    #[test]
+    fn permissions_of_roles() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::role::tests::permissions_of_roles
+        let mut editor = Role::default();
+        editor.id = 1;
+        editor.permissions = Some(vec!["files_show".to_string(), "users_show".to_string()]);
+        let mut auditor = Role::default();
+        auditor.id = 2;
+        auditor.permissions = Some(vec!["audit_log_show".to_string(), "unknown".to_string()]);
+        let roles = vec![editor, auditor];
+
+        let mut user = User::default();
+        assert_eq!(RoleService::permissions_of_roles(&user, &roles), Vec::<String>::new());
+
+        user.roles_ids = Some(vec![1]);
+        assert_eq!(
+            RoleService::permissions_of_roles(&user, &roles),
+            vec!["users_show".to_string(), "files_show".to_string()]
+        );
+
+        user.roles_ids = Some(vec![1, 2]);
+        assert_eq!(
+            RoleService::permissions_of_roles(&user, &roles),
+            vec!["users_show".to_string(), "files_show".to_string(), "audit_log_show".to_string()]
+        );
+
+        user.is_super_admin = true;
+        assert_eq!(
+            RoleService::permissions_of_roles(&user, &vec![]).len(),
+            Permission::VARIANTS.len()
+        );
+    }
+
+    fn make_role(id: u64, parent_id: Option<u64>, permissions: Vec<&str>) -> Role {
+        Role {
+            id,
+            parent_id,
+            permissions: Some(permissions.iter().map(|p| p.to_string()).collect()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn resolve_permissions() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::role::tests::resolve_permissions
+        assert!(Permission::VARIANTS.len() <= 64);
+
+        let mut roles = vec![
+            make_role(1, None, vec!["users_show"]),
+            make_role(2, Some(1), vec!["files_show"]),
+            make_role(3, Some(2), vec!["jobs_show"]),
+            // The cycle and the deleted parent end the chain.
+            make_role(4, Some(5), vec!["roles_show"]),
+            make_role(5, Some(4), vec!["roles_create"]),
+            make_role(6, Some(100), vec![]),
+        ];
+        RoleService::resolve_permissions(&mut roles);
+
+        assert_eq!(RoleService::ancestors_ids(&roles, 3), vec![2, 1]);
+        assert_eq!(RoleService::ancestors_ids(&roles, 4), vec![5]);
+        assert_eq!(RoleService::ancestors_ids(&roles, 6), Vec::<u64>::new());
+        assert!(roles[2].has_permission(&Permission::UsersShow));
+        assert!(roles[2].has_permission(&Permission::JobsShow));
+        assert!(!roles[1].has_permission(&Permission::JobsShow));
+        assert!(roles[3].has_permission(&Permission::RolesCreate));
+        assert_eq!(roles[5].effective_permissions, Some(0));
+
+        let user = User {
+            roles_ids: Some(vec![3]),
+            ..Default::default()
+        };
+        assert!(crate::UserPolicy::can_show(&user, &roles));
+        assert!(!crate::RolePolicy::can_show(&user, &roles));
+        assert_eq!(
+            RoleService::permissions_of_roles(&user, &roles),
+            vec!["users_show".to_string(), "files_show".to_string(), "jobs_show".to_string()]
+        );
+    }
+
+    #[test]
+    fn is_parent_cycle() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::role::tests::is_parent_cycle
+        let roles = vec![
+            make_role(1, None, vec![]),
+            make_role(2, Some(1), vec![]),
+            make_role(3, Some(2), vec![]),
+        ];
+        assert!(RoleService::is_parent_cycle(&roles, 1, 1));
+        assert!(RoleService::is_parent_cycle(&roles, 1, 3));
+        assert!(RoleService::is_parent_cycle(&roles, 2, 3));
+        assert!(!RoleService::is_parent_cycle(&roles, 3, 1));
+        assert!(!RoleService::is_parent_cycle(&roles, 0, 3));
+    }
+
+    #[test]
+    fn effective_permissions() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::role::tests::effective_permissions
+        let roles = vec![
+            make_role(1, None, vec!["users_show", "files_show"]),
+            make_role(2, Some(1), vec!["files_show", "unknown"]),
+        ];
+        assert_eq!(
+            RoleService::effective_permissions(&roles, 2),
+            vec![("users_show".to_string(), 1), ("files_show".to_string(), 2)]
+        );
+        assert_eq!(RoleService::effective_permissions(&roles, 100), Vec::new());
+    }
+}
@@ -1,26 +1,30 @@
-use crate::helpers::now_date_time_str;
+use crate::helpers::{now_date_time_str, DATE_TIME_FORMAT};
 use crate::{
-    make_select_mysql_query, make_update_mysql_query, AppError, AuthServiceError, File, FileColumn,
+    make_select_mysql_query, make_update_mysql_query, AppError, AuthServiceError, Config, File, FileColumn,
     FileFilter, FileServiceError, HashService, MysqlRepository, PaginationResult,
     TranslatableError, TranslatorService, User, UserColumn, UserCredentials, UserCredentialsColumn,
-    UserFileFilter, UserFilter, UserMysqlRepository, UserPaginateParams,
+    UserFileFilter, UserFilter, UserMysqlRepository, UserPaginateParams, UserSort,
 };
 use actix_web::web::Data;
 use actix_web::{error, Error};
+use chrono::{NaiveDateTime, Utc};
 use mysql::{params, Row};
 use strum_macros::{Display, EnumString};
 
 pub struct UserService {
+    config: Data<Config>,
     hash_service: Data<HashService>,
     user_repository: Data<UserMysqlRepository>,
 }
 
 impl UserService {
     pub fn new(
+        config: Data<Config>,
         hash_service: Data<HashService>,
         user_repository: Data<UserMysqlRepository>,
     ) -> Self {
         Self {
+            config,
             hash_service,
             user_repository,
         }
@@ -54,6 +58,27 @@ impl UserService {
         Err(error::ErrorNotFound(""))
     }
 
+    pub fn all(&self) -> Result<Vec<User>, UserServiceError> {
+        let sorts = vec![UserSort::IdAsc];
+        self.user_repository
+            .get_ref()
+            .all(None, Some(&sorts), &None)
+            .map_err(|e| self.match_error(e))
+    }
+
+    pub fn all_by_ids(&self, ids: &Vec<u64>) -> Result<Vec<User>, UserServiceError> {
+        let filters = vec![UserFilter::Ids(ids.to_owned())];
+        self.user_repository
+            .get_ref()
+            .all(Some(&filters), None, &None)
+            .map_err(|e| self.match_error(e))
+    }
+
+    pub fn all_by_ids_throw_http(&self, ids: &Vec<u64>) -> Result<Vec<User>, Error> {
+        self.all_by_ids(ids)
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+
     pub fn first_by_email(&self, email: &str) -> Result<Option<User>, UserServiceError> {
         self.user_repository
             .get_ref()
@@ -129,20 +154,85 @@ impl UserService {
 
     pub fn upsert(
         &self,
-        data: User,
+        mut data: User,
         columns: &Option<Vec<UserColumn>>,
     ) -> Result<(), UserServiceError> {
         if data.id == 0 {
+            // The users created by the administrators do not confirm the email.
+            if data.email_verified_at.is_none() {
+                data.email_verified_at = Some(now_date_time_str());
+            }
             self.create(data)
         } else {
             self.update(&data, columns)
         }
     }
 
+    pub fn mark_email_verified_by_id(&self, id: u64) -> Result<(), UserServiceError> {
+        let mut data = User::default();
+        data.id = id;
+        data.email_verified_at = Some(now_date_time_str());
+        self.update(&data, &Some(vec![UserColumn::EmailVerifiedAt]))
+    }
+
+    pub fn all_ldap(&self) -> Result<Vec<User>, UserServiceError> {
+        let filters = vec![UserFilter::IsLdap];
+        self.user_repository
+            .get_ref()
+            .all(Some(&filters), None, &None)
+            .map_err(|e| self.match_error(e))
+    }
+
+    pub fn all_invited(&self) -> Result<Vec<User>, UserServiceError> {
+        let filters = vec![UserFilter::IsInvited];
+        let sorts = vec![UserSort::IdDesc];
+        self.user_repository
+            .get_ref()
+            .all(Some(&filters), Some(&sorts), &None)
+            .map_err(|e| self.match_error(e))
+    }
+
+    pub fn all_invited_throw_http(&self) -> Result<Vec<User>, Error> {
+        self.all_invited()
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+
+    /// The disabled users can not log in, their sessions stop working.
+    pub fn set_disabled_by_id(&self, id: u64, is_disabled: bool) -> Result<(), UserServiceError> {
+        let mut data = User::default();
+        data.id = id;
+        data.disabled_at = if is_disabled { Some(now_date_time_str()) } else { None };
+        self.update(&data, &Some(vec![UserColumn::DisabledAt]))
+    }
+
     pub fn update_password_by_id(&self, id: u64, password: &str) -> Result<(), UserServiceError> {
+        let credentials = self
+            .user_repository
+            .get_ref()
+            .first_credentials_by_id(id)
+            .map_err(|e| self.match_error(e))?
+            .ok_or(UserServiceError::NotFound)?;
+
+        self.change_password(credentials, password)
+    }
+
+    pub fn update_password_by_email(
+        &self,
+        email: &str,
+        password: &str,
+    ) -> Result<(), UserServiceError> {
+        let credentials = self
+            .first_credentials_by_email(email)?
+            .ok_or(UserServiceError::NotFound)?;
+
+        self.change_password(credentials, password)
+    }
+
+    /// The password of the user is set again with the new hash parameters, it is not a change of the password.
+    pub fn rehash_password_by_id(&self, id: u64, password: &str) -> Result<(), UserServiceError> {
         let hash_service = self.hash_service.get_ref();
         let password = hash_service.hash_password(password).map_err(|e| {
-            log::error!("UserService::update_password_by_id - {e}");
+            log::error!("UserService::rehash_password_by_id - {e}");
             UserServiceError::PasswordHashFail
         })?;
 
@@ -152,23 +242,73 @@ impl UserService {
             .map_err(|e| self.match_error(e))
     }
 
-    pub fn update_password_by_email(
+    /// The last passwords from the history can not be set again, the current one is the newest of them.
+    fn change_password(
         &self,
-        email: &str,
+        credentials: UserCredentials,
         password: &str,
     ) -> Result<(), UserServiceError> {
         let hash_service = self.hash_service.get_ref();
+        let history_size = self.config.get_ref().auth.password.history;
+
+        let mut password_history: Vec<String> = Vec::new();
+        if history_size > 0 {
+            if let Some(current) = credentials.password {
+                password_history.push(current);
+            }
+            if let Some(previous) = credentials.password_history {
+                password_history.extend(previous);
+            }
+            password_history.truncate(history_size);
+
+            for hash in &password_history {
+                let is_reused = hash_service.verify_password(password, hash).unwrap_or_else(|e| {
+                    log::error!("UserService::change_password - {e}");
+                    false
+                });
+                if is_reused {
+                    return Err(UserServiceError::PasswordReused);
+                }
+            }
+            // The current password is stored separately from the history after the change.
+            password_history.truncate(history_size - 1);
+        }
+
         let hashed_password = hash_service.hash_password(password).map_err(|e| {
-            log::error!("UserService::update_password_by_email - {e}");
+            log::error!("UserService::change_password - {e}");
             UserServiceError::PasswordHashFail
         })?;
+        let password_history = if password_history.len() == 0 {
+            None
+        } else {
+            Some(password_history)
+        };
 
         self.user_repository
             .get_ref()
-            .update_password_by_email(email, &hashed_password)
+            .change_password_by_id(credentials.id, &hashed_password, &password_history)
             .map_err(|e| self.match_error(e))
     }
 
+    /// The password is older than the max age from the config, the directory users are not checked.
+    pub fn is_password_expired(&self, user: &User) -> bool {
+        let max_age = self.config.get_ref().auth.password.max_age;
+        if max_age <= 0 || user.is_ldap() {
+            return false;
+        }
+        let Some(password_changed_at) = &user.password_changed_at else {
+            return false;
+        };
+
+        match NaiveDateTime::parse_from_str(password_changed_at, DATE_TIME_FORMAT) {
+            Ok(changed_at) => changed_at.and_utc().timestamp() + max_age <= Utc::now().timestamp(),
+            Err(e) => {
+                log::error!("UserService::is_password_expired - {e}");
+                false
+            }
+        }
+    }
+
     pub fn delete_by_id(&self, id: u64) -> Result<(), UserServiceError> {
         self.user_repository
             .get_ref()
@@ -217,6 +357,7 @@ pub enum UserServiceError {
     DbConnectionFail,
     DuplicateEmail,
     PasswordHashFail,
+    PasswordReused,
     NotFound,
     Fail,
 }
@@ -233,6 +374,9 @@ impl TranslatableError for UserServiceError {
             Self::PasswordHashFail => {
                 translator_service.translate(lang, "error.UserServiceError.PasswordHashFail")
             }
+            Self::PasswordReused => {
+                translator_service.translate(lang, "error.UserServiceError.PasswordReused")
+            }
             Self::NotFound => translator_service.translate(lang, "error.UserServiceError.NotFound"),
             _ => translator_service.translate(lang, "error.UserServiceError.Fail"),
         }
@@ -1,8 +1,9 @@
 use crate::helpers::now_date_time_str;
 use crate::{
-    AppError, Config, Disk, DiskLocalRepository, File, FileServiceError, MysqlRepository,
-    TranslatableError, TranslatorService, User, UserColumn, UserFile, UserFileColumn,
-    UserFileFilter, UserFileMysqlRepository, UserFileSort, UserFilter, UserServiceError,
+    AppError, Config, Disk, DiskLocalRepository, DiskS3Repository, File, FileServiceError, MysqlRepository,
+    PaginationResult, TranslatableError, TranslatorService, User, UserColumn, UserFile,
+    UserFileColumn, UserFileFilter, UserFileMysqlRepository, UserFilePaginateParams, UserFileSort,
+    UserFilter, UserServiceError,
 };
 use actix_web::web::Data;
 use actix_web::{error, Error};
@@ -12,6 +13,7 @@ pub struct UserFileService {
     config: Data<Config>,
     user_file_repository: Data<UserFileMysqlRepository>,
     disk_local_repository: Data<DiskLocalRepository>,
+    disk_s3_repository: Data<DiskS3Repository>,
 }
 
 impl UserFileService {
@@ -19,11 +21,13 @@ impl UserFileService {
         config: Data<Config>,
         user_file_repository: Data<UserFileMysqlRepository>,
         disk_local_repository: Data<DiskLocalRepository>,
+        disk_s3_repository: Data<DiskS3Repository>,
     ) -> Self {
         Self {
             config,
             user_file_repository,
             disk_local_repository,
+            disk_s3_repository,
         }
     }
 
@@ -148,6 +152,12 @@ impl UserFileService {
                 return Some(public_path);
             }
         }
+
+        if Disk::S3.to_string().eq(&user_file.disk) {
+            if let Some(path) = &user_file.path {
+                return self.disk_s3_repository.get_ref().url(path).ok();
+            }
+        }
         None
     }
 
@@ -204,6 +214,24 @@ impl UserFileService {
             .map_err(|_| error::ErrorInternalServerError(""))
     }
 
+    pub fn paginate(
+        &self,
+        params: &UserFilePaginateParams,
+    ) -> Result<PaginationResult<UserFile>, UserFileServiceError> {
+        self.user_file_repository
+            .get_ref()
+            .paginate(params)
+            .map_err(|e| self.log_error("paginate", e.to_string(), UserFileServiceError::Fail))
+    }
+
+    pub fn paginate_throw_http(
+        &self,
+        params: &UserFilePaginateParams,
+    ) -> Result<PaginationResult<UserFile>, Error> {
+        self.paginate(params)
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+
     pub fn make_public_filename(&self, user_id: u64, filename: &str) -> String {
         let mut str = user_id.to_string();
         str.push('-');
@@ -216,18 +244,26 @@ impl UserFileService {
         user_file: &mut UserFile,
         file: &File,
     ) -> Result<bool, UserFileServiceError> {
-        if user_file.disk.ne(Disk::Local.to_string().as_str()) {
-            return Err(UserFileServiceError::Fail);
-        }
-        let disk_local_repository = self.disk_local_repository.get_ref();
         let mut is_updated = false;
         let filename = Some(self.make_public_filename(user_file.user_id, &file.filename));
 
-        let public_path = disk_local_repository
-            .set_public(&file.path, user_file.is_public, filename.clone())
-            .map_err(|e| {
-                self.log_error("apply_is_public", e.to_string(), UserFileServiceError::Fail)
-            })?;
+        let public_path = if user_file.disk.eq(Disk::Local.to_string().as_str()) {
+            self.disk_local_repository
+                .get_ref()
+                .set_public(&file.path, user_file.is_public, filename.clone())
+                .map_err(|e| {
+                    self.log_error("apply_is_public", e.to_string(), UserFileServiceError::Fail)
+                })?
+        } else if user_file.disk.eq(Disk::S3.to_string().as_str()) {
+            // The object stays in place, the url for it is made on every request.
+            if user_file.is_public {
+                Some(file.path.to_owned())
+            } else {
+                None
+            }
+        } else {
+            return Err(UserFileServiceError::Fail);
+        };
 
         if user_file.is_public {
             if filename.ne(&user_file.filename) {
@@ -0,0 +1,1067 @@
+use crate::app::validator::rules::contains_vec_str::ContainsVecStr;
+use crate::app::validator::rules::email::Email;
+use crate::app::validator::rules::required::Required;
+use crate::app::validator::rules::str_max_chars_count::StrMaxCharsCount;
+use crate::{
+    InvitationService, KeyValueService, LocaleService, RandomService, RoleService,
+    TranslatableError, TranslatorService, User, UserColumn, UserImportFile, UserImportPreview,
+    UserImportPreviewRow, UserImportResult, UserImportRow, UserImportStatus, UserService,
+    USER_IMPORT_COLUMNS, USER_IMPORT_MAX_ROWS,
+};
+use actix_web::web::Data;
+use actix_web::{error, Error};
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+use strum_macros::{Display, EnumString};
+use zip::ZipArchive;
+
+// The parsed file waits for the confirmation of the preview.
+const USER_IMPORT_KEY: &'static str = "user_import";
+const USER_IMPORT_TTL: u64 = 3600;
+const USER_IMPORT_KEY_LENGTH: usize = 32;
+
+pub struct UserImportService {
+    translator_service: Data<TranslatorService>,
+    locale_service: Data<LocaleService>,
+    user_service: Data<UserService>,
+    role_service: Data<RoleService>,
+    invitation_service: Data<InvitationService>,
+    key_value_service: Data<KeyValueService>,
+    random_service: Data<RandomService>,
+}
+
+impl UserImportService {
+    pub fn new(
+        translator_service: Data<TranslatorService>,
+        locale_service: Data<LocaleService>,
+        user_service: Data<UserService>,
+        role_service: Data<RoleService>,
+        invitation_service: Data<InvitationService>,
+        key_value_service: Data<KeyValueService>,
+        random_service: Data<RandomService>,
+    ) -> Self {
+        Self {
+            translator_service,
+            locale_service,
+            user_service,
+            role_service,
+            invitation_service,
+            key_value_service,
+            random_service,
+        }
+    }
+
+    pub fn get_service_name(&self) -> &str {
+        "UserImportService"
+    }
+
+    pub fn log_error(
+        &self,
+        method: &str,
+        error: String,
+        e: UserImportServiceError,
+    ) -> UserImportServiceError {
+        let service_name = self.get_service_name().to_string();
+        log::error!("{}::{} - {}", service_name, method, error);
+        e
+    }
+
+    /// Reads the CSV or the XLSX file, the format is detected by the content.
+    pub fn parse(&self, bytes: &[u8]) -> Result<UserImportFile, UserImportServiceError> {
+        let table = if bytes.starts_with(b"PK\x03\x04") {
+            parse_xlsx(bytes)?
+        } else {
+            parse_csv(bytes)?
+        };
+        map_table(table, &self.header_codes())
+    }
+
+    /// The header is the column code or the column label of the users list in any language.
+    fn header_codes(&self) -> HashMap<String, &'static str> {
+        let translator_service = self.translator_service.get_ref();
+        let mut codes: HashMap<String, &'static str> = HashMap::new();
+        for code in USER_IMPORT_COLUMNS {
+            codes.insert(code.to_string(), code);
+            for lang in self.locale_service.get_ref().get_locales_codes_ref() {
+                let label = translator_service
+                    .translate(lang, &format!("page.users.index.columns.{}", code));
+                codes.entry(normalize_header(&label)).or_insert(code);
+            }
+        }
+        codes
+    }
+
+    /// Validates every row with the rules of the user form and finds the existing users by the email.
+    pub fn preview(
+        &self,
+        file: &UserImportFile,
+        lang: &str,
+        can_set_roles: bool,
+    ) -> Result<UserImportPreview, UserImportServiceError> {
+        let translator_service = self.translator_service.get_ref();
+        let user_service = self.user_service.get_ref();
+
+        let roles_ids: HashMap<String, u64> = self
+            .role_service
+            .get_ref()
+            .all()
+            .map_err(|e| self.log_error("preview", e.to_string(), UserImportServiceError::Fail))?
+            .into_iter()
+            .map(|role| (role.code, role.id))
+            .collect();
+        let str_locales = self.locale_service.get_ref().get_locales_codes_ref();
+
+        let email_str = translator_service.translate(lang, "page.users.create.fields.email");
+        let surname_str = translator_service.translate(lang, "page.users.create.fields.surname");
+        let name_str = translator_service.translate(lang, "page.users.create.fields.name");
+        let patronymic_str =
+            translator_service.translate(lang, "page.users.create.fields.patronymic");
+        let locale_str = translator_service.translate(lang, "page.users.create.fields.locale");
+
+        let is_roles = can_set_roles && file.has_column("roles");
+        let mut lines_by_email: HashMap<String, usize> = HashMap::new();
+        let mut preview = UserImportPreview::default();
+
+        for row in &file.rows {
+            let mut errors: Vec<String> = Required::validated(
+                translator_service,
+                lang,
+                &row.email,
+                |value| Email::validate(translator_service, lang, value, &email_str),
+                &email_str,
+            );
+
+            if let Some(email) = &row.email {
+                let email = email.to_lowercase();
+                if let Some(line) = lines_by_email.get(&email) {
+                    let line = line.to_string();
+                    let mut vars = HashMap::new();
+                    vars.insert("line", line.as_str());
+                    errors.push(translator_service.variables(
+                        lang,
+                        "page.users.import.errors.duplicate",
+                        &vars,
+                    ));
+                } else {
+                    lines_by_email.insert(email, row.line);
+                }
+            }
+
+            if let Some(locale) = &row.locale {
+                errors.append(&mut ContainsVecStr::validate(
+                    translator_service,
+                    lang,
+                    locale,
+                    &str_locales,
+                    &locale_str,
+                ));
+            }
+            for (value, label) in [
+                (&row.surname, &surname_str),
+                (&row.name, &name_str),
+                (&row.patronymic, &patronymic_str),
+            ] {
+                if let Some(value) = value {
+                    errors.append(&mut StrMaxCharsCount::validate(
+                        translator_service,
+                        lang,
+                        value,
+                        255,
+                        label,
+                    ));
+                }
+            }
+
+            let mut row_roles_ids: Option<Vec<u64>> = None;
+            if is_roles {
+                let mut ids: Vec<u64> = Vec::new();
+                for code in &row.roles {
+                    match roles_ids.get(code) {
+                        Some(id) if !ids.contains(id) => ids.push(*id),
+                        Some(_) => {}
+                        None => {
+                            let mut vars = HashMap::new();
+                            vars.insert("code", code.as_str());
+                            errors.push(translator_service.variables(
+                                lang,
+                                "page.users.import.errors.role_not_found",
+                                &vars,
+                            ));
+                        }
+                    }
+                }
+                if !ids.is_empty() {
+                    row_roles_ids = Some(ids);
+                }
+            }
+
+            let mut user: Option<User> = None;
+            if errors.is_empty() {
+                let email = row.email.as_deref().unwrap_or("");
+                user = user_service.first_by_email(email).map_err(|e| {
+                    self.log_error("preview", e.to_string(), UserImportServiceError::Fail)
+                })?;
+            }
+
+            let status = if !errors.is_empty() {
+                preview.skipped += 1;
+                UserImportStatus::Skip
+            } else if let Some(user) = &user {
+                let (data, _) = merge_user(file, row, &row_roles_ids, is_roles, user);
+                if is_same_user(&data, user) {
+                    preview.skipped += 1;
+                    UserImportStatus::Skip
+                } else {
+                    preview.updated += 1;
+                    UserImportStatus::Update
+                }
+            } else {
+                preview.created += 1;
+                UserImportStatus::Create
+            };
+
+            preview.rows.push(UserImportPreviewRow {
+                row: row.to_owned(),
+                status,
+                errors,
+                user,
+                roles_ids: row_roles_ids,
+            });
+        }
+
+        Ok(preview)
+    }
+
+    pub fn preview_throw_http(
+        &self,
+        file: &UserImportFile,
+        lang: &str,
+        can_set_roles: bool,
+    ) -> Result<UserImportPreview, Error> {
+        self.preview(file, lang, can_set_roles)
+            .map_err(|e| e.to_http())
+    }
+
+    /// Creates and updates the valid rows, the new users are invited if it is asked.
+    /// The rows are validated again, the users could be changed after the preview.
+    pub fn import(
+        &self,
+        file: &UserImportFile,
+        lang: &str,
+        can_set_roles: bool,
+        invite: bool,
+    ) -> Result<UserImportResult, UserImportServiceError> {
+        let user_service = self.user_service.get_ref();
+        let is_roles = can_set_roles && file.has_column("roles");
+        let preview = self.preview(file, lang, can_set_roles)?;
+
+        let mut result = UserImportResult {
+            skipped: preview.skipped,
+            ..Default::default()
+        };
+
+        for preview_row in preview.rows {
+            let row = &preview_row.row;
+            let email = row.email.to_owned().unwrap_or_default();
+            let upserted = match (&preview_row.status, &preview_row.user) {
+                (UserImportStatus::Create, _) => {
+                    let data = User {
+                        email: email.to_owned(),
+                        surname: row.surname.to_owned(),
+                        name: row.name.to_owned(),
+                        patronymic: row.patronymic.to_owned(),
+                        locale: row.locale.to_owned(),
+                        roles_ids: if is_roles {
+                            preview_row.roles_ids.to_owned()
+                        } else {
+                            None
+                        },
+                        ..Default::default()
+                    };
+                    user_service.upsert(data, &None)
+                }
+                (UserImportStatus::Update, Some(user)) => {
+                    let (data, columns) =
+                        merge_user(file, row, &preview_row.roles_ids, is_roles, user);
+                    user_service.upsert(data, &Some(columns))
+                }
+                _ => continue,
+            };
+
+            if let Err(e) = upserted {
+                self.log_error("import", format!("line {} - {}", row.line, e), UserImportServiceError::Fail);
+                result.skipped += 1;
+                result.failed_lines.push(row.line);
+                continue;
+            }
+
+            let user = user_service
+                .first_by_email(&email)
+                .map_err(|e| self.log_error("import", e.to_string(), UserImportServiceError::Fail))?
+                .ok_or(UserImportServiceError::Fail)?;
+
+            match preview_row.user {
+                Some(old_user) => result.updated.push((old_user, user)),
+                None => {
+                    if invite {
+                        if let Err(e) = self.invitation_service.get_ref().invite(user.id, lang) {
+                            self.log_error("import", format!("line {} - {}", row.line, e), UserImportServiceError::Fail);
+                            result.failed_lines.push(row.line);
+                        }
+                    }
+                    result.created.push(user);
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub fn import_throw_http(
+        &self,
+        file: &UserImportFile,
+        lang: &str,
+        can_set_roles: bool,
+        invite: bool,
+    ) -> Result<UserImportResult, Error> {
+        self.import(file, lang, can_set_roles, invite)
+            .map_err(|e| e.to_http())
+    }
+
+    /// Keeps the parsed file until the preview is confirmed, returns the key of the confirmation form.
+    pub fn stage(&self, user: &User, file: &UserImportFile) -> Result<String, UserImportServiceError> {
+        let key = self.random_service.get_ref().str(USER_IMPORT_KEY_LENGTH);
+        let value = serde_json::to_string(file)
+            .map_err(|e| self.log_error("stage", e.to_string(), UserImportServiceError::Fail))?;
+        self.key_value_service
+            .get_ref()
+            .set_ex(self.make_key(user, &key), value, USER_IMPORT_TTL)
+            .map_err(|e| self.log_error("stage", e.to_string(), UserImportServiceError::Fail))?;
+        Ok(key)
+    }
+
+    pub fn stage_throw_http(&self, user: &User, file: &UserImportFile) -> Result<String, Error> {
+        self.stage(user, file).map_err(|e| e.to_http())
+    }
+
+    /// The staged file is available to the user who uploaded it only.
+    pub fn first_staged(
+        &self,
+        user: &User,
+        key: &str,
+    ) -> Result<UserImportFile, UserImportServiceError> {
+        let value: Option<String> = self
+            .key_value_service
+            .get_ref()
+            .get(self.make_key(user, key))
+            .map_err(|e| self.log_error("first_staged", e.to_string(), UserImportServiceError::Fail))?;
+        let value = value.ok_or(UserImportServiceError::NotFound)?;
+        serde_json::from_str(&value).map_err(|e| {
+            self.log_error("first_staged", e.to_string(), UserImportServiceError::Fail)
+        })
+    }
+
+    pub fn first_staged_throw_http(&self, user: &User, key: &str) -> Result<UserImportFile, Error> {
+        self.first_staged(user, key).map_err(|e| e.to_http())
+    }
+
+    pub fn forget_staged(&self, user: &User, key: &str) {
+        if let Err(e) = self.key_value_service.get_ref().del(self.make_key(user, key)) {
+            self.log_error("forget_staged", e.to_string(), UserImportServiceError::Fail);
+        }
+    }
+
+    fn make_key(&self, user: &User, key: &str) -> String {
+        format!("{}.{}.{}", USER_IMPORT_KEY, user.id, key)
+    }
+}
+
+/// The existing user with the values of the columns present in the file.
+fn merge_user(
+    file: &UserImportFile,
+    row: &UserImportRow,
+    roles_ids: &Option<Vec<u64>>,
+    is_roles: bool,
+    user: &User,
+) -> (User, Vec<UserColumn>) {
+    let mut data = user.to_owned();
+    let mut columns: Vec<UserColumn> = Vec::new();
+    let values = [
+        ("surname", &row.surname, &mut data.surname, UserColumn::Surname),
+        ("name", &row.name, &mut data.name, UserColumn::Name),
+        ("patronymic", &row.patronymic, &mut data.patronymic, UserColumn::Patronymic),
+        ("locale", &row.locale, &mut data.locale, UserColumn::Locale),
+    ];
+    for (code, value, field, column) in values {
+        if file.has_column(code) {
+            *field = value.to_owned();
+            columns.push(column);
+        }
+    }
+    if is_roles {
+        data.roles_ids = roles_ids.to_owned();
+        columns.push(UserColumn::RolesIds);
+    }
+    (data, columns)
+}
+
+fn is_same_user(a: &User, b: &User) -> bool {
+    let sorted_roles = |user: &User| {
+        let mut roles_ids = user.roles_ids.to_owned().unwrap_or_default();
+        roles_ids.sort();
+        roles_ids
+    };
+    a.surname.eq(&b.surname)
+        && a.name.eq(&b.name)
+        && a.patronymic.eq(&b.patronymic)
+        && a.locale.eq(&b.locale)
+        && sorted_roles(a).eq(&sorted_roles(b))
+}
+
+fn normalize_header(value: &str) -> String {
+    value.trim().to_lowercase()
+}
+
+/// The first row is the header, the empty rows are skipped.
+pub fn map_table(
+    table: Vec<(usize, Vec<String>)>,
+    header_codes: &HashMap<String, &'static str>,
+) -> Result<UserImportFile, UserImportServiceError> {
+    let mut table = table.into_iter();
+    let (_, header) = table.next().ok_or(UserImportServiceError::Empty)?;
+
+    let mut file = UserImportFile::default();
+    // The index of the cell of every known column, the repeated column is ignored.
+    let mut indexes: Vec<(usize, &'static str)> = Vec::new();
+    for (index, label) in header.iter().enumerate() {
+        if let Some(code) = header_codes.get(&normalize_header(label)) {
+            if !file.has_column(code) {
+                file.columns.push(code.to_string());
+                indexes.push((index, code));
+            }
+        }
+    }
+    if !file.has_column("email") {
+        return Err(UserImportServiceError::EmailColumnMissing);
+    }
+
+    for (line, cells) in table {
+        if cells.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+        if file.rows.len() >= USER_IMPORT_MAX_ROWS {
+            return Err(UserImportServiceError::TooManyRows);
+        }
+        let mut row = UserImportRow {
+            line,
+            ..Default::default()
+        };
+        for (index, code) in &indexes {
+            let value = cells
+                .get(*index)
+                .map(|cell| cell.trim())
+                .filter(|cell| !cell.is_empty())
+                .map(|cell| cell.to_string());
+            match *code {
+                "email" => row.email = value,
+                "surname" => row.surname = value,
+                "name" => row.name = value,
+                "patronymic" => row.patronymic = value,
+                "locale" => row.locale = value,
+                "roles" => {
+                    row.roles = value
+                        .unwrap_or_default()
+                        .split([',', ';'])
+                        .map(|code| code.trim())
+                        .filter(|code| !code.is_empty())
+                        .map(|code| code.to_string())
+                        .collect()
+                }
+                _ => {}
+            }
+        }
+        file.rows.push(row);
+    }
+
+    if file.rows.is_empty() {
+        return Err(UserImportServiceError::Empty);
+    }
+    Ok(file)
+}
+
+/// The rows with the numbers of their first lines. The delimiter is the comma, the semicolon
+/// or the tab, the one found most often in the header.
+pub fn parse_csv(bytes: &[u8]) -> Result<Vec<(usize, Vec<String>)>, UserImportServiceError> {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    let text = std::str::from_utf8(bytes).map_err(|_| UserImportServiceError::FileInvalid)?;
+
+    let header = text.lines().next().unwrap_or("");
+    let delimiter = [',', ';', '\t']
+        .into_iter()
+        .max_by_key(|delimiter| header.matches(*delimiter).count())
+        .unwrap_or(',');
+
+    let mut rows: Vec<(usize, Vec<String>)> = Vec::new();
+    let mut cells: Vec<String> = Vec::new();
+    let mut cell = String::new();
+    let mut is_quoted = false;
+    let mut is_cell_quoted = false;
+    let mut line = 1;
+    let mut row_line = 1;
+    let mut chars = text.chars().peekable();
+
+    let push_cell = |cells: &mut Vec<String>, cell: &mut String, is_cell_quoted: bool| {
+        let mut value = std::mem::take(cell);
+        // The export prefixes the formulas with a quote.
+        if !is_cell_quoted && value.starts_with('\'') && value[1..].starts_with(['=', '+', '-', '@']) {
+            value.remove(0);
+        }
+        cells.push(value);
+    };
+
+    while let Some(c) = chars.next() {
+        if is_quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    cell.push('"');
+                }
+                '"' => is_quoted = false,
+                '\n' => {
+                    line += 1;
+                    cell.push(c);
+                }
+                _ => cell.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if cell.is_empty() => {
+                is_quoted = true;
+                is_cell_quoted = true;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\r' | '\n' => {
+                push_cell(&mut cells, &mut cell, is_cell_quoted);
+                rows.push((row_line, std::mem::take(&mut cells)));
+                is_cell_quoted = false;
+                line += 1;
+                row_line = line;
+            }
+            c if c == delimiter => {
+                push_cell(&mut cells, &mut cell, is_cell_quoted);
+                is_cell_quoted = false;
+            }
+            _ => cell.push(c),
+        }
+    }
+    if is_quoted {
+        return Err(UserImportServiceError::FileInvalid);
+    }
+    if !cell.is_empty() || !cells.is_empty() {
+        push_cell(&mut cells, &mut cell, is_cell_quoted);
+        rows.push((row_line, cells));
+    }
+    Ok(rows)
+}
+
+/// The rows of the first sheet with their numbers, the shared and the inline strings are supported.
+pub fn parse_xlsx(bytes: &[u8]) -> Result<Vec<(usize, Vec<String>)>, UserImportServiceError> {
+    let mut archive =
+        ZipArchive::new(Cursor::new(bytes)).map_err(|_| UserImportServiceError::FileInvalid)?;
+
+    let shared_strings = match read_zip_file(&mut archive, "xl/sharedStrings.xml") {
+        Ok(xml) => parse_xlsx_shared_strings(&xml),
+        Err(_) => Vec::new(),
+    };
+
+    let mut sheet_name = "xl/worksheets/sheet1.xml".to_string();
+    if archive.index_for_name(&sheet_name).is_none() {
+        let mut names: Vec<&str> = archive
+            .file_names()
+            .filter(|name| name.starts_with("xl/worksheets/") && name.ends_with(".xml"))
+            .collect();
+        names.sort();
+        sheet_name = names
+            .first()
+            .ok_or(UserImportServiceError::FileInvalid)?
+            .to_string();
+    }
+    let xml = read_zip_file(&mut archive, &sheet_name)?;
+
+    let mut rows: Vec<(usize, Vec<String>)> = Vec::new();
+    let mut row: Option<(usize, Vec<String>)> = None;
+    // The index, the type and the value of the current cell.
+    let mut cell: Option<(usize, String, String)> = None;
+    let mut is_text = false;
+    for node in XmlNodes::new(&xml) {
+        match node {
+            XmlNode::Open(name, attributes, is_closed) => match name {
+                "row" => {
+                    let number = xml_attribute(attributes, "r")
+                        .and_then(|r| r.parse::<usize>().ok())
+                        .unwrap_or(rows.len() + 1);
+                    row = Some((number, Vec::new()));
+                    if is_closed {
+                        rows.extend(row.take());
+                    }
+                }
+                "c" => {
+                    let index = match (xml_attribute(attributes, "r"), &row) {
+                        (Some(r), _) => xlsx_column_index(&r),
+                        (None, Some((_, cells))) => Some(cells.len()),
+                        _ => None,
+                    };
+                    if let Some(index) = index {
+                        let kind = xml_attribute(attributes, "t").unwrap_or_default();
+                        cell = if is_closed {
+                            None
+                        } else {
+                            Some((index, kind, String::new()))
+                        };
+                    }
+                }
+                "v" | "t" if !is_closed => is_text = true,
+                _ => {}
+            },
+            XmlNode::Text(text) if is_text => {
+                if let Some((_, _, value)) = &mut cell {
+                    value.push_str(&xml_unescape(text));
+                }
+            }
+            XmlNode::Close(name) => match name {
+                "v" | "t" => is_text = false,
+                "c" => {
+                    if let (Some((index, kind, value)), Some((_, cells))) = (cell.take(), &mut row) {
+                        let value = match kind.as_str() {
+                            "s" => value
+                                .trim()
+                                .parse::<usize>()
+                                .ok()
+                                .and_then(|i| shared_strings.get(i).cloned())
+                                .unwrap_or_default(),
+                            _ => value,
+                        };
+                        if cells.len() <= index {
+                            cells.resize(index + 1, String::new());
+                        }
+                        cells[index] = value;
+                    }
+                }
+                "row" => rows.extend(row.take()),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    Ok(rows)
+}
+
+fn read_zip_file(
+    archive: &mut ZipArchive<Cursor<&[u8]>>,
+    name: &str,
+) -> Result<String, UserImportServiceError> {
+    let mut file = archive
+        .by_name(name)
+        .map_err(|_| UserImportServiceError::FileInvalid)?;
+    let mut xml = String::new();
+    file.read_to_string(&mut xml)
+        .map_err(|_| UserImportServiceError::FileInvalid)?;
+    Ok(xml)
+}
+
+/// Every string item is the text of all its runs, the phonetic hints are skipped.
+fn parse_xlsx_shared_strings(xml: &str) -> Vec<String> {
+    let mut strings: Vec<String> = Vec::new();
+    let mut value: Option<String> = None;
+    let mut is_text = false;
+    let mut is_phonetic = false;
+    for node in XmlNodes::new(xml) {
+        match node {
+            XmlNode::Open("si", _, is_closed) => {
+                if is_closed {
+                    strings.push(String::new());
+                } else {
+                    value = Some(String::new());
+                }
+            }
+            XmlNode::Open("rPh", _, false) => is_phonetic = true,
+            XmlNode::Open("t", _, false) => is_text = !is_phonetic,
+            XmlNode::Text(text) if is_text => {
+                if let Some(value) = &mut value {
+                    value.push_str(&xml_unescape(text));
+                }
+            }
+            XmlNode::Close("t") => is_text = false,
+            XmlNode::Close("rPh") => is_phonetic = false,
+            XmlNode::Close("si") => strings.extend(value.take()),
+            _ => {}
+        }
+    }
+    strings
+}
+
+/// The zero based index of the column of the cell reference: A1 is 0, AB12 is 27.
+pub fn xlsx_column_index(reference: &str) -> Option<usize> {
+    let mut index: usize = 0;
+    let mut is_empty = true;
+    for c in reference.chars().take_while(|c| c.is_ascii_alphabetic()) {
+        index = index.checked_mul(26)? + (c.to_ascii_uppercase() as usize - 'A' as usize + 1);
+        is_empty = false;
+    }
+    if is_empty {
+        None
+    } else {
+        Some(index - 1)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum XmlNode<'a> {
+    // The local name, the attributes and whether the element is self-closing.
+    Open(&'a str, &'a str, bool),
+    Close(&'a str),
+    Text(&'a str),
+}
+
+/// The minimal reader of the spreadsheet parts: the elements with the namespace prefix removed and the text.
+/// The declarations, the comments and the instructions are skipped.
+struct XmlNodes<'a> {
+    xml: &'a str,
+    position: usize,
+}
+
+impl<'a> XmlNodes<'a> {
+    fn new(xml: &'a str) -> Self {
+        Self { xml, position: 0 }
+    }
+}
+
+impl<'a> Iterator for XmlNodes<'a> {
+    type Item = XmlNode<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rest = &self.xml[self.position..];
+            if rest.is_empty() {
+                return None;
+            }
+            if !rest.starts_with('<') {
+                let end = rest.find('<').unwrap_or(rest.len());
+                self.position += end;
+                return Some(XmlNode::Text(&rest[..end]));
+            }
+            if rest.starts_with("<!--") {
+                self.position += rest.find("-->").map(|i| i + 3).unwrap_or(rest.len());
+                continue;
+            }
+            let end = rest.find('>')?;
+            self.position += end + 1;
+            let tag = &rest[1..end];
+            if tag.starts_with(['?', '!']) {
+                continue;
+            }
+            if let Some(name) = tag.strip_prefix('/') {
+                return Some(XmlNode::Close(local_name(name.trim())));
+            }
+            let is_closed = tag.ends_with('/');
+            let tag = tag.trim_end_matches('/');
+            let (name, attributes) = tag
+                .split_once(char::is_whitespace)
+                .unwrap_or((tag, ""));
+            return Some(XmlNode::Open(local_name(name), attributes, is_closed));
+        }
+    }
+}
+
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+fn xml_attribute(attributes: &str, name: &str) -> Option<String> {
+    let mut rest = attributes;
+    while let Some(i) = rest.find('=') {
+        let key = rest[..i].trim();
+        let value = rest[i + 1..].trim_start();
+        let quote = value.chars().next()?;
+        let value = &value[1..];
+        let end = value.find(quote)?;
+        if local_name(key).eq(name) {
+            return Some(xml_unescape(&value[..end]));
+        }
+        rest = &value[end + 1..];
+    }
+    None
+}
+
+fn xml_unescape(value: &str) -> String {
+    if !value.contains('&') {
+        return value.to_string();
+    }
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(i) = rest.find('&') {
+        result.push_str(&rest[..i]);
+        rest = &rest[i..];
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+        let c = entity.and_then(|(entity, _)| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16).ok())
+                .unwrap_or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        });
+        match (c, entity) {
+            (Some(c), Some((_, end))) => {
+                result.push(c);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[derive(Debug, Clone, Copy, Display, EnumString, PartialEq, Eq)]
+pub enum UserImportServiceError {
+    FileInvalid,
+    EmailColumnMissing,
+    TooManyRows,
+    Empty,
+    NotFound,
+    Fail,
+}
+
+impl UserImportServiceError {
+    pub fn to_http(&self) -> Error {
+        match self {
+            Self::NotFound => error::ErrorNotFound(""),
+            Self::Fail => error::ErrorInternalServerError(""),
+            _ => error::ErrorBadRequest(""),
+        }
+    }
+}
+
+impl TranslatableError for UserImportServiceError {
+    fn translate(&self, lang: &str, translator_service: &TranslatorService) -> String {
+        match self {
+            Self::FileInvalid => {
+                translator_service.translate(lang, "error.UserImportServiceError.FileInvalid")
+            }
+            Self::EmailColumnMissing => translator_service
+                .translate(lang, "error.UserImportServiceError.EmailColumnMissing"),
+            Self::TooManyRows => {
+                let max_rows = USER_IMPORT_MAX_ROWS.to_string();
+                let mut vars = HashMap::new();
+                vars.insert("max_rows", max_rows.as_str());
+                translator_service.variables(
+                    lang,
+                    "error.UserImportServiceError.TooManyRows",
+                    &vars,
+                )
+            }
+            Self::Empty => translator_service.translate(lang, "error.UserImportServiceError.Empty"),
+            Self::NotFound => {
+                translator_service.translate(lang, "error.UserImportServiceError.NotFound")
+            }
+            _ => translator_service.translate(lang, "error.UserImportServiceError.Fail"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ExportFormat, ExportValue, ExportWriter};
+
+    fn header_codes() -> HashMap<String, &'static str> {
+        let mut codes: HashMap<String, &'static str> = HashMap::new();
+        for code in USER_IMPORT_COLUMNS {
+            codes.insert(code.to_string(), code);
+        }
+        codes.insert("e-mail".to_string(), "email");
+        codes.insert("фамилия".to_string(), "surname");
+        codes
+    }
+
+    fn cells(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn csv() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::user_import::tests::csv
+        let rows = parse_csv(b"\xEF\xBB\xBFemail,name\r\na@example.com,\"Ivan, \"\"Jr\"\"\"\r\n").unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                (1, cells(&["email", "name"])),
+                (2, cells(&["a@example.com", "Ivan, \"Jr\""])),
+            ]
+        );
+
+        // The semicolon of the spreadsheet export and the multiline value.
+        let rows = parse_csv("email;surname\nb@example.com;\"Петров\nСидоров\"\nc@example.com;'-Иванов".as_bytes()).unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                (1, cells(&["email", "surname"])),
+                (2, cells(&["b@example.com", "Петров\nСидоров"])),
+                (4, cells(&["c@example.com", "-Иванов"])),
+            ]
+        );
+
+        assert_eq!(parse_csv(b"email\n\"a@example.com"), Err(UserImportServiceError::FileInvalid));
+        assert_eq!(parse_csv(b"email\n\xFF\xFE"), Err(UserImportServiceError::FileInvalid));
+    }
+
+    #[test]
+    fn xlsx() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::user_import::tests::xlsx
+        let columns = vec![
+            ("email".to_string(), "E-mail".to_string()),
+            ("surname".to_string(), "Фамилия".to_string()),
+            ("roles".to_string(), "Roles".to_string()),
+        ];
+        let mut writer = ExportWriter::new(ExportFormat::Xlsx, Cursor::new(Vec::new()), columns).unwrap();
+        writer
+            .write_row(&[
+                ExportValue::Text("a@example.com".to_string()),
+                ExportValue::Text("Smith & <Sons>".to_string()),
+                ExportValue::Text("admin, editor".to_string()),
+            ])
+            .unwrap();
+        writer
+            .write_row(&[ExportValue::Text("b@example.com".to_string()), ExportValue::Null, ExportValue::Number(7)])
+            .unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+
+        let rows = parse_xlsx(&bytes).unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                (1, cells(&["E-mail", "Фамилия", "Roles"])),
+                (2, cells(&["a@example.com", "Smith & <Sons>", "admin, editor"])),
+                (3, cells(&["b@example.com", "", "7"])),
+            ]
+        );
+
+        assert_eq!(parse_xlsx(b"PK\x03\x04broken"), Err(UserImportServiceError::FileInvalid));
+    }
+
+    #[test]
+    fn xlsx_shared_strings() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::user_import::tests::xlsx_shared_strings
+        let xml = "<?xml version=\"1.0\"?><sst xmlns=\"x\" count=\"3\"><si><t>email</t></si>\
+<si><r><rPr><b/></rPr><t xml:space=\"preserve\">Iv</t></r><r><t>an</t></r><rPh><t>x</t></rPh></si><si/>\
+<si><t>&#1048;&amp;&#x41;</t></si></sst>";
+        assert_eq!(parse_xlsx_shared_strings(xml), cells(&["email", "Ivan", "", "И&A"]));
+
+        assert_eq!(xlsx_column_index("A1"), Some(0));
+        assert_eq!(xlsx_column_index("Z3"), Some(25));
+        assert_eq!(xlsx_column_index("AB12"), Some(27));
+        assert_eq!(xlsx_column_index("12"), None);
+        assert_eq!(xml_attribute("r=\"B2\" s='1' t=\"s\"", "t"), Some("s".to_string()));
+        assert_eq!(xml_attribute("x:r=\"C3\"", "r"), Some("C3".to_string()));
+        assert_eq!(xml_attribute("r=\"B2\"", "t"), None);
+    }
+
+    #[test]
+    fn table() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::user_import::tests::table
+        let table = vec![
+            (1, cells(&["ID", " E-mail ", "Фамилия", "Locale", "roles", "email"])),
+            (2, cells(&["1", "a@example.com", " Petrov ", "", "admin; editor,,", "x@example.com"])),
+            (3, cells(&["", "", "", ""])),
+            (4, cells(&["2", "b@example.com"])),
+        ];
+        let file = map_table(table, &header_codes()).unwrap();
+        assert_eq!(file.columns, cells(&["email", "surname", "locale", "roles"]));
+        assert_eq!(
+            file.rows,
+            vec![
+                UserImportRow {
+                    line: 2,
+                    email: Some("a@example.com".to_string()),
+                    surname: Some("Petrov".to_string()),
+                    roles: cells(&["admin", "editor"]),
+                    ..Default::default()
+                },
+                UserImportRow {
+                    line: 4,
+                    email: Some("b@example.com".to_string()),
+                    ..Default::default()
+                },
+            ]
+        );
+        assert!(file.has_column("locale"));
+        assert!(!file.has_column("name"));
+
+        let table = vec![(1, cells(&["name"])), (2, cells(&["Ivan"]))];
+        assert_eq!(map_table(table, &header_codes()), Err(UserImportServiceError::EmailColumnMissing));
+        let table = vec![(1, cells(&["email"])), (2, cells(&[""]))];
+        assert_eq!(map_table(table, &header_codes()), Err(UserImportServiceError::Empty));
+        assert_eq!(map_table(Vec::new(), &header_codes()), Err(UserImportServiceError::Empty));
+
+        let mut table = vec![(1, cells(&["email"]))];
+        for line in 2..(USER_IMPORT_MAX_ROWS + 3) {
+            table.push((line, cells(&["a@example.com"])));
+        }
+        assert_eq!(map_table(table, &header_codes()), Err(UserImportServiceError::TooManyRows));
+    }
+
+    #[test]
+    fn merge() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::services::user_import::tests::merge
+        let user = User {
+            id: 5,
+            email: "a@example.com".to_string(),
+            name: Some("Ivan".to_string()),
+            surname: Some("Petrov".to_string()),
+            roles_ids: Some(vec![2, 1]),
+            ..Default::default()
+        };
+
+        let file = UserImportFile {
+            columns: cells(&["email", "surname"]),
+            rows: Vec::new(),
+        };
+        let row = UserImportRow {
+            line: 2,
+            email: Some("a@example.com".to_string()),
+            surname: Some("Petrov".to_string()),
+            name: Some("Ignored".to_string()),
+            ..Default::default()
+        };
+        let (data, columns) = merge_user(&file, &row, &None, false, &user);
+        assert_eq!(columns, vec![UserColumn::Surname]);
+        assert_eq!(data.name, Some("Ivan".to_string()));
+        assert!(is_same_user(&data, &user));
+
+        let (data, columns) = merge_user(&file, &row, &Some(vec![1, 2]), true, &user);
+        assert_eq!(columns, vec![UserColumn::Surname, UserColumn::RolesIds]);
+        assert!(is_same_user(&data, &user));
+
+        let (data, _) = merge_user(&file, &row, &None, true, &user);
+        assert_eq!(data.roles_ids, None);
+        assert!(!is_same_user(&data, &user));
+
+        let row = UserImportRow {
+            surname: None,
+            ..row
+        };
+        let (data, _) = merge_user(&file, &row, &None, false, &user);
+        assert_eq!(data.surname, None);
+        assert_eq!(data.id, 5);
+        assert!(!is_same_user(&data, &user));
+    }
+}
@@ -0,0 +1,566 @@
+use crate::helpers::now_date_time_str;
+use crate::{
+    AppError, Config, CryptService, HashService, KeyValueService, MysqlRepository, RandomService, Role,
+    TranslatableError, TranslatorService, User, UserTwoFactor, UserTwoFactorColumn,
+    UserTwoFactorMysqlRepository,
+};
+use actix_web::cookie::time::Duration;
+use actix_web::cookie::Cookie;
+use actix_web::web::Data;
+use actix_web::{error, Error, HttpRequest};
+use chrono::Utc;
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::sign::Signer;
+use qrcode::render::svg;
+use qrcode::QrCode;
+use strum_macros::{Display, EnumString};
+
+pub const TWO_FACTOR_DIGITS: u32 = 6;
+// The TOTP time step in seconds.
+pub const TWO_FACTOR_PERIOD: u64 = 30;
+// How many time steps before and after the current one are accepted.
+pub const TWO_FACTOR_WINDOW: u64 = 1;
+pub const TWO_FACTOR_SECRET_LENGTH: usize = 20;
+pub const TWO_FACTOR_RECOVERY_CODES_COUNT: usize = 8;
+pub const TWO_FACTOR_LOGIN_TTL: u64 = 300;
+pub const TWO_FACTOR_LOGIN_COOKIE: &'static str = "two_factor";
+const TWO_FACTOR_LOGIN_KEY: &'static str = "two_factor.login";
+const BASE32_ALPHABET: &'static [u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+pub struct UserTwoFactorService {
+    config: Data<Config>,
+    user_two_factor_repository: Data<UserTwoFactorMysqlRepository>,
+    crypt_service: Data<CryptService>,
+    hash_service: Data<HashService>,
+    random_service: Data<RandomService>,
+    key_value_service: Data<KeyValueService>,
+}
+
+impl UserTwoFactorService {
+    pub fn new(
+        config: Data<Config>,
+        user_two_factor_repository: Data<UserTwoFactorMysqlRepository>,
+        crypt_service: Data<CryptService>,
+        hash_service: Data<HashService>,
+        random_service: Data<RandomService>,
+        key_value_service: Data<KeyValueService>,
+    ) -> Self {
+        Self {
+            config,
+            user_two_factor_repository,
+            crypt_service,
+            hash_service,
+            random_service,
+            key_value_service,
+        }
+    }
+
+    pub fn get_service_name(&self) -> &str {
+        "UserTwoFactorService"
+    }
+
+    pub fn log_error(&self, method: &str, error: String, e: UserTwoFactorServiceError) -> UserTwoFactorServiceError {
+        let service_name = self.get_service_name().to_string();
+        log::error!("{}::{} - {}", service_name, method, error);
+        e
+    }
+
+    fn match_error(&self, method: &str, e: AppError) -> UserTwoFactorServiceError {
+        self.log_error(method, e.to_string(), UserTwoFactorServiceError::Fail)
+    }
+
+    /// RFC 4648 base32 without padding, the format authenticator apps expect for secrets.
+    pub fn base32_encode(data: &[u8]) -> String {
+        let mut result = String::new();
+        let mut buffer: u32 = 0;
+        let mut bits: u32 = 0;
+        for byte in data {
+            buffer = (buffer << 8) | *byte as u32;
+            bits += 8;
+            while bits >= 5 {
+                let index = (buffer >> (bits - 5)) & 31;
+                result.push(BASE32_ALPHABET[index as usize] as char);
+                bits -= 5;
+            }
+        }
+        if bits > 0 {
+            let index = (buffer << (5 - bits)) & 31;
+            result.push(BASE32_ALPHABET[index as usize] as char);
+        }
+        result
+    }
+
+    pub fn base32_decode(value: &str) -> Option<Vec<u8>> {
+        let mut result: Vec<u8> = Vec::new();
+        let mut buffer: u32 = 0;
+        let mut bits: u32 = 0;
+        for char in value.trim_end_matches('=').chars() {
+            if char.eq(&' ') || char.eq(&'-') {
+                continue;
+            }
+            let char = char.to_ascii_uppercase() as u8;
+            let index = BASE32_ALPHABET.iter().position(|c| c.eq(&char))? as u32;
+            buffer = (buffer << 5) | index;
+            bits += 5;
+            if bits >= 8 {
+                result.push(((buffer >> (bits - 8)) & 255) as u8);
+                bits -= 8;
+            }
+        }
+        Some(result)
+    }
+
+    /// RFC 4226 HOTP value of the counter.
+    pub fn hotp(secret: &[u8], counter: u64) -> Result<String, UserTwoFactorServiceError> {
+        let key = PKey::hmac(secret).map_err(|_| UserTwoFactorServiceError::Fail)?;
+        let mut signer =
+            Signer::new(MessageDigest::sha1(), &key).map_err(|_| UserTwoFactorServiceError::Fail)?;
+        signer
+            .update(&counter.to_be_bytes())
+            .map_err(|_| UserTwoFactorServiceError::Fail)?;
+        let hmac = signer
+            .sign_to_vec()
+            .map_err(|_| UserTwoFactorServiceError::Fail)?;
+
+        let offset = (hmac[hmac.len() - 1] & 0x0f) as usize;
+        let binary = ((hmac[offset] as u32 & 0x7f) << 24)
+            | ((hmac[offset + 1] as u32) << 16)
+            | ((hmac[offset + 2] as u32) << 8)
+            | (hmac[offset + 3] as u32);
+        let code = binary % 10u32.pow(TWO_FACTOR_DIGITS);
+
+        Ok(format!("{:0width$}", code, width = TWO_FACTOR_DIGITS as usize))
+    }
+
+    /// RFC 6238 TOTP time step of the unix timestamp.
+    pub fn time_step(timestamp: u64) -> u64 {
+        timestamp / TWO_FACTOR_PERIOD
+    }
+
+    pub fn now_time_step() -> u64 {
+        Self::time_step(Utc::now().timestamp() as u64)
+    }
+
+    /// Finds the time step of the window the code belongs to.
+    pub fn find_time_step(secret: &[u8], code: &str, time_step: u64) -> Option<u64> {
+        let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+        if code.len() != TWO_FACTOR_DIGITS as usize {
+            return None;
+        }
+        let from = time_step.saturating_sub(TWO_FACTOR_WINDOW);
+        let to = time_step + TWO_FACTOR_WINDOW;
+        (from..=to).find(|step| {
+            Self::hotp(secret, *step)
+                .map(|value| value.eq(&code))
+                .unwrap_or(false)
+        })
+    }
+
+    pub fn provisioning_uri(issuer: &str, account: &str, secret: &str) -> String {
+        let encode = |value: &str| -> String {
+            url::form_urlencoded::byte_serialize(value.as_bytes())
+                .collect::<String>()
+                .replace('+', "%20")
+        };
+        format!(
+            "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+            encode(issuer),
+            encode(account),
+            secret,
+            encode(issuer),
+            TWO_FACTOR_DIGITS,
+            TWO_FACTOR_PERIOD
+        )
+    }
+
+    pub fn qr_code_svg(data: &str) -> Result<String, UserTwoFactorServiceError> {
+        let code = QrCode::new(data.as_bytes()).map_err(|_| UserTwoFactorServiceError::Fail)?;
+        Ok(code
+            .render::<svg::Color>()
+            .min_dimensions(200, 200)
+            .quiet_zone(true)
+            .build())
+    }
+
+    /// Users with one of the roles that require a second factor must enable it.
+    pub fn is_required(user: &User, roles: &Vec<Role>) -> bool {
+        if let Some(roles_ids) = &user.roles_ids {
+            return roles
+                .iter()
+                .any(|role| role.requires_two_factor && roles_ids.contains(&role.id));
+        }
+        false
+    }
+
+    fn hash_recovery_code(&self, code: &str) -> String {
+        let code: String = code.trim().to_lowercase();
+        self.hash_service.get_ref().hash(code)
+    }
+
+    fn generate_recovery_codes(&self) -> (Vec<String>, Vec<String>) {
+        let random_service = self.random_service.get_ref();
+        let mut codes: Vec<String> = Vec::new();
+        let mut hashes: Vec<String> = Vec::new();
+        for _ in 0..TWO_FACTOR_RECOVERY_CODES_COUNT {
+            let value = random_service.str(10).to_lowercase();
+            let code = format!("{}-{}", &value[0..5], &value[5..10]);
+            hashes.push(self.hash_recovery_code(&code));
+            codes.push(code);
+        }
+        (codes, hashes)
+    }
+
+    fn decrypt_secret(&self, user_two_factor: &UserTwoFactor) -> Result<Vec<u8>, UserTwoFactorServiceError> {
+        let secret = self
+            .crypt_service
+            .get_ref()
+            .decrypt_string(&user_two_factor.secret)
+            .map_err(|e| self.log_error("decrypt_secret", e.to_string(), UserTwoFactorServiceError::Fail))?;
+        Self::base32_decode(&secret).ok_or(UserTwoFactorServiceError::Fail)
+    }
+
+    /// The base32 secret to show the user during the enrollment.
+    pub fn get_secret(&self, user_two_factor: &UserTwoFactor) -> Result<String, UserTwoFactorServiceError> {
+        Ok(Self::base32_encode(&self.decrypt_secret(user_two_factor)?))
+    }
+
+    pub fn first_by_user_id(
+        &self,
+        user_id: u64,
+    ) -> Result<Option<UserTwoFactor>, UserTwoFactorServiceError> {
+        self.user_two_factor_repository
+            .get_ref()
+            .first_by_user_id(user_id)
+            .map_err(|e| self.match_error("first_by_user_id", e))
+    }
+
+    pub fn first_by_user_id_throw_http(&self, user_id: u64) -> Result<Option<UserTwoFactor>, Error> {
+        self.first_by_user_id(user_id)
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+
+    pub fn is_enabled(&self, user_id: u64) -> Result<bool, UserTwoFactorServiceError> {
+        Ok(self
+            .first_by_user_id(user_id)?
+            .map(|user_two_factor| user_two_factor.is_confirmed())
+            .unwrap_or(false))
+    }
+
+    pub fn is_enabled_throw_http(&self, user_id: u64) -> Result<bool, Error> {
+        self.is_enabled(user_id)
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+
+    /// Creates a new unconfirmed secret, the previous unconfirmed one is replaced.
+    pub fn start_enrollment(&self, user_id: u64) -> Result<UserTwoFactor, UserTwoFactorServiceError> {
+        if self.is_enabled(user_id)? {
+            return Err(UserTwoFactorServiceError::AlreadyEnabled);
+        }
+        self.disable(user_id)?;
+
+        let bytes = self.random_service.get_ref().bytes_32();
+        let secret = Self::base32_encode(&bytes[0..TWO_FACTOR_SECRET_LENGTH]);
+
+        let mut data = UserTwoFactor::default();
+        data.user_id = user_id;
+        data.secret = self
+            .crypt_service
+            .get_ref()
+            .encrypt_string(&secret)
+            .map_err(|e| self.log_error("start_enrollment", e.to_string(), UserTwoFactorServiceError::Fail))?;
+        data.created_at = Some(now_date_time_str());
+
+        let repository = self.user_two_factor_repository.get_ref();
+        repository
+            .insert(&vec![data], None)
+            .map_err(|e| self.match_error("start_enrollment", e))?;
+
+        self.first_by_user_id(user_id)?
+            .ok_or(UserTwoFactorServiceError::NotFound)
+    }
+
+    /// Enables the second factor and returns the recovery codes, they are shown only once.
+    pub fn confirm_enrollment(
+        &self,
+        user_id: u64,
+        code: &str,
+    ) -> Result<Vec<String>, UserTwoFactorServiceError> {
+        let mut user_two_factor = self
+            .first_by_user_id(user_id)?
+            .ok_or(UserTwoFactorServiceError::NotFound)?;
+
+        if user_two_factor.is_confirmed() {
+            return Err(UserTwoFactorServiceError::AlreadyEnabled);
+        }
+
+        let secret = self.decrypt_secret(&user_two_factor)?;
+        let time_step = Self::find_time_step(&secret, code, Self::now_time_step())
+            .ok_or(UserTwoFactorServiceError::InvalidCode)?;
+
+        let (codes, hashes) = self.generate_recovery_codes();
+        user_two_factor.recovery_codes = Some(hashes);
+        user_two_factor.last_used_step = Some(time_step);
+        user_two_factor.confirmed_at = Some(now_date_time_str());
+
+        let columns = Some(vec![
+            UserTwoFactorColumn::RecoveryCodes,
+            UserTwoFactorColumn::LastUsedStep,
+            UserTwoFactorColumn::ConfirmedAt,
+        ]);
+        self.user_two_factor_repository
+            .get_ref()
+            .update_by_id(user_two_factor.id, &user_two_factor, &columns)
+            .map_err(|e| self.match_error("confirm_enrollment", e))?;
+
+        Ok(codes)
+    }
+
+    /// Checks the TOTP code or spends one of the recovery codes.
+    pub fn verify(&self, user_id: u64, code: &str) -> Result<(), UserTwoFactorServiceError> {
+        let mut user_two_factor = self
+            .first_by_user_id(user_id)?
+            .ok_or(UserTwoFactorServiceError::NotEnabled)?;
+
+        if !user_two_factor.is_confirmed() {
+            return Err(UserTwoFactorServiceError::NotEnabled);
+        }
+
+        let secret = self.decrypt_secret(&user_two_factor)?;
+        let time_step = Self::find_time_step(&secret, code, Self::now_time_step());
+
+        let columns = if let Some(time_step) = time_step {
+            if let Some(last_used_step) = user_two_factor.last_used_step {
+                if time_step <= last_used_step {
+                    return Err(UserTwoFactorServiceError::InvalidCode);
+                }
+            }
+            user_two_factor.last_used_step = Some(time_step);
+            vec![UserTwoFactorColumn::LastUsedStep]
+        } else {
+            let hash = self.hash_recovery_code(code);
+            let mut recovery_codes = user_two_factor.recovery_codes.to_owned().unwrap_or_default();
+            let index = recovery_codes
+                .iter()
+                .position(|value| value.eq(&hash))
+                .ok_or(UserTwoFactorServiceError::InvalidCode)?;
+            recovery_codes.remove(index);
+            user_two_factor.recovery_codes = Some(recovery_codes);
+            vec![UserTwoFactorColumn::RecoveryCodes]
+        };
+
+        self.user_two_factor_repository
+            .get_ref()
+            .update_by_id(user_two_factor.id, &user_two_factor, &Some(columns))
+            .map_err(|e| self.match_error("verify", e))
+    }
+
+    pub fn regenerate_recovery_codes(&self, user_id: u64) -> Result<Vec<String>, UserTwoFactorServiceError> {
+        let mut user_two_factor = self
+            .first_by_user_id(user_id)?
+            .ok_or(UserTwoFactorServiceError::NotEnabled)?;
+
+        if !user_two_factor.is_confirmed() {
+            return Err(UserTwoFactorServiceError::NotEnabled);
+        }
+
+        let (codes, hashes) = self.generate_recovery_codes();
+        user_two_factor.recovery_codes = Some(hashes);
+
+        let columns = Some(vec![UserTwoFactorColumn::RecoveryCodes]);
+        self.user_two_factor_repository
+            .get_ref()
+            .update_by_id(user_two_factor.id, &user_two_factor, &columns)
+            .map_err(|e| self.match_error("regenerate_recovery_codes", e))?;
+
+        Ok(codes)
+    }
+
+    pub fn disable(&self, user_id: u64) -> Result<(), UserTwoFactorServiceError> {
+        self.user_two_factor_repository
+            .get_ref()
+            .delete_by_user_id(user_id)
+            .map_err(|e| self.match_error("disable", e))
+    }
+
+    pub fn disable_throw_http(&self, user_id: u64) -> Result<(), Error> {
+        self.disable(user_id)
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+
+    /// Encrypts the secrets by the current APP_KEY after the key rotation, returns the count of the updated records.
+    pub fn reencrypt_secrets(&self) -> Result<u64, UserTwoFactorServiceError> {
+        let crypt_service = self.crypt_service.get_ref();
+        let repository = self.user_two_factor_repository.get_ref();
+        let records = repository
+            .all(None, None, &None)
+            .map_err(|e| self.match_error("reencrypt_secrets", e))?;
+
+        let columns = Some(vec![UserTwoFactorColumn::Secret]);
+        let mut count: u64 = 0;
+        for mut user_two_factor in records {
+            if !crypt_service.needs_reencrypt(&user_two_factor.secret) {
+                continue;
+            }
+            user_two_factor.secret = crypt_service
+                .reencrypt_string(&user_two_factor.secret)
+                .map_err(|e| self.log_error("reencrypt_secrets", e.to_string(), UserTwoFactorServiceError::Fail))?;
+            repository
+                .update_by_id(user_two_factor.id, &user_two_factor, &columns)
+                .map_err(|e| self.match_error("reencrypt_secrets", e))?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    fn make_login_key(&self, token: &str) -> String {
+        let hash = self.hash_service.get_ref().hash(token);
+        format!("{}.{}", TWO_FACTOR_LOGIN_KEY, hash)
+    }
+
+    /// Remembers the user who passed the password check and now has to enter the code.
+    pub fn make_login_token(&self, user_id: u64) -> Result<String, UserTwoFactorServiceError> {
+        let token = self.random_service.get_ref().str(64);
+        let key = self.make_login_key(&token);
+        self.key_value_service
+            .get_ref()
+            .set_ex(&key, user_id, TWO_FACTOR_LOGIN_TTL)
+            .map_err(|e| self.match_error("make_login_token", e))?;
+        Ok(token)
+    }
+
+    pub fn get_login_user_id(&self, token: &str) -> Result<Option<u64>, UserTwoFactorServiceError> {
+        let key = self.make_login_key(token);
+        self.key_value_service
+            .get_ref()
+            .get(&key)
+            .map_err(|e| self.match_error("get_login_user_id", e))
+    }
+
+    pub fn get_login_token_from_request(&self, req: &HttpRequest) -> Option<String> {
+        let cookie = req.cookie(TWO_FACTOR_LOGIN_COOKIE)?;
+        let token = cookie.value();
+        if token.is_empty() {
+            return None;
+        }
+        Some(token.to_string())
+    }
+
+    fn make_login_cookie_(&self, token: &str, max_age: u64) -> Cookie<'static> {
+        let config = self.config.get_ref();
+        let mut cookie = Cookie::build(TWO_FACTOR_LOGIN_COOKIE, token.to_string())
+            .path(config.auth.cookie.cookie_path.to_owned())
+            .http_only(true)
+            .secure(config.auth.cookie.cookie_secure)
+            .max_age(Duration::seconds(max_age as i64));
+
+        if config.auth.cookie.cookie_domain != "" {
+            cookie = cookie.domain(config.auth.cookie.cookie_domain.to_owned());
+        }
+
+        cookie.finish()
+    }
+
+    pub fn make_login_cookie(&self, token: &str) -> Cookie<'static> {
+        self.make_login_cookie_(token, TWO_FACTOR_LOGIN_TTL)
+    }
+
+    pub fn make_clear_login_cookie(&self) -> Cookie<'static> {
+        self.make_login_cookie_("", 0)
+    }
+
+    pub fn delete_login_token(&self, token: &str) -> Result<(), UserTwoFactorServiceError> {
+        let key = self.make_login_key(token);
+        self.key_value_service
+            .get_ref()
+            .del(&key)
+            .map_err(|e| self.match_error("delete_login_token", e))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Display, EnumString, PartialEq, Eq)]
+pub enum UserTwoFactorServiceError {
+    DbConnectionFail,
+    NotFound,
+    AlreadyEnabled,
+    NotEnabled,
+    InvalidCode,
+    Fail,
+}
+
+impl TranslatableError for UserTwoFactorServiceError {
+    fn translate(&self, lang: &str, translator_service: &TranslatorService) -> String {
+        match self {
+            Self::DbConnectionFail => translator_service
+                .translate(lang, "error.UserTwoFactorServiceError.DbConnectionFail"),
+            Self::NotFound => {
+                translator_service.translate(lang, "error.UserTwoFactorServiceError.NotFound")
+            }
+            Self::AlreadyEnabled => translator_service
+                .translate(lang, "error.UserTwoFactorServiceError.AlreadyEnabled"),
+            Self::NotEnabled => {
+                translator_service.translate(lang, "error.UserTwoFactorServiceError.NotEnabled")
+            }
+            Self::InvalidCode => {
+                translator_service.translate(lang, "error.UserTwoFactorServiceError.InvalidCode")
+            }
+            _ => translator_service.translate(lang, "error.UserTwoFactorServiceError.Fail"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base32() {
+        assert_eq!(UserTwoFactorService::base32_encode(b""), "");
+        assert_eq!(UserTwoFactorService::base32_encode(b"f"), "MY");
+        assert_eq!(UserTwoFactorService::base32_encode(b"foobar"), "MZXW6YTBOI");
+        assert_eq!(
+            UserTwoFactorService::base32_decode("MZXW6YTBOI======"),
+            Some(b"foobar".to_vec())
+        );
+        assert_eq!(
+            UserTwoFactorService::base32_decode("mzxw 6ytb oi"),
+            Some(b"foobar".to_vec())
+        );
+        assert_eq!(UserTwoFactorService::base32_decode("MZ1"), None);
+    }
+
+    #[test]
+    fn test_totp() {
+        // RFC 6238 test vectors for SHA1, truncated to 6 digits.
+        let secret = b"12345678901234567890";
+        let vectors: [(u64, &str); 4] = [
+            (59, "287082"),
+            (1111111109, "081804"),
+            (1234567890, "005924"),
+            (2000000000, "279037"),
+        ];
+        for (timestamp, code) in vectors {
+            let time_step = UserTwoFactorService::time_step(timestamp);
+            assert_eq!(UserTwoFactorService::hotp(secret, time_step).unwrap(), code);
+            assert_eq!(
+                UserTwoFactorService::find_time_step(secret, code, time_step + 1),
+                Some(time_step)
+            );
+            assert_eq!(
+                UserTwoFactorService::find_time_step(secret, code, time_step + 2),
+                None
+            );
+        }
+        assert_eq!(UserTwoFactorService::find_time_step(secret, "28708", 1), None);
+    }
+
+    #[test]
+    fn test_provisioning_uri() {
+        let uri = UserTwoFactorService::provisioning_uri("My App", "a@a.a", "MZXW6YTBOI");
+        assert_eq!(
+            uri,
+            "otpauth://totp/My%20App:a%40a.a?secret=MZXW6YTBOI&issuer=My%20App&algorithm=SHA1&digits=6&period=30"
+        );
+        assert!(UserTwoFactorService::qr_code_svg(&uri).unwrap().starts_with("<?xml"));
+    }
+}
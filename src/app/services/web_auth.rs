@@ -1,6 +1,8 @@
-use crate::{Config, CryptService, HashService, KeyValueService, RandomService, User, UserService};
+use crate::helpers::{now_date_time_str, DATE_TIME_FORMAT};
+use crate::{Config, CryptService, HashService, KeyValueService, RandomService, User, UserService, UserSession};
 use actix_web::cookie::time::Duration;
 use actix_web::cookie::Cookie;
+use actix_web::http::header::{AUTHORIZATION, USER_AGENT};
 use actix_web::web::Data;
 use actix_web::{error, Error, HttpRequest};
 use chrono::{DateTime, NaiveDateTime, TimeDelta, Utc};
@@ -11,6 +13,10 @@ use strum_macros::{Display, EnumString};
 const FORMAT: &'static str = "%Y.%m.%d %H:%M:%S";
 
 pub const CSRF_ERROR_MESSAGE: &'static str = "CSRF token mismatch.";
+pub const BEARER_PREFIX: &str = "Bearer ";
+// The last activity of a session is saved not more often than once per this number of seconds.
+pub const SESSION_ACTIVITY_INTERVAL: i64 = 60;
+pub const SESSION_USER_AGENT_MAX_LENGTH: usize = 512;
 
 #[derive(Debug, Clone)]
 pub struct Session(u64, u64, String, DateTime<Utc>, Option<String>);
@@ -130,6 +136,21 @@ impl WebAuthService {
         Some(token.unwrap())
     }
 
+    pub fn get_bearer_token_from_request(&self, req: &HttpRequest) -> Option<String> {
+        let header = req.headers().get(AUTHORIZATION)?;
+        let header = header.to_str().ok()?.trim();
+        let token = header.strip_prefix(BEARER_PREFIX)?.trim();
+        if token.is_empty() {
+            return None;
+        }
+        Some(token.to_string())
+    }
+
+    pub fn get_session_from_bearer(&self, req: &HttpRequest) -> Option<Session> {
+        let token = self.get_bearer_token_from_request(req)?;
+        self.decrypt_session(&token).ok()
+    }
+
     fn make_cookie_<'a, V>(&'a self, token: V, max_age: u64) -> Cookie<'a>
     where
         V: Into<Cow<'a, str>>,
@@ -179,11 +200,21 @@ impl WebAuthService {
     }
 
     pub fn get_token_value_key(&self, token: &Session) -> String {
-        let user_id = token.get_user_id().to_string();
-        let token_id = token.get_token_id().to_string();
+        self.make_token_value_key(token.get_user_id(), token.get_token_id())
+    }
+
+    fn make_token_value_key(&self, user_id: u64, token_id: u64) -> String {
         format!("auth.{}.tokens.{}.value", user_id, token_id)
     }
 
+    fn make_token_info_key(&self, user_id: u64, token_id: u64) -> String {
+        format!("auth.{}.tokens.{}.info", user_id, token_id)
+    }
+
+    fn make_tokens_key(&self, user_id: u64) -> String {
+        format!("auth.{}.tokens", user_id)
+    }
+
     #[allow(dead_code)]
     fn make_store_data(&self, token_value: &str, expires: u64) -> String {
         format!("{}-{}", token_value, expires.to_string())
@@ -207,16 +238,18 @@ impl WebAuthService {
         ))
     }
 
+    /// The token is added to the list of the user sessions here and not with its details,
+    /// so it can be revoked even when the details were not saved.
     pub fn save_session(&self, token: &Session) -> Result<(), WebAuthServiceError> {
         let config = self.config.get_ref();
         let key_value_service = self.key_value_service.get_ref();
+        let expires = config.auth.cookie.token_expires;
+        let tokens_key = self.make_tokens_key(token.get_user_id());
 
         key_value_service
-            .set_ex(
-                self.get_token_value_key(&token),
-                token.get_token_value(),
-                config.auth.cookie.token_expires,
-            )
+            .set_ex(self.get_token_value_key(token), token.get_token_value(), expires)
+            .and_then(|_| key_value_service.sadd(&tokens_key, token.get_token_id()))
+            .and_then(|_| key_value_service.expire(&tokens_key, expires as i64))
             .map_err(|e| {
                 log::error!("WebAuthService::save_session - {e}");
                 return WebAuthServiceError::Fail;
@@ -242,6 +275,38 @@ impl WebAuthService {
         Ok(())
     }
 
+    pub fn delete_session(&self, token: &Session) -> Result<(), WebAuthServiceError> {
+        let key_value_service = self.key_value_service.get_ref();
+
+        key_value_service
+            .del(self.get_token_value_key(token))
+            .map_err(|e| {
+                log::error!("WebAuthService::delete_session - {e}");
+                WebAuthServiceError::Fail
+            })?;
+        self.delete_session_info(token.get_user_id(), token.get_token_id())
+    }
+
+    /// The list of the user sessions and the details of the token live at least as long as
+    /// the token, otherwise the extended token drops out of the list and can not be revoked.
+    fn extend_session(&self, token: &Session) -> Result<(), WebAuthServiceError> {
+        let config = self.config.get_ref();
+        let key_value_service = self.key_value_service.get_ref();
+        let expires = config.auth.cookie.token_expires as i64;
+        let user_id = token.get_user_id();
+
+        key_value_service
+            .expire(self.make_tokens_key(user_id), expires)
+            .and_then(|_| {
+                key_value_service
+                    .expire(self.make_token_info_key(user_id, token.get_token_id()), expires)
+            })
+            .map_err(|e| {
+                log::error!("WebAuthService::extend_session - {e}");
+                WebAuthServiceError::Fail
+            })
+    }
+
     pub fn is_need_new_token(&self, token: &Session) -> bool {
         let expires = token.get_expires();
         let now = Utc::now();
@@ -277,6 +342,9 @@ impl WebAuthService {
         if token_value != token.get_token_value() {
             return Err(WebAuthServiceError::Fail);
         }
+        if !is_need_new_token {
+            self.extend_session(token)?;
+        }
 
         // Тут токен уже подтверждён и можно получить пользователя
         let user_service = self.user_service.get_ref();
@@ -292,6 +360,10 @@ impl WebAuthService {
 
         let user = user.unwrap();
 
+        if user.is_disabled() {
+            return Err(WebAuthServiceError::Fail);
+        }
+
         let token: Session = if is_need_new_token {
             let mut new_token = self.generate_session(user_id);
             new_token.set_old_token_value(Some(token.get_token_value().to_owned()));
@@ -299,6 +371,7 @@ impl WebAuthService {
                 log::error!("WebAuthService::login_by_session - {e}");
                 return WebAuthServiceError::Fail;
             })?;
+            self.rotate_session_info(token, &new_token)?;
             new_token
         } else {
             token.clone()
@@ -307,6 +380,38 @@ impl WebAuthService {
         Ok((user, token))
     }
 
+    /// Checks a session passed as a bearer token. Unlike the cookie session,
+    /// the token is not rotated, only its lifetime is extended.
+    pub fn login_by_bearer_session(&self, token: &Session) -> Result<User, WebAuthServiceError> {
+        let config = self.config.get_ref();
+        let key_value_service = self.key_value_service.get_ref();
+
+        let value: Option<String> = key_value_service
+            .get_ex(self.get_token_value_key(token), config.auth.cookie.token_expires)
+            .map_err(|e| {
+                log::error!("WebAuthService::login_by_bearer_session - {e}");
+                WebAuthServiceError::Fail
+            })?;
+
+        if value.is_none() {
+            return Err(WebAuthServiceError::Fail);
+        }
+
+        if value.unwrap() != token.get_token_value() {
+            return Err(WebAuthServiceError::Fail);
+        }
+        self.extend_session(token)?;
+
+        let user_service = self.user_service.get_ref();
+        let user = user_service.first_by_id(token.get_user_id()).map_err(|e| {
+            log::error!("WebAuthService::login_by_bearer_session - {e}");
+            WebAuthServiceError::Fail
+        })?;
+
+        user.filter(|user| !user.is_disabled())
+            .ok_or(WebAuthServiceError::Fail)
+    }
+
     pub fn login_by_req(&self, req: &HttpRequest) -> Result<(User, Session), WebAuthServiceError> {
         let session = self.get_session_from_request(req);
 
@@ -319,9 +424,246 @@ impl WebAuthService {
 
     pub fn logout_by_session(&self, session: &Session) -> Result<(), WebAuthServiceError> {
         self.expire_session(session)?;
+        self.delete_session_info(session.get_user_id(), session.get_token_id())?;
+        Ok(())
+    }
+
+    fn get_session_info(
+        &self,
+        user_id: u64,
+        token_id: u64,
+    ) -> Result<Option<UserSession>, WebAuthServiceError> {
+        self.key_value_service
+            .get_ref()
+            .get(self.make_token_info_key(user_id, token_id))
+            .map_err(|e| {
+                log::error!("WebAuthService::get_session_info - {e}");
+                WebAuthServiceError::Fail
+            })
+    }
+
+    fn set_session_info(&self, user_id: u64, info: &UserSession) -> Result<(), WebAuthServiceError> {
+        let config = self.config.get_ref();
+        let key_value_service = self.key_value_service.get_ref();
+        let expires = config.auth.cookie.token_expires;
+        let tokens_key = self.make_tokens_key(user_id);
+
+        key_value_service
+            .set_ex(self.make_token_info_key(user_id, info.token_id), info, expires)
+            .and_then(|_| key_value_service.sadd(&tokens_key, info.token_id))
+            .and_then(|_| key_value_service.expire(&tokens_key, expires as i64))
+            .map_err(|e| {
+                log::error!("WebAuthService::set_session_info - {e}");
+                WebAuthServiceError::Fail
+            })
+    }
+
+    fn delete_session_info(&self, user_id: u64, token_id: u64) -> Result<(), WebAuthServiceError> {
+        let key_value_service = self.key_value_service.get_ref();
+
+        key_value_service
+            .del(self.make_token_info_key(user_id, token_id))
+            .and_then(|_| key_value_service.srem(self.make_tokens_key(user_id), token_id))
+            .map_err(|e| {
+                log::error!("WebAuthService::delete_session_info - {e}");
+                WebAuthServiceError::Fail
+            })
+    }
+
+    /// Saves the client of the session and its last activity.
+    pub fn save_session_info(
+        &self,
+        req: &HttpRequest,
+        session: &Session,
+    ) -> Result<(), WebAuthServiceError> {
+        let user_id = session.get_user_id();
+        let token_id = session.get_token_id();
+        let now = Utc::now();
+        let ip = req.peer_addr().map(|addr| addr.ip().to_string());
+
+        let info = self.get_session_info(user_id, token_id)?;
+
+        if let Some(info) = &info {
+            let last_activity_at = info
+                .last_activity_at
+                .as_ref()
+                .and_then(|v| NaiveDateTime::parse_from_str(v, DATE_TIME_FORMAT).ok());
+            if let Some(last_activity_at) = last_activity_at {
+                let seconds = (now.naive_utc() - last_activity_at).num_seconds();
+                if seconds < SESSION_ACTIVITY_INTERVAL && info.ip.eq(&ip) {
+                    return Ok(());
+                }
+            }
+        }
+
+        let mut info = info.unwrap_or_default();
+        info.token_id = token_id;
+        if info.created_at.is_none() {
+            info.created_at = Some(now_date_time_str());
+        }
+        info.ip = ip;
+        info.user_agent = req
+            .headers()
+            .get(USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.chars().take(SESSION_USER_AGENT_MAX_LENGTH).collect());
+        info.last_activity_at = Some(now_date_time_str());
+
+        self.set_session_info(user_id, &info)
+    }
+
+    pub fn save_session_info_throw_http(
+        &self,
+        req: &HttpRequest,
+        session: &Session,
+    ) -> Result<(), Error> {
+        self.save_session_info(req, session)
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+
+    /// The rotated token keeps the details of the session it belongs to.
+    fn rotate_session_info(
+        &self,
+        old_session: &Session,
+        new_session: &Session,
+    ) -> Result<(), WebAuthServiceError> {
+        let config = self.config.get_ref();
+        let user_id = old_session.get_user_id();
+        let old_token_id = old_session.get_token_id();
+
+        let mut info = self.get_session_info(user_id, old_token_id)?.unwrap_or_default();
+        info.token_id = new_session.get_token_id();
+        info.previous_token_id = Some(old_token_id);
+        self.set_session_info(user_id, &info)?;
+
+        let key_value_service = self.key_value_service.get_ref();
+        key_value_service
+            .srem(self.make_tokens_key(user_id), old_token_id)
+            .and_then(|_| {
+                key_value_service.expire(
+                    self.make_token_info_key(user_id, old_token_id),
+                    config.auth.cookie.session_expires as i64,
+                )
+            })
+            .map_err(|e| {
+                log::error!("WebAuthService::rotate_session_info - {e}");
+                WebAuthServiceError::Fail
+            })
+    }
+
+    /// Active sessions of the user, the recently used ones go first.
+    pub fn all_sessions(&self, user_id: u64) -> Result<Vec<UserSession>, WebAuthServiceError> {
+        let key_value_service = self.key_value_service.get_ref();
+        let token_ids: Vec<u64> = key_value_service
+            .smembers(self.make_tokens_key(user_id))
+            .map_err(|e| {
+                log::error!("WebAuthService::all_sessions - {e}");
+                WebAuthServiceError::Fail
+            })?;
+
+        let mut sessions: Vec<UserSession> = Vec::new();
+
+        for token_id in token_ids {
+            let ttl: i64 = key_value_service
+                .ttl(self.make_token_value_key(user_id, token_id))
+                .map_err(|e| {
+                    log::error!("WebAuthService::all_sessions - {e}");
+                    WebAuthServiceError::Fail
+                })?;
+            let info = self.get_session_info(user_id, token_id)?;
+
+            match info {
+                Some(mut info) if ttl > 0 => {
+                    let expires_at = Utc::now().add(TimeDelta::seconds(ttl));
+                    info.expires_at = Some(expires_at.format(DATE_TIME_FORMAT).to_string());
+                    sessions.push(info);
+                }
+                // The details were not saved yet, the session is still listed to be revoked.
+                None if ttl > 0 => {
+                    let expires_at = Utc::now().add(TimeDelta::seconds(ttl));
+                    sessions.push(UserSession {
+                        token_id,
+                        expires_at: Some(expires_at.format(DATE_TIME_FORMAT).to_string()),
+                        ..Default::default()
+                    });
+                }
+                // The token has expired, only its details are left.
+                _ => self.delete_session_info(user_id, token_id)?,
+            }
+        }
+
+        sessions.sort_by(|a, b| b.last_activity_at.cmp(&a.last_activity_at));
+
+        Ok(sessions)
+    }
+
+    pub fn all_sessions_throw_http(&self, user_id: u64) -> Result<Vec<UserSession>, Error> {
+        self.all_sessions(user_id)
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+
+    /// Deletes the session token together with the rotated one that is still valid.
+    pub fn revoke_session(&self, user_id: u64, token_id: u64) -> Result<(), WebAuthServiceError> {
+        let key_value_service = self.key_value_service.get_ref();
+        let info = self.get_session_info(user_id, token_id)?;
+
+        let mut token_ids = vec![token_id];
+        if let Some(previous_token_id) = info.and_then(|info| info.previous_token_id) {
+            token_ids.push(previous_token_id);
+        }
+
+        for token_id in token_ids {
+            key_value_service
+                .del(self.make_token_value_key(user_id, token_id))
+                .map_err(|e| {
+                    log::error!("WebAuthService::revoke_session - {e}");
+                    WebAuthServiceError::Fail
+                })?;
+            self.delete_session_info(user_id, token_id)?;
+        }
+
         Ok(())
     }
 
+    pub fn revoke_session_throw_http(&self, user_id: u64, token_id: u64) -> Result<(), Error> {
+        self.revoke_session(user_id, token_id)
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+
+    /// Revokes every session of the user except the given one.
+    pub fn revoke_all_sessions(
+        &self,
+        user_id: u64,
+        except_token_id: Option<u64>,
+    ) -> Result<(), WebAuthServiceError> {
+        let token_ids: Vec<u64> = self
+            .key_value_service
+            .get_ref()
+            .smembers(self.make_tokens_key(user_id))
+            .map_err(|e| {
+                log::error!("WebAuthService::revoke_all_sessions - {e}");
+                WebAuthServiceError::Fail
+            })?;
+
+        for token_id in token_ids {
+            if except_token_id.eq(&Some(token_id)) {
+                continue;
+            }
+            self.revoke_session(user_id, token_id)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn revoke_all_sessions_throw_http(
+        &self,
+        user_id: u64,
+        except_token_id: Option<u64>,
+    ) -> Result<(), Error> {
+        self.revoke_all_sessions(user_id, except_token_id)
+            .map_err(|_| error::ErrorInternalServerError(""))
+    }
+
     pub fn logout_by_req(&self, req: &HttpRequest) -> Result<(), WebAuthServiceError> {
         let session = self.get_session_from_request(req);
         if session.is_none() {
@@ -443,6 +785,69 @@ mod tests {
         });
     }
 
+    fn has_session(auth: &WebAuthService, session: &Session) -> bool {
+        auth.all_sessions(session.get_user_id())
+            .unwrap()
+            .iter()
+            .any(|info| info.token_id == session.get_token_id())
+    }
+
+    #[test]
+    fn revoke_session() {
+        let (_, all_services) = preparation();
+        let auth = all_services.web_auth_service.get_ref();
+        let user_id = all_services.rand_service.get_ref().range(1_000_000..=u64::MAX);
+
+        // The details of the session are not saved, it is revoked by the token alone.
+        let session = auth.generate_session(user_id);
+        auth.save_session(&session).unwrap();
+        assert!(has_session(auth, &session));
+
+        auth.revoke_session(user_id, session.get_token_id()).unwrap();
+        assert!(!has_session(auth, &session));
+        assert!(auth.login_by_session(&session).is_err());
+    }
+
+    #[test]
+    fn revoke_all_sessions() {
+        let (_, all_services) = preparation();
+        let auth = all_services.web_auth_service.get_ref();
+        let user_id = all_services.rand_service.get_ref().range(1_000_000..=u64::MAX);
+
+        let sessions: Vec<Session> = (0..3).map(|_| auth.generate_session(user_id)).collect();
+        for session in &sessions {
+            auth.save_session(session).unwrap();
+        }
+
+        auth.revoke_all_sessions(user_id, Some(sessions[1].get_token_id()))
+            .unwrap();
+        assert!(!has_session(auth, &sessions[0]));
+        assert!(has_session(auth, &sessions[1]));
+        assert!(!has_session(auth, &sessions[2]));
+
+        auth.revoke_all_sessions(user_id, None).unwrap();
+        assert!(auth.all_sessions(user_id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn login_by_bearer_session_extends_sessions() {
+        let (_, all_services) = preparation();
+        let auth = all_services.web_auth_service.get_ref();
+        let key_value_service = all_services.key_value_service.get_ref();
+        let user_id = all_services.rand_service.get_ref().range(1_000_000..=u64::MAX);
+
+        let session = auth.generate_session(user_id);
+        auth.save_session(&session).unwrap();
+        let tokens_key = auth.make_tokens_key(user_id);
+        key_value_service.expire(&tokens_key, 10).unwrap();
+
+        // The user does not exist, but the token itself is valid and is extended.
+        let _ = auth.login_by_bearer_session(&session);
+        let ttl: i64 = key_value_service.ttl(&tokens_key).unwrap();
+        assert!(ttl > 10);
+        assert!(has_session(auth, &session));
+    }
+
     #[bench]
     fn bench_expire_session(b: &mut Bencher) {
         // 46,769.59 ns/iter (+/- 5,040.02)
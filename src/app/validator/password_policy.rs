@@ -0,0 +1,242 @@
+use crate::app::validator::rules::str_max_chars_count::StrMaxCharsCount;
+use crate::app::validator::rules::str_min_chars_count::StrMinCharsCount;
+use crate::config::AuthPasswordConfig;
+use crate::TranslatorService;
+use openssl::sha::sha1;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
+
+pub const PASSWORD_MAX_CHARS_COUNT: usize = 255;
+// The shorter parts of the email and the name are too common to forbid them.
+const SIMILAR_MIN_CHARS_COUNT: usize = 3;
+
+pub struct PasswordPolicy;
+
+impl PasswordPolicy {
+    pub fn apply_lowercase(value: &str) -> bool {
+        value.chars().any(|c| c.is_lowercase())
+    }
+
+    pub fn apply_uppercase(value: &str) -> bool {
+        value.chars().any(|c| c.is_uppercase())
+    }
+
+    pub fn apply_digit(value: &str) -> bool {
+        value.chars().any(|c| c.is_numeric())
+    }
+
+    pub fn apply_symbol(value: &str) -> bool {
+        value.chars().any(|c| !c.is_alphanumeric())
+    }
+
+    /// The inputs are the email and the parts of the full name, the domain of the email is ignored.
+    pub fn is_similar(value: &str, inputs: &[&str]) -> bool {
+        let value = value.to_lowercase();
+        let value_chars_count = value.chars().count();
+
+        for input in inputs {
+            let input = input.split('@').next().unwrap_or("").to_lowercase();
+            for part in input.split(|c: char| !c.is_alphanumeric()) {
+                let part_chars_count = part.chars().count();
+                if part_chars_count >= SIMILAR_MIN_CHARS_COUNT && value.contains(part) {
+                    return true;
+                }
+                if value_chars_count >= SIMILAR_MIN_CHARS_COUNT && part.contains(&value) {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Looks for the SHA-1 of the password in the list sorted by the hash, only the line
+    /// boundaries around the middle of the search range are read from the disk.
+    pub fn is_breached(path: &str, value: &str) -> io::Result<bool> {
+        let hash = hex::encode_upper(sha1(value.as_bytes()));
+        let file = File::open(path)?;
+        let size = file.metadata()?.len();
+        let mut reader = BufReader::new(file);
+
+        // The line with the hash, if any, starts inside low..high.
+        let mut low: u64 = 0;
+        let mut high: u64 = size;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match read_line_from(&mut reader, mid)? {
+                Some((start, line)) => {
+                    let line_hash = line.trim().split(':').next().unwrap_or("").to_uppercase();
+                    match line_hash.as_str().cmp(hash.as_str()) {
+                        Ordering::Equal => return Ok(true),
+                        Ordering::Less => low = start + line.len() as u64,
+                        Ordering::Greater => high = mid,
+                    }
+                }
+                None => high = mid,
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// The errors of all enabled rules, the breached list is skipped when it can not be read.
+    pub fn validate(
+        translator_service: &TranslatorService,
+        lang: &str,
+        config: &AuthPasswordConfig,
+        value: &str,
+        inputs: &[&str],
+        attribute_name: &str,
+    ) -> Vec<String> {
+        let mut errors = StrMinCharsCount::validate(
+            translator_service,
+            lang,
+            value,
+            config.min_length,
+            attribute_name,
+        );
+        let mut errors2 = StrMaxCharsCount::validate(
+            translator_service,
+            lang,
+            value,
+            PASSWORD_MAX_CHARS_COUNT,
+            attribute_name,
+        );
+        errors.append(&mut errors2);
+
+        let mut vars = HashMap::new();
+        vars.insert("attribute", attribute_name);
+
+        let mut keys: Vec<&str> = Vec::new();
+        if config.require_lowercase && !Self::apply_lowercase(value) {
+            keys.push("validation.password.lowercase");
+        }
+        if config.require_uppercase && !Self::apply_uppercase(value) {
+            keys.push("validation.password.uppercase");
+        }
+        if config.require_digit && !Self::apply_digit(value) {
+            keys.push("validation.password.numbers");
+        }
+        if config.require_symbol && !Self::apply_symbol(value) {
+            keys.push("validation.password.symbols");
+        }
+        if config.not_similar && Self::is_similar(value, inputs) {
+            keys.push("validation.password.similar");
+        }
+        if errors.is_empty() && keys.is_empty() && !config.breached_file.is_empty() {
+            match Self::is_breached(&config.breached_file, value) {
+                Ok(true) => keys.push("validation.password.uncompromised"),
+                Ok(false) => {}
+                Err(e) => log::error!(
+                    "PasswordPolicy::validate - {} - {}",
+                    config.breached_file,
+                    e
+                ),
+            }
+        }
+
+        for key in keys {
+            errors.push(translator_service.variables(lang, key, &vars));
+        }
+        errors
+    }
+}
+
+/// The first whole line which starts at the offset or after it, with the offset of its start.
+fn read_line_from(
+    reader: &mut BufReader<File>,
+    offset: u64,
+) -> io::Result<Option<(u64, String)>> {
+    let mut start = offset;
+    if offset > 0 {
+        // The previous byte tells whether the offset is the start of a line.
+        reader.seek(SeekFrom::Start(offset - 1))?;
+        let mut skipped = Vec::new();
+        start = offset - 1 + reader.read_until(b'\n', &mut skipped)? as u64;
+    } else {
+        reader.seek(SeekFrom::Start(0))?;
+    }
+
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some((start, line)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn make_breached_file(name: &str, passwords: &[&str]) -> String {
+        let mut lines: Vec<String> = passwords
+            .iter()
+            .enumerate()
+            .map(|(i, p)| format!("{}:{}\r\n", hex::encode_upper(sha1(p.as_bytes())), i + 1))
+            .collect();
+        lines.sort();
+        let path = std::env::temp_dir().join(format!(
+            "ralaver_breached_{}_{}.txt",
+            name,
+            std::process::id()
+        ));
+        let mut file = File::create(&path).unwrap();
+        file.write_all(lines.concat().as_bytes()).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn apply_classes() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::validator::password_policy::tests::apply_classes
+        assert!(PasswordPolicy::apply_lowercase("ABCd"));
+        assert!(PasswordPolicy::apply_lowercase("ПАРОЛь"));
+        assert!(!PasswordPolicy::apply_lowercase("ABC1!"));
+        assert!(PasswordPolicy::apply_uppercase("abcD"));
+        assert!(!PasswordPolicy::apply_uppercase("abc1!"));
+        assert!(PasswordPolicy::apply_digit("abc1"));
+        assert!(!PasswordPolicy::apply_digit("abc!"));
+        assert!(PasswordPolicy::apply_symbol("abc!"));
+        assert!(PasswordPolicy::apply_symbol("abc d"));
+        assert!(!PasswordPolicy::apply_symbol("abcЁ1"));
+    }
+
+    #[test]
+    fn is_similar() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::validator::password_policy::tests::is_similar
+        let inputs = ["ivan.petrov@example.com", "Петров", "Иван", ""];
+        assert!(PasswordPolicy::is_similar("Petrov2024!", &inputs));
+        assert!(PasswordPolicy::is_similar("IVAN.PETROV", &inputs));
+        assert!(PasswordPolicy::is_similar("мойпетров1", &inputs));
+        assert!(PasswordPolicy::is_similar("petr", &inputs));
+        assert!(!PasswordPolicy::is_similar("example2024", &inputs));
+        assert!(!PasswordPolicy::is_similar("Xk9#mQ2$", &inputs));
+        assert!(!PasswordPolicy::is_similar("Xk9#mQ2$", &[]));
+    }
+
+    #[test]
+    fn is_breached() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::validator::password_policy::tests::is_breached
+        let passwords = [
+            "123456", "password", "qwerty", "admin", "letmein", "111111", "abc123", "пароль",
+            "iloveyou", "dragon", "monkey", "sunshine",
+        ];
+        let path = make_breached_file("is_breached", &passwords);
+
+        for password in passwords {
+            assert!(PasswordPolicy::is_breached(&path, password).unwrap(), "{}", password);
+        }
+        for password in ["Xk9#mQ2$", "1234567", "Password", ""] {
+            assert!(!PasswordPolicy::is_breached(&path, password).unwrap(), "{}", password);
+        }
+
+        let empty_path = make_breached_file("is_breached_empty", &[]);
+        assert!(!PasswordPolicy::is_breached(&empty_path, "123456").unwrap());
+        assert!(PasswordPolicy::is_breached("/not/existing/breached.txt", "123456").is_err());
+
+        std::fs::remove_file(path).unwrap();
+        std::fs::remove_file(empty_path).unwrap();
+    }
+}
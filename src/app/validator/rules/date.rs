@@ -0,0 +1,43 @@
+#![allow(dead_code)]
+use std::collections::HashMap;
+use chrono::NaiveDate;
+use crate::TranslatorService;
+
+pub const DATE_FORMAT: &'static str = "%Y-%m-%d";
+
+pub struct Date;
+
+impl Date {
+    pub fn apply(value: &str) -> bool {
+        NaiveDate::parse_from_str(value, DATE_FORMAT).is_ok()
+    }
+
+    pub fn validate(
+        translator_service: &TranslatorService,
+        lang: &str,
+        value: &str,
+        attribute_name: &str,
+    ) -> Vec<String> {
+        let mut v: Vec<String> = Vec::new();
+        if !Self::apply(value) {
+            let mut vars = HashMap::new();
+            vars.insert("attribute", attribute_name);
+            v.push(translator_service.variables(&lang, "validation.date", &vars));
+        }
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact app::validator::rules::date::tests::apply
+        assert_eq!(true, Date::apply("2025-01-31"));
+        assert_eq!(false, Date::apply("2025-02-31"));
+        assert_eq!(false, Date::apply("31.01.2025"));
+        assert_eq!(false, Date::apply("2025-01-31 00:00:00"));
+    }
+}
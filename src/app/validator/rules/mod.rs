@@ -5,6 +5,7 @@ pub mod bytes_mut_min_length;
 pub mod confirmed;
 pub mod contains_str;
 pub mod contains_vec_str;
+pub mod date;
 pub mod email;
 pub mod mimes;
 pub mod required;
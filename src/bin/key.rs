@@ -0,0 +1,146 @@
+#![feature(test)]
+#![allow(dead_code)]
+extern crate core;
+extern crate test;
+
+#[path = "../app/mod.rs"]
+pub mod app;
+#[path = "../config.rs"]
+pub mod config;
+#[path = "../connections.rs"]
+pub mod connections;
+#[path = "../errors.rs"]
+pub mod errors;
+#[path = "../factories/mod.rs"]
+pub mod factories;
+#[path = "../helpers.rs"]
+pub mod helpers;
+#[path = "../libs/mod.rs"]
+pub mod libs;
+#[path = "../migrations/mod.rs"]
+pub mod migrations;
+#[path = "../routes/mod.rs"]
+pub mod routes;
+#[path = "../seeders/mod.rs"]
+pub mod seeders;
+#[path = "../services.rs"]
+pub mod services;
+
+use crate::services::Services;
+use actix_web::web::Data;
+pub use app::connections::mysql as mysql_connection;
+pub use app::connections::redis as redis_connection;
+pub use app::controllers::web::WebHttpRequest;
+pub use app::controllers::web::WebHttpResponse;
+pub use app::dto::*;
+pub use app::policies::*;
+pub use app::repositories::*;
+pub use app::services::*;
+pub use config::Config;
+pub use connections::Connections;
+pub use errors::AppError;
+pub use mysql_connection::MysqlPool;
+pub use mysql_connection::MysqlPooledConnection;
+use std::path::Path;
+use std::{env, fs, process};
+static COMMANDS_MESSAGE: &str =
+    "Allowed commands: \"generate\", \"rotate\", \"reencrypt\".";
+
+fn preparation<'a>() -> (Connections, Services<'a>) {
+    let config = Data::new(Config::new());
+
+    let all_connections: Connections = connections::all(config.get_ref());
+
+    let all_services = services::build(&all_connections, config);
+
+    (all_connections, all_services)
+}
+
+fn main() {
+    let env_path = dotenv::dotenv().ok();
+    let _ = env_logger::try_init_from_env(env_logger::Env::new().default_filter_or("info"));
+
+    let args: Vec<String> = env::args().collect();
+    let command = match args.get(1) {
+        Some(command) => command.as_str(),
+        None => exit_with_error(&format!("The command is missing. {}", COMMANDS_MESSAGE), 2),
+    };
+
+    let result = match command {
+        // Prints the new key without changing anything.
+        "generate" => {
+            println!("{}", CryptService::random_key());
+            Ok(())
+        }
+        // Replaces APP_KEY in the .env file, the old key is moved to APP_PREVIOUS_KEYS.
+        "rotate" => match &env_path {
+            Some(env_path) => rotate(env_path).and_then(|_| reencrypt()),
+            None => Err("The .env file is not found.".to_string()),
+        },
+        // Encrypts the stored secrets by the current APP_KEY.
+        "reencrypt" => reencrypt(),
+        command => exit_with_error(&format!("Wrong command \"{}\". {}", command, COMMANDS_MESSAGE), 2),
+    };
+
+    if let Err(e) = result {
+        exit_with_error(&e, 1);
+    }
+}
+
+fn exit_with_error(message: &str, code: i32) -> ! {
+    log::error!("{}", message);
+    process::exit(code);
+}
+
+fn rotate(env_path: &Path) -> Result<(), String> {
+    let key = env::var("APP_KEY").map_err(|_| "APP_KEY is missing!".to_string())?;
+    let key = key.trim().to_string();
+    let mut previous_keys: Vec<String> = vec![key];
+    for previous_key in env::var("APP_PREVIOUS_KEYS").unwrap_or("".to_string()).split(',') {
+        let previous_key = previous_key.trim();
+        if !previous_key.is_empty() && !previous_keys.iter().any(|k| k == previous_key) {
+            previous_keys.push(previous_key.to_string());
+        }
+    }
+    let new_key = CryptService::random_key();
+    let previous_keys = previous_keys.join(",");
+
+    let content = fs::read_to_string(env_path)
+        .map_err(|e| format!("Failed to read the .env file - {}", e))?;
+    let mut lines: Vec<String> = Vec::new();
+    let mut is_previous_keys_found = false;
+    for line in content.lines() {
+        if line.starts_with("APP_KEY=") {
+            lines.push(format!("APP_KEY={}", new_key));
+        } else if line.starts_with("APP_PREVIOUS_KEYS=") {
+            lines.push(format!("APP_PREVIOUS_KEYS={}", previous_keys));
+            is_previous_keys_found = true;
+        } else {
+            lines.push(line.to_string());
+        }
+    }
+    if !is_previous_keys_found {
+        lines.push(format!("APP_PREVIOUS_KEYS={}", previous_keys));
+    }
+    let mut content = lines.join("\n");
+    content.push('\n');
+    fs::write(env_path, content).map_err(|e| format!("Failed to write the .env file - {}", e))?;
+
+    env::set_var("APP_KEY", &new_key);
+    env::set_var("APP_PREVIOUS_KEYS", &previous_keys);
+    log::info!("The APP_KEY was rotated - {}", env_path.display());
+    Ok(())
+}
+
+/// Re-encrypts every column stored by CryptService::encrypt_string, a new one has to be added here.
+/// Sessions and signed links are not stored, they are checked by the previous keys.
+fn reencrypt() -> Result<(), String> {
+    let (_, all_services) = preparation();
+    let count = all_services
+        .user_two_factor_service
+        .get_ref()
+        .reencrypt_secrets()
+        .map_err(|e| format!("Failed to re-encrypt the two-factor secrets - {}", e))?;
+    log::info!("Re-encrypted the two-factor secrets - {}", count);
+    Ok(())
+}
@@ -0,0 +1,77 @@
+#![feature(test)]
+#![allow(dead_code)]
+extern crate core;
+extern crate test;
+
+#[path = "../app/mod.rs"]
+pub mod app;
+#[path = "../config.rs"]
+pub mod config;
+#[path = "../connections.rs"]
+pub mod connections;
+#[path = "../errors.rs"]
+pub mod errors;
+#[path = "../factories/mod.rs"]
+pub mod factories;
+#[path = "../helpers.rs"]
+pub mod helpers;
+#[path = "../libs/mod.rs"]
+pub mod libs;
+#[path = "../migrations/mod.rs"]
+pub mod migrations;
+#[path = "../routes/mod.rs"]
+pub mod routes;
+#[path = "../seeders/mod.rs"]
+pub mod seeders;
+#[path = "../services.rs"]
+pub mod services;
+
+use crate::services::Services;
+use actix_web::web::Data;
+pub use app::connections::mysql as mysql_connection;
+pub use app::connections::redis as redis_connection;
+pub use app::controllers::web::WebHttpRequest;
+pub use app::controllers::web::WebHttpResponse;
+pub use app::dto::*;
+pub use app::policies::*;
+pub use app::repositories::*;
+pub use app::services::*;
+pub use config::Config;
+pub use connections::Connections;
+pub use errors::AppError;
+pub use mysql_connection::MysqlPool;
+pub use mysql_connection::MysqlPooledConnection;
+use seeders::{Options, COMMANDS_MESSAGE};
+use std::{env, process};
+
+fn preparation<'a>() -> (Connections, Services<'a>) {
+    let config = Data::new(Config::new());
+
+    let all_connections: Connections = connections::all(config.get_ref());
+
+    let all_services = services::build(&all_connections, config);
+
+    (all_connections, all_services)
+}
+
+fn main() {
+    dotenv::dotenv().ok();
+    let _ = env_logger::try_init_from_env(env_logger::Env::new().default_filter_or("info"));
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match Options::parse(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            log::error!("{} {}", e, COMMANDS_MESSAGE);
+            process::exit(2);
+        }
+    };
+
+    // The seeders work through the services, so all connections are opened.
+    let (_, all_services) = preparation();
+    if let Err(e) = seeders::run(&all_services, &options.names, options.count) {
+        log::error!("Failed to seed the database - {}", e);
+        process::exit(1);
+    }
+    log::info!("The seeding was successful!");
+}
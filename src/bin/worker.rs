@@ -0,0 +1,168 @@
+#![feature(test)]
+#![allow(dead_code)]
+extern crate core;
+extern crate test;
+
+#[path = "../app/mod.rs"]
+pub mod app;
+#[path = "../config.rs"]
+pub mod config;
+#[path = "../connections.rs"]
+pub mod connections;
+#[path = "../errors.rs"]
+pub mod errors;
+#[path = "../factories/mod.rs"]
+pub mod factories;
+#[path = "../helpers.rs"]
+pub mod helpers;
+#[path = "../libs/mod.rs"]
+pub mod libs;
+#[path = "../migrations/mod.rs"]
+pub mod migrations;
+#[path = "../routes/mod.rs"]
+pub mod routes;
+#[path = "../seeders/mod.rs"]
+pub mod seeders;
+#[path = "../services.rs"]
+pub mod services;
+
+use crate::services::Services;
+use actix_web::web::Data;
+pub use app::connections::mysql as mysql_connection;
+pub use app::connections::redis as redis_connection;
+pub use app::controllers::web::WebHttpRequest;
+pub use app::controllers::web::WebHttpResponse;
+pub use app::dto::*;
+pub use app::policies::*;
+pub use app::repositories::*;
+pub use app::services::*;
+pub use config::Config;
+pub use connections::Connections;
+pub use errors::AppError;
+pub use mysql_connection::MysqlPool;
+pub use mysql_connection::MysqlPooledConnection;
+use std::thread::sleep;
+use std::time::Duration;
+
+// The pause when the queue is empty.
+const WORKER_IDLE_SLEEP: u64 = 1;
+// The deleted files are collected once per hour by one of the workers.
+const WORKER_COLLECT_FILE_GARBAGE_PERIOD: i64 = 3600;
+
+fn preparation<'a>() -> (Connections, Services<'a>) {
+    dotenv::dotenv().ok();
+    let config = Data::new(Config::new());
+    let _ = env_logger::try_init_from_env(env_logger::Env::new().default_filter_or("info"));
+
+    let all_connections: Connections = connections::all(config.get_ref());
+
+    let all_services = services::build(&all_connections, config);
+
+    (all_connections, all_services)
+}
+
+fn main() {
+    let (_, all_services) = preparation();
+    let job_service = all_services.job_service.get_ref();
+
+    log::info!("Starting worker");
+
+    loop {
+        let job = match job_service.reserve() {
+            Ok(job) => job,
+            Err(_) => {
+                sleep(Duration::from_secs(WORKER_IDLE_SLEEP));
+                continue;
+            }
+        };
+
+        let mut job = match job {
+            Some(job) => job,
+            None => {
+                let _ = job_service.dispatch_once_per(
+                    JobPayload::CollectFileGarbage,
+                    WORKER_COLLECT_FILE_GARBAGE_PERIOD,
+                );
+                let ldap_config = &all_services.config.get_ref().auth.ldap;
+                if ldap_config.enabled {
+                    let _ = job_service
+                        .dispatch_once_per(JobPayload::SyncLdapUsers, ldap_config.sync_period);
+                }
+                sleep(Duration::from_secs(WORKER_IDLE_SLEEP));
+                continue;
+            }
+        };
+
+        log::info!("Processing job {} - {}", job.id, job.payload);
+        match handle(&all_services, &job.payload) {
+            Ok(_) => {
+                let _ = job_service.complete(&job);
+                log::info!("Processed job {} - {}", job.id, job.payload);
+            }
+            Err(e) => {
+                log::error!("Failed job {} - {} - {}", job.id, job.payload, e);
+                let _ = job_service.release(&mut job, &e);
+            }
+        }
+    }
+}
+
+fn handle(all_services: &Services, payload: &JobPayload) -> Result<(), String> {
+    match payload {
+        JobPayload::SendEmail(message) => all_services
+            .mail_service
+            .get_ref()
+            .send_email(message)
+            .map_err(|e| e.to_string()),
+        JobPayload::GenerateFileVariants {
+            file_id,
+            is_replaced,
+        } => {
+            let file = all_services
+                .file_service
+                .get_ref()
+                .first_by_id(*file_id)
+                .map_err(|e| e.to_string())?;
+            match file {
+                Some(file) if !file.is_deleted => all_services
+                    .file_variant_service
+                    .get_ref()
+                    .refresh(&file, *is_replaced)
+                    .map_err(|e| e.to_string()),
+                _ => Ok(()),
+            }
+        }
+        JobPayload::CollectFileGarbage => {
+            let count = all_services
+                .file_service
+                .get_ref()
+                .collect_garbage()
+                .map_err(|e| e.to_string())?;
+            log::info!("Deleted files from the disk - {}", count);
+            Ok(())
+        }
+        JobPayload::SyncLdapUsers => {
+            let result = all_services
+                .ldap_service
+                .get_ref()
+                .sync()
+                .map_err(|e| e.to_string())?;
+            log::info!(
+                "Synchronized users with the directory - updated {}, disabled {}, skipped {}",
+                result.updated,
+                result.disabled,
+                result.skipped
+            );
+            Ok(())
+        }
+        JobPayload::Export { user_id, params } => {
+            let user_file = all_services
+                .export_service
+                .get_ref()
+                .export_to_user_file(*user_id, params)
+                .map_err(|e| e.to_string())?;
+            log::info!("Exported {} - user file {}", params.entity, user_file.id);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,658 @@
+use crate::app::controllers::web::users::create_update::audit_user_value;
+use crate::app::validator::password_policy::PasswordPolicy;
+use crate::app::validator::rules::email::Email;
+use crate::services::Services;
+use crate::{AuditLogAction, AuditLogEntity, AuditLogService, Role, TranslatableError, User, UserColumn};
+use serde_json::{json, Value};
+use std::io;
+use std::io::BufRead;
+
+pub static COMMANDS_MESSAGE: &str = "Allowed commands: \"create-user\", \"create-super-admin\", \
+    \"set-password\", \"add-roles\", \"remove-roles\", \"list-users\", \"revoke-sessions\", \
+    \"clear-rate-limits\", \"permissions\". The \"--json\" option prints the result as JSON.";
+// The options with the value, the other options are the flags.
+static VALUE_OPTIONS: [&str; 8] = [
+    "--surname", "--name", "--patronymic", "--locale", "--roles", "--role", "--ip", "--key",
+];
+const GENERATED_PASSWORD_LENGTH: usize = 20;
+// The audit log has no user for the changes made by the commands.
+pub const AUDIT_LOG_ACTOR: &str = "cli";
+
+#[derive(Debug, Default)]
+pub struct Args {
+    pub command: String,
+    pub positional: Vec<String>,
+    pub options: Vec<(String, Option<String>)>,
+}
+
+impl Args {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut result = Self::default();
+        let mut iter = args.iter();
+        result.command = iter.next().ok_or("The command is missing.")?.to_owned();
+
+        while let Some(arg) = iter.next() {
+            if !arg.starts_with("--") {
+                result.positional.push(arg.to_owned());
+            } else if VALUE_OPTIONS.contains(&arg.as_str()) {
+                let value = iter
+                    .next()
+                    .ok_or(format!("The value of \"{}\" is missing.", arg))?;
+                result.options.push((arg.to_owned(), Some(value.to_owned())));
+            } else {
+                result.options.push((arg.to_owned(), None));
+            }
+        }
+        Ok(result)
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        self.options.iter().any(|(n, _)| n.eq(name))
+    }
+
+    fn value(&self, name: &str) -> Option<String> {
+        self.options
+            .iter()
+            .find(|(n, _)| n.eq(name))
+            .and_then(|(_, v)| v.to_owned())
+    }
+
+    fn email(&self) -> Result<String, String> {
+        self.positional
+            .first()
+            .map(|e| e.trim().to_lowercase())
+            .ok_or("The email is missing.".to_string())
+    }
+
+    fn check_options(&self, allowed: &[&str]) -> Result<(), String> {
+        for (name, _) in &self.options {
+            if name.ne("--json") && !allowed.contains(&name.as_str()) {
+                return Err(format!(
+                    "The option \"{}\" is not allowed for \"{}\".",
+                    name, self.command
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The result of the command: the JSON for the scripts and the text for the people.
+pub struct Output {
+    pub json: Value,
+    pub text: String,
+}
+
+pub fn run(services: &Services, args: &Args) -> Result<Output, String> {
+    match args.command.as_str() {
+        "create-user" => create_user(services, args, false),
+        "create-super-admin" => create_user(services, args, true),
+        "set-password" => set_password(services, args),
+        "add-roles" => change_roles(services, args, true),
+        "remove-roles" => change_roles(services, args, false),
+        "list-users" => list_users(services, args),
+        "revoke-sessions" => revoke_sessions(services, args),
+        "clear-rate-limits" => clear_rate_limits(services, args),
+        "permissions" => permissions(services, args),
+        command => Err(format!("Wrong command \"{}\". {}", command, COMMANDS_MESSAGE)),
+    }
+}
+
+fn default_lang(services: &Services) -> String {
+    services.locale_service.get_ref().get_default_ref().code.to_owned()
+}
+
+fn translate_error(services: &Services, e: &impl TranslatableError) -> String {
+    e.translate(&default_lang(services), services.translator_service.get_ref())
+}
+
+fn find_user(services: &Services, email: &str) -> Result<User, String> {
+    services
+        .user_service
+        .get_ref()
+        .first_by_email(email)
+        .map_err(|e| translate_error(services, &e))?
+        .ok_or(format!("The user \"{}\" is not found.", email))
+}
+
+fn record_audit_log(
+    services: &Services,
+    action: AuditLogAction,
+    entity_type: AuditLogEntity,
+    entity_id: u64,
+    old_values: Option<Value>,
+    new_values: Option<Value>,
+) -> Result<(), String> {
+    services
+        .audit_log_service
+        .get_ref()
+        .record_console(AUDIT_LOG_ACTOR, action, entity_type, entity_id, old_values, new_values)
+        .map_err(|e| translate_error(services, &e))
+}
+
+fn find_roles(services: &Services, codes: &[String]) -> Result<Vec<Role>, String> {
+    let role_service = services.role_service.get_ref();
+    let mut roles: Vec<Role> = Vec::new();
+    for code in codes {
+        let role = role_service
+            .first_by_code(code.trim())
+            .map_err(|e| translate_error(services, &e))?
+            .ok_or(format!("The role \"{}\" is not found.", code))?;
+        roles.push(role);
+    }
+    Ok(roles)
+}
+
+fn user_json(user: &User, roles: &[Role]) -> Value {
+    let roles_ids = user.roles_ids.to_owned().unwrap_or_default();
+    let codes: Vec<&str> = roles
+        .iter()
+        .filter(|r| roles_ids.contains(&r.id))
+        .map(|r| r.code.as_str())
+        .collect();
+    json!({
+        "id": user.id,
+        "email": &user.email,
+        "surname": &user.surname,
+        "name": &user.name,
+        "patronymic": &user.patronymic,
+        "locale": &user.locale,
+        "is_super_admin": user.is_super_admin,
+        "roles": codes,
+        "email_verified_at": &user.email_verified_at,
+        "disabled_at": &user.disabled_at,
+        "is_ldap": user.is_ldap(),
+    })
+}
+
+fn user_text(user: &User, roles: &[Role]) -> String {
+    let roles_ids = user.roles_ids.to_owned().unwrap_or_default();
+    let codes: Vec<&str> = roles
+        .iter()
+        .filter(|r| roles_ids.contains(&r.id))
+        .map(|r| r.code.as_str())
+        .collect();
+    let mut flags: Vec<&str> = Vec::new();
+    if user.is_super_admin {
+        flags.push("super admin");
+    }
+    if user.is_disabled() {
+        flags.push("disabled");
+    }
+    if user.is_ldap() {
+        flags.push("ldap");
+    }
+    format!(
+        "{}\t{}\t{}\t[{}]\t{}",
+        user.id,
+        user.get_full_name_with_id_and_email(),
+        codes.join(","),
+        flags.join(","),
+        user.locale.as_deref().unwrap_or("")
+    )
+}
+
+/// The password from the first line of stdin is checked by the policy, without it a strong one is generated.
+fn read_or_generate_password(
+    services: &Services,
+    args: &Args,
+    user: &User,
+) -> Result<(String, bool), String> {
+    let config = services.config.get_ref();
+    let inputs = [
+        user.email.as_str(),
+        user.surname.as_deref().unwrap_or(""),
+        user.name.as_deref().unwrap_or(""),
+        user.patronymic.as_deref().unwrap_or(""),
+    ];
+
+    if args.flag("--password-stdin") {
+        let mut password = String::new();
+        io::stdin()
+            .lock()
+            .read_line(&mut password)
+            .map_err(|e| e.to_string())?;
+        let password = password.trim_end_matches(['\r', '\n']).to_string();
+        let errors = PasswordPolicy::validate(
+            services.translator_service.get_ref(),
+            &default_lang(services),
+            &config.auth.password,
+            &password,
+            &inputs,
+            "password",
+        );
+        if !errors.is_empty() {
+            return Err(errors.join(" "));
+        }
+        return Ok((password, false));
+    }
+
+    let rand_service = services.rand_service.get_ref();
+    loop {
+        // The symbols make it match the policy which requires them.
+        let password = format!(
+            "{}-{}",
+            rand_service.str(GENERATED_PASSWORD_LENGTH / 2),
+            rand_service.str(GENERATED_PASSWORD_LENGTH / 2)
+        );
+        let is_valid = PasswordPolicy::validate(
+            services.translator_service.get_ref(),
+            &default_lang(services),
+            &config.auth.password,
+            &password,
+            &inputs,
+            "password",
+        )
+        .is_empty();
+        if is_valid {
+            return Ok((password, true));
+        }
+    }
+}
+
+fn create_user(services: &Services, args: &Args, is_super_admin: bool) -> Result<Output, String> {
+    args.check_options(&[
+        "--surname",
+        "--name",
+        "--patronymic",
+        "--locale",
+        "--roles",
+        "--password-stdin",
+    ])?;
+    let user_service = services.user_service.get_ref();
+    let locale_service = services.locale_service.get_ref();
+
+    let email = args.email()?;
+    let errors = Email::validate(
+        services.translator_service.get_ref(),
+        &default_lang(services),
+        &email,
+        "email",
+    );
+    if !errors.is_empty() {
+        return Err(errors.join(" "));
+    }
+    let locale = match args.value("--locale") {
+        Some(locale) if locale_service.get_locale_ref(&locale).is_none() => {
+            return Err(format!("The locale \"{}\" is not found.", locale));
+        }
+        Some(locale) => locale,
+        None => default_lang(services),
+    };
+    let codes: Vec<String> = split_codes(&args.value("--roles").unwrap_or_default());
+    let roles = find_roles(services, &codes)?;
+
+    let mut user = User::empty(email.to_owned());
+    user.surname = args.value("--surname");
+    user.name = args.value("--name");
+    user.patronymic = args.value("--patronymic");
+    user.locale = Some(locale);
+    user.is_super_admin = is_super_admin;
+    if !roles.is_empty() {
+        user.roles_ids = Some(roles.iter().map(|r| r.id).collect());
+    }
+    let (password, is_generated) = read_or_generate_password(services, args, &user)?;
+
+    user_service
+        .upsert(user, &None)
+        .map_err(|e| translate_error(services, &e))?;
+    let user = find_user(services, &email)?;
+    user_service
+        .update_password_by_id(user.id, &password)
+        .map_err(|e| translate_error(services, &e))?;
+    let user = find_user(services, &email)?;
+    record_audit_log(
+        services,
+        AuditLogAction::Create,
+        AuditLogEntity::User,
+        user.id,
+        None,
+        audit_user_value(&user, &Some(password.to_owned())),
+    )?;
+
+    let mut json = user_json(&user, &roles);
+    let mut text = format!("The user was created.\n{}", user_text(&user, &roles));
+    if is_generated {
+        json["password"] = json!(&password);
+        text.push_str(&format!("\nThe password: {}", password));
+    }
+    Ok(Output { json, text })
+}
+
+fn set_password(services: &Services, args: &Args) -> Result<Output, String> {
+    args.check_options(&["--password-stdin"])?;
+    let user = find_user(services, &args.email()?)?;
+    if user.is_ldap() {
+        return Err("The password of the directory user is managed by the directory.".to_string());
+    }
+    let (password, is_generated) = read_or_generate_password(services, args, &user)?;
+
+    services
+        .user_service
+        .get_ref()
+        .update_password_by_id(user.id, &password)
+        .map_err(|e| translate_error(services, &e))?;
+    let new_user = find_user(services, &user.email)?;
+    record_audit_log(
+        services,
+        AuditLogAction::Update,
+        AuditLogEntity::User,
+        user.id,
+        AuditLogService::to_value(&user),
+        audit_user_value(&new_user, &Some(password.to_owned())),
+    )?;
+
+    let mut json = json!({ "id": user.id, "email": &user.email });
+    let mut text = format!("The password of {} was set.", user.email);
+    if is_generated {
+        json["password"] = json!(&password);
+        text.push_str(&format!("\nThe password: {}", password));
+    }
+    Ok(Output { json, text })
+}
+
+/// Returns None instead of the empty list, as the users without roles are stored.
+fn change_roles_ids(roles_ids: &Option<Vec<u64>>, roles: &[Role], is_add: bool) -> Option<Vec<u64>> {
+    let mut roles_ids = roles_ids.to_owned().unwrap_or_default();
+    for role in roles {
+        if is_add && !roles_ids.contains(&role.id) {
+            roles_ids.push(role.id);
+        } else if !is_add {
+            roles_ids.retain(|id| *id != role.id);
+        }
+    }
+    if roles_ids.is_empty() {
+        None
+    } else {
+        Some(roles_ids)
+    }
+}
+
+fn split_codes(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .filter(|c| !c.trim().is_empty())
+        .map(|c| c.to_string())
+        .collect()
+}
+
+fn change_roles(services: &Services, args: &Args, is_add: bool) -> Result<Output, String> {
+    args.check_options(&[])?;
+    let mut user = find_user(services, &args.email()?)?;
+    let codes: Vec<String> = args.positional.iter().skip(1).cloned().collect();
+    if codes.is_empty() {
+        return Err("The codes of the roles are missing.".to_string());
+    }
+    let roles = find_roles(services, &codes)?;
+
+    let old_value = AuditLogService::to_value(&user);
+    user.roles_ids = change_roles_ids(&user.roles_ids, &roles, is_add);
+
+    services
+        .user_service
+        .get_ref()
+        .update(&user, &Some(vec![UserColumn::RolesIds]))
+        .map_err(|e| translate_error(services, &e))?;
+    record_audit_log(
+        services,
+        AuditLogAction::Update,
+        AuditLogEntity::User,
+        user.id,
+        old_value,
+        AuditLogService::to_value(&user),
+    )?;
+
+    let all_roles = services
+        .role_service
+        .get_ref()
+        .all()
+        .map_err(|e| translate_error(services, &e))?;
+    Ok(Output {
+        json: user_json(&user, &all_roles),
+        text: user_text(&user, &all_roles),
+    })
+}
+
+fn list_users(services: &Services, args: &Args) -> Result<Output, String> {
+    args.check_options(&["--role", "--super-admins"])?;
+    let role = match args.value("--role") {
+        Some(code) => find_roles(services, &[code])?.pop(),
+        None => None,
+    };
+    let is_super_admins = args.flag("--super-admins");
+
+    let users: Vec<User> = services
+        .user_service
+        .get_ref()
+        .all()
+        .map_err(|e| translate_error(services, &e))?
+        .into_iter()
+        .filter(|u| !is_super_admins || u.is_super_admin)
+        .filter(|u| match &role {
+            Some(role) => u.roles_ids.as_ref().is_some_and(|ids| ids.contains(&role.id)),
+            None => true,
+        })
+        .collect();
+    let roles = services
+        .role_service
+        .get_ref()
+        .all()
+        .map_err(|e| translate_error(services, &e))?;
+
+    let json: Vec<Value> = users.iter().map(|u| user_json(u, &roles)).collect();
+    let mut lines: Vec<String> = users.iter().map(|u| user_text(u, &roles)).collect();
+    lines.push(format!("Total: {}", users.len()));
+    Ok(Output {
+        json: json!(json),
+        text: lines.join("\n"),
+    })
+}
+
+fn revoke_sessions(services: &Services, args: &Args) -> Result<Output, String> {
+    args.check_options(&[])?;
+    let user = find_user(services, &args.email()?)?;
+    let web_auth_service = services.web_auth_service.get_ref();
+
+    let count = web_auth_service
+        .all_sessions(user.id)
+        .map_err(|e| e.to_string())?
+        .len();
+    web_auth_service
+        .revoke_all_sessions(user.id, None)
+        .map_err(|e| e.to_string())?;
+    record_audit_log(
+        services,
+        AuditLogAction::Update,
+        AuditLogEntity::User,
+        user.id,
+        Some(json!({"sessions": count})),
+        Some(json!({"sessions": 0})),
+    )?;
+
+    Ok(Output {
+        json: json!({ "id": user.id, "email": &user.email, "revoked": count }),
+        text: format!("The sessions of {} were revoked - {}", user.email, count),
+    })
+}
+
+/// The limits are stored by the key "[ip].[name]", any part that is not given matches all.
+fn rate_limit_pattern(args: &Args) -> String {
+    format!(
+        "{}.{}",
+        args.value("--ip").unwrap_or("*".to_string()),
+        args.value("--key").unwrap_or("*".to_string())
+    )
+}
+
+/// The lockouts of the accounts are cleared by the email.
+fn clear_rate_limits(services: &Services, args: &Args) -> Result<Output, String> {
+    args.check_options(&["--ip", "--key"])?;
+    let pattern = rate_limit_pattern(args);
+    let count = services
+        .rate_limit_service
+        .get_ref()
+        .clear_by_pattern(&pattern)
+        .map_err(|e| e.to_string())?;
+    record_audit_log(
+        services,
+        AuditLogAction::Delete,
+        AuditLogEntity::RateLimit,
+        0,
+        Some(json!({"pattern": &pattern, "cleared": count})),
+        None,
+    )?;
+
+    let mut unlocked: Vec<String> = Vec::new();
+    let login_security_service = services.login_security_service.get_ref();
+    for email in &args.positional {
+        let email = email.trim().to_lowercase();
+        login_security_service
+            .unlock(&email)
+            .map_err(|e| translate_error(services, &e))?;
+        // The lockouts are kept by the email, so it may have no user.
+        let user = services
+            .user_service
+            .get_ref()
+            .first_by_email(&email)
+            .map_err(|e| translate_error(services, &e))?;
+        if let Some(user) = user {
+            record_audit_log(
+                services,
+                AuditLogAction::Update,
+                AuditLogEntity::User,
+                user.id,
+                Some(json!({"locked": true})),
+                Some(json!({"locked": false})),
+            )?;
+        }
+        unlocked.push(email);
+    }
+
+    let mut text = format!("The rate limits were cleared - {}", count);
+    if !unlocked.is_empty() {
+        text.push_str(&format!("\nThe accounts were unlocked - {}", unlocked.join(", ")));
+    }
+    Ok(Output {
+        json: json!({ "pattern": pattern, "cleared": count, "unlocked": unlocked }),
+        text,
+    })
+}
+
+fn permissions(services: &Services, args: &Args) -> Result<Output, String> {
+    args.check_options(&[])?;
+    let user = find_user(services, &args.email()?)?;
+    let permissions = services
+        .role_service
+        .get_ref()
+        .user_permissions(&user)
+        .map_err(|e| translate_error(services, &e))?;
+
+    let mut text = user.get_full_name_with_id_and_email();
+    if user.is_super_admin {
+        text.push_str(" (super admin)");
+    }
+    for permission in &permissions {
+        text.push_str(&format!("\n{}", permission));
+    }
+    Ok(Output {
+        json: json!({
+            "id": user.id,
+            "email": &user.email,
+            "is_super_admin": user.is_super_admin,
+            "permissions": permissions,
+        }),
+        text,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn role(id: u64, code: &str) -> Role {
+        Role {
+            id,
+            code: code.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_args_parse() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact commands::admin::tests::test_args_parse
+        let args = Args::parse(&strings(&[
+            "create-user", " Admin@Example.com", "--name", "Ivan", "--password-stdin", "--json",
+        ]))
+        .unwrap();
+        assert_eq!(args.command, "create-user");
+        assert_eq!(args.email().unwrap(), "admin@example.com");
+        assert_eq!(args.value("--name"), Some("Ivan".to_string()));
+        assert_eq!(args.value("--surname"), None);
+        assert!(args.flag("--password-stdin"));
+        assert!(!args.flag("--name-stdin"));
+        assert!(args.check_options(&["--name", "--password-stdin"]).is_ok());
+        assert!(args.check_options(&["--name"]).is_err());
+
+        let args = Args::parse(&strings(&["add-roles", "a@a.a", "admin", "editor"])).unwrap();
+        assert_eq!(args.positional, strings(&["a@a.a", "admin", "editor"]));
+
+        assert!(Args::parse(&[]).is_err());
+        assert!(Args::parse(&strings(&["create-user", "a@a.a", "--roles"])).is_err());
+        assert!(Args::parse(&strings(&["set-password"])).unwrap().email().is_err());
+    }
+
+    #[test]
+    fn test_change_roles_ids() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact commands::admin::tests::test_change_roles_ids
+        let roles = vec![role(2, "editor"), role(3, "viewer")];
+        assert_eq!(change_roles_ids(&None, &roles, true), Some(vec![2, 3]));
+        assert_eq!(change_roles_ids(&Some(vec![1, 2]), &roles, true), Some(vec![1, 2, 3]));
+        assert_eq!(change_roles_ids(&Some(vec![1, 2]), &roles, false), Some(vec![1]));
+        assert_eq!(change_roles_ids(&Some(vec![2, 3]), &roles, false), None);
+        assert_eq!(change_roles_ids(&None, &roles, false), None);
+        assert_eq!(change_roles_ids(&Some(vec![1]), &[], true), Some(vec![1]));
+    }
+
+    #[test]
+    fn test_split_codes() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact commands::admin::tests::test_split_codes
+        assert_eq!(split_codes("admin,editor"), strings(&["admin", "editor"]));
+        assert_eq!(split_codes("admin,, ,editor,"), strings(&["admin", "editor"]));
+        assert!(split_codes("").is_empty());
+    }
+
+    #[test]
+    fn test_rate_limit_pattern() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact commands::admin::tests::test_rate_limit_pattern
+        let args = Args::parse(&strings(&["clear-rate-limits"])).unwrap();
+        assert_eq!(rate_limit_pattern(&args), "*.*");
+        let args = Args::parse(&strings(&["clear-rate-limits", "--ip", "127.0.0.1"])).unwrap();
+        assert_eq!(rate_limit_pattern(&args), "127.0.0.1.*");
+        let args = Args::parse(&strings(&["clear-rate-limits", "--key", "login"])).unwrap();
+        assert_eq!(rate_limit_pattern(&args), "*.login");
+    }
+
+    #[test]
+    fn test_user_output() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact commands::admin::tests::test_user_output
+        let roles = vec![role(1, "admin"), role(2, "editor"), role(3, "viewer")];
+        let mut user = User::empty("a@a.a".to_string());
+        user.id = 5;
+        user.is_super_admin = true;
+        user.roles_ids = Some(vec![1, 3]);
+
+        let json = user_json(&user, &roles);
+        assert_eq!(json["id"], 5);
+        assert_eq!(json["email"], "a@a.a");
+        assert_eq!(json["is_super_admin"], true);
+        assert_eq!(json["roles"], json!(["admin", "viewer"]));
+
+        let text = user_text(&user, &roles);
+        assert!(text.starts_with("5\t"));
+        assert!(text.contains("\tadmin,viewer\t[super admin]\t"));
+    }
+}
@@ -8,6 +8,7 @@ pub struct Config {
     pub app: AppConfig,
     pub db: DbConfig,
     pub auth: AuthConfig,
+    pub hash: HashConfig,
     pub translator: TranslatorConfig,
     pub template: TemplateConfig,
     pub mail: MailConfig,
@@ -17,12 +18,29 @@ pub struct Config {
 #[derive(Debug, Clone)]
 pub struct FilesystemConfig {
     pub default: String,
+    // The upload limit in bytes for users whose roles do not set it.
+    pub upload_max_size: u64,
+    // The versions generated for the uploaded images.
+    pub image_variants: Vec<FilesystemImageVariantConfig>,
     pub disks: FilesystemDisksConfig
 }
 
+#[derive(Debug, Clone)]
+pub struct FilesystemImageVariantConfig {
+    // The name used in the ?variant= parameter.
+    pub name: String,
+    // "resize" keeps the proportions inside the box, "crop" fills the box, "none" keeps the size.
+    pub fit: String,
+    pub width: u32,
+    pub height: u32,
+    // "webp" or "original"
+    pub format: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct FilesystemDisksConfig {
     pub local: FilesystemLocalDiskConfig,
+    pub s3: FilesystemS3DiskConfig,
 }
 
 #[derive(Debug, Clone)]
@@ -32,6 +50,21 @@ pub struct FilesystemLocalDiskConfig {
     pub url_path: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct FilesystemS3DiskConfig {
+    pub key: String,
+    pub secret: String,
+    pub region: String,
+    pub bucket: String,
+    // The public url of the bucket, presigned urls are used when it is empty.
+    pub url: String,
+    pub endpoint: String,
+    pub use_path_style_endpoint: bool,
+    pub root: String,
+    // in seconds
+    pub presigned_expires: u64,
+}
+
 #[derive(Debug, Clone)]
 pub struct DbConfig {
     pub mysql: MysqlDbConfig,
@@ -61,6 +94,8 @@ pub struct KVConfig {
 #[derive(Debug, Clone)]
 pub struct AppConfig {
     pub key: String,
+    // The old keys, they are only used to decrypt the values made before the key rotation.
+    pub previous_keys: Vec<String>,
     pub url: String,
     pub locale: String,
     pub fallback_locale: String,
@@ -71,7 +106,106 @@ pub struct AppConfig {
 
 #[derive(Debug, Clone)]
 pub struct AuthConfig {
-    pub cookie: AuthCookieConfig
+    pub cookie: AuthCookieConfig,
+    // The self-registered users can not open the panel until they confirm the email.
+    pub email_verification: bool,
+    // in seconds
+    pub email_verification_expires: i64,
+    // in seconds, the lifetime of the invitation links sent from the admin panel.
+    pub invitation_expires: i64,
+    // The identity providers shown as the buttons on the login page.
+    pub oidc_providers: Vec<AuthOidcProviderConfig>,
+    pub ldap: AuthLdapConfig,
+    pub password: AuthPasswordConfig,
+    pub lockout: AuthLockoutConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuthLockoutConfig {
+    // in seconds, the failed logins of one account are counted during this time.
+    pub window: u64,
+    // The count of the failures after which each next attempt waits, the wait doubles with each failure.
+    pub delay_after: u64,
+    // in seconds
+    pub delay: u64,
+    // in seconds
+    pub max_delay: u64,
+    // The count of the failures after which the account is locked, 0 disables the lockout.
+    pub max_failures: u64,
+    // in seconds
+    pub lockout_time: u64,
+    // The owner gets a letter when someone signs in from a new IP address or browser.
+    pub notify_new_login: bool,
+    // in seconds, the known devices are forgotten after this time without logins.
+    pub devices_ttl: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuthPasswordConfig {
+    pub min_length: usize,
+    pub require_lowercase: bool,
+    pub require_uppercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    // The password can not contain the email or the parts of the full name.
+    pub not_similar: bool,
+    // The count of the previous passwords which can not be used again, 0 disables the check.
+    pub history: usize,
+    // in seconds, the user is sent to the profile to change the password after it, 0 disables the check.
+    pub max_age: i64,
+    // The path to the sorted list of the SHA-1 hashes of the leaked passwords in the "HASH:COUNT" format,
+    // for example, the pwned-passwords-sha1-ordered-by-hash file. Empty disables the check.
+    pub breached_file: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuthLdapConfig {
+    // The directory is asked before the local password when it is enabled.
+    pub enabled: bool,
+    // ldap://host:389 or ldaps://host:636
+    pub url: String,
+    pub starttls: bool,
+    // in seconds
+    pub timeout: u64,
+    // The service account for the search of the users, the anonymous search is used when it is empty.
+    pub bind_dn: String,
+    pub bind_password: String,
+    pub base_dn: String,
+    // The {login} placeholder is replaced by the escaped login, for example: "(&(objectClass=person)(mail={login}))"
+    pub user_filter: String,
+    pub email_attribute: String,
+    pub surname_attribute: String,
+    pub name_attribute: String,
+    pub patronymic_attribute: String,
+    pub groups_attribute: String,
+    // The pairs of a group DN and a role code.
+    pub roles: Vec<(String, String)>,
+    // The users unknown to the panel are created on the first login, otherwise they are rejected.
+    pub create_users: bool,
+    // The period of the sync which disables the users removed from the directory, in seconds.
+    pub sync_period: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuthOidcProviderConfig {
+    // The part of the login url: /login/oidc/{code}
+    pub code: String,
+    pub name: String,
+    // The discovery document is read from {issuer}/.well-known/openid-configuration
+    pub issuer: String,
+    pub client_id: String,
+    // It is empty for the public clients, they rely on the PKCE only.
+    pub client_secret: String,
+    pub scopes: String,
+    // The claims support the dotted paths, for example: "realm_access.roles"
+    pub email_claim: String,
+    pub name_claim: String,
+    pub surname_claim: String,
+    pub groups_claim: String,
+    // The pairs of a group from the groups claim and a role code.
+    pub roles: Vec<(String, String)>,
+    // The users unknown to the panel are created on the first login, otherwise they are rejected.
+    pub create_users: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -88,6 +222,14 @@ pub struct AuthCookieConfig {
     pub cookie_domain: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct HashConfig {
+    // The argon2id cost of the new password hashes, the old hashes are rehashed on login.
+    pub argon2id_opslimit: u32,
+    // in bytes
+    pub argon2id_memlimit: u32,
+}
+
 #[derive(Debug, Clone)]
 pub struct TranslatorConfig {
     pub translates_folder: String,
@@ -145,6 +287,7 @@ impl Config {
                     .unwrap()
                     .trim()
                     .to_string(),
+                previous_keys: parse_list(&env::var("APP_PREVIOUS_KEYS").unwrap_or("".to_string())),
                 url: env::var("APP_URL")
                     .unwrap_or("http://localhost".to_string())
                     .trim()
@@ -242,7 +385,180 @@ impl Config {
                         .unwrap_or("".to_string())
                         .trim()
                         .to_string(),
-                }
+                },
+                email_verification: env::var("AUTH_EMAIL_VERIFICATION")
+                    .unwrap_or("false".to_string())
+                    .trim()
+                    .parse::<bool>().unwrap_or(false),
+                email_verification_expires: env::var("AUTH_EMAIL_VERIFICATION_EXPIRES")
+                    // Default: 24 hours equal 86400 seconds
+                    .unwrap_or("86400".to_string())
+                    .trim()
+                    .parse::<i64>().unwrap_or(86400),
+                invitation_expires: env::var("AUTH_INVITATION_EXPIRES")
+                    // Default: 7 days equal 604800 seconds
+                    .unwrap_or("604800".to_string())
+                    .trim()
+                    .parse::<i64>().unwrap_or(604800),
+                oidc_providers: parse_oidc_providers(
+                    // The codes of the providers separated by commas, each one is configured
+                    // by the AUTH_OIDC_{CODE}_* variables.
+                    &env::var("AUTH_OIDC_PROVIDERS").unwrap_or("".to_string()),
+                ),
+                ldap: AuthLdapConfig {
+                    enabled: env::var("AUTH_LDAP_ENABLED")
+                        .unwrap_or("false".to_string())
+                        .trim()
+                        .parse::<bool>().unwrap_or(false),
+                    url: env::var("AUTH_LDAP_URL")
+                        .unwrap_or("ldap://127.0.0.1:389".to_string())
+                        .trim()
+                        .to_string(),
+                    starttls: env::var("AUTH_LDAP_STARTTLS")
+                        .unwrap_or("false".to_string())
+                        .trim()
+                        .parse::<bool>().unwrap_or(false),
+                    timeout: env::var("AUTH_LDAP_TIMEOUT")
+                        .unwrap_or("5".to_string())
+                        .trim()
+                        .parse::<u64>().unwrap_or(5),
+                    bind_dn: env::var("AUTH_LDAP_BIND_DN")
+                        .unwrap_or("".to_string())
+                        .trim()
+                        .to_string(),
+                    bind_password: env::var("AUTH_LDAP_BIND_PASSWORD")
+                        .unwrap_or("".to_string())
+                        .trim()
+                        .to_string(),
+                    base_dn: env::var("AUTH_LDAP_BASE_DN")
+                        .unwrap_or("".to_string())
+                        .trim()
+                        .to_string(),
+                    user_filter: env::var("AUTH_LDAP_USER_FILTER")
+                        .unwrap_or("(&(objectClass=person)(mail={login}))".to_string())
+                        .trim()
+                        .to_string(),
+                    email_attribute: env::var("AUTH_LDAP_EMAIL_ATTRIBUTE")
+                        .unwrap_or("mail".to_string())
+                        .trim()
+                        .to_string(),
+                    surname_attribute: env::var("AUTH_LDAP_SURNAME_ATTRIBUTE")
+                        .unwrap_or("sn".to_string())
+                        .trim()
+                        .to_string(),
+                    name_attribute: env::var("AUTH_LDAP_NAME_ATTRIBUTE")
+                        .unwrap_or("givenName".to_string())
+                        .trim()
+                        .to_string(),
+                    patronymic_attribute: env::var("AUTH_LDAP_PATRONYMIC_ATTRIBUTE")
+                        .unwrap_or("middleName".to_string())
+                        .trim()
+                        .to_string(),
+                    groups_attribute: env::var("AUTH_LDAP_GROUPS_ATTRIBUTE")
+                        .unwrap_or("memberOf".to_string())
+                        .trim()
+                        .to_string(),
+                    roles: parse_ldap_roles(
+                        // By mask: [group DN]:[role code], separated by semicolons,
+                        // because the DN contains commas.
+                        &env::var("AUTH_LDAP_ROLES").unwrap_or("".to_string()),
+                    ),
+                    create_users: env::var("AUTH_LDAP_CREATE_USERS")
+                        .unwrap_or("true".to_string())
+                        .trim()
+                        .parse::<bool>().unwrap_or(true),
+                    sync_period: env::var("AUTH_LDAP_SYNC_PERIOD")
+                        // Default: 1 hour
+                        .unwrap_or("3600".to_string())
+                        .trim()
+                        .parse::<i64>().unwrap_or(3600),
+                },
+                password: AuthPasswordConfig {
+                    min_length: env::var("AUTH_PASSWORD_MIN_LENGTH")
+                        .unwrap_or("4".to_string())
+                        .trim()
+                        .parse::<usize>().unwrap_or(4),
+                    require_lowercase: env::var("AUTH_PASSWORD_REQUIRE_LOWERCASE")
+                        .unwrap_or("false".to_string())
+                        .trim()
+                        .parse::<bool>().unwrap_or(false),
+                    require_uppercase: env::var("AUTH_PASSWORD_REQUIRE_UPPERCASE")
+                        .unwrap_or("false".to_string())
+                        .trim()
+                        .parse::<bool>().unwrap_or(false),
+                    require_digit: env::var("AUTH_PASSWORD_REQUIRE_DIGIT")
+                        .unwrap_or("false".to_string())
+                        .trim()
+                        .parse::<bool>().unwrap_or(false),
+                    require_symbol: env::var("AUTH_PASSWORD_REQUIRE_SYMBOL")
+                        .unwrap_or("false".to_string())
+                        .trim()
+                        .parse::<bool>().unwrap_or(false),
+                    not_similar: env::var("AUTH_PASSWORD_NOT_SIMILAR")
+                        .unwrap_or("false".to_string())
+                        .trim()
+                        .parse::<bool>().unwrap_or(false),
+                    history: env::var("AUTH_PASSWORD_HISTORY")
+                        .unwrap_or("0".to_string())
+                        .trim()
+                        .parse::<usize>().unwrap_or(0),
+                    max_age: env::var("AUTH_PASSWORD_MAX_AGE")
+                        .unwrap_or("0".to_string())
+                        .trim()
+                        .parse::<i64>().unwrap_or(0),
+                    breached_file: env::var("AUTH_PASSWORD_BREACHED_FILE")
+                        .unwrap_or("".to_string())
+                        .trim()
+                        .to_string(),
+                },
+                lockout: AuthLockoutConfig {
+                    window: env::var("AUTH_LOCKOUT_WINDOW")
+                        // Default: 15 minutes
+                        .unwrap_or("900".to_string())
+                        .trim()
+                        .parse::<u64>().unwrap_or(900),
+                    delay_after: env::var("AUTH_LOCKOUT_DELAY_AFTER")
+                        .unwrap_or("3".to_string())
+                        .trim()
+                        .parse::<u64>().unwrap_or(3),
+                    delay: env::var("AUTH_LOCKOUT_DELAY")
+                        .unwrap_or("1".to_string())
+                        .trim()
+                        .parse::<u64>().unwrap_or(1),
+                    max_delay: env::var("AUTH_LOCKOUT_MAX_DELAY")
+                        .unwrap_or("30".to_string())
+                        .trim()
+                        .parse::<u64>().unwrap_or(30),
+                    max_failures: env::var("AUTH_LOCKOUT_MAX_FAILURES")
+                        .unwrap_or("10".to_string())
+                        .trim()
+                        .parse::<u64>().unwrap_or(10),
+                    lockout_time: env::var("AUTH_LOCKOUT_TIME")
+                        // Default: 15 minutes
+                        .unwrap_or("900".to_string())
+                        .trim()
+                        .parse::<u64>().unwrap_or(900),
+                    notify_new_login: env::var("AUTH_LOCKOUT_NOTIFY_NEW_LOGIN")
+                        .unwrap_or("true".to_string())
+                        .trim()
+                        .parse::<bool>().unwrap_or(true),
+                    devices_ttl: env::var("AUTH_LOCKOUT_DEVICES_TTL")
+                        // Default: 90 days
+                        .unwrap_or("7776000".to_string())
+                        .trim()
+                        .parse::<u64>().unwrap_or(7776000),
+                },
+            },
+            hash: HashConfig {
+                argon2id_opslimit: env::var("HASH_ARGON2ID_OPSLIMIT")
+                    .unwrap_or("2".to_string())
+                    .trim()
+                    .parse::<u32>().unwrap_or(2),
+                argon2id_memlimit: env::var("HASH_ARGON2ID_MEMLIMIT")
+                    // Default: 64 MiB equal 67108864 bytes
+                    .unwrap_or("67108864".to_string())
+                    .trim()
+                    .parse::<u32>().unwrap_or(67108864),
             },
             translator: TranslatorConfig {
                 translates_folder: env::var("TRANSLATOR_TRANSLATES_FOLDER")
@@ -297,14 +613,139 @@ impl Config {
                     .unwrap_or("local".to_string())
                     .trim()
                     .to_string(),
+                upload_max_size: env::var("FILESYSTEM_UPLOAD_MAX_SIZE")
+                    // Default: 100 MB equal 104857600 bytes
+                    .unwrap_or("104857600".to_string())
+                    .trim()
+                    .parse::<u64>().unwrap_or(104857600),
+                image_variants: parse_image_variants(
+                    env::var("FILESYSTEM_IMAGE_VARIANTS")
+                        // By mask: [name]:[fit]:[width]x[height]:[format], separated by commas.
+                        .unwrap_or("thumb:crop:150x150:webp,medium:resize:800x800:original,webp:none:0x0:webp".to_string())
+                        .trim(),
+                ),
                 disks: FilesystemDisksConfig {
                     local: FilesystemLocalDiskConfig {
                         root: storage_local_root,
                         public_root: storage_local_public_root,
                         url_path: storage_local_public_url,
                     },
+                    s3: FilesystemS3DiskConfig {
+                        key: env::var("AWS_ACCESS_KEY_ID")
+                            .unwrap_or("".to_string())
+                            .trim()
+                            .to_string(),
+                        secret: env::var("AWS_SECRET_ACCESS_KEY")
+                            .unwrap_or("".to_string())
+                            .trim()
+                            .to_string(),
+                        region: env::var("AWS_DEFAULT_REGION")
+                            .unwrap_or("us-east-1".to_string())
+                            .trim()
+                            .to_string(),
+                        bucket: env::var("AWS_BUCKET")
+                            .unwrap_or("".to_string())
+                            .trim()
+                            .to_string(),
+                        url: env::var("AWS_URL")
+                            .unwrap_or("".to_string())
+                            .trim()
+                            .to_string(),
+                        endpoint: env::var("AWS_ENDPOINT")
+                            .unwrap_or("".to_string())
+                            .trim()
+                            .to_string(),
+                        use_path_style_endpoint: env::var("AWS_USE_PATH_STYLE_ENDPOINT")
+                            .unwrap_or("false".to_string())
+                            .trim()
+                            .parse::<bool>().unwrap_or(false),
+                        root: env::var("AWS_ROOT")
+                            .unwrap_or("files".to_string())
+                            .trim()
+                            .to_string(),
+                        presigned_expires: env::var("AWS_PRESIGNED_EXPIRES")
+                            // Default: 5 minutes
+                            .unwrap_or("300".to_string())
+                            .trim()
+                            .parse::<u64>().unwrap_or(300),
+                    },
                 }
             }
         }
     }
 }
+
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn parse_oidc_providers(value: &str) -> Vec<AuthOidcProviderConfig> {
+    let mut providers: Vec<AuthOidcProviderConfig> = Vec::new();
+    for code in parse_list(value) {
+        let prefix = format!("AUTH_OIDC_{}_", code.to_uppercase().replace('-', "_"));
+        let var = |name: &str, default: &str| -> String {
+            env::var(format!("{}{}", prefix, name))
+                .unwrap_or(default.to_string())
+                .trim()
+                .to_string()
+        };
+        let issuer = var("ISSUER", "");
+        let client_id = var("CLIENT_ID", "");
+        if issuer.is_empty() || client_id.is_empty() {
+            continue;
+        }
+        providers.push(AuthOidcProviderConfig {
+            name: var("NAME", &code),
+            issuer,
+            client_id,
+            client_secret: var("CLIENT_SECRET", ""),
+            scopes: var("SCOPES", "openid email profile"),
+            email_claim: var("EMAIL_CLAIM", "email"),
+            name_claim: var("NAME_CLAIM", "given_name"),
+            surname_claim: var("SURNAME_CLAIM", "family_name"),
+            groups_claim: var("GROUPS_CLAIM", "groups"),
+            // By mask: [group]:[role code], separated by commas.
+            roles: parse_list(&var("ROLES", ""))
+                .iter()
+                .filter_map(|item| item.rsplit_once(':'))
+                .map(|(group, role)| (group.trim().to_string(), role.trim().to_string()))
+                .filter(|(group, role)| !group.is_empty() && !role.is_empty())
+                .collect(),
+            create_users: var("CREATE_USERS", "true").parse::<bool>().unwrap_or(true),
+            code,
+        });
+    }
+    providers
+}
+
+fn parse_ldap_roles(value: &str) -> Vec<(String, String)> {
+    value
+        .split(';')
+        .filter_map(|item| item.rsplit_once(':'))
+        .map(|(group, role)| (group.trim().to_string(), role.trim().to_string()))
+        .filter(|(group, role)| !group.is_empty() && !role.is_empty())
+        .collect()
+}
+
+fn parse_image_variants(value: &str) -> Vec<FilesystemImageVariantConfig> {
+    let mut variants: Vec<FilesystemImageVariantConfig> = Vec::new();
+    for item in value.split(',') {
+        let parts: Vec<&str> = item.trim().split(':').map(|part| part.trim()).collect();
+        if parts.len() != 4 || parts[0].is_empty() {
+            continue;
+        }
+        let (width, height) = parts[2].split_once('x').unwrap_or(("0", "0"));
+        variants.push(FilesystemImageVariantConfig {
+            name: parts[0].to_string(),
+            fit: parts[1].to_string(),
+            width: width.parse::<u32>().unwrap_or(0),
+            height: height.parse::<u32>().unwrap_or(0),
+            format: parts[3].to_string(),
+        });
+    }
+    variants
+}
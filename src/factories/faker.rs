@@ -0,0 +1,341 @@
+use crate::helpers::DATE_TIME_FORMAT;
+use chrono::{Duration, Utc};
+use image::{ImageFormat, Rgb, RgbImage};
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::{Rng, SeedableRng};
+use std::io::Cursor;
+
+const EN_MALE_NAMES: &[&str] = &[
+    "James", "John", "Robert", "Michael", "William", "David", "Richard", "Joseph", "Thomas",
+    "Charles", "Daniel", "Matthew", "Anthony", "Mark", "Steven", "Andrew", "Paul", "Joshua",
+];
+const EN_FEMALE_NAMES: &[&str] = &[
+    "Mary", "Patricia", "Jennifer", "Linda", "Elizabeth", "Barbara", "Susan", "Jessica", "Sarah",
+    "Karen", "Nancy", "Lisa", "Emily", "Margaret", "Sandra", "Ashley", "Olivia", "Emma",
+];
+const EN_SURNAMES: &[&str] = &[
+    "Smith", "Johnson", "Williams", "Brown", "Jones", "Garcia", "Miller", "Davis", "Wilson",
+    "Anderson", "Taylor", "Thomas", "Moore", "Jackson", "Martin", "Lee", "Thompson", "White",
+    "Harris", "Clark", "Lewis", "Walker", "Young", "Allen",
+];
+const RU_MALE_NAMES: &[&str] = &[
+    "Александр", "Дмитрий", "Максим", "Сергей", "Андрей", "Алексей", "Артём", "Илья", "Кирилл",
+    "Михаил", "Никита", "Иван", "Егор", "Роман", "Павел", "Владимир", "Николай", "Олег",
+];
+const RU_FEMALE_NAMES: &[&str] = &[
+    "Анна", "Мария", "Елена", "Ольга", "Наталья", "Екатерина", "Татьяна", "Ирина", "Светлана",
+    "Юлия", "Дарья", "Ксения", "Полина", "Виктория", "Алина", "Софья", "Марина", "Вера",
+];
+// The female surnames get the "а" ending.
+const RU_SURNAMES: &[&str] = &[
+    "Иванов", "Смирнов", "Кузнецов", "Попов", "Васильев", "Петров", "Соколов", "Михайлов",
+    "Новиков", "Фёдоров", "Морозов", "Волков", "Алексеев", "Лебедев", "Семёнов", "Егоров",
+    "Павлов", "Козлов", "Степанов", "Николаев", "Орлов", "Андреев", "Макаров", "Зайцев",
+];
+// The male and the female forms of the patronymics.
+const RU_PATRONYMICS: &[(&str, &str)] = &[
+    ("Александрович", "Александровна"),
+    ("Дмитриевич", "Дмитриевна"),
+    ("Сергеевич", "Сергеевна"),
+    ("Андреевич", "Андреевна"),
+    ("Алексеевич", "Алексеевна"),
+    ("Иванович", "Ивановна"),
+    ("Михайлович", "Михайловна"),
+    ("Павлович", "Павловна"),
+    ("Владимирович", "Владимировна"),
+    ("Николаевич", "Николаевна"),
+    ("Олегович", "Олеговна"),
+    ("Романович", "Романовна"),
+];
+const EMAIL_DOMAINS: &[&str] = &["example.com", "example.org", "example.net", "mail.example"];
+const WORDS: &[&str] = &[
+    "access", "account", "archive", "audit", "billing", "catalog", "content", "customer",
+    "delivery", "document", "export", "finance", "import", "invoice", "media", "order",
+    "partner", "payment", "product", "project", "report", "review", "sales", "service",
+    "support", "task", "team", "upload", "user", "warehouse",
+];
+const IMAGE_NAMES: &[&str] = &[
+    "IMG", "DSC", "photo", "scan", "screenshot", "avatar", "banner", "cover", "logo", "preview",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gender {
+    Male,
+    Female,
+}
+
+#[derive(Debug, Clone)]
+pub struct FakePerson {
+    pub surname: String,
+    pub name: String,
+    pub patronymic: Option<String>,
+    pub gender: Gender,
+}
+
+/// The generator of the fake data, the fixed seed makes the same data on every run.
+pub struct Faker {
+    rng: StdRng,
+}
+
+impl Default for Faker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Faker {
+    pub fn new() -> Self {
+        Self {
+            rng: StdRng::from_rng(&mut rand::rng()),
+        }
+    }
+
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn number(&mut self, min: u64, max: u64) -> u64 {
+        self.rng.random_range(min..=max)
+    }
+
+    /// True with the chance from 0.0 to 1.0.
+    pub fn chance(&mut self, chance: f64) -> bool {
+        self.rng.random_bool(chance.clamp(0.0, 1.0))
+    }
+
+    pub fn element<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        items.choose(&mut self.rng)
+    }
+
+    /// The random part of the items without repeats, in the order of the items.
+    pub fn elements<T: Clone>(&mut self, items: &[T], min: usize, max: usize) -> Vec<T> {
+        let max = max.min(items.len());
+        let min = min.min(max);
+        let count = self.rng.random_range(min..=max);
+        let mut indexes: Vec<usize> = rand::seq::index::sample(&mut self.rng, items.len(), count).into_vec();
+        indexes.sort();
+        indexes.iter().map(|i| items[*i].clone()).collect()
+    }
+
+    pub fn hex(&mut self, length: usize) -> String {
+        (0..length)
+            .map(|_| char::from_digit(self.rng.random_range(0..16), 16).unwrap_or('0'))
+            .collect()
+    }
+
+    pub fn word(&mut self) -> &'static str {
+        self.element(WORDS).unwrap_or(&"word")
+    }
+
+    /// The sentence from the words, the first letter is capital.
+    pub fn sentence(&mut self, min_words: usize, max_words: usize) -> String {
+        let count = self.rng.random_range(min_words.max(1)..=max_words.max(min_words.max(1)));
+        let words: Vec<&str> = (0..count).map(|_| self.word()).collect();
+        let mut sentence = capitalize(&words.join(" "));
+        sentence.push('.');
+        sentence
+    }
+
+    /// The person with the name of the locale, only the russian names have the patronymic.
+    pub fn person(&mut self, locale: &str) -> FakePerson {
+        let gender = if self.chance(0.5) { Gender::Male } else { Gender::Female };
+        if locale == "ru" {
+            let names = if gender == Gender::Male { RU_MALE_NAMES } else { RU_FEMALE_NAMES };
+            let name = self.element(names).unwrap_or(&"Иван").to_string();
+            let mut surname = self.element(RU_SURNAMES).unwrap_or(&"Иванов").to_string();
+            let (male, female) = self.element(RU_PATRONYMICS).unwrap_or(&("Иванович", "Ивановна"));
+            let patronymic = if gender == Gender::Male {
+                male.to_string()
+            } else {
+                surname.push('а');
+                female.to_string()
+            };
+            FakePerson {
+                surname,
+                name,
+                patronymic: Some(patronymic),
+                gender,
+            }
+        } else {
+            let names = if gender == Gender::Male { EN_MALE_NAMES } else { EN_FEMALE_NAMES };
+            FakePerson {
+                surname: self.element(EN_SURNAMES).unwrap_or(&"Smith").to_string(),
+                name: self.element(names).unwrap_or(&"John").to_string(),
+                patronymic: None,
+                gender,
+            }
+        }
+    }
+
+    /// The email from the name, the number makes the collisions unlikely.
+    pub fn email(&mut self, name: &str, surname: &str) -> String {
+        let domain = self.element(EMAIL_DOMAINS).unwrap_or(&"example.com");
+        format!(
+            "{}.{}.{}@{}",
+            transliterate(name),
+            transliterate(surname),
+            self.number(1000, 99_999_999),
+            domain
+        )
+    }
+
+    /// The datetime in the last days, in the DATE_TIME_FORMAT.
+    pub fn date_time_in_days(&mut self, days: u64) -> String {
+        let seconds = self.number(0, days.max(1) * 86400) as i64;
+        (Utc::now() - Duration::seconds(seconds))
+            .format(DATE_TIME_FORMAT)
+            .to_string()
+    }
+
+    pub fn image_filename(&mut self, extension: &str) -> String {
+        let name = self.element(IMAGE_NAMES).unwrap_or(&"IMG");
+        format!("{}_{}.{}", name, self.number(1000, 9999), extension)
+    }
+
+    /// The image with the gradient and a few rectangles, encoded in the format.
+    pub fn image(&mut self, width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+        let from: [u8; 3] = self.rng.random();
+        let to: [u8; 3] = self.rng.random();
+        let mut image = RgbImage::from_fn(width, height, |x, y| {
+            let t = (x + y) as f32 / (width + height).max(1) as f32;
+            Rgb([
+                mix(from[0], to[0], t),
+                mix(from[1], to[1], t),
+                mix(from[2], to[2], t),
+            ])
+        });
+
+        for _ in 0..self.number(2, 6) {
+            let color: [u8; 3] = self.rng.random();
+            let x0 = self.rng.random_range(0..width);
+            let y0 = self.rng.random_range(0..height);
+            let x1 = (x0 + self.rng.random_range(1..=width / 2 + 1)).min(width);
+            let y1 = (y0 + self.rng.random_range(1..=height / 2 + 1)).min(height);
+            for x in x0..x1 {
+                for y in y0..y1 {
+                    image.put_pixel(x, y, Rgb(color));
+                }
+            }
+        }
+
+        let mut bytes: Vec<u8> = Vec::new();
+        if let Err(e) = image.write_to(&mut Cursor::new(&mut bytes), format) {
+            log::error!("Faker::image - {}", e);
+        }
+        bytes
+    }
+}
+
+fn mix(from: u8, to: u8, t: f32) -> u8 {
+    (from as f32 + (to as f32 - from as f32) * t).round() as u8
+}
+
+fn capitalize(value: &str) -> String {
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// The latin letters for the cyrillic ones, the other letters are lowercased as is.
+pub fn transliterate(value: &str) -> String {
+    let mut result = String::new();
+    for c in value.to_lowercase().chars() {
+        let s = match c {
+            'а' => "a", 'б' => "b", 'в' => "v", 'г' => "g", 'д' => "d", 'е' => "e", 'ё' => "e",
+            'ж' => "zh", 'з' => "z", 'и' => "i", 'й' => "y", 'к' => "k", 'л' => "l", 'м' => "m",
+            'н' => "n", 'о' => "o", 'п' => "p", 'р' => "r", 'с' => "s", 'т' => "t", 'у' => "u",
+            'ф' => "f", 'х' => "kh", 'ц' => "ts", 'ч' => "ch", 'ш' => "sh", 'щ' => "shch",
+            'ъ' => "", 'ы' => "y", 'ь' => "", 'э' => "e", 'ю' => "yu", 'я' => "ya",
+            c if c.is_ascii_alphanumeric() => {
+                result.push(c);
+                continue;
+            }
+            _ => "",
+        };
+        result.push_str(s);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transliterate() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact factories::faker::tests::test_transliterate
+        assert_eq!(transliterate("Щукина Юлия"), "shchukinayuliya");
+        assert_eq!(transliterate("Фёдоров"), "fedorov");
+        assert_eq!(transliterate("O'Brien"), "obrien");
+        assert_eq!(transliterate(""), "");
+    }
+
+    #[test]
+    fn test_from_seed() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact factories::faker::tests::test_from_seed
+        let mut faker1 = Faker::from_seed(42);
+        let mut faker2 = Faker::from_seed(42);
+        for _ in 0..20 {
+            let person1 = faker1.person("ru");
+            let person2 = faker2.person("ru");
+            assert_eq!(person1.surname, person2.surname);
+            assert_eq!(person1.name, person2.name);
+            assert_eq!(
+                faker1.email(&person1.name, &person1.surname),
+                faker2.email(&person2.name, &person2.surname)
+            );
+        }
+    }
+
+    #[test]
+    fn test_person() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact factories::faker::tests::test_person
+        let mut faker = Faker::from_seed(7);
+        for _ in 0..50 {
+            let person = faker.person("ru");
+            let patronymic = person.patronymic.unwrap();
+            if person.gender == Gender::Female {
+                assert!(person.surname.ends_with('а'));
+                assert!(patronymic.ends_with("на"));
+            } else {
+                assert!(patronymic.ends_with("ич"));
+            }
+
+            let person = faker.person("en");
+            assert!(person.patronymic.is_none());
+            let email = faker.email(&person.name, &person.surname);
+            assert!(email.is_ascii() && email.contains('@'));
+        }
+    }
+
+    #[test]
+    fn test_elements() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact factories::faker::tests::test_elements
+        let mut faker = Faker::from_seed(1);
+        let items: Vec<u64> = (1..=10).collect();
+        for _ in 0..50 {
+            let elements = faker.elements(&items, 2, 5);
+            assert!(elements.len() >= 2 && elements.len() <= 5);
+            assert!(elements.windows(2).all(|w| w[0] < w[1]));
+        }
+        assert_eq!(faker.elements(&items, 20, 30).len(), 10);
+        assert!(faker.elements::<u64>(&[], 1, 3).is_empty());
+    }
+
+    #[test]
+    fn test_image() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact factories::faker::tests::test_image
+        let mut faker = Faker::from_seed(3);
+        let bytes = faker.image(64, 48, ImageFormat::Png);
+        let image = image::load_from_memory_with_format(&bytes, ImageFormat::Png).unwrap();
+        assert_eq!((image.width(), image.height()), (64, 48));
+
+        let bytes = faker.image(32, 32, ImageFormat::Jpeg);
+        assert!(image::load_from_memory_with_format(&bytes, ImageFormat::Jpeg).is_ok());
+    }
+}
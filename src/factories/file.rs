@@ -0,0 +1,60 @@
+use crate::factories::Faker;
+use crate::helpers::now_date_time_str;
+use crate::File;
+use image::ImageFormat;
+use sha2::{Digest, Sha256};
+
+/// Makes the image files with their content, the records are not saved.
+pub struct FileFactory {
+    faker: Faker,
+    disk: String,
+}
+
+impl FileFactory {
+    pub fn new(faker: Faker, disk: &str) -> Self {
+        Self {
+            faker,
+            disk: disk.to_string(),
+        }
+    }
+
+    /// The file record and the image it describes, the filename is made as by the upload.
+    /// The path is the filename, the caller makes the path on the disk.
+    pub fn make(&mut self) -> (File, Vec<u8>) {
+        let faker = &mut self.faker;
+        let (format, mime, extension) = if faker.chance(0.5) {
+            (ImageFormat::Png, mime::IMAGE_PNG, "png")
+        } else {
+            (ImageFormat::Jpeg, mime::IMAGE_JPEG, "jpg")
+        };
+        let width = faker.number(160, 800) as u32;
+        let height = faker.number(120, 600) as u32;
+        let bytes = faker.image(width, height, format);
+
+        let hash = hex::encode(Sha256::digest(&bytes));
+        let size = bytes.len() as u64;
+        // [hash]-[size].[extensions]
+        let filename = format!("{}-{}.{}", hash, size, extension);
+        let now = now_date_time_str();
+
+        let file = File {
+            id: 0,
+            filename: filename.to_owned(),
+            path: filename,
+            mime: Some(mime.to_string()),
+            hash: Some(hash),
+            size: Some(size),
+            creator_user_id: None,
+            created_at: Some(now.to_owned()),
+            updated_at: Some(now),
+            delete_at: None,
+            deleted_at: None,
+            is_delete: false,
+            is_deleted: false,
+            disk: self.disk.to_owned(),
+            user_files: None,
+            variants: None,
+        };
+        (file, bytes)
+    }
+}
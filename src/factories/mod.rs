@@ -0,0 +1,11 @@
+mod faker;
+mod file;
+mod role;
+mod user;
+mod user_file;
+
+pub use self::faker::*;
+pub use self::file::*;
+pub use self::role::*;
+pub use self::user::*;
+pub use self::user_file::*;
@@ -0,0 +1,52 @@
+use crate::factories::Faker;
+use crate::{Permission, Role};
+use strum::VariantNames;
+
+const ROLE_NAMES: &[&str] = &[
+    "Editor", "Manager", "Moderator", "Support", "Accountant", "Analyst", "Auditor", "Designer",
+    "Developer", "Marketer", "Operator", "Reviewer",
+];
+
+/// Makes the roles with the random permissions, the records are not saved.
+pub struct RoleFactory {
+    faker: Faker,
+}
+
+impl RoleFactory {
+    pub fn new(faker: Faker) -> Self {
+        Self { faker }
+    }
+
+    pub fn make(&mut self) -> Role {
+        let faker = &mut self.faker;
+        // The code and the name are unique in the table.
+        let suffix = faker.hex(6);
+        let title = faker.element(ROLE_NAMES).unwrap_or(&"Editor");
+        let permissions: Vec<String> = faker
+            .elements(Permission::VARIANTS, 1, Permission::VARIANTS.len())
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        let upload_max_size = if faker.chance(0.3) {
+            Some(faker.number(1, 100) * 1048576)
+        } else {
+            None
+        };
+
+        Role {
+            id: 0,
+            code: format!("{}_{}", title.to_lowercase(), suffix),
+            name: format!("{} {}", title, suffix),
+            description: Some(faker.sentence(3, 8)),
+            permissions: Some(permissions),
+            requires_two_factor: faker.chance(0.1),
+            upload_max_size,
+            parent_id: None,
+            effective_permissions: None,
+        }
+    }
+
+    pub fn make_many(&mut self, count: usize) -> Vec<Role> {
+        (0..count).map(|_| self.make()).collect()
+    }
+}
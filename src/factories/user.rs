@@ -0,0 +1,58 @@
+use crate::factories::Faker;
+use crate::User;
+
+/// Makes the users with the names of their locales, the records are not saved.
+pub struct UserFactory {
+    faker: Faker,
+    locales: Vec<String>,
+    roles_ids: Vec<u64>,
+}
+
+impl UserFactory {
+    pub fn new(faker: Faker, locales: Vec<String>, roles_ids: Vec<u64>) -> Self {
+        Self {
+            faker,
+            locales,
+            roles_ids,
+        }
+    }
+
+    pub fn make(&mut self) -> User {
+        let faker = &mut self.faker;
+        let locale = faker.element(&self.locales).cloned().unwrap_or("en".to_string());
+        let person = faker.person(&locale);
+
+        let roles_ids = faker.elements(&self.roles_ids, 0, 2);
+        let email_verified_at = if faker.chance(0.9) {
+            Some(faker.date_time_in_days(365))
+        } else {
+            None
+        };
+        let disabled_at = if faker.chance(0.05) {
+            Some(faker.date_time_in_days(30))
+        } else {
+            None
+        };
+
+        User {
+            id: 0,
+            email: faker.email(&person.name, &person.surname),
+            locale: Some(locale),
+            surname: Some(person.surname),
+            name: Some(person.name),
+            patronymic: person.patronymic,
+            is_super_admin: false,
+            roles_ids: if roles_ids.is_empty() { None } else { Some(roles_ids) },
+            avatar_id: None,
+            email_verified_at,
+            ldap_dn: None,
+            disabled_at,
+            invited_at: None,
+            password_changed_at: None,
+        }
+    }
+
+    pub fn make_many(&mut self, count: usize) -> Vec<User> {
+        (0..count).map(|_| self.make()).collect()
+    }
+}
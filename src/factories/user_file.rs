@@ -0,0 +1,36 @@
+use crate::factories::Faker;
+use crate::helpers::now_date_time_str;
+use crate::{File, UserFile};
+
+/// Makes the link of the user to the file as it was uploaded by the user, the record is not saved.
+pub struct UserFileFactory {
+    faker: Faker,
+}
+
+impl UserFileFactory {
+    pub fn new(faker: Faker) -> Self {
+        Self { faker }
+    }
+
+    pub fn make(&mut self, user_id: u64, file: &File) -> UserFile {
+        let faker = &mut self.faker;
+        let extension = file.filename.rsplit('.').next().unwrap_or("png");
+        let now = now_date_time_str();
+
+        UserFile {
+            id: 0,
+            user_id,
+            file_id: file.id,
+            filename: None,
+            path: None,
+            upload_filename: Some(faker.image_filename(extension)),
+            mime: file.mime.to_owned(),
+            created_at: Some(now.to_owned()),
+            updated_at: Some(now),
+            deleted_at: None,
+            is_deleted: false,
+            is_public: faker.chance(0.3),
+            disk: file.disk.to_owned(),
+        }
+    }
+}
@@ -3,13 +3,16 @@ extern crate core;
 extern crate test;
 
 pub mod app;
+pub mod commands;
 pub mod config;
 pub mod connections;
 pub mod errors;
+pub mod factories;
 pub mod helpers;
 pub mod libs;
 pub mod migrations;
 pub mod routes;
+pub mod seeders;
 pub mod services;
 
 use crate::app::controllers::web::errors::default_error_handler;
@@ -59,10 +62,16 @@ async fn main() -> std::io::Result<()> {
             .app_data(all_services.template_service.clone())
             .app_data(all_services.hash_service.clone())
             .app_data(all_services.auth_service.clone())
+            .app_data(all_services.ldap_service.clone())
             .app_data(all_services.web_auth_service.clone())
             .app_data(all_services.locale_service.clone())
             .app_data(all_services.app_service.clone())
             .app_data(all_services.mail_service.clone())
+            .app_data(all_services.job_service.clone())
+            .app_data(all_services.email_verification_service.clone())
+            .app_data(all_services.invitation_service.clone())
+            .app_data(all_services.login_security_service.clone())
+            .app_data(all_services.oidc_service.clone())
             .app_data(all_services.rand_service.clone())
             .app_data(all_services.user_service.clone())
             .app_data(all_services.crypt_service.clone())
@@ -70,6 +79,13 @@ async fn main() -> std::io::Result<()> {
             .app_data(all_services.role_service.clone())
             .app_data(all_services.file_service.clone())
             .app_data(all_services.user_file_service.clone())
+            .app_data(all_services.file_variant_service.clone())
+            .app_data(all_services.resumable_upload_service.clone())
+            .app_data(all_services.export_service.clone())
+            .app_data(all_services.user_import_service.clone())
+            .app_data(all_services.api_token_service.clone())
+            .app_data(all_services.audit_log_service.clone())
+            .app_data(all_services.user_two_factor_service.clone())
             .wrap(Logger::default())
             .configure(routes::register)
             .wrap(ErrorHandlers::new().default_handler(default_error_handler))
@@ -0,0 +1,26 @@
+use crate::Config;
+
+pub fn up(_: &Config) -> Vec<String> {
+    let mut queries: Vec<String> = Vec::new();
+    let query = "CREATE TABLE `api_tokens` (
+   `id` BIGINT UNSIGNED PRIMARY KEY AUTO_INCREMENT,
+   `user_id` BIGINT UNSIGNED NOT NULL COMMENT 'The token owner.',
+   `name` VARCHAR(255) NOT NULL COMMENT 'The token name.',
+   `token_hash` VARCHAR(64) CHARACTER SET ascii COLLATE ascii_bin NOT NULL UNIQUE COMMENT 'Hash of the sha256 token.',
+   `scopes` JSON NULL DEFAULT NULL COMMENT 'Permissions available to the token.',
+   `expires_at` DATETIME NULL DEFAULT NULL COMMENT 'After this time, the token is no longer valid.',
+   `last_used_at` DATETIME NULL DEFAULT NULL COMMENT 'The datetime of the last token usage.',
+   `created_at` DATETIME NULL DEFAULT NULL COMMENT 'The datetime of the token creation.'
+) COMMENT 'Personal access tokens of users.';";
+    queries.push(query.to_string());
+
+    let query = "ALTER TABLE `api_tokens` ADD INDEX `user_idx` (`user_id`);";
+    queries.push(query.to_string());
+    queries
+}
+
+pub fn down(_: &Config) -> Vec<String> {
+    vec![
+        "DROP TABLE `api_tokens`;".to_string(),
+    ]
+}
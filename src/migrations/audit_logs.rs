@@ -0,0 +1,34 @@
+use crate::Config;
+
+pub fn up(_: &Config) -> Vec<String> {
+    let mut queries: Vec<String> = Vec::new();
+    let query = "CREATE TABLE `audit_logs` (
+   `id` BIGINT UNSIGNED PRIMARY KEY AUTO_INCREMENT,
+   `actor_id` BIGINT UNSIGNED NULL DEFAULT NULL COMMENT 'The user who made the change.',
+   `action` VARCHAR(32) CHARACTER SET ascii COLLATE ascii_bin NOT NULL COMMENT 'create, update, delete or restore.',
+   `entity_type` VARCHAR(32) CHARACTER SET ascii COLLATE ascii_bin NOT NULL COMMENT 'user, role, file or user_file.',
+   `entity_id` BIGINT UNSIGNED NOT NULL COMMENT 'The ID of the changed entity.',
+   `old_values` JSON NULL DEFAULT NULL COMMENT 'Changed fields before the change.',
+   `new_values` JSON NULL DEFAULT NULL COMMENT 'Changed fields after the change.',
+   `ip` VARCHAR(45) NULL DEFAULT NULL COMMENT 'The IP address of the actor.',
+   `user_agent` VARCHAR(512) NULL DEFAULT NULL COMMENT 'The User-Agent header of the actor.',
+   `created_at` DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT 'The datetime of the change.'
+) COMMENT 'Trail of admin mutations.';";
+    queries.push(query.to_string());
+
+    let query = "ALTER TABLE `audit_logs` ADD INDEX `actor_idx` (`actor_id`);";
+    queries.push(query.to_string());
+
+    let query = "ALTER TABLE `audit_logs` ADD INDEX `entity_idx` (`entity_type`, `entity_id`);";
+    queries.push(query.to_string());
+
+    let query = "ALTER TABLE `audit_logs` ADD INDEX `created_at_idx` (`created_at`);";
+    queries.push(query.to_string());
+    queries
+}
+
+pub fn down(_: &Config) -> Vec<String> {
+    vec![
+        "DROP TABLE `audit_logs`;".to_string(),
+    ]
+}
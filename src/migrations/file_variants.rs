@@ -0,0 +1,30 @@
+use crate::Config;
+
+pub fn up(_: &Config) -> Vec<String> {
+    let mut queries: Vec<String> = Vec::new();
+    let query = "CREATE TABLE `file_variants` (
+   `id` BIGINT UNSIGNED PRIMARY KEY AUTO_INCREMENT,
+   `file_id` BIGINT UNSIGNED NOT NULL COMMENT 'Relation to the files table.',
+   `name` VARCHAR(255) CHARACTER SET ascii COLLATE ascii_bin NOT NULL COMMENT 'The variant name from the config.',
+   `filename` VARCHAR(2048) CHARACTER SET ascii COLLATE ascii_bin NOT NULL COMMENT 'The file name by mask: [hash]-[size]-[name].[extensions].',
+   `path` VARCHAR(2048) CHARACTER SET ascii COLLATE ascii_bin NOT NULL COMMENT 'The path where the variant is saved on disk.',
+   `mime` VARCHAR(255) CHARACTER SET ascii COLLATE ascii_bin NULL DEFAULT NULL COMMENT 'The variant type.',
+   `size` BIGINT UNSIGNED NULL DEFAULT NULL COMMENT 'The variant size in bytes.',
+   `width` INT UNSIGNED NULL DEFAULT NULL COMMENT 'The image width in pixels.',
+   `height` INT UNSIGNED NULL DEFAULT NULL COMMENT 'The image height in pixels.',
+   `disk` VARCHAR(255) CHARACTER SET ascii COLLATE ascii_bin NOT NULL COMMENT 'The disk where the variant is stored.',
+   `created_at` DATETIME NULL DEFAULT NULL COMMENT 'The datetime of the variant creation.',
+   `updated_at` DATETIME NULL DEFAULT NULL COMMENT 'The datetime of the last variant update.'
+) COMMENT 'Resized and converted versions of the image files.';";
+    queries.push(query.to_string());
+
+    let query = "ALTER TABLE `file_variants` ADD UNIQUE `file_name_udx` (`file_id`, `name`);";
+    queries.push(query.to_string());
+    queries
+}
+
+pub fn down(_: &Config) -> Vec<String> {
+    vec![
+        "DROP TABLE `file_variants`;".to_string(),
+    ]
+}
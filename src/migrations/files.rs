@@ -1,7 +1,6 @@
-use crate::{Config, MysqlPooledConnection};
-use mysql::prelude::Queryable;
+use crate::Config;
 
-fn create_users_files_table(connection: &mut MysqlPooledConnection) {
+fn create_users_files_table(queries: &mut Vec<String>) {
     let query = "CREATE TABLE `users_files` (
    `id` BIGINT UNSIGNED PRIMARY KEY AUTO_INCREMENT,
    `file_id` BIGINT UNSIGNED NOT NULL COMMENT 'Relation to the files table.',
@@ -17,28 +16,28 @@ fn create_users_files_table(connection: &mut MysqlPooledConnection) {
    `is_public` BOOLEAN NOT NULL DEFAULT FALSE COMMENT 'Label: public file or not.',
    `disk` VARCHAR(255) CHARACTER SET ascii COLLATE ascii_bin NOT NULL COMMENT 'The disk where the file is stored.'
 ) COMMENT 'Files belonging to users.';";
-    connection.query_drop(query).unwrap();
+    queries.push(query.to_string());
 
     let query = "ALTER TABLE `users_files` ADD UNIQUE `file_user_udx` (`user_id`, `file_id`);";
-    connection.query_drop(query).unwrap();
+    queries.push(query.to_string());
 
     let query = "ALTER TABLE `users_files` ADD INDEX `user_idx` (`user_id`);";
-    connection.query_drop(query).unwrap();
+    queries.push(query.to_string());
 
     let query = "ALTER TABLE `users_files` ADD INDEX `file_idx` (`file_id`);";
-    connection.query_drop(query).unwrap();
+    queries.push(query.to_string());
 
     let query = "ALTER TABLE `users_files` ADD INDEX `path_idx` (`path`);";
-    connection.query_drop(query).unwrap();
+    queries.push(query.to_string());
 
     let query = "ALTER TABLE `users_files` ADD INDEX `filename_idx` (`filename`);";
-    connection.query_drop(query).unwrap();
+    queries.push(query.to_string());
 
     let query = "ALTER TABLE `users_files` ADD INDEX `upload_filename_idx` (`upload_filename`);";
-    connection.query_drop(query).unwrap();
+    queries.push(query.to_string());
 }
 
-fn create_files_table(connection: &mut MysqlPooledConnection) {
+fn create_files_table(queries: &mut Vec<String>) {
     let query = "CREATE TABLE `files` (
    `id` BIGINT UNSIGNED PRIMARY KEY AUTO_INCREMENT,
    `filename` VARCHAR(2048) CHARACTER SET ascii COLLATE ascii_bin NOT NULL COMMENT 'The file name is made up of the hash, size, and extensions obtained when uploading the file, by mask: [hash]-[size].[extensions].',
@@ -55,27 +54,31 @@ fn create_files_table(connection: &mut MysqlPooledConnection) {
    `is_deleted` BOOLEAN NOT NULL DEFAULT FALSE COMMENT 'Label: whether the file has been deleted.',
    `disk` VARCHAR(255) CHARACTER SET ascii COLLATE ascii_bin NOT NULL COMMENT 'The disk where the file is stored.'
 ) COMMENT 'The file table.';";
-    connection.query_drop(query).unwrap();
+    queries.push(query.to_string());
 
     let query = "ALTER TABLE `files` ADD UNIQUE `disk_path_udx` (`disk`, `path`);";
-    connection.query_drop(query).unwrap();
+    queries.push(query.to_string());
 
     let query = "ALTER TABLE `files` ADD INDEX `creator_user_idx` (`creator_user_id`);";
-    connection.query_drop(query).unwrap();
+    queries.push(query.to_string());
 
     let query = "ALTER TABLE `files` ADD INDEX `path_idx` (`path`);";
-    connection.query_drop(query).unwrap();
+    queries.push(query.to_string());
 
     let query = "ALTER TABLE `files` ADD INDEX `filename_idx` (`filename`);";
-    connection.query_drop(query).unwrap();
+    queries.push(query.to_string());
 }
 
-pub fn up(_: &Config, connection: &mut MysqlPooledConnection) {
-    create_users_files_table(connection);
-    create_files_table(connection);
+pub fn up(_: &Config) -> Vec<String> {
+    let mut queries: Vec<String> = Vec::new();
+    create_users_files_table(&mut queries);
+    create_files_table(&mut queries);
+    queries
 }
 
-pub fn down(_: &Config, connection: &mut MysqlPooledConnection) {
-    connection.query_drop("DROP TABLE `users_files`;").unwrap();
-    connection.query_drop("DROP TABLE `files`;").unwrap();
+pub fn down(_: &Config) -> Vec<String> {
+    vec![
+        "DROP TABLE `users_files`;".to_string(),
+        "DROP TABLE `files`;".to_string(),
+    ]
 }
@@ -1,15 +1,56 @@
-use crate::{Config, MysqlPooledConnection};
+use crate::Config;
+use openssl::sha::Sha256;
 
+pub mod api_tokens;
+pub mod audit_logs;
 pub mod files;
 pub mod users;
 pub mod roles;
+pub mod user_two_factors;
+pub mod role_upload_limits;
+pub mod file_variants;
+pub mod user_email_verifications;
+pub mod user_ldap;
+pub mod user_invitations;
+pub mod user_passwords;
+pub mod role_parents;
+pub mod runner;
 
-pub fn get_migrations() -> Vec<(String, [fn(&Config, &mut MysqlPooledConnection); 2])> {
-    let mut items: Vec<(String, [fn(&Config, &mut MysqlPooledConnection); 2])> = Vec::new();
+/// Returns the SQL statements of the migration, they are executed one by one.
+pub type MigrationFn = fn(&Config) -> Vec<String>;
+
+pub fn get_migrations() -> Vec<(String, [MigrationFn; 2])> {
+    let mut items: Vec<(String, [MigrationFn; 2])> = Vec::new();
 
     items.push(("users".to_string(), [users::up, users::down]));
     items.push(("roles".to_string(), [roles::up, roles::down]));
     items.push(("files".to_string(), [files::up, files::down]));
+    items.push(("api_tokens".to_string(), [api_tokens::up, api_tokens::down]));
+    items.push(("audit_logs".to_string(), [audit_logs::up, audit_logs::down]));
+    items.push(("user_two_factors".to_string(), [user_two_factors::up, user_two_factors::down]));
+    items.push(("role_upload_limits".to_string(), [role_upload_limits::up, role_upload_limits::down]));
+    items.push(("file_variants".to_string(), [file_variants::up, file_variants::down]));
+    items.push(("user_email_verifications".to_string(), [user_email_verifications::up, user_email_verifications::down]));
+    items.push(("user_ldap".to_string(), [user_ldap::up, user_ldap::down]));
+    items.push(("user_invitations".to_string(), [user_invitations::up, user_invitations::down]));
+    items.push(("user_passwords".to_string(), [user_passwords::up, user_passwords::down]));
+    items.push(("role_parents".to_string(), [role_parents::up, role_parents::down]));
 
     items
-}
\ No newline at end of file
+}
+
+/// The SHA-256 of the up and down statements, it changes when the applied migration is edited.
+pub fn checksum(config: &Config, [up, down]: &[MigrationFn; 2]) -> String {
+    let mut hasher = Sha256::new();
+    for query in up(config) {
+        hasher.update(query.as_bytes());
+        hasher.update(b"\0");
+    }
+    hasher.update(b"\0");
+    for query in down(config) {
+        hasher.update(query.as_bytes());
+        hasher.update(b"\0");
+    }
+    hex::encode(hasher.finish())
+}
+
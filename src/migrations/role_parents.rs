@@ -0,0 +1,14 @@
+use crate::Config;
+
+pub fn up(_: &Config) -> Vec<String> {
+    let mut queries: Vec<String> = Vec::new();
+    let query = "ALTER TABLE `roles` ADD `parent_id` INT UNSIGNED NULL DEFAULT NULL COMMENT 'The role the permissions are inherited from.';";
+    queries.push(query.to_string());
+    queries
+}
+
+pub fn down(_: &Config) -> Vec<String> {
+    vec![
+        "ALTER TABLE `roles` DROP COLUMN `parent_id`;".to_string(),
+    ]
+}
@@ -0,0 +1,14 @@
+use crate::Config;
+
+pub fn up(_: &Config) -> Vec<String> {
+    let mut queries: Vec<String> = Vec::new();
+    let query = "ALTER TABLE `roles` ADD `upload_max_size` BIGINT UNSIGNED NULL DEFAULT NULL COMMENT 'The upload limit of users with the role in bytes.';";
+    queries.push(query.to_string());
+    queries
+}
+
+pub fn down(_: &Config) -> Vec<String> {
+    vec![
+        "ALTER TABLE `roles` DROP COLUMN `upload_max_size`;".to_string(),
+    ]
+}
@@ -1,7 +1,7 @@
-use mysql::prelude::Queryable;
-use crate::{Config, MysqlPooledConnection};
+use crate::Config;
 
-pub fn up(_: &Config, connection: &mut MysqlPooledConnection) {
+pub fn up(_: &Config) -> Vec<String> {
+    let mut queries: Vec<String> = Vec::new();
     let query = "CREATE TABLE `roles` (
    `id` INT UNSIGNED PRIMARY KEY AUTO_INCREMENT,
    `code` VARCHAR(255) NOT NULL UNIQUE,
@@ -10,7 +10,7 @@ pub fn up(_: &Config, connection: &mut MysqlPooledConnection) {
    `permissions` JSON NULL DEFAULT NULL
 );
 ";
-    connection.query_drop(query).unwrap();
+    queries.push(query.to_string());
 
     let query = "
 INSERT INTO `roles` (`id`, `code`, `name`, `permissions`) VALUES (1, 'admin', 'Admin', '[\"users_show\",
@@ -23,10 +23,13 @@ INSERT INTO `roles` (`id`, `code`, `name`, `permissions`) VALUES (1, 'admin', 'A
 \"roles_update\",
 \"roles_delete\"]');
 ";
-    connection.query_drop(query).unwrap();
+    queries.push(query.to_string());
+    queries
 }
 
-pub fn down(_: &Config, connection: &mut MysqlPooledConnection){
+pub fn down(_: &Config) -> Vec<String> {
+    let mut queries: Vec<String> = Vec::new();
     let query = "DROP TABLE `roles`;";
-    connection.query_drop(query).unwrap();
+    queries.push(query.to_string());
+    queries
 }
@@ -0,0 +1,477 @@
+use crate::migrations::{self, MigrationFn};
+use crate::{mysql_connection, Config, MysqlPool, MysqlPooledConnection};
+use mysql::prelude::Queryable;
+use mysql::{params, Row};
+use std::collections::HashMap;
+use std::fmt;
+
+static MIGRATIONS_TABLE: &str = "__migrations";
+pub static COMMANDS_MESSAGE: &str = "Allowed commands: \"up\", \"down\", \"status\", \"fresh\". \
+Options: \"--dry-run\" prints the SQL without executing it, \"down --steps N\" rolls back N migrations, \
+\"down --batch\" rolls back the last batch.";
+
+pub fn run(options: &Options) -> Result<(), MigrateError> {
+    let config = Config::new();
+    let mysql_pool: MysqlPool = mysql_connection::get_connection_pool(&config.db.mysql)
+        .map_err(|e| MigrateError::Connection(e.to_string()))?;
+    let mut mysql_connection: MysqlPooledConnection = mysql_pool
+        .get()
+        .map_err(|e| MigrateError::Connection(e.to_string()))?;
+
+    let mut migrator = Migrator {
+        config: &config,
+        connection: &mut mysql_connection,
+        dry_run: options.dry_run,
+        is_dropped: false,
+    };
+
+    match options.command {
+        Command::Up => migrator.up()?,
+        Command::Down => {
+            if options.batch {
+                migrator.down_batch()?
+            } else {
+                migrator.down(options.steps)?
+            }
+        }
+        Command::Status => migrator.status()?,
+        Command::Fresh => {
+            migrator.drop_all_tables()?;
+            migrator.up()?;
+        }
+    }
+
+    if options.dry_run {
+        log::info!("Dry run, nothing was executed.");
+    } else if options.command != Command::Status {
+        log::info!("The migration was successful!");
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Up,
+    Down,
+    Status,
+    Fresh,
+}
+
+#[derive(Debug)]
+pub struct Options {
+    pub command: Command,
+    pub dry_run: bool,
+    pub steps: u64,
+    pub batch: bool,
+}
+
+impl Options {
+    pub fn parse(args: &[String]) -> Result<Self, MigrateError> {
+        let command = match args.first().map(|s| s.as_str()) {
+            Some("up") => Command::Up,
+            Some("down") => Command::Down,
+            Some("status") => Command::Status,
+            Some("fresh") => Command::Fresh,
+            Some(command) => {
+                return Err(MigrateError::Arguments(format!("Wrong command \"{}\".", command)))
+            }
+            None => return Err(MigrateError::Arguments("The command is missing.".to_string())),
+        };
+
+        let mut options = Self {
+            command,
+            dry_run: false,
+            steps: 1,
+            batch: false,
+        };
+
+        let mut args = args.iter().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--dry-run" if command != Command::Status => options.dry_run = true,
+                "--batch" if command == Command::Down => options.batch = true,
+                "--steps" if command == Command::Down => {
+                    options.steps = args
+                        .next()
+                        .and_then(|s| s.parse::<u64>().ok())
+                        .filter(|steps| *steps > 0)
+                        .ok_or(MigrateError::Arguments(
+                            "\"--steps\" needs a positive number.".to_string(),
+                        ))?;
+                }
+                _ => {
+                    return Err(MigrateError::Arguments(format!("Wrong option \"{}\".", arg)));
+                }
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+struct Migrator<'a> {
+    config: &'a Config,
+    connection: &'a mut MysqlPooledConnection,
+    dry_run: bool,
+    // The dry run of "fresh" does not drop the tables, the following "up" sees them as dropped.
+    is_dropped: bool,
+}
+
+impl Migrator<'_> {
+    fn up(&mut self) -> Result<(), MigrateError> {
+        self.create_migrations_table()?;
+        let migrations = self.load_migrations()?;
+        let items = migrations::get_migrations();
+        let index: HashMap<&str, &[MigrationFn; 2]> =
+            items.iter().map(|(name, item)| (name.as_str(), item)).collect();
+
+        // The applied migrations are not executed again, so their edits would be lost silently.
+        let mut changed: Vec<&str> = Vec::new();
+        for migration in &migrations {
+            if let Some(item) = index.get(migration.name.as_str()) {
+                let checksum = migrations::checksum(self.config, item);
+                match &migration.checksum {
+                    Some(checksum_) if checksum_.ne(&checksum) => changed.push(&migration.name),
+                    None if !self.dry_run => self.update_checksum(&migration.name, &checksum)?,
+                    _ => {}
+                }
+            }
+        }
+        if !changed.is_empty() {
+            return Err(MigrateError::Changed(changed.join(", ")));
+        }
+
+        let batch = migrations.iter().map(|m| m.batch).max().unwrap_or(0) + 1;
+        for (name, item) in &items {
+            if migrations.iter().any(|m| m.name.eq(name)) {
+                continue;
+            }
+            let [up, _] = item;
+            log::info!("Up migrating - {}", name);
+            self.execute(name, "up", up(self.config))?;
+            if !self.dry_run {
+                self.insert_migration(name, batch, &migrations::checksum(self.config, item))?;
+            }
+            log::info!("Up migrated - {}", name);
+        }
+
+        Ok(())
+    }
+
+    /// Rolls back the last migrations one by one, the newest first.
+    fn down(&mut self, steps: u64) -> Result<(), MigrateError> {
+        self.create_migrations_table()?;
+        let migrations = self.load_migrations()?;
+        let migrations: Vec<&Migration> = migrations.iter().rev().take(steps as usize).collect();
+        self.rollback(&migrations)
+    }
+
+    /// Rolls back all migrations of the last "up" run.
+    fn down_batch(&mut self) -> Result<(), MigrateError> {
+        self.create_migrations_table()?;
+        let migrations = self.load_migrations()?;
+        let batch = migrations.iter().map(|m| m.batch).max().unwrap_or(0);
+        let migrations: Vec<&Migration> = migrations
+            .iter()
+            .rev()
+            .filter(|m| m.batch == batch)
+            .collect();
+        self.rollback(&migrations)
+    }
+
+    fn rollback(&mut self, migrations: &[&Migration]) -> Result<(), MigrateError> {
+        let items = migrations::get_migrations();
+
+        // All migrations are checked before the first one is rolled back.
+        let mut downs: Vec<(&str, MigrationFn)> = Vec::new();
+        for migration in migrations {
+            let name = migration.name.as_str();
+            let item = items
+                .iter()
+                .find(|(name_, _)| name_.eq(name))
+                .map(|(_, item)| item)
+                .ok_or(MigrateError::NotFound(name.to_string()))?;
+            if let Some(checksum) = &migration.checksum {
+                if checksum.ne(&migrations::checksum(self.config, item)) {
+                    log::warn!("The migration was changed after it was applied - {}", name);
+                }
+            }
+            downs.push((name, item[1]));
+        }
+
+        for (name, down) in downs {
+            log::info!("Down migrating - {}", name);
+            self.execute(name, "down", down(self.config))?;
+            if !self.dry_run {
+                self.delete_migration(name)?;
+            }
+            log::info!("Down migrated - {}", name);
+        }
+
+        Ok(())
+    }
+
+    fn status(&mut self) -> Result<(), MigrateError> {
+        let migrations = if self.is_migrations_table_exists()? {
+            self.load_migrations()?
+        } else {
+            Vec::new()
+        };
+        let items = migrations::get_migrations();
+
+        println!("{:<8} {:<6} {:<20} Name", "Status", "Batch", "Migrated at");
+        for migration in &migrations {
+            let item = items.iter().find(|(name, _)| name.eq(&migration.name));
+            let status = match (item, &migration.checksum) {
+                (None, _) => "Missing",
+                (Some((_, item)), Some(checksum))
+                    if checksum.ne(&migrations::checksum(self.config, item)) =>
+                {
+                    "Changed"
+                }
+                _ => "Applied",
+            };
+            println!(
+                "{:<8} {:<6} {:<20} {}",
+                status,
+                migration.batch,
+                migration.migrated_at.as_deref().unwrap_or("-"),
+                migration.name
+            );
+        }
+        for (name, _) in &items {
+            if !migrations.iter().any(|m| m.name.eq(name)) {
+                println!("{:<8} {:<6} {:<20} {}", "Pending", "-", "-", name);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Drops all tables of the database, including the tables which are not created by the migrations.
+    fn drop_all_tables(&mut self) -> Result<(), MigrateError> {
+        let tables: Vec<String> = self
+            .connection
+            .query_map("SHOW FULL TABLES WHERE Table_type = 'BASE TABLE'", |row: Row| {
+                row.get::<String, usize>(0).unwrap_or_default()
+            })
+            .map_err(|e| MigrateError::Query(e.to_string()))?;
+
+        let mut queries: Vec<String> = Vec::new();
+        queries.push("SET FOREIGN_KEY_CHECKS = 0;".to_string());
+        for table in tables {
+            queries.push(format!("DROP TABLE `{}`;", table.replace('`', "``")));
+        }
+        queries.push("SET FOREIGN_KEY_CHECKS = 1;".to_string());
+
+        log::info!("Dropping all tables");
+        self.execute("fresh", "drop", queries)?;
+        self.is_dropped = true;
+        Ok(())
+    }
+
+    /// The statements are executed one by one, the failed one is reported with its number.
+    fn execute(&mut self, name: &str, direction: &str, queries: Vec<String>) -> Result<(), MigrateError> {
+        let count = queries.len();
+        for (i, query) in queries.into_iter().enumerate() {
+            if self.dry_run {
+                println!("-- {} {} {}/{}\n{}\n", name, direction, i + 1, count, query.trim());
+                continue;
+            }
+            self.connection.query_drop(&query).map_err(|e| MigrateError::Statement {
+                name: name.to_string(),
+                direction: direction.to_string(),
+                number: i + 1,
+                count,
+                query: query.trim().to_string(),
+                error: e.to_string(),
+            })?;
+        }
+        Ok(())
+    }
+
+    fn is_migrations_table_exists(&mut self) -> Result<bool, MigrateError> {
+        let query = format!("SHOW TABLES LIKE '{MIGRATIONS_TABLE}'");
+        let row: Option<Row> = self
+            .connection
+            .query_first(query)
+            .map_err(|e| MigrateError::Query(e.to_string()))?;
+        Ok(row.is_some())
+    }
+
+    fn create_migrations_table(&mut self) -> Result<(), MigrateError> {
+        // The dry run does not change anything, the missing table is read as empty.
+        if self.dry_run {
+            return Ok(());
+        }
+        let query = format!(
+            "CREATE TABLE IF NOT EXISTS {MIGRATIONS_TABLE} (
+   id BIGINT UNSIGNED PRIMARY KEY AUTO_INCREMENT,
+   name VARCHAR(255) NOT NULL UNIQUE,
+   batch INT UNSIGNED NOT NULL DEFAULT 1,
+   checksum VARCHAR(64) CHARACTER SET ascii COLLATE ascii_bin NULL DEFAULT NULL,
+   migrated_at DATETIME NULL DEFAULT NULL
+);"
+        );
+        self.connection
+            .query_drop(query)
+            .map_err(|e| MigrateError::Query(e.to_string()))?;
+
+        // The table of the earlier versions has only the names, all of them are the first batch.
+        let query = format!("SHOW COLUMNS FROM {MIGRATIONS_TABLE} LIKE 'batch'");
+        let row: Option<Row> = self
+            .connection
+            .query_first(query)
+            .map_err(|e| MigrateError::Query(e.to_string()))?;
+        if row.is_none() {
+            let query = format!(
+                "ALTER TABLE {MIGRATIONS_TABLE}
+   ADD batch INT UNSIGNED NOT NULL DEFAULT 1,
+   ADD checksum VARCHAR(64) CHARACTER SET ascii COLLATE ascii_bin NULL DEFAULT NULL,
+   ADD migrated_at DATETIME NULL DEFAULT NULL;"
+            );
+            self.connection
+                .query_drop(query)
+                .map_err(|e| MigrateError::Query(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    fn load_migrations(&mut self) -> Result<Vec<Migration>, MigrateError> {
+        if self.dry_run && (self.is_dropped || !self.is_migrations_table_exists()?) {
+            return Ok(Vec::new());
+        }
+        let query = format!("SELECT * FROM {MIGRATIONS_TABLE} ORDER BY id ASC");
+        let rows: Vec<Row> = self
+            .connection
+            .query(query)
+            .map_err(|e| MigrateError::Query(e.to_string()))?;
+
+        Ok(rows.iter().map(Migration::from_db_row).collect())
+    }
+
+    fn insert_migration(&mut self, name: &str, batch: u64, checksum: &str) -> Result<(), MigrateError> {
+        let query = format!(
+            "INSERT INTO {MIGRATIONS_TABLE} (name, batch, checksum, migrated_at) VALUES (:name, :batch, :checksum, UTC_TIMESTAMP())"
+        );
+        self.connection
+            .exec_drop(query, params! {"name" => name, "batch" => batch, "checksum" => checksum})
+            .map_err(|e| MigrateError::Query(e.to_string()))
+    }
+
+    fn update_checksum(&mut self, name: &str, checksum: &str) -> Result<(), MigrateError> {
+        let query = format!("UPDATE {MIGRATIONS_TABLE} SET checksum=:checksum WHERE name=:name");
+        self.connection
+            .exec_drop(query, params! {"name" => name, "checksum" => checksum})
+            .map_err(|e| MigrateError::Query(e.to_string()))
+    }
+
+    fn delete_migration(&mut self, name: &str) -> Result<(), MigrateError> {
+        let query = format!("DELETE FROM {MIGRATIONS_TABLE} WHERE name=:name");
+        self.connection
+            .exec_drop(query, params! {"name" => name})
+            .map_err(|e| MigrateError::Query(e.to_string()))
+    }
+}
+
+struct Migration {
+    name: String,
+    batch: u64,
+    checksum: Option<String>,
+    migrated_at: Option<String>,
+}
+
+impl Migration {
+    pub fn from_db_row(row: &Row) -> Self {
+        Self {
+            name: row.get("name").unwrap_or("".to_string()),
+            batch: row.get("batch").unwrap_or(1),
+            checksum: row.get("checksum").unwrap_or(None),
+            migrated_at: row.get("migrated_at").unwrap_or(None),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum MigrateError {
+    Arguments(String),
+    Connection(String),
+    Query(String),
+    NotFound(String),
+    Changed(String),
+    Statement {
+        name: String,
+        direction: String,
+        number: usize,
+        count: usize,
+        query: String,
+        error: String,
+    },
+}
+
+impl fmt::Display for MigrateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Arguments(message) => write!(f, "{}", message),
+            Self::Connection(error) => write!(f, "Failed to connect to the database - {}", error),
+            Self::Query(error) => write!(f, "Failed to query the migrations table - {}", error),
+            Self::NotFound(name) => write!(f, "Migration not found - {}", name),
+            Self::Changed(names) => write!(
+                f,
+                "The applied migrations were changed, roll them back before the edit or restore them - {}",
+                names
+            ),
+            Self::Statement { name, direction, number, count, query, error } => write!(
+                f,
+                "Migration {} ({}) failed on the statement {}/{} - {}\n{}",
+                name, direction, number, count, error, query
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, MigrateError> {
+        let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        Options::parse(&args)
+    }
+
+    #[test]
+    fn test_options_parse() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact migrations::runner::tests::test_options_parse
+        let options = parse(&["up"]).unwrap();
+        assert_eq!(options.command, Command::Up);
+        assert!(!options.dry_run);
+
+        let options = parse(&["down"]).unwrap();
+        assert_eq!(options.command, Command::Down);
+        assert_eq!(options.steps, 1);
+        assert!(!options.batch);
+
+        let options = parse(&["down", "--steps", "3", "--dry-run"]).unwrap();
+        assert_eq!(options.steps, 3);
+        assert!(options.dry_run);
+
+        let options = parse(&["down", "--batch"]).unwrap();
+        assert!(options.batch);
+
+        let options = parse(&["fresh", "--dry-run"]).unwrap();
+        assert_eq!(options.command, Command::Fresh);
+        assert!(options.dry_run);
+
+        assert_eq!(parse(&["status"]).unwrap().command, Command::Status);
+
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["sideways"]).is_err());
+        assert!(parse(&["down", "--steps"]).is_err());
+        assert!(parse(&["down", "--steps", "0"]).is_err());
+        assert!(parse(&["down", "--steps", "x"]).is_err());
+        assert!(parse(&["up", "--batch"]).is_err());
+        assert!(parse(&["status", "--dry-run"]).is_err());
+        assert!(parse(&["seed"]).is_err());
+        assert!(parse(&["up", "users"]).is_err());
+    }
+}
@@ -0,0 +1,17 @@
+use crate::Config;
+
+pub fn up(_: &Config) -> Vec<String> {
+    let mut queries: Vec<String> = Vec::new();
+    let query = "ALTER TABLE `users` ADD `email_verified_at` DATETIME NULL DEFAULT NULL COMMENT 'The datetime when the user confirmed the email.';";
+    queries.push(query.to_string());
+    // The existing users were created before the verification, they are not blocked.
+    let query = "UPDATE `users` SET `email_verified_at` = UTC_TIMESTAMP();";
+    queries.push(query.to_string());
+    queries
+}
+
+pub fn down(_: &Config) -> Vec<String> {
+    vec![
+        "ALTER TABLE `users` DROP COLUMN `email_verified_at`;".to_string(),
+    ]
+}
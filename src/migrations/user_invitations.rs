@@ -0,0 +1,14 @@
+use crate::Config;
+
+pub fn up(_: &Config) -> Vec<String> {
+    let mut queries: Vec<String> = Vec::new();
+    let query = "ALTER TABLE `users` ADD `invited_at` DATETIME NULL DEFAULT NULL COMMENT 'The datetime when the invitation was sent, it is cleared when the invitation is accepted or revoked.';";
+    queries.push(query.to_string());
+    queries
+}
+
+pub fn down(_: &Config) -> Vec<String> {
+    vec![
+        "ALTER TABLE `users` DROP COLUMN `invited_at`;".to_string(),
+    ]
+}
@@ -0,0 +1,17 @@
+use crate::Config;
+
+pub fn up(_: &Config) -> Vec<String> {
+    let mut queries: Vec<String> = Vec::new();
+    let query = "ALTER TABLE `users` ADD `ldap_dn` VARCHAR(1024) NULL DEFAULT NULL COMMENT 'The distinguished name of the user provisioned from the directory.';";
+    queries.push(query.to_string());
+    let query = "ALTER TABLE `users` ADD `disabled_at` DATETIME NULL DEFAULT NULL COMMENT 'The datetime when the user was disabled, the disabled users can not log in.';";
+    queries.push(query.to_string());
+    queries
+}
+
+pub fn down(_: &Config) -> Vec<String> {
+    vec![
+        "ALTER TABLE `users` DROP COLUMN `disabled_at`;".to_string(),
+        "ALTER TABLE `users` DROP COLUMN `ldap_dn`;".to_string(),
+    ]
+}
@@ -0,0 +1,15 @@
+use crate::Config;
+
+pub fn up(_: &Config) -> Vec<String> {
+    let mut queries: Vec<String> = Vec::new();
+    let query = "ALTER TABLE `users` ADD `password_history` JSON NULL DEFAULT NULL COMMENT 'The hashes of the previous passwords, the newest first.', ADD `password_changed_at` DATETIME NULL DEFAULT NULL COMMENT 'The datetime of the last change of the password.';";
+    queries.push(query.to_string());
+    queries.push("UPDATE `users` SET `password_changed_at` = UTC_TIMESTAMP() WHERE `password` IS NOT NULL;".to_string());
+    queries
+}
+
+pub fn down(_: &Config) -> Vec<String> {
+    vec![
+        "ALTER TABLE `users` DROP COLUMN `password_history`, DROP COLUMN `password_changed_at`;".to_string(),
+    ]
+}
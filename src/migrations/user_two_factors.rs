@@ -0,0 +1,26 @@
+use crate::Config;
+
+pub fn up(_: &Config) -> Vec<String> {
+    let mut queries: Vec<String> = Vec::new();
+    let query = "CREATE TABLE `user_two_factors` (
+   `id` BIGINT UNSIGNED PRIMARY KEY AUTO_INCREMENT,
+   `user_id` BIGINT UNSIGNED NOT NULL UNIQUE COMMENT 'The second factor owner.',
+   `secret` VARCHAR(255) NOT NULL COMMENT 'The encrypted TOTP secret.',
+   `recovery_codes` JSON NULL DEFAULT NULL COMMENT 'Hashes of the unused recovery codes.',
+   `last_used_step` BIGINT UNSIGNED NULL DEFAULT NULL COMMENT 'The last accepted TOTP time step.',
+   `confirmed_at` DATETIME NULL DEFAULT NULL COMMENT 'The datetime of the enrollment confirmation.',
+   `created_at` DATETIME NULL DEFAULT NULL COMMENT 'The datetime of the enrollment start.'
+) COMMENT 'TOTP second factor of users.';";
+    queries.push(query.to_string());
+
+    let query = "ALTER TABLE `roles` ADD `requires_two_factor` BOOLEAN NOT NULL DEFAULT 0 COMMENT 'Users with the role must enable the second factor.';";
+    queries.push(query.to_string());
+    queries
+}
+
+pub fn down(_: &Config) -> Vec<String> {
+    vec![
+        "ALTER TABLE `roles` DROP COLUMN `requires_two_factor`;".to_string(),
+        "DROP TABLE `user_two_factors`;".to_string(),
+    ]
+}
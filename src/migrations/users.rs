@@ -1,7 +1,7 @@
-use mysql::prelude::Queryable;
-use crate::{Config, MysqlPooledConnection};
+use crate::Config;
 
-pub fn up(_: &Config, connection: &mut MysqlPooledConnection) {
+pub fn up(_: &Config) -> Vec<String> {
+    let mut queries: Vec<String> = Vec::new();
     let query = "CREATE TABLE `users` (
    `id` BIGINT UNSIGNED PRIMARY KEY AUTO_INCREMENT,
    `email` VARCHAR(255) NOT NULL UNIQUE,
@@ -15,17 +15,20 @@ pub fn up(_: &Config, connection: &mut MysqlPooledConnection) {
    `avatar_id` BIGINT UNSIGNED NULL DEFAULT NULL
 );
 ";
-    connection.query_drop(query).unwrap();
+    queries.push(query.to_string());
     let query = "
 INSERT INTO `users` (`id`, `email`, `is_super_admin`, `roles_ids`) VALUES (1, 'admin@admin.example', true, '[1]');
 ";
-    connection.query_drop(query).unwrap();
+    queries.push(query.to_string());
 
     let query = "ALTER TABLE `users` ADD INDEX `avatar_idx` (`avatar_id`);";
-    connection.query_drop(query).unwrap();
+    queries.push(query.to_string());
+    queries
 }
 
-pub fn down(_: &Config, connection: &mut MysqlPooledConnection){
+pub fn down(_: &Config) -> Vec<String> {
+    let mut queries: Vec<String> = Vec::new();
     let query = "DROP TABLE `users`;";
-    connection.query_drop(query).unwrap();
+    queries.push(query.to_string());
+    queries
 }
\ No newline at end of file
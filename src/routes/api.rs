@@ -1,12 +1,71 @@
 use actix_web::web;
 
 use crate::app::controllers::api::v1;
-use crate::app::middlewares::web_auth::WebAuthMiddleware;
+use crate::app::middlewares::api_auth::ApiAuthMiddleware;
 
 pub fn register(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::resource("/api/v1")
-            .wrap(WebAuthMiddleware)
+            .wrap(ApiAuthMiddleware)
             .route(web::get().to(v1::index::index)),
     );
+    cfg.service(
+        web::resource("/api/v1/auth/token")
+            .route(web::post().to(v1::auth::token)),
+    );
+    cfg.service(
+        web::resource("/api/v1/auth/logout")
+            .wrap(ApiAuthMiddleware)
+            .route(web::post().to(v1::auth::logout)),
+    );
+    cfg.service(
+        web::resource("/api/v1/users")
+            .wrap(ApiAuthMiddleware)
+            .route(web::get().to(v1::users::index))
+            .route(web::post().to(v1::users::store)),
+    );
+    cfg.service(
+        web::resource("/api/v1/users/{user_id}")
+            .wrap(ApiAuthMiddleware)
+            .route(web::get().to(v1::users::show))
+            .route(web::put().to(v1::users::update))
+            .route(web::delete().to(v1::users::delete)),
+    );
+    cfg.service(
+        web::resource("/api/v1/roles")
+            .wrap(ApiAuthMiddleware)
+            .route(web::get().to(v1::roles::index))
+            .route(web::post().to(v1::roles::store)),
+    );
+    cfg.service(
+        web::resource("/api/v1/roles/{role_id}")
+            .wrap(ApiAuthMiddleware)
+            .route(web::get().to(v1::roles::show))
+            .route(web::put().to(v1::roles::update))
+            .route(web::delete().to(v1::roles::delete)),
+    );
+    cfg.service(
+        web::resource("/api/v1/files")
+            .wrap(ApiAuthMiddleware)
+            .route(web::get().to(v1::files::index))
+            .route(web::post().to(v1::files::store)),
+    );
+    cfg.service(
+        web::resource("/api/v1/files/{file_id}")
+            .wrap(ApiAuthMiddleware)
+            .route(web::get().to(v1::files::show))
+            .route(web::delete().to(v1::files::delete)),
+    );
+    cfg.service(
+        web::resource("/api/v1/user-files")
+            .wrap(ApiAuthMiddleware)
+            .route(web::get().to(v1::user_files::index)),
+    );
+    cfg.service(
+        web::resource("/api/v1/user-files/{user_file_id}")
+            .wrap(ApiAuthMiddleware)
+            .route(web::get().to(v1::user_files::show))
+            .route(web::put().to(v1::user_files::update))
+            .route(web::delete().to(v1::user_files::delete)),
+    );
 }
@@ -16,6 +16,19 @@ pub fn register(cfg: &mut web::ServiceConfig) {
             .route(web::get().to(controllers::web::auth::login::show))
             .route(web::post().to(controllers::web::auth::login::invoke)),
     );
+    cfg.service(
+        web::resource("/login/two-factor")
+            .route(web::get().to(controllers::web::auth::login_two_factor::show))
+            .route(web::post().to(controllers::web::auth::login_two_factor::invoke)),
+    );
+    cfg.service(
+        web::resource(format!("{}/{{provider}}", crate::OIDC_URL))
+            .route(web::get().to(controllers::web::auth::oidc::redirect)),
+    );
+    cfg.service(
+        web::resource(format!("{}/{{provider}}/callback", crate::OIDC_URL))
+            .route(web::get().to(controllers::web::auth::oidc::callback)),
+    );
     cfg.service(
         web::resource("/logout").route(web::post().to(controllers::web::auth::logout::invoke)),
     );
@@ -34,12 +47,53 @@ pub fn register(cfg: &mut web::ServiceConfig) {
             .route(web::get().to(controllers::web::auth::reset_password_confirm::show))
             .route(web::post().to(controllers::web::auth::reset_password_confirm::invoke)),
     );
+    cfg.service(
+        web::resource(controllers::web::auth::verify_email::get_url())
+            .route(web::get().to(controllers::web::auth::verify_email::invoke)),
+    );
+    cfg.service(
+        web::resource(controllers::web::auth::invitation::get_url())
+            .route(web::get().to(controllers::web::auth::invitation::show))
+            .route(web::post().to(controllers::web::auth::invitation::invoke)),
+    );
+    cfg.service(
+        web::resource(controllers::web::auth::verify_email::get_notice_url())
+            .wrap(WebAuthMiddleware)
+            .route(web::get().to(controllers::web::auth::verify_email::notice)),
+    );
+    cfg.service(
+        web::resource(controllers::web::auth::verify_email::get_resend_url())
+            .wrap(WebAuthMiddleware)
+            .route(web::post().to(controllers::web::auth::verify_email::resend)),
+    );
     cfg.service(
         web::resource(controllers::web::profile::get_url())
             .wrap(WebAuthMiddleware)
             .route(web::get().to(controllers::web::profile::index))
             .route(web::post().to(controllers::web::profile::update)),
     );
+    cfg.service(
+        web::resource(controllers::web::api_tokens::index::get_url())
+            .wrap(WebAuthMiddleware)
+            .route(web::get().to(controllers::web::api_tokens::index::index))
+            .route(web::post().to(controllers::web::api_tokens::index::store)),
+    );
+    cfg.service(
+        web::resource("/profile/api-tokens/{api_token_id}/delete")
+            .wrap(WebAuthMiddleware)
+            .route(web::post().to(controllers::web::api_tokens::delete::invoke)),
+    );
+    cfg.service(
+        web::resource(controllers::web::users::sessions::get_profile_revoke_url())
+            .wrap(WebAuthMiddleware)
+            .route(web::post().to(controllers::web::users::sessions::profile_revoke)),
+    );
+    cfg.service(
+        web::resource(controllers::web::two_factor::index::get_url())
+            .wrap(WebAuthMiddleware)
+            .route(web::get().to(controllers::web::two_factor::index::index))
+            .route(web::post().to(controllers::web::two_factor::index::store)),
+    );
     //
     cfg.service(
         web::resource("/users")
@@ -47,12 +101,44 @@ pub fn register(cfg: &mut web::ServiceConfig) {
             .route(web::get().to(controllers::web::users::index::invoke))
             .route(web::post().to(controllers::web::users::mass_actions::invoke)),
     );
+    cfg.service(
+        web::resource("/users/export")
+            .wrap(WebAuthMiddleware)
+            .route(web::get().to(controllers::web::users::export::invoke))
+            .route(web::post().to(controllers::web::users::export::selected)),
+    );
     cfg.service(
         web::resource("/users/create")
             .wrap(WebAuthMiddleware)
             .route(web::get().to(controllers::web::users::create_update::create))
             .route(web::post().to(controllers::web::users::create_update::store)),
     );
+    cfg.service(
+        web::resource(controllers::web::users::import::get_url())
+            .wrap(WebAuthMiddleware)
+            .route(web::get().to(controllers::web::users::import::show))
+            .route(web::post().to(controllers::web::users::import::preview)),
+    );
+    cfg.service(
+        web::resource(controllers::web::users::import::get_confirm_url())
+            .wrap(WebAuthMiddleware)
+            .route(web::post().to(controllers::web::users::import::store)),
+    );
+    cfg.service(
+        web::resource(controllers::web::users::invitations::get_url())
+            .wrap(WebAuthMiddleware)
+            .route(web::get().to(controllers::web::users::invitations::index)),
+    );
+    cfg.service(
+        web::resource(controllers::web::users::invitations::get_resend_url("{user_id}"))
+            .wrap(WebAuthMiddleware)
+            .route(web::post().to(controllers::web::users::invitations::resend)),
+    );
+    cfg.service(
+        web::resource(controllers::web::users::invitations::get_revoke_url("{user_id}"))
+            .wrap(WebAuthMiddleware)
+            .route(web::post().to(controllers::web::users::invitations::revoke)),
+    );
     cfg.service(
         web::resource("/users/{user_id}")
             .wrap(WebAuthMiddleware)
@@ -64,6 +150,21 @@ pub fn register(cfg: &mut web::ServiceConfig) {
             .wrap(WebAuthMiddleware)
             .route(web::post().to(controllers::web::users::delete::invoke)),
     );
+    cfg.service(
+        web::resource("/users/{user_id}/two-factor/reset")
+            .wrap(WebAuthMiddleware)
+            .route(web::post().to(controllers::web::users::two_factor_reset::invoke)),
+    );
+    cfg.service(
+        web::resource("/users/{user_id}/unlock")
+            .wrap(WebAuthMiddleware)
+            .route(web::post().to(controllers::web::users::unlock::invoke)),
+    );
+    cfg.service(
+        web::resource("/users/{user_id}/sessions/revoke")
+            .wrap(WebAuthMiddleware)
+            .route(web::post().to(controllers::web::users::sessions::revoke_by_user)),
+    );
     //
     cfg.service(
         web::resource("/roles")
@@ -71,6 +172,12 @@ pub fn register(cfg: &mut web::ServiceConfig) {
             .route(web::get().to(controllers::web::roles::index::invoke))
             .route(web::post().to(controllers::web::roles::mass_actions::invoke)),
     );
+    cfg.service(
+        web::resource("/roles/export")
+            .wrap(WebAuthMiddleware)
+            .route(web::get().to(controllers::web::roles::export::invoke))
+            .route(web::post().to(controllers::web::roles::export::selected)),
+    );
     cfg.service(
         web::resource("/roles/create")
             .wrap(WebAuthMiddleware)
@@ -95,12 +202,40 @@ pub fn register(cfg: &mut web::ServiceConfig) {
             .route(web::get().to(controllers::web::files::index::invoke))
             .route(web::post().to(controllers::web::files::mass_actions::invoke)),
     );
+    cfg.service(
+        web::resource("/files/export")
+            .wrap(WebAuthMiddleware)
+            .route(web::get().to(controllers::web::files::export::invoke))
+            .route(web::post().to(controllers::web::files::export::selected)),
+    );
+    cfg.service(
+        web::resource(controllers::web::exports::get_download_url("{user_file_id}"))
+            .wrap(WebAuthMiddleware)
+            .route(web::get().to(controllers::web::exports::download)),
+    );
     cfg.service(
         web::resource(controllers::web::files::upload::get_upload_url().as_str())
             .wrap(WebAuthMiddleware)
             .route(web::get().to(controllers::web::files::upload::show))
             .route(web::post().to(controllers::web::files::upload::upload)),
     );
+    cfg.service(
+        web::resource(controllers::web::files::resumable::get_resumable_create_url().as_str())
+            .wrap(WebAuthMiddleware)
+            .route(web::post().to(controllers::web::files::resumable::create)),
+    );
+    cfg.service(
+        web::resource(controllers::web::files::resumable::get_resumable_url("{id}").as_str())
+            .wrap(WebAuthMiddleware)
+            .route(web::head().to(controllers::web::files::resumable::head))
+            .route(web::patch().to(controllers::web::files::resumable::patch))
+            .route(web::delete().to(controllers::web::files::resumable::delete)),
+    );
+    cfg.service(
+        web::resource(controllers::web::files::resumable::get_resumable_finalize_url("{id}").as_str())
+            .wrap(WebAuthMiddleware)
+            .route(web::post().to(controllers::web::files::resumable::finalize)),
+    );
     cfg.service(
         web::resource(controllers::web::files::upload::get_upload_avatar_url().as_str())
             .wrap(WebAuthMiddleware)
@@ -138,4 +273,21 @@ pub fn register(cfg: &mut web::ServiceConfig) {
             .wrap(WebAuthMiddleware)
             .route(web::post().to(controllers::web::user_files::restore::invoke)),
     );
+    //
+    cfg.service(
+        web::resource("/audit-log")
+            .wrap(WebAuthMiddleware)
+            .route(web::get().to(controllers::web::audit_log::index::invoke)),
+    );
+    //
+    cfg.service(
+        web::resource("/jobs/failed")
+            .wrap(WebAuthMiddleware)
+            .route(web::get().to(controllers::web::jobs::index::invoke)),
+    );
+    cfg.service(
+        web::resource("/jobs/failed/{job_id}/retry")
+            .wrap(WebAuthMiddleware)
+            .route(web::post().to(controllers::web::jobs::retry::invoke)),
+    );
 }
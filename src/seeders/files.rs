@@ -0,0 +1,50 @@
+use crate::factories::{FileFactory, Faker, UserFileFactory};
+use crate::services::Services;
+use crate::{Disk, DiskRepository, MysqlRepository};
+
+/// The images are written to the local disk, every file is linked to a random user.
+pub fn run(services: &Services, count: u64) -> Result<(), String> {
+    let disk_local_repository = services.disk_local_repository.get_ref();
+    let file_service = services.file_service.get_ref();
+    let file_mysql_repository = services.file_mysql_repository.get_ref();
+    let user_file_service = services.user_file_service.get_ref();
+    let user_mysql_repository = services.user_mysql_repository.get_ref();
+
+    let users_ids: Vec<u64> = user_mysql_repository
+        .all_ids(None, None)
+        .map_err(|e| e.to_string())?;
+    if users_ids.is_empty() {
+        return Err("There are no users to link the files to.".to_string());
+    }
+
+    let mut faker = Faker::new();
+    let mut file_factory = FileFactory::new(Faker::new(), &Disk::Local.to_string());
+    let mut user_file_factory = UserFileFactory::new(Faker::new());
+
+    for _ in 0..count {
+        let user_id = *faker.element(&users_ids).unwrap_or(&users_ids[0]);
+        let (mut file, bytes) = file_factory.make();
+
+        file.path = disk_local_repository
+            .path(&file.filename)
+            .map_err(|e| e.to_string())?;
+        file.creator_user_id = Some(user_id);
+        disk_local_repository
+            .put(&file.path, bytes)
+            .map_err(|e| e.to_string())?;
+
+        let path = file.path.to_owned();
+        file_service.create(file).map_err(|e| e.to_string())?;
+        let file = file_mysql_repository
+            .first_by_disk_and_path(&Disk::Local, &path)
+            .map_err(|e| e.to_string())?
+            .ok_or(format!("The file was not saved - {}", path))?;
+
+        let user_file = user_file_factory.make(user_id, &file);
+        user_file_service
+            .create(user_file, &file)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
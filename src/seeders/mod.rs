@@ -0,0 +1,109 @@
+use crate::services::Services;
+
+pub mod files;
+pub mod roles;
+pub mod users;
+
+// The records are inserted by the chunks, one query per chunk.
+pub const CHUNK_SIZE: usize = 500;
+
+/// Inserts the count of the fake records, returns the error message.
+pub type SeederFn = fn(&Services, u64) -> Result<(), String>;
+
+/// The seeders in the order of running, with the default count of the records.
+pub fn get_seeders() -> Vec<(String, SeederFn, u64)> {
+    let mut items: Vec<(String, SeederFn, u64)> = Vec::new();
+
+    items.push(("roles".to_string(), roles::run, 10));
+    items.push(("users".to_string(), users::run, 1000));
+    items.push(("files".to_string(), files::run, 200));
+
+    items
+}
+
+pub static COMMANDS_MESSAGE: &str =
+    "Usage: \"seed [name...] --count N\" runs the named seeders or all of them.";
+
+#[derive(Debug, Default)]
+pub struct Options {
+    // The names of the seeders, all seeders are run when it is empty.
+    pub names: Vec<String>,
+    pub count: Option<u64>,
+}
+
+impl Options {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = Self::default();
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--count" => {
+                    options.count = Some(
+                        args.next()
+                            .and_then(|s| s.parse::<u64>().ok())
+                            .ok_or("\"--count\" needs a number.".to_string())?,
+                    );
+                }
+                name if !name.starts_with("--") => options.names.push(name.to_string()),
+                _ => return Err(format!("Wrong option \"{}\".", arg)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// Runs the seeders by the names, all seeders are run when the names are empty.
+pub fn run(services: &Services, names: &Vec<String>, count: Option<u64>) -> Result<(), String> {
+    let seeders = get_seeders();
+
+    for name in names {
+        if !seeders.iter().any(|(name_, _, _)| name_.eq(name)) {
+            let names_: Vec<&str> = seeders.iter().map(|(name_, _, _)| name_.as_str()).collect();
+            return Err(format!(
+                "Unknown seeder \"{}\", the seeders are: {}.",
+                name,
+                names_.join(", ")
+            ));
+        }
+    }
+
+    for (name, seeder, default_count) in seeders.iter() {
+        if !names.is_empty() && !names.contains(name) {
+            continue;
+        }
+        let count = count.unwrap_or(*default_count);
+        log::info!("Seeding - {} ({})", name, count);
+        seeder(services, count).map_err(|e| format!("{} - {}", name, e))?;
+        log::info!("Seeded - {}", name);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        Options::parse(&args)
+    }
+
+    #[test]
+    fn test_options_parse() {
+        // RUSTFLAGS=-Awarnings CARGO_INCREMENTAL=0 cargo test -- --nocapture --exact seeders::tests::test_options_parse
+        let options = parse(&[]).unwrap();
+        assert!(options.names.is_empty());
+        assert_eq!(options.count, None);
+
+        let options = parse(&["roles", "users", "--count", "500"]).unwrap();
+        assert_eq!(options.names, vec!["roles".to_string(), "users".to_string()]);
+        assert_eq!(options.count, Some(500));
+
+        assert!(parse(&["--count"]).is_err());
+        assert!(parse(&["--count", "x"]).is_err());
+        assert!(parse(&["--dry-run"]).is_err());
+    }
+}
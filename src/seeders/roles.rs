@@ -0,0 +1,18 @@
+use crate::factories::{Faker, RoleFactory};
+use crate::seeders::CHUNK_SIZE;
+use crate::services::Services;
+use crate::MysqlRepository;
+
+pub fn run(services: &Services, count: u64) -> Result<(), String> {
+    let role_mysql_repository = services.role_mysql_repository.get_ref();
+    let mut factory = RoleFactory::new(Faker::new());
+
+    let roles = factory.make_many(count as usize);
+    for chunk in roles.chunks(CHUNK_SIZE) {
+        role_mysql_repository
+            .insert(&chunk.to_vec(), None)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
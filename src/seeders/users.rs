@@ -0,0 +1,44 @@
+use crate::factories::{Faker, UserFactory};
+use crate::seeders::CHUNK_SIZE;
+use crate::services::Services;
+use crate::{MysqlRepository, User};
+use std::collections::HashSet;
+
+/// The users have no password, it can be set by the admin or by the password reset.
+pub fn run(services: &Services, count: u64) -> Result<(), String> {
+    let locale_service = services.locale_service.get_ref();
+    let role_service = services.role_service.get_ref();
+    let user_mysql_repository = services.user_mysql_repository.get_ref();
+
+    let locales: Vec<String> = locale_service
+        .get_locales_codes_ref()
+        .iter()
+        .map(|c| c.to_string())
+        .collect();
+    // The admin role gives all permissions, so the fake users do not get it.
+    let roles_ids: Vec<u64> = role_service
+        .all()
+        .map_err(|e| e.to_string())?
+        .iter()
+        .filter(|r| r.code.ne("admin"))
+        .map(|r| r.id)
+        .collect();
+    let mut factory = UserFactory::new(Faker::new(), locales, roles_ids);
+
+    let mut emails: HashSet<String> = HashSet::new();
+    let mut users: Vec<User> = Vec::new();
+    while (users.len() as u64) < count {
+        let user = factory.make();
+        if emails.insert(user.email.to_owned()) {
+            users.push(user);
+        }
+    }
+
+    for chunk in users.chunks(CHUNK_SIZE) {
+        user_mysql_repository
+            .insert(&chunk.to_vec(), None)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
@@ -1,10 +1,11 @@
 use crate::connections::Connections;
 use crate::{
-    AppService, AuthService, Config, CryptService, DiskExternalRepository, DiskLocalRepository,
-    FileMysqlRepository, FileService, HashService, KVRepository, KeyValueService, LocaleService,
-    MailService, RandomService, RateLimitService, RedisRepository, RoleMysqlRepository,
-    RoleService, TemplateService, TranslatorService, UserFileMysqlRepository, UserFileService,
-    UserMysqlRepository, UserService, WebAuthService,
+    ApiTokenMysqlRepository, ApiTokenService, AppService, AuditLogMysqlRepository, AuditLogService, AuthService, Config, CryptService, DiskExternalRepository, EmailVerificationService, ExportService, DiskLocalRepository, DiskS3Repository,
+    FileMysqlRepository, FileService, FileVariantMysqlRepository, FileVariantService, HashService, InvitationService, JobService, KVRepository, KeyValueService, LdapService, LocaleService, LoginSecurityService,
+    MailService, OidcService, RandomService, RateLimitService, RedisRepository, ResumableUploadService, RoleMysqlRepository,
+    RoleService, TemplateService, TranslatorService, UserFileMysqlRepository, UserFileService, UserImportService,
+    UserMysqlRepository, UserService, UserTwoFactorMysqlRepository, UserTwoFactorService,
+    WebAuthService,
 };
 use actix_web::web::Data;
 use std::path::MAIN_SEPARATOR_STR;
@@ -18,9 +19,15 @@ pub struct Services<'a> {
     pub hash_service: Data<HashService>,
     pub web_auth_service: Data<WebAuthService>,
     pub auth_service: Data<AuthService>,
+    pub ldap_service: Data<LdapService>,
     pub locale_service: Data<LocaleService>,
     pub app_service: Data<AppService>,
     pub mail_service: Data<MailService>,
+    pub job_service: Data<JobService>,
+    pub email_verification_service: Data<EmailVerificationService>,
+    pub invitation_service: Data<InvitationService>,
+    pub login_security_service: Data<LoginSecurityService>,
+    pub oidc_service: Data<OidcService>,
     pub rand_service: Data<RandomService>,
     pub rate_limit_service: Data<RateLimitService>,
     pub user_service: Data<UserService>,
@@ -29,10 +36,22 @@ pub struct Services<'a> {
     pub role_mysql_repository: Data<RoleMysqlRepository>,
     pub disk_local_repository: Data<DiskLocalRepository>,
     pub disk_external_repository: Data<DiskExternalRepository>,
+    pub disk_s3_repository: Data<DiskS3Repository>,
     pub file_service: Data<FileService>,
     pub file_mysql_repository: Data<FileMysqlRepository>,
     pub user_file_service: Data<UserFileService>,
+    pub file_variant_service: Data<FileVariantService>,
+    pub file_variant_mysql_repository: Data<FileVariantMysqlRepository>,
+    pub resumable_upload_service: Data<ResumableUploadService>,
+    pub export_service: Data<ExportService>,
+    pub user_import_service: Data<UserImportService>,
     pub user_file_mysql_repository: Data<UserFileMysqlRepository>,
+    pub api_token_service: Data<ApiTokenService>,
+    pub audit_log_service: Data<AuditLogService>,
+    pub audit_log_mysql_repository: Data<AuditLogMysqlRepository>,
+    pub api_token_mysql_repository: Data<ApiTokenMysqlRepository>,
+    pub user_two_factor_service: Data<UserTwoFactorService>,
+    pub user_two_factor_mysql_repository: Data<UserTwoFactorMysqlRepository>,
     pub redis_repository: Data<RedisRepository>,
     pub kv_repository: Data<KVRepository<'a>>,
 }
@@ -56,6 +75,7 @@ pub fn build<'a>(c: &Connections, config: Data<Config>) -> Services<'a> {
     let hash_service = Data::new(HashService::new(config.clone()));
     let user_mysql_repository = Data::new(UserMysqlRepository::new(c.mysql.clone()));
     let user_service = Data::new(UserService::new(
+        config.clone(),
         hash_service.clone(),
         user_mysql_repository.clone(),
     ));
@@ -65,15 +85,55 @@ pub fn build<'a>(c: &Connections, config: Data<Config>) -> Services<'a> {
         rand_service.clone(),
         hash_service.clone(),
     ));
+    let role_mysql_repository = Data::new(RoleMysqlRepository::new(c.mysql.clone()));
+    let role_service = Data::new(RoleService::new(role_mysql_repository.clone()));
+    let ldap_service = Data::new(LdapService::new(
+        config.clone(),
+        user_service.clone(),
+        role_service.clone(),
+    ));
     let auth_service = Data::new(AuthService::new(
         key_value_service.clone(),
         hash_service.clone(),
         user_service.clone(),
+        ldap_service.clone(),
     ));
     let locale_service = Data::new(LocaleService::new(config.clone()));
     let app_service = Data::new(AppService::new(config.clone(), locale_service.clone()));
     let mail_service = Data::new(MailService::new(config.clone(), c.smtp.clone()));
     let rate_limit_service = Data::new(RateLimitService::new(key_value_service.clone()));
+    let job_service = Data::new(JobService::new(
+        key_value_service.clone(),
+        rand_service.clone(),
+    ));
+    let email_verification_service = Data::new(EmailVerificationService::new(
+        config.clone(),
+        user_service.clone(),
+        job_service.clone(),
+        app_service.clone(),
+        template_service.clone(),
+        translator_service.clone(),
+        crypt_service.clone(),
+    ));
+    let invitation_service = Data::new(InvitationService::new(
+        config.clone(),
+        user_service.clone(),
+        job_service.clone(),
+        app_service.clone(),
+        template_service.clone(),
+        translator_service.clone(),
+        crypt_service.clone(),
+    ));
+    let login_security_service = Data::new(LoginSecurityService::new(
+        config.clone(),
+        key_value_service.clone(),
+        hash_service.clone(),
+        user_service.clone(),
+        job_service.clone(),
+        app_service.clone(),
+        template_service.clone(),
+        translator_service.clone(),
+    ));
     let web_auth_service = Data::new(WebAuthService::new(
         config.clone(),
         crypt_service.clone(),
@@ -83,8 +143,14 @@ pub fn build<'a>(c: &Connections, config: Data<Config>) -> Services<'a> {
         user_service.clone(),
     ));
 
-    let role_mysql_repository = Data::new(RoleMysqlRepository::new(c.mysql.clone()));
-    let role_service = Data::new(RoleService::new(role_mysql_repository.clone()));
+    let oidc_service = Data::new(OidcService::new(
+        config.clone(),
+        key_value_service.clone(),
+        rand_service.clone(),
+        app_service.clone(),
+        user_service.clone(),
+        role_service.clone(),
+    ));
 
     let disk_local_repository = Data::new(DiskLocalRepository::new(
         &config.get_ref().filesystem.disks.local.root,
@@ -92,6 +158,7 @@ pub fn build<'a>(c: &Connections, config: Data<Config>) -> Services<'a> {
         MAIN_SEPARATOR_STR,
     ));
     let disk_external_repository = Data::new(DiskExternalRepository::new());
+    let disk_s3_repository = Data::new(DiskS3Repository::new(&config.get_ref().filesystem.disks.s3));
 
     let file_mysql_repository = Data::new(FileMysqlRepository::new(c.mysql.clone()));
     let user_file_mysql_repository = Data::new(UserFileMysqlRepository::new(c.mysql.clone()));
@@ -99,15 +166,73 @@ pub fn build<'a>(c: &Connections, config: Data<Config>) -> Services<'a> {
         config.clone(),
         user_file_mysql_repository.clone(),
         disk_local_repository.clone(),
+        disk_s3_repository.clone(),
+    ));
+    let file_variant_mysql_repository = Data::new(FileVariantMysqlRepository::new(c.mysql.clone()));
+    let file_variant_service = Data::new(FileVariantService::new(
+        config.clone(),
+        file_variant_mysql_repository.clone(),
+        user_file_service.clone(),
+        disk_local_repository.clone(),
+        disk_s3_repository.clone(),
     ));
     let file_service = Data::new(FileService::new(
         config.clone(),
         file_mysql_repository.clone(),
         user_file_service.clone(),
+        file_variant_service.clone(),
+        job_service.clone(),
         disk_local_repository.clone(),
         disk_external_repository.clone(),
+        disk_s3_repository.clone(),
+        rand_service.clone(),
+        hash_service.clone(),
+    ));
+    let resumable_upload_service = Data::new(ResumableUploadService::new(
+        key_value_service.clone(),
+        file_service.clone(),
+        disk_local_repository.clone(),
+        rand_service.clone(),
+    ));
+    let export_service = Data::new(ExportService::new(
+        translator_service.clone(),
+        template_service.clone(),
+        app_service.clone(),
+        job_service.clone(),
+        user_service.clone(),
+        role_service.clone(),
+        file_service.clone(),
+        user_file_service.clone(),
+        disk_local_repository.clone(),
+        rand_service.clone(),
+    ));
+    let user_import_service = Data::new(UserImportService::new(
+        translator_service.clone(),
+        locale_service.clone(),
+        user_service.clone(),
+        role_service.clone(),
+        invitation_service.clone(),
+        key_value_service.clone(),
+        rand_service.clone(),
+    ));
+
+    let api_token_mysql_repository = Data::new(ApiTokenMysqlRepository::new(c.mysql.clone()));
+    let api_token_service = Data::new(ApiTokenService::new(
+        hash_service.clone(),
         rand_service.clone(),
+        api_token_mysql_repository.clone(),
+    ));
+    let audit_log_mysql_repository = Data::new(AuditLogMysqlRepository::new(c.mysql.clone()));
+    let audit_log_service = Data::new(AuditLogService::new(audit_log_mysql_repository.clone()));
+    let user_two_factor_mysql_repository =
+        Data::new(UserTwoFactorMysqlRepository::new(c.mysql.clone()));
+    let user_two_factor_service = Data::new(UserTwoFactorService::new(
+        config.clone(),
+        user_two_factor_mysql_repository.clone(),
+        crypt_service.clone(),
         hash_service.clone(),
+        rand_service.clone(),
+        key_value_service.clone(),
     ));
 
     Services {
@@ -119,9 +244,15 @@ pub fn build<'a>(c: &Connections, config: Data<Config>) -> Services<'a> {
         crypt_service,
         web_auth_service,
         auth_service,
+        ldap_service,
         locale_service,
         app_service,
         mail_service,
+        job_service,
+        email_verification_service,
+        invitation_service,
+        login_security_service,
+        oidc_service,
         rand_service,
         rate_limit_service,
         user_service,
@@ -130,10 +261,22 @@ pub fn build<'a>(c: &Connections, config: Data<Config>) -> Services<'a> {
         role_mysql_repository,
         disk_local_repository,
         disk_external_repository,
+        disk_s3_repository,
         file_service,
         file_mysql_repository,
         user_file_service,
+        file_variant_service,
+        file_variant_mysql_repository,
+        resumable_upload_service,
+        export_service,
+        user_import_service,
         user_file_mysql_repository,
+        api_token_service,
+        api_token_mysql_repository,
+        audit_log_service,
+        audit_log_mysql_repository,
+        user_two_factor_service,
+        user_two_factor_mysql_repository,
         redis_repository,
         kv_repository,
     }